@@ -0,0 +1,153 @@
+//! Mirrors the perp fills of another account with the strategy runtime.
+//!
+//! Every fill of the leader is copied with an IOC order scaled by `ratio`, priced
+//! `slippage_bps` past the leader's fill price so it takes liquidity.
+//!
+//! Stop with Ctrl-C.
+
+use std::collections::HashMap;
+
+use clap::Parser;
+use hypersdk::{
+    Address,
+    hypercore::{
+        self as hypercore, PerpMarket,
+        strategy::{Context, Runtime, Strategy},
+        types::*,
+    },
+};
+use rust_decimal::{Decimal, RoundingStrategy};
+
+use crate::credentials::Credentials;
+
+mod credentials;
+
+#[derive(Parser, Debug, derive_more::Deref)]
+#[command(author, version, about, long_about = None)]
+struct Cli {
+    #[deref]
+    #[command(flatten)]
+    common: Credentials,
+
+    /// Account to copy
+    leader: Address,
+
+    /// Size of the copied orders relative to the leader's fills
+    #[arg(long, default_value = "0.1")]
+    ratio: Decimal,
+
+    /// Distance past the leader's fill price, in basis points
+    #[arg(long, default_value = "20")]
+    slippage_bps: Decimal,
+}
+
+struct CopyTrade {
+    leader: Address,
+    ratio: Decimal,
+    slippage_bps: Decimal,
+    markets: HashMap<String, PerpMarket>,
+}
+
+impl CopyTrade {
+    fn copy(&self, fill: &Fill) -> Option<OrderRequest> {
+        let market = self.markets.get(&fill.coin)?;
+        let sz = (fill.sz * self.ratio)
+            .round_dp_with_strategy(market.sz_decimals.max(0) as u32, RoundingStrategy::ToZero);
+        if sz.is_zero() {
+            return None;
+        }
+        let is_buy = fill.side == Side::Bid;
+        let offset = fill.px * self.slippage_bps / Decimal::from(10_000);
+        let px = if is_buy {
+            fill.px + offset
+        } else {
+            fill.px - offset
+        };
+        Some(OrderRequest {
+            asset: market.index,
+            is_buy,
+            limit_px: market.round_by_side(fill.side, px, false)?,
+            sz,
+            reduce_only: false,
+            order_type: OrderTypePlacement::Limit {
+                tif: TimeInForce::Ioc,
+            },
+            cloid: Default::default(),
+        })
+    }
+}
+
+impl Strategy for CopyTrade {
+    fn subscriptions(&self) -> Vec<Subscription> {
+        vec![Subscription::UserFills { user: self.leader }]
+    }
+
+    fn on_message(&mut self, ctx: &mut Context, msg: &Incoming) -> anyhow::Result<()> {
+        let Incoming::UserFills {
+            is_snapshot: false,
+            user,
+            fills,
+        } = msg
+        else {
+            return Ok(());
+        };
+        if *user != self.leader {
+            return Ok(());
+        }
+
+        let orders: Vec<_> = fills.iter().filter_map(|fill| self.copy(fill)).collect();
+        if !orders.is_empty() {
+            println!("Copying {} fills", orders.len());
+            ctx.place(BatchOrder {
+                orders,
+                grouping: OrderGrouping::Na,
+                builder: None,
+            });
+        }
+        Ok(())
+    }
+
+    fn on_fill(&mut self, ctx: &mut Context, fill: &Fill) -> anyhow::Result<()> {
+        println!(
+            "Copied {} {} {} @ {}, position {}",
+            fill.side,
+            fill.sz,
+            fill.coin,
+            fill.px,
+            ctx.position(&fill.coin)
+        );
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let _ = simple_logger::init_with_level(log::Level::Info);
+
+    let args = Cli::parse();
+    let signer = args.get()?;
+
+    let client = hypercore::testnet();
+    let markets = client
+        .perps()
+        .await?
+        .into_iter()
+        .map(|market| (market.name.clone(), market))
+        .collect();
+
+    let mut copy = CopyTrade {
+        leader: args.leader,
+        ratio: args.ratio,
+        slippage_bps: args.slippage_bps,
+        markets,
+    };
+
+    let runtime = Runtime::new(client, signer);
+    let kill = runtime.kill_switch();
+    tokio::spawn(async move {
+        let _ = tokio::signal::ctrl_c().await;
+        kill.trigger();
+    });
+
+    runtime.run(&mut copy).await
+}
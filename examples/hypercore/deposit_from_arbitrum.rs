@@ -0,0 +1,64 @@
+//! Deposit USDC from Arbitrum into Hyperliquid.
+//!
+//! Sends USDC from the signer's Arbitrum wallet to the Hyperliquid bridge contract, then
+//! waits until the deposit is credited to the signer's perp balance on HyperCore.
+
+use std::time::Duration;
+
+use clap::Parser;
+use hypersdk::{bridge::arbitrum, hypercore, hyperevm};
+use rust_decimal::Decimal;
+
+use crate::credentials::Credentials;
+
+mod credentials;
+
+#[derive(Parser, Debug, derive_more::Deref)]
+#[command(author, version, about, long_about = None)]
+struct Cli {
+    #[deref]
+    #[command(flatten)]
+    common: Credentials,
+    /// USDC amount to deposit (at least 5)
+    #[arg(short, long)]
+    amount: Decimal,
+    /// Arbitrum RPC URL
+    #[arg(short, long, default_value = arbitrum::DEFAULT_RPC_URL)]
+    rpc_url: String,
+    /// Seconds to wait for the credit on HyperCore
+    #[arg(long, default_value_t = 300)]
+    timeout: u64,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let _ = simple_logger::init_with_level(log::Level::Debug);
+
+    let args = Cli::parse();
+    let signer = args.get()?;
+    let user = signer.address();
+
+    log::info!("Signer address: {user}");
+
+    let client = hypercore::mainnet();
+    let provider = hyperevm::mainnet_with_signer_and_url(&args.rpc_url, signer).await?;
+
+    log::info!("Depositing {} USDC from Arbitrum", args.amount);
+    let (tx_hash, credit) = arbitrum::deposit_and_wait(
+        &provider,
+        &client,
+        user,
+        args.amount,
+        Duration::from_secs(args.timeout),
+    )
+    .await?;
+    log::info!("Sent {tx_hash}");
+    log::info!(
+        "Credited {} USDC at {} ({})",
+        credit.usdc,
+        credit.time,
+        credit.hash
+    );
+
+    Ok(())
+}
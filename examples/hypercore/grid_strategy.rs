@@ -0,0 +1,168 @@
+//! Runs a grid market maker on a perp with the strategy runtime.
+//!
+//! Places `levels` post-only buys below and sells above the first mid seen, `spacing`
+//! apart. Each filled buy is replaced by a sell one level up and each filled sell by
+//! a buy one level down, so the grid earns the spacing on every round trip.
+//!
+//! Stop with Ctrl-C: the runtime cancels the grid's resting orders on the way out.
+
+use clap::Parser;
+use hypersdk::hypercore::{
+    self as hypercore, PerpMarket,
+    strategy::{Context, Runtime, Strategy},
+    types::*,
+};
+use rust_decimal::Decimal;
+
+use crate::credentials::Credentials;
+
+mod credentials;
+
+#[derive(Parser, Debug, derive_more::Deref)]
+#[command(author, version, about, long_about = None)]
+struct Cli {
+    #[deref]
+    #[command(flatten)]
+    common: Credentials,
+
+    /// Coin to trade (e.g. "ETH", "BTC")
+    coin: String,
+
+    /// Distance between grid levels, in quote units
+    #[arg(long)]
+    spacing: Decimal,
+
+    /// Size of each order in base units
+    #[arg(long)]
+    size: Decimal,
+
+    /// Number of levels on each side
+    #[arg(long, default_value_t = 5)]
+    levels: u32,
+}
+
+struct Grid {
+    market: PerpMarket,
+    spacing: Decimal,
+    size: Decimal,
+    levels: u32,
+    started: bool,
+}
+
+impl Grid {
+    fn order(&self, is_buy: bool, px: Decimal) -> Option<OrderRequest> {
+        let side = if is_buy { Side::Bid } else { Side::Ask };
+        Some(OrderRequest {
+            asset: self.market.index,
+            is_buy,
+            limit_px: self.market.round_by_side(side, px, true)?,
+            sz: self.size,
+            reduce_only: false,
+            order_type: OrderTypePlacement::Limit {
+                tif: TimeInForce::Alo,
+            },
+            cloid: Default::default(),
+        })
+    }
+
+    fn place(&self, ctx: &mut Context, orders: Vec<OrderRequest>) {
+        if orders.is_empty() {
+            return;
+        }
+        ctx.place(BatchOrder {
+            orders,
+            grouping: OrderGrouping::Na,
+            builder: None,
+        });
+    }
+}
+
+impl Strategy for Grid {
+    fn subscriptions(&self) -> Vec<Subscription> {
+        vec![Subscription::L2Book {
+            coin: self.market.name.clone(),
+        }]
+    }
+
+    fn on_book(&mut self, ctx: &mut Context, book: &L2Book) -> anyhow::Result<()> {
+        if self.started {
+            return Ok(());
+        }
+        let Some(mid) = book.mid() else {
+            return Ok(());
+        };
+        self.started = true;
+        println!("Grid around {mid}");
+
+        let orders = (1..=self.levels)
+            .flat_map(|level| {
+                let offset = self.spacing * Decimal::from(level);
+                [
+                    self.order(true, mid - offset),
+                    self.order(false, mid + offset),
+                ]
+            })
+            .flatten()
+            .collect();
+        self.place(ctx, orders);
+        Ok(())
+    }
+
+    fn on_fill(&mut self, ctx: &mut Context, fill: &Fill) -> anyhow::Result<()> {
+        if fill.coin != self.market.name {
+            return Ok(());
+        }
+        println!(
+            "{} {} @ {}, position {}",
+            fill.side,
+            fill.sz,
+            fill.px,
+            ctx.position(&fill.coin)
+        );
+        // Replace the filled order on the other side of the grid.
+        let replacement = match fill.side {
+            Side::Bid => self.order(false, fill.px + self.spacing),
+            Side::Ask => self.order(true, fill.px - self.spacing),
+        };
+        self.place(ctx, replacement.into_iter().collect());
+        Ok(())
+    }
+
+    fn on_stop(&mut self, ctx: &mut Context) -> anyhow::Result<()> {
+        println!("Stopping with position {}", ctx.position(&self.market.name));
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let _ = simple_logger::init_with_level(log::Level::Info);
+
+    let args = Cli::parse();
+    let signer = args.get()?;
+
+    let client = hypercore::testnet();
+    let market = client
+        .perps()
+        .await?
+        .into_iter()
+        .find(|m| m.name == args.coin)
+        .ok_or_else(|| anyhow::anyhow!("market '{}' not found", args.coin))?;
+
+    let mut grid = Grid {
+        market,
+        spacing: args.spacing,
+        size: args.size,
+        levels: args.levels,
+        started: false,
+    };
+
+    let runtime = Runtime::new(client, signer);
+    let kill = runtime.kill_switch();
+    tokio::spawn(async move {
+        let _ = tokio::signal::ctrl_c().await;
+        kill.trigger();
+    });
+
+    runtime.run(&mut grid).await
+}
@@ -3,11 +3,7 @@
 //! This example places an order, modifies it and then cancels it by oid.
 
 use clap::Parser;
-use hypersdk::hypercore::{
-    self as hypercore, BatchCancel, BatchModify, Cancel, Cloid, Modify, NonceHandler, OidOrCloid,
-    types::{BatchOrder, OrderGrouping, OrderRequest, OrderTypePlacement, TimeInForce},
-};
-use rust_decimal::dec;
+use hypersdk::{hypercore, prelude::*};
 
 use crate::credentials::Credentials;
 
@@ -79,6 +79,7 @@ async fn main() -> anyhow::Result<()> {
         match event {
             Event::Connected => println!("Connected"),
             Event::Disconnected => println!("Disconnected, reconnecting..."),
+            Event::Lagging { .. } | Event::Gap { .. } => {}
             Event::Message(msg) => match msg {
                 Incoming::UserEvents(user_event) => match user_event {
                     UserEvent::Fills { fills } => {
@@ -3,15 +3,19 @@
 //! This module provides commands for managing Ethereum keystores:
 //! - Creating new accounts with random private keys
 //! - Importing existing private keys into keystores
-//! - Listing available keystores
+//! - Listing available keystores (Foundry and geth)
+//! - Storing encrypted secrets referenced as `secret:<name>`
 
-use std::fs;
+use std::{fs, path::Path, str::FromStr};
 
 use alloy::signers::{self, Signer, ledger::LedgerSigner, trezor::TrezorSigner};
 use clap::{Args, Subcommand};
 use hypersdk::hypercore::PrivateKeySigner;
 
-use crate::utils::keystore_dir;
+use crate::{
+    keystore::{self, ScryptArgs},
+    utils::keystore_dir,
+};
 
 /// Account management commands.
 #[derive(Subcommand)]
@@ -20,6 +24,8 @@ pub enum AccountCmd {
     Create(CreateCmd),
     /// List available keystores
     List(ListCmd),
+    /// Store an encrypted secret (private key or password) referenced as `secret:<name>`
+    SetSecret(SetSecretCmd),
     /// Test hardware wallet signer (Ledger/Trezor)
     TestSigner(TestSignerCmd),
 }
@@ -29,6 +35,7 @@ impl AccountCmd {
         match self {
             Self::Create(cmd) => cmd.run().await,
             Self::List(cmd) => cmd.run().await,
+            Self::SetSecret(cmd) => cmd.run().await,
             Self::TestSigner(cmd) => cmd.run().await,
         }
     }
@@ -56,10 +63,17 @@ pub struct CreateCmd {
     #[arg(long)]
     pub name: String,
 
+    /// Private key to import instead of generating one. Accepts `secret:<name>`.
+    #[arg(long)]
+    pub private_key: Option<String>,
+
     /// Password for encrypting the keystore
     /// If not provided, will be prompted interactively
     #[arg(long)]
     pub password: Option<String>,
+
+    #[command(flatten)]
+    pub scrypt: ScryptArgs,
 }
 
 impl CreateCmd {
@@ -75,26 +89,24 @@ impl CreateCmd {
             anyhow::bail!("Keystore '{}' already exists", self.name);
         }
 
+        let signer = match self.private_key {
+            Some(key) => PrivateKeySigner::from_str(&keystore::reveal(&key)?)?,
+            None => PrivateKeySigner::random(),
+        };
+
         // Get password
         let password = match self.password {
-            Some(p) => p,
-            None => {
-                let pass = rpassword::prompt_password("Enter password for keystore: ")?;
-                let confirm = rpassword::prompt_password("Confirm password: ")?;
-                if pass != confirm {
-                    anyhow::bail!("Passwords do not match");
-                }
-                pass
-            }
+            Some(p) => keystore::reveal(&p)?,
+            None => prompt_new_password("Enter password for keystore: ")?,
         };
 
-        // Encrypt and save using eth_keystore
-        let (signer, _) = PrivateKeySigner::new_keystore(
-            &dir,
-            &mut rand_08::thread_rng(),
-            password.as_str(),
-            Some(self.name.as_str()),
+        let doc = keystore::encrypt(
+            signer.to_bytes().as_slice(),
+            &password,
+            self.scrypt,
+            Some(signer.address()),
         )?;
+        keystore::write_private(&keystore_path, &serde_json::to_vec_pretty(&doc)?)?;
 
         println!("Keystore created: {}", self.name);
         println!("Address: {}", signer.address());
@@ -106,62 +118,113 @@ impl CreateCmd {
 
 /// List available keystores.
 ///
-/// Shows all keystores in ~/.foundry/keystores/
+/// Shows all keystores in ~/.foundry/keystores/ and ~/.ethereum/keystore/
 #[derive(Args)]
 pub struct ListCmd {}
 
 impl ListCmd {
     pub async fn run(self) -> anyhow::Result<()> {
-        let dir = keystore_dir()?;
-
-        if !dir.exists() {
-            println!("No keystores found (directory does not exist)");
-            println!("Path: {}", dir.display());
-            return Ok(());
+        let foundry = list_dir(&keystore_dir()?)?;
+        let geth = list_dir(&keystore::geth_keystore_dir()?)?;
+        if !foundry && !geth {
+            println!("No keystores found");
+            println!("Path: {}", keystore_dir()?.display());
         }
+        Ok(())
+    }
+}
 
-        let entries: Vec<_> = fs::read_dir(&dir)?
-            .filter_map(|e| e.ok())
-            .filter(|e| e.file_type().map(|t| t.is_file()).unwrap_or(false))
-            .collect();
+/// Prints the keystores in `dir`, returning whether there were any.
+fn list_dir(dir: &Path) -> anyhow::Result<bool> {
+    if !dir.exists() {
+        return Ok(false);
+    }
 
-        if entries.is_empty() {
-            println!("No keystores found");
-            println!("Path: {}", dir.display());
-            return Ok(());
-        }
+    let entries: Vec<_> = fs::read_dir(dir)?
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().map(|t| t.is_file()).unwrap_or(false))
+        .collect();
 
-        println!("Available keystores ({}):", dir.display());
-
-        for entry in entries {
-            let name = entry.file_name();
-            let name_str = name.to_string_lossy();
-
-            // Try to read and parse the keystore to get the address
-            let path = entry.path();
-            match fs::read_to_string(&path) {
-                Ok(content) => {
-                    // Parse JSON to extract address
-                    if let Ok(json) = serde_json::from_str::<serde_json::Value>(&content) {
-                        if let Some(addr) = json.get("address").and_then(|a| a.as_str()) {
-                            println!("  {} (0x{})", name_str, addr);
-                        } else {
-                            println!("  {}", name_str);
-                        }
+    if entries.is_empty() {
+        return Ok(false);
+    }
+
+    println!("Available keystores ({}):", dir.display());
+
+    for entry in entries {
+        let name = entry.file_name();
+        let name_str = name.to_string_lossy();
+
+        // Try to read and parse the keystore to get the address
+        let path = entry.path();
+        match fs::read_to_string(&path) {
+            Ok(content) => {
+                // Parse JSON to extract address
+                if let Ok(json) = serde_json::from_str::<serde_json::Value>(&content) {
+                    if let Some(addr) = json.get("address").and_then(|a| a.as_str()) {
+                        println!("  {} (0x{})", name_str, addr.trim_start_matches("0x"));
                     } else {
                         println!("  {}", name_str);
                     }
-                }
-                Err(_) => {
+                } else {
                     println!("  {}", name_str);
                 }
             }
+            Err(_) => {
+                println!("  {}", name_str);
+            }
         }
+    }
+
+    Ok(true)
+}
 
+/// Store an encrypted secret.
+///
+/// The value is prompted and encrypted with a passphrase into
+/// `~/.hypecli/secrets/<name>.json`. Reference it as `secret:<name>` in `--private-key`,
+/// `--password` or their `HYPECLI_*` variables; the passphrase is prompted on use, or
+/// read from `HYPECLI_SECRETS_PASSWORD`.
+///
+/// # Examples
+///
+/// ```bash
+/// hypecli account set-secret --name trading-key
+/// echo 'HYPECLI_TRADING_PRIVATE_KEY=secret:trading-key' >> .env
+/// ```
+#[derive(Args)]
+pub struct SetSecretCmd {
+    /// Name of the secret
+    #[arg(long)]
+    pub name: String,
+
+    #[command(flatten)]
+    pub scrypt: ScryptArgs,
+}
+
+impl SetSecretCmd {
+    pub async fn run(self) -> anyhow::Result<()> {
+        let value = rpassword::prompt_password(format!("Value of {}: ", self.name))?;
+        anyhow::ensure!(!value.is_empty(), "empty secret");
+        let passphrase = prompt_new_password("Passphrase: ")?;
+
+        let path = keystore::save_secret(&self.name, &value, &passphrase, self.scrypt)?;
+        println!("Secret stored: {}", path.display());
+        println!("Reference it as secret:{}", self.name);
         Ok(())
     }
 }
 
+/// Prompts for a new password twice.
+fn prompt_new_password(prompt: &str) -> anyhow::Result<String> {
+    let pass = rpassword::prompt_password(prompt)?;
+    let confirm = rpassword::prompt_password("Confirm password: ")?;
+    if pass != confirm {
+        anyhow::bail!("Passwords do not match");
+    }
+    Ok(pass)
+}
+
 #[derive(Args)]
 pub struct TestSignerCmd {}
 
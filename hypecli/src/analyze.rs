@@ -0,0 +1,124 @@
+//! Market analysis commands.
+//!
+//! This module provides commands that sample market data over time and write
+//! the results as CSV for research.
+
+use std::fs::File;
+use std::io::{BufWriter, Write, stdout};
+use std::time::Duration;
+
+use clap::{Args, Subcommand};
+use hypersdk::hypercore::{Chain, depth::DepthStats};
+use rust_decimal::Decimal;
+use tokio::time::{Instant, MissedTickBehavior, interval};
+
+use crate::utils::resolve_asset_for_subscription;
+
+/// Sample market data and export statistics.
+#[derive(Subcommand)]
+pub enum AnalyzeCmd {
+    /// Sample order book depth, spread and market impact to CSV
+    Depth(DepthCmd),
+}
+
+impl AnalyzeCmd {
+    pub async fn run(self) -> anyhow::Result<()> {
+        match self {
+            Self::Depth(cmd) => cmd.run().await,
+        }
+    }
+}
+
+/// Sample L2 book snapshots and record depth statistics as CSV.
+///
+/// Each row holds the mid, the spread, the top-of-book sizes, the notional
+/// resting within each band around the mid, and the impact of sweeping the
+/// book for each notional.
+///
+/// # Example
+///
+/// ```bash
+/// hypecli analyze depth --asset BTC --hours 24
+/// hypecli analyze depth --asset PURR/USDC --interval 10 --bands 5,25 --output purr.csv
+/// ```
+#[derive(Args)]
+pub struct DepthCmd {
+    /// Asset name. Formats:
+    /// - "BTC" for BTC perpetual
+    /// - "PURR/USDC" for PURR spot market
+    /// - "xyz:BTC" for BTC perpetual on xyz HIP3 DEX
+    #[arg(long)]
+    pub asset: String,
+    /// How long to sample for, in hours
+    #[arg(long, default_value = "1")]
+    pub hours: Decimal,
+    /// Seconds between snapshots
+    #[arg(long, default_value = "60")]
+    pub interval: u64,
+    /// Depth bands around the mid, in basis points
+    #[arg(long, value_delimiter = ',', default_value = "5,10,25,50,100")]
+    pub bands: Vec<Decimal>,
+    /// Notionals to measure the market impact of, in quote units
+    #[arg(long, value_delimiter = ',', default_value = "10000,100000,1000000")]
+    pub impact: Vec<Decimal>,
+    /// CSV file to write to (default: stdout)
+    #[arg(long)]
+    pub output: Option<String>,
+    /// Target chain
+    #[arg(long, default_value = "Mainnet")]
+    pub chain: Chain,
+}
+
+impl DepthCmd {
+    pub async fn run(self) -> anyhow::Result<()> {
+        anyhow::ensure!(self.interval > 0, "--interval must be positive");
+        let client = crate::utils::http_client(self.chain);
+        let resolved = resolve_asset_for_subscription(&client, &self.asset).await?;
+
+        let mut out: Box<dyn Write> = match &self.output {
+            Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+            None => Box::new(stdout()),
+        };
+
+        let seconds = (self.hours * Decimal::from(3600)).trunc();
+        let seconds: u64 = seconds.try_into().unwrap_or_default();
+        let deadline = Instant::now() + Duration::from_secs(seconds);
+        let mut ticker = interval(Duration::from_secs(self.interval));
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+        eprintln!(
+            "Sampling {} depth every {}s for {}h...",
+            self.asset, self.interval, self.hours
+        );
+
+        let mut header = false;
+        let mut samples = 0u64;
+        loop {
+            ticker.tick().await;
+            if Instant::now() > deadline {
+                break;
+            }
+            let book = match client.l2_book(resolved.coin.clone(), None, None).await {
+                Ok(book) => book,
+                Err(err) => {
+                    eprintln!("Failed to fetch book: {err:#}");
+                    continue;
+                }
+            };
+            let Some(stats) = DepthStats::new(&book, &self.bands, &self.impact) else {
+                eprintln!("Skipping one-sided book at {}", book.time);
+                continue;
+            };
+            if !header {
+                writeln!(out, "{}", stats.csv_header())?;
+                header = true;
+            }
+            writeln!(out, "{}", stats.csv_row())?;
+            out.flush()?;
+            samples += 1;
+        }
+
+        eprintln!("Recorded {samples} snapshots");
+        Ok(())
+    }
+}
@@ -88,7 +88,7 @@ pub struct BalanceCmd {
 
 impl BalanceCmd {
     pub async fn run(self) -> anyhow::Result<()> {
-        let core = hypercore::mainnet();
+        let core = crate::utils::http_client(hypercore::Chain::Mainnet);
 
         // Query spot balances
         let spot_balances = core.user_balances(self.user).await?;
@@ -0,0 +1,236 @@
+//! Environment diagnostics.
+//!
+//! Checks everything a command depends on before it is run for real: the info,
+//! exchange and WebSocket endpoints of the chain, the HyperEVM RPC, the local clock,
+//! the configured keystores and the account's request budget. Every check runs even
+//! if an earlier one fails, and the command fails if any of them did.
+
+use std::time::{Duration, Instant};
+
+use clap::Args;
+use futures::StreamExt;
+use hypersdk::{
+    Address, hyperevm,
+    hypercore::{Chain, NonceHandler, registry::Purpose, ws::Event},
+};
+
+use crate::{
+    SignerArgs,
+    utils::{find_signer_sync, http_client, keystore_for, private_key_for},
+};
+
+/// Default HyperEVM testnet RPC URL.
+const TESTNET_RPC_URL: &str = "https://rpc.hyperliquid-testnet.xyz/evm";
+
+/// How long the WebSocket may take to connect.
+const WS_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Share of the request budget above which the rate limit is reported as a warning.
+const RATE_LIMIT_WARNING: f64 = 0.8;
+
+/// Check connectivity, clock skew, keystores and rate limits.
+#[derive(Args)]
+pub struct DoctorCmd {
+    /// HyperEVM RPC endpoint. Defaults to the chain's public RPC; skipped on a local
+    /// chain unless set
+    #[arg(long)]
+    rpc_url: Option<String>,
+    /// Account whose rate limit to report. Defaults to the first configured signer
+    #[arg(long)]
+    user: Option<Address>,
+    #[command(flatten)]
+    signer: SignerArgs,
+}
+
+/// Outcome of one check.
+enum Status {
+    Ok,
+    Warn,
+    Fail,
+}
+
+/// Prints check results and counts failures.
+#[derive(Default)]
+struct Report {
+    failures: usize,
+}
+
+impl Report {
+    fn line(&mut self, status: Status, check: &str, detail: impl std::fmt::Display) {
+        let tag = match status {
+            Status::Ok => " ok ",
+            Status::Warn => "warn",
+            Status::Fail => {
+                self.failures += 1;
+                "FAIL"
+            }
+        };
+        println!("[{tag}] {check:<10} {detail}");
+    }
+
+    fn check<T>(&mut self, check: &str, res: anyhow::Result<T>) -> Option<T> {
+        match res {
+            Ok(value) => Some(value),
+            Err(err) => {
+                self.line(Status::Fail, check, format!("{err:#}"));
+                None
+            }
+        }
+    }
+}
+
+impl DoctorCmd {
+    pub async fn run(self) -> anyhow::Result<()> {
+        let chain = self.signer.chain;
+        let client = http_client(chain);
+        let mut report = Report::default();
+        println!("hypecli doctor: {chain:?} ({})", chain.api_url());
+
+        // Info endpoint and clock, from one timed request.
+        if let Some(skew) = report.check("info", client.measure_clock_skew().await) {
+            report.line(Status::Ok, "info", format!("{}ms", skew.round_trip.as_millis()));
+            let offset = skew.offset.num_milliseconds();
+            let direction = if offset > 0 { "behind" } else { "ahead of" };
+            let detail = format!(
+                "local clock {}ms {direction} the server (±{}ms)",
+                offset.abs(),
+                skew.round_trip.as_millis() / 2
+            );
+            if skew.exceeds(NonceHandler::SKEW_WARNING) {
+                report.line(Status::Warn, "clock", format!("{detail}; sync it with NTP"));
+            } else {
+                report.line(Status::Ok, "clock", detail);
+            }
+        }
+
+        // The exchange rejects an empty body, which proves it is reachable.
+        let mut exchange_url = chain.api_url();
+        exchange_url.set_path("/exchange");
+        let start = Instant::now();
+        let res = reqwest::Client::new()
+            .post(exchange_url)
+            .json(&serde_json::json!({}))
+            .timeout(WS_TIMEOUT)
+            .send()
+            .await;
+        match res {
+            Ok(res) if !res.status().is_server_error() => {
+                let detail = format!("{}ms", start.elapsed().as_millis());
+                report.line(Status::Ok, "exchange", detail);
+            }
+            Ok(res) => report.line(Status::Fail, "exchange", format!("HTTP {}", res.status())),
+            Err(err) => report.line(Status::Fail, "exchange", err),
+        }
+
+        let start = Instant::now();
+        let mut ws = client.websocket();
+        let connected = tokio::time::timeout(WS_TIMEOUT, async {
+            while let Some(event) = ws.next().await {
+                if matches!(event, Event::Connected) {
+                    return true;
+                }
+            }
+            false
+        })
+        .await;
+        match connected {
+            Ok(true) => {
+                let detail = format!("{}ms", start.elapsed().as_millis());
+                report.line(Status::Ok, "websocket", detail);
+            }
+            _ => {
+                let detail = format!("no connection within {WS_TIMEOUT:?}");
+                report.line(Status::Fail, "websocket", detail);
+            }
+        }
+        ws.close();
+
+        let rpc_url = self.rpc_url.clone().or(match chain {
+            Chain::Mainnet => Some(hyperevm::DEFAULT_RPC_URL.to_owned()),
+            Chain::Testnet => Some(TESTNET_RPC_URL.to_owned()),
+            Chain::Local => None,
+        });
+        match rpc_url {
+            Some(url) => {
+                let start = Instant::now();
+                let block = async {
+                    let provider = hyperevm::mainnet_with_url(&url).await?;
+                    block_number(&provider).await
+                };
+                if let Some(block) = report.check("evm rpc", block.await) {
+                    let detail = format!("{}ms, block {block}", start.elapsed().as_millis());
+                    report.line(Status::Ok, "evm rpc", detail);
+                }
+            }
+            None => report.line(Status::Warn, "evm rpc", "skipped: pass --rpc-url"),
+        }
+
+        // Each configured key, once, whichever purposes share it.
+        let mut seen = Vec::new();
+        let mut signer_address = None;
+        for purpose in [
+            Purpose::Trading,
+            Purpose::Withdrawals,
+            Purpose::Account,
+            Purpose::MultiSig,
+        ] {
+            let key = private_key_for(&self.signer, purpose);
+            let (id, source) = match (key, keystore_for(&self.signer, purpose)) {
+                (Ok(Some(key)), _) => (key, "private key".to_owned()),
+                (_, Some(keystore)) => (keystore.clone(), format!("keystore {keystore}")),
+                (Ok(None), None) => continue,
+                (Err(err), None) => {
+                    report.line(Status::Fail, "signer", format!("{purpose}: {err:#}"));
+                    continue;
+                }
+            };
+            if seen.contains(&id) {
+                continue;
+            }
+            seen.push(id);
+            match find_signer_sync(&self.signer, purpose) {
+                Ok(signer) => {
+                    signer_address.get_or_insert(signer.address());
+                    let detail = format!("{purpose}: {source} unlocks {}", signer.address());
+                    report.line(Status::Ok, "signer", detail);
+                }
+                Err(err) => {
+                    let detail = format!("{purpose}: {source}: {err:#}");
+                    report.line(Status::Fail, "signer", detail);
+                }
+            }
+        }
+        if seen.is_empty() {
+            report.line(Status::Warn, "signer", "no private key or keystore configured");
+        }
+
+        match self.user.or(signer_address) {
+            Some(user) => {
+                let limit = report.check("rate limit", client.user_rate_limit(user).await);
+                if let Some(limit) = limit {
+                    let used = limit.n_requests_used as f64 / limit.n_requests_cap.max(1) as f64;
+                    let detail = format!(
+                        "{user}: {} of {} requests used (volume {})",
+                        limit.n_requests_used, limit.n_requests_cap, limit.cum_vlm
+                    );
+                    let status = if used > RATE_LIMIT_WARNING {
+                        Status::Warn
+                    } else {
+                        Status::Ok
+                    };
+                    report.line(status, "rate limit", detail);
+                }
+            }
+            None => report.line(Status::Warn, "rate limit", "skipped: pass --user or a signer"),
+        }
+
+        if report.failures > 0 {
+            anyhow::bail!("{} check(s) failed", report.failures);
+        }
+        Ok(())
+    }
+}
+
+async fn block_number<P: hyperevm::Provider>(provider: &P) -> anyhow::Result<u64> {
+    Ok(provider.get_block_number().await?)
+}
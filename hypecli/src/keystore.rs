@@ -0,0 +1,229 @@
+//! Keystore files and encrypted secrets.
+//!
+//! Keystores are read from both layouts in use:
+//! - Foundry: `~/.foundry/keystores/<name>`
+//! - geth: `~/.ethereum/keystore/UTC--<time>--<address>`
+//!
+//! New keystores are written in the Web3 Secret Storage (v3) format with configurable
+//! scrypt parameters, so they can be imported by either tool.
+//!
+//! Secrets (private keys, keystore passwords) can be stored encrypted in
+//! `~/.hypecli/secrets/<name>.json` and referenced as `secret:<name>` from the `.env`
+//! file or flags, so they never appear in shell history.
+
+use std::{
+    env::home_dir,
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use aes::cipher::{KeyIvInit, StreamCipher};
+use alloy::primitives::{Address, keccak256};
+use clap::Args;
+use rand_08::RngCore;
+
+use crate::utils::keystore_dir;
+
+/// Prefix of values that reference an encrypted secret.
+const SECRET_PREFIX: &str = "secret:";
+
+type Aes128Ctr = ctr::Ctr128BE<aes::Aes128>;
+
+/// scrypt parameters for new keystores and secrets.
+///
+/// The defaults match Foundry (`cast wallet new`). geth uses `--scrypt-log-n 18`, which
+/// is slower to brute-force and to unlock.
+#[derive(Args, Clone, Copy)]
+pub struct ScryptArgs {
+    /// scrypt cost as a power of two (N = 2^log_n).
+    #[arg(long, default_value_t = 13)]
+    pub scrypt_log_n: u8,
+    /// scrypt block size.
+    #[arg(long, default_value_t = 8)]
+    pub scrypt_r: u32,
+    /// scrypt parallelism.
+    #[arg(long, default_value_t = 1)]
+    pub scrypt_p: u32,
+}
+
+/// `~/.ethereum/keystore`, geth's default keystore directory.
+pub fn geth_keystore_dir() -> anyhow::Result<PathBuf> {
+    let home = home_dir().ok_or_else(|| anyhow::anyhow!("Unable to locate home directory"))?;
+    Ok(home.join(".ethereum").join("keystore"))
+}
+
+/// `~/.hypecli/secrets`, where encrypted secrets are kept.
+pub fn secrets_dir() -> anyhow::Result<PathBuf> {
+    let home = home_dir().ok_or_else(|| anyhow::anyhow!("Unable to locate home directory"))?;
+    Ok(home.join(".hypecli").join("secrets"))
+}
+
+/// Resolves a `--keystore` value to a file.
+///
+/// Accepts, in order: a path to a keystore file, a Foundry keystore name, or a geth
+/// keystore's file name or address.
+pub fn resolve_keystore(name: &str) -> anyhow::Result<PathBuf> {
+    let path = Path::new(name);
+    if path.components().count() > 1 && path.is_file() {
+        return Ok(path.to_path_buf());
+    }
+
+    let foundry = keystore_dir()?.join(name);
+    if foundry.is_file() {
+        return Ok(foundry);
+    }
+
+    let geth_dir = geth_keystore_dir()?;
+    let address = name.trim_start_matches("0x").to_lowercase();
+    if let Ok(entries) = fs::read_dir(&geth_dir) {
+        for entry in entries.filter_map(|e| e.ok()) {
+            let file_name = entry.file_name().to_string_lossy().to_lowercase();
+            if file_name == name.to_lowercase() || file_name.ends_with(&format!("--{address}")) {
+                return Ok(entry.path());
+            }
+        }
+    }
+
+    anyhow::bail!(
+        "keystore {name} doesn't exist in {} or {}",
+        keystore_dir()?.display(),
+        geth_dir.display()
+    )
+}
+
+/// Encrypts `secret` as a Web3 Secret Storage (v3) document.
+pub fn encrypt(
+    secret: &[u8],
+    password: &str,
+    scrypt: ScryptArgs,
+    address: Option<Address>,
+) -> anyhow::Result<serde_json::Value> {
+    let mut rng = rand_08::thread_rng();
+    let mut salt = [0u8; 32];
+    let mut iv = [0u8; 16];
+    rng.fill_bytes(&mut salt);
+    rng.fill_bytes(&mut iv);
+
+    let params = scrypt::Params::new(scrypt.scrypt_log_n, scrypt.scrypt_r, scrypt.scrypt_p, 32)
+        .map_err(|err| anyhow::anyhow!("invalid scrypt parameters: {err}"))?;
+    let mut key = [0u8; 32];
+    scrypt::scrypt(password.as_bytes(), &salt, &params, &mut key)
+        .map_err(|err| anyhow::anyhow!("scrypt: {err}"))?;
+
+    let mut ciphertext = secret.to_vec();
+    Aes128Ctr::new(key[..16].into(), &iv.into()).apply_keystream(&mut ciphertext);
+    let mac = keccak256([&key[16..32], &ciphertext[..]].concat());
+
+    let mut doc = serde_json::json!({
+        "crypto": {
+            "cipher": "aes-128-ctr",
+            "cipherparams": { "iv": hex::encode(iv) },
+            "ciphertext": hex::encode(&ciphertext),
+            "kdf": "scrypt",
+            "kdfparams": {
+                "dklen": 32,
+                "n": 1u64 << scrypt.scrypt_log_n,
+                "p": scrypt.scrypt_p,
+                "r": scrypt.scrypt_r,
+                "salt": hex::encode(salt),
+            },
+            "mac": hex::encode(mac),
+        },
+        "id": uuid::Uuid::new_v4().to_string(),
+        "version": 3,
+    });
+    if let Some(address) = address {
+        doc["address"] = hex::encode(address).into();
+    }
+    Ok(doc)
+}
+
+/// Encrypts `value` with `passphrase` and stores it as secret `name`.
+pub fn save_secret(
+    name: &str,
+    value: &str,
+    passphrase: &str,
+    scrypt: ScryptArgs,
+) -> anyhow::Result<PathBuf> {
+    anyhow::ensure!(
+        !name.is_empty() && !name.contains(['/', '\\']),
+        "invalid secret name {name:?}"
+    );
+    let dir = secrets_dir()?;
+    fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("{name}.json"));
+    let doc = encrypt(value.as_bytes(), passphrase, scrypt, None)?;
+    write_private(&path, &serde_json::to_vec_pretty(&doc)?)?;
+    Ok(path)
+}
+
+/// Writes key material to `path`, readable only by the owner on Unix.
+pub fn write_private(path: &Path, contents: &[u8]) -> anyhow::Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path)?;
+    // The mode only applies to new files; tighten an existing one too.
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    }
+    file.write_all(contents)?;
+    Ok(())
+}
+
+/// Returns `value`, decrypting it first if it references a secret (`secret:<name>`).
+///
+/// The passphrase is read from `HYPECLI_SECRETS_PASSWORD` or prompted.
+pub fn reveal(value: &str) -> anyhow::Result<String> {
+    let Some(name) = value.strip_prefix(SECRET_PREFIX) else {
+        return Ok(value.to_owned());
+    };
+    let path = secrets_dir()?.join(format!("{name}.json"));
+    anyhow::ensure!(path.is_file(), "secret {name} doesn't exist");
+    let passphrase = match std::env::var("HYPECLI_SECRETS_PASSWORD") {
+        Ok(passphrase) => passphrase,
+        Err(_) => rpassword::prompt_password(format!("Passphrase for secret {name}: "))?,
+    };
+    let bytes = eth_keystore::decrypt_key(&path, passphrase)
+        .map_err(|err| anyhow::anyhow!("unable to decrypt secret {name}: {err}"))?;
+    Ok(String::from_utf8(bytes)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypt_decrypts_with_eth_keystore() {
+        let secret = [7u8; 32];
+        let scrypt = ScryptArgs {
+            scrypt_log_n: 10,
+            scrypt_r: 8,
+            scrypt_p: 1,
+        };
+        let doc = encrypt(&secret, "hunter2", scrypt, Some(Address::ZERO)).unwrap();
+
+        let name = format!("hypecli-keystore-{}.json", std::process::id());
+        let path = std::env::temp_dir().join(name);
+        write_private(&path, &serde_json::to_vec(&doc).unwrap()).unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        let decrypted = eth_keystore::decrypt_key(&path, "hunter2");
+        let wrong = eth_keystore::decrypt_key(&path, "hunter3");
+        fs::remove_file(&path).unwrap();
+        assert_eq!(decrypted.unwrap(), secret);
+        assert!(wrong.is_err());
+    }
+}
@@ -0,0 +1,57 @@
+//! Leverage command.
+//!
+//! This module provides a command for setting the leverage and margin mode
+//! (cross or isolated) of a perpetual market.
+
+use clap::Args;
+use hypersdk::hypercore::{NonceHandler, registry::Purpose};
+
+use crate::SignerArgs;
+use crate::utils::{find_signer_sync, resolve_perp};
+
+/// Set leverage for a perpetual market.
+#[derive(Args, derive_more::Deref)]
+pub struct LeverageCmd {
+    #[deref]
+    #[command(flatten)]
+    pub signer: SignerArgs,
+
+    /// Perpetual asset name (e.g., "BTC", "xyz:BTC")
+    #[arg(long)]
+    pub asset: String,
+
+    /// Leverage to apply (e.g., 10 for 10x)
+    #[arg(long)]
+    pub leverage: u32,
+
+    /// Use isolated margin instead of cross margin
+    #[arg(long, default_value = "false")]
+    pub isolated: bool,
+}
+
+impl LeverageCmd {
+    pub async fn run(self) -> anyhow::Result<()> {
+        let signer = find_signer_sync(&self.signer, Purpose::Trading)?;
+        let client = crate::utils::http_client(self.signer.chain);
+        let market = resolve_perp(&client, &self.asset).await?;
+
+        let mode = if self.isolated { "isolated" } else { "cross" };
+        println!("Setting {} to {}x {}", market.name, self.leverage, mode);
+
+        let nonce = NonceHandler::default().next();
+        client
+            .set_leverage(
+                &signer,
+                &market,
+                !self.isolated,
+                self.leverage,
+                nonce,
+                None,
+                None,
+            )
+            .await?;
+
+        println!("Leverage updated.");
+        Ok(())
+    }
+}
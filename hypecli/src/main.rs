@@ -1,5 +1,9 @@
 mod account;
+mod analyze;
 mod balances;
+mod doctor;
+mod keystore;
+mod leverage;
 mod markets;
 mod morpho;
 mod multisig;
@@ -8,16 +12,21 @@ mod orders_list;
 mod positions;
 mod prio;
 mod send;
+mod staking;
 mod subscribe;
 mod to_multisig;
 mod twap;
 mod utils;
 mod vault;
+mod verify;
 
 use account::AccountCmd;
+use analyze::AnalyzeCmd;
 use balances::BalanceCmd;
 use clap::{Args, Parser};
+use doctor::DoctorCmd;
 use hypersdk::hypercore::Chain;
+use leverage::LeverageCmd;
 use markets::{DexesCmd, PerpsCmd, SpotCmd};
 use morpho::{MorphoApyCmd, MorphoPositionCmd, MorphoVaultApyCmd};
 use multisig::MultiSigCmd;
@@ -26,10 +35,12 @@ use orders_list::OrdersCmd;
 use positions::PositionsCmd;
 use prio::PrioCmd;
 use send::SendCmd;
+use staking::StakingCmd;
 use subscribe::SubscribeCmd;
 use to_multisig::ToMultiSigCmd;
 use twap::TwapCmd;
 use vault::VaultCmd;
+use verify::VerifyActionCmd;
 
 /// Main CLI structure for hypecli - A command-line interface for Hyperliquid.
 #[derive(Parser)]
@@ -40,6 +51,13 @@ struct Cli {
     #[arg(long)]
     agent_help: bool,
 
+    /// Load settings from ~/.hypecli/profiles/<PROFILE>.env.
+    #[arg(long, global = true, env = "HYPECLI_PROFILE")]
+    profile: Option<String>,
+
+    #[command(flatten)]
+    network: NetworkArgs,
+
     #[command(subcommand)]
     command: Option<Command>,
 }
@@ -72,6 +90,8 @@ enum Command {
     /// Order management (place and cancel orders)
     #[command(subcommand)]
     Order(OrderCmd),
+    /// Set leverage and margin mode for a perpetual market
+    Leverage(LeverageCmd),
     /// Subscribe to real-time WebSocket data feeds
     #[command(subcommand)]
     Subscribe(SubscribeCmd),
@@ -80,6 +100,9 @@ enum Command {
     /// Vault deposit and withdrawal commands
     #[command(subcommand)]
     Vault(VaultCmd),
+    /// HYPE staking: move to/from the staking balance and delegate to validators
+    #[command(subcommand)]
+    Staking(StakingCmd),
     /// Query open perpetual positions for a user
     Positions(PositionsCmd),
     /// Query historical orders or trade fills
@@ -90,6 +113,13 @@ enum Command {
     Prio(PrioCmd),
     /// Execute a stealth TWAP as independent market orders
     Twap(TwapCmd),
+    /// Recompute an action's signing hash and recover its signer
+    VerifyAction(VerifyActionCmd),
+    /// Sample market data and export statistics
+    #[command(subcommand)]
+    Analyze(AnalyzeCmd),
+    /// Check connectivity, clock skew, keystores and rate limits
+    Doctor(DoctorCmd),
 }
 
 impl Command {
@@ -106,13 +136,18 @@ impl Command {
             Self::Multisig(cmd) => cmd.run().await,
             Self::ToMultisig(cmd) => cmd.run().await,
             Self::Order(cmd) => cmd.run().await,
+            Self::Leverage(cmd) => cmd.run().await,
             Self::Subscribe(cmd) => cmd.run().await,
             Self::Send(cmd) => cmd.run().await,
             Self::Vault(cmd) => cmd.run().await,
+            Self::Staking(cmd) => cmd.run().await,
             Self::Positions(cmd) => cmd.run().await,
             Self::Orders(cmd) => cmd.run().await,
             Self::Prio(cmd) => cmd.run().await,
             Self::Twap(cmd) => cmd.run().await,
+            Self::VerifyAction(cmd) => cmd.run().await,
+            Self::Analyze(cmd) => cmd.run().await,
+            Self::Doctor(cmd) => cmd.run().await,
         }
     }
 }
@@ -123,24 +158,59 @@ impl Command {
 /// the signer credentials and target multi-sig wallet.
 #[derive(Args)]
 pub struct SignerArgs {
-    /// Private key for signing (hex format).
+    /// Private key for signing (hex format, or `secret:<name>`). Defaults to
+    /// `HYPECLI_<PURPOSE>_PRIVATE_KEY`, then `HYPECLI_PRIVATE_KEY`.
     #[arg(long)]
     pub private_key: Option<String>,
-    /// Foundry keystore.
-    #[arg(long, env = "HYPECLI_KEYSTORE")]
+    /// Foundry keystore name, geth keystore address, or keystore path. Defaults to `HYPECLI_<PURPOSE>_KEYSTORE`, then `HYPECLI_KEYSTORE`.
+    #[arg(long)]
     pub keystore: Option<String>,
-    /// Keystore password. Otherwise it'll be prompted.
-    #[arg(long, env = "HYPECLI_PASSWORD")]
+    /// Keystore password. Defaults to `HYPECLI_<PURPOSE>_PASSWORD`, then `HYPECLI_PASSWORD`.
+    /// Otherwise it'll be prompted.
+    #[arg(long)]
     pub password: Option<String>,
-    /// Target chain for the operation.
+    /// Target chain for the operation (mainnet, testnet, or local).
     #[arg(long, default_value = "mainnet")]
     pub chain: Chain,
 }
 
+/// HTTP settings shared by every command.
+///
+/// Set per invocation with the global flags, or with `HYPECLI_TIMEOUT`, `HYPECLI_RETRIES`
+/// and `HYPECLI_CACHE_TTL` in the environment, a `.env` file or a `--profile`.
+#[derive(Args, Clone, Copy)]
+pub struct NetworkArgs {
+    /// HTTP request timeout in seconds (at least 1).
+    #[arg(
+        long,
+        global = true,
+        env = "HYPECLI_TIMEOUT",
+        default_value_t = 10,
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub timeout: u64,
+    /// Retries for failed queries (connection errors, timeouts, 429 and 5xx).
+    #[arg(long, global = true, env = "HYPECLI_RETRIES", default_value_t = 0)]
+    pub retries: u32,
+    /// Seconds cached market metadata is reused before it is refetched (0 disables the cache).
+    #[arg(
+        long,
+        global = true,
+        env = "HYPECLI_CACHE_TTL",
+        default_value_t = utils::METADATA_TTL.as_secs()
+    )]
+    pub cache_ttl: u64,
+    /// Refetch market metadata instead of reusing the cache.
+    #[arg(long, global = true)]
+    pub refresh: bool,
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
+    utils::load_profile()?;
     dotenvy::dotenv().ok();
     let cli = Cli::parse();
+    utils::init_network(cli.network);
 
     if cli.agent_help {
         print_agent_help();
@@ -172,6 +242,23 @@ CHAIN SELECTION
 Most commands require a `--chain` argument. Valid values are:
   - Mainnet  Production Hyperliquid network
   - Testnet  Test network for development
+  - Local    Local node or emulator, signed as testnet
+             (http://localhost:3001, override with HYPERLIQUID_LOCAL_URL)
+
+NETWORK
+-------
+Global flags accepted by every command:
+  --timeout <SECS>      HTTP request timeout (default 10, env HYPECLI_TIMEOUT)
+  --retries <N>         Retries for failed queries (default 0, env HYPECLI_RETRIES)
+  --cache-ttl <SECS>    Reuse cached market metadata for this long (default 300,
+                        env HYPECLI_CACHE_TTL, 0 disables the cache)
+  --refresh             Refetch market metadata instead of reusing the cache
+  --profile <NAME>      Load HYPECLI_* settings from ~/.hypecli/profiles/<NAME>.env
+                        (env HYPECLI_PROFILE); the environment and flags still win
+
+Market metadata (perps, spot markets, HIP-3 DEXes) is cached in ~/.cache/hypecli, so
+asset names resolve without refetching the universe on every command. Pass --refresh
+right after a new listing.
 
 AUTHENTICATION
 --------------
@@ -180,6 +267,12 @@ Commands that modify state (orders, transfers, etc.) require authentication via
   --keystore <NAME>     Foundry keystore name (located in ~/.foundry/keystores/)
   --password <PASS>     Keystore password (prompted if not provided)
 
+Each command signs for a purpose: trading (orders, leverage, TWAP, priority bids),
+withdrawals (sends, vault and staking transfers), account (multi-sig conversion) or
+multisig. Without --keystore, the keystore comes from HYPECLI_<PURPOSE>_KEYSTORE
+(e.g. HYPECLI_TRADING_KEYSTORE), falling back to HYPECLI_KEYSTORE. Passwords resolve
+the same way with HYPECLI_<PURPOSE>_PASSWORD and HYPECLI_PASSWORD.
+
 Note: Ledger and Trezor hardware wallets are supported for multi-sig operations but NOT for
 order placement/cancellation (which require synchronous signing).
 
@@ -205,10 +298,26 @@ Create a New Keystore:
 Or alternatively you can specify the password
   hypecli account create --name <KEYSTORE_NAME> --password <PASSWORD>
 
+Import a Private Key:
+  hypecli account create --name <KEYSTORE_NAME> --private-key <HEX>
+
+Tune scrypt for new keystores (defaults match Foundry; geth uses --scrypt-log-n 18):
+  hypecli account create --name <KEYSTORE_NAME> --scrypt-log-n 18
+
 List Available Keystores:
   hypecli account list
 
-Keystores are stored in ~/.foundry/keystores/ and are compatible with Foundry.
+Keystores are stored in ~/.foundry/keystores/ and are compatible with Foundry. geth
+keystores in ~/.ethereum/keystore/ can be used with --keystore <ADDRESS>, and any keystore
+file with --keystore <PATH>.
+
+Store an Encrypted Secret:
+  hypecli account set-secret --name <NAME>
+  # Value and passphrase are prompted
+
+Reference it as secret:<NAME> in --private-key, --password or HYPECLI_*_PRIVATE_KEY /
+HYPECLI_*_PASSWORD so keys never appear in shell history. The passphrase is prompted on
+use, or read from HYPECLI_SECRETS_PASSWORD.
 
 QUERY COMMANDS (No Authentication Required)
 -------------------------------------------
@@ -282,6 +391,33 @@ Query Trade Fills:
   --coin <SYMBOL>             Filter by asset
   --format <pretty|table|json>
 
+Sample Order Book Depth to CSV:
+  hypecli analyze depth --asset BTC --hours 24
+  hypecli analyze depth --asset PURR/USDC --interval 10 --output purr.csv
+
+  Options:
+  --hours <N>              How long to sample for (default: 1)
+  --interval <SECS>        Seconds between snapshots (default: 60)
+  --bands <BPS,...>        Depth bands around the mid (default: 5,10,25,50,100)
+  --impact <NTL,...>       Notionals to measure impact of (default: 10000,100000,1000000)
+  --output <FILE>          CSV file to write to (default: stdout)
+
+  Each row holds the mid, spread (bps), top-of-book sizes, bid/ask notional within
+  each band, and the average fill price of each impact notional in bps from the mid.
+
+Diagnose the Environment:
+  hypecli doctor
+  hypecli doctor --chain testnet --keystore <NAME> --user <ADDRESS>
+
+  Options:
+  --rpc-url <URL>          HyperEVM RPC to check (default: the chain's public RPC)
+  --user <ADDRESS>         Account whose rate limit to report (default: the signer)
+
+  Checks the info, exchange and WebSocket endpoints and the HyperEVM RPC with their
+  latency, the local clock against the server, that each configured private key or
+  keystore unlocks, and how much of the account's request budget is used. Exits with
+  an error if any check fails.
+
 ORDER COMMANDS
 --------------
 
@@ -344,6 +480,20 @@ Cancel Order (by OID or CLOID):
     --oid <NUMBER>    Exchange-assigned order ID (use this OR --cloid)
     --cloid <HEX>     Client-assigned order ID, 32 hex chars (use this OR --oid)
 
+Set Leverage (Perpetuals Only):
+  hypecli leverage \
+    --chain mainnet \
+    --private-key <HEX> \
+    --asset BTC \
+    --leverage 10
+
+  Arguments:
+    --asset <NAME>       Perpetual asset name (BTC or xyz:BTC)
+    --leverage <N>       Leverage to apply (1 up to the market's max leverage)
+    --isolated           Use isolated margin (default: cross)
+
+  Markets that only support isolated margin reject cross leverage.
+
 MULTI-SIG COMMANDS
 ------------------
 
@@ -385,6 +535,36 @@ Convert Multi-Sig to Normal User:
     --private-key <HEX> \
     --multi-sig-addr <MULTISIG_ADDRESS>
 
+Offline Multi-Sig (no P2P):
+  Add --offline to send-asset, update, convert-to-normal-user or resume to print
+  the proposal as a base64 blob (--qr for a QR code) and paste signatures back.
+  Signers answer with:
+  hypecli multisig sign \
+    --chain mainnet \
+    --private-key <HEX> \
+    --multi-sig-addr <MULTISIG_ADDRESS> \
+    --proposal <BLOB>
+
+Resume an Interrupted Multi-Sig Session:
+  hypecli multisig resume \
+    --private-key <HEX> \
+    --session ~/.hypecli/multisig/<HASH>.json
+
+  Multi-sig commands save the proposal and every collected signature to the
+  session file printed at startup. Resume with the same lead signer; the chain
+  and nonce come from the file. The file is removed once the action succeeds.
+
+Verify an Action Signature (no authentication required):
+  hypecli verify-action \
+    --chain mainnet \
+    --payload action.json \
+    [--signature <HEX>] \
+    [--signer <EXPECTED_ADDRESS>]
+
+  The payload is an /exchange request body ({"action", "nonce", ...}). Prints the
+  signing hash and the recovered signer, plus the signer of every inner signature
+  for multi-sig actions. Fails if --signer doesn't match.
+
 GOSSIP PRIORITY AUCTION COMMANDS
 --------------------------------
 
@@ -579,6 +759,34 @@ Withdraw USDC from a vault:
     --vault <ADDRESS>    Vault address to deposit into or withdraw from
     --amount <DECIMAL>   Amount of USDC
 
+STAKING COMMANDS
+----------------
+
+Move HYPE from spot to the staking balance:
+  hypecli staking deposit \
+    --chain mainnet \
+    --private-key <HEX> \
+    --amount 100
+
+Move HYPE from the staking balance back to spot (7-day queue):
+  hypecli staking withdraw \
+    --chain mainnet \
+    --private-key <HEX> \
+    --amount 100
+
+Delegate or undelegate staked HYPE:
+  hypecli staking delegate \
+    --chain mainnet \
+    --private-key <HEX> \
+    --validator <VALIDATOR_ADDRESS> \
+    --amount 100
+
+  hypecli staking undelegate [same arguments]
+
+  Arguments:
+    --validator <ADDRESS>  Validator address
+    --amount <DECIMAL>     Amount of HYPE (up to 8 decimals)
+
 SUBSCRIBE COMMANDS (Real-time WebSocket Data)
 ---------------------------------------------
 
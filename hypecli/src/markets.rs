@@ -38,7 +38,7 @@ pub struct PerpsCmd {
 
 impl PerpsCmd {
     pub async fn run(self) -> anyhow::Result<()> {
-        let core = hypercore::mainnet();
+        let core = crate::utils::http_client(hypercore::Chain::Mainnet);
 
         let perps = if let Some(dex_name) = &self.dex {
             let dexes = core.perp_dexes().await?;
@@ -102,7 +102,7 @@ pub struct DexesCmd;
 
 impl DexesCmd {
     pub async fn run(self) -> anyhow::Result<()> {
-        let core = hypercore::mainnet();
+        let core = crate::utils::http_client(hypercore::Chain::Mainnet);
         let dexes = core.perp_dexes().await?;
 
         println!("name");
@@ -138,7 +138,7 @@ pub struct SpotCmd;
 
 impl SpotCmd {
     pub async fn run(self) -> anyhow::Result<()> {
-        let core = hypercore::mainnet();
+        let core = crate::utils::http_client(hypercore::Chain::Mainnet);
         let markets = core.spot().await?;
         let mut writer = tabwriter::TabWriter::new(stdout());
 
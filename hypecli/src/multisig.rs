@@ -1,35 +1,37 @@
 //! Multi-signature transaction commands for hypecli.
 //!
 //! Supports sending assets, USD transfers, and spot transfers through a multisig wallet
-//! using P2P peer coordination via iroh.
+//! using P2P peer coordination via iroh (see `hypersdk-multisig-p2p`), or copy-paste blobs and QR codes with
+//! `--offline` when signers can't reach each other.
 
 use std::{
+    collections::{BTreeMap, BTreeSet},
+    env::home_dir,
     io::{Write, stdout},
+    path::{Path, PathBuf},
     time::Duration,
 };
 
 use alloy::signers::Signer;
+use anyhow::Context;
 use clap::{Args, Subcommand};
-use futures::{SinkExt, StreamExt};
 use hypersdk::{
     Address, Decimal,
     hypercore::{
-        self, AssetTarget, HttpClient, NonceHandler, SendAsset, SendToken, Signature,
-        api::{
-            self, Action, ConvertToMultiSigUser, MultiSigAction, MultiSigPayload, SignersConfig,
-        },
+        self, AssetTarget, Chain, HttpClient, NonceHandler, SendAsset, SendToken, Signature,
+        api::{self, Action, ConvertToMultiSigUser, SignersConfig},
+        multisig::{self, Proposal},
     },
 };
+use hypersdk_multisig_p2p::{self as p2p, Collector};
 use indicatif::{ProgressBar, ProgressStyle};
-use iroh::{endpoint::Connection, protocol::Router};
 use iroh_tickets::endpoint::EndpointTicket;
+use qrcode::{QrCode, render::unicode};
 use serde::{Deserialize, Serialize};
 use tokio::{
-    io::{AsyncReadExt, stdin},
+    io::{AsyncBufReadExt, AsyncReadExt, BufReader, stdin},
     signal::ctrl_c,
-    sync::mpsc::unbounded_channel,
 };
-use tokio_util::codec::{FramedRead, FramedWrite};
 
 use crate::{
     SignerArgs,
@@ -46,6 +48,7 @@ pub enum MultiSigCmd {
     Update(UpdateMultiSigCmd),
     SendAsset(MultiSigSendAsset),
     ConvertToNormalUser(MultiSigConvertToNormalUser),
+    Resume(MultiSigResume),
 }
 
 impl MultiSigCmd {
@@ -55,6 +58,7 @@ impl MultiSigCmd {
             MultiSigCmd::SendAsset(cmd) => cmd.run().await,
             MultiSigCmd::ConvertToNormalUser(cmd) => cmd.run().await,
             MultiSigCmd::Update(cmd) => cmd.run().await,
+            MultiSigCmd::Resume(cmd) => cmd.run().await,
         }
     }
 }
@@ -87,9 +91,8 @@ pub struct MultiSigSendAsset {
     /// Destination DEX. Can be "spot" or a dex name.
     #[arg(long)]
     pub dest: Option<String>,
-    /// Sign and submit using only local signers, without starting P2P gossip.
-    #[arg(long)]
-    pub local: bool,
+    #[command(flatten)]
+    pub collect: CollectArgs,
 }
 
 impl MultiSigSendAsset {
@@ -109,8 +112,15 @@ pub struct MultiSigSign {
     #[command(flatten)]
     pub common: SignerArgs,
     /// Endpoint ticket to connect to the transaction initiator.
-    #[arg(long)]
-    pub connect: EndpointTicket,
+    #[arg(long, required_unless_present = "proposal")]
+    pub connect: Option<EndpointTicket>,
+    /// Proposal blob printed by an initiator running with --offline. Signatures are
+    /// printed instead of sent back over P2P.
+    #[arg(long, conflicts_with = "connect")]
+    pub proposal: Option<String>,
+    /// With --proposal, also print signatures as QR codes.
+    #[arg(long, requires = "proposal")]
+    pub qr: bool,
     /// Multi-sig wallet address.
     #[arg(long)]
     pub multi_sig_addr: Address,
@@ -134,9 +144,8 @@ pub struct MultiSigConvertToNormalUser {
     /// Multi-sig wallet address.
     #[arg(long)]
     pub multi_sig_addr: Address,
-    /// Sign and submit using only local signers, without starting P2P gossip.
-    #[arg(long)]
-    pub local: bool,
+    #[command(flatten)]
+    pub collect: CollectArgs,
 }
 
 impl MultiSigConvertToNormalUser {
@@ -164,9 +173,8 @@ pub struct UpdateMultiSigCmd {
     #[arg(long)]
     multi_sig_addr: Address,
 
-    /// Sign and submit using only local signers, without starting P2P gossip.
-    #[arg(long)]
-    local: bool,
+    #[command(flatten)]
+    collect: CollectArgs,
 }
 
 impl UpdateMultiSigCmd {
@@ -175,6 +183,43 @@ impl UpdateMultiSigCmd {
     }
 }
 
+/// Resume collecting signatures for an interrupted multi-sig action.
+///
+/// Every multi-sig command saves its proposal and the signatures collected so far
+/// under `~/.hypecli/multisig/`. The chain and nonce are taken from the session file;
+/// the lead signer must be the one that started the session.
+#[derive(Args, derive_more::Deref)]
+pub struct MultiSigResume {
+    #[deref]
+    #[command(flatten)]
+    pub common: SignerArgs,
+    /// Session file printed when the action was proposed.
+    #[arg(long)]
+    pub session: PathBuf,
+    #[command(flatten)]
+    pub collect: CollectArgs,
+}
+
+impl MultiSigResume {
+    pub async fn run(self) -> anyhow::Result<()> {
+        resume(self).await
+    }
+}
+
+/// How signatures from other signers are collected.
+#[derive(Args, Clone, Copy)]
+pub struct CollectArgs {
+    /// Sign and submit using only local signers, without starting P2P gossip.
+    #[arg(long, conflicts_with = "offline")]
+    pub local: bool,
+    /// Exchange the proposal and signatures as copy-paste blobs instead of P2P gossip.
+    #[arg(long)]
+    pub offline: bool,
+    /// With --offline, also print the proposal as a QR code.
+    #[arg(long, requires = "offline")]
+    pub qr: bool,
+}
+
 /// Animation strings for the connecting spinner.
 const CONNECTING_STRINGS: &[&str] = &[
     "Connecting",
@@ -190,7 +235,7 @@ const CONNECTING_STRINGS: &[&str] = &[
 ];
 
 async fn send_asset(cmd: MultiSigSendAsset) -> anyhow::Result<()> {
-    let hl = HttpClient::new(cmd.chain);
+    let hl = crate::utils::http_client(cmd.chain);
     let multisig_config = hl.multi_sig_config(cmd.multi_sig_addr).await?;
     println!("Can sign with:");
     for signer in &multisig_config.authorized_users {
@@ -202,7 +247,7 @@ async fn send_asset(cmd: MultiSigSendAsset) -> anyhow::Result<()> {
         println!("Using signer {}", s.address());
     }
 
-    let tokens = hypercore::mainnet().spot_tokens().await?;
+    let tokens = crate::utils::http_client(hypercore::Chain::Mainnet).spot_tokens().await?;
     let token = tokens
         .iter()
         .find(|token| token.name.eq_ignore_ascii_case(&cmd.token))
@@ -240,13 +285,13 @@ async fn send_asset(cmd: MultiSigSendAsset) -> anyhow::Result<()> {
         Action::from(send_action),
         nonce,
         &multisig_config,
-        cmd.local,
+        cmd.collect,
     )
     .await
 }
 
 async fn update(cmd: UpdateMultiSigCmd) -> anyhow::Result<()> {
-    let hl = HttpClient::new(cmd.chain);
+    let hl = crate::utils::http_client(cmd.chain);
     let multisig_config = hl.multi_sig_config(cmd.multi_sig_addr).await?;
     let signers = find_signers(&cmd.common, &multisig_config.authorized_users).await?;
 
@@ -274,13 +319,13 @@ async fn update(cmd: UpdateMultiSigCmd) -> anyhow::Result<()> {
         action,
         nonce,
         &multisig_config,
-        cmd.local,
+        cmd.collect,
     )
     .await
 }
 
 async fn convert_to_normal_user(cmd: MultiSigConvertToNormalUser) -> anyhow::Result<()> {
-    let hl = HttpClient::new(cmd.chain);
+    let hl = crate::utils::http_client(cmd.chain);
     let multisig_config = hl.multi_sig_config(cmd.multi_sig_addr).await?;
     let signers = find_signers(&cmd.common, &multisig_config.authorized_users).await?;
 
@@ -311,22 +356,55 @@ async fn convert_to_normal_user(cmd: MultiSigConvertToNormalUser) -> anyhow::Res
         action,
         nonce,
         &multisig_config,
-        cmd.local,
+        cmd.collect,
     )
     .await
 }
 
+async fn resume(cmd: MultiSigResume) -> anyhow::Result<()> {
+    let session = Session::load(&cmd.session)?;
+    check_nonce(session.proposal.nonce)?;
+    let multi_sig_addr = session.proposal.multi_sig_user()?;
+    let hl = crate::utils::http_client(session.chain());
+    let multisig_config = hl.multi_sig_config(multi_sig_addr).await?;
+    let signers = find_signers(&cmd.common, &multisig_config.authorized_users).await?;
+
+    let lead = signers[0].address();
+    if session.proposal.lead()? != lead {
+        anyhow::bail!(
+            "session was started by {} but the lead signer is {lead}",
+            session.proposal.payload.outer_signer
+        );
+    }
+
+    println!(
+        "Resuming {multi_sig_addr} on {}: {}/{} signatures collected",
+        session.chain(),
+        session.signatures.len(),
+        multisig_config.threshold
+    );
+
+    run_session(session, cmd.session, hl, signers, &multisig_config, cmd.collect).await
+}
+
 async fn sign(cmd: MultiSigSign) -> anyhow::Result<()> {
-    let multisig_config = HttpClient::new(cmd.chain)
+    let multisig_config = crate::utils::http_client(cmd.chain)
         .multi_sig_config(cmd.multi_sig_addr)
         .await?;
     let signers = find_signers(&cmd.common, &multisig_config.authorized_users).await?;
-    let key = utils::make_key(&signers[0]);
 
     for s in &signers {
         println!("Signer found using {}", s.address());
     }
 
+    if let Some(blob) = &cmd.proposal {
+        return sign_offline(&cmd, blob, &signers).await;
+    }
+    let Some(ticket) = &cmd.connect else {
+        anyhow::bail!("either --connect or --proposal is required");
+    };
+    let key = utils::make_key(&signers[0]);
+
     let pb = ProgressBar::new_spinner();
     pb.enable_steady_tick(Duration::from_millis(100));
     pb.set_style(
@@ -337,73 +415,106 @@ async fn sign(cmd: MultiSigSign) -> anyhow::Result<()> {
 
     let (endpoint, _ticket) = utils::start_gossip(key, true).await?;
 
-    let addr = cmd.connect.endpoint_addr();
-    let conn = endpoint.connect(addr.clone(), proto::ALPN).await?;
-
     pb.finish_and_clear();
-
-    let (send, recv) = conn.open_bi().await?;
-
-    let mut read = FramedRead::new(recv, proto::Codec::default());
-    let mut write = FramedWrite::new(send, proto::Codec::default());
-
-    let _ = write.send(proto::Message::Hello).await;
-
-    match read.next().await {
-        Some(Ok(proto::Message::Action(nonce, action))) => {
-            println!("{:#?}", action);
-            print!("Accept (y/n)? ");
-            let _ = stdout().flush();
-            let mut input = [0u8; 1];
-            let _ = stdin().read_exact(&mut input).await;
-            if input[0] == b'y' {
-                let mut signed_addresses: Vec<Address> = Vec::new();
-                for signer in &signers {
-                    let signature = action.sign(signer, nonce, cmd.chain).await?;
-                    println!("Signed with {}", signer.address());
-                    signed_addresses.push(signer.address());
-                    write.send(proto::Message::Signature(signature)).await?;
-                }
-                loop {
-                    println!(
-                        "Swap hardware wallet and press Enter to scan, or any other key to finish."
-                    );
-                    let mut swap_input = [0u8; 1];
-                    let _ = stdin().read_exact(&mut swap_input).await;
-                    if swap_input[0] != b'\n' {
-                        break;
-                    }
-                    let new_signers = utils::scan_hw_signers(
-                        &multisig_config.authorized_users,
-                        &signed_addresses,
-                    )
+    println!("Sign the membership message with {}", signers[0].address());
+    let membership =
+        p2p::Membership::sign(signers[0].as_ref(), cmd.multi_sig_addr, endpoint.id()).await?;
+
+    let mut session = p2p::join(
+        &endpoint,
+        ticket.endpoint_addr(),
+        membership,
+        &multisig_config.authorized_users,
+        cmd.chain,
+    )
+    .await?;
+    let proposal = session.proposal().clone();
+    let mut nonces = SignedNonces::load()?;
+    check_proposal(&proposal, cmd.multi_sig_addr, &nonces)?;
+
+    println!("{:#?}", proposal.payload.action);
+    print!("Accept (y/n)? ");
+    let _ = stdout().flush();
+    let mut input = [0u8; 1];
+    let _ = stdin().read_exact(&mut input).await;
+    if input[0] == b'y' {
+        nonces.insert(cmd.multi_sig_addr, proposal.nonce)?;
+        let mut signed_addresses: Vec<Address> = Vec::new();
+        for signer in &signers {
+            let signature = proposal.sign(signer).await?;
+            println!("Signed with {}", signer.address());
+            signed_addresses.push(signer.address());
+            session.send(signature).await?;
+        }
+        loop {
+            println!("Swap hardware wallet and press Enter to scan, or any other key to finish.");
+            let mut swap_input = [0u8; 1];
+            let _ = stdin().read_exact(&mut swap_input).await;
+            if swap_input[0] != b'\n' {
+                break;
+            }
+            let new_signers =
+                utils::scan_hw_signers(&multisig_config.authorized_users, &signed_addresses)
                     .await;
-                    if new_signers.is_empty() {
-                        println!("No new hardware wallets found.");
-                        continue;
-                    }
-                    for signer in &new_signers {
-                        let signature = action.sign(signer, nonce, cmd.chain).await?;
-                        println!("Signed with {}", signer.address());
-                        signed_addresses.push(signer.address());
-                        write.send(proto::Message::Signature(signature)).await?;
-                    }
-                }
-            } else {
-                println!("Rejected");
+            if new_signers.is_empty() {
+                println!("No new hardware wallets found.");
+                continue;
+            }
+            for signer in &new_signers {
+                let signature = proposal.sign(signer).await?;
+                println!("Signed with {}", signer.address());
+                signed_addresses.push(signer.address());
+                session.send(signature).await?;
             }
         }
-        _ => {
-            panic!("unexpected message");
-        }
+    } else {
+        println!("Rejected");
     }
 
-    conn.closed().await;
+    session.closed().await;
     endpoint.close().await;
 
     Ok(())
 }
 
+/// Signs a proposal blob from an initiator running with `--offline` and prints the
+/// signatures to send back.
+async fn sign_offline(
+    cmd: &MultiSigSign,
+    blob: &str,
+    signers: &[Box<dyn Signer + Send + Sync>],
+) -> anyhow::Result<()> {
+    let proposal = Proposal::decode(blob)?;
+    if proposal.chain != cmd.chain {
+        anyhow::bail!("proposal is for {}, pass --chain {}", proposal.chain, proposal.chain);
+    }
+    let mut nonces = SignedNonces::load()?;
+    check_proposal(&proposal, cmd.multi_sig_addr, &nonces)?;
+
+    println!("Nonce: {}", proposal.nonce);
+    println!("{:#?}", proposal.payload.action);
+    print!("Accept (y/n)? ");
+    let _ = stdout().flush();
+    let mut input = [0u8; 1];
+    let _ = stdin().read_exact(&mut input).await;
+    if input[0] != b'y' {
+        println!("Rejected");
+        return Ok(());
+    }
+
+    nonces.insert(cmd.multi_sig_addr, proposal.nonce)?;
+    for signer in signers {
+        let signature = multisig::encode_signature(&proposal.sign(signer).await?);
+        println!("Signature from {}:\n{signature}", signer.address());
+        if cmd.qr {
+            print_qr(&signature)?;
+        }
+    }
+    println!("Send the signatures back to the initiator.");
+
+    Ok(())
+}
+
 /// Execute a multisig action by collecting signatures from authorized signers.
 ///
 /// This is the core multisig execution logic used by all multisig commands.
@@ -414,55 +525,81 @@ async fn execute_multisig_action(
     inner_action: Action,
     nonce: u64,
     multisig_config: &hypersdk::hypercore::MultiSigConfig,
-    local: bool,
+    collect: CollectArgs,
 ) -> anyhow::Result<()> {
-    let lead_signer = &signers[0];
-
-    let action = MultiSigPayload {
-        multi_sig_user: multi_sig_addr.to_string().to_lowercase(),
-        outer_signer: lead_signer.address().to_string().to_lowercase(),
-        action: Box::new(inner_action),
+    let session = Session {
+        chain: Some(hl.chain()),
+        proposal: Proposal::new(
+            hl.chain(),
+            multi_sig_addr,
+            signers[0].address(),
+            inner_action,
+            nonce,
+        ),
+        signatures: vec![],
     };
+    let path = session.default_path()?;
 
-    let mut signatures = vec![];
-    let mut signed_addresses: Vec<Address> = Vec::new();
+    run_session(session, path, hl, signers, multisig_config, collect).await
+}
+
+/// Collects the signatures a session is missing and submits the action.
+///
+/// The session is written to `path` after every new signature, so collection can be
+/// resumed with `hypecli multisig resume` if the process dies. The file is removed once
+/// the action is accepted.
+async fn run_session(
+    session: Session,
+    path: PathBuf,
+    hl: HttpClient,
+    signers: Vec<Box<dyn Signer + Send + Sync>>,
+    multisig_config: &hypersdk::hypercore::MultiSigConfig,
+    collect: CollectArgs,
+) -> anyhow::Result<()> {
+    let lead_signer = &signers[0];
+    // Signatures that are no longer valid are dropped.
+    let mut collector = Collector::new(session.proposal, multisig_config);
+    collector.extend(session.signatures);
 
     for signer in &signers {
-        if multisig_config.authorized_users.contains(&signer.address()) {
+        let address = signer.address();
+        if collector.is_authorized(address) && !collector.has_signed(address) {
             println!(
-                "Using local signer {} to sign message:\n{action:#?}",
-                signer.address()
+                "Using local signer {address} to sign message:\n{:#?}",
+                collector.proposal().payload
             );
-            signatures.push(action.sign(signer, nonce, hl.chain()).await?);
-            signed_addresses.push(signer.address());
+            let signature = collector.proposal().sign(signer).await?;
+            collector.add(signature)?;
+            Session::save(&collector, hl.chain(), &path)?;
         }
     }
 
-    if !local {
-        collect_remote_signatures(
-            &action,
-            &mut signatures,
-            &mut signed_addresses,
-            nonce,
-            &hl,
-            multi_sig_addr,
-            multisig_config,
-            lead_signer,
-        )
-        .await?;
-    } else if signatures.len() < multisig_config.threshold {
+    if !collect.local {
+        println!("Session saved to {}", path.display());
+        if collect.offline {
+            collect_offline_signatures(&mut collector, hl.chain(), &path, collect.qr).await?;
+        } else {
+            collect_remote_signatures(&mut collector, &path, &hl, lead_signer.as_ref()).await?;
+        }
+        if !collector.is_complete() {
+            println!(
+                "Collected {}/{} signatures. Continue with:\nhypecli multisig resume --session {}",
+                collector.signatures().len(),
+                collector.threshold(),
+                path.display()
+            );
+            return Ok(());
+        }
+    } else if !collector.is_complete() {
         anyhow::bail!(
             "not enough local signers: have {} but need {}",
-            signatures.len(),
-            multisig_config.threshold
+            collector.signatures().len(),
+            collector.threshold()
         );
     }
 
-    let multi_sig_action = MultiSigAction {
-        signature_chain_id: hl.chain().arbitrum_id().to_owned(),
-        signatures,
-        payload: action,
-    };
+    let nonce = collector.proposal().nonce;
+    let multi_sig_action = collector.into_action();
 
     let req = hypercore::signing::multisig_lead_msg(
         lead_signer,
@@ -477,6 +614,7 @@ async fn execute_multisig_action(
     match hl.send(req).await? {
         api::Response::Ok(_) => {
             println!("Success");
+            let _ = std::fs::remove_file(&path);
         }
         api::Response::Err(err) => {
             println!("error: {err}");
@@ -487,16 +625,13 @@ async fn execute_multisig_action(
 }
 
 async fn collect_remote_signatures(
-    action: &MultiSigPayload,
-    signatures: &mut Vec<Signature>,
-    signed_addresses: &mut Vec<Address>,
-    nonce: u64,
+    collector: &mut Collector,
+    path: &Path,
     hl: &HttpClient,
-    multi_sig_addr: Address,
-    multisig_config: &hypersdk::hypercore::MultiSigConfig,
     lead_signer: &(dyn Signer + Send + Sync),
 ) -> anyhow::Result<()> {
     let key = utils::make_key(lead_signer);
+    let multi_sig_addr = collector.proposal().multi_sig_user()?;
 
     let pb = ProgressBar::new_spinner();
     pb.enable_steady_tick(Duration::from_millis(100));
@@ -509,12 +644,13 @@ async fn collect_remote_signatures(
     let (endpoint, ticket) = utils::start_gossip(key, true).await?;
 
     pb.finish_and_clear();
+    let membership = p2p::Membership::sign(lead_signer, multi_sig_addr, endpoint.id()).await?;
 
-    let pb = ProgressBar::new(multisig_config.threshold as u64);
+    let pb = ProgressBar::new(collector.threshold() as u64);
     pb.set_style(ProgressStyle::with_template("{msg}\nAuthorized {pos}/{len}").unwrap());
-    pb.set_position(signatures.len() as u64);
+    pb.set_position(collector.signatures().len() as u64);
 
-    while signatures.len() < multisig_config.threshold {
+    while !collector.is_complete() {
         println!(
             "Swap hardware wallet and press Enter to scan, or any other key to wait for P2P peers."
         );
@@ -524,56 +660,60 @@ async fn collect_remote_signatures(
             break;
         }
         let new_signers =
-            utils::scan_hw_signers(&multisig_config.authorized_users, signed_addresses).await;
+            utils::scan_hw_signers(collector.authorized_users(), collector.signed()).await;
         if new_signers.is_empty() {
             println!("No new hardware wallets found.");
             continue;
         }
         for signer in &new_signers {
             println!("Found new signer {}", signer.address());
-            signatures.push(action.sign(signer, nonce, hl.chain()).await?);
-            signed_addresses.push(signer.address());
+            let signature = collector.proposal().sign(signer).await?;
+            collector.add(signature)?;
+            Session::save(collector, hl.chain(), path)?;
             pb.inc(1);
         }
     }
 
-    let (tx, mut rx) = unbounded_channel();
-    let router = Router::builder(endpoint)
-        .accept(
-            proto::ALPN,
-            proto::Serve((nonce, action.clone(), tx.clone())),
-        )
-        .spawn();
+    let config = hypersdk::hypercore::MultiSigConfig {
+        authorized_users: collector.authorized_users().to_vec(),
+        threshold: collector.threshold(),
+    };
+    let (router, mut events) = p2p::serve(endpoint, collector.proposal(), membership, &config);
 
     let mut msgs = String::new();
 
     use std::fmt::Write;
 
-    while signatures.len() < multisig_config.threshold {
+    while !collector.is_complete() {
         pb.set_message(format!(
             "Authorized users: {:?}\n{msgs}\nhypecli multisig sign --multi-sig-addr {} --chain {} --connect {}",
-            multisig_config.authorized_users, multi_sig_addr, hl.chain(), ticket
+            config.authorized_users, multi_sig_addr, hl.chain(), ticket
         ));
 
         tokio::select! {
             _ = ctrl_c() => {
+                pb.finish_and_clear();
                 router.shutdown().await?;
                 return Ok(());
             }
-            Some(signature) = rx.recv() => {
-                writeln!(&mut msgs, "> Receive signature {signature}")?;
-                match action.recover(&signature, nonce, hl.chain()) {
-                    Ok(address) => {
-                        if !multisig_config.authorized_users.contains(&address) {
-                            writeln!(&mut msgs, ">X Received signature from unauthorized user {address}")?;
-                        } else {
+            Some(event) = events.recv() => match event {
+                p2p::Event::Joined { member, .. } => {
+                    writeln!(&mut msgs, "> {member} joined")?;
+                }
+                p2p::Event::Rejected { peer, reason } => {
+                    writeln!(&mut msgs, ">X Rejected peer {peer}: {reason}")?;
+                }
+                p2p::Event::Signature(signature) => {
+                    writeln!(&mut msgs, "> Receive signature {signature}")?;
+                    match collector.add(signature) {
+                        Ok(_) => {
                             pb.inc(1);
                             writeln!(&mut msgs, "> Received: {signature}")?;
-                            signatures.push(signature);
+                            Session::save(collector, hl.chain(), path)?;
+                        }
+                        Err(err) => {
+                            writeln!(&mut msgs, ">X {err}")?;
                         }
-                    }
-                    Err(err) => {
-                        let _ = writeln!(&mut msgs, ">X unable to verify signature: {err}");
                     }
                 }
             }
@@ -586,90 +726,260 @@ async fn collect_remote_signatures(
     Ok(())
 }
 
-mod proto {
-    use super::*;
-    use bytes::{Bytes, BytesMut};
-    use futures::SinkExt;
-    use iroh::protocol::ProtocolHandler;
-    use tokio::sync::mpsc::UnboundedSender;
-    use tokio_util::codec::{self, LengthDelimitedCodec};
-
-    pub const ALPN: &[u8] = b"/hypersdk-multisig/0";
-
-    /// Messages exchanged over the gossip network during multi-sig coordination.
-    #[derive(Serialize, Deserialize)]
-    pub enum Message {
-        /// We need to write something when opening the connection
-        ///
-        /// https://docs.rs/iroh/latest/iroh/endpoint/struct.Connection.html#method.accept_bi
-        Hello,
-        /// A proposed action with its nonce that needs to be signed.
-        Action(u64, MultiSigPayload),
-        /// A signature from an authorized signer.
-        Signature(Signature),
-    }
-
-    #[derive(Default)]
-    pub struct Codec {
-        inner: LengthDelimitedCodec,
-    }
-
-    impl codec::Decoder for Codec {
-        type Item = Message;
-        type Error = anyhow::Error;
-
-        fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
-            let payload = match self.inner.decode(src)? {
-                Some(data) => data,
-                None => {
-                    return Ok(None);
-                }
-            };
+/// Collects signatures pasted into the terminal, for signers that can't join the P2P
+/// network.
+///
+/// The proposal is printed as a base64 blob for the other signers to sign with
+/// `hypecli multisig sign --proposal`. Stops at the threshold or on an empty line.
+async fn collect_offline_signatures(
+    collector: &mut Collector,
+    chain: Chain,
+    path: &Path,
+    qr: bool,
+) -> anyhow::Result<()> {
+    let proposal = collector.proposal();
+    let blob = proposal.encode()?;
+    println!("Proposal:\n{blob}");
+    if qr {
+        print_qr(&blob)?;
+    }
+    println!(
+        "Each signer runs:\nhypecli multisig sign --multi-sig-addr {} --chain {} --proposal <PROPOSAL>",
+        proposal.payload.multi_sig_user, proposal.chain
+    );
 
-            let msg = rmp_serde::from_slice(&payload)?;
-            Ok(Some(msg))
+    let mut lines = BufReader::new(stdin()).lines();
+    while !collector.is_complete() {
+        println!(
+            "Paste a signature ({}/{} collected), or an empty line to stop:",
+            collector.signatures().len(),
+            collector.threshold()
+        );
+        let Some(line) = lines.next_line().await? else {
+            break;
+        };
+        if line.trim().is_empty() {
+            break;
+        }
+        let signature = match multisig::decode_signature(&line) {
+            Ok(signature) => signature,
+            Err(err) => {
+                println!("X {err:#}");
+                continue;
+            }
+        };
+        match collector.add(signature) {
+            Ok(address) => {
+                println!("> Signed by {address}");
+                Session::save(collector, chain, path)?;
+            }
+            Err(err) => println!("X {err}"),
         }
     }
 
-    impl codec::Encoder<Message> for Codec {
-        type Error = anyhow::Error;
+    Ok(())
+}
+
+/// Prints `data` as a QR code made of Unicode half blocks.
+fn print_qr(data: &str) -> anyhow::Result<()> {
+    let code = QrCode::new(data.as_bytes())?;
+    let image = code
+        .render::<unicode::Dense1x2>()
+        .dark_color(unicode::Dense1x2::Light)
+        .light_color(unicode::Dense1x2::Dark)
+        .build();
+    println!("{image}");
+    Ok(())
+}
+
+/// The exchange rejects nonces more than two days behind its clock.
+const NONCE_MAX_AGE: u64 = 2 * 24 * 60 * 60 * 1000;
+
+/// The exchange rejects nonces more than a day ahead of its clock.
+const NONCE_MAX_AHEAD: u64 = 24 * 60 * 60 * 1000;
+
+/// `~/.hypecli/multisig`, where sessions and signed nonces are kept.
+fn state_dir() -> anyhow::Result<PathBuf> {
+    let home = home_dir().ok_or_else(|| anyhow::anyhow!("unable to locate home dir"))?;
+    Ok(home.join(".hypecli").join("multisig"))
+}
 
-        fn encode(&mut self, item: Message, dst: &mut BytesMut) -> Result<(), Self::Error> {
-            let msg = rmp_serde::to_vec(&item)?;
-            self.inner.encode(Bytes::from(msg), dst)?;
-            Ok(())
+/// Fails if the exchange would reject `nonce` as stale or too far in the future.
+fn check_nonce(nonce: u64) -> anyhow::Result<()> {
+    let now = chrono::Utc::now().timestamp_millis() as u64;
+    if nonce.saturating_add(NONCE_MAX_AGE) < now {
+        anyhow::bail!("nonce {nonce} has expired");
+    }
+    if nonce > now.saturating_add(NONCE_MAX_AHEAD) {
+        anyhow::bail!("nonce {nonce} is too far in the future");
+    }
+    Ok(())
+}
+
+/// Refuses proposals for another multi-sig user, with a nonce the exchange would
+/// reject, or with a nonce this machine already signed for the multi-sig.
+fn check_proposal(
+    proposal: &Proposal,
+    multi_sig_addr: Address,
+    nonces: &SignedNonces,
+) -> anyhow::Result<()> {
+    if proposal.multi_sig_user()? != multi_sig_addr {
+        anyhow::bail!(
+            "proposal is for multi-sig user {}, not {multi_sig_addr}",
+            proposal.payload.multi_sig_user
+        );
+    }
+    check_nonce(proposal.nonce)?;
+    if nonces.contains(multi_sig_addr, proposal.nonce) {
+        anyhow::bail!("already signed a proposal with nonce {}", proposal.nonce);
+    }
+    Ok(())
+}
+
+/// Nonces signed on this machine per multi-sig user, to refuse replayed proposals.
+///
+/// Stored in `~/.hypecli/multisig/signed-nonces.json`. Nonces old enough for the
+/// exchange to reject are pruned on save.
+#[derive(Default, Serialize, Deserialize)]
+struct SignedNonces(BTreeMap<Address, BTreeSet<u64>>);
+
+impl SignedNonces {
+    fn path() -> anyhow::Result<PathBuf> {
+        Ok(state_dir()?.join("signed-nonces.json"))
+    }
+
+    fn load() -> anyhow::Result<Self> {
+        let path = Self::path()?;
+        match std::fs::read_to_string(&path) {
+            Ok(text) => serde_json::from_str(&text)
+                .with_context(|| format!("parsing {}", path.display())),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err.into()),
         }
     }
 
-    #[derive(Debug, Clone)]
-    pub struct Serve(pub (u64, MultiSigPayload, UnboundedSender<Signature>));
+    fn contains(&self, multi_sig_user: Address, nonce: u64) -> bool {
+        self.0
+            .get(&multi_sig_user)
+            .is_some_and(|nonces| nonces.contains(&nonce))
+    }
+
+    /// Records `nonce` and saves the file.
+    fn insert(&mut self, multi_sig_user: Address, nonce: u64) -> anyhow::Result<()> {
+        self.0.entry(multi_sig_user).or_default().insert(nonce);
 
-    impl ProtocolHandler for Serve {
-        fn accept(
-            &self,
-            connection: Connection,
-        ) -> impl Future<Output = Result<(), iroh::protocol::AcceptError>> + Send {
-            let (nonce, action, tx) = self.clone().0;
-            async move {
-                let (send, recv) = connection.accept_bi().await?;
+        let oldest = (chrono::Utc::now().timestamp_millis() as u64).saturating_sub(NONCE_MAX_AGE);
+        for nonces in self.0.values_mut() {
+            nonces.retain(|&nonce| nonce >= oldest);
+        }
+        self.0.retain(|_, nonces| !nonces.is_empty());
 
-                let mut read = FramedRead::new(recv, proto::Codec::default());
-                let mut write = FramedWrite::new(send, proto::Codec::default());
+        let path = Self::path()?;
+        std::fs::create_dir_all(state_dir()?)?;
+        std::fs::write(&path, serde_json::to_vec_pretty(self)?)?;
+        Ok(())
+    }
+}
 
-                let _ = write.send(Message::Action(nonce, action)).await;
-                loop {
-                    match read.next().await {
-                        Some(Ok(Message::Signature(sig))) => {
-                            let _ = tx.send(sig);
-                        }
-                        Some(Ok(Message::Hello)) => {}
-                        None => break Ok(()),
-                        _ => {
-                            println!("received unexpected msg");
-                        }
-                    }
-                }
-            }
+/// A multi-sig proposal and the signatures collected for it so far.
+///
+/// Stored as JSON under `~/.hypecli/multisig/`, named after the hash the signers sign.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Session {
+    /// Chain the session submits to, by name: the proposal stores `Local` as the
+    /// `Testnet` it signs as. Absent in sessions saved before it was added.
+    #[serde(default, with = "chain_name")]
+    chain: Option<Chain>,
+    proposal: Proposal,
+    signatures: Vec<Signature>,
+}
+
+/// (De)serializes a chain by its display name, which keeps `Local` distinct.
+mod chain_name {
+    use hypersdk::hypercore::Chain;
+    use serde::{Deserialize, Deserializer, Serializer, de::Error};
+
+    pub fn serialize<S: Serializer>(chain: &Option<Chain>, ser: S) -> Result<S::Ok, S::Error> {
+        match chain {
+            Some(chain) => ser.collect_str(chain),
+            None => ser.serialize_none(),
         }
     }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(de: D) -> Result<Option<Chain>, D::Error> {
+        Option::<String>::deserialize(de)?
+            .map(|name| name.parse().map_err(D::Error::custom))
+            .transpose()
+    }
+}
+
+impl Session {
+    /// The chain to resume on.
+    fn chain(&self) -> Chain {
+        self.chain.unwrap_or(self.proposal.chain)
+    }
+
+    fn load(path: &Path) -> anyhow::Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("reading session {}", path.display()))?;
+        serde_json::from_str(&text).with_context(|| format!("parsing session {}", path.display()))
+    }
+
+    /// Writes `collector`'s proposal and signatures for `chain` to `path`, replacing
+    /// the previous copy atomically.
+    fn save(collector: &Collector, chain: Chain, path: &Path) -> anyhow::Result<()> {
+        let session = Session {
+            chain: Some(chain),
+            proposal: collector.proposal().clone(),
+            signatures: collector.signatures().to_vec(),
+        };
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_vec_pretty(&session)?)?;
+        std::fs::rename(&tmp, path)?;
+        Ok(())
+    }
+
+    /// `~/.hypecli/multisig/<hash>.json`
+    fn default_path(&self) -> anyhow::Result<PathBuf> {
+        let hash = self.proposal.hash()?;
+        Ok(state_dir()?.join(format!("{hash}.json")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_nonce_bounds() {
+        let now = chrono::Utc::now().timestamp_millis() as u64;
+        assert!(check_nonce(now).is_ok());
+        assert!(check_nonce(now - NONCE_MAX_AGE - 60_000).is_err());
+        assert!(check_nonce(now + NONCE_MAX_AHEAD + 60_000).is_err());
+        // A forged nonce must not overflow into looking fresh.
+        assert!(check_nonce(u64::MAX).is_err());
+    }
+
+    #[test]
+    fn session_keeps_local_chain() {
+        let proposal = Proposal::new(Chain::Local, Address::ZERO, Address::ZERO, Action::Noop, 1);
+        let config = hypercore::MultiSigConfig {
+            authorized_users: vec![],
+            threshold: 1,
+        };
+        let collector = Collector::new(proposal, &config);
+        let name = format!("hypecli-session-{}.json", std::process::id());
+        let path = std::env::temp_dir().join(name);
+
+        Session::save(&collector, Chain::Local, &path).unwrap();
+        let session = Session::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(session.chain(), Chain::Local);
+        // The proposal itself signs as testnet.
+        assert_eq!(session.proposal.chain, Chain::Testnet);
+    }
 }
@@ -15,8 +15,8 @@
 use alloy::primitives::B128;
 use clap::{Args, Subcommand, ValueEnum};
 use hypersdk::hypercore::{
-    BatchCancel, BatchCancelCloid, BatchOrder, Cancel, CancelByCloid, Cloid, HttpClient,
-    OrderGrouping, OrderRequest, OrderTypePlacement, TimeInForce,
+    BatchCancel, BatchCancelCloid, BatchOrder, Cancel, CancelByCloid, Cloid, OrderGrouping,
+    OrderRequest, OrderTypePlacement, TimeInForce, registry::Purpose,
 };
 use rust_decimal::Decimal;
 
@@ -122,8 +122,8 @@ pub struct LimitOrderCmd {
 
 impl LimitOrderCmd {
     pub async fn run(self) -> anyhow::Result<()> {
-        let client = HttpClient::new(self.chain);
-        let signer = find_signer_sync(&self.signer)?;
+        let client = crate::utils::http_client(self.chain);
+        let signer = find_signer_sync(&self.signer, Purpose::Trading)?;
 
         let asset_index = resolve_asset(&client, &self.asset).await?;
 
@@ -214,8 +214,8 @@ pub struct MarketOrderCmd {
 
 impl MarketOrderCmd {
     pub async fn run(self) -> anyhow::Result<()> {
-        let client = HttpClient::new(self.chain);
-        let signer = find_signer_sync(&self.signer)?;
+        let client = crate::utils::http_client(self.chain);
+        let signer = find_signer_sync(&self.signer, Purpose::Trading)?;
 
         let asset_index = resolve_asset(&client, &self.asset).await?;
 
@@ -304,8 +304,8 @@ impl CancelOrderCmd {
             _ => {}
         }
 
-        let client = HttpClient::new(self.chain);
-        let signer = find_signer_sync(&self.signer)?;
+        let client = crate::utils::http_client(self.chain);
+        let signer = find_signer_sync(&self.signer, Purpose::Trading)?;
 
         let asset_index = resolve_asset(&client, &self.asset).await?;
 
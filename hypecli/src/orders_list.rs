@@ -102,7 +102,7 @@ pub struct ListOrdersCmd {
 
 impl ListOrdersCmd {
     pub async fn run(self) -> anyhow::Result<()> {
-        let client = hypercore::HttpClient::new(hypersdk::hypercore::Chain::Mainnet);
+        let client = crate::utils::http_client(hypercore::Chain::Mainnet);
 
         let orders = client.historical_orders(self.user).await?;
 
@@ -249,7 +249,7 @@ pub struct FillsCmd {
 
 impl FillsCmd {
     pub async fn run(self) -> anyhow::Result<()> {
-        let client = hypercore::HttpClient::new(hypersdk::hypercore::Chain::Mainnet);
+        let client = crate::utils::http_client(hypercore::Chain::Mainnet);
 
         let fills = client.user_fills(self.user).await?;
 
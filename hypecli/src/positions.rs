@@ -87,7 +87,7 @@ pub struct PositionsCmd {
 
 impl PositionsCmd {
     pub async fn run(self) -> anyhow::Result<()> {
-        let client = hypercore::HttpClient::new(hypersdk::hypercore::Chain::Mainnet);
+        let client = crate::utils::http_client(hypercore::Chain::Mainnet);
 
         let state = client
             .clearinghouse_state(self.user, self.dex.clone())
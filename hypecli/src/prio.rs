@@ -25,7 +25,7 @@
 
 use clap::{Args, Subcommand};
 use hypersdk::hypercore::types::{OkResponse, Response};
-use hypersdk::hypercore::{Chain, HttpClient, NonceHandler};
+use hypersdk::hypercore::{Chain, NonceHandler, registry::Purpose};
 use rust_decimal::Decimal;
 use rust_decimal::prelude::FromPrimitive;
 
@@ -80,7 +80,7 @@ impl StatusCmd {
     /// `currentGas == null` → settled, cannot bid this cycle.
     /// Prints `started <timestamp>` at the top so you know when the cycle began.
     pub async fn run(self) -> anyhow::Result<()> {
-        let client = HttpClient::new(self.chain);
+        let client = crate::utils::http_client(self.chain);
         let status = client.gossip_priority_auction_status().await?;
 
         if let Some(first) = status.first() {
@@ -142,8 +142,8 @@ impl BidCmd {
     /// You pay the live `currentGas` price at TX mining time — not `--max`.
     /// The difference is refunded automatically. Winning amount is burned.
     pub async fn run(self) -> anyhow::Result<()> {
-        let signer = find_signer_sync(&self.signer)?;
-        let client = HttpClient::new(self.chain);
+        let signer = find_signer_sync(&self.signer, Purpose::Trading)?;
+        let client = crate::utils::http_client(self.chain);
 
         let decimals = client
             .spot_tokens()
@@ -7,7 +7,7 @@ use alloy::primitives::Address;
 use clap::Args;
 use hypersdk::{
     Decimal,
-    hypercore::{self, AssetTarget, HttpClient, NonceHandler, SendAsset, SendToken},
+    hypercore::{self, AssetTarget, NonceHandler, SendAsset, SendToken, registry::Purpose},
 };
 
 use crate::SignerArgs;
@@ -75,11 +75,11 @@ pub struct SendCmd {
 
 impl SendCmd {
     pub async fn run(self) -> anyhow::Result<()> {
-        let signer = find_signer_sync(&self.signer)?;
-        let client = HttpClient::new(self.chain);
+        let signer = find_signer_sync(&self.signer, Purpose::Withdrawals)?;
+        let client = crate::utils::http_client(self.chain);
 
         // Find the token
-        let tokens = hypercore::mainnet().spot_tokens().await?;
+        let tokens = crate::utils::http_client(hypercore::Chain::Mainnet).spot_tokens().await?;
         let token = tokens
             .iter()
             .find(|t| t.name.eq_ignore_ascii_case(&self.token))
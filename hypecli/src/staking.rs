@@ -0,0 +1,102 @@
+//! HYPE staking commands.
+//!
+//! This module provides commands for moving HYPE between the spot and
+//! staking balances and for delegating stake to validators.
+
+use alloy::primitives::Address;
+use clap::{Args, Subcommand};
+use hypersdk::{
+    Decimal,
+    hypercore::{NonceHandler, registry::Purpose},
+};
+
+use crate::{SignerArgs, utils::find_signer_sync};
+
+/// HYPE staking commands.
+#[derive(Subcommand)]
+pub enum StakingCmd {
+    /// Move HYPE from the spot balance to the staking balance
+    Deposit(StakingTransferCmd),
+    /// Move HYPE from the staking balance to the spot balance (7-day queue)
+    Withdraw(StakingTransferCmd),
+    /// Delegate staked HYPE to a validator
+    Delegate(DelegateCmd),
+    /// Undelegate HYPE from a validator
+    Undelegate(DelegateCmd),
+}
+
+impl StakingCmd {
+    pub async fn run(self) -> anyhow::Result<()> {
+        match self {
+            StakingCmd::Deposit(cmd) => execute_transfer(cmd, true).await,
+            StakingCmd::Withdraw(cmd) => execute_transfer(cmd, false).await,
+            StakingCmd::Delegate(cmd) => execute_delegate(cmd, false).await,
+            StakingCmd::Undelegate(cmd) => execute_delegate(cmd, true).await,
+        }
+    }
+}
+
+async fn execute_transfer(cmd: StakingTransferCmd, is_deposit: bool) -> anyhow::Result<()> {
+    let (verb, direction) = if is_deposit {
+        ("Staking", "spot -> staking")
+    } else {
+        ("Unstaking", "staking -> spot")
+    };
+    let signer = find_signer_sync(&cmd.signer, Purpose::Withdrawals)?;
+    let client = crate::utils::http_client(cmd.signer.chain);
+    let nonce = NonceHandler::default().next();
+    println!("{} {} HYPE ({})", verb, cmd.amount, direction);
+    client
+        .staking_transfer(&signer, cmd.amount, nonce, is_deposit)
+        .await?;
+    println!("Done.");
+    Ok(())
+}
+
+async fn execute_delegate(cmd: DelegateCmd, is_undelegate: bool) -> anyhow::Result<()> {
+    let (verb, preposition) = if is_undelegate {
+        ("Undelegating", "from")
+    } else {
+        ("Delegating", "to")
+    };
+    let signer = find_signer_sync(&cmd.signer, Purpose::Withdrawals)?;
+    let client = crate::utils::http_client(cmd.signer.chain);
+    let nonce = NonceHandler::default().next();
+    println!(
+        "{verb} {} HYPE {preposition} validator {}",
+        cmd.amount, cmd.validator
+    );
+    client
+        .delegate(&signer, cmd.validator, cmd.amount, nonce, is_undelegate)
+        .await?;
+    println!("Done.");
+    Ok(())
+}
+
+/// Arguments for staking deposits and withdrawals.
+#[derive(Args, derive_more::Deref)]
+pub struct StakingTransferCmd {
+    #[deref]
+    #[command(flatten)]
+    pub signer: SignerArgs,
+
+    /// Amount of HYPE to transfer
+    #[arg(long)]
+    pub amount: Decimal,
+}
+
+/// Arguments for delegating and undelegating.
+#[derive(Args, derive_more::Deref)]
+pub struct DelegateCmd {
+    #[deref]
+    #[command(flatten)]
+    pub signer: SignerArgs,
+
+    /// Validator address
+    #[arg(long)]
+    pub validator: Address,
+
+    /// Amount of HYPE to delegate or undelegate
+    #[arg(long)]
+    pub amount: Decimal,
+}
@@ -16,7 +16,7 @@ use alloy::primitives::Address;
 use clap::{Args, Subcommand, ValueEnum};
 use futures::StreamExt;
 use hypersdk::hypercore::{
-    self, Chain, HttpClient,
+    self, CandleInterval, Chain,
     types::{Incoming, Subscription},
     ws::Event,
 };
@@ -94,12 +94,13 @@ pub struct TradesCmd {
 
 impl TradesCmd {
     pub async fn run(self) -> anyhow::Result<()> {
-        let client = HttpClient::new(self.chain);
+        let client = crate::utils::http_client(self.chain);
         let resolved = resolve_asset_for_subscription(&client, &self.asset).await?;
 
         let core = match self.chain {
-            Chain::Mainnet => hypercore::mainnet(),
+            Chain::Mainnet => crate::utils::http_client(hypercore::Chain::Mainnet),
             Chain::Testnet => hypercore::testnet(),
+            Chain::Local => hypercore::local(),
         };
 
         let mut ws = core.websocket();
@@ -113,6 +114,8 @@ impl TradesCmd {
             match event {
                 Event::Connected => eprintln!("Connected"),
                 Event::Disconnected => eprintln!("Disconnected, reconnecting..."),
+                Event::Lagging { age, .. } => eprintln!("No update for {age:?}"),
+                Event::Gap { missed, .. } => eprintln!("Missed {missed:?} of updates"),
                 Event::Message(msg) => match msg {
                     Incoming::Trades(trades) => {
                         for trade in trades {
@@ -170,12 +173,13 @@ pub struct BboCmd {
 
 impl BboCmd {
     pub async fn run(self) -> anyhow::Result<()> {
-        let client = HttpClient::new(self.chain);
+        let client = crate::utils::http_client(self.chain);
         let resolved = resolve_asset_for_subscription(&client, &self.asset).await?;
 
         let core = match self.chain {
-            Chain::Mainnet => hypercore::mainnet(),
+            Chain::Mainnet => crate::utils::http_client(hypercore::Chain::Mainnet),
             Chain::Testnet => hypercore::testnet(),
+            Chain::Local => hypercore::local(),
         };
 
         let mut ws = core.websocket();
@@ -189,6 +193,8 @@ impl BboCmd {
             match event {
                 Event::Connected => eprintln!("Connected"),
                 Event::Disconnected => eprintln!("Disconnected, reconnecting..."),
+                Event::Lagging { age, .. } => eprintln!("No update for {age:?}"),
+                Event::Gap { missed, .. } => eprintln!("Missed {missed:?} of updates"),
                 Event::Message(msg) => match msg {
                     Incoming::Bbo(bbo) => match self.format {
                         OutputFormat::Pretty => {
@@ -253,12 +259,13 @@ pub struct OrderbookCmd {
 
 impl OrderbookCmd {
     pub async fn run(self) -> anyhow::Result<()> {
-        let client = HttpClient::new(self.chain);
+        let client = crate::utils::http_client(self.chain);
         let resolved = resolve_asset_for_subscription(&client, &self.asset).await?;
 
         let core = match self.chain {
-            Chain::Mainnet => hypercore::mainnet(),
+            Chain::Mainnet => crate::utils::http_client(hypercore::Chain::Mainnet),
             Chain::Testnet => hypercore::testnet(),
+            Chain::Local => hypercore::local(),
         };
 
         let mut ws = core.websocket();
@@ -275,6 +282,8 @@ impl OrderbookCmd {
             match event {
                 Event::Connected => eprintln!("Connected"),
                 Event::Disconnected => eprintln!("Disconnected, reconnecting..."),
+                Event::Lagging { age, .. } => eprintln!("No update for {age:?}"),
+                Event::Gap { missed, .. } => eprintln!("Missed {missed:?} of updates"),
                 Event::Message(msg) => match msg {
                     Incoming::L2Book(book) => match self.format {
                         OutputFormat::Pretty => {
@@ -322,17 +331,21 @@ impl OrderbookCmd {
 /// # Example
 ///
 /// ```bash
-/// hypecli subscribe candles --coin BTC --interval 1m
-/// hypecli subscribe candles --coin ETH --interval 15m --format json
+/// hypecli subscribe candles --asset BTC --interval 1m
+/// hypecli subscribe candles --asset PURR/USDC --interval 15m --format json
+/// hypecli subscribe candles --asset xyz:BTC --interval 5m
 /// ```
 #[derive(Args)]
 pub struct CandlesCmd {
-    /// Coin symbol (e.g., BTC, ETH)
+    /// Asset name. Formats:
+    /// - "BTC" for BTC perpetual
+    /// - "PURR/USDC" for PURR spot market
+    /// - "xyz:BTC" for BTC perpetual on xyz HIP3 DEX
     #[arg(long)]
-    pub coin: String,
+    pub asset: String,
     /// Candle interval (1m, 3m, 5m, 15m, 30m, 1h, 2h, 4h, 8h, 12h, 1d, 3d, 1w, 1M)
     #[arg(long, default_value = "1m")]
-    pub interval: String,
+    pub interval: CandleInterval,
     /// Target chain
     #[arg(long, default_value = "Mainnet")]
     pub chain: Chain,
@@ -343,23 +356,29 @@ pub struct CandlesCmd {
 
 impl CandlesCmd {
     pub async fn run(self) -> anyhow::Result<()> {
+        let client = crate::utils::http_client(self.chain);
+        let resolved = resolve_asset_for_subscription(&client, &self.asset).await?;
+
         let core = match self.chain {
-            Chain::Mainnet => hypercore::mainnet(),
+            Chain::Mainnet => crate::utils::http_client(hypercore::Chain::Mainnet),
             Chain::Testnet => hypercore::testnet(),
+            Chain::Local => hypercore::local(),
         };
 
         let mut ws = core.websocket();
         ws.subscribe(Subscription::Candle {
-            coin: self.coin.clone(),
-            interval: self.interval.clone(),
+            coin: resolved.coin.clone(),
+            interval: self.interval,
         });
 
-        eprintln!("Subscribing to {} {} candles...", self.coin, self.interval);
+        eprintln!("Subscribing to {} {} candles...", self.asset, self.interval);
 
         while let Some(event) = ws.next().await {
             match event {
                 Event::Connected => eprintln!("Connected"),
                 Event::Disconnected => eprintln!("Disconnected, reconnecting..."),
+                Event::Lagging { age, .. } => eprintln!("No update for {age:?}"),
+                Event::Gap { missed, .. } => eprintln!("Missed {missed:?} of updates"),
                 Event::Message(msg) => match msg {
                     Incoming::Candle(candle) => match self.format {
                         OutputFormat::Pretty => {
@@ -427,8 +446,9 @@ pub struct AllMidsCmd {
 impl AllMidsCmd {
     pub async fn run(self) -> anyhow::Result<()> {
         let core = match self.chain {
-            Chain::Mainnet => hypercore::mainnet(),
+            Chain::Mainnet => crate::utils::http_client(hypercore::Chain::Mainnet),
             Chain::Testnet => hypercore::testnet(),
+            Chain::Local => hypercore::local(),
         };
 
         let mut ws = core.websocket();
@@ -447,6 +467,8 @@ impl AllMidsCmd {
             match event {
                 Event::Connected => eprintln!("Connected"),
                 Event::Disconnected => eprintln!("Disconnected, reconnecting..."),
+                Event::Lagging { age, .. } => eprintln!("No update for {age:?}"),
+                Event::Gap { missed, .. } => eprintln!("Missed {missed:?} of updates"),
                 Event::Message(msg) => match msg {
                     Incoming::AllMids { dex, mids } => match self.format {
                         OutputFormat::Pretty => {
@@ -509,8 +531,9 @@ pub struct OrderUpdatesCmd {
 impl OrderUpdatesCmd {
     pub async fn run(self) -> anyhow::Result<()> {
         let core = match self.chain {
-            Chain::Mainnet => hypercore::mainnet(),
+            Chain::Mainnet => crate::utils::http_client(hypercore::Chain::Mainnet),
             Chain::Testnet => hypercore::testnet(),
+            Chain::Local => hypercore::local(),
         };
 
         let mut ws = core.websocket();
@@ -522,6 +545,8 @@ impl OrderUpdatesCmd {
             match event {
                 Event::Connected => eprintln!("Connected"),
                 Event::Disconnected => eprintln!("Disconnected, reconnecting..."),
+                Event::Lagging { age, .. } => eprintln!("No update for {age:?}"),
+                Event::Gap { missed, .. } => eprintln!("Missed {missed:?} of updates"),
                 Event::Message(msg) => match msg {
                     Incoming::OrderUpdates(updates) => {
                         for update in updates {
@@ -577,8 +602,9 @@ pub struct FillsCmd {
 impl FillsCmd {
     pub async fn run(self) -> anyhow::Result<()> {
         let core = match self.chain {
-            Chain::Mainnet => hypercore::mainnet(),
+            Chain::Mainnet => crate::utils::http_client(hypercore::Chain::Mainnet),
             Chain::Testnet => hypercore::testnet(),
+            Chain::Local => hypercore::local(),
         };
 
         let mut ws = core.websocket();
@@ -590,6 +616,8 @@ impl FillsCmd {
             match event {
                 Event::Connected => eprintln!("Connected"),
                 Event::Disconnected => eprintln!("Disconnected, reconnecting..."),
+                Event::Lagging { age, .. } => eprintln!("No update for {age:?}"),
+                Event::Gap { missed, .. } => eprintln!("Missed {missed:?} of updates"),
                 Event::Message(msg) => match msg {
                     Incoming::UserFills { user, fills, .. } => {
                         for fill in fills {
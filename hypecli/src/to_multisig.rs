@@ -3,7 +3,7 @@
 use clap::Parser;
 use hypersdk::{
     Address,
-    hypercore::{HttpClient, NonceHandler},
+    hypercore::{NonceHandler, registry::Purpose},
 };
 
 use crate::{SignerArgs, utils};
@@ -29,8 +29,8 @@ pub struct ToMultiSigCmd {
 
 impl ToMultiSigCmd {
     pub async fn run(self) -> anyhow::Result<()> {
-        let signer = utils::find_signer(&self.common, None).await?;
-        let client = HttpClient::new(self.chain);
+        let signer = utils::find_signer(&self.common, Purpose::Account, None).await?;
+        let client = crate::utils::http_client(self.chain);
 
         println!("Converting user {} to multi-sig...", signer.address());
         println!("Authorized users: {:?}", self.authorized_user);
@@ -5,6 +5,7 @@ use futures::StreamExt;
 use hypersdk::hypercore::{
     self, BatchCancel, BatchModify, BatchOrder, Cancel, Chain, HttpClient, Modify, OidOrCloid,
     OrderGrouping, OrderRequest, OrderTypePlacement, PriceTick, TimeInForce,
+    registry::Purpose,
     types::{Incoming, OrderResponseStatus, Side as BookSide, Subscription},
     ws::Event,
 };
@@ -209,14 +210,15 @@ impl TwapCmd {
             self.slices
         );
 
-        let client = HttpClient::new(self.chain);
-        let signer = find_signer_sync(&self.signer)?;
+        let client = crate::utils::http_client(self.chain);
+        let signer = find_signer_sync(&self.signer, Purpose::Trading)?;
         let market = resolve_market(&client, &self.asset).await?;
         let resolved = resolve_asset_for_subscription(&client, &self.asset).await?;
 
         let core = match self.chain {
-            Chain::Mainnet => hypercore::mainnet(),
+            Chain::Mainnet => crate::utils::http_client(hypercore::Chain::Mainnet),
             Chain::Testnet => hypercore::testnet(),
+            Chain::Local => hypercore::local(),
         };
 
         let mut ws = core.websocket();
@@ -279,6 +281,7 @@ impl TwapCmd {
                         Some(Event::Message(msg)) => { state.update_bbo(&msg); }
                         Some(Event::Connected) => eprintln!("Connected to websocket"),
                         Some(Event::Disconnected) => eprintln!("Disconnected, reconnecting..."),
+                        Some(Event::Lagging { .. } | Event::Gap { .. }) => {}
                         None => anyhow::bail!("websocket closed"),
                     }
                 }
@@ -377,6 +380,7 @@ impl TwapCmd {
                         Some(Event::Message(msg)) => msg,
                         Some(Event::Connected) => { eprintln!("Connected to websocket"); continue; }
                         Some(Event::Disconnected) => { eprintln!("Disconnected, reconnecting..."); continue; }
+                        Some(Event::Lagging { .. } | Event::Gap { .. }) => continue,
                         None => anyhow::bail!("websocket closed"),
                     };
 
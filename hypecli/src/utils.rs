@@ -11,12 +11,17 @@
 //! - Common query arguments and formatting
 
 use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::Duration;
 use std::{env::home_dir, str::FromStr};
 
 use alloy::signers::{self, Signer, ledger::LedgerSigner, trezor::TrezorSigner};
 use anyhow::Context;
 use clap::ValueEnum;
-use hypersdk::{Address, hypercore::PrivateKeySigner};
+use hypersdk::{
+    Address,
+    hypercore::{PrivateKeySigner, registry::Purpose},
+};
 use iroh::{
     Endpoint, SecretKey,
     address_lookup::{dns::DnsAddressLookup, pkarr::PkarrPublisher},
@@ -26,9 +31,80 @@ use iroh_mdns_address_lookup::MdnsAddressLookup;
 use iroh_tickets::endpoint::EndpointTicket;
 use strsim::levenshtein;
 
-use hypersdk::hypercore::{HttpClient, PerpMarket, PriceTick, SpotMarket};
+use hypersdk::hypercore::{self, Chain, HttpClient, PerpMarket, PriceTick, SpotMarket, cache::InfoCache};
+
+use crate::{NetworkArgs, SignerArgs, keystore};
+
+/// Global HTTP settings, set once at startup.
+static NETWORK: OnceLock<NetworkArgs> = OnceLock::new();
+
+/// Stores the global HTTP settings used by [`http_client`].
+pub fn init_network(args: NetworkArgs) {
+    let _ = NETWORK.set(args);
+}
+
+/// Loads the selected profile from `~/.hypecli/profiles/<NAME>.env`.
+///
+/// The profile comes from `--profile <NAME>` or `HYPECLI_PROFILE`. It is read before
+/// the arguments are parsed, so its `HYPECLI_*` settings (timeout, retries, keys, ...)
+/// act as defaults: variables already in the environment and explicit flags win.
+pub fn load_profile() -> anyhow::Result<()> {
+    let mut args = std::env::args().skip(1);
+    let mut name = None;
+    while let Some(arg) = args.next() {
+        if arg == "--profile" {
+            name = args.next();
+            break;
+        }
+        if let Some(value) = arg.strip_prefix("--profile=") {
+            name = Some(value.to_owned());
+            break;
+        }
+    }
+    let Some(name) = name.or_else(|| std::env::var("HYPECLI_PROFILE").ok()) else {
+        return Ok(());
+    };
+
+    let home = home_dir().ok_or_else(|| anyhow::anyhow!("Unable to locate home directory"))?;
+    let path = home
+        .join(".hypecli")
+        .join("profiles")
+        .join(format!("{name}.env"));
+    dotenvy::from_path(&path)
+        .with_context(|| format!("failed to load profile {name} from {}", path.display()))
+}
+
+/// How long cached market metadata is used before it is revalidated, by default.
+pub const METADATA_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// Returns the directory market metadata is cached in, `~/.cache/hypecli`.
+pub fn cache_dir() -> Option<PathBuf> {
+    Some(home_dir()?.join(".cache").join("hypecli"))
+}
 
-use crate::SignerArgs;
+/// Creates an HTTP client for `chain` with the global timeout, retries and cache
+/// settings.
+///
+/// Market metadata (perps, spot, DEXes) is cached on disk so that back-to-back
+/// invocations don't refetch it. `--refresh` revalidates it once and stores the result.
+pub fn http_client(chain: Chain) -> HttpClient {
+    let mut builder = HttpClient::builder(chain);
+    let mut cache_ttl = Some(METADATA_TTL);
+    if let Some(network) = NETWORK.get() {
+        builder = builder
+            .timeout(Duration::from_secs(network.timeout))
+            .retries(network.retries);
+        cache_ttl = match (network.cache_ttl, network.refresh) {
+            (0, _) => None,
+            (_, true) => Some(Duration::ZERO),
+            (secs, false) => Some(Duration::from_secs(secs)),
+        };
+    }
+    if let (Some(ttl), Some(dir)) = (cache_ttl, cache_dir()) {
+        builder = builder.cache(InfoCache::disk(dir, ttl));
+    }
+    builder.build()
+}
 
 /// Find similar symbols to a given input string.
 ///
@@ -136,6 +212,45 @@ pub async fn start_gossip(
     Ok((endpoint, ticket))
 }
 
+/// Returns the keystore to use for `purpose`.
+///
+/// `--keystore` wins, then the profile's `HYPECLI_<PURPOSE>_KEYSTORE` (e.g.
+/// `HYPECLI_TRADING_KEYSTORE`), then `HYPECLI_KEYSTORE`. This lets a profile keep the
+/// trading agent, the withdrawal key and the multi-sig member key apart.
+pub fn keystore_for(cmd: &SignerArgs, purpose: Purpose) -> Option<String> {
+    cmd.keystore.clone().or_else(|| profile_var(purpose, "KEYSTORE"))
+}
+
+/// Returns the keystore password for `purpose`, resolved like [`keystore_for`].
+///
+/// `secret:<name>` values are decrypted with [`keystore::reveal`].
+pub fn password_for(cmd: &SignerArgs, purpose: Purpose) -> anyhow::Result<Option<String>> {
+    cmd.password
+        .clone()
+        .or_else(|| profile_var(purpose, "PASSWORD"))
+        .map(|password| keystore::reveal(&password))
+        .transpose()
+}
+
+/// Returns the private key for `purpose`: `--private-key`, then
+/// `HYPECLI_<PURPOSE>_PRIVATE_KEY`, then `HYPECLI_PRIVATE_KEY`.
+///
+/// `secret:<name>` values are decrypted with [`keystore::reveal`].
+pub fn private_key_for(cmd: &SignerArgs, purpose: Purpose) -> anyhow::Result<Option<String>> {
+    cmd.private_key
+        .clone()
+        .or_else(|| profile_var(purpose, "PRIVATE_KEY"))
+        .map(|key| keystore::reveal(&key))
+        .transpose()
+}
+
+fn profile_var(purpose: Purpose, name: &str) -> Option<String> {
+    let purpose = purpose.to_string().to_uppercase();
+    std::env::var(format!("HYPECLI_{purpose}_{name}"))
+        .or_else(|_| std::env::var(format!("HYPECLI_{name}")))
+        .ok()
+}
+
 /// Finds and loads a synchronous signer (private key or keystore only).
 ///
 /// This is for operations that require `SignerSync` trait, such as `send_asset`.
@@ -155,16 +270,12 @@ pub async fn start_gossip(
 /// - Private key is invalid
 /// - Keystore file not found or password incorrect
 /// - No private key or keystore provided
-pub fn find_signer_sync(cmd: &SignerArgs) -> anyhow::Result<PrivateKeySigner> {
-    if let Some(key) = cmd.private_key.as_ref() {
-        Ok(PrivateKeySigner::from_str(key)?)
-    } else if let Some(filename) = cmd.keystore.as_ref() {
-        let home_dir = home_dir().ok_or(anyhow::anyhow!("unable to locate home dir"))?;
-        let keypath = home_dir.join(".foundry").join("keystores").join(filename);
-        anyhow::ensure!(keypath.exists(), "keystore {filename} doesn't exist");
-        let password = cmd
-            .password
-            .clone()
+pub fn find_signer_sync(cmd: &SignerArgs, purpose: Purpose) -> anyhow::Result<PrivateKeySigner> {
+    if let Some(key) = private_key_for(cmd, purpose)? {
+        Ok(PrivateKeySigner::from_str(&key)?)
+    } else if let Some(filename) = keystore_for(cmd, purpose) {
+        let keypath = keystore::resolve_keystore(&filename)?;
+        let password = password_for(cmd, purpose)?
             .or_else(|| {
                 rpassword::prompt_password(format!(
                     "{} password: ",
@@ -210,17 +321,14 @@ pub fn find_signer_sync(cmd: &SignerArgs) -> anyhow::Result<PrivateKeySigner> {
 /// - No signer source provided
 pub async fn find_signer(
     cmd: &SignerArgs,
+    purpose: Purpose,
     filter_by: Option<&[Address]>,
 ) -> anyhow::Result<Box<dyn Signer + Send + Sync + 'static>> {
-    if let Some(key) = cmd.private_key.as_ref() {
-        Ok(Box::new(PrivateKeySigner::from_str(key)?) as Box<_>)
-    } else if let Some(filename) = cmd.keystore.as_ref() {
-        let home_dir = home_dir().ok_or(anyhow::anyhow!("unable to locate home dir"))?;
-        let keypath = home_dir.join(".foundry").join("keystores").join(filename);
-        anyhow::ensure!(keypath.exists(), "keystore {filename} doesn't exist");
-        let password = cmd
-            .password
-            .clone()
+    if let Some(key) = private_key_for(cmd, purpose)? {
+        Ok(Box::new(PrivateKeySigner::from_str(&key)?) as Box<_>)
+    } else if let Some(filename) = keystore_for(cmd, purpose) {
+        let keypath = keystore::resolve_keystore(&filename)?;
+        let password = password_for(cmd, purpose)?
             .or_else(|| {
                 rpassword::prompt_password(format!(
                     "{} password: ",
@@ -280,21 +388,17 @@ pub async fn find_signers(
     let mut signers: Vec<Box<dyn Signer + Send + Sync + 'static>> = Vec::new();
     let mut found: Vec<Address> = Vec::new();
 
-    if let Some(key) = cmd.private_key.as_ref() {
-        let signer = PrivateKeySigner::from_str(key)?;
+    if let Some(key) = private_key_for(cmd, Purpose::MultiSig)? {
+        let signer = PrivateKeySigner::from_str(&key)?;
         if filter_by.contains(&signer.address()) && !found.contains(&signer.address()) {
             found.push(signer.address());
             signers.push(Box::new(signer));
         }
     }
 
-    if let Some(filename) = cmd.keystore.as_ref() {
-        let home_dir = home_dir().ok_or(anyhow::anyhow!("unable to locate home dir"))?;
-        let keypath = home_dir.join(".foundry").join("keystores").join(filename);
-        if keypath.exists() {
-            let password = cmd
-                .password
-                .clone()
+    if let Some(filename) = keystore_for(cmd, Purpose::MultiSig) {
+        if let Ok(keypath) = keystore::resolve_keystore(&filename) {
+            let password = password_for(cmd, Purpose::MultiSig)?
                 .or_else(|| {
                     rpassword::prompt_password(format!(
                         "{} password: ",
@@ -502,6 +606,36 @@ pub async fn resolve_market(client: &HttpClient, asset: &str) -> anyhow::Result<
     }
 }
 
+/// Resolve a perpetual asset name (e.g., "BTC" or "xyz:BTC") to its market metadata.
+///
+/// Spot pairs are rejected since they have no leverage or margin settings.
+pub async fn resolve_perp(client: &HttpClient, asset: &str) -> anyhow::Result<PerpMarket> {
+    let spec = parse_asset_spec(asset)?;
+
+    let (mut perps, idx) = match spec {
+        AssetSpec::Perp(symbol) => {
+            let perps = client.perps().await?;
+            let idx = find_perp_index(&perps, symbol)?;
+            (perps, idx)
+        }
+        AssetSpec::Spot(base, quote) => {
+            anyhow::bail!("'{base}/{quote}' is a spot market; expected a perpetual")
+        }
+        AssetSpec::Hip3Perp(dex_name, symbol) => {
+            let dexs = client.perp_dexes().await?;
+            let dex = dexs
+                .iter()
+                .find(|d| d.name().eq_ignore_ascii_case(dex_name))
+                .ok_or_else(|| anyhow::anyhow!("HIP3 DEX '{}' not found", dex_name))?;
+            let perps = client.perps_from(dex.clone()).await?;
+            let idx = find_perp_index_with_dex(&perps, symbol, Some(dex_name))?;
+            (perps, idx)
+        }
+    };
+
+    Ok(perps.swap_remove(idx))
+}
+
 /// Find a perpetual market index by symbol with fuzzy matching suggestions.
 fn find_perp_index(perps: &[PerpMarket], symbol: &str) -> anyhow::Result<usize> {
     // First try exact match
@@ -650,7 +784,7 @@ fn find_spot_index(spots: &[SpotMarket], base: &str, quote: &str) -> anyhow::Res
 /// Contains the coin name to use for WebSocket subscriptions.
 #[derive(Debug, Clone)]
 pub struct ResolvedAsset {
-    /// The coin name to use for subscriptions (e.g., "BTC", "PURR", "@123")
+    /// The coin name to use for subscriptions (e.g., "BTC", "@123", "xyz:BTC")
     pub coin: String,
 }
 
@@ -691,25 +825,16 @@ pub async fn resolve_asset_for_subscription(
             })
         }
         AssetSpec::Spot(base, quote) => {
-            // Verify the spot market exists and get its index for the @-prefixed coin name
+            // Spot subscriptions use the market's coin name (e.g. "@107")
             let spots = client.spot().await?;
-            let spot = spots
-                .iter()
-                .find(|s| {
-                    s.base().name.eq_ignore_ascii_case(base)
-                        && s.quote().name.eq_ignore_ascii_case(quote)
-                })
-                .ok_or_else(|| {
-                    anyhow::anyhow!(
-                        "Spot market '{}/{}' not found. Use 'hypecli spot' to list available markets.",
-                        base,
-                        quote
-                    )
-                })?;
-            // Spot subscriptions use @index format
-            Ok(ResolvedAsset {
-                coin: format!("@{}", spot.index - 10_000),
-            })
+            let coin = hypercore::spot_coin_for_pair(&spots, base, quote).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Spot market '{}/{}' not found. Use 'hypecli spot' to list available markets.",
+                    base,
+                    quote
+                )
+            })?;
+            Ok(ResolvedAsset { coin })
         }
         AssetSpec::Hip3Perp(dex_name, symbol) => {
             // First get the DEX
@@ -732,8 +857,9 @@ pub async fn resolve_asset_for_subscription(
                     )
                 })?;
 
+            // HIP3 coins are scoped by the DEX prefix (e.g. "xyz:BTC")
             Ok(ResolvedAsset {
-                coin: perp.name.clone(),
+                coin: dex.coin(&perp.name),
             })
         }
     }
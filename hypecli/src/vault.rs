@@ -5,7 +5,10 @@
 
 use alloy::primitives::Address;
 use clap::{Args, Subcommand};
-use hypersdk::{Decimal, hypercore::{self, HttpClient, NonceHandler}};
+use hypersdk::{
+    Decimal,
+    hypercore::{self, NonceHandler, registry::Purpose},
+};
 
 use crate::SignerArgs;
 use crate::utils::find_signer_sync;
@@ -33,8 +36,8 @@ impl VaultCmd {
 
 async fn execute_transfer(cmd: VaultTransferCmd, is_deposit: bool) -> anyhow::Result<()> {
     let (verb, past) = if is_deposit { ("Depositing", "Deposited") } else { ("Withdrawing", "Withdrawn") };
-    let signer = find_signer_sync(&cmd.signer)?;
-    let client = HttpClient::new(cmd.signer.chain);
+    let signer = find_signer_sync(&cmd.signer, Purpose::Withdrawals)?;
+    let client = crate::utils::http_client(cmd.signer.chain);
     let nonce = NonceHandler::default().next();
     println!("{} ${} vault {}", verb, cmd.amount, cmd.vault);
     client.vault_transfer(&signer, cmd.vault, cmd.amount, nonce, is_deposit).await?;
@@ -72,7 +75,7 @@ pub struct VaultDetailsCmd {
 
 impl VaultDetailsCmd {
     pub async fn run(self) -> anyhow::Result<()> {
-        let client = hypercore::mainnet();
+        let client = crate::utils::http_client(hypercore::Chain::Mainnet);
         let details = client.vault_details(self.vault, self.user).await?;
 
         println!("Vault: {}", details.name);
@@ -96,6 +99,17 @@ impl VaultDetailsCmd {
             println!("TVL: ${}", tvl);
         }
 
+        let relationships = details.relationships();
+        if let Some(parent) = relationships.parent {
+            println!("Parent Vault: {:?}", parent);
+        }
+        if !relationships.children.is_empty() {
+            println!("Child Vaults:");
+            for child in &relationships.children {
+                println!("  {:?}", child);
+            }
+        }
+
         if let Some(state) = details.follower_state {
             println!();
             println!("Your Position:");
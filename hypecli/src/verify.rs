@@ -0,0 +1,99 @@
+//! Signature verification for exchange actions.
+//!
+//! Recomputes the hash an action was signed over and recovers the signer, to debug
+//! "signature verification failed" errors from multi-sig setups and relayers.
+
+use std::path::PathBuf;
+
+use anyhow::{Context, anyhow, bail};
+use chrono::DateTime;
+use clap::Args;
+use hypersdk::{
+    Address,
+    hypercore::{Chain, Signature, api::Action},
+};
+use serde::Deserialize;
+
+/// Recompute an action's signing hash and recover its signer.
+#[derive(Args)]
+pub struct VerifyActionCmd {
+    /// JSON file with the `/exchange` request body: `action` and `nonce`, optionally
+    /// `signature`, `vaultAddress` and `expiresAfter`
+    #[arg(long)]
+    payload: PathBuf,
+    /// Signature to verify (hex). Defaults to the payload's `signature`
+    #[arg(long)]
+    signature: Option<Signature>,
+    /// Expected signer. Fails if the recovered address differs
+    #[arg(long)]
+    signer: Option<Address>,
+    /// Chain the action was signed for (mainnet, testnet, or local)
+    #[arg(long, default_value = "mainnet")]
+    chain: Chain,
+}
+
+/// An `/exchange` request body, signature optional.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Payload {
+    action: Action,
+    nonce: u64,
+    #[serde(default)]
+    signature: Option<Signature>,
+    #[serde(default)]
+    vault_address: Option<Address>,
+    #[serde(default)]
+    expires_after: Option<u64>,
+}
+
+impl VerifyActionCmd {
+    pub async fn run(self) -> anyhow::Result<()> {
+        let text = std::fs::read_to_string(&self.payload)
+            .with_context(|| format!("reading {}", self.payload.display()))?;
+        let payload: Payload = serde_json::from_str(&text).context("parsing payload")?;
+        let signature = self
+            .signature
+            .or(payload.signature)
+            .ok_or_else(|| anyhow!("no signature: pass --signature or add one to the payload"))?;
+
+        let Payload {
+            action,
+            nonce,
+            vault_address,
+            expires_after,
+            ..
+        } = payload;
+        let expires_at = expires_after.and_then(|ms| DateTime::from_timestamp_millis(ms as i64));
+
+        if let Some(chain) = action.chain() {
+            println!("Action chain:   {chain}");
+        } else {
+            let connection_id = action.hash(nonce, vault_address, expires_after)?;
+            println!("Action hash:    {connection_id}");
+        }
+        let prehash = action.prehash(nonce, vault_address, expires_at, self.chain)?;
+        println!("Signing hash:   {prehash}");
+        let recovered = action.recover(&signature, nonce, vault_address, expires_at, self.chain)?;
+        println!("Recovered:      {recovered}");
+
+        if let Action::MultiSig(multisig) = &action {
+            println!("Multi-sig user: {}", multisig.payload.multi_sig_user);
+            println!("Outer signer:   {}", multisig.payload.outer_signer);
+            for (i, signature) in multisig.signatures.iter().enumerate() {
+                match multisig.payload.recover(signature, nonce, self.chain) {
+                    Ok(address) => println!("  signature {i}: {address}"),
+                    Err(err) => println!("  signature {i}: invalid ({err})"),
+                }
+            }
+        }
+
+        if let Some(expected) = self.signer {
+            if recovered != expected {
+                bail!("signer mismatch: expected {expected}, recovered {recovered}");
+            }
+            println!("OK: signed by {expected}");
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,53 @@
+use bytes::{Bytes, BytesMut};
+use hypersdk::hypercore::{Signature, api::MultiSigPayload};
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::{Decoder, Encoder, LengthDelimitedCodec};
+
+use crate::Membership;
+
+/// Messages exchanged during multi-sig coordination.
+#[derive(Serialize, Deserialize)]
+pub enum Message {
+    /// First message from each side, proving the sender is an authorized signer.
+    ///
+    /// The connecting side also needs to write something for the stream to open:
+    /// https://docs.rs/iroh/latest/iroh/endpoint/struct.Connection.html#method.accept_bi
+    Hello(Membership),
+    /// A proposed action with its nonce that needs to be signed.
+    Action(u64, MultiSigPayload),
+    /// A signature from an authorized signer.
+    Signature(Signature),
+}
+
+/// Length-delimited MessagePack framing for [`Message`].
+#[derive(Default)]
+pub struct Codec {
+    inner: LengthDelimitedCodec,
+}
+
+impl Decoder for Codec {
+    type Item = Message;
+    type Error = anyhow::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        let payload = match self.inner.decode(src)? {
+            Some(data) => data,
+            None => {
+                return Ok(None);
+            }
+        };
+
+        let msg = rmp_serde::from_slice(&payload)?;
+        Ok(Some(msg))
+    }
+}
+
+impl Encoder<Message> for Codec {
+    type Error = anyhow::Error;
+
+    fn encode(&mut self, item: Message, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        let msg = rmp_serde::to_vec(&item)?;
+        self.inner.encode(Bytes::from(msg), dst)?;
+        Ok(())
+    }
+}
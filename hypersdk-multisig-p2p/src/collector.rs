@@ -0,0 +1,174 @@
+use hypersdk::{
+    Address,
+    hypercore::{MultiSigConfig, Signature, api::MultiSigAction, multisig::Proposal},
+};
+
+/// Why [`Collector::add`] refused a signature.
+#[derive(Debug, thiserror::Error)]
+pub enum Rejected {
+    /// The signature doesn't recover to an address.
+    #[error("unable to verify signature: {0}")]
+    Invalid(anyhow::Error),
+    /// The signer isn't an authorized user of the multi-sig.
+    #[error("signature from unauthorized user {0}")]
+    Unauthorized(Address),
+    /// The signer already signed.
+    #[error("already signed by {0}")]
+    Duplicate(Address),
+}
+
+/// Collects signatures for a proposal until the multi-sig's threshold is met.
+///
+/// Every signature is recovered against the proposal and kept only if it comes from
+/// an authorized user that hasn't signed yet, so the collected set can be submitted
+/// as is.
+#[derive(Debug, Clone)]
+pub struct Collector {
+    proposal: Proposal,
+    authorized_users: Vec<Address>,
+    threshold: usize,
+    signatures: Vec<Signature>,
+    signed: Vec<Address>,
+}
+
+impl Collector {
+    /// Starts collecting for `proposal` against the multi-sig's current config.
+    #[must_use]
+    pub fn new(proposal: Proposal, config: &MultiSigConfig) -> Self {
+        Self {
+            proposal,
+            authorized_users: config.authorized_users.clone(),
+            threshold: config.threshold,
+            signatures: vec![],
+            signed: vec![],
+        }
+    }
+
+    /// Adds previously collected signatures, e.g. from a saved session.
+    ///
+    /// Signatures that are no longer valid, because the multi-sig's signers changed,
+    /// are dropped.
+    pub fn extend(&mut self, signatures: impl IntoIterator<Item = Signature>) {
+        for signature in signatures {
+            let _ = self.add(signature);
+        }
+    }
+
+    /// Verifies and adds `signature`, returning its signer.
+    pub fn add(&mut self, signature: Signature) -> Result<Address, Rejected> {
+        let address = self
+            .proposal
+            .recover(&signature)
+            .map_err(Rejected::Invalid)?;
+        if !self.is_authorized(address) {
+            return Err(Rejected::Unauthorized(address));
+        }
+        if self.has_signed(address) {
+            return Err(Rejected::Duplicate(address));
+        }
+        self.signatures.push(signature);
+        self.signed.push(address);
+        Ok(address)
+    }
+
+    /// The proposal being signed.
+    #[must_use]
+    pub fn proposal(&self) -> &Proposal {
+        &self.proposal
+    }
+
+    /// Authorized users of the multi-sig.
+    #[must_use]
+    pub fn authorized_users(&self) -> &[Address] {
+        &self.authorized_users
+    }
+
+    /// Whether `address` may sign.
+    #[must_use]
+    pub fn is_authorized(&self, address: Address) -> bool {
+        self.authorized_users.contains(&address)
+    }
+
+    /// Whether `address` has signed.
+    #[must_use]
+    pub fn has_signed(&self, address: Address) -> bool {
+        self.signed.contains(&address)
+    }
+
+    /// Users that have signed, in the order their signatures arrived.
+    #[must_use]
+    pub fn signed(&self) -> &[Address] {
+        &self.signed
+    }
+
+    /// Collected signatures.
+    #[must_use]
+    pub fn signatures(&self) -> &[Signature] {
+        &self.signatures
+    }
+
+    /// Number of signatures required.
+    #[must_use]
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Whether enough signatures have been collected.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.signatures.len() >= self.threshold
+    }
+
+    /// Wraps the proposal and the collected signatures into the action the lead
+    /// submits.
+    #[must_use]
+    pub fn into_action(self) -> MultiSigAction {
+        self.proposal.into_action(self.signatures)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::signers::local::PrivateKeySigner;
+    use hypersdk::hypercore::{Chain, api::Action};
+
+    use super::*;
+
+    #[test]
+    fn test_collector_threshold() {
+        let signers: Vec<_> = (0..3).map(|_| PrivateKeySigner::random()).collect();
+        let outsider = PrivateKeySigner::random();
+        let config = MultiSigConfig {
+            authorized_users: signers.iter().map(|s| s.address()).collect(),
+            threshold: 2,
+        };
+        let proposal = Proposal::new(
+            Chain::Testnet,
+            Address::repeat_byte(1),
+            signers[0].address(),
+            Action::Noop,
+            1,
+        );
+        let mut collector = Collector::new(proposal.clone(), &config);
+
+        let first = proposal.sign_sync(&signers[0]).unwrap();
+        assert_eq!(collector.add(first).unwrap(), signers[0].address());
+        assert!(matches!(collector.add(first), Err(Rejected::Duplicate(_))));
+        let stranger = proposal.sign_sync(&outsider).unwrap();
+        assert!(matches!(
+            collector.add(stranger),
+            Err(Rejected::Unauthorized(_))
+        ));
+        assert!(!collector.is_complete());
+
+        collector
+            .add(proposal.sign_sync(&signers[2]).unwrap())
+            .unwrap();
+        assert!(collector.is_complete());
+        assert_eq!(
+            collector.signed(),
+            [signers[0].address(), signers[2].address()]
+        );
+        assert_eq!(collector.into_action().signatures.len(), 2);
+    }
+}
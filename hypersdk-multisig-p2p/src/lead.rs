@@ -0,0 +1,112 @@
+use futures::{SinkExt, StreamExt};
+use hypersdk::{
+    Address,
+    hypercore::{MultiSigConfig, Signature, api::MultiSigPayload, multisig::Proposal},
+};
+use iroh::{
+    Endpoint, EndpointId,
+    endpoint::Connection,
+    protocol::{AcceptError, ProtocolHandler, Router},
+};
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender, unbounded_channel};
+use tokio_util::codec::{FramedRead, FramedWrite};
+
+use crate::{ALPN, Codec, Membership, Message};
+
+/// What happened on the lead's side of a session.
+#[derive(Debug)]
+pub enum Event {
+    /// A peer proved its membership and received the proposal.
+    Joined {
+        /// The peer's endpoint
+        peer: EndpointId,
+        /// The wallet it signs for
+        member: Address,
+    },
+    /// A peer failed to prove its membership and was disconnected.
+    Rejected {
+        /// The peer's endpoint
+        peer: EndpointId,
+        /// Why it was rejected
+        reason: String,
+    },
+    /// A member sent a signature. Check it with [`Collector::add`](crate::Collector::add)
+    /// before using it.
+    Signature(Signature),
+}
+
+/// Serves `proposal` on `endpoint` to peers that prove their membership.
+///
+/// `membership` is the lead's own, sent to every peer. Signatures and connection
+/// events arrive on the returned channel until the router is shut down.
+pub fn serve(
+    endpoint: Endpoint,
+    proposal: &Proposal,
+    membership: Membership,
+    config: &MultiSigConfig,
+) -> (Router, UnboundedReceiver<Event>) {
+    let (tx, rx) = unbounded_channel();
+    let handler = Serve {
+        nonce: proposal.nonce,
+        payload: proposal.payload.clone(),
+        membership,
+        authorized_users: config.authorized_users.clone(),
+        tx,
+    };
+    let router = Router::builder(endpoint).accept(ALPN, handler).spawn();
+    (router, rx)
+}
+
+#[derive(Debug, Clone)]
+struct Serve {
+    nonce: u64,
+    payload: MultiSigPayload,
+    membership: Membership,
+    authorized_users: Vec<Address>,
+    tx: UnboundedSender<Event>,
+}
+
+impl ProtocolHandler for Serve {
+    fn accept(
+        &self,
+        connection: Connection,
+    ) -> impl Future<Output = Result<(), AcceptError>> + Send {
+        let this = self.clone();
+        async move {
+            let peer = connection.remote_id();
+            let (send, recv) = connection.accept_bi().await?;
+
+            let mut read = FramedRead::new(recv, Codec::default());
+            let mut write = FramedWrite::new(send, Codec::default());
+
+            let multi_sig_user = this.membership.multi_sig_user;
+            let verified = match read.next().await {
+                Some(Ok(Message::Hello(membership))) => {
+                    membership.verify(multi_sig_user, peer, &this.authorized_users)
+                }
+                _ => Err(anyhow::anyhow!("no membership")),
+            };
+            let member = match verified {
+                Ok(member) => member,
+                Err(err) => {
+                    let reason = err.to_string();
+                    let _ = this.tx.send(Event::Rejected { peer, reason });
+                    return Ok(());
+                }
+            };
+            let _ = this.tx.send(Event::Joined { peer, member });
+
+            let _ = write.send(Message::Hello(this.membership)).await;
+            let _ = write.send(Message::Action(this.nonce, this.payload)).await;
+            loop {
+                match read.next().await {
+                    Some(Ok(Message::Signature(sig))) => {
+                        let _ = this.tx.send(Event::Signature(sig));
+                    }
+                    Some(Ok(_)) => {}
+                    Some(Err(_)) | None => break Ok(()),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,65 @@
+//! Peer-to-peer signature collection for Hyperliquid multi-sig actions.
+//!
+//! The coordination layer behind `hypecli multisig`, usable from bots and GUIs. The
+//! lead signer serves a [`Proposal`] on an [iroh] endpoint; co-signers connect with
+//! the lead's endpoint ticket, review the proposal, and send their signatures back.
+//! Sessions are addressed by the lead's endpoint rather than a shared gossip topic,
+//! so no signer learns about proposals it wasn't invited to.
+//!
+//! - [`Membership`]: a wallet signature binding an iroh endpoint to a multi-sig. Both
+//!   sides exchange one first, and peers that aren't authorized users are dropped.
+//! - [`Message`] / [`Codec`]: the wire format.
+//! - [`Collector`]: verifies incoming signatures until the threshold is met.
+//! - [`serve`] (lead side) and [`join`] (signer side): the connection flow.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use hypersdk::hypercore::{self, PrivateKeySigner, multisig::Proposal};
+//! use hypersdk_multisig_p2p::{Collector, Event, Membership, serve};
+//! use iroh::Endpoint;
+//!
+//! # async fn example(proposal: Proposal, endpoint: Endpoint) -> anyhow::Result<()> {
+//! let lead: PrivateKeySigner = "your_key".parse()?;
+//! let client = hypercore::mainnet();
+//! let config = client.multi_sig_config(proposal.multi_sig_user()?).await?;
+//!
+//! let membership =
+//!     Membership::sign(&lead, proposal.multi_sig_user()?, endpoint.id()).await?;
+//! let mut collector = Collector::new(proposal.clone(), &config);
+//! collector.add(proposal.sign(&lead).await?)?;
+//!
+//! let (router, mut events) = serve(endpoint, &proposal, membership, &config);
+//! while !collector.is_complete() {
+//!     match events.recv().await {
+//!         Some(Event::Signature(signature)) => match collector.add(signature) {
+//!             Ok(signer) => println!("signed by {signer}"),
+//!             Err(err) => println!("rejected: {err}"),
+//!         },
+//!         Some(_) => {}
+//!         None => break,
+//!     }
+//! }
+//! router.shutdown().await?;
+//! let action = collector.into_action();
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [iroh]: https://docs.rs/iroh
+
+mod codec;
+mod collector;
+mod lead;
+mod membership;
+mod signer;
+
+pub use codec::{Codec, Message};
+pub use collector::{Collector, Rejected};
+pub use hypersdk::hypercore::multisig::Proposal;
+pub use lead::{Event, serve};
+pub use membership::Membership;
+pub use signer::{SignerSession, join};
+
+/// ALPN of the coordination protocol. Bumped on incompatible wire changes.
+pub const ALPN: &[u8] = b"/hypersdk-multisig/1";
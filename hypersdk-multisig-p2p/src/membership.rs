@@ -0,0 +1,117 @@
+use alloy::signers::Signer;
+use hypersdk::{Address, hypercore::Signature};
+use iroh::EndpointId;
+use serde::{Deserialize, Serialize};
+
+/// A wallet's signature binding an iroh endpoint to a multi-sig it can sign for.
+///
+/// iroh connections are authenticated by endpoint keys, so once the peer's membership
+/// is verified against its endpoint ID, every message on the connection is known to
+/// come from that wallet. The wallet signs once per session rather than once per
+/// message, which keeps hardware wallets usable.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Membership {
+    /// Multi-sig user the endpoint signs for
+    pub multi_sig_user: Address,
+    /// Endpoint the wallet delegates to
+    pub endpoint: EndpointId,
+    /// EIP-191 signature by the wallet
+    pub signature: Signature,
+}
+
+impl Membership {
+    /// Signs a membership for `endpoint` with `signer` (EIP-191).
+    pub async fn sign<S>(
+        signer: &S,
+        multi_sig_user: Address,
+        endpoint: EndpointId,
+    ) -> anyhow::Result<Self>
+    where
+        S: Signer + Send + Sync + ?Sized,
+    {
+        let message = Self::message(multi_sig_user, endpoint);
+        let signature = signer.sign_message(message.as_bytes()).await?;
+        Ok(Self {
+            multi_sig_user,
+            endpoint,
+            signature: signature.into(),
+        })
+    }
+
+    /// Returns the member's address if the membership is for `multi_sig_user`, was
+    /// signed for the `remote` endpoint, and the signer is one of `authorized_users`.
+    pub fn verify(
+        &self,
+        multi_sig_user: Address,
+        remote: EndpointId,
+        authorized_users: &[Address],
+    ) -> anyhow::Result<Address> {
+        anyhow::ensure!(
+            self.multi_sig_user == multi_sig_user,
+            "peer is a member of {}, not {multi_sig_user}",
+            self.multi_sig_user
+        );
+        anyhow::ensure!(
+            self.endpoint == remote,
+            "membership was signed for another endpoint"
+        );
+        anyhow::ensure!(
+            matches!(self.signature.v, 27 | 28),
+            "invalid membership signature"
+        );
+        let signature = alloy::signers::Signature::from(self.signature);
+        let address = signature
+            .recover_address_from_msg(Self::message(self.multi_sig_user, self.endpoint))?;
+        anyhow::ensure!(
+            authorized_users.contains(&address),
+            "{address} is not an authorized user"
+        );
+        Ok(address)
+    }
+
+    fn message(multi_sig_user: Address, endpoint: EndpointId) -> String {
+        format!(
+            "hypersdk multisig membership\nmulti-sig user: {multi_sig_user}\nendpoint: {endpoint}"
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::signers::local::PrivateKeySigner;
+    use iroh::SecretKey;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_membership_verify() {
+        let wallet = PrivateKeySigner::random();
+        let multi_sig_user = Address::repeat_byte(1);
+        let endpoint = SecretKey::generate().public();
+        let membership = Membership::sign(&wallet, multi_sig_user, endpoint)
+            .await
+            .unwrap();
+
+        let authorized = [wallet.address()];
+        assert_eq!(
+            membership
+                .verify(multi_sig_user, endpoint, &authorized)
+                .unwrap(),
+            wallet.address()
+        );
+
+        // Replayed from another endpoint, for another multi-sig, or by an outsider.
+        let other = SecretKey::generate().public();
+        assert!(
+            membership
+                .verify(multi_sig_user, other, &authorized)
+                .is_err()
+        );
+        assert!(
+            membership
+                .verify(Address::repeat_byte(2), endpoint, &authorized)
+                .is_err()
+        );
+        assert!(membership.verify(multi_sig_user, endpoint, &[]).is_err());
+    }
+}
@@ -0,0 +1,103 @@
+use futures::{SinkExt, StreamExt};
+use hypersdk::{
+    Address,
+    hypercore::{Chain, Signature, multisig::Proposal},
+};
+use iroh::{
+    Endpoint, EndpointAddr,
+    endpoint::{Connection, RecvStream, SendStream},
+};
+use tokio_util::codec::{FramedRead, FramedWrite};
+
+use crate::{ALPN, Codec, Membership, Message};
+
+/// A co-signer's connection to a lead, holding the proposal it served.
+pub struct SignerSession {
+    conn: Connection,
+    _read: FramedRead<RecvStream, Codec>,
+    write: FramedWrite<SendStream, Codec>,
+    lead: Address,
+    proposal: Proposal,
+}
+
+/// Connects to the lead at `addr` and receives its proposal.
+///
+/// Sends `membership` first, then checks the lead's: it must be an authorized user
+/// and the lead named in the proposal. `chain` is the chain the signer expects to sign
+/// for; the proposal's payload doesn't carry it.
+pub async fn join(
+    endpoint: &Endpoint,
+    addr: EndpointAddr,
+    membership: Membership,
+    authorized_users: &[Address],
+    chain: Chain,
+) -> anyhow::Result<SignerSession> {
+    let multi_sig_user = membership.multi_sig_user;
+    let conn = endpoint.connect(addr, ALPN).await?;
+    let (send, recv) = conn.open_bi().await?;
+
+    let mut read = FramedRead::new(recv, Codec::default());
+    let mut write = FramedWrite::new(send, Codec::default());
+
+    write.send(Message::Hello(membership)).await?;
+
+    let lead = match read.next().await {
+        Some(Ok(Message::Hello(membership))) => {
+            membership.verify(multi_sig_user, conn.remote_id(), authorized_users)?
+        }
+        _ => anyhow::bail!("lead did not prove its membership"),
+    };
+
+    let proposal = match read.next().await {
+        Some(Ok(Message::Action(nonce, payload))) => Proposal {
+            chain,
+            nonce,
+            payload,
+        },
+        _ => anyhow::bail!("lead did not send a proposal"),
+    };
+    if proposal.multi_sig_user()? != multi_sig_user {
+        anyhow::bail!(
+            "proposal is for multi-sig user {}, not {multi_sig_user}",
+            proposal.payload.multi_sig_user
+        );
+    }
+    if proposal.lead()? != lead {
+        anyhow::bail!(
+            "proposal names {} as lead but was sent by {lead}",
+            proposal.payload.outer_signer
+        );
+    }
+
+    Ok(SignerSession {
+        conn,
+        _read: read,
+        write,
+        lead,
+        proposal,
+    })
+}
+
+impl SignerSession {
+    /// The proposal to review and sign.
+    #[must_use]
+    pub fn proposal(&self) -> &Proposal {
+        &self.proposal
+    }
+
+    /// The lead's wallet address.
+    #[must_use]
+    pub fn lead(&self) -> Address {
+        self.lead
+    }
+
+    /// Sends a signature of the proposal to the lead.
+    pub async fn send(&mut self, signature: Signature) -> anyhow::Result<()> {
+        self.write.send(Message::Signature(signature)).await
+    }
+
+    /// Waits for the lead to close the connection.
+    pub async fn closed(self) {
+        self.conn.closed().await;
+    }
+}
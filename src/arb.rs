@@ -0,0 +1,371 @@
+//! Cross-layer price divergence between HyperCore spot and HyperEVM.
+//!
+//! Tokens linked between HyperCore and HyperEVM trade on both layers: on the Core spot
+//! order book and in Uniswap V3 pools on HyperEVM. [`ArbMonitor`] compares the two and
+//! reports a [`Divergence`] whenever buying on one layer and selling on the other clears
+//! a threshold.
+//!
+//! HyperEVM prices come from the pool's quoter for the route's trade size, so they
+//! already include the pool fee and price impact. The remaining round-trip costs
+//! (bridging between layers, EVM gas, and the Core taker fee) are estimated with a
+//! [`RoundTripCost`].
+//!
+//! # Example
+//!
+//! ```no_run
+//! use futures::StreamExt;
+//! use hypersdk::{
+//!     arb::{ArbMonitor, Route, RoundTripCost},
+//!     dec, hypercore,
+//!     hyperevm::uniswap::prjx,
+//! };
+//! use std::time::Duration;
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let core = hypercore::mainnet();
+//! let routes: Vec<Route> = core
+//!     .spot()
+//!     .await?
+//!     .into_iter()
+//!     .filter_map(|market| Route::new(market, 3000, dec!(100)))
+//!     .collect();
+//!
+//! let monitor = ArbMonitor::new(core, prjx::mainnet().await?, routes)
+//!     .threshold_bps(dec!(50))
+//!     .costs(RoundTripCost {
+//!         bridge: dec!(0.1),
+//!         gas: dec!(0.05),
+//!         core_fee_rate: dec!(0.0004),
+//!     });
+//!
+//! let mut events = Box::pin(monitor.watch(Duration::from_secs(5)));
+//! while let Some(event) = events.next().await {
+//!     println!(
+//!         "{} ({}): {:.1} bps, est. profit {}",
+//!         event.coin, event.direction, event.divergence_bps, event.net_profit
+//!     );
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::time::Duration;
+
+use alloy::primitives::{Address, U160, aliases::U24};
+use anyhow::anyhow;
+use futures::Stream;
+use rust_decimal::Decimal;
+
+use crate::{
+    hypercore::{HttpClient, SpotMarket},
+    hyperevm::{
+        Provider,
+        uniswap::{
+            self,
+            contracts::IQuoterV2::{QuoteExactInputSingleParams, QuoteExactOutputSingleParams},
+        },
+    },
+};
+
+/// A spot market traded on both layers.
+#[derive(Debug, Clone)]
+pub struct Route {
+    /// HyperCore spot market
+    pub market: SpotMarket,
+    /// Base token contract on HyperEVM
+    pub base: Address,
+    /// Quote token contract on HyperEVM
+    pub quote: Address,
+    /// Uniswap V3 pool fee tier (e.g. 3000 for 0.3%)
+    pub fee: u32,
+    /// Trade size in base token units
+    pub size: Decimal,
+}
+
+impl Route {
+    /// Creates a route for `market`, or `None` if either token isn't linked to HyperEVM.
+    #[must_use]
+    pub fn new(market: SpotMarket, fee: u32, size: Decimal) -> Option<Self> {
+        let base = market.base().evm_contract?;
+        let quote = market.quote().evm_contract?;
+        Some(Self {
+            market,
+            base,
+            quote,
+            fee,
+            size,
+        })
+    }
+}
+
+/// Round-trip costs not reflected in either layer's prices, in quote token units.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RoundTripCost {
+    /// Cost of moving funds between HyperCore and HyperEVM and back
+    pub bridge: Decimal,
+    /// Gas for the HyperEVM swap
+    pub gas: Decimal,
+    /// Taker fee rate on HyperCore (e.g. `0.0004`)
+    pub core_fee_rate: Decimal,
+}
+
+impl RoundTripCost {
+    /// Total cost of a round trip with `core_notional` traded on HyperCore.
+    #[must_use]
+    pub fn total(&self, core_notional: Decimal) -> Decimal {
+        self.bridge + self.gas + self.core_fee_rate * core_notional
+    }
+}
+
+/// Which layer is cheaper.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, derive_more::Display)]
+pub enum Direction {
+    /// Buy on the HyperCore order book, sell in the HyperEVM pool.
+    #[display("buy core / sell evm")]
+    BuyCoreSellEvm,
+    /// Buy in the HyperEVM pool, sell on the HyperCore order book.
+    #[display("buy evm / sell core")]
+    BuyEvmSellCore,
+}
+
+/// Executable HyperEVM prices for a route's trade size, in quote per base.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EvmQuote {
+    /// Average price paid to buy `size` base
+    pub buy: Decimal,
+    /// Average price received selling `size` base
+    pub sell: Decimal,
+}
+
+/// A price divergence above the monitor's threshold.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Divergence {
+    /// HyperCore coin (e.g. `"@107"`)
+    pub coin: String,
+    /// Trade direction
+    pub direction: Direction,
+    /// Trade size in base units
+    pub size: Decimal,
+    /// HyperCore price used (ask when buying, bid when selling)
+    pub core_price: Decimal,
+    /// HyperEVM price used (quoted sell or buy price)
+    pub evm_price: Decimal,
+    /// Gross divergence relative to the buy price, in basis points
+    pub divergence_bps: Decimal,
+    /// Estimated round-trip cost in quote units
+    pub cost: Decimal,
+    /// Gross profit minus cost, in quote units
+    pub net_profit: Decimal,
+    /// HyperCore book timestamp in milliseconds
+    pub time: u64,
+}
+
+/// Evaluates both directions and returns the profitable one, if any.
+///
+/// `bid` and `ask` are the HyperCore top of book.
+#[must_use]
+pub fn evaluate(
+    coin: &str,
+    time: u64,
+    (bid, ask): (Decimal, Decimal),
+    evm: EvmQuote,
+    size: Decimal,
+    costs: &RoundTripCost,
+) -> Option<Divergence> {
+    let candidates = [
+        // (direction, core price, evm price, buy, sell)
+        (Direction::BuyCoreSellEvm, ask, evm.sell, ask, evm.sell),
+        (Direction::BuyEvmSellCore, bid, evm.buy, evm.buy, bid),
+    ];
+
+    candidates
+        .into_iter()
+        .filter(|&(_, _, _, buy, sell)| sell > buy && !buy.is_zero())
+        .map(|(direction, core_price, evm_price, buy, sell)| {
+            let cost = costs.total(core_price * size);
+            Divergence {
+                coin: coin.to_owned(),
+                direction,
+                size,
+                core_price,
+                evm_price,
+                divergence_bps: (sell - buy) / buy * Decimal::from(10_000),
+                cost,
+                net_profit: (sell - buy) * size - cost,
+                time,
+            }
+        })
+        .max_by_key(|d| d.net_profit)
+}
+
+/// Watches HyperCore / HyperEVM price divergences for a set of routes.
+pub struct ArbMonitor<P>
+where
+    P: Provider,
+{
+    core: HttpClient,
+    uniswap: uniswap::Client<P>,
+    routes: Vec<Route>,
+    threshold_bps: Decimal,
+    costs: RoundTripCost,
+}
+
+impl<P> ArbMonitor<P>
+where
+    P: Provider,
+{
+    /// Creates a monitor reporting any positive divergence, with zero costs.
+    pub fn new(core: HttpClient, uniswap: uniswap::Client<P>, routes: Vec<Route>) -> Self {
+        Self {
+            core,
+            uniswap,
+            routes,
+            threshold_bps: Decimal::ZERO,
+            costs: RoundTripCost::default(),
+        }
+    }
+
+    /// Only reports divergences of at least `bps` basis points.
+    #[must_use]
+    pub fn threshold_bps(mut self, bps: Decimal) -> Self {
+        self.threshold_bps = bps;
+        self
+    }
+
+    /// Sets the round-trip cost model.
+    #[must_use]
+    pub fn costs(mut self, costs: RoundTripCost) -> Self {
+        self.costs = costs;
+        self
+    }
+
+    /// Returns the monitored routes.
+    pub fn routes(&self) -> &[Route] {
+        &self.routes
+    }
+
+    /// Quotes buying and selling `route.size` base in the route's pool.
+    pub async fn evm_quote(&self, route: &Route) -> anyhow::Result<EvmQuote> {
+        let base = route.market.base();
+        let quote = route.market.quote();
+        let amount = base.to_wei(route.size);
+        let fee = U24::from(route.fee);
+        let quoter = self.uniswap.quoter();
+
+        let sold = quoter
+            .quoteExactInputSingle(QuoteExactInputSingleParams {
+                tokenIn: route.base,
+                tokenOut: route.quote,
+                amountIn: amount,
+                fee,
+                sqrtPriceLimitX96: U160::ZERO,
+            })
+            .call()
+            .await?;
+        let bought = quoter
+            .quoteExactOutputSingle(QuoteExactOutputSingleParams {
+                tokenIn: route.quote,
+                tokenOut: route.base,
+                amount,
+                fee,
+                sqrtPriceLimitX96: U160::ZERO,
+            })
+            .call()
+            .await?;
+
+        Ok(EvmQuote {
+            buy: quote.from_wei(bought.amountIn) / route.size,
+            sell: quote.from_wei(sold.amountOut) / route.size,
+        })
+    }
+
+    /// Checks a single route.
+    pub async fn check(&self, route: &Route) -> anyhow::Result<Option<Divergence>> {
+        let coin = route.market.coin().to_owned();
+        let book = self.core.l2_book(coin.clone(), None, None).await?;
+        let (bid, ask) = match (book.bids().first(), book.asks().first()) {
+            (Some(bid), Some(ask)) => (bid.px, ask.px),
+            _ => return Err(anyhow!("{coin}: empty order book")),
+        };
+        let evm = self.evm_quote(route).await?;
+
+        Ok(
+            evaluate(&coin, book.time, (bid, ask), evm, route.size, &self.costs)
+                .filter(|d| d.divergence_bps >= self.threshold_bps),
+        )
+    }
+
+    /// Checks every route once and returns the divergences above the threshold.
+    ///
+    /// Routes that fail (e.g. no pool for the fee tier) are logged and skipped.
+    pub async fn scan(&self) -> Vec<Divergence> {
+        let mut events = vec![];
+        for route in &self.routes {
+            match self.check(route).await {
+                Ok(Some(event)) => events.push(event),
+                Ok(None) => {}
+                Err(err) => log::warn!("{}: {err:?}", route.market.name),
+            }
+        }
+        events
+    }
+
+    /// Scans every `interval` and yields each divergence found.
+    pub fn watch(self, interval: Duration) -> impl Stream<Item = Divergence> {
+        futures::stream::unfold(
+            (self, Vec::<Divergence>::new(), true),
+            move |(monitor, mut pending, mut first)| async move {
+                loop {
+                    if let Some(event) = pending.pop() {
+                        return Some((event, (monitor, pending, first)));
+                    }
+                    if !first {
+                        tokio::time::sleep(interval).await;
+                    }
+                    first = false;
+                    pending = monitor.scan().await;
+                    pending.reverse();
+                }
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::dec;
+
+    use super::*;
+
+    #[test]
+    fn test_evaluate_directions() {
+        let costs = RoundTripCost {
+            bridge: dec!(0.5),
+            gas: dec!(0.1),
+            core_fee_rate: dec!(0.001),
+        };
+
+        // EVM pays 10.2 for what costs 10.0 on Core.
+        let quote = EvmQuote {
+            buy: dec!(10.3),
+            sell: dec!(10.2),
+        };
+        let d = evaluate("@1", 0, (dec!(9.9), dec!(10)), quote, dec!(100), &costs).unwrap();
+        assert_eq!(d.direction, Direction::BuyCoreSellEvm);
+        assert_eq!(d.divergence_bps, dec!(200));
+        // 0.5 + 0.1 + 0.001 * 1000
+        assert_eq!(d.cost, dec!(1.6));
+        assert_eq!(d.net_profit, dec!(18.4));
+
+        // Core bids 10.5 for what costs 10.3 on EVM.
+        let d = evaluate("@1", 0, (dec!(10.5), dec!(10.6)), quote, dec!(100), &costs).unwrap();
+        assert_eq!(d.direction, Direction::BuyEvmSellCore);
+
+        // No divergence inside the spread.
+        let inside = EvmQuote {
+            buy: dec!(10.1),
+            sell: dec!(9.95),
+        };
+        assert!(evaluate("@1", 0, (dec!(9.99), dec!(10)), inside, dec!(100), &costs).is_none());
+    }
+}
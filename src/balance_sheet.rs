@@ -0,0 +1,350 @@
+//! Consolidated account balances across HyperCore and HyperEVM.
+//!
+//! [`Hyperliquid::balance_sheet`] collects everything an address holds in one call:
+//!
+//! - perp account value on the default DEX and every HIP-3 DEX
+//! - spot balances
+//! - vault equities
+//! - staked HYPE (delegated, undelegated, and pending withdrawal)
+//! - HyperEVM balances of native HYPE and of every EVM-linked spot token
+//!
+//! Each line is valued in USD with a [`PriceOracle`] built from HyperCore mid prices.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use hypersdk::Hyperliquid;
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let client = Hyperliquid::mainnet().await?;
+//! let sheet = client.balance_sheet("0x...".parse()?).await?;
+//! println!("net worth: ${}", sheet.total_usd().round_dp(2));
+//! for balance in &sheet.spot {
+//!     println!("{}: {} (${:?})", balance.token, balance.amount, balance.usd);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+
+use alloy::primitives::{Address, U256};
+use futures::future::try_join_all;
+use rust_decimal::Decimal;
+
+use crate::{
+    Hyperliquid,
+    hypercore::{HttpClient, SpotMarket, SpotToken, UserVaultEquity},
+    hyperevm::{Provider, from_wei_lossy},
+};
+
+/// Quote token used as the USD reference.
+const USDC: &str = "USDC";
+
+/// Name of the native HyperEVM token.
+const HYPE: &str = "HYPE";
+
+/// USD prices of spot tokens, derived from HyperCore mid prices.
+///
+/// A token is priced from its `TOKEN/USDC` spot market, falling back to the perp of the
+/// same name. USDC is valued at 1.
+#[derive(Debug, Clone, Default)]
+pub struct PriceOracle {
+    prices: HashMap<String, Decimal>,
+}
+
+impl PriceOracle {
+    /// Builds an oracle from spot markets and `allMids`.
+    #[must_use]
+    pub fn new(markets: &[SpotMarket], mids: &HashMap<String, Decimal>) -> Self {
+        let mut prices = HashMap::from([(USDC.to_owned(), Decimal::ONE)]);
+        for market in markets {
+            if market.quote().name != USDC {
+                continue;
+            }
+            if let Some(&px) = mids.get(market.coin()) {
+                prices.entry(market.base().name.clone()).or_insert(px);
+            }
+        }
+        // Perp mids are keyed by coin name, spot mids by "@index" or "BASE/QUOTE".
+        for (coin, &px) in mids {
+            if !coin.contains(['@', '/', ':']) {
+                prices.entry(coin.clone()).or_insert(px);
+            }
+        }
+        Self { prices }
+    }
+
+    /// Fetches spot markets and mid prices.
+    pub async fn fetch(core: &HttpClient) -> anyhow::Result<Self> {
+        let (markets, mids) = futures::try_join!(core.spot(), core.all_mids(None))?;
+        Ok(Self::new(&markets, &mids))
+    }
+
+    /// USD price of `token`.
+    #[must_use]
+    pub fn price(&self, token: &str) -> Option<Decimal> {
+        self.prices.get(token).copied()
+    }
+
+    /// USD value of `amount` of `token`.
+    #[must_use]
+    pub fn value(&self, token: &str, amount: Decimal) -> Option<Decimal> {
+        self.price(token).map(|px| px * amount)
+    }
+}
+
+/// A token balance and its USD value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssetBalance {
+    /// Token name
+    pub token: String,
+    /// Amount held
+    pub amount: Decimal,
+    /// USD value, if the token has a price
+    pub usd: Option<Decimal>,
+}
+
+/// Perp account value on one DEX.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PerpEquity {
+    /// DEX name, empty for the default DEX
+    pub dex: String,
+    /// Account value in the DEX's collateral, treated as USD
+    pub account_value: Decimal,
+}
+
+/// Staked HYPE.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StakingBalance {
+    /// Delegated to validators
+    pub delegated: Decimal,
+    /// Staked but not delegated
+    pub undelegated: Decimal,
+    /// Queued to return to spot
+    pub pending_withdrawal: Decimal,
+    /// USD value of all of the above
+    pub usd: Option<Decimal>,
+}
+
+impl StakingBalance {
+    /// Total HYPE in the staking balance.
+    #[must_use]
+    pub fn total(&self) -> Decimal {
+        self.delegated + self.undelegated + self.pending_withdrawal
+    }
+}
+
+/// Everything an address holds across HyperCore and HyperEVM.
+#[derive(Debug, Clone)]
+pub struct BalanceSheet {
+    /// Address the sheet is for
+    pub user: Address,
+    /// Perp account value per DEX
+    pub perps: Vec<PerpEquity>,
+    /// HyperCore spot balances
+    pub spot: Vec<AssetBalance>,
+    /// Vault equities, in USDC
+    pub vaults: Vec<UserVaultEquity>,
+    /// Staked HYPE
+    pub staking: StakingBalance,
+    /// HyperEVM balances
+    pub evm: Vec<AssetBalance>,
+}
+
+impl BalanceSheet {
+    /// Perp account value across all DEXes.
+    #[must_use]
+    pub fn perp_usd(&self) -> Decimal {
+        self.perps.iter().map(|p| p.account_value).sum()
+    }
+
+    /// USD value of priced spot balances.
+    #[must_use]
+    pub fn spot_usd(&self) -> Decimal {
+        self.spot.iter().filter_map(|b| b.usd).sum()
+    }
+
+    /// Vault equity across all vaults.
+    #[must_use]
+    pub fn vault_usd(&self) -> Decimal {
+        self.vaults.iter().map(|v| v.equity).sum()
+    }
+
+    /// USD value of priced HyperEVM balances.
+    #[must_use]
+    pub fn evm_usd(&self) -> Decimal {
+        self.evm.iter().filter_map(|b| b.usd).sum()
+    }
+
+    /// Total USD value. Balances without a price are left out.
+    #[must_use]
+    pub fn total_usd(&self) -> Decimal {
+        self.perp_usd()
+            + self.spot_usd()
+            + self.vault_usd()
+            + self.staking.usd.unwrap_or_default()
+            + self.evm_usd()
+    }
+}
+
+impl<P> Hyperliquid<P>
+where
+    P: Provider,
+{
+    /// Returns the consolidated balances of `user`, valued in USD.
+    pub async fn balance_sheet(&self, user: Address) -> anyhow::Result<BalanceSheet> {
+        let core = self.core();
+        let (oracle, tokens, dexes, spot, vaults, staking) = futures::try_join!(
+            PriceOracle::fetch(core),
+            core.spot_tokens(),
+            core.perp_dexes(),
+            core.user_balances(user),
+            core.user_vault_equities(user),
+            core.delegator_summary(user),
+        )?;
+
+        let names = std::iter::once(None).chain(dexes.iter().map(|dex| Some(dex.name())));
+        let perps = try_join_all(names.map(|dex| async move {
+            let state = core
+                .clearinghouse_state(user, dex.map(str::to_owned))
+                .await?;
+            anyhow::Ok(PerpEquity {
+                dex: dex.unwrap_or_default().to_owned(),
+                account_value: state.margin_summary.account_value,
+            })
+        }))
+        .await?;
+
+        let spot = spot
+            .into_iter()
+            .filter(|balance| !balance.total.is_zero())
+            .map(|balance| AssetBalance {
+                usd: oracle.value(&balance.coin, balance.total),
+                token: balance.coin,
+                amount: balance.total,
+            })
+            .collect();
+
+        let mut staking = StakingBalance {
+            delegated: staking.delegated,
+            undelegated: staking.undelegated,
+            pending_withdrawal: staking.total_pending_withdrawal,
+            usd: None,
+        };
+        staking.usd = oracle.value(HYPE, staking.total());
+
+        let evm = self.evm_balances(user, &tokens, &oracle).await?;
+
+        Ok(BalanceSheet {
+            user,
+            perps,
+            spot,
+            vaults,
+            staking,
+            evm,
+        })
+    }
+
+    /// Native HYPE plus every EVM-linked token with a non-zero balance.
+    async fn evm_balances(
+        &self,
+        user: Address,
+        tokens: &[SpotToken],
+        oracle: &PriceOracle,
+    ) -> anyhow::Result<Vec<AssetBalance>> {
+        let linked: Vec<_> = tokens
+            .iter()
+            .filter_map(|token| Some((token, token.evm_contract?)))
+            .collect();
+
+        let mut batch = crate::hyperevm::multicall(self.evm().clone());
+        let slots: Vec<_> = linked
+            .iter()
+            .map(|&(_, contract)| batch.balance_of(contract, user))
+            .collect();
+        let (native, results) = futures::try_join!(
+            async { anyhow::Ok(self.evm().get_balance(user).await?) },
+            batch.call(),
+        )?;
+
+        let mut balances = vec![];
+        let native = from_wei_lossy(native, 18);
+        if !native.is_zero() {
+            balances.push(AssetBalance {
+                token: HYPE.to_owned(),
+                amount: native,
+                usd: oracle.value(HYPE, native),
+            });
+        }
+        for ((token, _), slot) in linked.into_iter().zip(slots) {
+            // Tokens whose contract doesn't answer `balanceOf` are skipped.
+            let Ok(raw) = results.get(&slot) else {
+                continue;
+            };
+            if raw == U256::ZERO {
+                continue;
+            }
+            let amount = token.from_wei(raw);
+            balances.push(AssetBalance {
+                token: token.name.clone(),
+                amount,
+                usd: oracle.value(&token.name, amount),
+            });
+        }
+        Ok(balances)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::dec;
+
+    use super::*;
+
+    #[test]
+    fn test_balance_sheet_totals() {
+        let sheet = BalanceSheet {
+            user: Address::ZERO,
+            perps: vec![
+                PerpEquity {
+                    dex: String::new(),
+                    account_value: dec!(1000),
+                },
+                PerpEquity {
+                    dex: "xyz".into(),
+                    account_value: dec!(250),
+                },
+            ],
+            spot: vec![
+                AssetBalance {
+                    token: "USDC".into(),
+                    amount: dec!(100),
+                    usd: Some(dec!(100)),
+                },
+                AssetBalance {
+                    token: "UNPRICED".into(),
+                    amount: dec!(5),
+                    usd: None,
+                },
+            ],
+            vaults: vec![],
+            staking: StakingBalance {
+                delegated: dec!(10),
+                undelegated: dec!(1),
+                pending_withdrawal: dec!(1),
+                usd: Some(dec!(480)),
+            },
+            evm: vec![AssetBalance {
+                token: "HYPE".into(),
+                amount: dec!(2),
+                usd: Some(dec!(80)),
+            }],
+        };
+
+        assert_eq!(sheet.perp_usd(), dec!(1250));
+        assert_eq!(sheet.spot_usd(), dec!(100));
+        assert_eq!(sheet.staking.total(), dec!(12));
+        assert_eq!(sheet.total_usd(), dec!(1910));
+    }
+}
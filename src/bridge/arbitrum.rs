@@ -0,0 +1,247 @@
+//! USDC deposits from Arbitrum.
+//!
+//! Hyperliquid onboards USDC through a bridge contract on Arbitrum: a plain ERC-20
+//! transfer of USDC to the contract is credited to the sender's perp balance on
+//! HyperCore about a minute later. [`deposit`] sends the transfer and waits for it to
+//! be mined, [`wait_for_credit`] polls the Core ledger until the deposit shows up, and
+//! [`deposit_and_wait`] does both.
+//!
+//! Deposits below [`MIN_DEPOSIT`] are lost, so [`deposit`] refuses them. The Arbitrum
+//! provider is an ordinary Alloy provider pointed at an Arbitrum RPC, such as the one
+//! returned by [`hyperevm::mainnet_with_signer_and_url`] with [`DEFAULT_RPC_URL`].
+//!
+//! # Example
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use alloy::signers::local::PrivateKeySigner;
+//! use hypersdk::{bridge::arbitrum, dec, hypercore, hyperevm};
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let signer: PrivateKeySigner = "your_key".parse()?;
+//! let user = signer.address();
+//! let url = arbitrum::DEFAULT_RPC_URL;
+//! let arbitrum = hyperevm::mainnet_with_signer_and_url(url, signer).await?;
+//! let core = hypercore::mainnet();
+//!
+//! let (tx_hash, credit) =
+//!     arbitrum::deposit_and_wait(&arbitrum, &core, user, dec!(100), Duration::from_secs(300))
+//!         .await?;
+//! println!("deposit {tx_hash} credited {} USDC at {}", credit.usdc, credit.time);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::time::Duration;
+
+use alloy::{network::ReceiptResponse, primitives::TxHash};
+use anyhow::{Result, anyhow, bail};
+use chrono::Utc;
+use rust_decimal::{Decimal, dec};
+
+use crate::{
+    hypercore::{Chain, HttpClient},
+    hyperevm::{self, Address, ERC20, Provider, address},
+};
+
+/// Default Arbitrum One RPC URL.
+pub const DEFAULT_RPC_URL: &str = "https://arb1.arbitrum.io/rpc";
+
+/// Default Arbitrum Sepolia RPC URL, bridging to the Hyperliquid testnet.
+pub const TESTNET_RPC_URL: &str = "https://sepolia-rollup.arbitrum.io/rpc";
+
+/// Smallest deposit the bridge credits; smaller transfers are lost.
+pub const MIN_DEPOSIT: Decimal = dec!(5);
+
+/// Decimals of USDC on Arbitrum.
+pub const USDC_DECIMALS: u32 = 6;
+
+/// How often [`wait_for_credit`] polls the ledger.
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Bridge and USDC contracts of one network.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Contracts {
+    /// Hyperliquid bridge contract
+    pub bridge: Address,
+    /// USDC token accepted by the bridge
+    pub usdc: Address,
+}
+
+impl Contracts {
+    /// Contracts on Arbitrum One, bridging to Hyperliquid mainnet.
+    pub const MAINNET: Self = Self {
+        bridge: address!("0x2Df1c51E09aECF9cacB7bc98cB1742757f163dF7"),
+        usdc: address!("0xaf88d065e77c8cC2239327C5EDb3A432268e5831"),
+    };
+
+    /// Contracts on Arbitrum Sepolia, bridging to Hyperliquid testnet.
+    pub const TESTNET: Self = Self {
+        bridge: address!("0x08cfc1B6b2dCF36A1480b99353A354AA8AC56f89"),
+        usdc: address!("0x1baAbB04529D43a73232B713C0FE471f7c7334d5"),
+    };
+
+    /// Returns the contracts bridging to `chain`, `None` for a local chain.
+    #[must_use]
+    pub fn for_chain(chain: Chain) -> Option<Self> {
+        match chain {
+            Chain::Mainnet => Some(Self::MAINNET),
+            Chain::Testnet => Some(Self::TESTNET),
+            Chain::Local => None,
+        }
+    }
+}
+
+/// A deposit credited to a HyperCore account.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Credit {
+    /// Time of the credit in milliseconds
+    pub time: u64,
+    /// HyperCore hash of the ledger update
+    pub hash: String,
+    /// Amount credited
+    pub usdc: Decimal,
+}
+
+/// Transfers `amount` USDC from `from` to the bridge and waits for the transfer to be
+/// mined.
+///
+/// Fails without sending anything if `amount` is below [`MIN_DEPOSIT`] or exceeds the
+/// USDC balance of `from`. Returns the Arbitrum transaction hash.
+pub async fn deposit<P: Provider>(
+    provider: &P,
+    contracts: Contracts,
+    from: Address,
+    amount: Decimal,
+) -> Result<TxHash> {
+    if amount < MIN_DEPOSIT {
+        bail!("deposit of {amount} USDC is below the bridge minimum of {MIN_DEPOSIT}");
+    }
+
+    let usdc = ERC20::new(contracts.usdc, provider.clone());
+    let wei = hyperevm::to_wei(amount, USDC_DECIMALS);
+    let balance = usdc.balanceOf(from).call().await?;
+    if balance < wei {
+        let balance = hyperevm::from_wei(balance, USDC_DECIMALS);
+        bail!("{from} holds {balance} USDC, less than the {amount} to deposit");
+    }
+
+    let receipt = usdc
+        .transfer(contracts.bridge, wei)
+        .from(from)
+        .send()
+        .await?
+        .get_receipt()
+        .await?;
+    if !receipt.status() {
+        return Err(anyhow!(
+            "USDC transfer to the bridge reverted in {}",
+            receipt.transaction_hash()
+        ));
+    }
+
+    Ok(receipt.transaction_hash())
+}
+
+/// Polls the ledger of `user` until a deposit of `amount` credited at or after
+/// `since` (milliseconds) appears, for at most `timeout`.
+pub async fn wait_for_credit(
+    client: &HttpClient,
+    user: Address,
+    amount: Decimal,
+    since: u64,
+    timeout: Duration,
+) -> Result<Credit> {
+    let poll = async {
+        loop {
+            let updates = client
+                .user_non_funding_ledger_updates(user, since, None)
+                .await?;
+            if let Some(credit) = find_credit(&updates, amount, since) {
+                return Ok::<_, anyhow::Error>(credit);
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    };
+    tokio::time::timeout(timeout, poll).await.map_err(|_| {
+        anyhow!("deposit of {amount} USDC not credited to {user} within {timeout:?}")
+    })?
+}
+
+/// Deposits `amount` USDC from `from` and waits up to `timeout` for the credit on the
+/// chain of `client`.
+///
+/// Returns the Arbitrum transaction hash and the HyperCore credit.
+pub async fn deposit_and_wait<P: Provider>(
+    provider: &P,
+    client: &HttpClient,
+    from: Address,
+    amount: Decimal,
+    timeout: Duration,
+) -> Result<(TxHash, Credit)> {
+    let contracts = Contracts::for_chain(client.chain())
+        .ok_or_else(|| anyhow!("no Arbitrum bridge for {}", client.chain()))?;
+    let since = Utc::now().timestamp_millis() as u64;
+    let tx_hash = deposit(provider, contracts, from, amount).await?;
+    let credit = wait_for_credit(client, from, amount, since, timeout).await?;
+    Ok((tx_hash, credit))
+}
+
+/// Finds a deposit of `amount` at or after `since` in raw ledger updates.
+fn find_credit(updates: &[serde_json::Value], amount: Decimal, since: u64) -> Option<Credit> {
+    updates.iter().find_map(|update| {
+        let delta = update.get("delta")?;
+        if delta.get("type")?.as_str()? != "deposit" {
+            return None;
+        }
+        let time = update.get("time")?.as_u64()?;
+        let usdc: Decimal = delta.get("usdc")?.as_str()?.parse().ok()?;
+        if time < since || usdc != amount {
+            return None;
+        }
+        Some(Credit {
+            time,
+            hash: update.get("hash")?.as_str()?.to_owned(),
+            usdc,
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_credit() {
+        let updates = serde_json::json!([
+            {
+                "time": 900,
+                "hash": "0x01",
+                "delta": { "type": "deposit", "usdc": "100.0" }
+            },
+            {
+                "time": 1_100,
+                "hash": "0x02",
+                "delta": { "type": "internalTransfer", "usdc": "100.0" }
+            },
+            {
+                "time": 1_200,
+                "hash": "0x03",
+                "delta": { "type": "deposit", "usdc": "100.0" }
+            }
+        ]);
+        let updates = updates.as_array().unwrap();
+
+        assert_eq!(
+            find_credit(updates, dec!(100), 1_000),
+            Some(Credit {
+                time: 1_200,
+                hash: "0x03".into(),
+                usdc: dec!(100),
+            })
+        );
+        assert_eq!(find_credit(updates, dec!(50), 1_000), None);
+        assert_eq!(find_credit(updates, dec!(100), 1_300), None);
+    }
+}
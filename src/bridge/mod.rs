@@ -0,0 +1,7 @@
+//! Bridging funds into Hyperliquid from other chains.
+//!
+//! # Submodules
+//!
+//! - [`arbitrum`]: USDC deposits through the Hyperliquid bridge contract on Arbitrum
+
+pub mod arbitrum;
@@ -0,0 +1,67 @@
+//! Combined HyperCore and HyperEVM client.
+//!
+//! [`Hyperliquid`] pairs a HyperCore [`HttpClient`] with a HyperEVM provider for
+//! queries that span both layers, such as
+//! [`balance_sheet`](Hyperliquid::balance_sheet).
+//!
+//! # Example
+//!
+//! ```no_run
+//! use hypersdk::Hyperliquid;
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let client = Hyperliquid::mainnet().await?;
+//! let mids = client.core().all_mids(None).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use alloy::{providers::DynProvider, transports::TransportError};
+
+use crate::{
+    hypercore::{self, HttpClient},
+    hyperevm::{self, Provider},
+};
+
+/// HyperCore and HyperEVM clients for the same network.
+pub struct Hyperliquid<P>
+where
+    P: Provider,
+{
+    core: HttpClient,
+    evm: P,
+}
+
+impl Hyperliquid<DynProvider> {
+    /// Creates a client for mainnet.
+    pub async fn mainnet() -> Result<Self, TransportError> {
+        let evm = DynProvider::new(hyperevm::mainnet().await?);
+        Ok(Self::new(hypercore::mainnet(), evm))
+    }
+
+    /// Creates a client for mainnet with a custom HyperEVM RPC URL.
+    pub async fn mainnet_with_url(url: &str) -> Result<Self, TransportError> {
+        let evm = DynProvider::new(hyperevm::mainnet_with_url(url).await?);
+        Ok(Self::new(hypercore::mainnet(), evm))
+    }
+}
+
+impl<P> Hyperliquid<P>
+where
+    P: Provider,
+{
+    /// Creates a client from existing HyperCore and HyperEVM clients.
+    pub fn new(core: HttpClient, evm: P) -> Self {
+        Self { core, evm }
+    }
+
+    /// Returns the HyperCore client.
+    pub fn core(&self) -> &HttpClient {
+        &self.core
+    }
+
+    /// Returns the HyperEVM provider.
+    pub fn evm(&self) -> &P {
+        &self.evm
+    }
+}
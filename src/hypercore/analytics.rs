@@ -0,0 +1,420 @@
+//! Trading flow analytics from an account's history.
+//!
+//! Fee tiers reward volume and maker rebates reward maker share, so planning for a
+//! tier starts with knowing how an account actually trades. [`FlowStats`] splits
+//! fills into maker and taker flow, with volume, fees, and how long maker orders
+//! rested in the book before filling.
+//!
+//! [`FeeModel`] rebuilds an account's fee rates from its tier inputs (14-day
+//! volume, maker share and staked HYPE) and projects the rates it would pay with a
+//! different stake, to weigh staking more HYPE against the fees it saves.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use hypersdk::{dec, hypercore};
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let client = hypercore::mainnet();
+//! let user = "0x...".parse()?;
+//! let end = chrono::Utc::now().timestamp_millis() as u64;
+//! let start = end - 14 * 24 * 60 * 60 * 1000;
+//!
+//! let fills = client.user_fills_by_time(user, start, Some(end), false).await?;
+//! let orders = client.historical_orders(user).await?;
+//! let orders: Vec<_> = orders.into_iter().map(|update| update.order).collect();
+//!
+//! let stats = hypercore::analytics::FlowStats::new(&fills, &orders);
+//! println!("maker ratio: {:?}", stats.maker_ratio());
+//! println!("average queue time: {:?}", stats.average_queue_time());
+//!
+//! let model = client.fee_model(user).await?;
+//! let current = model.current();
+//! for projection in model.staking_scenarios() {
+//!     let saved = current.cost(dec!(1_000_000), dec!(0), false)
+//!         - projection.cost(dec!(1_000_000), dec!(0), false);
+//!     println!("stake {} HYPE: save {saved} per 1M taker volume", projection.staked);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    time::Duration,
+};
+
+use rust_decimal::{Decimal, dec};
+
+use super::types::{BasicOrder, DelegatorSummary, FeeSchedule, Fill, UserFees};
+
+/// Maximum supply of HYPE, the base of the staking discount tiers.
+pub const HYPE_MAX_SUPPLY: Decimal = dec!(1_000_000_000);
+
+/// Fills on one side of the maker/taker split.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Flow {
+    /// Number of fills
+    pub fills: usize,
+    /// Notional volume
+    pub volume: Decimal,
+    /// Exchange fees by fee token, negative for rebates
+    pub fees: BTreeMap<String, Decimal>,
+    /// Builder fees, in the fee token of each fill
+    pub builder_fees: Decimal,
+}
+
+impl Flow {
+    fn add(&mut self, fill: &Fill) {
+        self.fills += 1;
+        self.volume += fill.notional();
+        *self.fees.entry(fill.fee_token.clone()).or_default() += fill.fee;
+        self.builder_fees += fill.builder_fee.unwrap_or_default();
+    }
+
+    /// Returns the fees paid in `token`.
+    #[must_use]
+    pub fn fees_in(&self, token: &str) -> Decimal {
+        self.fees.get(token).copied().unwrap_or_default()
+    }
+}
+
+/// Maker and taker flow of an account.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FlowStats {
+    /// Fills that added liquidity
+    pub maker: Flow,
+    /// Fills that took liquidity
+    pub taker: Flow,
+    /// Time from order placement to fill, for each maker fill whose order is known
+    pub queue_times: Vec<Duration>,
+}
+
+impl FlowStats {
+    /// Classifies `fills`, measuring queue times against the placement time of
+    /// `orders`.
+    ///
+    /// Orders can come from
+    /// [`historical_orders`](crate::hypercore::HttpClient::historical_orders) or an
+    /// order update stream. Maker fills of orders missing from `orders` are counted
+    /// without a queue time.
+    #[must_use]
+    pub fn new(fills: &[Fill], orders: &[BasicOrder]) -> Self {
+        let placed: HashMap<u64, u64> = orders
+            .iter()
+            .map(|order| (order.oid, order.timestamp))
+            .collect();
+
+        let mut stats = Self::default();
+        for fill in fills {
+            if fill.is_taker() {
+                stats.taker.add(fill);
+                continue;
+            }
+            stats.maker.add(fill);
+            if let Some(&placed) = placed.get(&fill.oid) {
+                let queued = fill.time.saturating_sub(placed);
+                stats.queue_times.push(Duration::from_millis(queued));
+            }
+        }
+        stats
+    }
+
+    /// Returns the maker share of notional volume.
+    #[must_use]
+    pub fn maker_ratio(&self) -> Option<Decimal> {
+        let total = self.maker.volume + self.taker.volume;
+        (!total.is_zero()).then(|| self.maker.volume / total)
+    }
+
+    /// Returns the average time maker orders rested before filling.
+    #[must_use]
+    pub fn average_queue_time(&self) -> Option<Duration> {
+        let n = u32::try_from(self.queue_times.len())
+            .ok()
+            .filter(|n| *n > 0)?;
+        Some(self.queue_times.iter().sum::<Duration>() / n)
+    }
+
+    /// Returns the median time maker orders rested before filling.
+    #[must_use]
+    pub fn median_queue_time(&self) -> Option<Duration> {
+        let mut times = self.queue_times.clone();
+        times.sort_unstable();
+        times.get(times.len() / 2).copied()
+    }
+
+    /// Returns the total fees paid in `token`, maker and taker.
+    #[must_use]
+    pub fn fees_in(&self, token: &str) -> Decimal {
+        self.maker.fees_in(token) + self.taker.fees_in(token)
+    }
+}
+
+/// Inputs the exchange derives an account's fee rates from.
+#[derive(Debug, Clone)]
+pub struct FeeModel {
+    /// Base rates and tiers of the exchange
+    pub schedule: FeeSchedule,
+    /// Taker plus maker volume over the 14-day window
+    pub volume_14d: Decimal,
+    /// Share of the exchange's 14-day maker volume that was the account's
+    pub maker_fraction: Decimal,
+    /// HYPE delegated to validators
+    pub staked: Decimal,
+    /// Active referral discount, as a fraction of the fee
+    pub referral_discount: Decimal,
+}
+
+impl FeeModel {
+    /// Builds the model from the account's [`UserFees`] and staking summary.
+    ///
+    /// Every trade has exactly one maker, so the exchange's maker volume equals its
+    /// total volume.
+    #[must_use]
+    pub fn new(fees: &UserFees, staking: &DelegatorSummary) -> Self {
+        let maker: Decimal = fees.daily_user_vlm.iter().map(|day| day.user_add).sum();
+        let exchange: Decimal = fees.daily_user_vlm.iter().map(|day| day.exchange).sum();
+        Self {
+            schedule: fees.fee_schedule.clone(),
+            volume_14d: fees.volume_14d(),
+            maker_fraction: if exchange.is_zero() {
+                Decimal::ZERO
+            } else {
+                maker / exchange
+            },
+            staked: staking.delegated,
+            referral_discount: fees.active_referral_discount,
+        }
+    }
+
+    /// Returns the rates at the current stake.
+    #[must_use]
+    pub fn current(&self) -> FeeProjection {
+        self.project(self.staked)
+    }
+
+    /// Returns the rates the account would pay with `staked` HYPE, all else equal.
+    ///
+    /// Discounts only reduce positive fees; maker rebates are paid in full.
+    #[must_use]
+    pub fn project(&self, staked: Decimal) -> FeeProjection {
+        let schedule = &self.schedule;
+        let vip = schedule
+            .tiers
+            .vip
+            .iter()
+            .rposition(|tier| self.volume_14d >= tier.ntl_cutoff);
+        let (cross, mut add, spot_cross, spot_add) = match vip.map(|i| &schedule.tiers.vip[i]) {
+            Some(tier) => (tier.cross, tier.add, tier.spot_cross, tier.spot_add),
+            None => (
+                schedule.cross,
+                schedule.add,
+                schedule.spot_cross,
+                schedule.spot_add,
+            ),
+        };
+        if let Some(tier) = schedule
+            .tiers
+            .mm
+            .iter()
+            .rfind(|tier| self.maker_fraction >= tier.maker_fraction_cutoff)
+        {
+            add = add.min(tier.add);
+        }
+
+        let staked_bps = staked / HYPE_MAX_SUPPLY * dec!(10_000);
+        let staking_discount = schedule
+            .staking_discount_tiers
+            .iter()
+            .rfind(|tier| staked_bps >= tier.bps_of_max_supply)
+            .map_or(Decimal::ZERO, |tier| tier.discount);
+        let discount = |rate: Decimal| {
+            if rate.is_sign_positive() {
+                rate * (Decimal::ONE - staking_discount) * (Decimal::ONE - self.referral_discount)
+            } else {
+                rate
+            }
+        };
+
+        FeeProjection {
+            staked,
+            staking_discount,
+            vip_tier: vip.map(|i| i + 1),
+            maker_rate: discount(add),
+            taker_rate: discount(cross),
+            spot_maker_rate: discount(spot_add),
+            spot_taker_rate: discount(spot_cross),
+        }
+    }
+
+    /// Returns the rates at the minimum stake of each staking tier above the current
+    /// stake, lowest first.
+    #[must_use]
+    pub fn staking_scenarios(&self) -> Vec<FeeProjection> {
+        self.schedule
+            .staking_discount_tiers
+            .iter()
+            .map(|tier| tier.bps_of_max_supply * HYPE_MAX_SUPPLY / dec!(10_000))
+            .filter(|stake| *stake > self.staked)
+            .map(|stake| self.project(stake))
+            .collect()
+    }
+}
+
+/// Fee rates of an account at a given stake.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeeProjection {
+    /// HYPE staked
+    pub staked: Decimal,
+    /// Staking discount, as a fraction of the fee
+    pub staking_discount: Decimal,
+    /// VIP tier by 14-day volume, starting at 1; `None` for base rates
+    pub vip_tier: Option<usize>,
+    /// Perpetual maker rate, negative for a rebate
+    pub maker_rate: Decimal,
+    /// Perpetual taker rate
+    pub taker_rate: Decimal,
+    /// Spot maker rate
+    pub spot_maker_rate: Decimal,
+    /// Spot taker rate
+    pub spot_taker_rate: Decimal,
+}
+
+impl FeeProjection {
+    /// Returns the fees paid on `taker_volume` and `maker_volume`, negative for a net
+    /// rebate.
+    #[must_use]
+    pub fn cost(&self, taker_volume: Decimal, maker_volume: Decimal, is_spot: bool) -> Decimal {
+        let (taker, maker) = if is_spot {
+            (self.spot_taker_rate, self.spot_maker_rate)
+        } else {
+            (self.taker_rate, self.maker_rate)
+        };
+        taker_volume * taker + maker_volume * maker
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fill(oid: u64, time: u64, crossed: bool, fee: &str) -> Fill {
+        serde_json::from_value(serde_json::json!({
+            "coin": "BTC",
+            "px": "100",
+            "sz": "1",
+            "side": "B",
+            "time": time,
+            "startPosition": "0",
+            "dir": "Open Long",
+            "closedPnl": "0",
+            "hash": "0x0",
+            "oid": oid,
+            "crossed": crossed,
+            "fee": fee,
+            "tid": time,
+            "feeToken": "USDC",
+        }))
+        .unwrap()
+    }
+
+    fn order(oid: u64, timestamp: u64) -> BasicOrder {
+        serde_json::from_value(serde_json::json!({
+            "coin": "BTC",
+            "side": "B",
+            "limitPx": "100",
+            "sz": "0",
+            "oid": oid,
+            "timestamp": timestamp,
+            "origSz": "1",
+            "orderType": "Limit",
+            "tif": "Gtc",
+            "reduceOnly": false,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_flow_stats() {
+        let fills = [
+            fill(1, 2_000, false, "-0.001"),
+            fill(2, 5_000, false, "-0.001"),
+            fill(3, 6_000, true, "0.045"),
+            fill(4, 7_000, false, "-0.001"),
+        ];
+        let stats = FlowStats::new(&fills, &[order(1, 1_000), order(2, 1_000)]);
+
+        assert_eq!(stats.maker.fills, 3);
+        assert_eq!(stats.taker.fills, 1);
+        assert_eq!(stats.maker_ratio(), Some(Decimal::new(75, 2)));
+        assert_eq!(stats.queue_times.len(), 2);
+        assert_eq!(
+            stats.average_queue_time(),
+            Some(Duration::from_millis(2_500))
+        );
+        assert_eq!(stats.fees_in("USDC"), Decimal::new(42, 3));
+    }
+
+    #[test]
+    fn test_fee_model() {
+        let fees: UserFees = serde_json::from_value(serde_json::json!({
+            "dailyUserVlm": [
+                {"date": "2025-05-23", "userCross": "4000000", "userAdd": "2000000", "exchange": "100000000"},
+            ],
+            "feeSchedule": {
+                "cross": "0.00045",
+                "add": "0.00015",
+                "spotCross": "0.0007",
+                "spotAdd": "0.0004",
+                "tiers": {
+                    "vip": [{
+                        "ntlCutoff": "5000000.0",
+                        "cross": "0.0004",
+                        "add": "0.00012",
+                        "spotCross": "0.0006",
+                        "spotAdd": "0.0003",
+                    }],
+                    "mm": [{"makerFractionCutoff": "0.005", "add": "-0.00001"}],
+                },
+                "referralDiscount": "0.04",
+                "stakingDiscountTiers": [
+                    {"bpsOfMaxSupply": "0.0", "discount": "0.0"},
+                    {"bpsOfMaxSupply": "0.0001", "discount": "0.05"},
+                    {"bpsOfMaxSupply": "0.001", "discount": "0.1"},
+                ],
+            },
+            "userCrossRate": "0.0004",
+            "userAddRate": "-0.00001",
+            "userSpotCrossRate": "0.0006",
+            "userSpotAddRate": "0.0003",
+            "activeReferralDiscount": "0.0",
+        }))
+        .unwrap();
+        let staking = DelegatorSummary {
+            delegated: dec!(50),
+            undelegated: Decimal::ZERO,
+            total_pending_withdrawal: Decimal::ZERO,
+            n_pending_withdrawals: 0,
+        };
+        let model = FeeModel::new(&fees, &staking);
+        assert_eq!(model.maker_fraction, dec!(0.02));
+
+        let current = model.current();
+        assert_eq!(current.vip_tier, Some(1));
+        assert_eq!(current.staking_discount, dec!(0.05));
+        assert_eq!(current.taker_rate, dec!(0.00038));
+        // Rebates are not discounted.
+        assert_eq!(current.maker_rate, dec!(-0.00001));
+
+        let scenarios = model.staking_scenarios();
+        assert_eq!(scenarios.len(), 1);
+        assert_eq!(scenarios[0].staked, dec!(100));
+        assert_eq!(scenarios[0].taker_rate, dec!(0.00036));
+        assert_eq!(
+            current.cost(dec!(1_000_000), Decimal::ZERO, false)
+                - scenarios[0].cost(dec!(1_000_000), Decimal::ZERO, false),
+            dec!(20)
+        );
+    }
+}
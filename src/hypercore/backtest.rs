@@ -0,0 +1,780 @@
+//! Deterministic backtests.
+//!
+//! A [`Backtest`] drives a [`Strategy`] with recorded market data instead of the live
+//! feeds. Its orders go to a simulated exchange that fills them against the recorded
+//! books and trades, and its fills come back through the same hooks as in the
+//! [`Runtime`](super::strategy::Runtime), so the same strategy runs in both.
+//!
+//! Runs are exactly reproducible. Time is virtual: [`Context::now`] and the timer
+//! follow the recorded timestamps, never the wall clock. The only randomness, order
+//! latency jitter and whether a resting order at the traded price is reached in the
+//! queue, comes from a generator seeded with [`BacktestConfig::seed`]. The
+//! [`RunManifest`] of each report records the data range, the seed, and a hash of
+//! the configuration, for experiment tracking.
+//!
+//! The simulation is deliberately simple: taker orders walk the last recorded book
+//! without depleting it, and trigger orders aren't simulated.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use hypersdk::hypercore::{
+//!     backtest::{Backtest, BacktestConfig, Tick},
+//!     strategy::Strategy,
+//! };
+//!
+//! # fn example(mut strategy: impl Strategy, ticks: Vec<Tick>) -> anyhow::Result<()> {
+//! let config = BacktestConfig {
+//!     seed: 7,
+//!     ..BacktestConfig::default()
+//! };
+//! let report = Backtest::new(config).asset(0, "BTC").run(&mut strategy, ticks)?;
+//!
+//! println!("{:?}", report.manifest);
+//! println!("{} fills, pnl {}", report.fills.len(), report.pnl());
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+
+use alloy::primitives::{B256, keccak256};
+use anyhow::Result;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    Cloid,
+    strategy::{Command, Context, Strategy},
+    types::{
+        BatchCancel, BatchOrder, Fill, FillDirection, Incoming, L2Book, OrderRequest,
+        OrderTypePlacement, Side, TimeInForce, Trade,
+    },
+};
+
+/// A recorded message and the time it was received, in milliseconds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Tick {
+    /// Receive time in milliseconds
+    pub time: u64,
+    /// The message
+    pub msg: Incoming,
+}
+
+/// Parameters of the simulated exchange.
+///
+/// The whole configuration is part of the run's [`config_hash`](RunManifest::config_hash).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BacktestConfig {
+    /// Seed of the random generator
+    pub seed: u64,
+    /// Delay between a hook queuing a command and the exchange applying it
+    pub latency_ms: u64,
+    /// Random extra delay, uniform in `[0, latency_jitter_ms]`
+    pub latency_jitter_ms: u64,
+    /// Chance that a resting order fills when a trade prints at its price
+    pub queue_fill_probability: Decimal,
+    /// Fee rate of maker fills
+    pub maker_fee: Decimal,
+    /// Fee rate of taker fills
+    pub taker_fee: Decimal,
+    /// Interval between [`Strategy::on_timer`] calls in virtual time, 0 to disable
+    pub timer_ms: u64,
+}
+
+impl Default for BacktestConfig {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            latency_ms: 50,
+            latency_jitter_ms: 0,
+            queue_fill_probability: Decimal::new(5, 1),
+            maker_fee: Decimal::new(15, 5),
+            taker_fee: Decimal::new(45, 5),
+            timer_ms: 1_000,
+        }
+    }
+}
+
+/// What a run was made of.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunManifest {
+    /// Time of the first tick in milliseconds
+    pub start: u64,
+    /// Time of the last tick in milliseconds
+    pub end: u64,
+    /// Number of ticks replayed
+    pub ticks: u64,
+    /// Seed of the random generator
+    pub seed: u64,
+    /// Keccak-256 of the backtest configuration, asset map and strategy configuration
+    pub config_hash: B256,
+}
+
+/// Outcome of a run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BacktestReport {
+    /// What the run was made of
+    pub manifest: RunManifest,
+    /// Orders submitted by the strategy
+    pub orders: u64,
+    /// Simulated fills, in order
+    pub fills: Vec<Fill>,
+    /// Realized PnL, before fees
+    pub realized_pnl: Decimal,
+    /// Fees paid
+    pub fees: Decimal,
+    /// Positions left open at the end
+    pub positions: BTreeMap<String, Decimal>,
+    /// Equity (PnL net of fees, open positions marked to the mid) at each timer and
+    /// at the end, as `(time, equity)`
+    pub equity: Vec<(u64, Decimal)>,
+}
+
+impl BacktestReport {
+    /// Returns the final equity: realized and unrealized PnL, net of fees.
+    #[must_use]
+    pub fn pnl(&self) -> Decimal {
+        self.equity
+            .last()
+            .map(|(_, equity)| *equity)
+            .unwrap_or_default()
+    }
+}
+
+/// Replays recorded market data through a [`Strategy`].
+#[derive(Debug, Clone, Default)]
+pub struct Backtest {
+    config: BacktestConfig,
+    assets: BTreeMap<usize, String>,
+    strategy_config: serde_json::Value,
+}
+
+impl Backtest {
+    /// Creates a backtest with `config`.
+    #[must_use]
+    pub fn new(config: BacktestConfig) -> Self {
+        Self {
+            config,
+            ..Self::default()
+        }
+    }
+
+    /// Maps the asset index `index`, used by orders, to `coin`, used by market data.
+    #[must_use]
+    pub fn asset(mut self, index: usize, coin: impl Into<String>) -> Self {
+        self.assets.insert(index, coin.into());
+        self
+    }
+
+    /// Includes the strategy's configuration in the run's config hash.
+    #[must_use]
+    pub fn strategy_config(mut self, config: &impl Serialize) -> Self {
+        self.strategy_config = serde_json::to_value(config).unwrap_or_default();
+        self
+    }
+
+    /// Returns the configuration this backtest runs with.
+    #[must_use]
+    pub fn config(&self) -> &BacktestConfig {
+        &self.config
+    }
+
+    /// Returns the hash identifying this configuration.
+    #[must_use]
+    pub fn config_hash(&self) -> B256 {
+        let config = serde_json::json!({
+            "backtest": self.config,
+            "assets": self.assets,
+            "strategy": self.strategy_config,
+        });
+        keccak256(serde_json::to_vec(&config).unwrap_or_default())
+    }
+
+    /// Runs `strategy` over `ticks`, which must be in time order.
+    ///
+    /// Returns the error of the hook that failed, if any.
+    pub fn run(
+        &self,
+        strategy: &mut impl Strategy,
+        ticks: impl IntoIterator<Item = Tick>,
+    ) -> Result<BacktestReport> {
+        let mut sim = Simulation {
+            config: &self.config,
+            assets: &self.assets,
+            strategy,
+            ctx: Context::default(),
+            rng: Rng(self.config.seed),
+            books: HashMap::new(),
+            resting: BTreeMap::new(),
+            pending: BTreeMap::new(),
+            seq: 0,
+            next_timer: 0,
+            positions: HashMap::new(),
+            report: BacktestReport {
+                manifest: RunManifest {
+                    start: 0,
+                    end: 0,
+                    ticks: 0,
+                    seed: self.config.seed,
+                    config_hash: self.config_hash(),
+                },
+                orders: 0,
+                fills: vec![],
+                realized_pnl: Decimal::ZERO,
+                fees: Decimal::ZERO,
+                positions: BTreeMap::new(),
+                equity: vec![],
+            },
+        };
+        sim.run(ticks)?;
+        Ok(sim.finish())
+    }
+}
+
+/// SplitMix64, so that runs don't depend on a platform or crate version.
+#[derive(Debug)]
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns `true` with probability `p`.
+    fn chance(&mut self, p: Decimal) -> bool {
+        let unit = Decimal::from(self.next_u64() >> 11) / Decimal::from(1u64 << 53);
+        unit < p
+    }
+
+    /// Returns a number in `[0, max]`.
+    fn up_to(&mut self, max: u64) -> u64 {
+        match max {
+            0 => 0,
+            u64::MAX => self.next_u64(),
+            max => self.next_u64() % (max + 1),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Resting {
+    coin: String,
+    is_buy: bool,
+    px: Decimal,
+    sz: Decimal,
+    cloid: Cloid,
+}
+
+/// Size and average entry price of a simulated position.
+#[derive(Debug, Default, Clone, Copy)]
+struct Position {
+    szi: Decimal,
+    entry_px: Decimal,
+}
+
+struct Simulation<'a, S> {
+    config: &'a BacktestConfig,
+    assets: &'a BTreeMap<usize, String>,
+    strategy: &'a mut S,
+    ctx: Context,
+    rng: Rng,
+    books: HashMap<String, L2Book>,
+    /// Resting orders by oid, so they match in placement order
+    resting: BTreeMap<u64, Resting>,
+    /// Commands in flight, by arrival time then submission order
+    pending: BTreeMap<(u64, u64), Command>,
+    seq: u64,
+    next_timer: u64,
+    positions: HashMap<String, Position>,
+    report: BacktestReport,
+}
+
+impl<S: Strategy> Simulation<'_, S> {
+    fn run(&mut self, ticks: impl IntoIterator<Item = Tick>) -> Result<()> {
+        for tick in ticks {
+            if self.report.manifest.ticks == 0 {
+                self.report.manifest.start = tick.time;
+                self.ctx.now = tick.time;
+                self.next_timer = tick.time + self.config.timer_ms;
+                self.strategy.on_start(&mut self.ctx)?;
+                self.submit();
+            }
+            anyhow::ensure!(
+                tick.time >= self.ctx.now,
+                "tick at {} after tick at {}",
+                tick.time,
+                self.ctx.now
+            );
+            self.report.manifest.ticks += 1;
+            self.report.manifest.end = tick.time;
+
+            self.advance(tick.time)?;
+            if self.ctx.stopping {
+                break;
+            }
+            self.ctx.now = tick.time;
+            match &tick.msg {
+                Incoming::L2Book(book) => {
+                    self.books.insert(book.coin.clone(), book.clone());
+                    self.match_book(book)?;
+                    self.strategy.on_book(&mut self.ctx, book)?;
+                }
+                Incoming::Trades(trades) => {
+                    for trade in trades {
+                        self.match_trade(trade)?;
+                        self.strategy.on_trade(&mut self.ctx, trade)?;
+                    }
+                }
+                msg => self.strategy.on_message(&mut self.ctx, msg)?,
+            }
+            self.submit();
+            if self.ctx.stopping {
+                break;
+            }
+        }
+        if self.report.manifest.ticks > 0 {
+            self.strategy.on_stop(&mut self.ctx)?;
+        }
+        Ok(())
+    }
+
+    fn finish(mut self) -> BacktestReport {
+        let end = self.ctx.now;
+        self.sample_equity(end);
+        self.report.positions = self
+            .positions
+            .iter()
+            .filter(|(_, position)| !position.szi.is_zero())
+            .map(|(coin, position)| (coin.clone(), position.szi))
+            .collect();
+        self.report
+    }
+
+    /// Runs the timers and applies the commands due up to `time`, in time order.
+    fn advance(&mut self, time: u64) -> Result<()> {
+        loop {
+            let arrival = self.pending.keys().next().map(|(arrival, _)| *arrival);
+            let timer = (self.config.timer_ms > 0).then_some(self.next_timer);
+            let next = match (arrival, timer) {
+                (Some(arrival), Some(timer)) => arrival.min(timer),
+                (Some(next), None) | (None, Some(next)) => next,
+                (None, None) => return Ok(()),
+            };
+            if next > time || self.ctx.stopping {
+                return Ok(());
+            }
+            self.ctx.now = next;
+            if arrival == Some(next) {
+                let (_, command) = self.pending.pop_first().expect("pending command");
+                match command {
+                    Command::Place(batch) => self.place(batch)?,
+                    Command::Cancel(batch) => self.cancel(batch),
+                }
+            } else {
+                self.next_timer += self.config.timer_ms;
+                self.sample_equity(next);
+                self.strategy.on_timer(&mut self.ctx)?;
+            }
+            self.submit();
+        }
+    }
+
+    /// Sends the commands queued by the last hook.
+    fn submit(&mut self) {
+        for command in std::mem::take(&mut self.ctx.commands) {
+            if let Command::Place(batch) = &command {
+                self.report.orders += batch.orders.len() as u64;
+            }
+            let latency = self.config.latency_ms + self.rng.up_to(self.config.latency_jitter_ms);
+            self.pending
+                .insert((self.ctx.now + latency, self.seq), command);
+            self.seq += 1;
+        }
+    }
+
+    fn place(&mut self, batch: BatchOrder) -> Result<()> {
+        for order in batch.orders {
+            let Some(coin) = self.assets.get(&order.asset).cloned() else {
+                log::warn!("backtest: unknown asset {}", order.asset);
+                continue;
+            };
+            let OrderTypePlacement::Limit { tif } = order.order_type else {
+                log::warn!("backtest: trigger orders aren't simulated");
+                continue;
+            };
+            let oid = self.seq;
+            self.seq += 1;
+
+            let crosses = self.books.get(&coin).is_some_and(|book| {
+                if order.is_buy {
+                    book.best_ask().is_some_and(|ask| ask.px <= order.limit_px)
+                } else {
+                    book.best_bid().is_some_and(|bid| bid.px >= order.limit_px)
+                }
+            });
+            if matches!(tif, TimeInForce::Alo) && crosses {
+                log::debug!("backtest: post-only order {oid} would cross");
+                continue;
+            }
+
+            let remaining = if crosses {
+                self.take(&coin, oid, &order)?
+            } else {
+                order.sz
+            };
+            let rests = matches!(tif, TimeInForce::Gtc | TimeInForce::Alo);
+            if rests && !remaining.is_zero() {
+                self.resting.insert(
+                    oid,
+                    Resting {
+                        coin,
+                        is_buy: order.is_buy,
+                        px: order.limit_px,
+                        sz: remaining,
+                        cloid: order.cloid,
+                    },
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Fills `order` against the last book, returning the size left.
+    fn take(&mut self, coin: &str, oid: u64, order: &OrderRequest) -> Result<Decimal> {
+        let Some(book) = self.books.get(coin) else {
+            return Ok(order.sz);
+        };
+        let levels = if order.is_buy {
+            book.asks()
+        } else {
+            book.bids()
+        };
+        let takes: Vec<_> = levels
+            .iter()
+            .take_while(|level| {
+                if order.is_buy {
+                    level.px <= order.limit_px
+                } else {
+                    level.px >= order.limit_px
+                }
+            })
+            .map(|level| (level.px, level.sz))
+            .collect();
+
+        let mut remaining = order.sz;
+        for (px, sz) in takes {
+            if remaining.is_zero() {
+                break;
+            }
+            let sz = sz.min(remaining);
+            remaining -= sz;
+            self.fill(coin, oid, order.cloid, order.is_buy, px, sz, true)?;
+        }
+        Ok(remaining)
+    }
+
+    fn cancel(&mut self, batch: BatchCancel) {
+        for cancel in batch.cancels {
+            let same_asset = self
+                .resting
+                .get(&cancel.oid)
+                .is_some_and(|order| self.assets.get(&cancel.asset) == Some(&order.coin));
+            if same_asset {
+                self.resting.remove(&cancel.oid);
+            }
+        }
+    }
+
+    /// Fills the resting orders a new book crosses, at their limit price.
+    fn match_book(&mut self, book: &L2Book) -> Result<()> {
+        let best_bid = book.best_bid().map(|level| level.px);
+        let best_ask = book.best_ask().map(|level| level.px);
+        let crossed: Vec<u64> = self
+            .resting
+            .iter()
+            .filter(|(_, order)| order.coin == book.coin)
+            .filter(|(_, order)| {
+                if order.is_buy {
+                    best_ask.is_some_and(|ask| ask <= order.px)
+                } else {
+                    best_bid.is_some_and(|bid| bid >= order.px)
+                }
+            })
+            .map(|(oid, _)| *oid)
+            .collect();
+        for oid in crossed {
+            let order = self.resting.remove(&oid).expect("resting order");
+            self.fill(
+                &order.coin,
+                oid,
+                order.cloid,
+                order.is_buy,
+                order.px,
+                order.sz,
+                false,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Fills the resting orders a trade reaches, up to the traded size.
+    ///
+    /// Orders the trade went through fill; orders at the traded price fill with
+    /// [`BacktestConfig::queue_fill_probability`].
+    fn match_trade(&mut self, trade: &Trade) -> Result<()> {
+        let candidates: Vec<u64> = self
+            .resting
+            .iter()
+            .filter(|(_, order)| order.coin == trade.coin)
+            .filter(|(_, order)| {
+                if order.is_buy {
+                    trade.px <= order.px
+                } else {
+                    trade.px >= order.px
+                }
+            })
+            .map(|(oid, _)| *oid)
+            .collect();
+
+        let mut available = trade.sz;
+        for oid in candidates {
+            if available.is_zero() {
+                break;
+            }
+            let order = &self.resting[&oid];
+            if trade.px == order.px && !self.rng.chance(self.config.queue_fill_probability) {
+                continue;
+            }
+            let sz = order.sz.min(available);
+            available -= sz;
+            let (coin, cloid, is_buy, px) =
+                (order.coin.clone(), order.cloid, order.is_buy, order.px);
+
+            let order = self.resting.get_mut(&oid).expect("resting order");
+            order.sz -= sz;
+            if order.sz.is_zero() {
+                self.resting.remove(&oid);
+            }
+            self.fill(&coin, oid, cloid, is_buy, px, sz, false)?;
+        }
+        Ok(())
+    }
+
+    /// Books a fill and delivers it to the strategy.
+    #[allow(clippy::too_many_arguments)]
+    fn fill(
+        &mut self,
+        coin: &str,
+        oid: u64,
+        cloid: Cloid,
+        is_buy: bool,
+        px: Decimal,
+        sz: Decimal,
+        crossed: bool,
+    ) -> Result<()> {
+        let position = self.positions.entry(coin.to_owned()).or_default();
+        let start = *position;
+        let delta = if is_buy { sz } else { -sz };
+
+        let mut closed_pnl = Decimal::ZERO;
+        let increases = start.szi.is_zero() || (start.szi > Decimal::ZERO) == is_buy;
+        if increases {
+            let size = start.szi.abs() + sz;
+            position.entry_px = (start.szi.abs() * start.entry_px + sz * px) / size;
+        } else {
+            let closing = sz.min(start.szi.abs());
+            let direction = if start.szi > Decimal::ZERO {
+                Decimal::ONE
+            } else {
+                Decimal::NEGATIVE_ONE
+            };
+            closed_pnl = closing * (px - start.entry_px) * direction;
+            if sz > start.szi.abs() {
+                position.entry_px = px;
+            } else if sz == start.szi.abs() {
+                position.entry_px = Decimal::ZERO;
+            }
+        }
+        position.szi += delta;
+
+        let dir = match (is_buy, increases) {
+            (true, true) => FillDirection::OpenLong,
+            (false, true) => FillDirection::OpenShort,
+            (true, false) if sz > start.szi.abs() => FillDirection::ShortToLong,
+            (true, false) => FillDirection::CloseShort,
+            (false, false) if sz > start.szi.abs() => FillDirection::LongToShort,
+            (false, false) => FillDirection::CloseLong,
+        };
+        let rate = if crossed {
+            self.config.taker_fee
+        } else {
+            self.config.maker_fee
+        };
+        let fee = px * sz * rate;
+        self.report.realized_pnl += closed_pnl;
+        self.report.fees += fee;
+
+        let fill = Fill {
+            coin: coin.to_owned(),
+            px,
+            sz,
+            side: if is_buy { Side::Bid } else { Side::Ask },
+            time: self.ctx.now,
+            start_position: start.szi,
+            dir,
+            closed_pnl,
+            hash: format!("{:#066x}", self.report.fills.len()),
+            oid,
+            crossed,
+            fee,
+            tid: self.report.fills.len() as u64,
+            cloid: (!cloid.is_zero()).then_some(cloid),
+            fee_token: "USDC".to_owned(),
+            builder_fee: None,
+            liquidation: None,
+        };
+        self.report.fills.push(fill.clone());
+        self.ctx.apply_fill(&fill);
+        self.strategy.on_fill(&mut self.ctx, &fill)?;
+        self.submit();
+        Ok(())
+    }
+
+    fn sample_equity(&mut self, time: u64) {
+        let unrealized: Decimal = self
+            .positions
+            .iter()
+            .filter_map(|(coin, position)| {
+                let mid = self.books.get(coin)?.mid()?;
+                Some(position.szi * (mid - position.entry_px))
+            })
+            .sum();
+        let equity = self.report.realized_pnl - self.report.fees + unrealized;
+        self.report.equity.push((time, equity));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::dec;
+
+    use super::*;
+    use crate::hypercore::types::{BookLevel, OrderGrouping, Subscription};
+
+    /// Bids one below the first mid and sells one above once filled.
+    struct Scalper;
+
+    impl Strategy for Scalper {
+        fn subscriptions(&self) -> Vec<Subscription> {
+            vec![]
+        }
+
+        fn on_book(&mut self, ctx: &mut Context, book: &L2Book) -> Result<()> {
+            if ctx.now() == 0 {
+                let px = book.mid().unwrap() - Decimal::ONE;
+                ctx.place(batch(true, px));
+            }
+            Ok(())
+        }
+
+        fn on_fill(&mut self, ctx: &mut Context, fill: &Fill) -> Result<()> {
+            if fill.side == Side::Bid {
+                ctx.place(batch(false, fill.px + Decimal::TWO));
+            }
+            Ok(())
+        }
+    }
+
+    fn batch(is_buy: bool, limit_px: Decimal) -> BatchOrder {
+        BatchOrder {
+            orders: vec![OrderRequest {
+                asset: 0,
+                is_buy,
+                limit_px,
+                sz: dec!(1),
+                reduce_only: false,
+                order_type: OrderTypePlacement::Limit {
+                    tif: TimeInForce::Gtc,
+                },
+                cloid: Cloid::ZERO,
+            }],
+            grouping: OrderGrouping::Na,
+            builder: None,
+        }
+    }
+
+    fn book(time: u64, bid: Decimal, ask: Decimal) -> Tick {
+        let level = |px| BookLevel {
+            px,
+            sz: dec!(10),
+            n: 1,
+        };
+        Tick {
+            time,
+            msg: Incoming::L2Book(L2Book {
+                coin: "BTC".into(),
+                time,
+                snapshot: true,
+                levels: [vec![level(bid)], vec![level(ask)]],
+            }),
+        }
+    }
+
+    fn trade(time: u64, px: Decimal) -> Tick {
+        Tick {
+            time,
+            msg: Incoming::Trades(vec![Trade {
+                coin: "BTC".into(),
+                side: Side::Ask,
+                px,
+                sz: dec!(1),
+                time,
+                hash: String::new(),
+                tid: time,
+                users: Default::default(),
+                liquidation: None,
+            }]),
+        }
+    }
+
+    #[test]
+    fn test_backtest_is_reproducible() {
+        let ticks = || {
+            let mut ticks = vec![book(0, dec!(99), dec!(101))];
+            for i in 1..=20 {
+                ticks.push(trade(i * 100, dec!(99)));
+            }
+            ticks.push(book(3_000, dec!(102), dec!(103)));
+            ticks.push(book(5_000, dec!(104), dec!(105)));
+            ticks
+        };
+        let backtest = |seed| {
+            Backtest::new(BacktestConfig {
+                seed,
+                latency_jitter_ms: 40,
+                ..BacktestConfig::default()
+            })
+            .asset(0, "BTC")
+        };
+
+        let first = backtest(1).run(&mut Scalper, ticks()).unwrap();
+        let again = backtest(1).run(&mut Scalper, ticks()).unwrap();
+        assert_eq!(first.manifest, again.manifest);
+        assert_eq!(first.equity, again.equity);
+        assert_eq!(first.manifest.ticks, 23);
+        assert_eq!((first.manifest.start, first.manifest.end), (0, 5_000));
+
+        // Bought at 99 from the trades, sold at 101 when the book crossed.
+        assert_eq!(first.fills.len(), 2);
+        assert_eq!(first.realized_pnl, dec!(2));
+        assert!(first.positions.is_empty());
+        assert_eq!(first.pnl(), dec!(2) - first.fees);
+
+        assert_ne!(first.manifest.config_hash, backtest(2).config_hash());
+    }
+}
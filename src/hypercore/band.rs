@@ -0,0 +1,189 @@
+//! Fat-finger protection for order placement.
+//!
+//! A limit price with a misplaced decimal point is still a valid order: a buy at
+//! 10x the market fills at whatever the book offers. A [`PriceBand`] rejects orders
+//! priced further than a set fraction from a reference mid before they are signed.
+//!
+//! Reference mids are keyed by asset index. Fetch them with
+//! [`HttpClient::asset_mids`](crate::hypercore::HttpClient::asset_mids) or build them
+//! from a locally maintained book.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use hypersdk::{
+//!     dec,
+//!     hypercore::{self, NonceHandler, PrivateKeySigner, band::PriceBand, types::*},
+//! };
+//!
+//! # async fn example(batch: BatchOrder) -> anyhow::Result<()> {
+//! let client = hypercore::mainnet();
+//! let signer: PrivateKeySigner = "your_key".parse()?;
+//! let nonces = NonceHandler::default();
+//!
+//! // Reject anything priced more than 5% away from the mid.
+//! let band = PriceBand::new(dec!(0.05));
+//! let mids = client.asset_mids().await?;
+//! let statuses = client
+//!     .place_within_band(&signer, batch, &band, &mids, nonces.next(), None, None)
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::{HashMap, HashSet};
+
+use rust_decimal::Decimal;
+
+use super::types::BatchOrder;
+
+/// An order the [`PriceBand`] refused.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum BandViolation {
+    /// No reference mid for the order's asset.
+    #[error("order #{index}: no reference price for asset {asset}")]
+    NoReference {
+        /// Position of the order in the batch
+        index: usize,
+        /// Asset index of the order
+        asset: usize,
+    },
+    /// The order's price is too far from the reference mid.
+    #[error("order #{index}: price {limit_px} is {deviation} away from mid {mid} on asset {asset}")]
+    OutsideBand {
+        /// Position of the order in the batch
+        index: usize,
+        /// Asset index of the order
+        asset: usize,
+        /// Price of the order
+        limit_px: Decimal,
+        /// Reference mid
+        mid: Decimal,
+        /// Relative distance from the mid
+        deviation: Decimal,
+    },
+}
+
+/// Largest accepted distance between an order's price and the mid.
+///
+/// Only prices on the aggressive side are checked: a buy above the mid or a sell
+/// below it. Passive orders far from the market can't fill at a bad price.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PriceBand {
+    max_deviation: Decimal,
+    exempt: HashSet<usize>,
+}
+
+impl PriceBand {
+    /// Creates a band accepting prices within `max_deviation` (a fraction, `0.05` for
+    /// 5%) of the mid.
+    #[must_use]
+    pub fn new(max_deviation: Decimal) -> Self {
+        Self {
+            max_deviation,
+            exempt: HashSet::new(),
+        }
+    }
+
+    /// Returns the largest accepted deviation.
+    #[must_use]
+    pub fn max_deviation(&self) -> Decimal {
+        self.max_deviation
+    }
+
+    /// Exempts an asset from the check, e.g. an illiquid market with no reliable mid.
+    #[must_use]
+    pub fn exempt(mut self, asset: usize) -> Self {
+        self.exempt.insert(asset);
+        self
+    }
+
+    /// Checks every order of `batch` against `mids`, keyed by asset index.
+    ///
+    /// Orders on an asset without a mid are refused, unless the asset is exempt.
+    pub fn check(
+        &self,
+        batch: &BatchOrder,
+        mids: &HashMap<usize, Decimal>,
+    ) -> Result<(), BandViolation> {
+        for (index, order) in batch.orders.iter().enumerate() {
+            if self.exempt.contains(&order.asset) {
+                continue;
+            }
+            let mid = mids
+                .get(&order.asset)
+                .copied()
+                .filter(|mid| mid.is_sign_positive() && !mid.is_zero())
+                .ok_or(BandViolation::NoReference {
+                    index,
+                    asset: order.asset,
+                })?;
+            let deviation = if order.is_buy {
+                (order.limit_px - mid) / mid
+            } else {
+                (mid - order.limit_px) / mid
+            };
+            if deviation > self.max_deviation {
+                return Err(BandViolation::OutsideBand {
+                    index,
+                    asset: order.asset,
+                    limit_px: order.limit_px,
+                    mid,
+                    deviation,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::dec;
+
+    use super::*;
+    use crate::hypercore::types::{OrderGrouping, OrderRequest, OrderTypePlacement, TimeInForce};
+
+    fn order(asset: usize, is_buy: bool, limit_px: Decimal) -> BatchOrder {
+        BatchOrder {
+            orders: vec![OrderRequest {
+                asset,
+                is_buy,
+                limit_px,
+                sz: dec!(1),
+                reduce_only: false,
+                order_type: OrderTypePlacement::Limit {
+                    tif: TimeInForce::Gtc,
+                },
+                cloid: Default::default(),
+            }],
+            grouping: OrderGrouping::Na,
+            builder: None,
+        }
+    }
+
+    #[test]
+    fn test_price_band() {
+        let band = PriceBand::new(dec!(0.05)).exempt(7);
+        let mids = HashMap::from([(0, dec!(100))]);
+
+        assert!(band.check(&order(0, true, dec!(105)), &mids).is_ok());
+        assert!(band.check(&order(0, false, dec!(95)), &mids).is_ok());
+        // Passive orders far from the mid are fine.
+        assert!(band.check(&order(0, true, dec!(10)), &mids).is_ok());
+
+        assert!(matches!(
+            band.check(&order(0, true, dec!(1000)), &mids),
+            Err(BandViolation::OutsideBand { index: 0, .. })
+        ));
+        assert!(matches!(
+            band.check(&order(0, false, dec!(90)), &mids),
+            Err(BandViolation::OutsideBand { .. })
+        ));
+        assert_eq!(
+            band.check(&order(1, true, dec!(100)), &mids),
+            Err(BandViolation::NoReference { index: 0, asset: 1 })
+        );
+        assert!(band.check(&order(7, true, dec!(1000)), &mids).is_ok());
+    }
+}
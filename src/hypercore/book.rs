@@ -0,0 +1,329 @@
+//! Local order book maintained from WebSocket feeds.
+//!
+//! Every bot quoting or sizing against the book needs the same state: the latest
+//! levels, sorted, with messages that would corrupt it turned away. An [`OrderBook`]
+//! keeps that state for one market. Hyperliquid publishes the top levels of the
+//! `l2Book` feed in full on every update, so each message replaces the book. The
+//! optional `bbo` feed updates faster; its top of book is merged in until the next
+//! `l2Book` message.
+//!
+//! Messages older than the book and books that would end up crossed are rejected
+//! with a [`BookError`] and leave the book unchanged.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use futures::StreamExt;
+//! use hypersdk::hypercore::{self, book::OrderBook, types::*, ws::Event};
+//! use rust_decimal::dec;
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let mut book = OrderBook::new("BTC");
+//! let mut ws = hypercore::mainnet_ws();
+//! for sub in book.subscriptions(true) {
+//!     ws.subscribe(sub);
+//! }
+//!
+//! while let Some(event) = ws.next().await {
+//!     let Event::Message(msg) = event else { continue };
+//!     match book.apply(&msg) {
+//!         Ok(true) => println!(
+//!             "bid {:?} / buy 1 BTC at {:?}",
+//!             book.best_bid().map(|level| level.px),
+//!             book.vwap_for_size(Side::Bid, dec!(1))
+//!         ),
+//!         Ok(false) => {}
+//!         Err(err) => eprintln!("rejected update: {err}"),
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::BTreeMap;
+
+use rust_decimal::Decimal;
+
+use super::types::{Bbo, BookLevel, Incoming, L2Book, Side, Subscription};
+
+/// An update an [`OrderBook`] refused to apply.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum BookError {
+    /// The message is older than the book.
+    #[error("update at {time} is older than the book at {last}")]
+    OutOfOrder {
+        /// Timestamp of the message in milliseconds
+        time: u64,
+        /// Timestamp of the book in milliseconds
+        last: u64,
+    },
+    /// Applying the message would leave the best bid at or above the best ask.
+    #[error("update crosses the book: bid {bid} >= ask {ask}")]
+    Crossed {
+        /// Best bid price
+        bid: Decimal,
+        /// Best ask price
+        ask: Decimal,
+    },
+}
+
+/// Sorted in-memory book of one market.
+#[derive(Debug, Clone, Default)]
+pub struct OrderBook {
+    coin: String,
+    time: u64,
+    bids: BTreeMap<Decimal, BookLevel>,
+    asks: BTreeMap<Decimal, BookLevel>,
+}
+
+impl OrderBook {
+    /// Creates an empty book for `coin`.
+    pub fn new(coin: impl Into<String>) -> Self {
+        Self {
+            coin: coin.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Returns the market symbol.
+    #[must_use]
+    pub fn coin(&self) -> &str {
+        &self.coin
+    }
+
+    /// Returns the timestamp of the last applied update in milliseconds, 0 before any.
+    #[must_use]
+    pub fn time(&self) -> u64 {
+        self.time
+    }
+
+    /// Returns the subscriptions feeding the book, with `bbo` if `with_bbo` is set.
+    #[must_use]
+    pub fn subscriptions(&self, with_bbo: bool) -> Vec<Subscription> {
+        let mut subs = vec![Subscription::L2Book {
+            coin: self.coin.clone(),
+            n_sig_figs: None,
+            mantissa: None,
+            fast: false,
+        }];
+        if with_bbo {
+            subs.push(Subscription::Bbo {
+                coin: self.coin.clone(),
+            });
+        }
+        subs
+    }
+
+    /// Applies a WebSocket message.
+    ///
+    /// Returns `Ok(true)` if the book changed and `Ok(false)` for messages of other
+    /// channels or markets.
+    pub fn apply(&mut self, msg: &Incoming) -> Result<bool, BookError> {
+        match msg {
+            Incoming::L2Book(book) if book.coin == self.coin => self.apply_book(book).map(|_| true),
+            Incoming::Bbo(bbo) if bbo.coin == self.coin => self.apply_bbo(bbo),
+            _ => Ok(false),
+        }
+    }
+
+    /// Replaces the book with an `l2Book` message.
+    pub fn apply_book(&mut self, book: &L2Book) -> Result<(), BookError> {
+        self.check_time(book.time)?;
+        let bids = levels(book.bids());
+        let asks = levels(book.asks());
+        check_crossed(&bids, &asks)?;
+        self.bids = bids;
+        self.asks = asks;
+        self.time = book.time;
+        Ok(())
+    }
+
+    /// Merges a `bbo` message into the top of the book.
+    ///
+    /// Levels better than the new best bid or ask are gone and are removed. A `bbo`
+    /// message no newer than the book carries nothing new and is skipped, returning
+    /// `Ok(false)`.
+    pub fn apply_bbo(&mut self, bbo: &Bbo) -> Result<bool, BookError> {
+        if bbo.time <= self.time {
+            return Ok(false);
+        }
+        let mut bids = self.bids.clone();
+        let mut asks = self.asks.clone();
+        if let Some(bid) = bbo.bid() {
+            bids.retain(|px, _| *px < bid.px);
+            bids.insert(bid.px, *bid);
+        }
+        if let Some(ask) = bbo.ask() {
+            asks.retain(|px, _| *px > ask.px);
+            asks.insert(ask.px, *ask);
+        }
+        check_crossed(&bids, &asks)?;
+        self.bids = bids;
+        self.asks = asks;
+        self.time = bbo.time;
+        Ok(true)
+    }
+
+    /// Iterates over the bid levels, best first.
+    pub fn bids(&self) -> impl Iterator<Item = &BookLevel> {
+        self.bids.values().rev()
+    }
+
+    /// Iterates over the ask levels, best first.
+    pub fn asks(&self) -> impl Iterator<Item = &BookLevel> {
+        self.asks.values()
+    }
+
+    /// Returns the best bid level.
+    #[must_use]
+    pub fn best_bid(&self) -> Option<&BookLevel> {
+        self.bids().next()
+    }
+
+    /// Returns the best ask level.
+    #[must_use]
+    pub fn best_ask(&self) -> Option<&BookLevel> {
+        self.asks().next()
+    }
+
+    /// Returns the mid price, if both sides have levels.
+    #[must_use]
+    pub fn mid(&self) -> Option<Decimal> {
+        Some((self.best_bid()?.px + self.best_ask()?.px) / Decimal::TWO)
+    }
+
+    /// Returns the spread, if both sides have levels.
+    #[must_use]
+    pub fn spread(&self) -> Option<Decimal> {
+        Some(self.best_ask()?.px - self.best_bid()?.px)
+    }
+
+    /// Returns the size resting from the touch up to `px` inclusive, on the side of
+    /// the book `px` falls on.
+    ///
+    /// A price inside the spread has no depth.
+    #[must_use]
+    pub fn depth_at(&self, px: Decimal) -> Decimal {
+        let bids = self.bids().take_while(|level| level.px >= px);
+        let asks = self.asks().take_while(|level| level.px <= px);
+        bids.chain(asks).map(|level| level.sz).sum()
+    }
+
+    /// Returns the average price of filling `sz` against the book, or `None` if the
+    /// book is too thin.
+    ///
+    /// [`Side::Bid`] buys from the asks, [`Side::Ask`] sells into the bids.
+    #[must_use]
+    pub fn vwap_for_size(&self, side: Side, sz: Decimal) -> Option<Decimal> {
+        if sz <= Decimal::ZERO {
+            return None;
+        }
+        let levels: Box<dyn Iterator<Item = &BookLevel>> = match side {
+            Side::Bid => Box::new(self.asks()),
+            Side::Ask => Box::new(self.bids()),
+        };
+        let mut remaining = sz;
+        let mut notional = Decimal::ZERO;
+        for level in levels {
+            let take = remaining.min(level.sz);
+            notional += take * level.px;
+            remaining -= take;
+            if remaining.is_zero() {
+                return Some(notional / sz);
+            }
+        }
+        None
+    }
+
+    fn check_time(&self, time: u64) -> Result<(), BookError> {
+        if time < self.time {
+            return Err(BookError::OutOfOrder {
+                time,
+                last: self.time,
+            });
+        }
+        Ok(())
+    }
+}
+
+fn levels(levels: &[BookLevel]) -> BTreeMap<Decimal, BookLevel> {
+    levels
+        .iter()
+        .filter(|level| level.sz > Decimal::ZERO)
+        .map(|level| (level.px, *level))
+        .collect()
+}
+
+fn check_crossed(
+    bids: &BTreeMap<Decimal, BookLevel>,
+    asks: &BTreeMap<Decimal, BookLevel>,
+) -> Result<(), BookError> {
+    match (bids.last_key_value(), asks.first_key_value()) {
+        (Some((&bid, _)), Some((&ask, _))) if bid >= ask => Err(BookError::Crossed { bid, ask }),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::dec;
+
+    use super::*;
+
+    #[test]
+    fn test_order_book() {
+        let level = |px, sz| BookLevel { px, sz, n: 1 };
+        let l2 = |time, bids, asks| {
+            Incoming::L2Book(L2Book {
+                coin: "BTC".into(),
+                time,
+                snapshot: false,
+                levels: [bids, asks],
+            })
+        };
+        let mut book = OrderBook::new("BTC");
+
+        let msg = l2(
+            10,
+            vec![level(dec!(99), dec!(1)), level(dec!(98), dec!(2))],
+            vec![level(dec!(101), dec!(1)), level(dec!(102), dec!(3))],
+        );
+        assert_eq!(book.apply(&msg), Ok(true));
+        assert_eq!(book.mid(), Some(dec!(100)));
+        assert_eq!(book.depth_at(dec!(98)), dec!(3));
+        assert_eq!(book.depth_at(dec!(100)), dec!(0));
+        assert_eq!(book.vwap_for_size(Side::Bid, dec!(2)), Some(dec!(101.5)));
+        assert_eq!(book.vwap_for_size(Side::Ask, dec!(4)), None);
+
+        // Stale and crossed updates leave the book as it was.
+        let stale = l2(9, vec![], vec![]);
+        assert_eq!(
+            book.apply(&stale),
+            Err(BookError::OutOfOrder { time: 9, last: 10 })
+        );
+        let crossed = l2(
+            11,
+            vec![level(dec!(102), dec!(1))],
+            vec![level(dec!(101), dec!(1))],
+        );
+        assert!(matches!(
+            book.apply(&crossed),
+            Err(BookError::Crossed { .. })
+        ));
+        assert_eq!(book.best_bid(), Some(&level(dec!(99), dec!(1))));
+
+        // A newer bbo takes out the ask it traded through.
+        let bbo = Incoming::Bbo(Bbo {
+            coin: "BTC".into(),
+            time: 12,
+            bbo: (
+                Some(level(dec!(99), dec!(1))),
+                Some(level(dec!(102), dec!(2))),
+            ),
+        });
+        assert_eq!(book.apply(&bbo), Ok(true));
+        assert_eq!(book.best_ask(), Some(&level(dec!(102), dec!(2))));
+        assert_eq!(book.asks().count(), 1);
+        assert_eq!(book.time(), 12);
+    }
+}
@@ -0,0 +1,317 @@
+//! Circuit breaker for order submission.
+//!
+//! A strategy with a bug can end up in a loop that sends the same invalid order
+//! over and over: every rejection costs request weight, and the loop keeps going
+//! until the account is rate limited and can no longer cancel either. A
+//! [`CircuitBreaker`] attached to the client counts rejected orders and failed
+//! requests over a sliding window. Once their share crosses the configured rate it
+//! trips: order submissions fail immediately for a cool-down and a
+//! [`BreakerEvent`] is sent to every subscriber. Cancels always go through.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use hypersdk::hypercore::{Chain, HttpClient, breaker::{BreakerEvent, CircuitBreaker}};
+//! use rust_decimal::dec;
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let breaker = CircuitBreaker::new()
+//!     .max_error_rate(dec!(0.5))
+//!     .cooldown(Duration::from_secs(60));
+//! let mut events = breaker.subscribe();
+//!
+//! let client = HttpClient::builder(Chain::Mainnet)
+//!     .circuit_breaker(breaker)
+//!     .build();
+//!
+//! tokio::spawn(async move {
+//!     while let Ok(event) = events.recv().await {
+//!         if let BreakerEvent::Tripped { error_rate, .. } = event {
+//!             eprintln!("order submission halted: {error_rate} of orders failing");
+//!         }
+//!     }
+//! });
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use rust_decimal::{Decimal, dec};
+use tokio::sync::broadcast;
+
+use super::{
+    api::{OkResponse, Response},
+    types::OrderResponseStatus,
+};
+
+/// Default share of failed orders that trips the breaker (50%).
+pub const DEFAULT_MAX_ERROR_RATE: Decimal = dec!(0.5);
+
+/// Default number of orders in the window before the breaker can trip.
+pub const DEFAULT_MIN_ORDERS: usize = 10;
+
+/// Default length of the sliding window.
+pub const DEFAULT_WINDOW: Duration = Duration::from_secs(60);
+
+/// Default time submissions stay blocked after the breaker trips.
+pub const DEFAULT_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// A change of state of a [`CircuitBreaker`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BreakerEvent {
+    /// Too many orders failed; submissions are blocked for `cooldown`.
+    Tripped {
+        /// Share of failed orders in the window
+        error_rate: Decimal,
+        /// Failed orders in the window
+        failures: usize,
+        /// Orders in the window
+        orders: usize,
+        /// How long submissions are blocked
+        cooldown: Duration,
+    },
+    /// The cool-down elapsed and submissions are allowed again.
+    Reset,
+}
+
+/// The breaker is open; the submission was not sent.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("circuit breaker open for another {remaining:?}")]
+pub struct BreakerOpen {
+    /// Time left in the cool-down
+    pub remaining: Duration,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    /// Outcome of each order in the window, `true` for a failure
+    outcomes: VecDeque<(Instant, bool)>,
+    open_until: Option<Instant>,
+}
+
+/// Blocks order submission after a spike of rejections or failed requests.
+///
+/// Cloning shares the state, so one breaker can guard several clients of the same
+/// account.
+#[derive(Debug, Clone)]
+pub struct CircuitBreaker {
+    max_error_rate: Decimal,
+    min_orders: usize,
+    window: Duration,
+    cooldown: Duration,
+    state: Arc<Mutex<State>>,
+    events: broadcast::Sender<BreakerEvent>,
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CircuitBreaker {
+    /// Creates a breaker with the default thresholds.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            max_error_rate: DEFAULT_MAX_ERROR_RATE,
+            min_orders: DEFAULT_MIN_ORDERS,
+            window: DEFAULT_WINDOW,
+            cooldown: DEFAULT_COOLDOWN,
+            state: Arc::default(),
+            events: broadcast::channel(16).0,
+        }
+    }
+
+    /// Sets the share of failed orders in the window that trips the breaker.
+    #[must_use]
+    pub fn max_error_rate(mut self, max_error_rate: Decimal) -> Self {
+        self.max_error_rate = max_error_rate;
+        self
+    }
+
+    /// Sets how many orders the window must hold before the breaker can trip, so a
+    /// single early rejection doesn't.
+    #[must_use]
+    pub fn min_orders(mut self, min_orders: usize) -> Self {
+        self.min_orders = min_orders;
+        self
+    }
+
+    /// Sets the length of the sliding window.
+    #[must_use]
+    pub fn window(mut self, window: Duration) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Sets how long submissions stay blocked after the breaker trips.
+    #[must_use]
+    pub fn cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    /// Returns a receiver of the breaker's state changes.
+    pub fn subscribe(&self) -> broadcast::Receiver<BreakerEvent> {
+        self.events.subscribe()
+    }
+
+    /// Returns an error while the breaker is open.
+    pub fn check(&self) -> Result<(), BreakerOpen> {
+        self.check_at(Instant::now())
+    }
+
+    /// Returns true while submissions are blocked.
+    #[must_use]
+    pub fn is_open(&self) -> bool {
+        self.check().is_err()
+    }
+
+    /// Records the per-order statuses of a submission.
+    pub fn record_statuses(&self, statuses: &[OrderResponseStatus]) {
+        let now = Instant::now();
+        for status in statuses {
+            self.record_at(now, !status.is_ok());
+        }
+    }
+
+    /// Records a submission of `orders` orders that failed as a whole: a timeout, a
+    /// transport error or a rejected request.
+    pub fn record_failure(&self, orders: usize) {
+        let now = Instant::now();
+        for _ in 0..orders.max(1) {
+            self.record_at(now, true);
+        }
+    }
+
+    /// Closes the breaker and forgets the window.
+    pub fn reset(&self) {
+        let was_open = {
+            let mut state = self.state.lock().unwrap();
+            state.outcomes.clear();
+            state.open_until.take().is_some()
+        };
+        if was_open {
+            let _ = self.events.send(BreakerEvent::Reset);
+        }
+    }
+
+    /// Records the response to an order or modify action of `orders` orders.
+    pub(super) fn record_response(&self, resp: &anyhow::Result<Response>, orders: usize) {
+        match resp {
+            Ok(Response::Ok(OkResponse::Order { statuses })) => self.record_statuses(statuses),
+            _ => self.record_failure(orders),
+        }
+    }
+
+    fn check_at(&self, now: Instant) -> Result<(), BreakerOpen> {
+        let mut state = self.state.lock().unwrap();
+        let Some(until) = state.open_until else {
+            return Ok(());
+        };
+        if now < until {
+            return Err(BreakerOpen {
+                remaining: until - now,
+            });
+        }
+        // Start over, so the failures that tripped it don't trip it again.
+        state.open_until = None;
+        state.outcomes.clear();
+        drop(state);
+        let _ = self.events.send(BreakerEvent::Reset);
+        Ok(())
+    }
+
+    fn record_at(&self, now: Instant, failed: bool) {
+        let mut state = self.state.lock().unwrap();
+        if state.open_until.is_some() {
+            return;
+        }
+        state.outcomes.push_back((now, failed));
+        while state
+            .outcomes
+            .front()
+            .is_some_and(|(at, _)| now.duration_since(*at) > self.window)
+        {
+            state.outcomes.pop_front();
+        }
+
+        let orders = state.outcomes.len();
+        if orders < self.min_orders {
+            return;
+        }
+        let failures = state.outcomes.iter().filter(|(_, failed)| *failed).count();
+        let error_rate = Decimal::from(failures) / Decimal::from(orders);
+        if error_rate < self.max_error_rate {
+            return;
+        }
+        state.open_until = Some(now + self.cooldown);
+        drop(state);
+        log::warn!("circuit breaker tripped: {failures} of {orders} orders failed");
+        let _ = self.events.send(BreakerEvent::Tripped {
+            error_rate,
+            failures,
+            orders,
+            cooldown: self.cooldown,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_circuit_breaker() {
+        let breaker = CircuitBreaker::new()
+            .min_orders(4)
+            .cooldown(Duration::from_secs(30));
+        let mut events = breaker.subscribe();
+        let start = Instant::now();
+
+        breaker.record_at(start, true);
+        breaker.record_at(start, true);
+        breaker.record_at(start, false);
+        // Too few orders to judge yet.
+        assert_eq!(breaker.check_at(start), Ok(()));
+
+        breaker.record_at(start, true);
+        assert_eq!(
+            breaker.check_at(start + Duration::from_secs(10)),
+            Err(BreakerOpen {
+                remaining: Duration::from_secs(20)
+            })
+        );
+        assert_eq!(
+            events.try_recv().unwrap(),
+            BreakerEvent::Tripped {
+                error_rate: dec!(0.75),
+                failures: 3,
+                orders: 4,
+                cooldown: Duration::from_secs(30),
+            }
+        );
+
+        assert_eq!(breaker.check_at(start + Duration::from_secs(30)), Ok(()));
+        assert_eq!(events.try_recv().unwrap(), BreakerEvent::Reset);
+
+        // Failures older than the window no longer count.
+        let later = start + Duration::from_secs(31);
+        for _ in 0..3 {
+            breaker.record_at(later, true);
+        }
+        let much_later = later + DEFAULT_WINDOW + Duration::from_secs(1);
+        for _ in 0..4 {
+            breaker.record_at(much_later, false);
+        }
+        assert_eq!(breaker.check_at(much_later), Ok(()));
+    }
+}
@@ -0,0 +1,296 @@
+//! Read-through cache for static info responses.
+//!
+//! Market metadata (`meta`, `spotMeta`, `perpDexs`) changes a few times a day at most,
+//! yet every [`perps`](super::HttpClient::perps) or [`spot`](super::HttpClient::spot)
+//! call fetches it again and spends request weight. A short-lived process such as a
+//! CLI invocation pays this on every run. An [`InfoCache`] attached to the client
+//! serves these responses from a [`CacheStore`] while they are younger than the TTL.
+//! Past the TTL the request is revalidated with `If-None-Match` / `If-Modified-Since`
+//! when the server sent an `ETag` or `Last-Modified`, so an unchanged response costs a
+//! `304` rather than a full body.
+//!
+//! [`MemoryStore`] lives as long as the client; [`DiskStore`] persists across
+//! processes.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use hypersdk::hypercore::{Chain, HttpClient, cache::InfoCache};
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let cache = InfoCache::disk("/tmp/hypersdk-cache", Duration::from_secs(300));
+//! let client = HttpClient::builder(Chain::Mainnet).cache(cache).build();
+//!
+//! // Only the first run within five minutes hits the API.
+//! let perps = client.perps().await?;
+//! println!("{} perps", perps.len());
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    collections::HashMap,
+    fmt, fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use alloy::primitives::keccak256;
+use chrono::Utc;
+use reqwest::header::{self, HeaderMap, HeaderValue};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// A cached response body with its validators.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CachedResponse {
+    /// Response body
+    pub body: String,
+    /// `ETag` header, if the server sent one
+    pub etag: Option<String>,
+    /// `Last-Modified` header, if the server sent one
+    pub last_modified: Option<String>,
+    /// When the response was fetched or last revalidated, in milliseconds
+    pub fetched_at: u64,
+}
+
+impl CachedResponse {
+    /// Creates an entry for a response fetched now.
+    #[must_use]
+    pub fn new(body: String, headers: &HeaderMap) -> Self {
+        let header = |name| {
+            headers
+                .get(name)
+                .and_then(|value: &HeaderValue| value.to_str().ok())
+                .map(str::to_owned)
+        };
+        Self {
+            body,
+            etag: header(header::ETAG),
+            last_modified: header(header::LAST_MODIFIED),
+            fetched_at: now_ms(),
+        }
+    }
+
+    /// Returns true if the entry is younger than `ttl`.
+    #[must_use]
+    pub fn is_fresh(&self, ttl: Duration) -> bool {
+        now_ms().saturating_sub(self.fetched_at) < ttl.as_millis() as u64
+    }
+
+    /// Returns the headers that revalidate this entry.
+    #[must_use]
+    pub fn conditional_headers(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        let validators = [
+            (header::IF_NONE_MATCH, &self.etag),
+            (header::IF_MODIFIED_SINCE, &self.last_modified),
+        ];
+        for (name, value) in validators {
+            if let Some(value) = value.as_deref().and_then(|v| HeaderValue::from_str(v).ok()) {
+                headers.insert(name, value);
+            }
+        }
+        headers
+    }
+
+    /// Marks the entry as revalidated now.
+    pub fn touch(&mut self) {
+        self.fetched_at = now_ms();
+    }
+}
+
+/// Storage backing an [`InfoCache`].
+pub trait CacheStore: Send + Sync {
+    /// Returns the entry stored under `key`.
+    fn load(&self, key: &str) -> Option<CachedResponse>;
+
+    /// Stores `response` under `key`, replacing any previous entry.
+    fn store(&self, key: &str, response: &CachedResponse);
+}
+
+/// In-memory store, shared by the clients holding the same [`InfoCache`].
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+    entries: Mutex<HashMap<String, CachedResponse>>,
+}
+
+impl CacheStore for MemoryStore {
+    fn load(&self, key: &str) -> Option<CachedResponse> {
+        self.entries.lock().unwrap().get(key).cloned()
+    }
+
+    fn store(&self, key: &str, response: &CachedResponse) {
+        self.entries
+            .lock()
+            .unwrap()
+            .insert(key.to_owned(), response.clone());
+    }
+}
+
+/// On-disk store keeping one JSON file per entry in a directory.
+///
+/// The directory is created on first write. Unreadable entries are treated as
+/// missing and failed writes are logged, so a broken cache directory only costs the
+/// requests it would have saved.
+#[derive(Debug, Clone)]
+pub struct DiskStore {
+    dir: PathBuf,
+}
+
+impl DiskStore {
+    /// Creates a store in `dir`.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Returns the cache directory.
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Deletes every entry.
+    pub fn clear(&self) -> std::io::Result<()> {
+        match fs::remove_dir_all(&self.dir) {
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            res => res,
+        }
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{key}.json"))
+    }
+
+    fn write(&self, key: &str, response: &CachedResponse) -> std::io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        // Write then rename, so a concurrent reader never sees a partial file.
+        let tmp = self.dir.join(format!("{key}.{}.tmp", std::process::id()));
+        fs::write(&tmp, serde_json::to_vec(response)?)?;
+        fs::rename(&tmp, self.path(key))
+    }
+}
+
+impl CacheStore for DiskStore {
+    fn load(&self, key: &str) -> Option<CachedResponse> {
+        let bytes = fs::read(self.path(key)).ok()?;
+        serde_json::from_slice(&bytes).ok()
+    }
+
+    fn store(&self, key: &str, response: &CachedResponse) {
+        if let Err(err) = self.write(key, response) {
+            log::warn!(
+                "failed to write cache entry to {}: {err}",
+                self.dir.display()
+            );
+        }
+    }
+}
+
+/// Read-through cache of static info responses.
+///
+/// Cloning shares the store.
+#[derive(Clone)]
+pub struct InfoCache {
+    store: Arc<dyn CacheStore>,
+    ttl: Duration,
+}
+
+impl fmt::Debug for InfoCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InfoCache").field("ttl", &self.ttl).finish()
+    }
+}
+
+impl InfoCache {
+    /// Creates a cache over `store`, serving entries younger than `ttl` without a
+    /// request.
+    pub fn new(store: impl CacheStore + 'static, ttl: Duration) -> Self {
+        Self {
+            store: Arc::new(store),
+            ttl,
+        }
+    }
+
+    /// Creates an in-memory cache.
+    #[must_use]
+    pub fn memory(ttl: Duration) -> Self {
+        Self::new(MemoryStore::default(), ttl)
+    }
+
+    /// Creates a cache persisted in `dir`.
+    pub fn disk(dir: impl Into<PathBuf>, ttl: Duration) -> Self {
+        Self::new(DiskStore::new(dir), ttl)
+    }
+
+    /// Returns how long entries are served without revalidation.
+    #[must_use]
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Returns the key of a request: a hash of the URL and the request body.
+    #[must_use]
+    pub fn key(url: &Url, body: &[u8]) -> String {
+        let mut bytes = url.as_str().as_bytes().to_vec();
+        bytes.push(b'\n');
+        bytes.extend_from_slice(body);
+        keccak256(bytes).to_string()
+    }
+
+    /// Returns the entry stored under `key`.
+    #[must_use]
+    pub fn load(&self, key: &str) -> Option<CachedResponse> {
+        self.store.load(key)
+    }
+
+    /// Stores `response` under `key`.
+    pub fn store(&self, key: &str, response: &CachedResponse) {
+        self.store.store(key, response);
+    }
+}
+
+fn now_ms() -> u64 {
+    Utc::now().timestamp_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_entries() {
+        let url: Url = "https://api.hyperliquid.xyz/info".parse().unwrap();
+        let key = InfoCache::key(&url, br#"{"type":"meta"}"#);
+        assert_ne!(key, InfoCache::key(&url, br#"{"type":"spotMeta"}"#));
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::ETAG, HeaderValue::from_static("\"abc\""));
+        let mut response = CachedResponse::new("[]".into(), &headers);
+        assert!(response.is_fresh(Duration::from_secs(60)));
+        assert_eq!(
+            response.conditional_headers()[header::IF_NONE_MATCH],
+            "\"abc\""
+        );
+        assert!(
+            !response
+                .conditional_headers()
+                .contains_key(header::IF_MODIFIED_SINCE)
+        );
+
+        response.fetched_at = 0;
+        assert!(!response.is_fresh(Duration::from_secs(60)));
+        response.touch();
+        assert!(response.is_fresh(Duration::from_secs(60)));
+
+        let dir = std::env::temp_dir().join(format!("hypersdk-cache-{}", std::process::id()));
+        let disk = InfoCache::disk(&dir, Duration::from_secs(60));
+        assert_eq!(disk.load(&key), None);
+        disk.store(&key, &response);
+        assert_eq!(disk.load(&key), Some(response));
+        DiskStore::new(&dir).clear().unwrap();
+    }
+}
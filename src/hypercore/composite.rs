@@ -0,0 +1,234 @@
+//! Composite series for an underlying listed on several HIP-3 DEXes.
+//!
+//! HIP-3 lets anyone deploy a perp DEX, so the same underlying can trade as
+//! `"xyz:TSLA"` on one DEX and `"abc:TSLA"` on another, each with its own book and a
+//! fraction of the liquidity. Looking at any single listing understates volume and
+//! overweights thin venues. This module merges the listings into one series: candles
+//! sharing an open time are combined into a [`CompositeCandle`] whose prices are
+//! weighted by each listing's volume, and trades are bucketed the same way.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use hypersdk::hypercore::{self, CandleInterval};
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let client = hypercore::mainnet();
+//! let end = chrono::Utc::now().timestamp_millis() as u64;
+//! let start = end - 24 * 60 * 60 * 1000;
+//!
+//! let candles = client
+//!     .composite_candles("TSLA", CandleInterval::OneHour, start, end)
+//!     .await?;
+//! for candle in candles {
+//!     println!(
+//!         "{} close={} volume={} venues={:?}",
+//!         candle.open_time, candle.close, candle.volume, candle.venues
+//!     );
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::BTreeMap;
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use super::types::{Candle, CandleInterval, Trade};
+
+/// Returns the underlying symbol of a market name, without its DEX prefix.
+///
+/// `"xyz:TSLA"` and `"TSLA"` both return `"TSLA"`.
+#[must_use]
+pub fn underlying(coin: &str) -> &str {
+    coin.split_once(':').map_or(coin, |(_, symbol)| symbol)
+}
+
+/// A candle merged across every listing of an underlying.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompositeCandle {
+    /// Underlying symbol, without DEX prefix
+    pub coin: String,
+    /// Candle open time (milliseconds)
+    pub open_time: u64,
+    /// Candle close time (milliseconds)
+    pub close_time: u64,
+    /// Volume-weighted open price
+    pub open: Decimal,
+    /// Volume-weighted high price
+    pub high: Decimal,
+    /// Volume-weighted low price
+    pub low: Decimal,
+    /// Volume-weighted close price
+    pub close: Decimal,
+    /// Total volume across listings
+    pub volume: Decimal,
+    /// Total number of trades across listings
+    pub num_trades: u64,
+    /// Listings that traded in this candle
+    pub venues: Vec<String>,
+}
+
+/// Running volume-weighted sums for one bucket.
+#[derive(Default)]
+struct Bucket {
+    close_time: u64,
+    open: Decimal,
+    high: Decimal,
+    low: Decimal,
+    close: Decimal,
+    volume: Decimal,
+    num_trades: u64,
+    // Unweighted sums, used when the bucket has no volume.
+    count: Decimal,
+    plain: [Decimal; 4],
+    venues: Vec<String>,
+}
+
+impl Bucket {
+    fn add(&mut self, candle: &Candle) {
+        let weight = candle.volume;
+        self.close_time = self.close_time.max(candle.close_time);
+        self.open += candle.open * weight;
+        self.high += candle.high * weight;
+        self.low += candle.low * weight;
+        self.close += candle.close * weight;
+        self.volume += weight;
+        self.num_trades += candle.num_trades;
+        self.count += Decimal::ONE;
+        for (sum, px) in
+            self.plain
+                .iter_mut()
+                .zip([candle.open, candle.high, candle.low, candle.close])
+        {
+            *sum += px;
+        }
+        if !self.venues.contains(&candle.coin) {
+            self.venues.push(candle.coin.clone());
+        }
+    }
+
+    fn finish(mut self, coin: &str, open_time: u64) -> CompositeCandle {
+        let [open, high, low, close] = if self.volume.is_zero() {
+            self.plain.map(|sum| sum / self.count)
+        } else {
+            [self.open, self.high, self.low, self.close].map(|sum| sum / self.volume)
+        };
+        self.venues.sort();
+        CompositeCandle {
+            coin: coin.to_owned(),
+            open_time,
+            close_time: self.close_time,
+            open,
+            high,
+            low,
+            close,
+            volume: self.volume,
+            num_trades: self.num_trades,
+            venues: self.venues,
+        }
+    }
+}
+
+/// Merges candles of several listings into a composite series, oldest first.
+///
+/// Candles are grouped by open time, so every listing should use the same interval.
+/// Prices are weighted by each listing's volume in that candle; a candle where no
+/// listing traded falls back to equal weights. The composite is named after the
+/// underlying of the first candle.
+#[must_use]
+pub fn merge_candles<'a>(candles: impl IntoIterator<Item = &'a Candle>) -> Vec<CompositeCandle> {
+    let mut coin = None;
+    let mut buckets: BTreeMap<u64, Bucket> = BTreeMap::new();
+    for candle in candles {
+        coin.get_or_insert_with(|| underlying(&candle.coin).to_owned());
+        buckets.entry(candle.open_time).or_default().add(candle);
+    }
+    let coin = coin.unwrap_or_default();
+    buckets
+        .into_iter()
+        .map(|(open_time, bucket)| bucket.finish(&coin, open_time))
+        .collect()
+}
+
+/// Buckets trades of several listings into composite candles, oldest first.
+///
+/// Each listing's trades in a bucket form a candle of their own, which are then
+/// merged with [`merge_candles`]. Buckets without trades are skipped.
+#[must_use]
+pub fn merge_trades<'a>(
+    trades: impl IntoIterator<Item = &'a Trade>,
+    interval: CandleInterval,
+) -> Vec<CompositeCandle> {
+    let width = (interval.to_duration().as_millis() as u64).max(1);
+    let mut trades: Vec<&Trade> = trades.into_iter().collect();
+    trades.sort_by_key(|trade| (trade.time, trade.tid));
+
+    let mut candles: BTreeMap<(u64, &str), Candle> = BTreeMap::new();
+    for trade in trades {
+        let open_time = trade.time - trade.time % width;
+        candles
+            .entry((open_time, trade.coin.as_str()))
+            .and_modify(|candle| {
+                candle.high = candle.high.max(trade.px);
+                candle.low = candle.low.min(trade.px);
+                candle.close = trade.px;
+                candle.volume += trade.sz;
+                candle.num_trades += 1;
+            })
+            .or_insert_with(|| Candle {
+                open_time,
+                close_time: open_time + width - 1,
+                coin: trade.coin.clone(),
+                interval: interval.to_string(),
+                open: trade.px,
+                high: trade.px,
+                low: trade.px,
+                close: trade.px,
+                volume: trade.sz,
+                num_trades: 1,
+            });
+    }
+    merge_candles(candles.values())
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::dec;
+
+    use super::*;
+
+    #[test]
+    fn test_merge_candles() {
+        let candle = |coin: &str, open_time, close, volume| Candle {
+            open_time,
+            close_time: open_time + 59_999,
+            coin: coin.into(),
+            interval: "1m".into(),
+            open: close,
+            high: close,
+            low: close,
+            close,
+            volume,
+            num_trades: 1,
+        };
+        let candles = [
+            candle("xyz:TSLA", 0, dec!(100), dec!(3)),
+            candle("abc:TSLA", 0, dec!(104), dec!(1)),
+            candle("abc:TSLA", 60_000, dec!(105), dec!(0)),
+            candle("xyz:TSLA", 60_000, dec!(101), dec!(0)),
+        ];
+
+        let merged = merge_candles(&candles);
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0].coin, "TSLA");
+        assert_eq!(merged[0].close, dec!(101));
+        assert_eq!(merged[0].volume, dec!(4));
+        assert_eq!(merged[0].num_trades, 2);
+        assert_eq!(merged[0].venues, ["abc:TSLA", "xyz:TSLA"]);
+        // Without volume the listings are weighted equally.
+        assert_eq!(merged[1].close, dec!(103));
+    }
+}
@@ -0,0 +1,384 @@
+//! Client-side conditional orders.
+//!
+//! The exchange only knows take-profit and stop-loss triggers on the mark price. A
+//! [`ConditionalEngine`] holds orders armed with richer [`Condition`]s over the
+//! market data feeds (a price touched, a tight enough spread, a time window) and
+//! releases them when their condition holds. Orders armed together with
+//! [`arm_oco`](ConditionalEngine::arm_oco) form a one-cancels-other group: the first
+//! leg to trigger disarms the others.
+//!
+//! The engine doesn't send anything; place the [`Triggered`] batches with
+//! [`HttpClient::place`](crate::hypercore::HttpClient::place).
+//!
+//! # Example
+//!
+//! ```no_run
+//! use futures::StreamExt;
+//! use hypersdk::hypercore::{
+//!     self, NonceHandler, PrivateKeySigner,
+//!     conditional::{Condition, ConditionalEngine},
+//!     types::*,
+//!     ws::Event,
+//! };
+//!
+//! # async fn example(take_profit: BatchOrder, stop: BatchOrder) -> anyhow::Result<()> {
+//! let client = hypercore::mainnet();
+//! let signer: PrivateKeySigner = "your_key".parse()?;
+//! let nonces = NonceHandler::default();
+//!
+//! let mut engine = ConditionalEngine::new();
+//! engine.arm_oco([
+//!     (Condition::at_or_above("BTC", hypersdk::dec!(110000)), take_profit),
+//!     (Condition::at_or_below("BTC", hypersdk::dec!(95000)), stop),
+//! ]);
+//!
+//! let mut ws = hypercore::mainnet_ws();
+//! ws.subscribe(Subscription::Bbo { coin: "BTC".into() });
+//! while let Some(event) = ws.next().await {
+//!     let Event::Message(msg) = event else { continue };
+//!     engine.update(&msg);
+//!     let now = chrono::Utc::now().timestamp_millis() as u64;
+//!     for triggered in engine.poll(now) {
+//!         client
+//!             .place(&signer, triggered.batch, nonces.next(), None, None)
+//!             .await?;
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+
+use rust_decimal::Decimal;
+
+use super::types::{BatchOrder, Incoming};
+
+/// A predicate over market data and time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Condition {
+    /// The last price of `coin` is at or above `px`.
+    PriceAtOrAbove {
+        /// Market symbol
+        coin: String,
+        /// Price to touch
+        px: Decimal,
+    },
+    /// The last price of `coin` is at or below `px`.
+    PriceAtOrBelow {
+        /// Market symbol
+        coin: String,
+        /// Price to touch
+        px: Decimal,
+    },
+    /// The bid-ask spread of `coin` is at most `max`, in price units.
+    SpreadAtMost {
+        /// Market symbol
+        coin: String,
+        /// Widest accepted spread
+        max: Decimal,
+    },
+    /// The time is within `[start, end)`, in milliseconds.
+    TimeWindow {
+        /// Start of the window
+        start: u64,
+        /// End of the window
+        end: u64,
+    },
+    /// Every condition holds.
+    All(Vec<Condition>),
+    /// At least one condition holds.
+    Any(Vec<Condition>),
+}
+
+impl Condition {
+    /// The last price of `coin` touches `px` from below.
+    #[must_use]
+    pub fn at_or_above(coin: impl Into<String>, px: Decimal) -> Self {
+        Self::PriceAtOrAbove {
+            coin: coin.into(),
+            px,
+        }
+    }
+
+    /// The last price of `coin` touches `px` from above.
+    #[must_use]
+    pub fn at_or_below(coin: impl Into<String>, px: Decimal) -> Self {
+        Self::PriceAtOrBelow {
+            coin: coin.into(),
+            px,
+        }
+    }
+
+    /// The time is at or after `start` (milliseconds).
+    #[must_use]
+    pub fn after(start: u64) -> Self {
+        Self::TimeWindow {
+            start,
+            end: u64::MAX,
+        }
+    }
+
+    /// Both `self` and `other` hold.
+    #[must_use]
+    pub fn and(self, other: Self) -> Self {
+        match self {
+            Self::All(mut all) => {
+                all.push(other);
+                Self::All(all)
+            }
+            this => Self::All(vec![this, other]),
+        }
+    }
+
+    /// Either `self` or `other` holds.
+    #[must_use]
+    pub fn or(self, other: Self) -> Self {
+        match self {
+            Self::Any(mut any) => {
+                any.push(other);
+                Self::Any(any)
+            }
+            this => Self::Any(vec![this, other]),
+        }
+    }
+
+    /// Returns whether the condition holds on `market` at `now`.
+    ///
+    /// Conditions on a coin without data yet don't hold.
+    fn holds(&self, market: &MarketState, now: u64) -> bool {
+        match self {
+            Self::PriceAtOrAbove { coin, px } => {
+                market.last_px(coin).is_some_and(|last| last >= *px)
+            }
+            Self::PriceAtOrBelow { coin, px } => {
+                market.last_px(coin).is_some_and(|last| last <= *px)
+            }
+            Self::SpreadAtMost { coin, max } => {
+                market.spread(coin).is_some_and(|spread| spread <= *max)
+            }
+            Self::TimeWindow { start, end } => (*start..*end).contains(&now),
+            Self::All(all) => all.iter().all(|condition| condition.holds(market, now)),
+            Self::Any(any) => any.iter().any(|condition| condition.holds(market, now)),
+        }
+    }
+}
+
+/// Latest prices and spreads seen on the feeds.
+#[derive(Debug, Default)]
+struct MarketState {
+    last_px: HashMap<String, Decimal>,
+    spread: HashMap<String, Decimal>,
+}
+
+impl MarketState {
+    fn last_px(&self, coin: &str) -> Option<Decimal> {
+        self.last_px.get(coin).copied()
+    }
+
+    fn spread(&self, coin: &str) -> Option<Decimal> {
+        self.spread.get(coin).copied()
+    }
+
+    fn book(&mut self, coin: &str, mid: Option<Decimal>, spread: Option<Decimal>) {
+        if let Some(mid) = mid {
+            self.last_px.insert(coin.to_owned(), mid);
+        }
+        if let Some(spread) = spread {
+            self.spread.insert(coin.to_owned(), spread);
+        }
+    }
+}
+
+/// An order released by [`ConditionalEngine::poll`].
+#[derive(Debug, Clone)]
+pub struct Triggered {
+    /// Id returned when the order was armed
+    pub id: u64,
+    /// Orders to place
+    pub batch: BatchOrder,
+    /// Ids of the OCO siblings disarmed by this trigger
+    pub cancelled: Vec<u64>,
+}
+
+#[derive(Debug)]
+struct Armed {
+    id: u64,
+    group: Option<u64>,
+    condition: Condition,
+    batch: BatchOrder,
+}
+
+/// Holds armed orders and releases them when their conditions hold.
+#[derive(Debug, Default)]
+pub struct ConditionalEngine {
+    market: MarketState,
+    armed: Vec<Armed>,
+    next_id: u64,
+}
+
+impl ConditionalEngine {
+    /// Creates an engine with no armed orders.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Arms `batch` to be released once `condition` holds, returning its id.
+    pub fn arm(&mut self, condition: Condition, batch: BatchOrder) -> u64 {
+        self.push(None, condition, batch)
+    }
+
+    /// Arms one-cancels-other legs, returning their ids in order.
+    ///
+    /// When a leg triggers, the other legs are disarmed. If several hold on the same
+    /// poll, only the first one armed triggers.
+    pub fn arm_oco(&mut self, legs: impl IntoIterator<Item = (Condition, BatchOrder)>) -> Vec<u64> {
+        let group = self.next_id;
+        legs.into_iter()
+            .map(|(condition, batch)| self.push(Some(group), condition, batch))
+            .collect()
+    }
+
+    /// Disarms an order, returning it if it was still armed.
+    ///
+    /// Disarming one leg of an OCO group leaves the other legs armed.
+    pub fn disarm(&mut self, id: u64) -> Option<BatchOrder> {
+        let index = self.armed.iter().position(|armed| armed.id == id)?;
+        Some(self.armed.remove(index).batch)
+    }
+
+    /// Returns the ids of the armed orders.
+    pub fn armed(&self) -> impl Iterator<Item = u64> + '_ {
+        self.armed.iter().map(|armed| armed.id)
+    }
+
+    /// Updates market state from a message.
+    ///
+    /// Trades and mids set the last price; books and BBOs set the last price to their
+    /// mid and record their spread. Other messages are ignored.
+    pub fn update(&mut self, msg: &Incoming) {
+        match msg {
+            Incoming::L2Book(book) => self.market.book(&book.coin, book.mid(), book.spread()),
+            Incoming::Bbo(bbo) => self.market.book(&bbo.coin, bbo.mid(), bbo.spread()),
+            Incoming::Trades(trades) => {
+                for trade in trades {
+                    self.market.last_px.insert(trade.coin.clone(), trade.px);
+                }
+            }
+            Incoming::AllMids { mids, .. } => {
+                for (coin, mid) in mids {
+                    self.market.last_px.insert(coin.clone(), *mid);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Releases the orders whose conditions hold at `now` (milliseconds).
+    ///
+    /// Call it after every [`update`](Self::update), and on a timer if any condition
+    /// is time-based.
+    pub fn poll(&mut self, now: u64) -> Vec<Triggered> {
+        let holding: Vec<u64> = self
+            .armed
+            .iter()
+            .filter(|armed| armed.condition.holds(&self.market, now))
+            .map(|armed| armed.id)
+            .collect();
+
+        let mut triggered = vec![];
+        for id in holding {
+            // Gone if an earlier OCO sibling triggered on this poll.
+            let Some(index) = self.armed.iter().position(|armed| armed.id == id) else {
+                continue;
+            };
+            let armed = self.armed.remove(index);
+            let mut cancelled = vec![];
+            if let Some(group) = armed.group {
+                self.armed.retain(|other| {
+                    let sibling = other.group == Some(group);
+                    if sibling {
+                        cancelled.push(other.id);
+                    }
+                    !sibling
+                });
+            }
+            triggered.push(Triggered {
+                id,
+                batch: armed.batch,
+                cancelled,
+            });
+        }
+        triggered
+    }
+
+    fn push(&mut self, group: Option<u64>, condition: Condition, batch: BatchOrder) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.armed.push(Armed {
+            id,
+            group,
+            condition,
+            batch,
+        });
+        id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::dec;
+
+    use super::*;
+    use crate::hypercore::types::{Bbo, BookLevel, OrderGrouping};
+
+    fn batch() -> BatchOrder {
+        BatchOrder {
+            orders: vec![],
+            grouping: OrderGrouping::Na,
+            builder: None,
+        }
+    }
+
+    fn bbo(bid: Decimal, ask: Decimal) -> Incoming {
+        let level = |px| BookLevel {
+            px,
+            sz: dec!(1),
+            n: 1,
+        };
+        Incoming::Bbo(Bbo {
+            coin: "BTC".into(),
+            time: 0,
+            bbo: (Some(level(bid)), Some(level(ask))),
+        })
+    }
+
+    #[test]
+    fn test_conditional_engine() {
+        let mut engine = ConditionalEngine::new();
+        let legs = engine.arm_oco([
+            (Condition::at_or_above("BTC", dec!(110)), batch()),
+            (Condition::at_or_below("BTC", dec!(90)), batch()),
+        ]);
+        let timed = engine.arm(
+            Condition::after(1_000).and(Condition::SpreadAtMost {
+                coin: "BTC".into(),
+                max: dec!(1),
+            }),
+            batch(),
+        );
+
+        engine.update(&bbo(dec!(99), dec!(101)));
+        assert!(engine.poll(0).is_empty());
+        // The spread is too wide for the timed order.
+        assert!(engine.poll(1_000).is_empty());
+
+        engine.update(&bbo(dec!(89), dec!(90)));
+        let triggered = engine.poll(1_000);
+        let ids: Vec<_> = triggered.iter().map(|t| t.id).collect();
+        assert_eq!(ids, [legs[1], timed]);
+        assert_eq!(triggered[0].cancelled, [legs[0]]);
+        assert_eq!(engine.armed().count(), 0);
+    }
+}
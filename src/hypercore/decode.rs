@@ -0,0 +1,323 @@
+//! Strict and lenient decoding of API responses.
+//!
+//! Hyperliquid adds response fields without notice. By default responses are decoded
+//! leniently: fields the typed structs in [`hypercore::types`](super::types) don't know
+//! about are ignored and logged at debug level, so a new field never breaks a
+//! production client. In strict mode they fail the request with [`UnknownFields`]
+//! instead, which is what a CI job watching for API changes wants.
+//!
+//! The mode is set per client with [`HttpClient::with_decode_mode`](super::HttpClient::with_decode_mode).
+//! It defaults to [`DecodeMode::Strict`] when the `strict` cargo feature is enabled and
+//! to [`DecodeMode::Lenient`] otherwise, so CI can run the same code with
+//! `--features strict`.
+//!
+//! To keep the unknown fields rather than drop them, decode into [`WithExtra`].
+//!
+//! Fields inside `#[serde(flatten)]` structs and internally tagged enums are buffered
+//! by serde before they reach the typed struct, so they are not reported.
+//!
+//! # Example
+//!
+//! ```
+//! use hypersdk::hypercore::{
+//!     decode::{DecodeMode, UnknownFields, WithExtra, decode},
+//!     types::UserBalance,
+//! };
+//!
+//! let json = r#"{"coin":"USDC","token":0,"hold":"0","total":"10","entryNtl":"0","isNew":true}"#;
+//!
+//! let balance: UserBalance = decode(json, DecodeMode::Lenient).unwrap();
+//! assert_eq!(balance.coin, "USDC");
+//!
+//! let err = decode::<UserBalance>(json, DecodeMode::Strict).unwrap_err();
+//! assert!(err.is::<UnknownFields>());
+//!
+//! let balance: WithExtra<UserBalance> = decode(json, DecodeMode::Strict).unwrap();
+//! assert_eq!(balance.extra["isNew"], true);
+//! ```
+
+use std::ops::{Deref, DerefMut};
+
+use serde::{Deserialize, Deserializer, de::DeserializeOwned};
+use serde_ignored::Path;
+use serde_json::{Map, Value};
+
+/// How unknown response fields are handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, derive_more::IsVariant)]
+pub enum DecodeMode {
+    /// Ignore unknown fields.
+    Lenient,
+    /// Fail with [`UnknownFields`].
+    Strict,
+}
+
+impl Default for DecodeMode {
+    fn default() -> Self {
+        if cfg!(feature = "strict") {
+            Self::Strict
+        } else {
+            Self::Lenient
+        }
+    }
+}
+
+/// A response contained fields the target type doesn't know about.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("unknown fields: {}", .0.join(", "))]
+pub struct UnknownFields(pub Vec<String>);
+
+/// Decodes JSON into `T`, handling unknown fields according to `mode`.
+///
+/// Unknown fields are reported by dotted path, e.g. `levels.0.1.foo`.
+pub fn decode<T: DeserializeOwned>(json: &str, mode: DecodeMode) -> anyhow::Result<T> {
+    let mut deserializer = serde_json::Deserializer::from_str(json);
+    let mut unknown = vec![];
+    let value = serde_ignored::deserialize(&mut deserializer, |path| {
+        unknown.push(segments(&path).join("."));
+    })?;
+    deserializer.end()?;
+
+    if !unknown.is_empty() {
+        match mode {
+            DecodeMode::Strict => return Err(UnknownFields(unknown).into()),
+            DecodeMode::Lenient => log::debug!("ignored unknown fields: {}", unknown.join(", ")),
+        }
+    }
+    Ok(value)
+}
+
+/// A decoded value together with the fields its type doesn't know about.
+///
+/// `extra` maps the dotted path of each unknown field to its raw JSON value. Decoding
+/// into `WithExtra` never fails on unknown fields, even in strict mode.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WithExtra<T> {
+    /// Decoded value
+    pub value: T,
+    /// Unknown fields by dotted path
+    pub extra: Map<String, Value>,
+}
+
+impl<T> Deref for WithExtra<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> DerefMut for WithExtra<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<'de, T> Deserialize<'de> for WithExtra<T>
+where
+    T: DeserializeOwned,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        let raw = Value::deserialize(deserializer)?;
+        let mut unknown = vec![];
+        let value = serde_ignored::deserialize(&raw, |path| unknown.push(segments(&path)))
+            .map_err(D::Error::custom)?;
+
+        let extra = unknown
+            .into_iter()
+            .filter_map(|path| Some((path.join("."), lookup(&raw, &path)?.clone())))
+            .collect();
+        Ok(Self { value, extra })
+    }
+}
+
+/// Splits a JSON array arriving in chunks into the JSON of its elements.
+///
+/// Lets a large array response be decoded one element at a time instead of being
+/// held whole in memory.
+#[derive(Debug, Default)]
+pub(crate) struct ArrayElements {
+    buf: Vec<u8>,
+    pos: usize,
+    start: usize,
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+    done: bool,
+}
+
+impl ArrayElements {
+    /// Appends `chunk` and returns the elements it completes.
+    pub(crate) fn feed(&mut self, chunk: &[u8]) -> anyhow::Result<Vec<String>> {
+        if self.done {
+            anyhow::ensure!(
+                chunk.iter().all(u8::is_ascii_whitespace),
+                "data after end of array"
+            );
+            return Ok(vec![]);
+        }
+        self.buf.extend_from_slice(chunk);
+
+        let mut elements = vec![];
+        while self.pos < self.buf.len() && !self.done {
+            let byte = self.buf[self.pos];
+            if self.in_string {
+                match byte {
+                    _ if self.escaped => self.escaped = false,
+                    b'\\' => self.escaped = true,
+                    b'"' => self.in_string = false,
+                    _ => {}
+                }
+            } else {
+                match byte {
+                    b'"' => self.in_string = true,
+                    b'[' | b'{' => {
+                        self.depth += 1;
+                        if self.depth == 1 {
+                            anyhow::ensure!(byte == b'[', "expected a JSON array");
+                            self.start = self.pos + 1;
+                        }
+                    }
+                    b']' | b'}' => {
+                        anyhow::ensure!(self.depth > 0, "unbalanced JSON array");
+                        self.depth -= 1;
+                        if self.depth == 0 {
+                            self.push(&mut elements)?;
+                            self.done = true;
+                        }
+                    }
+                    b',' if self.depth == 1 => {
+                        self.push(&mut elements)?;
+                        self.start = self.pos + 1;
+                    }
+                    _ if self.depth == 0 && !byte.is_ascii_whitespace() => {
+                        anyhow::bail!("expected a JSON array");
+                    }
+                    _ => {}
+                }
+            }
+            self.pos += 1;
+        }
+
+        // Drop the bytes of emitted elements, or everything up to the closing bracket.
+        let consumed = if self.done { self.pos } else { self.start };
+        self.buf.drain(..consumed);
+        self.pos -= consumed;
+        self.start -= consumed.min(self.start);
+        Ok(elements)
+    }
+
+    /// Fails if the array was not closed or is followed by more data.
+    pub(crate) fn finish(&self) -> anyhow::Result<()> {
+        anyhow::ensure!(self.done, "truncated JSON array");
+        anyhow::ensure!(
+            self.buf.iter().all(u8::is_ascii_whitespace),
+            "data after end of array"
+        );
+        Ok(())
+    }
+
+    fn push(&self, elements: &mut Vec<String>) -> anyhow::Result<()> {
+        let element = std::str::from_utf8(&self.buf[self.start..self.pos])?.trim();
+        if !element.is_empty() {
+            elements.push(element.to_owned());
+        }
+        Ok(())
+    }
+}
+
+/// Map keys and sequence indices leading to `path`.
+fn segments(path: &Path<'_>) -> Vec<String> {
+    let mut out = match path {
+        Path::Root => return vec![],
+        Path::Seq { parent, .. }
+        | Path::Map { parent, .. }
+        | Path::Some { parent }
+        | Path::NewtypeStruct { parent }
+        | Path::NewtypeVariant { parent } => segments(parent),
+    };
+    match path {
+        Path::Seq { index, .. } => out.push(index.to_string()),
+        Path::Map { key, .. } => out.push(key.clone()),
+        _ => {}
+    }
+    out
+}
+
+fn lookup<'a>(value: &'a Value, segments: &[String]) -> Option<&'a Value> {
+    segments
+        .iter()
+        .try_fold(value, |value, segment| match value {
+            Value::Object(map) => map.get(segment),
+            Value::Array(items) => items.get(segment.parse::<usize>().ok()?),
+            _ => None,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    struct Level {
+        px: String,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Book {
+        coin: String,
+        levels: Vec<Level>,
+    }
+
+    const BOOK: &str = r#"{"coin":"BTC","levels":[{"px":"1"},{"px":"2","n":3}],"time":5}"#;
+
+    #[test]
+    fn test_decode_modes() {
+        let book: Book = decode(BOOK, DecodeMode::Lenient).unwrap();
+        assert_eq!(book.coin, "BTC");
+        assert_eq!(book.levels[1].px, "2");
+
+        let err = decode::<Book>(BOOK, DecodeMode::Strict).unwrap_err();
+        let err = err.downcast::<UnknownFields>().unwrap();
+        assert_eq!(err.0, ["levels.1.n", "time"]);
+
+        let known = r#"{"coin":"BTC","levels":[]}"#;
+        assert!(decode::<Book>(known, DecodeMode::Strict).is_ok());
+    }
+
+    #[test]
+    fn test_array_elements_across_chunks() {
+        let json = br#" [{"px":"1","s":"a,]}"}, [1,2] ,"x\"y" ] "#;
+        let mut splitter = ArrayElements::default();
+        let mut elements = vec![];
+        for chunk in json.chunks(3) {
+            elements.extend(splitter.feed(chunk).unwrap());
+        }
+        splitter.finish().unwrap();
+        assert_eq!(elements, [r#"{"px":"1","s":"a,]}"}"#, "[1,2]", r#""x\"y""#]);
+
+        let mut empty = ArrayElements::default();
+        assert!(empty.feed(b"[]").unwrap().is_empty());
+        empty.finish().unwrap();
+
+        let mut truncated = ArrayElements::default();
+        truncated.feed(b"[1,").unwrap();
+        assert!(truncated.finish().is_err());
+    }
+
+    #[test]
+    fn test_with_extra_captures_unknown_fields() {
+        let book: WithExtra<Book> = decode(BOOK, DecodeMode::Strict).unwrap();
+        assert_eq!(book.coin, "BTC");
+        assert_eq!(
+            Value::Object(book.extra),
+            json!({"levels.1.n": 3, "time": 5})
+        );
+    }
+}
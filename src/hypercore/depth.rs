@@ -0,0 +1,230 @@
+//! Order book depth and market impact statistics.
+//!
+//! Liquidity research starts with how much size sits near the mid and what a given
+//! order would cost to fill. [`DepthStats`] reduces an [`L2Book`] snapshot to those
+//! numbers: the spread, the top-of-book sizes, the notional resting within bands of
+//! basis points around the mid, and the impact of sweeping the book for given
+//! notionals. Sampled on an interval, the rows form a CSV time series.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use hypersdk::{dec, hypercore::{self, depth::DepthStats}};
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let client = hypercore::mainnet();
+//! let book = client.l2_book("BTC".into(), None, None).await?;
+//! let stats = DepthStats::new(&book, &[dec!(10), dec!(50)], &[dec!(100000)]).unwrap();
+//! println!("{}", stats.csv_header());
+//! println!("{}", stats.csv_row());
+//! # Ok(())
+//! # }
+//! ```
+
+use rust_decimal::{Decimal, dec};
+use serde::{Deserialize, Serialize};
+
+use super::types::{BookLevel, L2Book};
+
+/// Basis points in one.
+const BPS: Decimal = dec!(10000);
+
+/// Notional resting within a band around the mid.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DepthBand {
+    /// Half-width of the band in basis points of the mid
+    pub bps: Decimal,
+    /// Bid notional priced at or above `mid - bps`
+    pub bid_ntl: Decimal,
+    /// Ask notional priced at or below `mid + bps`
+    pub ask_ntl: Decimal,
+}
+
+/// Cost of sweeping the book for a notional.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Impact {
+    /// Notional to fill, in quote units
+    pub notional: Decimal,
+    /// Average buy price above the mid in basis points; `None` if the asks are too thin
+    pub buy_bps: Option<Decimal>,
+    /// Average sell price below the mid in basis points; `None` if the bids are too thin
+    pub sell_bps: Option<Decimal>,
+}
+
+/// Depth and impact statistics of a book snapshot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DepthStats {
+    /// Market symbol
+    pub coin: String,
+    /// Snapshot time in milliseconds
+    pub time: u64,
+    /// Mid price
+    pub mid: Decimal,
+    /// Spread in basis points of the mid
+    pub spread_bps: Decimal,
+    /// Size at the best bid
+    pub best_bid_sz: Decimal,
+    /// Size at the best ask
+    pub best_ask_sz: Decimal,
+    /// Depth within each band, in the order given
+    pub bands: Vec<DepthBand>,
+    /// Impact of each notional, in the order given
+    pub impacts: Vec<Impact>,
+}
+
+impl DepthStats {
+    /// Computes the statistics of `book` for depth `bands` (in basis points) and
+    /// impact `notionals`.
+    ///
+    /// Returns `None` if either side of the book is empty.
+    #[must_use]
+    pub fn new(book: &L2Book, bands: &[Decimal], notionals: &[Decimal]) -> Option<Self> {
+        let best_bid = book.best_bid()?;
+        let best_ask = book.best_ask()?;
+        let mid = book.mid()?;
+
+        let bands = bands
+            .iter()
+            .map(|&bps| {
+                let offset = mid * bps / BPS;
+                DepthBand {
+                    bps,
+                    bid_ntl: notional_while(book.bids(), |px| px >= mid - offset),
+                    ask_ntl: notional_while(book.asks(), |px| px <= mid + offset),
+                }
+            })
+            .collect();
+        let impacts = notionals
+            .iter()
+            .map(|&notional| Impact {
+                notional,
+                buy_bps: average_px(book.asks(), notional).map(|px| (px - mid) / mid * BPS),
+                sell_bps: average_px(book.bids(), notional).map(|px| (mid - px) / mid * BPS),
+            })
+            .collect();
+
+        Some(Self {
+            coin: book.coin.clone(),
+            time: book.time,
+            mid,
+            spread_bps: (best_ask.px - best_bid.px) / mid * BPS,
+            best_bid_sz: best_bid.sz,
+            best_ask_sz: best_ask.sz,
+            bands,
+            impacts,
+        })
+    }
+
+    /// Returns the CSV header matching [`csv_row`](Self::csv_row).
+    #[must_use]
+    pub fn csv_header(&self) -> String {
+        let mut columns: Vec<String> = [
+            "time",
+            "coin",
+            "mid",
+            "spread_bps",
+            "best_bid_sz",
+            "best_ask_sz",
+        ]
+        .map(String::from)
+        .into();
+        for band in &self.bands {
+            columns.push(format!("bid_ntl_{}bps", band.bps));
+            columns.push(format!("ask_ntl_{}bps", band.bps));
+        }
+        for impact in &self.impacts {
+            columns.push(format!("buy_impact_bps_{}", impact.notional));
+            columns.push(format!("sell_impact_bps_{}", impact.notional));
+        }
+        columns.join(",")
+    }
+
+    /// Returns the statistics as a CSV row, rounded to 4 decimal places.
+    ///
+    /// Impacts the book is too thin for are left empty.
+    #[must_use]
+    pub fn csv_row(&self) -> String {
+        let round = |value: Decimal| value.round_dp(4).normalize().to_string();
+        let mut columns = vec![
+            self.time.to_string(),
+            self.coin.clone(),
+            self.mid.normalize().to_string(),
+            round(self.spread_bps),
+            self.best_bid_sz.normalize().to_string(),
+            self.best_ask_sz.normalize().to_string(),
+        ];
+        for band in &self.bands {
+            columns.push(round(band.bid_ntl));
+            columns.push(round(band.ask_ntl));
+        }
+        for impact in &self.impacts {
+            columns.push(impact.buy_bps.map(round).unwrap_or_default());
+            columns.push(impact.sell_bps.map(round).unwrap_or_default());
+        }
+        columns.join(",")
+    }
+}
+
+/// Sums the notional of the leading `levels` whose price passes `within`.
+fn notional_while(levels: &[BookLevel], within: impl Fn(Decimal) -> bool) -> Decimal {
+    levels
+        .iter()
+        .take_while(|level| within(level.px))
+        .map(|level| level.px * level.sz)
+        .sum()
+}
+
+/// Returns the average price of filling `notional` against `levels`.
+fn average_px(levels: &[BookLevel], notional: Decimal) -> Option<Decimal> {
+    if notional <= Decimal::ZERO {
+        return None;
+    }
+    let mut remaining = notional;
+    let mut size = Decimal::ZERO;
+    for level in levels {
+        let take = remaining.min(level.px * level.sz);
+        size += take / level.px;
+        remaining -= take;
+        if remaining.is_zero() {
+            return Some(notional / size);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_depth_stats() {
+        let level = |px, sz| BookLevel { px, sz, n: 1 };
+        let book = L2Book {
+            coin: "BTC".into(),
+            time: 1,
+            snapshot: true,
+            levels: [
+                vec![level(dec!(99.9), dec!(2)), level(dec!(99), dec!(10))],
+                vec![level(dec!(100.1), dec!(1)), level(dec!(101), dec!(10))],
+            ],
+        };
+        let stats =
+            DepthStats::new(&book, &[dec!(10), dec!(200)], &[dec!(100.1), dec!(5000)]).unwrap();
+
+        assert_eq!(stats.mid, dec!(100));
+        assert_eq!(stats.spread_bps, dec!(20));
+        assert_eq!(stats.bands[0].bid_ntl, dec!(199.8));
+        assert_eq!(stats.bands[0].ask_ntl, dec!(100.1));
+        assert_eq!(stats.bands[1].bid_ntl, dec!(1189.8));
+        assert_eq!(stats.bands[1].ask_ntl, dec!(1110.1));
+
+        // The first ask level fills 100.1 exactly.
+        assert_eq!(stats.impacts[0].buy_bps, Some(dec!(10)));
+        assert_eq!(stats.impacts[1].buy_bps, None);
+
+        let header = stats.csv_header();
+        let row = stats.csv_row();
+        assert_eq!(header.split(',').count(), row.split(',').count());
+        assert!(row.starts_with("1,BTC,100,20,2,1,199.8,100.1,"));
+    }
+}
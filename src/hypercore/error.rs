@@ -1,18 +1,91 @@
-use std::fmt;
+use std::{fmt, sync::Arc};
+
+use super::{Chain, Cloid};
 
 #[derive(Debug, thiserror::Error)]
 #[error("{0}")]
 pub struct ApiError(pub String);
 
+/// A user-signed action carries chain fields that don't match the chain signing it.
+///
+/// Signing such an action would produce a signature the exchange rejects with
+/// "signature verification failed", so it is caught before submission.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("{field} is {found:?} but {chain} signs with {expected:?}")]
+pub struct ChainMismatch {
+    /// Chain the action is being signed for
+    pub chain: Chain,
+    /// Mismatched action field (`signatureChainId` or `hyperliquidChain`)
+    pub field: &'static str,
+    /// Value expected for `chain`
+    pub expected: String,
+    /// Value found in the action
+    pub found: String,
+}
+
+/// Two orders would share a client order ID.
+///
+/// The exchange accepts a cloid that is already in use, after which cancelling or
+/// querying by that cloid no longer identifies a single order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateCloid {
+    /// The duplicated cloid
+    pub cloid: Cloid,
+    /// Whether it clashes with a live order rather than another order of the batch
+    pub live: bool,
+}
+
+impl fmt::Display for DuplicateCloid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.live {
+            write!(f, "cloid {} is used by a live order", self.cloid)
+        } else {
+            write!(f, "cloid {} is used twice in the batch", self.cloid)
+        }
+    }
+}
+
+impl std::error::Error for DuplicateCloid {}
+
+/// An exchange action was attempted on a read-only client.
+///
+/// See [`Client::read_only`](super::HttpClient::read_only).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("client is read-only: exchange actions are disabled")]
+pub struct ReadOnly;
+
 #[derive(Debug, Clone)]
 pub struct ActionError<T> {
     pub(crate) ids: Vec<T>,
     pub(crate) err: String,
+    pub(crate) source: Option<Arc<dyn std::error::Error + Send + Sync>>,
 }
 
 impl<T> ActionError<T> {
     pub fn new(ids: Vec<T>, err: String) -> Self {
-        Self { ids, err }
+        Self {
+            ids,
+            err,
+            source: None,
+        }
+    }
+
+    /// Creates an error caused by a typed `source`, which
+    /// [`downcast_source`](Self::downcast_source) gives back.
+    pub fn with_source<E>(ids: Vec<T>, source: E) -> Self
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        Self {
+            ids,
+            err: source.to_string(),
+            source: Some(Arc::new(source)),
+        }
+    }
+
+    /// Returns the typed error behind this one, such as a [`DuplicateCloid`].
+    pub fn downcast_source<E: std::error::Error + 'static>(&self) -> Option<&E> {
+        self.source.as_deref()?.downcast_ref()
     }
 
     pub fn message(&self) -> &str {
@@ -37,4 +110,13 @@ where
     }
 }
 
-impl<T> std::error::Error for ActionError<T> where T: fmt::Display + fmt::Debug {}
+impl<T> std::error::Error for ActionError<T>
+where
+    T: fmt::Display + fmt::Debug,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.source
+            .as_deref()
+            .map(|err| err as &(dyn std::error::Error + 'static))
+    }
+}
@@ -0,0 +1,320 @@
+//! Block explorer RPC.
+//!
+//! The info endpoint reports account state; the explorer RPC reports what landed on
+//! chain: the transactions of a block, a transaction by hash, and the recent
+//! transactions of a user, each with its action and whether it failed. Audit tools
+//! use it to check that an action submitted through the exchange endpoint was
+//! included as signed.
+//!
+//! The explorer also streams new blocks and transactions over its own WebSocket;
+//! [`ExplorerClient::websocket`] opens it as a regular [`WebSocket`], with the same
+//! reconnect and re-subscribe handling as the market data socket.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use hypersdk::hypercore;
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let explorer = hypercore::mainnet().explorer();
+//! let block = explorer.block_details(600_000_000).await?;
+//! for tx in &block.txs {
+//!     println!("{} {} {:?}", tx.hash, tx.action_type(), tx.error);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! ## Following the Chain
+//!
+//! ```no_run
+//! use futures::StreamExt;
+//! use hypersdk::hypercore;
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let user = "0x...".parse()?;
+//! let ws = hypercore::mainnet().explorer().websocket();
+//! let mut blocks = ws.explorer_blocks();
+//! let mut txs = ws.explorer_txs().filter(|tx| std::future::ready(tx.user == user));
+//!
+//! loop {
+//!     tokio::select! {
+//!         Some(block) = blocks.next() => println!("block {} with {} txs", block.height, block.num_txs),
+//!         Some(tx) = txs.next() => println!("{} {} {:?}", tx.hash, tx.action_type(), tx.error),
+//!         else => break,
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use alloy::primitives::{Address, B256};
+use anyhow::{Context, Result};
+use either::Either;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use super::{ApiError, Chain, WebSocket, types::api::Action};
+
+/// Returns the explorer RPC URL of `chain`.
+///
+/// For [`Chain::Local`], this is `/explorer` on the local node URL.
+pub fn explorer_url(chain: Chain) -> Url {
+    match chain {
+        Chain::Mainnet => "https://rpc.hyperliquid.xyz/explorer".parse().unwrap(),
+        Chain::Testnet => "https://rpc.hyperliquid-testnet.xyz/explorer"
+            .parse()
+            .unwrap(),
+        Chain::Local => {
+            let mut url = super::local_url();
+            url.set_path("/explorer");
+            url
+        }
+    }
+}
+
+/// A transaction as reported by the explorer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExplorerTx {
+    /// Block time in milliseconds
+    pub time: u64,
+    /// Account that sent the action
+    pub user: Address,
+    /// The action, or its raw JSON if this version doesn't know its type
+    #[serde(with = "either::serde_untagged")]
+    pub action: Either<Action, serde_json::Value>,
+    /// Block height
+    pub block: u64,
+    /// Transaction hash
+    pub hash: B256,
+    /// Why the action failed, if it did
+    pub error: Option<String>,
+}
+
+impl ExplorerTx {
+    /// Returns whether the action was applied.
+    #[must_use]
+    pub fn is_success(&self) -> bool {
+        self.error.is_none()
+    }
+
+    /// Returns the action's `type`, e.g. `"order"`.
+    #[must_use]
+    pub fn action_type(&self) -> String {
+        let value = match &self.action {
+            Either::Left(action) => serde_json::to_value(action).unwrap_or_default(),
+            Either::Right(value) => value.clone(),
+        };
+        value
+            .get("type")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default()
+            .to_owned()
+    }
+}
+
+/// A block and its transactions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockDetails {
+    /// Block height
+    pub height: u64,
+    /// Block time in milliseconds
+    pub block_time: u64,
+    /// Block hash
+    pub hash: B256,
+    /// Validator that proposed the block
+    pub proposer: Address,
+    /// Number of transactions
+    pub num_txs: u64,
+    /// Transactions in the block
+    pub txs: Vec<ExplorerTx>,
+}
+
+/// A new block, as streamed by the explorer WebSocket.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockSummary {
+    /// Block height
+    pub height: u64,
+    /// Block time in milliseconds
+    pub block_time: u64,
+    /// Block hash
+    pub hash: B256,
+    /// Validator that proposed the block
+    pub proposer: Address,
+    /// Number of transactions
+    pub num_txs: u64,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type")]
+enum ExplorerRequest {
+    #[serde(rename = "blockDetails")]
+    Block { height: u64 },
+    #[serde(rename = "txDetails")]
+    Tx { hash: B256 },
+    #[serde(rename = "userDetails")]
+    User { user: Address },
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BlockDetailsResponse {
+    block_details: BlockDetails,
+}
+
+#[derive(Deserialize)]
+struct TxDetailsResponse {
+    tx: ExplorerTx,
+}
+
+#[derive(Deserialize)]
+struct UserDetailsResponse {
+    txs: Vec<ExplorerTx>,
+}
+
+/// Client for the explorer RPC.
+///
+/// Create one with [`HttpClient::explorer`](crate::hypercore::HttpClient::explorer)
+/// to share its connection pool, or with [`new`](Self::new).
+#[derive(Debug, Clone)]
+pub struct ExplorerClient {
+    http_client: reqwest::Client,
+    url: Url,
+}
+
+impl ExplorerClient {
+    /// Creates a client for the explorer of `chain`.
+    #[must_use]
+    pub fn new(chain: Chain) -> Self {
+        Self::with_client(reqwest::Client::new(), explorer_url(chain))
+    }
+
+    pub(crate) fn with_client(http_client: reqwest::Client, url: Url) -> Self {
+        Self { http_client, url }
+    }
+
+    /// Sets the explorer URL, e.g. for a self-hosted node.
+    #[must_use]
+    pub fn with_url(mut self, url: Url) -> Self {
+        self.url = url;
+        self
+    }
+
+    /// Creates a connection to the explorer WebSocket, next to the RPC URL.
+    ///
+    /// Subscribe with [`Subscription::ExplorerBlock`](super::types::Subscription::ExplorerBlock)
+    /// and [`Subscription::ExplorerTxs`](super::types::Subscription::ExplorerTxs), or use
+    /// the typed [`explorer_blocks`](super::ws::Connection::explorer_blocks) and
+    /// [`explorer_txs`](super::ws::Connection::explorer_txs) feeds.
+    pub fn websocket(&self) -> WebSocket {
+        let mut url = self.url.clone();
+        let scheme = if url.scheme() == "http" { "ws" } else { "wss" };
+        let _ = url.set_scheme(scheme);
+        url.set_path("/ws");
+        WebSocket::new(url)
+    }
+
+    /// Returns the block at `height` with its transactions.
+    pub async fn block_details(&self, height: u64) -> Result<BlockDetails> {
+        let req = ExplorerRequest::Block { height };
+        let resp: BlockDetailsResponse = self.send("block_details", &req).await?;
+        Ok(resp.block_details)
+    }
+
+    /// Returns the transaction with `hash`.
+    pub async fn tx_details(&self, hash: B256) -> Result<ExplorerTx> {
+        let req = ExplorerRequest::Tx { hash };
+        let resp: TxDetailsResponse = self.send("tx_details", &req).await?;
+        Ok(resp.tx)
+    }
+
+    /// Returns the recent transactions sent by `user`.
+    pub async fn user_details(&self, user: Address) -> Result<Vec<ExplorerTx>> {
+        let req = ExplorerRequest::User { user };
+        let resp: UserDetailsResponse = self.send("user_details", &req).await?;
+        Ok(resp.txs)
+    }
+
+    async fn send<R>(&self, label: &str, req: &ExplorerRequest) -> Result<R>
+    where
+        R: for<'de> Deserialize<'de>,
+    {
+        let res = self
+            .http_client
+            .post(self.url.clone())
+            .json(req)
+            .send()
+            .await?;
+        let status = res.status();
+        let text = res.text().await?;
+        if !status.is_success() {
+            return Err(ApiError(format!("[{label}] HTTP {status} body={text}")).into());
+        }
+        serde_json::from_str(&text).with_context(|| format!("[{label}] body={text}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hypercore::types::{Incoming, Subscription};
+
+    #[test]
+    fn test_explorer_responses() {
+        let resp: TxDetailsResponse = serde_json::from_value(serde_json::json!({
+            "type": "txDetails",
+            "tx": {
+                "time": 1733958000123u64,
+                "user": "0x0000000000000000000000000000000000001234",
+                "action": {
+                    "type": "cancel",
+                    "cancels": [{"a": 0, "o": 42}]
+                },
+                "block": 600000000u64,
+                "hash": "0x1111111111111111111111111111111111111111111111111111111111111111",
+                "error": null
+            }
+        }))
+        .unwrap();
+        assert!(resp.tx.action.is_left());
+        assert_eq!(resp.tx.action_type(), "cancel");
+        assert!(resp.tx.is_success());
+
+        let resp: UserDetailsResponse = serde_json::from_value(serde_json::json!({
+            "type": "userDetails",
+            "txs": [{
+                "time": 1733958000123u64,
+                "user": "0x0000000000000000000000000000000000001234",
+                "action": {"type": "someFutureAction", "x": 1},
+                "block": 600000000u64,
+                "hash": "0x1111111111111111111111111111111111111111111111111111111111111111",
+                "error": "Insufficient margin"
+            }]
+        }))
+        .unwrap();
+        assert!(resp.txs[0].action.is_right());
+        assert_eq!(resp.txs[0].action_type(), "someFutureAction");
+        assert!(!resp.txs[0].is_success());
+
+        let msg: Incoming = serde_json::from_value(serde_json::json!({
+            "channel": "explorerBlock",
+            "data": [{
+                "height": 600000001u64,
+                "blockTime": 1733958000456u64,
+                "hash": "0x2222222222222222222222222222222222222222222222222222222222222222",
+                "proposer": "0x0000000000000000000000000000000000005678",
+                "numTxs": 12
+            }]
+        }))
+        .unwrap();
+        assert!(Subscription::ExplorerBlock.matches(&msg));
+        assert!(matches!(&msg, Incoming::ExplorerBlock(blocks) if blocks[0].num_txs == 12));
+
+        assert_eq!(
+            serde_json::to_value(ExplorerRequest::Block { height: 1 }).unwrap(),
+            serde_json::json!({"type": "blockDetails", "height": 1})
+        );
+    }
+}
@@ -0,0 +1,255 @@
+//! Funding settlement times.
+//!
+//! Hyperliquid settles perp funding at the top of every hour, while other venues
+//! settle every eight hours or on their own schedule. Strategies that trade around
+//! funding need the next settlement of each coin, and a signal shortly before and
+//! after it. [`FundingCalendar`] holds the next settlement per coin as the exchange
+//! reports it, and [`FundingSchedule::countdown`] streams [`FundingEvent`]s around
+//! each settlement.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use futures::StreamExt;
+//! use hypersdk::hypercore::{
+//!     self,
+//!     funding::{FundingPhase, FundingSchedule},
+//! };
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let client = hypercore::mainnet();
+//! let calendar = client.funding_calendar().await?;
+//! println!("BTC settles at {:?}", calendar.next_funding("BTC"));
+//!
+//! let lead = Duration::from_secs(30);
+//! let lag = Duration::from_secs(5);
+//! let events = FundingSchedule::HOURLY.countdown(lead, lag);
+//! futures::pin_mut!(events);
+//! while let Some(event) = events.next().await {
+//!     match event.phase {
+//!         FundingPhase::Upcoming => println!("funding settles at {}", event.settlement),
+//!         FundingPhase::Settled => println!("funding settled at {}", event.settlement),
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{collections::HashMap, time::Duration};
+
+use chrono::Utc;
+use futures::Stream;
+
+use super::types::{FundingVenue, PredictedFunding};
+
+/// A funding schedule settling at every multiple of a fixed interval since the Unix
+/// epoch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FundingSchedule {
+    interval: Duration,
+}
+
+impl FundingSchedule {
+    /// Hyperliquid's schedule: every hour on the hour.
+    pub const HOURLY: Self = Self::every(Duration::from_secs(60 * 60));
+
+    /// Creates a schedule settling every `interval`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `interval` is shorter than a millisecond.
+    #[must_use]
+    pub const fn every(interval: Duration) -> Self {
+        assert!(
+            interval.as_millis() > 0,
+            "funding interval must be positive"
+        );
+        Self { interval }
+    }
+
+    /// Returns the time between settlements.
+    #[must_use]
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Returns the first settlement strictly after `time` (milliseconds).
+    #[must_use]
+    pub fn next_after(&self, time: u64) -> u64 {
+        let interval = self.interval.as_millis() as u64;
+        (time / interval + 1) * interval
+    }
+
+    /// Returns the time left until the next settlement.
+    #[must_use]
+    pub fn until_next(&self) -> Duration {
+        let now = now_ms();
+        Duration::from_millis(self.next_after(now) - now)
+    }
+
+    /// Returns a stream of events `lead` before and `lag` after every settlement.
+    ///
+    /// The stream never ends. Events due while the consumer isn't polling are
+    /// delivered late rather than skipped, each carrying the time it was due.
+    pub fn countdown(self, lead: Duration, lag: Duration) -> impl Stream<Item = FundingEvent> {
+        futures::stream::unfold(now_ms(), move |last| async move {
+            let event = self.event_after(last, lead, lag);
+            let wait = event.time.saturating_sub(now_ms());
+            tokio::time::sleep(Duration::from_millis(wait)).await;
+            Some((event, event.time))
+        })
+    }
+
+    /// Returns the first event strictly after `time` (milliseconds).
+    fn event_after(&self, time: u64, lead: Duration, lag: Duration) -> FundingEvent {
+        let lead = lead.as_millis() as u64;
+        let lag = lag.as_millis() as u64;
+
+        let upcoming = self.next_after(time + lead);
+        let settled = self.next_after(time.saturating_sub(lag));
+        if upcoming - lead <= settled + lag {
+            FundingEvent {
+                time: upcoming - lead,
+                settlement: upcoming,
+                phase: FundingPhase::Upcoming,
+            }
+        } else {
+            FundingEvent {
+                time: settled + lag,
+                settlement: settled,
+                phase: FundingPhase::Settled,
+            }
+        }
+    }
+}
+
+/// Where a [`FundingEvent`] falls relative to its settlement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FundingPhase {
+    /// The settlement is about to happen.
+    Upcoming,
+    /// The settlement just happened.
+    Settled,
+}
+
+/// An event emitted by [`FundingSchedule::countdown`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FundingEvent {
+    /// When the event was due, in milliseconds
+    pub time: u64,
+    /// Settlement the event is about, in milliseconds
+    pub settlement: u64,
+    /// Whether the settlement is upcoming or done
+    pub phase: FundingPhase,
+}
+
+/// Next funding settlement of every coin on a venue.
+///
+/// Built from [`HttpClient::funding_calendar`](crate::hypercore::HttpClient::funding_calendar)
+/// or from predicted fundings fetched elsewhere.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FundingCalendar {
+    next: HashMap<String, (u64, FundingSchedule)>,
+}
+
+impl FundingCalendar {
+    /// Collects the next settlement of each coin on `venue`.
+    ///
+    /// Coins without an interval are assumed to settle hourly.
+    #[must_use]
+    pub fn new(fundings: &[PredictedFunding], venue: &FundingVenue) -> Self {
+        let next = fundings
+            .iter()
+            .filter_map(|funding| {
+                let venue = funding.venue(venue)?;
+                let schedule = match venue.funding_interval_hours {
+                    Some(hours) if hours > 0 => {
+                        FundingSchedule::every(Duration::from_secs(u64::from(hours) * 60 * 60))
+                    }
+                    _ => FundingSchedule::HOURLY,
+                };
+                Some((funding.coin.clone(), (venue.next_funding_time, schedule)))
+            })
+            .collect();
+        Self { next }
+    }
+
+    /// Returns the next settlement of `coin` in milliseconds.
+    ///
+    /// Once the reported settlement has passed, the following ones are projected
+    /// from the coin's interval.
+    #[must_use]
+    pub fn next_funding(&self, coin: &str) -> Option<u64> {
+        let (next, schedule) = self.next.get(coin)?;
+        let now = now_ms();
+        if *next > now {
+            return Some(*next);
+        }
+        let interval = schedule.interval.as_millis() as u64;
+        let missed = (now - next) / interval + 1;
+        Some(next + missed * interval)
+    }
+
+    /// Returns the time left until the next settlement of `coin`.
+    #[must_use]
+    pub fn until_funding(&self, coin: &str) -> Option<Duration> {
+        let next = self.next_funding(coin)?;
+        Some(Duration::from_millis(next.saturating_sub(now_ms())))
+    }
+
+    /// Returns the funding schedule of `coin`.
+    #[must_use]
+    pub fn schedule(&self, coin: &str) -> Option<FundingSchedule> {
+        self.next.get(coin).map(|(_, schedule)| *schedule)
+    }
+
+    /// Returns the coins in the calendar.
+    pub fn coins(&self) -> impl Iterator<Item = &str> {
+        self.next.keys().map(String::as_str)
+    }
+}
+
+fn now_ms() -> u64 {
+    Utc::now().timestamp_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: u64 = 3_600_000;
+
+    #[test]
+    fn test_funding_schedule() {
+        let schedule = FundingSchedule::HOURLY;
+        assert_eq!(schedule.next_after(0), HOUR);
+        assert_eq!(schedule.next_after(HOUR - 1), HOUR);
+        assert_eq!(schedule.next_after(HOUR), 2 * HOUR);
+
+        let lead = Duration::from_secs(30);
+        let lag = Duration::from_secs(5);
+        let upcoming = schedule.event_after(HOUR / 2, lead, lag);
+        assert_eq!(
+            upcoming,
+            FundingEvent {
+                time: HOUR - 30_000,
+                settlement: HOUR,
+                phase: FundingPhase::Upcoming,
+            }
+        );
+        let settled = schedule.event_after(upcoming.time, lead, lag);
+        assert_eq!(
+            settled,
+            FundingEvent {
+                time: HOUR + 5_000,
+                settlement: HOUR,
+                phase: FundingPhase::Settled,
+            }
+        );
+        let next = schedule.event_after(settled.time, lead, lag);
+        assert_eq!(next.settlement, 2 * HOUR);
+        assert_eq!(next.phase, FundingPhase::Upcoming);
+    }
+}
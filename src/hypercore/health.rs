@@ -0,0 +1,407 @@
+//! Market data quality checks for WebSocket feeds.
+//!
+//! Exchanges occasionally publish bad data: a crossed book after a missed update, a
+//! message older than the one before it, a print far outside the market. A strategy
+//! quoting off such data loses money, so [`FeedHealth`] inspects every market data
+//! message and reports [`Anomaly`]s that the strategy can stand down on.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use futures::StreamExt;
+//! use hypersdk::hypercore::{self, health::FeedHealth, types::*, ws::Event};
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let mut ws = hypercore::mainnet_ws();
+//! ws.subscribe(Subscription::L2Book { coin: "BTC".into() });
+//! ws.subscribe(Subscription::Trades { coin: "BTC".into() });
+//!
+//! let mut health = FeedHealth::new();
+//! while let Some(event) = ws.next().await {
+//!     let Event::Message(msg) = event else { continue };
+//!     for anomaly in health.observe(&msg) {
+//!         eprintln!("bad data on {}: {anomaly}", anomaly.coin());
+//!         // Pull quotes until the feed recovers...
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{collections::HashMap, time::Duration};
+
+use rust_decimal::{Decimal, dec};
+
+use super::types::{BookLevel, Incoming};
+
+/// Default largest accepted move between consecutive prices, as a fraction (10%).
+pub const DEFAULT_MAX_JUMP: Decimal = dec!(0.1);
+
+/// Default longest gap between updates before a feed is stale.
+pub const DEFAULT_MAX_STALENESS: Duration = Duration::from_secs(10);
+
+/// Market data feed an anomaly was seen on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, derive_more::Display)]
+pub enum Feed {
+    /// `l2Book`
+    #[display("l2Book")]
+    L2Book,
+    /// `bbo`
+    #[display("bbo")]
+    Bbo,
+    /// `trades`
+    #[display("trades")]
+    Trades,
+    /// `allMids`
+    #[display("allMids")]
+    AllMids,
+}
+
+/// Bad market data reported by [`FeedHealth`].
+#[derive(Debug, Clone, PartialEq, Eq, derive_more::Display)]
+pub enum Anomaly {
+    /// The best bid is above the best ask.
+    #[display("{feed} crossed: bid {bid} > ask {ask}")]
+    Crossed {
+        /// Market symbol
+        coin: String,
+        /// Feed the book came from
+        feed: Feed,
+        /// Timestamp in milliseconds
+        time: u64,
+        /// Best bid price
+        bid: Decimal,
+        /// Best ask price
+        ask: Decimal,
+    },
+    /// The best bid equals the best ask.
+    #[display("{feed} locked at {px}")]
+    Locked {
+        /// Market symbol
+        coin: String,
+        /// Feed the book came from
+        feed: Feed,
+        /// Timestamp in milliseconds
+        time: u64,
+        /// Bid and ask price
+        px: Decimal,
+    },
+    /// A message is older than the previous one on the same feed.
+    #[display("{feed} went back in time: {time} < {last}")]
+    OutOfOrder {
+        /// Market symbol
+        coin: String,
+        /// Feed the message came from
+        feed: Feed,
+        /// Timestamp of the message in milliseconds
+        time: u64,
+        /// Timestamp of the previous message in milliseconds
+        last: u64,
+    },
+    /// A price moved further than the sanity band from the previous one.
+    #[display("{feed} jumped from {from} to {to}")]
+    PriceJump {
+        /// Market symbol
+        coin: String,
+        /// Feed the price came from
+        feed: Feed,
+        /// Previous price
+        from: Decimal,
+        /// New price
+        to: Decimal,
+    },
+    /// A feed hasn't updated for longer than the staleness limit.
+    #[display("{feed} stale since {last}")]
+    Stale {
+        /// Market symbol
+        coin: String,
+        /// Feed that stopped updating
+        feed: Feed,
+        /// Timestamp of the last message in milliseconds
+        last: u64,
+    },
+}
+
+impl Anomaly {
+    /// Returns the market the anomaly was seen on.
+    #[must_use]
+    pub fn coin(&self) -> &str {
+        match self {
+            Self::Crossed { coin, .. }
+            | Self::Locked { coin, .. }
+            | Self::OutOfOrder { coin, .. }
+            | Self::PriceJump { coin, .. }
+            | Self::Stale { coin, .. } => coin,
+        }
+    }
+
+    /// Returns the feed the anomaly was seen on.
+    #[must_use]
+    pub fn feed(&self) -> Feed {
+        match self {
+            Self::Crossed { feed, .. }
+            | Self::Locked { feed, .. }
+            | Self::OutOfOrder { feed, .. }
+            | Self::PriceJump { feed, .. }
+            | Self::Stale { feed, .. } => *feed,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct FeedState {
+    time: Option<u64>,
+    px: Option<Decimal>,
+}
+
+/// Checks market data messages for crossed books, time travel, price jumps and
+/// stale feeds.
+///
+/// Each `(coin, feed)` pair is tracked separately: book mids are compared with book
+/// mids and trades with trades.
+#[derive(Debug)]
+pub struct FeedHealth {
+    max_jump: Decimal,
+    max_staleness: Duration,
+    feeds: HashMap<(String, Feed), FeedState>,
+}
+
+impl Default for FeedHealth {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FeedHealth {
+    /// Creates a monitor with [`DEFAULT_MAX_JUMP`] and [`DEFAULT_MAX_STALENESS`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            max_jump: DEFAULT_MAX_JUMP,
+            max_staleness: DEFAULT_MAX_STALENESS,
+            feeds: HashMap::new(),
+        }
+    }
+
+    /// Sets the largest accepted move between consecutive prices, as a fraction.
+    #[must_use]
+    pub fn max_jump(mut self, max_jump: Decimal) -> Self {
+        self.max_jump = max_jump;
+        self
+    }
+
+    /// Sets the longest gap between updates before [`stale`](Self::stale) reports a
+    /// feed.
+    #[must_use]
+    pub fn max_staleness(mut self, max_staleness: Duration) -> Self {
+        self.max_staleness = max_staleness;
+        self
+    }
+
+    /// Checks a message and returns the anomalies found in it.
+    ///
+    /// Messages other than books, trades and mids are ignored. Messages that arrive
+    /// out of order don't move the reference time or price.
+    pub fn observe(&mut self, msg: &Incoming) -> Vec<Anomaly> {
+        let mut anomalies = vec![];
+        match msg {
+            Incoming::L2Book(book) => self.check_book(
+                &book.coin,
+                Feed::L2Book,
+                book.time,
+                book.best_bid(),
+                book.best_ask(),
+                &mut anomalies,
+            ),
+            Incoming::Bbo(bbo) => self.check_book(
+                &bbo.coin,
+                Feed::Bbo,
+                bbo.time,
+                bbo.bid(),
+                bbo.ask(),
+                &mut anomalies,
+            ),
+            Incoming::Trades(trades) => {
+                for trade in trades {
+                    if self.check_time(&trade.coin, Feed::Trades, trade.time, &mut anomalies) {
+                        self.check_px(&trade.coin, Feed::Trades, trade.px, &mut anomalies);
+                    }
+                }
+            }
+            Incoming::AllMids { mids, .. } => {
+                for (coin, mid) in mids {
+                    self.check_px(coin, Feed::AllMids, *mid, &mut anomalies);
+                }
+            }
+            _ => {}
+        }
+        anomalies
+    }
+
+    /// Returns the feeds whose last message is older than the staleness limit at
+    /// `now` (milliseconds).
+    ///
+    /// Call it on a timer: a feed that stopped sending produces no messages to
+    /// [`observe`](Self::observe). `allMids` carries no timestamps and is never
+    /// reported.
+    #[must_use]
+    pub fn stale(&self, now: u64) -> Vec<Anomaly> {
+        let limit = self.max_staleness.as_millis() as u64;
+        self.feeds
+            .iter()
+            .filter_map(|((coin, feed), state)| {
+                let last = state.time?;
+                (now.saturating_sub(last) > limit).then(|| Anomaly::Stale {
+                    coin: coin.clone(),
+                    feed: *feed,
+                    last,
+                })
+            })
+            .collect()
+    }
+
+    /// Forgets the state of `coin`, e.g. after resubscribing.
+    pub fn reset(&mut self, coin: &str) {
+        self.feeds.retain(|(c, _), _| c != coin);
+    }
+
+    fn check_book(
+        &mut self,
+        coin: &str,
+        feed: Feed,
+        time: u64,
+        bid: Option<&BookLevel>,
+        ask: Option<&BookLevel>,
+        anomalies: &mut Vec<Anomaly>,
+    ) {
+        if !self.check_time(coin, feed, time, anomalies) {
+            return;
+        }
+        let (Some(bid), Some(ask)) = (bid, ask) else {
+            return;
+        };
+        if bid.px > ask.px {
+            anomalies.push(Anomaly::Crossed {
+                coin: coin.to_owned(),
+                feed,
+                time,
+                bid: bid.px,
+                ask: ask.px,
+            });
+        } else if bid.px == ask.px {
+            anomalies.push(Anomaly::Locked {
+                coin: coin.to_owned(),
+                feed,
+                time,
+                px: bid.px,
+            });
+        } else {
+            let mid = (bid.px + ask.px) / Decimal::TWO;
+            self.check_px(coin, feed, mid, anomalies);
+        }
+    }
+
+    /// Records `time`, returning false if it goes back in time.
+    fn check_time(
+        &mut self,
+        coin: &str,
+        feed: Feed,
+        time: u64,
+        anomalies: &mut Vec<Anomaly>,
+    ) -> bool {
+        let state = self.state(coin, feed);
+        match state.time {
+            Some(last) if time < last => {
+                anomalies.push(Anomaly::OutOfOrder {
+                    coin: coin.to_owned(),
+                    feed,
+                    time,
+                    last,
+                });
+                false
+            }
+            _ => {
+                state.time = Some(time);
+                true
+            }
+        }
+    }
+
+    fn check_px(&mut self, coin: &str, feed: Feed, px: Decimal, anomalies: &mut Vec<Anomaly>) {
+        let max_jump = self.max_jump;
+        let state = self.state(coin, feed);
+        let jumped = state
+            .px
+            .is_some_and(|last| !last.is_zero() && ((px - last) / last).abs() > max_jump);
+        if jumped {
+            anomalies.push(Anomaly::PriceJump {
+                coin: coin.to_owned(),
+                feed,
+                from: state.px.unwrap_or_default(),
+                to: px,
+            });
+        }
+        state.px = Some(px);
+    }
+
+    fn state(&mut self, coin: &str, feed: Feed) -> &mut FeedState {
+        self.feeds.entry((coin.to_owned(), feed)).or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hypercore::types::L2Book;
+
+    fn book(time: u64, bid: Decimal, ask: Decimal) -> Incoming {
+        let level = |px| BookLevel {
+            px,
+            sz: dec!(1),
+            n: 1,
+        };
+        Incoming::L2Book(L2Book {
+            coin: "BTC".into(),
+            time,
+            snapshot: true,
+            levels: [vec![level(bid)], vec![level(ask)]],
+        })
+    }
+
+    #[test]
+    fn test_feed_health() {
+        let mut health = FeedHealth::new();
+        assert!(health.observe(&book(1, dec!(100), dec!(101))).is_empty());
+
+        assert!(matches!(
+            health.observe(&book(2, dec!(101), dec!(100)))[..],
+            [Anomaly::Crossed { .. }]
+        ));
+        assert!(matches!(
+            health.observe(&book(3, dec!(100), dec!(100)))[..],
+            [Anomaly::Locked { .. }]
+        ));
+        assert!(matches!(
+            health.observe(&book(2, dec!(100), dec!(101)))[..],
+            [Anomaly::OutOfOrder {
+                time: 2,
+                last: 3,
+                ..
+            }]
+        ));
+        assert!(matches!(
+            health.observe(&book(4, dec!(150), dec!(151)))[..],
+            [Anomaly::PriceJump { .. }]
+        ));
+
+        assert!(health.stale(4 + 10_000).is_empty());
+        assert_eq!(
+            health.stale(4 + 10_001),
+            [Anomaly::Stale {
+                coin: "BTC".into(),
+                feed: Feed::L2Book,
+                last: 4,
+            }]
+        );
+    }
+}
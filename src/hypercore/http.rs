@@ -40,8 +40,12 @@
 //! ```
 
 use std::{
-    collections::{HashMap, VecDeque},
-    time::Duration,
+    collections::{HashMap, HashSet, VecDeque},
+    sync::{
+        Arc,
+        atomic::{self, AtomicI64},
+    },
+    time::{Duration, Instant},
 };
 
 use alloy::{
@@ -50,31 +54,53 @@ use alloy::{
 };
 use anyhow::{Context, Result, anyhow};
 use chrono::{DateTime, Utc};
+use futures::{Stream, StreamExt, TryStreamExt};
+use reqwest::header::HeaderMap;
 use rust_decimal::{Decimal, prelude::ToPrimitive};
 use serde::Deserialize;
 use url::Url;
 
-use super::{ApiError, AssetTarget, signing::*};
+use super::{
+    ApiError, AssetTarget, ReadOnly,
+    analytics::FeeModel,
+    band::PriceBand,
+    breaker::CircuitBreaker,
+    cache::{CachedResponse, InfoCache},
+    composite::{self, CompositeCandle},
+    decode::{ArrayElements, DecodeMode, decode},
+    explorer::{ExplorerClient, explorer_url},
+    funding::FundingCalendar,
+    oracle::OraclePrices,
+    registry::SignerRegistry,
+    signing::*,
+    slippage::{FixedBps, SlippageContext, SlippageModel},
+    timestamped::Timestamped,
+};
 use crate::hypercore::{
     ActionError, ApiAgent, Builder, CandleInterval, Chain, Cloid, Dex, GossipPriorityAuctionStatus,
     Market, MultiSigConfig, OidOrCloid, OutcomeMeta, PerpMarket, Signature, SpotMarket, SpotToken,
     api::{
-        Action, ActionRequest, ApproveAgent, ApproveBuilderFee, ConvertToMultiSigUser,
-        GossipPriorityBid, Hip3LiquidatorTransferAction, OkResponse, Response, SignersConfig,
-        TokenDelegateAction, TwapOrderParams, UpdateIsolatedMargin, UpdateLeverage,
-        UsdClassTransferAction, UserOutcomeAction, VaultTransfer, Withdraw3Action,
+        Action, ActionRequest, ApproveAgent, ApproveBuilderFee, CDepositAction, CWithdrawAction,
+        ConvertToMultiSigUser, GossipPriorityBid, Hip3LiquidatorTransferAction,
+        IsolatedMarginUpdate, OkResponse, PerpDeployAction, Response, SignersConfig,
+        SpotDeployAction, TokenDelegateAction, TwapOrderParams, UpdateIsolatedMargin,
+        UpdateLeverage, UsdClassTransferAction, UserOutcomeAction, VaultTransfer, Withdraw3Action,
     },
-    mainnet_url, testnet_url,
+    leaderboard_url,
     types::{
-        AbstractionMode, ActiveAssetData, AgentSendAsset, BasicOrder, BatchCancel,
-        BatchCancelCloid, BatchModify, BatchOrder, ClearinghouseState, Delegation,
-        DelegatorSummary, DeployAuctionStatus, Fill, FundingRate, InfoRequest, L2Book,
-        OrderGrouping, OrderRequest, OrderResponseStatus, OrderTypePlacement, OrderUpdate,
-        PerpDexLimits, PerpDexStatus, PredictedFundingVenue, ScheduleCancel, SendAsset, SendToken,
-        SpotSend, SubAccount, TimeInForce, TokenDetails, TwapSliceFill, UsdSend, UserBalance,
-        UserFees, UserFundingEntry, UserRateLimit, UserRole, UserSetAbstractionAction,
-        UserVaultEquity, VaultDetails,
+        AbstractionMode, ActionResponse, ActiveAssetData, AgentSendAsset, BasicOrder, BatchCancel,
+        BatchCancelCloid, BatchModify, BatchOrder, ClearinghouseState, ClockSkew, CloidStatuses,
+        Delegation, DelegatorEvent, DelegatorReward, DelegatorSummary, DeployAuctionStatus,
+        ExchangeStatus, Fill, FundingRate, FundingVenue, IndexedOrderStatus, InfoRequest, L2Book,
+        Leaderboard, OiUtilization, OpenOrder, OrderGrouping, OrderRequest, OrderResponseStatus,
+        OrderTypePlacement, OrderUpdate, PerpAssetCtx, PerpContext, PerpDexLimits, PerpDexStatus,
+        Portfolio, PositionData, PredictedFunding, ScheduleCancel, SendAsset, SendToken, Side,
+        SpotSend, SubAccount, TimeInForce, TokenDetails, TokenGenesis, TwapHistory, TwapSliceFill,
+        UsdSend, UserBalance, UserFees, UserFundingEntry, UserRateLimit, UserRole,
+        UserSetAbstractionAction, UserVaultEquity, ValidatorSummary, VaultDetails, VaultFilter,
+        VaultRelationships, VaultSummary, hype_to_wei,
     },
+    vaults_url,
 };
 
 /// HTTP client for HyperCore API.
@@ -94,6 +120,180 @@ pub struct Client {
     http_client: reqwest::Client,
     base_url: Url,
     chain: Chain,
+    decode_mode: DecodeMode,
+    retries: u32,
+    expires_after: Option<Duration>,
+    clock_skew_ms: AtomicI64,
+    slippage: Arc<dyn SlippageModel>,
+    cache: Option<InfoCache>,
+    breaker: Option<CircuitBreaker>,
+    read_only: bool,
+}
+
+/// Default timeout for HTTP requests.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Delay before the first retry; doubled on each further attempt.
+const RETRY_BACKOFF: Duration = Duration::from_millis(250);
+
+/// Upper bound on the delay between retries.
+const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(5);
+
+/// Maximum number of fills returned by one `userFillsByTime` request.
+const USER_FILLS_PAGE_SIZE: usize = 2000;
+
+/// Maximum number of entries returned by one `userFunding` request.
+const USER_FUNDING_PAGE_SIZE: usize = 500;
+
+/// Maximum number of candles returned by one `candleSnapshot` request.
+const CANDLES_PAGE_SIZE: u64 = 500;
+
+/// Requests in flight at once in [`Client::clearinghouse_states`].
+const BULK_CONCURRENCY: usize = 8;
+
+/// Minimum spacing between request starts in [`Client::clearinghouse_states`].
+///
+/// `clearinghouseState` weighs 2 against the 1200-per-minute IP budget, so this keeps a
+/// bulk query at about half of the budget.
+const BULK_SPACING: Duration = Duration::from_millis(200);
+
+/// Builder for [`Client`] with timeout and retry settings.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use hypersdk::hypercore::{Chain, HttpClient};
+///
+/// let client = HttpClient::builder(Chain::Mainnet)
+///     .timeout(Duration::from_secs(30))
+///     .retries(3)
+///     .build();
+/// ```
+#[derive(Debug, Clone)]
+pub struct ClientBuilder {
+    chain: Chain,
+    base_url: Option<Url>,
+    timeout: Duration,
+    retries: u32,
+    decode_mode: DecodeMode,
+    expires_after: Option<Duration>,
+    slippage: Arc<dyn SlippageModel>,
+    cache: Option<InfoCache>,
+    breaker: Option<CircuitBreaker>,
+    read_only: bool,
+}
+
+impl ClientBuilder {
+    /// Sets a custom base URL instead of the chain's default.
+    #[must_use]
+    pub fn url(mut self, base_url: Url) -> Self {
+        self.base_url = Some(base_url);
+        self
+    }
+
+    /// Sets the timeout of each HTTP request. Defaults to [`DEFAULT_TIMEOUT`].
+    #[must_use]
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets how many times a failed info request is retried. Defaults to 0.
+    ///
+    /// Connection errors, timeouts, HTTP 429, and HTTP 5xx are retried with exponential
+    /// backoff. Exchange actions are never retried.
+    #[must_use]
+    pub fn retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Sets how info responses with unknown fields are decoded.
+    #[must_use]
+    pub fn decode_mode(mut self, decode_mode: DecodeMode) -> Self {
+        self.decode_mode = decode_mode;
+        self
+    }
+
+    /// Sets a default `expiresAfter` window for L1 actions. Disabled by default.
+    ///
+    /// Actions signed without an explicit expiry expire `window` after the estimated
+    /// server time, so an action stuck behind a network stall is rejected instead of
+    /// executing minutes later. Pass an expiry to a call to override it.
+    ///
+    /// User-signed actions (transfers, approvals) don't support `expiresAfter` and are
+    /// left untouched.
+    #[must_use]
+    pub fn expires_after(mut self, window: Duration) -> Self {
+        self.expires_after = Some(window);
+        self
+    }
+
+    /// Sets the default slippage model of [`Client::market_order`] and
+    /// [`Client::market_close`]. Defaults to [`FixedBps::DEFAULT`].
+    #[must_use]
+    pub fn slippage(mut self, model: impl SlippageModel + 'static) -> Self {
+        self.slippage = Arc::new(model);
+        self
+    }
+
+    /// Caches metadata responses (perp and spot universes, DEX list) in `cache`.
+    /// Disabled by default.
+    ///
+    /// See [`cache`](super::cache).
+    #[must_use]
+    pub fn cache(mut self, cache: InfoCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Guards order submission with `breaker`. Disabled by default.
+    ///
+    /// While the breaker is open, [`Client::place`], [`Client::modify`] and the
+    /// calls built on them fail without sending anything. Cancels are never blocked.
+    /// See [`breaker`](super::breaker).
+    #[must_use]
+    pub fn circuit_breaker(mut self, breaker: CircuitBreaker) -> Self {
+        self.breaker = Some(breaker);
+        self
+    }
+
+    /// Disables exchange actions.
+    ///
+    /// Every call that would send a signed action fails with [`ReadOnly`] before
+    /// anything is sent, as do posts on the client's
+    /// [`websocket`](Client::websocket). Info requests are unaffected. See
+    /// [`Client::read_only`].
+    #[must_use]
+    pub fn read_only(mut self) -> Self {
+        self.read_only = true;
+        self
+    }
+
+    /// Builds the client.
+    pub fn build(self) -> Client {
+        let http_client = reqwest::Client::builder()
+            .timeout(self.timeout)
+            .tcp_nodelay(true)
+            .build()
+            .unwrap();
+
+        Client {
+            http_client,
+            base_url: self.base_url.unwrap_or_else(|| self.chain.api_url()),
+            chain: self.chain,
+            decode_mode: self.decode_mode,
+            retries: self.retries,
+            expires_after: self.expires_after,
+            clock_skew_ms: AtomicI64::new(0),
+            slippage: self.slippage,
+            cache: self.cache,
+            breaker: self.breaker,
+            read_only: self.read_only,
+        }
+    }
 }
 
 impl Client {
@@ -102,10 +302,13 @@ impl Client {
     /// The base URL is automatically determined based on the chain:
     /// - `Chain::Mainnet`: `https://api.hyperliquid.xyz`
     /// - `Chain::Testnet`: `https://api.hyperliquid-testnet.xyz`
+    /// - `Chain::Local`: [`local_url`](super::local_url) (`http://localhost:3001` by default)
     ///
     /// All actions signed by this client will use chain-specific values:
     /// - Agent source field: `"a"` for mainnet, `"b"` for testnet
-    /// - Multisig chain ID: `"0x66eee"` for mainnet, `"0x66eef"` for testnet
+    /// - Signature chain ID: `"0xa4b1"` for mainnet, `"0x66eee"` otherwise
+    ///
+    /// See [`SigningContext`](super::SigningContext).
     ///
     /// # Example
     ///
@@ -119,25 +322,43 @@ impl Client {
     /// let testnet_client = HttpClient::new(Chain::Testnet);
     /// ```
     pub fn new(chain: Chain) -> Self {
-        let base_url = if chain.is_mainnet() {
-            mainnet_url()
-        } else {
-            testnet_url()
-        };
-
-        let http_client = reqwest::Client::builder()
-            .timeout(Duration::from_secs(10))
-            .tcp_nodelay(true)
-            .build()
-            .unwrap();
+        Self::builder(chain).build()
+    }
 
-        Self {
-            http_client,
-            base_url,
+    /// Returns a [`ClientBuilder`] for the specified chain.
+    pub fn builder(chain: Chain) -> ClientBuilder {
+        ClientBuilder {
             chain,
+            base_url: None,
+            timeout: DEFAULT_TIMEOUT,
+            retries: 0,
+            decode_mode: DecodeMode::default(),
+            expires_after: None,
+            slippage: Arc::new(FixedBps::DEFAULT),
+            cache: None,
+            breaker: None,
+            read_only: false,
         }
     }
 
+    /// Creates a client for `chain` that can't send exchange actions.
+    ///
+    /// Meant for analytics services and other deployments that only read: even if a
+    /// signer is wired in by mistake, every action fails with [`ReadOnly`] before it
+    /// is sent.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hypersdk::hypercore::{Chain, HttpClient};
+    ///
+    /// let client = HttpClient::read_only(Chain::Mainnet);
+    /// assert!(client.is_read_only());
+    /// ```
+    pub fn read_only(chain: Chain) -> Self {
+        Self::builder(chain).read_only().build()
+    }
+
     /// Sets a custom base URL for this client.
     ///
     /// This is useful when connecting to a custom Hyperliquid node or proxy.
@@ -169,12 +390,129 @@ impl Client {
         }
     }
 
+    /// Sets how info responses with unknown fields are decoded.
+    ///
+    /// See [`decode`](super::decode) for the modes and the default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hypersdk::hypercore::{self, decode::DecodeMode};
+    ///
+    /// // Fail on any field the typed responses don't know about.
+    /// let client = hypercore::mainnet().with_decode_mode(DecodeMode::Strict);
+    /// ```
+    #[must_use]
+    pub fn with_decode_mode(self, decode_mode: DecodeMode) -> Self {
+        Self {
+            decode_mode,
+            ..self
+        }
+    }
+
+    /// Caches metadata responses in `cache`.
+    ///
+    /// See [`ClientBuilder::cache`].
+    #[must_use]
+    pub fn with_cache(self, cache: InfoCache) -> Self {
+        Self {
+            cache: Some(cache),
+            ..self
+        }
+    }
+
+    /// Guards order submission with `breaker`.
+    ///
+    /// See [`ClientBuilder::circuit_breaker`].
+    #[must_use]
+    pub fn with_circuit_breaker(self, breaker: CircuitBreaker) -> Self {
+        Self {
+            breaker: Some(breaker),
+            ..self
+        }
+    }
+
+    /// Returns the circuit breaker guarding order submission, if any.
+    #[must_use]
+    pub fn circuit_breaker(&self) -> Option<&CircuitBreaker> {
+        self.breaker.as_ref()
+    }
+
+    /// Returns true if exchange actions are disabled.
+    ///
+    /// See [`read_only`](Self::read_only).
+    #[must_use]
+    pub const fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
     /// Returns the chain this client is configured for.
     #[must_use]
     pub const fn chain(&self) -> Chain {
         self.chain
     }
 
+    /// Returns the default `expiresAfter` window, if any.
+    ///
+    /// See [`ClientBuilder::expires_after`].
+    #[must_use]
+    pub fn default_expires_after(&self) -> Option<Duration> {
+        self.expires_after
+    }
+
+    /// Returns the estimated offset of the server clock from the local clock.
+    ///
+    /// Estimated from the `Date` header of every response, so offsets below a second
+    /// read as zero until [`measure_clock_skew`](Self::measure_clock_skew) refines
+    /// them.
+    #[must_use]
+    pub fn clock_skew(&self) -> chrono::TimeDelta {
+        chrono::TimeDelta::milliseconds(self.clock_skew_ms.load(atomic::Ordering::Relaxed))
+    }
+
+    /// Returns the current time on the server, per [`clock_skew`](Self::clock_skew).
+    #[must_use]
+    pub fn server_time(&self) -> DateTime<Utc> {
+        Utc::now() + self.clock_skew()
+    }
+
+    /// Applies the default expiry to L1 actions and checks explicit ones.
+    fn resolve_expires_after(
+        &self,
+        action: &Action,
+        expires_after: Option<DateTime<Utc>>,
+    ) -> Result<Option<DateTime<Utc>>> {
+        if action.chain_fields().is_some() {
+            return Ok(expires_after);
+        }
+        let now = self.server_time();
+        match expires_after {
+            Some(expires_after) if expires_after <= now => Err(anyhow!(
+                "expiresAfter {expires_after} has already passed on the server (clock skew {}ms)",
+                self.clock_skew().num_milliseconds()
+            )),
+            Some(expires_after) => Ok(Some(expires_after)),
+            None => Ok(self
+                .expires_after
+                .and_then(|window| chrono::TimeDelta::from_std(window).ok())
+                .map(|window| now + window)),
+        }
+    }
+
+    /// Updates the clock skew estimate from a response's `Date` header.
+    fn observe_server_time(&self, headers: &HeaderMap) {
+        let Some(server) = date_header(headers) else {
+            return;
+        };
+        let skew = server.timestamp_millis() - Utc::now().timestamp_millis();
+        // `Date` has one-second resolution, so it can only correct estimates that are
+        // off by more than that.
+        let current = self.clock_skew_ms.load(atomic::Ordering::Relaxed);
+        if (skew - current).abs() > 1000 {
+            self.clock_skew_ms.store(skew, atomic::Ordering::Relaxed);
+        }
+    }
+
     /// Creates a WebSocket connection using the same base URL as this HTTP client.
     ///
     /// # Example
@@ -191,9 +529,11 @@ impl Client {
     /// ```
     pub fn websocket(&self) -> super::WebSocket {
         let mut url = self.base_url.clone();
-        let _ = url.set_scheme("wss");
+        // Plain HTTP (e.g. a local node) gets a plain WebSocket.
+        let scheme = if url.scheme() == "http" { "ws" } else { "wss" };
+        let _ = url.set_scheme(scheme);
         url.set_path("/ws");
-        super::WebSocket::new(url)
+        self.configure_websocket(super::WebSocket::new(url))
     }
 
     /// Creates a WebSocket connection without TLS (uses `ws://` instead of `wss://`).
@@ -203,7 +543,26 @@ impl Client {
         let mut url = self.base_url.clone();
         let _ = url.set_scheme("ws");
         url.set_path("/ws");
-        super::WebSocket::new(url)
+        self.configure_websocket(super::WebSocket::new(url))
+    }
+
+    /// Creates an explorer RPC client for this client's chain, sharing its HTTP
+    /// connection pool.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hypersdk::hypercore;
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let explorer = hypercore::mainnet().explorer();
+    /// let tx = explorer.tx_details("0x...".parse()?).await?;
+    /// println!("{} in block {}: {:?}", tx.action_type(), tx.block, tx.error);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn explorer(&self) -> ExplorerClient {
+        ExplorerClient::with_client(self.http_client.clone(), explorer_url(self.chain))
     }
 
     /// Fetches all available perpetual futures markets.
@@ -223,9 +582,8 @@ impl Client {
     /// # Ok(())
     /// # }
     /// ```
-    #[inline(always)]
     pub async fn perps(&self) -> Result<Vec<PerpMarket>> {
-        super::perp_markets(self.base_url.clone(), self.http_client.clone(), None).await
+        self.perp_markets(None).await
     }
 
     /// Fetches perpetual markets from a specific DEX.
@@ -258,9 +616,19 @@ impl Client {
     /// # Ok(())
     /// # }
     /// ```
-    #[inline(always)]
     pub async fn perps_from(&self, dex: Dex) -> Result<Vec<PerpMarket>> {
-        super::perp_markets(self.base_url.clone(), self.http_client.clone(), Some(dex)).await
+        self.perp_markets(Some(dex)).await
+    }
+
+    async fn perp_markets(&self, dex: Option<Dex>) -> Result<Vec<PerpMarket>> {
+        let meta = InfoRequest::Meta {
+            dex: dex.as_ref().map(|dex| dex.name().to_owned()),
+        };
+        let (spot, data) = futures::try_join!(
+            self.static_info::<super::SpotTokens>("spot_meta", &InfoRequest::SpotMeta),
+            self.static_info::<super::PerpTokens>("meta", &meta)
+        )?;
+        super::perp_markets_from(&spot, data, dex)
     }
 
     /// Fetches all available perpetual futures DEXes.
@@ -282,9 +650,11 @@ impl Client {
     /// # Ok(())
     /// # }
     /// ```
-    #[inline(always)]
     pub async fn perp_dexes(&self) -> Result<Vec<Dex>> {
-        super::perp_dexes(self.base_url.clone(), self.http_client.clone()).await
+        let dexes = self
+            .static_info::<Vec<Option<super::PerpDex>>>("perp_dexs", &InfoRequest::PerpDexs)
+            .await?;
+        Ok(super::perp_dexes_from(dexes))
     }
 
     /// Misspelled alias of [`Self::perp_dexes`].
@@ -311,9 +681,11 @@ impl Client {
     /// # Ok(())
     /// # }
     /// ```
-    #[inline(always)]
     pub async fn spot(&self) -> Result<Vec<SpotMarket>> {
-        super::spot_markets(self.base_url.clone(), self.http_client.clone()).await
+        let data = self
+            .static_info::<super::SpotTokens>("spot_meta", &InfoRequest::SpotMeta)
+            .await?;
+        super::spot_markets_from(data)
     }
 
     /// Fetches all available spot tokens.
@@ -333,9 +705,11 @@ impl Client {
     /// # Ok(())
     /// # }
     /// ```
-    #[inline(always)]
     pub async fn spot_tokens(&self) -> Result<Vec<SpotToken>> {
-        super::spot_tokens(self.base_url.clone(), self.http_client.clone()).await
+        let data = self
+            .static_info::<super::SpotTokens>("spot_meta", &InfoRequest::SpotMeta)
+            .await?;
+        Ok(super::spot_tokens_from(&data))
     }
 
     /// Fetches outcome market metadata.
@@ -387,15 +761,33 @@ impl Client {
     ///
     /// The `label` parameter is included in error messages for debugging — it should
     /// identify the calling endpoint (e.g., `"open_orders"`, `"user_balances"`).
+    ///
+    /// Transient failures are retried up to the configured number of
+    /// [`retries`](ClientBuilder::retries).
     async fn send_info_request<R>(&self, label: &str, req: &impl serde::Serialize) -> Result<R>
     where
         R: for<'de> Deserialize<'de>,
     {
-        let mut api_url = self.base_url.clone();
-        api_url.set_path("/info");
+        self.send_info_request_timestamped(label, req)
+            .await
+            .map(Timestamped::into_inner)
+    }
 
-        let res = self.http_client.post(api_url).json(&req).send().await?;
+    /// Like [`send_info_request`](Self::send_info_request), with the response's
+    /// `Date` header as the server time.
+    async fn send_info_request_timestamped<R>(
+        &self,
+        label: &str,
+        req: &impl serde::Serialize,
+    ) -> Result<Timestamped<R>>
+    where
+        R: for<'de> Deserialize<'de>,
+    {
+        let res = self.post_info(label, req, HeaderMap::new()).await?;
+        let received_at = Utc::now().timestamp_millis() as u64;
         let status = res.status();
+        let server_time =
+            date_header(res.headers()).map_or(received_at, |date| date.timestamp_millis() as u64);
         let bytes = res.bytes().await?;
         let text = String::from_utf8_lossy(&bytes);
 
@@ -403,7 +795,105 @@ impl Client {
             return Err(ApiError(format!("[{label}] HTTP {status} body={text}")).into());
         }
 
-        serde_json::from_str(&text).with_context(|| format!("[{label}] body={text}"))
+        let value =
+            decode(&text, self.decode_mode).with_context(|| format!("[{label}] body={text}"))?;
+        Ok(Timestamped {
+            value,
+            server_time,
+            received_at,
+        })
+    }
+
+    /// Fetches a metadata endpoint, through the [`InfoCache`] if one is set.
+    ///
+    /// Metadata is decoded leniently in every [`DecodeMode`], like the standalone
+    /// metadata functions.
+    async fn static_info<R>(&self, label: &str, req: &InfoRequest) -> Result<R>
+    where
+        R: for<'de> Deserialize<'de>,
+    {
+        let parse = |body: &str| -> Result<R> {
+            serde_json::from_str(body).with_context(|| format!("[{label}] body={body}"))
+        };
+        let Some(cache) = &self.cache else {
+            let res = self.post_info(label, req, HeaderMap::new()).await?;
+            let status = res.status();
+            let text = res.text().await?;
+            if !status.is_success() {
+                return Err(ApiError(format!("[{label}] HTTP {status} body={text}")).into());
+            }
+            return parse(&text);
+        };
+
+        let key = InfoCache::key(&self.base_url, &serde_json::to_vec(req)?);
+        let cached = cache.load(&key);
+        if let Some(cached) = cached
+            .as_ref()
+            .filter(|cached| cached.is_fresh(cache.ttl()))
+        {
+            log::debug!("[{label}] served from cache");
+            return parse(&cached.body);
+        }
+
+        let headers = cached
+            .as_ref()
+            .map(CachedResponse::conditional_headers)
+            .unwrap_or_default();
+        let res = self.post_info(label, req, headers).await?;
+        let status = res.status();
+        if let Some(mut cached) = cached.filter(|_| status == reqwest::StatusCode::NOT_MODIFIED) {
+            log::debug!("[{label}] revalidated cache entry");
+            cached.touch();
+            cache.store(&key, &cached);
+            return parse(&cached.body);
+        }
+
+        let res_headers = res.headers().clone();
+        let text = res.text().await?;
+        if !status.is_success() {
+            return Err(ApiError(format!("[{label}] HTTP {status} body={text}")).into());
+        }
+        let value = parse(&text)?;
+        cache.store(&key, &CachedResponse::new(text, &res_headers));
+        Ok(value)
+    }
+
+    /// Posts an info request, retrying as configured.
+    async fn post_info(
+        &self,
+        label: &str,
+        req: &impl serde::Serialize,
+        headers: HeaderMap,
+    ) -> Result<reqwest::Response> {
+        let mut api_url = self.base_url.clone();
+        api_url.set_path("/info");
+
+        let mut attempt = 0;
+        let res = loop {
+            let res = self
+                .http_client
+                .post(api_url.clone())
+                .headers(headers.clone())
+                .json(&req)
+                .send()
+                .await;
+            let retryable = match &res {
+                Ok(res) => {
+                    res.status().is_server_error()
+                        || res.status() == reqwest::StatusCode::TOO_MANY_REQUESTS
+                }
+                Err(err) => err.is_connect() || err.is_timeout(),
+            };
+            if !retryable || attempt >= self.retries {
+                break res?;
+            }
+            log::debug!("[{label}] retrying after attempt {}", attempt + 1);
+            let backoff = RETRY_BACKOFF * 2u32.saturating_pow(attempt);
+            tokio::time::sleep(backoff.min(MAX_RETRY_BACKOFF)).await;
+            attempt += 1;
+        };
+        self.observe_server_time(res.headers());
+        Ok(res)
     }
 
     /// Returns all open orders for a user.
@@ -437,6 +927,80 @@ impl Client {
         self.send_info_request("open_orders", &req).await
     }
 
+    /// Returns a user's open orders with the detail shown by the Hyperliquid frontend.
+    ///
+    /// Unlike [`open_orders`](Self::open_orders), each [`OpenOrder`] carries its trigger
+    /// condition and the TP/SL orders attached to it as `children`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hypersdk::hypercore;
+    /// use hypersdk::Address;
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let client = hypercore::mainnet();
+    /// let user: Address = "0x...".parse()?;
+    /// for order in client.frontend_open_orders(user, None).await? {
+    ///     for order in order.iter_tree() {
+    ///         let basic = &order.basic_order;
+    ///         println!(
+    ///             "{} {:?} {} @ {} ({})",
+    ///             basic.coin, basic.order_type, basic.sz, basic.limit_px, order.trigger_condition
+    ///         );
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn frontend_open_orders(
+        &self,
+        user: Address,
+        dex_name: Option<String>,
+    ) -> Result<Vec<OpenOrder>> {
+        let req = InfoRequest::FrontendOpenOrders {
+            user,
+            dex: dex_name,
+        };
+        self.send_info_request("frontend_open_orders", &req).await
+    }
+
+    /// Returns the cloids of a user's open orders, including untriggered TP/SL
+    /// children.
+    ///
+    /// Pass them to [`BatchOrder::check_cloids_against`] to catch a batch reusing
+    /// the cloid of a live order before it is sent.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hypersdk::hypercore::{self, types::*};
+    /// use alloy::primitives::address;
+    ///
+    /// # async fn example(batch: BatchOrder) -> anyhow::Result<()> {
+    /// let client = hypercore::mainnet();
+    /// let user = address!("0x0000000000000000000000000000000000000000");
+    ///
+    /// let live = client.live_cloids(user, None).await?;
+    /// if let Err(err) = batch.check_cloids_against(&live) {
+    ///     eprintln!("not sending: {err}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn live_cloids(
+        &self,
+        user: Address,
+        dex_name: Option<String>,
+    ) -> Result<HashSet<Cloid>> {
+        let orders = self.frontend_open_orders(user, dex_name).await?;
+        Ok(orders
+            .iter()
+            .flat_map(OpenOrder::iter_tree)
+            .filter_map(|order| order.basic_order.cloid)
+            .collect())
+    }
+
     /// Returns mid prices for all perpetual markets.
     ///
     /// Returns a map of market name to mid price.
@@ -461,6 +1025,24 @@ impl Client {
         self.send_info_request("all_mids", &req).await
     }
 
+    /// Returns the mid price of every main DEX perp and spot market, keyed by asset
+    /// index.
+    ///
+    /// Orders reference markets by asset index while [`all_mids`](Self::all_mids) is
+    /// keyed by coin; this joins the two, e.g. as reference prices for a
+    /// [`PriceBand`](super::band::PriceBand). Markets without a mid are left out.
+    pub async fn asset_mids(&self) -> Result<HashMap<usize, Decimal>> {
+        let (perps, spots, mids) =
+            futures::try_join!(self.perps(), self.spot(), self.all_mids(None))?;
+        let perps = perps
+            .iter()
+            .filter_map(|market| Some((market.index, *mids.get(&market.name)?)));
+        let spots = spots
+            .iter()
+            .filter_map(|market| Some((market.index, *mids.get(market.coin())?)));
+        Ok(perps.chain(spots).collect())
+    }
+
     /// Retrieves historical orders for a user.
     ///
     /// Returns all past (non-open) orders, including filled, canceled, and expired orders.
@@ -473,6 +1055,9 @@ impl Client {
     ///
     /// Retrieves all trade fills (executed orders) for a user, including the fill price, size,
     /// side, and associated order ID.
+    ///
+    /// Only the 2000 most recent fills are returned. Use
+    /// [`user_fills_by_time`](Self::user_fills_by_time) to go further back.
     pub async fn user_fills(&self, user: Address) -> Result<Vec<Fill>> {
         let req = InfoRequest::UserFills {
             user,
@@ -483,27 +1068,68 @@ impl Client {
 
     /// Returns the user's fills filtered by time range.
     ///
-    /// Retrieves all trade fills for a user within the specified time window.
-    /// This is useful for P&L calculation and trade history analysis.
+    /// Retrieves all trade fills for a user within the specified time window, oldest
+    /// first. This is useful for P&L calculation and trade history analysis.
+    ///
+    /// The API returns at most 2000 fills per request; this method pages through the
+    /// range until it is exhausted. Only the 10000 most recent fills of a user are
+    /// available.
     ///
     /// # Parameters
     ///
     /// - `user`: The address to query fills for
     /// - `start_time`: Start timestamp in milliseconds (inclusive)
     /// - `end_time`: Optional end timestamp in milliseconds (inclusive). Defaults to now if `None`.
+    /// - `aggregate_by_time`: Combine partial fills of the same crossing order into one
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hypersdk::hypercore;
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let client = hypercore::mainnet();
+    /// let user = "0x...".parse()?;
+    ///
+    /// let end = chrono::Utc::now().timestamp_millis() as u64;
+    /// let start = end - 30 * 24 * 60 * 60 * 1000;
+    /// let fills = client.user_fills_by_time(user, start, Some(end), false).await?;
+    ///
+    /// let pnl: hypersdk::Decimal = fills.iter().map(|f| f.closed_pnl - f.fee).sum();
+    /// println!("{} fills, net PnL {pnl}", fills.len());
+    /// # Ok(())
+    /// # }
+    /// ```
     pub async fn user_fills_by_time(
         &self,
         user: Address,
         start_time: u64,
         end_time: Option<u64>,
+        aggregate_by_time: bool,
     ) -> Result<Vec<Fill>> {
-        let req = InfoRequest::UserFillsByTime {
-            user,
-            start_time,
-            end_time,
-            aggregate_by_time: None,
-        };
-        self.send_info_request("user_fills_by_time", &req).await
+        let mut fills = vec![];
+        let mut start_time = start_time;
+        loop {
+            let req = InfoRequest::UserFillsByTime {
+                user,
+                start_time,
+                end_time,
+                aggregate_by_time: aggregate_by_time.then_some(true),
+            };
+            let page: Vec<Fill> = self.send_info_request("user_fills_by_time", &req).await?;
+            let full = page.len() >= USER_FILLS_PAGE_SIZE;
+            let Some(last) = page.last().map(|fill| fill.time) else {
+                break;
+            };
+            append_fill_page(&mut fills, page, start_time);
+
+            if !full || end_time.is_some_and(|end| last >= end) {
+                break;
+            }
+            // A full page within a single millisecond can't be split any further.
+            start_time = if last > start_time { last } else { last + 1 };
+        }
+        Ok(fills)
     }
 
     /// Returns the status of an order.
@@ -540,7 +1166,8 @@ impl Client {
     /// Returns historical candlestick data for a market.
     ///
     /// Retrieves OHLCV (Open, High, Low, Close, Volume) candlestick data for the specified
-    /// market and time range. Only the most recent 5000 candles are available.
+    /// market and time range. Only the most recent 5000 candles are available, and at most
+    /// 500 are returned per request; use [`candles`](Self::candles) for longer ranges.
     ///
     /// # Parameters
     ///
@@ -597,6 +1224,116 @@ impl Client {
         self.send_info_request("candle_snapshot", &req).await
     }
 
+    /// Returns all candles of a market between `start_time` and `end_time`, oldest first.
+    ///
+    /// Unlike [`candle_snapshot`](Self::candle_snapshot), the range is split into chunks
+    /// of at most 500 candles, the server's per-request cap, and the results stitched
+    /// into one continuous series. Only the most recent 5000 candles of each interval are
+    /// available.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hypersdk::hypercore::{self, CandleInterval};
+    /// use chrono::{Utc, Duration};
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let client = hypercore::mainnet();
+    ///
+    /// // Three days of 1-minute candles (4320 rows, 9 requests)
+    /// let end_time = Utc::now().timestamp_millis() as u64;
+    /// let start_time = (Utc::now() - Duration::days(3)).timestamp_millis() as u64;
+    ///
+    /// let candles = client
+    ///     .candles("BTC", CandleInterval::OneMinute, start_time, end_time)
+    ///     .await?;
+    /// println!("{} candles", candles.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn candles(
+        &self,
+        coin: impl Into<String>,
+        interval: CandleInterval,
+        start_time: u64,
+        end_time: u64,
+    ) -> Result<Vec<super::types::Candle>> {
+        let coin = coin.into();
+        let chunk = interval.to_duration().as_millis() as u64 * CANDLES_PAGE_SIZE;
+        let mut candles = vec![];
+        let mut start = start_time;
+        while start <= end_time {
+            let end = start.saturating_add(chunk - 1).min(end_time);
+            let page = self
+                .candle_snapshot(coin.clone(), interval, start, end)
+                .await?;
+            append_candles(&mut candles, page);
+            start = end + 1;
+        }
+        Ok(candles)
+    }
+
+    /// Returns the names of every perp listing of an underlying, on the main DEX and
+    /// every HIP-3 DEX.
+    ///
+    /// `"TSLA"` may return `["xyz:TSLA", "abc:TSLA"]`. The symbol is matched without
+    /// its DEX prefix, see [`composite::underlying`](super::composite::underlying).
+    pub async fn listings(&self, symbol: &str) -> Result<Vec<String>> {
+        let symbol = composite::underlying(symbol);
+        let dexes = self.perp_dexes().await?;
+        let (main, hip3) = futures::try_join!(
+            self.perps(),
+            futures::future::try_join_all(dexes.into_iter().map(|dex| self.perps_from(dex)))
+        )?;
+        Ok(main
+            .into_iter()
+            .chain(hip3.into_iter().flatten())
+            .map(|market| market.name)
+            .filter(|name| composite::underlying(name) == symbol)
+            .collect())
+    }
+
+    /// Returns the candles of an underlying merged across all its listings, oldest
+    /// first.
+    ///
+    /// Fetches [`candles`](Self::candles) for every market returned by
+    /// [`listings`](Self::listings) and combines them with
+    /// [`composite::merge_candles`], weighting prices by each listing's volume.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hypersdk::hypercore::{self, CandleInterval};
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let client = hypercore::mainnet();
+    /// let end = chrono::Utc::now().timestamp_millis() as u64;
+    /// let start = end - 24 * 60 * 60 * 1000;
+    ///
+    /// let candles = client
+    ///     .composite_candles("TSLA", CandleInterval::FifteenMinutes, start, end)
+    ///     .await?;
+    /// println!("{} candles", candles.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn composite_candles(
+        &self,
+        symbol: &str,
+        interval: CandleInterval,
+        start_time: u64,
+        end_time: u64,
+    ) -> Result<Vec<CompositeCandle>> {
+        let listings = self.listings(symbol).await?;
+        let candles = futures::future::try_join_all(
+            listings
+                .into_iter()
+                .map(|coin| self.candles(coin, interval, start_time, end_time)),
+        )
+        .await?;
+        Ok(composite::merge_candles(candles.iter().flatten()))
+    }
+
     /// Retrieves spot token balances for a user.
     ///
     /// Returns all tokens the user holds on the spot market, including held (locked) and total amounts.
@@ -631,8 +1368,10 @@ impl Client {
 
     /// Retrieves user-specific fee rates.
     ///
-    /// Returns effective maker/taker rates for perps and spot, referral discount,
-    /// daily volume, and staking discount information.
+    /// Returns effective maker/taker rates for perps and spot, the daily volume the
+    /// fee tier is computed on, the exchange's fee schedule, and the active referral
+    /// and staking discounts. The effective rates already include the discounts, so
+    /// [`UserFees::expected_fee`] gives the cost of an order.
     ///
     /// # Example
     ///
@@ -645,11 +1384,13 @@ impl Client {
     /// let user: Address = "0x...".parse()?;
     /// let fees = client.user_fees(user).await?;
     ///
-    /// println!("maker={} taker={} referral_discount={}",
+    /// println!("maker={} taker={} referral_discount={} staking_discount={}",
     ///     fees.maker_rate,
     ///     fees.taker_rate,
-    ///     fees.active_referral_discount
+    ///     fees.active_referral_discount,
+    ///     fees.staking_discount(),
     /// );
+    /// println!("14d volume: {}", fees.volume_14d());
     /// # Ok(())
     /// # }
     /// ```
@@ -658,6 +1399,16 @@ impl Client {
         self.send_info_request("user_fees", &req).await
     }
 
+    /// Returns the inputs of a user's fee tier, from [`user_fees`](Self::user_fees)
+    /// and [`delegator_summary`](Self::delegator_summary).
+    ///
+    /// See [`FeeModel::project`] to price the fees of staking more HYPE.
+    pub async fn fee_model(&self, user: Address) -> Result<FeeModel> {
+        let (fees, staking) =
+            futures::try_join!(self.user_fees(user), self.delegator_summary(user))?;
+        Ok(FeeModel::new(&fees, &staking))
+    }
+
     /// Retrieves the clearinghouse state for a user's perpetual positions.
     ///
     /// Returns the complete state of a user's perpetual trading account, including
@@ -708,6 +1459,82 @@ impl Client {
         self.send_info_request("clearinghouse_state", &req).await
     }
 
+    /// Returns [`clearinghouse_state`](Self::clearinghouse_state) with the time of
+    /// the snapshot and of its receipt.
+    ///
+    /// See [`timestamped`](super::timestamped).
+    pub async fn clearinghouse_state_timestamped(
+        &self,
+        user: Address,
+        dex_name: Option<String>,
+    ) -> Result<Timestamped<ClearinghouseState>> {
+        let req = InfoRequest::ClearinghouseState {
+            user,
+            dex: dex_name,
+        };
+        let mut state: Timestamped<ClearinghouseState> = self
+            .send_info_request_timestamped("clearinghouse_state", &req)
+            .await?;
+        state.server_time = state.value.time;
+        Ok(state)
+    }
+
+    /// Retrieves the clearinghouse states of many users, keyed by address.
+    ///
+    /// Requests run concurrently, a few at a time, and are paced to stay well within
+    /// the IP rate limit, so monitoring hundreds of accounts doesn't starve the rest of
+    /// the process. Rate-limited requests are retried per the client's
+    /// [`retries`](ClientBuilder::retries).
+    ///
+    /// A failure for one user doesn't fail the others; each address maps to its own
+    /// result. Duplicate addresses are queried once.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hypersdk::hypercore;
+    /// use hypersdk::Address;
+    ///
+    /// # async fn example(users: Vec<Address>) -> anyhow::Result<()> {
+    /// let client = hypercore::HttpClient::builder(hypercore::Chain::Mainnet)
+    ///     .retries(3)
+    ///     .build();
+    /// for (user, state) in client.clearinghouse_states(&users, None).await {
+    ///     match state {
+    ///         Ok(state) => println!("{user}: {}", state.margin_summary.account_value),
+    ///         Err(err) => eprintln!("{user}: {err}"),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn clearinghouse_states(
+        &self,
+        users: &[Address],
+        dex_name: Option<String>,
+    ) -> HashMap<Address, Result<ClearinghouseState>> {
+        let users: Vec<Address> = {
+            let mut seen = HashSet::new();
+            users
+                .iter()
+                .copied()
+                .filter(|user| seen.insert(*user))
+                .collect()
+        };
+        let start = tokio::time::Instant::now();
+        futures::stream::iter(users.into_iter().enumerate())
+            .map(|(index, user)| {
+                let dex_name = dex_name.clone();
+                async move {
+                    tokio::time::sleep_until(start + BULK_SPACING * index as u32).await;
+                    (user, self.clearinghouse_state(user, dex_name).await)
+                }
+            })
+            .buffer_unordered(BULK_CONCURRENCY)
+            .collect()
+            .await
+    }
+
     /// Retrieves historical funding rates for a perpetual market.
     ///
     /// Returns funding rate snapshots for the specified coin within the given time range.
@@ -869,6 +1696,153 @@ impl Client {
         self.send_info_request("vault_details", &req).await
     }
 
+    /// Returns the parent and child vaults linked to a vault.
+    ///
+    /// Protocol vaults such as HLP deposit into a parent vault that allocates to child
+    /// vaults. Following [`VaultRelationships::children`] from a parent, or
+    /// [`VaultRelationships::parent`] from a child, walks the structure.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hypersdk::hypercore;
+    /// use hypersdk::Address;
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let client = hypercore::mainnet();
+    /// let hlp: Address = "0xdfc24b077bc1425ad1dea75bcb6f8158e10df303".parse()?;
+    ///
+    /// let relationships = client.vault_relationships(hlp).await?;
+    /// for child in relationships.children {
+    ///     let details = client.vault_details(child, None).await?;
+    ///     println!("{child}: {} ({} followers)", details.name, details.followers.len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn vault_relationships(&self, vault: Address) -> Result<VaultRelationships> {
+        let details = self.vault_details(vault, None).await?;
+        Ok(details.relationships())
+    }
+
+    /// Returns the vaults passing `filter`.
+    ///
+    /// The vault list comes from the stats service at
+    /// [`vaults_url`](crate::hypercore::vaults_url) and has no server-side filter, so
+    /// this is [`vault_summaries_stream`](Self::vault_summaries_stream) collected:
+    /// vaults that don't match are dropped as they are decoded.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hypersdk::{dec, hypercore::{self, types::VaultFilter}};
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let client = hypercore::mainnet();
+    /// let filter = VaultFilter::default().open_only().min_tvl(dec!(100000)).limit(20);
+    /// for vault in client.vault_summaries(&filter).await? {
+    ///     println!("{}: tvl {} apr {}", vault.summary.name, vault.summary.tvl, vault.apr);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn vault_summaries(&self, filter: &VaultFilter) -> Result<Vec<VaultSummary>> {
+        self.vault_summaries_stream(filter.clone())
+            .try_collect()
+            .await
+    }
+
+    /// Streams the vaults passing `filter`, decoding the response as it arrives.
+    ///
+    /// The vault list is a single large array; the stream yields each vault once its
+    /// JSON has been received, so the whole list is never held in memory. The stream
+    /// ends early once `filter.limit` vaults have been yielded.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use futures::TryStreamExt;
+    /// use hypersdk::hypercore::{self, types::VaultFilter};
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let client = hypercore::mainnet();
+    /// let leader = "0x677d831aef5328190852e24f13c46cac05f984e7".parse()?;
+    /// let vaults = client.vault_summaries_stream(VaultFilter::default().leader(leader));
+    /// futures::pin_mut!(vaults);
+    /// while let Some(vault) = vaults.try_next().await? {
+    ///     println!("{} {}", vault.summary.vault_address, vault.summary.name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn vault_summaries_stream(
+        &self,
+        filter: VaultFilter,
+    ) -> impl Stream<Item = Result<VaultSummary>> + '_ {
+        struct State {
+            filter: VaultFilter,
+            res: Option<reqwest::Response>,
+            elements: ArrayElements,
+            pending: VecDeque<String>,
+            skipped: usize,
+            yielded: usize,
+        }
+
+        let state = State {
+            filter,
+            res: None,
+            elements: ArrayElements::default(),
+            pending: VecDeque::new(),
+            skipped: 0,
+            yielded: 0,
+        };
+        futures::stream::try_unfold(state, move |mut state| async move {
+            loop {
+                if state
+                    .filter
+                    .limit
+                    .is_some_and(|limit| state.yielded >= limit)
+                {
+                    return Ok(None);
+                }
+                if let Some(json) = state.pending.pop_front() {
+                    let vault: VaultSummary = decode(&json, self.decode_mode)
+                        .context("[vault_summaries] invalid vault")?;
+                    if !state.filter.matches(&vault) {
+                        continue;
+                    }
+                    if state.skipped < state.filter.offset {
+                        state.skipped += 1;
+                        continue;
+                    }
+                    state.yielded += 1;
+                    return Ok(Some((vault, state)));
+                }
+
+                let Some(res) = state.res.as_mut() else {
+                    let res = self.http_client.get(vaults_url(self.chain)).send().await?;
+                    let status = res.status();
+                    if !status.is_success() {
+                        let text = res.text().await?;
+                        return Err(ApiError(format!(
+                            "[vault_summaries] HTTP {status} body={text}"
+                        ))
+                        .into());
+                    }
+                    state.res = Some(res);
+                    continue;
+                };
+                match res.chunk().await? {
+                    Some(chunk) => state.pending.extend(state.elements.feed(&chunk)?),
+                    None => {
+                        state.elements.finish()?;
+                        return Ok(None);
+                    }
+                }
+            }
+        })
+    }
+
     /// Retrieve a user's vault deposits.
     ///
     /// Returns all vaults that a user has deposited into, along with their
@@ -1035,7 +2009,11 @@ impl Client {
     ///
     /// This is a signed action that tells the exchange to cancel all of the user's
     /// open orders at the given timestamp. Useful for risk management — for example,
-    /// scheduling an end-of-day order sweep.
+    /// as a dead man's switch that is pushed forward periodically while the client is
+    /// healthy, or to schedule an end-of-day order sweep.
+    ///
+    /// `when` must be at least 5 seconds in the future. Use
+    /// [`clear_scheduled_cancel`](Self::clear_scheduled_cancel) to disarm the timer.
     ///
     /// # Parameters
     ///
@@ -1055,9 +2033,35 @@ impl Client {
         let resp = self
             .sign_and_send_sync(
                 signer,
-                ScheduleCancel {
-                    time: Some(when.timestamp_millis() as u64),
-                },
+                ScheduleCancel::at(when),
+                nonce,
+                vault_address,
+                expires_after,
+            )
+            .await?;
+
+        resp.into_default()
+    }
+
+    /// Clears a cancellation previously armed with [`schedule_cancel`](Self::schedule_cancel).
+    ///
+    /// # Parameters
+    ///
+    /// - `signer`: The wallet signing the action
+    /// - `nonce`: Unique nonce for this request
+    /// - `vault_address`: Optional vault/subaccount address
+    /// - `expires_after`: Optional expiration time for the request itself
+    pub async fn clear_scheduled_cancel<S: SignerSync>(
+        &self,
+        signer: &S,
+        nonce: u64,
+        vault_address: Option<Address>,
+        expires_after: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        let resp = self
+            .sign_and_send_sync(
+                signer,
+                ScheduleCancel::clear(),
                 nonce,
                 vault_address,
                 expires_after,
@@ -1071,6 +2075,15 @@ impl Client {
     ///
     /// Submits one or more orders to the exchange. Each order must be signed with your private key.
     ///
+    /// A batch in which two orders share a cloid is refused before signing; the
+    /// [`DuplicateCloid`](crate::hypercore::DuplicateCloid) is available through
+    /// [`ActionError::downcast_source`].
+    ///
+    /// Statuses are returned in submission order. If the exchange answers fewer orders
+    /// than were submitted, only the answered ones are returned; use
+    /// [`place_by_cloid`](Self::place_by_cloid) or [`place_timed`](Self::place_timed)
+    /// to detect it.
+    ///
     /// # Parameters
     ///
     /// - `signer`: Private key signer for EIP-712 signatures
@@ -1104,22 +2117,205 @@ impl Client {
         expires_after: Option<DateTime<Utc>>,
     ) -> impl Future<Output = Result<Vec<OrderResponseStatus>, ActionError<Cloid>>> + Send + 'static
     {
+        let future = self.send_orders(signer, batch, nonce, vault_address, expires_after);
+        async move { future.await.map(ActionResponse::into_inner) }
+    }
+
+    /// Places a batch of orders after checking their prices against a [`PriceBand`].
+    ///
+    /// If any order is priced too far from its reference mid, nothing is sent and an
+    /// [`ActionError`] describing the first offending order is returned. Use
+    /// [`place`](Self::place) to send an order outside the band on purpose.
+    ///
+    /// See [`band`](super::band) for an example.
+    #[allow(clippy::too_many_arguments)]
+    pub fn place_within_band<S: SignerSync>(
+        &self,
+        signer: &S,
+        batch: BatchOrder,
+        band: &PriceBand,
+        mids: &HashMap<usize, Decimal>,
+        nonce: u64,
+        vault_address: Option<Address>,
+        expires_after: Option<DateTime<Utc>>,
+    ) -> impl Future<Output = Result<Vec<OrderResponseStatus>, ActionError<Cloid>>> + Send + 'static
+    {
+        let future = match band.check(&batch, mids) {
+            Ok(()) => Ok(self.place(signer, batch, nonce, vault_address, expires_after)),
+            Err(err) => Err(ActionError::new(
+                batch.orders.iter().map(|req| req.cloid).collect(),
+                err.to_string(),
+            )),
+        };
+        async move { future?.await }
+    }
+
+    /// Places a batch of orders and returns the statuses keyed by client order ID.
+    ///
+    /// Every order in the batch must carry a unique, non-zero cloid; otherwise the
+    /// request is not sent and an [`ActionError`] is returned. If the exchange answers
+    /// fewer orders than were submitted, the unanswered cloids end up in
+    /// [`CloidStatuses::missing`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hypersdk::hypercore::{self, types::*, PrivateKeySigner};
+    ///
+    /// # async fn example(batch: BatchOrder) -> anyhow::Result<()> {
+    /// let client = hypercore::mainnet();
+    /// let signer: PrivateKeySigner = "your_key".parse()?;
+    /// let nonce = chrono::Utc::now().timestamp_millis() as u64;
+    ///
+    /// let res = client.place_by_cloid(&signer, batch, nonce, None, None).await?;
+    /// for (cloid, err) in res.errors() {
+    ///     eprintln!("{cloid} rejected: {err}");
+    /// }
+    /// if !res.is_complete() {
+    ///     eprintln!("no status for {:?}", res.missing);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn place_by_cloid<S: SignerSync>(
+        &self,
+        signer: &S,
+        batch: BatchOrder,
+        nonce: u64,
+        vault_address: Option<Address>,
+        expires_after: Option<DateTime<Utc>>,
+    ) -> impl Future<Output = Result<CloidStatuses, ActionError<Cloid>>> + Send + 'static {
         let cloids: Vec<_> = batch.orders.iter().map(|req| req.cloid).collect();
 
-        let future = self.sign_and_send_sync(signer, batch, nonce, vault_address, expires_after);
+        let future = if cloids.iter().any(|cloid| cloid.is_zero()) {
+            Err(ActionError::new(
+                cloids.clone(),
+                "every order must have a non-zero cloid".to_owned(),
+            ))
+        } else {
+            Ok(self.send_orders(signer, batch, nonce, vault_address, expires_after))
+        };
+        async move {
+            // Partial answers are kept: unanswered cloids end up in `missing`.
+            let resp = future?.await?;
+            Ok(CloidStatuses::new(cloids, resp.data))
+        }
+    }
+
+    /// Places a batch of orders and returns per-order statuses with request metadata.
+    ///
+    /// Same as [`Client::place`], but each status carries the index and cloid of the
+    /// order it answers, and the result records when the request was sent and when
+    /// the response arrived (see [`ActionResponse::latency`]). Fails if the exchange
+    /// doesn't return exactly one status per order.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hypersdk::hypercore::{self, types::*, PrivateKeySigner};
+    ///
+    /// # async fn example(batch: BatchOrder) -> anyhow::Result<()> {
+    /// let client = hypercore::mainnet();
+    /// let signer: PrivateKeySigner = "your_key".parse()?;
+    /// let nonce = chrono::Utc::now().timestamp_millis() as u64;
+    ///
+    /// let resp = client.place_timed(&signer, batch, nonce, None, None).await?;
+    /// println!("round trip: {:?}", resp.latency());
+    /// for res in &resp.data {
+    ///     println!("order #{} ({}): {:?}", res.index, res.cloid, res.status);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn place_timed<S: SignerSync>(
+        &self,
+        signer: &S,
+        batch: BatchOrder,
+        nonce: u64,
+        vault_address: Option<Address>,
+        expires_after: Option<DateTime<Utc>>,
+    ) -> impl Future<Output = Result<ActionResponse<Vec<IndexedOrderStatus>>, ActionError<Cloid>>>
+    + Send
+    + 'static {
+        let cloids: Vec<_> = batch.orders.iter().map(|req| req.cloid).collect();
+        let future = self.send_orders(signer, batch, nonce, vault_address, expires_after);
+        async move {
+            let resp = future.await?;
+            let answered = resp.data.len();
+            if answered != cloids.len() {
+                let err = format!(
+                    "exchange returned {answered} statuses for {} orders",
+                    cloids.len()
+                );
+                return Err(ActionError::new(cloids, err));
+            }
+
+            Ok(resp.map(|statuses| {
+                statuses
+                    .into_iter()
+                    .zip(cloids)
+                    .enumerate()
+                    .map(|(index, (status, cloid))| IndexedOrderStatus {
+                        index,
+                        cloid,
+                        status,
+                    })
+                    .collect()
+            }))
+        }
+    }
+
+    /// Signs and sends a batch of orders, returning the statuses the exchange answered
+    /// in submission order.
+    fn send_orders<S: SignerSync>(
+        &self,
+        signer: &S,
+        batch: BatchOrder,
+        nonce: u64,
+        vault_address: Option<Address>,
+        expires_after: Option<DateTime<Utc>>,
+    ) -> impl Future<Output = Result<ActionResponse<Vec<OrderResponseStatus>>, ActionError<Cloid>>>
+    + Send
+    + 'static {
+        let cloids: Vec<_> = batch.orders.iter().map(|req| req.cloid).collect();
+        let breaker = self.breaker.clone();
+
+        // Checked before signing: a repeated cloid makes cancels by cloid ambiguous.
+        let future = match batch.check_cloids() {
+            Ok(()) => {
+                Ok(self.sign_and_send_sync(signer, batch, nonce, vault_address, expires_after))
+            }
+            Err(err) => Err(ActionError::with_source(cloids.clone(), err)),
+        };
         async move {
-            let resp = future.await.map_err(|err| ActionError {
-                ids: cloids.clone(),
-                err: err.to_string(),
-            })?;
+            let future = future?;
+            if let Some(Err(err)) = breaker.as_ref().map(CircuitBreaker::check) {
+                return Err(ActionError::new(cloids, err.to_string()));
+            }
+
+            let sent_at = Utc::now();
+            let start = Instant::now();
+            let resp = future.await;
+            if let Some(breaker) = &breaker {
+                breaker.record_response(&resp, cloids.len());
+            }
+            let resp = resp.map_err(|err| ActionError::new(cloids.clone(), err.to_string()))?;
+            let elapsed = start.elapsed();
+            let received_at = Utc::now();
 
             match resp {
-                Response::Ok(OkResponse::Order { statuses }) => Ok(statuses),
-                Response::Err(err) => Err(ActionError { ids: cloids, err }),
-                _ => Err(ActionError {
-                    ids: cloids,
-                    err: format!("unexpected response type: {resp:?}"),
+                Response::Ok(OkResponse::Order { statuses }) => Ok(ActionResponse {
+                    data: statuses,
+                    nonce,
+                    sent_at,
+                    received_at,
+                    elapsed,
                 }),
+                Response::Err(err) => Err(ActionError::new(cloids, err)),
+                _ => Err(ActionError::new(
+                    cloids,
+                    format!("unexpected response type: {resp:?}"),
+                )),
             }
         }
     }
@@ -1178,27 +2374,181 @@ impl Client {
         expires_after: Option<DateTime<Utc>>,
         builder: Option<Builder>,
     ) -> Result<Vec<OrderResponseStatus>> {
-        let batch = BatchOrder {
-            orders: vec![OrderRequest {
-                asset: market.asset_index(),
-                is_buy,
-                limit_px,
-                sz: size,
-                reduce_only: false,
-                order_type: OrderTypePlacement::Limit {
-                    tif: TimeInForce::Gtc,
-                },
-                cloid: Default::default(),
-            }],
-            grouping: OrderGrouping::Na,
-            builder,
+        let batch = market_batch(market.asset_index(), is_buy, limit_px, size, false, builder);
+        Ok(self
+            .place(signer, batch, nonce, vault_address, expires_after)
+            .await?)
+    }
+
+    /// Returns the limit price a slippage model gives a market order, rounded to the
+    /// market's tick.
+    ///
+    /// The model prices from a fresh L2 snapshot; `None` uses the client's
+    /// [default model](ClientBuilder::slippage).
+    pub async fn market_price(
+        &self,
+        market: impl Market,
+        is_buy: bool,
+        size: Decimal,
+        slippage: Option<&dyn SlippageModel>,
+    ) -> Result<Decimal> {
+        let book = self.l2_book(market.feed_coin(), None, None).await?;
+        let model = slippage.unwrap_or(&*self.slippage);
+        let ctx = SlippageContext {
+            is_buy,
+            size,
+            book: &book,
         };
+        let limit_px = model
+            .limit_px(&ctx)
+            .ok_or_else(|| anyhow!("{model:?} can't price {size} {} from the book", book.coin))?;
+        let side = if is_buy { Side::Bid } else { Side::Ask };
+        market
+            .tick_table()
+            .round_by_side(side, limit_px, false)
+            .ok_or_else(|| anyhow!("invalid market price {limit_px}"))
+    }
+
+    /// Places a market order priced by a slippage model.
+    ///
+    /// Like [`market_open`](Self::market_open), but the worst acceptable price comes
+    /// from [`market_price`](Self::market_price), and the order is sent as
+    /// [`TimeInForce::FrontendMarket`] so any unfilled size is cancelled.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hypersdk::{dec, hypercore::{self, NonceHandler, slippage::DepthBased}};
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let client = hypercore::mainnet();
+    /// let signer: hypercore::PrivateKeySigner = "your_key".parse()?;
+    /// let nonces = NonceHandler::default();
+    /// let perps = client.perps().await?;
+    /// let eth = perps.iter().find(|m| m.name == "ETH").expect("ETH");
+    ///
+    /// // Sweep the book for 1 ETH, accepting up to 20 bps past the sweep price.
+    /// let model = DepthBased::new(dec!(20));
+    /// client
+    ///     .market_order(&signer, eth, true, dec!(1), Some(&model), nonces.next(), None, None, None)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[allow(clippy::too_many_arguments)]
+    pub async fn market_order<S: SignerSync>(
+        &self,
+        signer: &S,
+        market: impl Market,
+        is_buy: bool,
+        size: Decimal,
+        slippage: Option<&dyn SlippageModel>,
+        nonce: u64,
+        vault_address: Option<Address>,
+        expires_after: Option<DateTime<Utc>>,
+        builder: Option<Builder>,
+    ) -> Result<Vec<OrderResponseStatus>> {
+        let limit_px = self.market_price(&market, is_buy, size, slippage).await?;
+        let batch = market_batch(market.asset_index(), is_buy, limit_px, size, false, builder);
+        Ok(self
+            .place(signer, batch, nonce, vault_address, expires_after)
+            .await?)
+    }
+
+    /// Closes the whole position on a perp with a reduce-only market order.
+    ///
+    /// The position is read from the clearinghouse state of `vault_address`, or of
+    /// the signer when trading for itself, and priced like
+    /// [`market_order`](Self::market_order).
+    #[allow(clippy::too_many_arguments)]
+    pub async fn market_close<S: Signer + SignerSync>(
+        &self,
+        signer: &S,
+        market: &PerpMarket,
+        slippage: Option<&dyn SlippageModel>,
+        nonce: u64,
+        vault_address: Option<Address>,
+        expires_after: Option<DateTime<Utc>>,
+        builder: Option<Builder>,
+    ) -> Result<Vec<OrderResponseStatus>> {
+        let user = vault_address.unwrap_or_else(|| signer.address());
+        let position = self.open_position(user, market).await?;
+
+        let is_buy = position.is_short();
+        let size = position.abs_size();
+        let limit_px = self.market_price(market, is_buy, size, slippage).await?;
+        let batch = market_batch(market.index, is_buy, limit_px, size, true, builder);
+        Ok(self
+            .place(signer, batch, nonce, vault_address, expires_after)
+            .await?)
+    }
+
+    /// Places a take-profit ladder scaling out of the position on a perp.
+    ///
+    /// The position is read from the clearinghouse state of `vault_address`, or of
+    /// the signer when trading for itself. Each level closes a fraction of it once the
+    /// price moves an offset away from the entry; see [`PerpMarket::scale_out`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hypersdk::{
+    ///     dec,
+    ///     hypercore::{self, NonceHandler},
+    /// };
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let client = hypercore::mainnet();
+    /// let signer: hypercore::PrivateKeySigner = "your_key".parse()?;
+    /// let nonces = NonceHandler::default();
+    /// let perps = client.perps().await?;
+    /// let eth = perps.iter().find(|m| m.name == "ETH").expect("ETH");
+    ///
+    /// // Close 50% at +1%, 25% at +2% and the rest at +4%.
+    /// let levels = [
+    ///     (dec!(0.5), dec!(0.01)),
+    ///     (dec!(0.25), dec!(0.02)),
+    ///     (dec!(0.25), dec!(0.04)),
+    /// ];
+    /// client
+    ///     .scale_out(&signer, eth, &levels, nonces.next(), None, None)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn scale_out<S: Signer + SignerSync>(
+        &self,
+        signer: &S,
+        market: &PerpMarket,
+        levels: &[(Decimal, Decimal)],
+        nonce: u64,
+        vault_address: Option<Address>,
+        expires_after: Option<DateTime<Utc>>,
+    ) -> Result<Vec<OrderResponseStatus>> {
+        let user = vault_address.unwrap_or_else(|| signer.address());
+        let position = self.open_position(user, market).await?;
+        let entry_px = position
+            .entry_px
+            .ok_or_else(|| anyhow!("{user} has no entry price on {}", market.name))?;
 
+        let batch = market.scale_out(position.szi, entry_px, levels)?;
         Ok(self
             .place(signer, batch, nonce, vault_address, expires_after)
             .await?)
     }
 
+    /// Returns the open position of `user` on a perp.
+    async fn open_position(&self, user: Address, market: &PerpMarket) -> Result<PositionData> {
+        let dex = market.name.split_once(':').map(|(dex, _)| dex.to_owned());
+        let state = self.clearinghouse_state(user, dex).await?;
+        state
+            .asset_positions
+            .into_iter()
+            .map(|asset| asset.position)
+            .find(|position| position.coin == market.name && !position.szi.is_zero())
+            .ok_or_else(|| anyhow!("{user} has no {} position", market.name))
+    }
+
     /// Cancel a batch of orders by exchange-assigned order ID (OID).
     ///
     /// Each cancel request specifies an asset and an order ID. Returns the status
@@ -1218,18 +2568,17 @@ impl Client {
         let future = self.sign_and_send_sync(signer, batch, nonce, vault_address, expires_after);
 
         async move {
-            let resp = future.await.map_err(|err| ActionError {
-                ids: oids.clone(),
-                err: err.to_string(),
-            })?;
+            let resp = future
+                .await
+                .map_err(|err| ActionError::new(oids.clone(), err.to_string()))?;
 
             match resp {
                 Response::Ok(OkResponse::Cancel { statuses }) => Ok(statuses),
-                Response::Err(err) => Err(ActionError { ids: oids, err }),
-                _ => Err(ActionError {
-                    ids: oids,
-                    err: format!("unexpected response type: {resp:?}"),
-                }),
+                Response::Err(err) => Err(ActionError::new(oids, err)),
+                _ => Err(ActionError::new(
+                    oids,
+                    format!("unexpected response type: {resp:?}"),
+                )),
             }
         }
     }
@@ -1253,18 +2602,17 @@ impl Client {
         let future = self.sign_and_send_sync(signer, batch, nonce, vault_address, expires_after);
 
         async move {
-            let resp = future.await.map_err(|err| ActionError {
-                ids: cloids.clone(),
-                err: err.to_string(),
-            })?;
+            let resp = future
+                .await
+                .map_err(|err| ActionError::new(cloids.clone(), err.to_string()))?;
 
             match resp {
                 Response::Ok(OkResponse::Cancel { statuses }) => Ok(statuses),
-                Response::Err(err) => Err(ActionError { ids: cloids, err }),
-                _ => Err(ActionError {
-                    ids: cloids,
-                    err: format!("unexpected response type: {resp:?}"),
-                }),
+                Response::Err(err) => Err(ActionError::new(cloids, err)),
+                _ => Err(ActionError::new(
+                    cloids,
+                    format!("unexpected response type: {resp:?}"),
+                )),
             }
         }
     }
@@ -1275,6 +2623,24 @@ impl Client {
     /// new price (`limit_px`) and/or size (`sz`). If only one field is changed, set
     /// the other to its current value. Returns the status for each modification attempt.
     /// Errors are wrapped in [`ActionError`] with the failed order IDs accessible via `.ids()`.
+    ///
+    /// Modifying keeps the order's identity instead of cancelling and re-placing it.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hypersdk::hypercore::{self, types::*, PrivateKeySigner};
+    ///
+    /// # async fn example(new_order: OrderRequest) -> anyhow::Result<()> {
+    /// let client = hypercore::mainnet();
+    /// let signer: PrivateKeySigner = "your_key".parse()?;
+    /// let nonce = chrono::Utc::now().timestamp_millis() as u64;
+    ///
+    /// let batch = BatchModify::from(Modify::by_oid(77738308, new_order));
+    /// let statuses = client.modify(&signer, batch, nonce, None, None).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
     pub fn modify<S: SignerSync>(
         &self,
         signer: &S,
@@ -1285,22 +2651,28 @@ impl Client {
     ) -> impl Future<Output = Result<Vec<OrderResponseStatus>, ActionError<OidOrCloid>>> + Send + 'static
     {
         let cloids: Vec<_> = batch.modifies.iter().map(|req| req.oid).collect();
+        let breaker = self.breaker.clone();
 
         let future = self.sign_and_send_sync(signer, batch, nonce, vault_address, expires_after);
 
         async move {
-            let resp = future.await.map_err(|err| ActionError {
-                ids: cloids.clone(),
-                err: err.to_string(),
-            })?;
+            if let Some(Err(err)) = breaker.as_ref().map(CircuitBreaker::check) {
+                return Err(ActionError::new(cloids, err.to_string()));
+            }
+
+            let resp = future.await;
+            if let Some(breaker) = &breaker {
+                breaker.record_response(&resp, cloids.len());
+            }
+            let resp = resp.map_err(|err| ActionError::new(cloids.clone(), err.to_string()))?;
 
             match resp {
                 Response::Ok(OkResponse::Order { statuses }) => Ok(statuses),
-                Response::Err(err) => Err(ActionError { ids: cloids, err }),
-                _ => Err(ActionError {
-                    ids: cloids,
-                    err: format!("unexpected response type: {resp:?}"),
-                }),
+                Response::Err(err) => Err(ActionError::new(cloids, err)),
+                _ => Err(ActionError::new(
+                    cloids,
+                    format!("unexpected response type: {resp:?}"),
+                )),
             }
         }
     }
@@ -1749,32 +3121,108 @@ impl Client {
         resp.into_default()
     }
 
-    /// Updates isolated margin for a position.
-    pub async fn update_isolated_margin<S: SignerSync>(
+    /// Update leverage for a perpetual market, validated against its metadata.
+    ///
+    /// Same as [`update_leverage`](Self::update_leverage), but takes the [`PerpMarket`]
+    /// and rejects the request locally if `leverage` is zero or above
+    /// [`PerpMarket::max_leverage`], or if cross margin is requested on an
+    /// isolated-only market.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use hypersdk::hypercore::{self, PrivateKeySigner, NonceHandler};
+    ///
+    /// let client = hypercore::mainnet();
+    /// let signer: PrivateKeySigner = "0x...".parse()?;
+    /// let nonce_handler = NonceHandler::default();
+    ///
+    /// let perps = client.perps().await?;
+    /// let btc = perps.iter().find(|m| m.name == "BTC").unwrap();
+    /// client.set_leverage(&signer, btc, false, 5, nonce_handler.next(), None, None).await?;
+    /// ```
+    #[allow(clippy::too_many_arguments)]
+    pub async fn set_leverage<S: SignerSync>(
         &self,
         signer: &S,
-        asset: usize,
-        is_buy: bool,
-        ntli: u64,
+        market: &PerpMarket,
+        is_cross: bool,
+        leverage: u32,
         nonce: u64,
         vault_address: Option<Address>,
         expires_after: Option<DateTime<Utc>>,
     ) -> Result<()> {
+        if leverage == 0 || u64::from(leverage) > market.max_leverage {
+            return Err(anyhow!(
+                "leverage {leverage}x out of range for {} (1-{}x)",
+                market.name,
+                market.max_leverage
+            ));
+        }
+        if is_cross && !market.allows_cross() {
+            return Err(anyhow!("{} only supports isolated margin", market.name));
+        }
+
+        self.update_leverage(
+            signer,
+            market.index,
+            is_cross,
+            leverage,
+            nonce,
+            vault_address,
+            expires_after,
+        )
+        .await
+    }
+
+    /// Adds or removes margin from an isolated-margin position.
+    ///
+    /// Build the request with [`UpdateIsolatedMargin::add`] or
+    /// [`UpdateIsolatedMargin::remove`], which take USD amounts.
+    /// On success, returns the applied change as an [`IsolatedMarginUpdate`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use hypersdk::hypercore::{self, PrivateKeySigner, NonceHandler, api::UpdateIsolatedMargin};
+    /// use rust_decimal::dec;
+    ///
+    /// let client = hypercore::mainnet();
+    /// let signer: PrivateKeySigner = "0x...".parse()?;
+    /// let nonce_handler = NonceHandler::default();
+    ///
+    /// // Add $25 of margin to a long BTC position
+    /// let update = UpdateIsolatedMargin::add(0, true, dec!(25)).unwrap();
+    /// let applied = client
+    ///     .update_isolated_margin(&signer, update, nonce_handler.next(), None, None)
+    ///     .await?;
+    /// println!("added ${} to asset {}", applied.usd, applied.asset);
+    /// ```
+    pub async fn update_isolated_margin<S: SignerSync>(
+        &self,
+        signer: &S,
+        update: UpdateIsolatedMargin,
+        nonce: u64,
+        vault_address: Option<Address>,
+        expires_after: Option<DateTime<Utc>>,
+    ) -> Result<IsolatedMarginUpdate> {
+        let applied = IsolatedMarginUpdate {
+            asset: update.asset,
+            is_buy: update.is_buy,
+            usd: update.usd(),
+        };
         let resp = self
             .sign_and_send_sync(
                 signer,
-                Action::UpdateIsolatedMargin(UpdateIsolatedMargin {
-                    asset,
-                    is_buy,
-                    ntli,
-                }),
+                Action::UpdateIsolatedMargin(update),
                 nonce,
                 vault_address,
                 expires_after,
             )
             .await?;
 
-        resp.into_default()
+        resp.into_default()?;
+        Ok(applied)
     }
 
     /// Toggle the EVM user "big blocks" setting via signed action.
@@ -1869,6 +3317,39 @@ impl Client {
         self.send_info_request("max_builder_fee", &req).await
     }
 
+    /// Checks that `user` has approved `builder` for at least its fee.
+    ///
+    /// Orders carrying a builder fee above the approved maximum are rejected by the
+    /// exchange. Check once before attaching a builder to a user's orders, and ask
+    /// for an [`approve_builder_fee`](Self::approve_builder_fee) if it fails.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hypersdk::{Address, hypercore::{self, Builder}};
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let client = hypercore::mainnet();
+    /// let user: Address = "0x...".parse()?;
+    /// let builder = Builder {
+    ///     builder_address: "0x...".parse()?,
+    ///     fee: 10, // 1 bps
+    /// };
+    /// client.ensure_builder_approved(user, &builder).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn ensure_builder_approved(&self, user: Address, builder: &Builder) -> Result<()> {
+        let max_fee = self.max_builder_fee(user, builder.builder_address).await?;
+        anyhow::ensure!(
+            builder.fee <= max_fee,
+            "{user} approved builder {} for at most {max_fee} tenths of a bps, order fee is {}",
+            builder.builder_address,
+            builder.fee
+        );
+        Ok(())
+    }
+
     /// Query the current account abstraction mode for a user.
     ///
     /// Sends an info request to `/info` with type `"abstraction"`.
@@ -2048,6 +3529,19 @@ impl Client {
         }
     }
 
+    /// Carries the client's read-only setting over to a WebSocket.
+    fn configure_websocket(&self, ws: super::WebSocket) -> super::WebSocket {
+        if self.read_only { ws.read_only() } else { ws }
+    }
+
+    /// Fails with [`ReadOnly`] if exchange actions are disabled.
+    fn check_writable(&self) -> Result<()> {
+        if self.read_only {
+            return Err(ReadOnly.into());
+        }
+        Ok(())
+    }
+
     /// Send a signed action hashing.
     fn sign_and_send_sync<S: SignerSync, A: Into<Action>>(
         &self,
@@ -2058,13 +3552,18 @@ impl Client {
         maybe_expires_after: Option<DateTime<Utc>>,
     ) -> impl Future<Output = Result<Response>> + Send + 'static {
         let action: Action = action.into();
-        let res = action.sign_sync(
-            signer,
-            nonce,
-            maybe_vault_address,
-            maybe_expires_after,
-            self.chain,
-        );
+        let res = self
+            .check_writable()
+            .and_then(|()| self.resolve_expires_after(&action, maybe_expires_after))
+            .and_then(|maybe_expires_after| {
+                action.sign_sync(
+                    signer,
+                    nonce,
+                    maybe_vault_address,
+                    maybe_expires_after,
+                    self.chain,
+                )
+            });
 
         let http_client = self.http_client.clone();
         let mut url = self.base_url.clone();
@@ -2097,7 +3596,9 @@ impl Client {
         maybe_vault_address: Option<Address>,
         maybe_expires_after: Option<DateTime<Utc>>,
     ) -> Result<Response> {
+        self.check_writable()?;
         let action: Action = action.into();
+        let maybe_expires_after = self.resolve_expires_after(&action, maybe_expires_after)?;
         let req = action
             .sign(
                 signer,
@@ -2111,21 +3612,49 @@ impl Client {
         self.send(req).await
     }
 
+    /// Signs `action` with the registry's signer for its purpose and submits it.
+    ///
+    /// Fails without sending anything if no signer is registered for the action's
+    /// [`Purpose`](crate::hypercore::registry::Purpose) or its policy refuses the action.
+    pub async fn send_with(
+        &self,
+        registry: &SignerRegistry,
+        action: impl Into<Action>,
+        nonce: u64,
+        maybe_vault_address: Option<Address>,
+        maybe_expires_after: Option<DateTime<Utc>>,
+    ) -> Result<Response> {
+        self.check_writable()?;
+        let action = action.into();
+        let maybe_expires_after = self.resolve_expires_after(&action, maybe_expires_after)?;
+        let req = registry
+            .sign(
+                action,
+                nonce,
+                maybe_vault_address,
+                maybe_expires_after,
+                self.chain,
+            )
+            .await?;
+        self.send(req).await
+    }
+
     #[doc(hidden)]
     pub async fn send(&self, req: ActionRequest) -> Result<Response> {
+        self.check_writable()?;
         let http_client = self.http_client.clone();
         let mut url = self.base_url.clone();
         url.set_path("/exchange");
 
         let res = http_client
             .post(url)
-            .timeout(Duration::from_secs(5))
             // .header(header::CONTENT_TYPE, "application/json")
             // .body(text)
             .json(&req)
             .send()
             .await?;
 
+        self.observe_server_time(res.headers());
         let status = res.status();
         let bytes = res.bytes().await?;
         let text = String::from_utf8_lossy(&bytes);
@@ -2140,11 +3669,74 @@ impl Client {
     }
 
     /// Returns combined perpetual metadata and asset contexts.
+    ///
+    /// See [`perp_contexts`](Self::perp_contexts) for the typed contexts.
     pub async fn meta_and_asset_ctxs(&self, dex: Option<String>) -> Result<serde_json::Value> {
         let req = InfoRequest::MetaAndAssetCtxs { dex };
         self.send_info_request("meta_and_asset_ctxs", &req).await
     }
 
+    /// Returns the market context of every perpetual on the main DEX.
+    ///
+    /// Unlike [`perps`](Self::perps), which only carries static metadata, each context
+    /// has the live mark and oracle prices, funding rate and premium, open interest and
+    /// 24h volume, in the same order as the DEX's universe.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use hypersdk::hypercore;
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let client = hypercore::mainnet();
+    /// for perp in client.perp_contexts().await? {
+    ///     println!(
+    ///         "{}: mark {} oracle {} funding {}",
+    ///         perp.coin, perp.ctx.mark_px, perp.ctx.oracle_px, perp.ctx.funding
+    ///     );
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn perp_contexts(&self) -> Result<Vec<PerpContext>> {
+        self.fetch_perp_contexts(None).await
+    }
+
+    /// Returns [`perp_contexts`](Self::perp_contexts) with the time of the snapshot
+    /// and of its receipt.
+    ///
+    /// The contexts carry no timestamp, so the server time is the response's `Date`
+    /// header, to the second. See [`timestamped`](super::timestamped).
+    pub async fn perp_contexts_timestamped(&self) -> Result<Timestamped<Vec<PerpContext>>> {
+        let req = InfoRequest::MetaAndAssetCtxs { dex: None };
+        let res: Timestamped<(PerpUniverse, Vec<PerpAssetCtx>)> = self
+            .send_info_request_timestamped("perp_contexts", &req)
+            .await?;
+        let Timestamped {
+            value: (meta, ctxs),
+            server_time,
+            received_at,
+        } = res;
+        Ok(Timestamped {
+            value: zip_perp_contexts(meta, ctxs)?,
+            server_time,
+            received_at,
+        })
+    }
+
+    /// Returns the market context of every perpetual on a HIP-3 DEX.
+    ///
+    /// See [`perp_contexts`](Self::perp_contexts).
+    pub async fn perp_contexts_from(&self, dex: Dex) -> Result<Vec<PerpContext>> {
+        self.fetch_perp_contexts(Some(dex.name)).await
+    }
+
+    async fn fetch_perp_contexts(&self, dex: Option<String>) -> Result<Vec<PerpContext>> {
+        let req = InfoRequest::MetaAndAssetCtxs { dex };
+        let (meta, ctxs): (PerpUniverse, Vec<PerpAssetCtx>) =
+            self.send_info_request("perp_contexts", &req).await?;
+        zip_perp_contexts(meta, ctxs)
+    }
+
     /// Returns combined spot metadata and asset contexts.
     pub async fn spot_meta_and_asset_ctxs(&self) -> Result<serde_json::Value> {
         let req = InfoRequest::SpotMetaAndAssetCtxs;
@@ -2158,19 +3750,72 @@ impl Client {
         self.send_info_request("user_rate_limit", &req).await
     }
 
-    /// Returns the user's funding history.
+    /// Returns the user's funding payments within a time range, oldest first.
+    ///
+    /// Each entry carries the coin, the USDC paid or received (negative when paid), the
+    /// funding rate and the position size it was applied to, so funding costs can be
+    /// attributed per position.
+    ///
+    /// The API returns at most 500 entries per request; this method pages through the
+    /// range until it is exhausted.
+    ///
+    /// # Parameters
+    ///
+    /// - `user`: The address to query funding for
+    /// - `start_time`: Start timestamp in milliseconds (inclusive)
+    /// - `end_time`: Optional end timestamp in milliseconds (inclusive). Defaults to now if `None`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::collections::HashMap;
+    ///
+    /// use hypersdk::{Decimal, hypercore};
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let client = hypercore::mainnet();
+    /// let user = "0x...".parse()?;
+    ///
+    /// let end = chrono::Utc::now().timestamp_millis() as u64;
+    /// let start = end - 7 * 24 * 60 * 60 * 1000;
+    ///
+    /// let mut by_coin: HashMap<String, Decimal> = HashMap::new();
+    /// for entry in client.user_funding(user, start, Some(end)).await? {
+    ///     *by_coin.entry(entry.delta.coin).or_default() += entry.delta.usdc;
+    /// }
+    /// for (coin, usdc) in by_coin {
+    ///     println!("{coin}: {usdc} USDC");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
     pub async fn user_funding(
         &self,
         user: Address,
         start_time: u64,
         end_time: Option<u64>,
     ) -> Result<Vec<UserFundingEntry>> {
-        let req = InfoRequest::UserFunding {
-            user,
-            start_time,
-            end_time,
-        };
-        self.send_info_request("user_funding", &req).await
+        let mut entries = vec![];
+        let mut start_time = start_time;
+        loop {
+            let req = InfoRequest::UserFunding {
+                user,
+                start_time,
+                end_time,
+            };
+            let page: Vec<UserFundingEntry> = self.send_info_request("user_funding", &req).await?;
+            let full = page.len() >= USER_FUNDING_PAGE_SIZE;
+            let Some(last) = page.last().map(|entry| entry.time) else {
+                break;
+            };
+            append_funding_page(&mut entries, page, start_time);
+
+            if !full || end_time.is_some_and(|end| last >= end) {
+                break;
+            }
+            start_time = if last > start_time { last } else { last + 1 };
+        }
+        Ok(entries)
     }
 
     /// Returns the user's non-funding ledger updates.
@@ -2189,21 +3834,136 @@ impl Client {
             .await
     }
 
-    /// Returns predicted funding rates for all coins.
-    pub async fn predicted_fundings(
-        &self,
-    ) -> Result<Vec<(String, Vec<(String, PredictedFundingVenue)>)>> {
+    /// Returns predicted funding rates for all coins on Hyperliquid and other venues.
+    ///
+    /// Venues settle funding at different intervals; compare them with
+    /// [`PredictedFundingVenue::hourly_rate`](crate::hypercore::PredictedFundingVenue::hourly_rate).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hypersdk::hypercore::{self, FundingVenue};
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let client = hypercore::mainnet();
+    /// for funding in client.predicted_fundings().await? {
+    ///     if let Some(spread) = funding.hourly_spread(&FundingVenue::Binance) {
+    ///         println!("{}: HL - Binance = {spread}/h", funding.coin);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn predicted_fundings(&self) -> Result<Vec<PredictedFunding>> {
         let req = InfoRequest::PredictedFundings;
         self.send_info_request("predicted_fundings", &req).await
     }
 
-    /// Returns coins at open interest cap.
+    /// Returns the next funding settlement of every Hyperliquid perp.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hypersdk::hypercore;
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let client = hypercore::mainnet();
+    /// let calendar = client.funding_calendar().await?;
+    /// if let Some(left) = calendar.until_funding("BTC") {
+    ///     println!("BTC funding in {}s", left.as_secs());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn funding_calendar(&self) -> Result<FundingCalendar> {
+        let fundings = self.predicted_fundings().await?;
+        Ok(FundingCalendar::new(&fundings, &FundingVenue::Hyperliquid))
+    }
+
+    /// Returns the perps of a DEX that are at their open interest cap.
+    ///
+    /// While a perp is at its cap, orders that would increase open interest are
+    /// rejected; only reducing orders are accepted. Routers can check this list
+    /// instead of sending orders that will bounce. `None` queries the main DEX.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use hypersdk::hypercore;
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let client = hypercore::mainnet();
+    /// let capped = client.perps_at_open_interest_cap(None).await?;
+    /// if capped.iter().any(|coin| coin == "BTC") {
+    ///     println!("BTC is at its open interest cap, only reduce-only orders will fill");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
     pub async fn perps_at_open_interest_cap(&self, dex: Option<String>) -> Result<Vec<String>> {
         let req = InfoRequest::PerpsAtOpenInterestCap { dex };
         self.send_info_request("perps_at_open_interest_cap", &req)
             .await
     }
 
+    /// Returns the perps at their open interest cap on the main DEX and every HIP-3
+    /// DEX.
+    ///
+    /// HIP-3 perps are named with their DEX prefix (`"xyz:TSLA"`), so the set can be
+    /// checked against any market name. See
+    /// [`perps_at_open_interest_cap`](Self::perps_at_open_interest_cap).
+    pub async fn all_perps_at_open_interest_cap(&self) -> Result<HashSet<String>> {
+        let dexes = self.perp_dexes().await?;
+        let names = std::iter::once(None).chain(dexes.into_iter().map(|dex| Some(dex.name)));
+        let capped =
+            futures::future::try_join_all(names.map(|dex| self.perps_at_open_interest_cap(dex)))
+                .await?;
+        Ok(capped.into_iter().flatten().collect())
+    }
+
+    /// Returns the server time and any special exchange statuses.
+    pub async fn exchange_status(&self) -> Result<ExchangeStatus> {
+        self.send_info_request("exchange_status", &InfoRequest::ExchangeStatus)
+            .await
+    }
+
+    /// Measures the offset of the server clock from the local clock.
+    ///
+    /// Nonces and `expiresAfter` are timestamps checked against the server clock, so a
+    /// skewed local clock gets actions rejected. This times an
+    /// [`exchange_status`](Self::exchange_status) request and compares the server
+    /// time to the midpoint of the round trip, which is accurate to half the round
+    /// trip rather than the second of the `Date` header. The result also updates
+    /// [`clock_skew`](Self::clock_skew).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// use hypersdk::hypercore::{self, NonceHandler};
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let client = hypercore::mainnet();
+    /// let skew = client.measure_clock_skew().await?;
+    /// if skew.exceeds(Duration::from_millis(500)) {
+    ///     eprintln!("clock is off by {}ms", skew.offset.num_milliseconds());
+    /// }
+    ///
+    /// let nonces = NonceHandler::default();
+    /// nonces.set_clock_skew(skew.offset);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn measure_clock_skew(&self) -> Result<ClockSkew> {
+        let sent = Utc::now().timestamp_millis() as u64;
+        let status = self.exchange_status().await?;
+        let received = Utc::now().timestamp_millis() as u64;
+        let skew = ClockSkew::new(sent, status.time, received);
+        self.clock_skew_ms
+            .store(skew.offset.num_milliseconds(), atomic::Ordering::Relaxed);
+        Ok(skew)
+    }
+
     /// Returns perp deploy auction status.
     pub async fn perp_deploy_auction_status(&self) -> Result<DeployAuctionStatus> {
         let req = InfoRequest::PerpDeployAuctionStatus;
@@ -2223,6 +3983,33 @@ impl Client {
         self.send_info_request("perp_dex_limits", &req).await
     }
 
+    /// Returns how much of its OI caps each perp of a HIP-3 DEX uses.
+    ///
+    /// Combines [`perp_dex_limits`](Self::perp_dex_limits) with the DEX's market
+    /// contexts; see [`PerpDexLimits::utilization`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hypersdk::hypercore;
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let client = hypercore::mainnet();
+    /// let dex = client.perp_dexs().await?.into_iter().find(|dex| dex.name() == "xyz");
+    /// for perp in client.perp_dex_utilization(dex.unwrap()).await? {
+    ///     println!("{}: {:?} of cap", perp.coin, perp.ntl_utilization);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn perp_dex_utilization(&self, dex: Dex) -> Result<Vec<OiUtilization>> {
+        let (limits, contexts) = futures::try_join!(
+            self.perp_dex_limits(dex.name().to_owned()),
+            self.perp_contexts_from(dex)
+        )?;
+        Ok(limits.utilization(&contexts))
+    }
+
     /// Returns total net deposit for a HIP-3 DEX.
     pub async fn perp_dex_status(&self, dex: String) -> Result<PerpDexStatus> {
         let req = InfoRequest::PerpDexStatus { dex };
@@ -2268,23 +4055,111 @@ impl Client {
     }
 
     /// Returns detailed token info by tokenId.
+    ///
+    /// Besides supply and prices, the details carry the token's
+    /// [genesis distribution](TokenDetails::genesis) and the balances excluded from
+    /// its circulating supply.
     pub async fn token_details(&self, token_id: String) -> Result<TokenDetails> {
         let req = InfoRequest::TokenDetails { token_id };
         self.send_info_request("token_details", &req).await
     }
 
+    /// Returns the genesis distribution of a spot token by tokenId.
+    ///
+    /// The API doesn't list current holders; the genesis balances and the
+    /// non-circulating balances from [`token_details`](Self::token_details) are the
+    /// holder data it exposes.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use hypersdk::hypercore;
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let client = hypercore::mainnet();
+    /// let genesis = client
+    ///     .token_genesis("0xc1fb593aeffbeb02f85e0308e9956a90".into())
+    ///     .await?;
+    /// let total = genesis.total();
+    /// for (holder, balance) in genesis.top_holders(10) {
+    ///     println!("{holder}: {balance} ({}%)", balance / total * rust_decimal::Decimal::ONE_HUNDRED);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn token_genesis(&self, token_id: String) -> Result<TokenGenesis> {
+        let details = self.token_details(token_id).await?;
+        Ok(details.genesis.unwrap_or_default())
+    }
+
     /// Returns settled outcome market result.
     pub async fn settled_outcome(&self, outcome: u64) -> Result<serde_json::Value> {
         let req = InfoRequest::SettledOutcome { outcome };
         self.send_info_request("settled_outcome", &req).await
     }
 
-    /// Returns user portfolio performance.
-    pub async fn portfolio(&self, user: Address) -> Result<serde_json::Value> {
+    /// Returns the account value and PnL histories of a user.
+    ///
+    /// Histories cover the last day, week, month and all time, each for the whole
+    /// account and for perps only.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hypersdk::hypercore::{self, PortfolioPeriod};
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let client = hypercore::mainnet();
+    /// let user = "0x...".parse()?;
+    /// let portfolio = client.portfolio(user).await?;
+    /// if let Some(month) = portfolio.period(&PortfolioPeriod::Month) {
+    ///     for (time, value) in &month.account_value_history {
+    ///         println!("{time}: {value}");
+    ///     }
+    ///     println!("max drawdown: {}", month.max_drawdown());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn portfolio(&self, user: Address) -> Result<Portfolio> {
         let req = InfoRequest::Portfolio { user };
         self.send_info_request("portfolio", &req).await
     }
 
+    /// Returns the trader leaderboard.
+    ///
+    /// The leaderboard comes from the stats service at
+    /// [`leaderboard_url`](crate::hypercore::leaderboard_url) and lists every ranked
+    /// trader, so the response is large; fetch it sparingly.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hypersdk::hypercore::{self, PortfolioPeriod};
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let client = hypercore::mainnet();
+    /// let leaderboard = client.leaderboard().await?;
+    /// for entry in leaderboard.top(&PortfolioPeriod::Week, 10, |perf| perf.pnl) {
+    ///     let week = entry.window(&PortfolioPeriod::Week).unwrap();
+    ///     println!("{}: pnl {} roi {}", entry.eth_address, week.pnl, week.roi);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn leaderboard(&self) -> Result<Leaderboard> {
+        let res = self
+            .http_client
+            .get(leaderboard_url(self.chain))
+            .send()
+            .await?;
+        let status = res.status();
+        let text = res.text().await?;
+        if !status.is_success() {
+            return Err(ApiError(format!("[leaderboard] HTTP {status} body={text}")).into());
+        }
+        decode(&text, self.decode_mode).context("[leaderboard] invalid body")
+    }
+
     /// Returns referral state and rewards.
     pub async fn referral(&self, user: Address) -> Result<serde_json::Value> {
         let req = InfoRequest::Referral { user };
@@ -2309,18 +4184,41 @@ impl Client {
         self.send_info_request("delegator_summary", &req).await
     }
 
-    /// Returns delegation history for a user.
-    pub async fn delegator_history(&self, user: Address) -> Result<Vec<serde_json::Value>> {
+    /// Returns staking history for a user: delegations, deposits, and withdrawals.
+    pub async fn delegator_history(&self, user: Address) -> Result<Vec<DelegatorEvent>> {
         let req = InfoRequest::DelegatorHistory { user };
         self.send_info_request("delegator_history", &req).await
     }
 
-    /// Returns delegation rewards for a user.
-    pub async fn delegator_rewards(&self, user: Address) -> Result<Vec<serde_json::Value>> {
+    /// Returns staking rewards accrued by a user, including validator commission.
+    pub async fn delegator_rewards(&self, user: Address) -> Result<Vec<DelegatorReward>> {
         let req = InfoRequest::DelegatorRewards { user };
         self.send_info_request("delegator_rewards", &req).await
     }
 
+    /// Returns every validator with its stake, commission, and uptime.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hypersdk::hypercore;
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let client = hypercore::mainnet();
+    /// let mut validators = client.validator_summaries().await?;
+    /// validators.retain(|v| v.is_active && !v.is_jailed);
+    /// validators.sort_by_key(|v| v.commission);
+    /// for v in validators.iter().take(5) {
+    ///     println!("{} {}: {} HYPE staked", v.name, v.commission, v.stake_hype());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn validator_summaries(&self) -> Result<Vec<ValidatorSummary>> {
+        let req = InfoRequest::ValidatorSummaries;
+        self.send_info_request("validator_summaries", &req).await
+    }
+
     /// Returns borrow/lend user state.
     pub async fn borrow_lend_user_state(&self, user: Address) -> Result<serde_json::Value> {
         let req = InfoRequest::BorrowLendUserState { user };
@@ -2349,12 +4247,69 @@ impl Client {
     }
 
     /// Returns TWAP slice fills for a user via info endpoint.
+    ///
+    /// These are the fills the `userTwapSliceFills` WebSocket feed delivers; fetch
+    /// them after a reconnect to reconcile slices missed while disconnected.
     pub async fn user_twap_slice_fills(&self, user: Address) -> Result<Vec<TwapSliceFill>> {
         let req = InfoRequest::UserTwapSliceFills { user };
         self.send_info_request("user_twap_slice_fills", &req).await
     }
 
-    /// Returns L2 order book snapshot.
+    /// Returns the TWAP lifecycle history for a user via info endpoint.
+    ///
+    /// These are the entries the `userTwapHistory` WebSocket feed delivers, with
+    /// their [`twap_id`](TwapHistory::twap_id) set.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hypersdk::{Address, hypercore};
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let client = hypercore::mainnet();
+    /// let user: Address = "0x...".parse()?;
+    /// for twap in client.user_twap_history(user).await? {
+    ///     println!(
+    ///         "{:?} {} {}/{} {:?}",
+    ///         twap.twap_id,
+    ///         twap.state.coin,
+    ///         twap.state.executed_sz,
+    ///         twap.state.sz,
+    ///         twap.status.status
+    ///     );
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn user_twap_history(&self, user: Address) -> Result<Vec<TwapHistory>> {
+        let req = InfoRequest::TwapHistory { user };
+        self.send_info_request("user_twap_history", &req).await
+    }
+
+    /// Returns an L2 order book snapshot.
+    ///
+    /// This is the same [`L2Book`] the `l2Book` WebSocket feed delivers, marked as a
+    /// snapshot, so one-shot depth queries don't need a WebSocket. Up to 20 levels per
+    /// side are returned.
+    ///
+    /// # Parameters
+    ///
+    /// - `coin`: Market name (`"BTC"`, `"@107"`, `"xyz:BTC"`)
+    /// - `n_sig_figs`: Aggregate levels to 2-5 significant figures; `None` for full precision
+    /// - `mantissa`: With `n_sig_figs: Some(5)`, aggregate to a multiple of 1, 2 or 5
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hypersdk::hypercore;
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let client = hypercore::mainnet();
+    /// let book = client.l2_book("BTC".into(), None, None).await?;
+    /// println!("mid {:?} spread {:?}", book.mid(), book.spread());
+    /// # Ok(())
+    /// # }
+    /// ```
     pub async fn l2_book(
         &self,
         coin: String,
@@ -2362,11 +4317,42 @@ impl Client {
         mantissa: Option<u8>,
     ) -> Result<L2Book> {
         let req = InfoRequest::L2Book {
-            coin,
+            coin: coin.clone(),
+            n_sig_figs,
+            mantissa,
+        };
+        let book: Option<L2Book> = self.send_info_request("l2_book", &req).await?;
+        let mut book = book.ok_or_else(|| anyhow!("unknown coin {coin}"))?;
+        book.snapshot = true;
+        Ok(book)
+    }
+
+    /// Returns [`l2_book`](Self::l2_book) with the time of the snapshot and of its
+    /// receipt.
+    ///
+    /// See [`timestamped`](super::timestamped).
+    pub async fn l2_book_timestamped(
+        &self,
+        coin: String,
+        n_sig_figs: Option<u8>,
+        mantissa: Option<u8>,
+    ) -> Result<Timestamped<L2Book>> {
+        let req = InfoRequest::L2Book {
+            coin: coin.clone(),
             n_sig_figs,
             mantissa,
         };
-        self.send_info_request("l2_book", &req).await
+        let book: Timestamped<Option<L2Book>> =
+            self.send_info_request_timestamped("l2_book", &req).await?;
+        let Some(mut value) = book.value else {
+            return Err(anyhow!("unknown coin {coin}"));
+        };
+        value.snapshot = true;
+        Ok(Timestamped {
+            server_time: value.time,
+            value,
+            received_at: book.received_at,
+        })
     }
 
     /// Returns simple open orders for a user.
@@ -2387,6 +4373,7 @@ impl Client {
         expires_after: Option<DateTime<Utc>>,
     ) -> Result<Response> {
         let action = Action::TwapOrder { twap: params };
+        let expires_after = self.resolve_expires_after(&action, expires_after)?;
         let req = action.sign_sync(signer, nonce, vault_address, expires_after, self.chain)?;
         self.send(req).await
     }
@@ -2405,6 +4392,7 @@ impl Client {
             a: asset,
             t: twap_id,
         };
+        let expires_after = self.resolve_expires_after(&action, expires_after)?;
         let req = action.sign_sync(signer, nonce, vault_address, expires_after, self.chain)?;
         self.send(req).await
     }
@@ -2426,6 +4414,7 @@ impl Client {
             amount,
             time: nonce,
         });
+        let expires_after = self.resolve_expires_after(&action, expires_after)?;
         let req = action.sign_sync(signer, nonce, vault_address, expires_after, self.chain)?;
         self.send(req).await?.into_default()
     }
@@ -2447,11 +4436,15 @@ impl Client {
             to_perp,
             nonce,
         });
+        let expires_after = self.resolve_expires_after(&action, expires_after)?;
         let req = action.sign_sync(signer, nonce, vault_address, expires_after, self.chain)?;
         self.send(req).await?.into_default()
     }
 
-    /// Stake native token (HYPE).
+    /// Transfer HYPE from the spot balance to the staking balance.
+    ///
+    /// `wei` is in units of 1e-8 HYPE; see [`staking_transfer`](Self::staking_transfer)
+    /// for a `Decimal` amount.
     pub async fn stake<S: SignerSync>(
         &self,
         signer: &S,
@@ -2460,12 +4453,20 @@ impl Client {
         vault_address: Option<Address>,
         expires_after: Option<DateTime<Utc>>,
     ) -> Result<()> {
-        let action = Action::CDeposit { wei };
+        let action = Action::CDeposit(CDepositAction {
+            signature_chain_id: self.chain.arbitrum_id().to_string(),
+            hyperliquid_chain: self.chain,
+            wei,
+            nonce,
+        });
+        let expires_after = self.resolve_expires_after(&action, expires_after)?;
         let req = action.sign_sync(signer, nonce, vault_address, expires_after, self.chain)?;
         self.send(req).await?.into_default()
     }
 
-    /// Unstake native token (HYPE). 7-day queue.
+    /// Transfer HYPE from the staking balance to the spot balance. 7-day queue.
+    ///
+    /// `wei` is in units of 1e-8 HYPE.
     pub async fn unstake<S: SignerSync>(
         &self,
         signer: &S,
@@ -2474,12 +4475,20 @@ impl Client {
         vault_address: Option<Address>,
         expires_after: Option<DateTime<Utc>>,
     ) -> Result<()> {
-        let action = Action::CWithdraw { wei };
+        let action = Action::CWithdraw(CWithdrawAction {
+            signature_chain_id: self.chain.arbitrum_id().to_string(),
+            hyperliquid_chain: self.chain,
+            wei,
+            nonce,
+        });
+        let expires_after = self.resolve_expires_after(&action, expires_after)?;
         let req = action.sign_sync(signer, nonce, vault_address, expires_after, self.chain)?;
         self.send(req).await?.into_default()
     }
 
-    /// Delegate or undelegate staked tokens to a validator.
+    /// Delegate or undelegate staked HYPE to a validator.
+    ///
+    /// `wei` is in units of 1e-8 HYPE.
     pub async fn token_delegate<S: SignerSync>(
         &self,
         signer: &S,
@@ -2491,14 +4500,69 @@ impl Client {
         expires_after: Option<DateTime<Utc>>,
     ) -> Result<()> {
         let action = Action::TokenDelegate(TokenDelegateAction {
+            signature_chain_id: self.chain.arbitrum_id().to_string(),
+            hyperliquid_chain: self.chain,
             validator,
-            is_undelegate,
             wei,
+            is_undelegate,
+            nonce,
         });
+        let expires_after = self.resolve_expires_after(&action, expires_after)?;
         let req = action.sign_sync(signer, nonce, vault_address, expires_after, self.chain)?;
         self.send(req).await?.into_default()
     }
 
+    /// Move HYPE between the spot and staking balances.
+    ///
+    /// # Parameters
+    ///
+    /// - `signer`: The signer for signing the action
+    /// - `amount`: Amount of HYPE (e.g. `dec!(10.5)`; converted internally to wei)
+    /// - `nonce`: Unique nonce (typically current timestamp in milliseconds)
+    /// - `is_deposit`: `true` to stake, `false` to unstake
+    ///
+    /// <https://hyperliquid.gitbook.io/hyperliquid-docs/for-developers/api/exchange-endpoint#deposit-into-staking>
+    pub async fn staking_transfer<S: SignerSync>(
+        &self,
+        signer: &S,
+        amount: Decimal,
+        nonce: u64,
+        is_deposit: bool,
+    ) -> Result<()> {
+        let wei = hype_to_wei(amount)
+            .ok_or_else(|| anyhow!("staking_transfer: invalid HYPE amount: {amount}"))?;
+        if is_deposit {
+            self.stake(signer, wei, nonce, None, None).await
+        } else {
+            self.unstake(signer, wei, nonce, None, None).await
+        }
+    }
+
+    /// Delegate or undelegate staked HYPE to a validator.
+    ///
+    /// # Parameters
+    ///
+    /// - `signer`: The signer for signing the action
+    /// - `validator`: Validator address
+    /// - `amount`: Amount of HYPE (converted internally to wei)
+    /// - `nonce`: Unique nonce (typically current timestamp in milliseconds)
+    /// - `is_undelegate`: `true` to undelegate, `false` to delegate
+    ///
+    /// <https://hyperliquid.gitbook.io/hyperliquid-docs/for-developers/api/exchange-endpoint#delegate-or-undelegate-stake-from-validator>
+    pub async fn delegate<S: SignerSync>(
+        &self,
+        signer: &S,
+        validator: Address,
+        amount: Decimal,
+        nonce: u64,
+        is_undelegate: bool,
+    ) -> Result<()> {
+        let wei = hype_to_wei(amount)
+            .ok_or_else(|| anyhow!("delegate: invalid HYPE amount: {amount}"))?;
+        self.token_delegate(signer, validator, is_undelegate, wei, nonce, None, None)
+            .await
+    }
+
     /// Reserve rate-limit request capacity.
     pub async fn reserve_request_weight<S: SignerSync>(
         &self,
@@ -2509,6 +4573,7 @@ impl Client {
         expires_after: Option<DateTime<Utc>>,
     ) -> Result<()> {
         let action = Action::ReserveRequestWeight { weight };
+        let expires_after = self.resolve_expires_after(&action, expires_after)?;
         let req = action.sign_sync(signer, nonce, vault_address, expires_after, self.chain)?;
         self.send(req).await?.into_default()
     }
@@ -2529,6 +4594,7 @@ impl Client {
             ntl,
             is_deposit,
         });
+        let expires_after = self.resolve_expires_after(&action, expires_after)?;
         let req = action.sign_sync(signer, nonce, vault_address, expires_after, self.chain)?;
         self.send(req).await?.into_default()
     }
@@ -2637,6 +4703,66 @@ impl Client {
             expires_after,
         )
     }
+
+    /// Submit one step of a HIP-1 / HIP-2 spot token deployment (`spotDeploy`).
+    ///
+    /// See [`SpotDeployAction`] for the steps, in the order they must be sent.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hypersdk::hypercore::{self, NonceHandler, PrivateKeySigner, api::SpotDeployAction};
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let client = hypercore::testnet();
+    /// let signer: PrivateKeySigner = "your_key".parse()?;
+    /// let nonces = NonceHandler::default();
+    ///
+    /// let register = SpotDeployAction::register_token("MYTKN", 2, 8, 1_000_000_000_000, None);
+    /// client.spot_deploy(&signer, register, nonces.next()).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn spot_deploy<S: SignerSync>(
+        &self,
+        signer: &S,
+        action: SpotDeployAction,
+        nonce: u64,
+    ) -> impl Future<Output = Result<()>> + Send + 'static {
+        let future = self.sign_and_send_sync(signer, Action::SpotDeploy(action), nonce, None, None);
+        async move { future.await?.into_default() }
+    }
+
+    /// Submit a HIP-3 perp DEX deployment or maintenance action (`perpDeploy`).
+    ///
+    /// See [`PerpDeployAction`] for the operations, or use [`set_oracle`](Self::set_oracle)
+    /// for price updates.
+    pub fn perp_deploy<S: SignerSync>(
+        &self,
+        signer: &S,
+        action: PerpDeployAction,
+        nonce: u64,
+    ) -> impl Future<Output = Result<()>> + Send + 'static {
+        let future = self.sign_and_send_sync(signer, Action::PerpDeploy(action), nonce, None, None);
+        async move { future.await?.into_default() }
+    }
+
+    /// Push oracle and mark prices for the assets of a HIP-3 `dex`.
+    ///
+    /// To push on a schedule, use [`OraclePusher`](super::oracle::OraclePusher).
+    pub fn set_oracle<S: SignerSync>(
+        &self,
+        signer: &S,
+        dex: &str,
+        prices: &OraclePrices,
+        nonce: u64,
+    ) -> impl Future<Output = Result<()>> + Send + 'static {
+        self.perp_deploy(
+            signer,
+            PerpDeployAction::set_oracle(dex, &prices.oracle, &prices.mark, &prices.external),
+            nonce,
+        )
+    }
 }
 
 /// Builder for constructing and executing multisig transactions on Hyperliquid.
@@ -2871,27 +4997,21 @@ where
             self.client.chain,
         )
         .await
-        .map_err(|err| ActionError {
-            ids: cloids.clone(),
-            err: err.to_string(),
-        })?;
+        .map_err(|err| ActionError::new(cloids.clone(), err.to_string()))?;
 
         let resp = self
             .client
             .sign_and_send(self.lead, action, self.nonce, vault_address, expires_after)
             .await
-            .map_err(|err| ActionError {
-                ids: cloids.clone(),
-                err: err.to_string(),
-            })?;
+            .map_err(|err| ActionError::new(cloids.clone(), err.to_string()))?;
 
         match resp {
             Response::Ok(OkResponse::Order { statuses }) => Ok(statuses),
-            Response::Err(err) => Err(ActionError { ids: cloids, err }),
-            _ => Err(ActionError {
-                ids: cloids,
-                err: format!("unexpected response type: {resp:?}"),
-            }),
+            Response::Err(err) => Err(ActionError::new(cloids, err)),
+            _ => Err(ActionError::new(
+                cloids,
+                format!("unexpected response type: {resp:?}"),
+            )),
         }
     }
 
@@ -2914,13 +5034,11 @@ where
     /// # Example
     ///
     /// ```rust,ignore
-    /// use hypersdk::hypercore::types::{UsdSend, Chain};
-    /// use hypersdk::hypercore::ARBITRUM_SIGNATURE_CHAIN_ID;
+    /// use hypersdk::hypercore::types::UsdSend;
     /// use rust_decimal::dec;
     ///
+    /// // Chain fields are filled in from the client's chain.
     /// let send = UsdSend {
-    ///     hyperliquid_chain: Chain::Mainnet,
-    ///     signature_chain_id: ARBITRUM_SIGNATURE_CHAIN_ID,
     ///     destination: "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb".parse()?,
     ///     amount: dec!(100),
     ///     time: chrono::Utc::now().timestamp_millis() as u64,
@@ -2983,7 +5101,6 @@ where
     ///
     /// ```rust,ignore
     /// use hypersdk::hypercore::types::{SendAsset, SendToken};
-    /// use hypersdk::hypercore::ARBITRUM_MAINNET_CHAIN_ID;
     /// use rust_decimal::dec;
     ///
     /// // Get the token info first
@@ -2991,8 +5108,6 @@ where
     /// let usdc = tokens.iter().find(|t| t.name == "USDC").unwrap();
     ///
     /// let send = SendAsset {
-    ///     hyperliquid_chain: Chain::Mainnet,
-    ///     signature_chain_id: ARBITRUM_MAINNET_CHAIN_ID,
     ///     destination: "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb".parse()?,
     ///     source_dex: "".to_string(),      // Empty for perp balance
     ///     destination_dex: "".to_string(), // Empty for recipient's perp balance
@@ -3173,3 +5288,364 @@ where
         resp.into_default()
     }
 }
+
+/// A single [`TimeInForce::FrontendMarket`] order with `limit_px` as the worst price.
+///
+/// Shared by every market order helper so they all price and send orders the same way.
+fn market_batch(
+    asset: usize,
+    is_buy: bool,
+    limit_px: Decimal,
+    sz: Decimal,
+    reduce_only: bool,
+    builder: Option<Builder>,
+) -> BatchOrder {
+    BatchOrder {
+        orders: vec![OrderRequest {
+            asset,
+            is_buy,
+            limit_px,
+            sz,
+            reduce_only,
+            order_type: OrderTypePlacement::Limit {
+                tif: TimeInForce::FrontendMarket,
+            },
+            cloid: Default::default(),
+        }],
+        grouping: OrderGrouping::Na,
+        builder,
+    }
+}
+
+/// Perpetual names from a `meta` response.
+#[derive(Deserialize)]
+struct PerpUniverse {
+    universe: Vec<PerpUniverseName>,
+}
+
+#[derive(Deserialize)]
+struct PerpUniverseName {
+    name: String,
+}
+
+/// Pairs `metaAndAssetCtxs` contexts with their perpetual, by universe index.
+fn zip_perp_contexts(meta: PerpUniverse, ctxs: Vec<PerpAssetCtx>) -> Result<Vec<PerpContext>> {
+    anyhow::ensure!(
+        meta.universe.len() == ctxs.len(),
+        "metaAndAssetCtxs returned {} contexts for {} perps",
+        ctxs.len(),
+        meta.universe.len()
+    );
+    Ok(meta
+        .universe
+        .into_iter()
+        .zip(ctxs)
+        .map(|(perp, ctx)| PerpContext {
+            coin: perp.name,
+            ctx,
+        })
+        .collect())
+}
+
+/// Appends a `userFillsByTime` page to `fills`.
+///
+/// Each page after the first starts at the previous page's last timestamp, so fills
+/// at that timestamp already in `fills` are skipped.
+fn append_fill_page(fills: &mut Vec<Fill>, page: Vec<Fill>, start_time: u64) {
+    let seen: HashSet<(u64, u64)> = fills
+        .iter()
+        .rev()
+        .take_while(|fill| fill.time >= start_time)
+        .map(|fill| (fill.tid, fill.oid))
+        .collect();
+    fills.extend(
+        page.into_iter()
+            .filter(|fill| !seen.contains(&(fill.tid, fill.oid))),
+    );
+}
+
+/// Appends a `candleSnapshot` chunk to `candles`, skipping candles already present.
+///
+/// Adjacent chunks can both return the candle that straddles their boundary.
+fn append_candles(candles: &mut Vec<super::types::Candle>, page: Vec<super::types::Candle>) {
+    let last = candles.last().map(|candle| candle.open_time);
+    candles.extend(
+        page.into_iter()
+            .filter(|candle| last.is_none_or(|last| candle.open_time > last)),
+    );
+}
+
+/// Appends a `userFunding` page to `entries`.
+///
+/// Funding is paid at most once per coin per timestamp, so entries at the page
+/// boundary are deduplicated by `(time, coin)`.
+fn append_funding_page(
+    entries: &mut Vec<UserFundingEntry>,
+    page: Vec<UserFundingEntry>,
+    start_time: u64,
+) {
+    let seen: HashSet<(u64, String)> = entries
+        .iter()
+        .rev()
+        .take_while(|entry| entry.time >= start_time)
+        .map(|entry| (entry.time, entry.delta.coin.clone()))
+        .collect();
+    entries.extend(
+        page.into_iter()
+            .filter(|entry| !seen.contains(&(entry.time, entry.delta.coin.clone()))),
+    );
+}
+
+/// Parses the `Date` header of a response.
+fn date_header(headers: &HeaderMap) -> Option<DateTime<chrono::FixedOffset>> {
+    let date = headers.get(reqwest::header::DATE)?.to_str().ok()?;
+    DateTime::parse_from_rfc2822(date).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fill(time: u64, tid: u64) -> Fill {
+        serde_json::from_value(serde_json::json!({
+            "coin": "BTC",
+            "px": "100000",
+            "sz": "0.1",
+            "side": "B",
+            "time": time,
+            "startPosition": "0",
+            "dir": "Open Long",
+            "closedPnl": "0",
+            "hash": "0x0",
+            "oid": 1,
+            "crossed": true,
+            "fee": "0.1",
+            "tid": tid,
+            "feeToken": "USDC",
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_append_fill_page_skips_boundary_duplicates() {
+        let mut fills = vec![];
+        append_fill_page(&mut fills, vec![fill(1, 1), fill(2, 2), fill(2, 3)], 0);
+        append_fill_page(
+            &mut fills,
+            vec![fill(2, 2), fill(2, 3), fill(2, 4), fill(3, 5)],
+            2,
+        );
+
+        let tids: Vec<_> = fills.iter().map(|fill| fill.tid).collect();
+        assert_eq!(tids, [1, 2, 3, 4, 5]);
+    }
+
+    fn candle(open_time: u64) -> crate::hypercore::types::Candle {
+        serde_json::from_value(serde_json::json!({
+            "t": open_time,
+            "T": open_time + 59_999,
+            "s": "BTC",
+            "i": "1m",
+            "o": "100",
+            "h": "101",
+            "l": "99",
+            "c": "100.5",
+            "v": "12.5",
+            "n": 42,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_append_candles_skips_overlap() {
+        let mut candles = vec![];
+        append_candles(&mut candles, vec![candle(0), candle(60_000)]);
+        append_candles(&mut candles, vec![candle(60_000), candle(120_000)]);
+        append_candles(&mut candles, vec![]);
+
+        let times: Vec<_> = candles.iter().map(|candle| candle.open_time).collect();
+        assert_eq!(times, [0, 60_000, 120_000]);
+    }
+
+    fn funding(time: u64, coin: &str) -> UserFundingEntry {
+        serde_json::from_value(serde_json::json!({
+            "delta": {
+                "type": "funding",
+                "coin": coin,
+                "usdc": "-1.25",
+                "szi": "0.5",
+                "fundingRate": "0.0000125",
+                "nSamples": null,
+            },
+            "hash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "time": time,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_append_funding_page_skips_boundary_duplicates() {
+        let mut entries = vec![];
+        append_funding_page(
+            &mut entries,
+            vec![funding(1, "BTC"), funding(2, "BTC"), funding(2, "ETH")],
+            0,
+        );
+        append_funding_page(
+            &mut entries,
+            vec![
+                funding(2, "BTC"),
+                funding(2, "ETH"),
+                funding(2, "SOL"),
+                funding(3, "BTC"),
+            ],
+            2,
+        );
+
+        let keys: Vec<_> = entries
+            .iter()
+            .map(|entry| (entry.time, entry.delta.coin.as_str()))
+            .collect();
+        assert_eq!(
+            keys,
+            [(1, "BTC"), (2, "BTC"), (2, "ETH"), (2, "SOL"), (3, "BTC")]
+        );
+        assert_eq!(entries[0].delta.usdc, Decimal::new(-125, 2));
+    }
+
+    #[test]
+    fn test_default_expires_after() {
+        let client = Client::builder(Chain::Testnet)
+            .expires_after(Duration::from_secs(30))
+            .build();
+        client.clock_skew_ms.store(5_000, atomic::Ordering::Relaxed);
+
+        let expires_after = client
+            .resolve_expires_after(&Action::Noop, None)
+            .unwrap()
+            .unwrap();
+        let window = expires_after - Utc::now();
+        assert!(window > chrono::TimeDelta::seconds(30));
+        assert!(window <= chrono::TimeDelta::seconds(35));
+
+        // Already expired on the server, though not locally.
+        let stale = Utc::now() + chrono::TimeDelta::seconds(2);
+        assert!(
+            client
+                .resolve_expires_after(&Action::Noop, Some(stale))
+                .is_err()
+        );
+
+        let transfer = Action::UsdClassTransfer(UsdClassTransferAction {
+            signature_chain_id: "0x66eee".into(),
+            hyperliquid_chain: Chain::Testnet,
+            amount: "1".into(),
+            to_perp: true,
+            nonce: 1,
+        });
+        assert_eq!(client.resolve_expires_after(&transfer, None).unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_read_only_rejects_actions() {
+        let client = Client::read_only(Chain::Testnet);
+        let signer = crate::hypercore::PrivateKeySigner::random();
+        let err = client.noop(&signer, 1, None, None).await.unwrap_err();
+        assert!(err.downcast_ref::<ReadOnly>().is_some());
+
+        // Helpers that sign themselves are stopped before sending.
+        let err = client
+            .reserve_request_weight(&signer, 1, 1, None, None)
+            .await
+            .unwrap_err();
+        assert!(err.downcast_ref::<ReadOnly>().is_some());
+
+        let req = Action::Noop
+            .sign_sync(&signer, 1, None, None, Chain::Testnet)
+            .unwrap();
+        let err = client.websocket().post(req).await.unwrap_err();
+        assert!(err.downcast_ref::<ReadOnly>().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_place_rejects_duplicate_cloids() {
+        let client = Client::new(Chain::Testnet);
+        let signer = crate::hypercore::PrivateKeySigner::random();
+        let cloid = Cloid::with_last_byte(7);
+        let mut batch = market_batch(0, true, Decimal::from(100), Decimal::ONE, false, None);
+        batch.orders[0].cloid = cloid;
+        batch.orders.push(batch.orders[0].clone());
+
+        let err = client
+            .place(&signer, batch, 1, None, None)
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(err.ids(), [cloid, cloid]);
+        assert_eq!(
+            err.downcast_source::<crate::hypercore::DuplicateCloid>(),
+            Some(&crate::hypercore::DuplicateCloid { cloid, live: false })
+        );
+    }
+
+    #[test]
+    fn test_market_batch_is_frontend_market() {
+        let batch = market_batch(3, true, Decimal::from(3500), Decimal::new(1, 2), true, None);
+        assert_eq!(batch.orders.len(), 1);
+        let order = &batch.orders[0];
+        assert_eq!(order.asset, 3);
+        assert!(order.reduce_only);
+        assert!(matches!(
+            order.order_type,
+            OrderTypePlacement::Limit {
+                tif: TimeInForce::FrontendMarket
+            }
+        ));
+    }
+
+    #[test]
+    fn test_zip_perp_contexts() {
+        let (meta, ctxs): (PerpUniverse, Vec<PerpAssetCtx>) =
+            serde_json::from_value(serde_json::json!([
+                {
+                    "universe": [
+                        { "name": "BTC", "szDecimals": 5, "maxLeverage": 40 },
+                        { "name": "ETH", "szDecimals": 4, "maxLeverage": 25 },
+                    ],
+                    "marginTables": [],
+                },
+                [
+                    {
+                        "dayNtlVlm": "1169046.29",
+                        "funding": "0.0000125",
+                        "impactPxs": ["14.3047", "14.3444"],
+                        "markPx": "14.3161",
+                        "midPx": "14.314",
+                        "openInterest": "688.11",
+                        "oraclePx": "14.32",
+                        "premium": "0.00031774",
+                        "prevDayPx": "15.322",
+                    },
+                    {
+                        "dayNtlVlm": "0.0",
+                        "funding": "-0.00001",
+                        "impactPxs": null,
+                        "markPx": "2500.5",
+                        "midPx": null,
+                        "openInterest": "0.0",
+                        "oraclePx": "2500.0",
+                        "premium": null,
+                        "prevDayPx": "2500.5",
+                    },
+                ],
+            ]))
+            .unwrap();
+
+        let perps = zip_perp_contexts(meta, ctxs).unwrap();
+        assert_eq!(perps.len(), 2);
+        assert_eq!(perps[0].coin, "BTC");
+        assert_eq!(perps[0].ctx.oracle_px, Decimal::new(1432, 2));
+        assert_eq!(perps[1].coin, "ETH");
+        assert_eq!(perps[1].ctx.mid_px, None);
+    }
+}
@@ -86,16 +86,43 @@
 //! # }
 //! ```
 
+pub mod analytics;
+pub mod backtest;
+pub mod band;
+pub mod book;
+pub mod breaker;
+pub mod cache;
+pub mod composite;
+pub mod conditional;
+pub mod decode;
+pub mod depth;
 pub mod error;
+pub mod explorer;
+pub mod funding;
+pub mod health;
 pub mod http;
+pub mod multisig;
+pub mod oracle;
+pub mod params;
+pub mod pool;
+pub mod record;
+pub mod registry;
+pub mod session;
 pub mod signing;
+pub mod slippage;
+pub mod strategy;
+pub mod sweep;
+pub mod timestamped;
+pub mod trailing;
 pub mod types;
 mod utils;
+pub mod webhooks;
 pub mod ws;
 
 use std::{
     hash::Hash,
-    sync::atomic::{self, AtomicU64},
+    sync::atomic::{self, AtomicI64, AtomicU64},
+    time::Duration,
 };
 
 /// Reimport signers.
@@ -108,10 +135,12 @@ use anyhow::Context;
 use chrono::Utc;
 use either::Either;
 /// Re-export error types.
-pub use error::{ActionError, ApiError};
+pub use error::{ActionError, ApiError, ChainMismatch, DuplicateCloid, ReadOnly};
 use reqwest::IntoUrl;
 use rust_decimal::{Decimal, MathematicalOps, RoundingStrategy, prelude::ToPrimitive};
 use serde::{Deserialize, Serialize};
+/// Re-export the signing context.
+pub use signing::SigningContext;
 /// Re-import types.
 pub use types::*;
 use url::Url;
@@ -119,6 +148,7 @@ use url::Url;
 use crate::{
     Address,
     hyperevm::{from_wei, to_wei},
+    numeric::{self, Numeric},
 };
 
 /// Client order ID (cloid).
@@ -137,6 +167,8 @@ pub type OidOrCloid = Either<u64, Cloid>;
 ///
 /// Use this client for placing orders, querying balances, and managing positions.
 pub use http::Client as HttpClient;
+/// Re-export of the HTTP client builder.
+pub use http::ClientBuilder as HttpClientBuilder;
 /// Re-export of the WebSocket connection for real-time market data.
 ///
 /// Use this for subscribing to trades, order books, and order updates.
@@ -160,6 +192,14 @@ pub use ws::Connection as WebSocket;
 /// 3. If the nonce falls behind the current time by more than 300ms, jumps to current time
 /// 4. This ensures nonces stay close to real time while maintaining uniqueness
 ///
+/// # Clock Skew
+///
+/// The exchange only accepts nonces within a window around its own clock, so a
+/// skewed local clock gets actions rejected. Measure the skew with
+/// [`HttpClient::measure_clock_skew`] and pass it to
+/// [`set_clock_skew`](Self::set_clock_skew) to generate nonces on the server's
+/// clock instead.
+///
 /// # Example
 ///
 /// ```
@@ -196,6 +236,7 @@ pub use ws::Connection as WebSocket;
 /// ```
 pub struct NonceHandler {
     nonce: AtomicU64,
+    skew_ms: AtomicI64,
 }
 
 /// An outcome order book — one tradable side of an outcome.
@@ -244,6 +285,10 @@ pub trait Market: private::Sealed {
 
     /// Price tick configuration for rounding prices to valid ticks.
     fn tick_table(&self) -> PriceTick;
+
+    /// Coin naming the market in info requests and WebSocket feeds (`"BTC"`,
+    /// `"@107"`, `"#10"`).
+    fn feed_coin(&self) -> String;
 }
 
 mod private {
@@ -266,6 +311,10 @@ impl Market for PerpMarket {
     fn tick_table(&self) -> PriceTick {
         self.table
     }
+
+    fn feed_coin(&self) -> String {
+        self.name.clone()
+    }
 }
 
 impl Market for SpotMarket {
@@ -276,6 +325,10 @@ impl Market for SpotMarket {
     fn tick_table(&self) -> PriceTick {
         self.table
     }
+
+    fn feed_coin(&self) -> String {
+        self.coin().to_owned()
+    }
 }
 
 impl Market for OutcomeMarket {
@@ -287,6 +340,10 @@ impl Market for OutcomeMarket {
         // Outcomes trade between 0 and 1; use a perp-style tick with no sz_decimals limit.
         PriceTick::for_perp(0)
     }
+
+    fn feed_coin(&self) -> String {
+        self.coin()
+    }
 }
 
 // Blanket impl so `&PerpMarket`, `&SpotMarket`, `&OutcomeMarket` also satisfy `impl Market`.
@@ -298,6 +355,10 @@ impl<T: Market> Market for &T {
     fn tick_table(&self) -> PriceTick {
         (*self).tick_table()
     }
+
+    fn feed_coin(&self) -> String {
+        (*self).feed_coin()
+    }
 }
 
 impl Default for NonceHandler {
@@ -305,11 +366,15 @@ impl Default for NonceHandler {
         let now = Utc::now().timestamp_millis() as u64;
         Self {
             nonce: AtomicU64::new(now),
+            skew_ms: AtomicI64::new(0),
         }
     }
 }
 
 impl NonceHandler {
+    /// Skew above which [`set_clock_skew`](Self::set_clock_skew) logs a warning.
+    pub const SKEW_WARNING: Duration = Duration::from_secs(1);
+
     /// Generates the next unique nonce for a transaction.
     ///
     /// This method is thread-safe and can be called concurrently from multiple threads.
@@ -340,7 +405,8 @@ impl NonceHandler {
     /// println!("Transaction nonce: {}", nonce);
     /// ```
     pub fn next(&self) -> u64 {
-        let now = Utc::now().timestamp_millis() as u64;
+        let skew = self.skew_ms.load(atomic::Ordering::Relaxed);
+        let now = (Utc::now().timestamp_millis() + skew) as u64;
 
         let prev = self.nonce.load(atomic::Ordering::Relaxed);
         if prev + 300 < now {
@@ -349,6 +415,51 @@ impl NonceHandler {
 
         self.nonce.fetch_add(1, atomic::Ordering::Relaxed)
     }
+
+    /// Generates nonces on the server's clock, `skew` ahead of the local clock.
+    ///
+    /// Logs a warning when the skew exceeds [`SKEW_WARNING`](Self::SKEW_WARNING):
+    /// the local clock should be fixed, as other timestamps it produces (e.g.
+    /// `expiresAfter`) are off by as much.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hypersdk::hypercore::{self, NonceHandler};
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let client = hypercore::mainnet();
+    /// let nonces = NonceHandler::default();
+    /// let skew = client.measure_clock_skew().await?;
+    /// nonces.set_clock_skew(skew.offset);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_clock_skew(&self, skew: chrono::TimeDelta) {
+        if skew
+            .abs()
+            .to_std()
+            .is_ok_and(|skew| skew > Self::SKEW_WARNING)
+        {
+            log::warn!(
+                "local clock is {}ms {} the server; correcting nonces",
+                skew.num_milliseconds().abs(),
+                if skew > chrono::TimeDelta::zero() {
+                    "behind"
+                } else {
+                    "ahead of"
+                }
+            );
+        }
+        self.skew_ms
+            .store(skew.num_milliseconds(), atomic::Ordering::Relaxed);
+    }
+
+    /// Returns the correction applied to the local clock.
+    #[must_use]
+    pub fn clock_skew(&self) -> chrono::TimeDelta {
+        chrono::TimeDelta::milliseconds(self.skew_ms.load(atomic::Ordering::Relaxed))
+    }
 }
 
 /// Chain identifier for Hyperliquid operations.
@@ -359,6 +470,10 @@ impl NonceHandler {
 ///
 /// Serializes to PascalCase format: "Mainnet" or "Testnet".
 /// This format is required by the Hyperliquid API.
+///
+/// [`Chain::Local`] is for local nodes and emulators. It signs exactly like testnet
+/// (and serializes as "Testnet") but defaults to [`local_url`] instead of the
+/// public testnet API.
 #[derive(
     Debug,
     Clone,
@@ -379,6 +494,10 @@ pub enum Chain {
     /// Testnet chain
     #[display("Testnet")]
     Testnet,
+    /// Local development chain, signed as testnet
+    #[display("Local")]
+    #[serde(rename(serialize = "Testnet"))]
+    Local,
 }
 
 impl Chain {
@@ -436,6 +555,78 @@ impl Chain {
             ARBITRUM_TESTNET_EIP712_DOMAIN
         }
     }
+
+    /// Returns the `hyperliquidChain` value signed into user actions.
+    ///
+    /// This is `"Mainnet"` for mainnet and `"Testnet"` otherwise, including
+    /// [`Chain::Local`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use hypersdk::hypercore::Chain;
+    ///
+    /// assert_eq!(Chain::Local.hyperliquid_chain(), "Testnet");
+    /// ```
+    pub fn hyperliquid_chain(&self) -> &'static str {
+        if self.is_mainnet() {
+            "Mainnet"
+        } else {
+            "Testnet"
+        }
+    }
+
+    /// Returns the chain that signs with `signature_chain_id`, if any.
+    ///
+    /// The comparison is case-insensitive. Testnet IDs map to [`Chain::Testnet`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use hypersdk::hypercore::Chain;
+    ///
+    /// assert_eq!(Chain::from_signature_chain_id("0xa4b1"), Some(Chain::Mainnet));
+    /// assert_eq!(Chain::from_signature_chain_id("0x66eee"), Some(Chain::Testnet));
+    /// assert_eq!(Chain::from_signature_chain_id("0x1"), None);
+    /// ```
+    pub fn from_signature_chain_id(signature_chain_id: &str) -> Option<Self> {
+        if signature_chain_id.eq_ignore_ascii_case(ARBITRUM_MAINNET_CHAIN_ID) {
+            Some(Chain::Mainnet)
+        } else if signature_chain_id.eq_ignore_ascii_case(ARBITRUM_TESTNET_CHAIN_ID) {
+            Some(Chain::Testnet)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the [`SigningContext`] for this chain.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use hypersdk::hypercore::Chain;
+    ///
+    /// let ctx = Chain::Testnet.signing_context();
+    /// assert_eq!(ctx.signature_chain_id(), "0x66eee");
+    /// ```
+    pub fn signing_context(&self) -> SigningContext {
+        SigningContext::from(*self)
+    }
+
+    /// Returns the default HTTP API URL for this chain.
+    pub fn api_url(&self) -> Url {
+        match self {
+            Chain::Mainnet => mainnet_url(),
+            Chain::Testnet => testnet_url(),
+            Chain::Local => local_url(),
+        }
+    }
+}
+
+impl From<Chain> for Eip712Domain {
+    fn from(chain: Chain) -> Self {
+        chain.domain()
+    }
 }
 
 /// Arbitrum One mainnet chain ID for EIP-712 signatures.
@@ -543,6 +734,23 @@ pub fn testnet() -> HttpClient {
     HttpClient::new(Chain::Testnet)
 }
 
+/// Creates an HTTP client for a local node.
+///
+/// Uses [`local_url`], which can be overridden with the `HYPERLIQUID_LOCAL_URL`
+/// environment variable.
+///
+/// # Example
+///
+/// ```
+/// use hypersdk::hypercore;
+///
+/// let client = hypercore::local();
+/// ```
+#[inline(always)]
+pub fn local() -> HttpClient {
+    HttpClient::new(Chain::Local)
+}
+
 /// Creates a mainnet WebSocket connection for HyperCore.
 ///
 /// This is a convenience function that creates a WebSocket connection to the mainnet API.
@@ -595,6 +803,56 @@ pub fn testnet_websocket_url() -> Url {
     "wss://api.hyperliquid-testnet.xyz/ws".parse().unwrap()
 }
 
+/// Returns the HTTP API URL of a local node.
+///
+/// URL: `$HYPERLIQUID_LOCAL_URL` if set, otherwise `http://localhost:3001`
+pub fn local_url() -> Url {
+    std::env::var("HYPERLIQUID_LOCAL_URL")
+        .ok()
+        .and_then(|url| url.parse().ok())
+        .unwrap_or_else(|| "http://localhost:3001".parse().unwrap())
+}
+
+/// Returns the leaderboard URL of `chain`.
+///
+/// The leaderboard is served by the stats service rather than the info endpoint.
+/// For [`Chain::Local`], this is `/leaderboard` on the local node URL.
+pub fn leaderboard_url(chain: Chain) -> Url {
+    match chain {
+        Chain::Mainnet => "https://stats-data.hyperliquid.xyz/Mainnet/leaderboard"
+            .parse()
+            .unwrap(),
+        Chain::Testnet => "https://stats-data.hyperliquid-testnet.xyz/Testnet/leaderboard"
+            .parse()
+            .unwrap(),
+        Chain::Local => {
+            let mut url = local_url();
+            url.set_path("/leaderboard");
+            url
+        }
+    }
+}
+
+/// Returns the vault list URL of `chain`.
+///
+/// Like the leaderboard, the vault list is served by the stats service. For
+/// [`Chain::Local`], this is `/vaults` on the local node URL.
+pub fn vaults_url(chain: Chain) -> Url {
+    match chain {
+        Chain::Mainnet => "https://stats-data.hyperliquid.xyz/Mainnet/vaults"
+            .parse()
+            .unwrap(),
+        Chain::Testnet => "https://stats-data.hyperliquid-testnet.xyz/Testnet/vaults"
+            .parse()
+            .unwrap(),
+        Chain::Local => {
+            let mut url = local_url();
+            url.set_path("/vaults");
+            url
+        }
+    }
+}
+
 /// Creates a testnet WebSocket connection for HyperCore.
 ///
 /// This is a convenience function that creates a WebSocket connection to the testnet API.
@@ -865,6 +1123,14 @@ impl PerpMarket {
         &self.name
     }
 
+    /// Returns true if positions in this market can use cross margin.
+    ///
+    /// Markets flagged `onlyIsolated` or with a [`MarginMode`] only accept isolated leverage.
+    #[must_use]
+    pub fn allows_cross(&self) -> bool {
+        !self.isolated_margin && self.margin_mode.is_none()
+    }
+
     /// Returns the price tick configuration for this market.
     #[must_use]
     pub fn tick_table(&self) -> &PriceTick {
@@ -874,15 +1140,15 @@ impl PerpMarket {
     /// Returns the tick size for a given price in this market.
     ///
     /// See [`PriceTick::tick_for`] for details on the calculation.
-    pub fn tick_for(&self, price: Decimal) -> Option<Decimal> {
-        self.table.tick_for(price)
+    pub fn tick_for<T: Numeric>(&self, price: T) -> Option<T> {
+        numeric::map(price, |price| self.table.tick_for(price))
     }
 
     /// Rounds a price to the nearest valid tick for this market.
     ///
     /// Uses midpoint-toward-zero rounding strategy (round half down).
     ///
-    /// Returns `None` if the price is invalid.
+    /// Returns `None` if the price is invalid. Accepts any [`Numeric`] backend.
     ///
     /// # Example
     ///
@@ -892,8 +1158,8 @@ impl PerpMarket {
     /// // Example: round_price() rounds to nearest valid tick
     /// // let rounded = market.round_price(price);
     /// ```
-    pub fn round_price(&self, price: Decimal) -> Option<Decimal> {
-        self.table.round(price)
+    pub fn round_price<T: Numeric>(&self, price: T) -> Option<T> {
+        numeric::map(price, |price| self.table.round(price))
     }
 
     /// Rounds a price based on order side and trading strategy.
@@ -938,8 +1204,106 @@ impl PerpMarket {
     ///
     /// - [`PriceTick::round_by_side`]: Detailed explanation of rounding logic
     /// - [`round_price`](Self::round_price): Neutral rounding
-    pub fn round_by_side(&self, side: Side, price: Decimal, conservative: bool) -> Option<Decimal> {
-        self.table.round_by_side(side, price, conservative)
+    pub fn round_by_side<T: Numeric>(&self, side: Side, price: T, conservative: bool) -> Option<T> {
+        numeric::map(price, |price| {
+            self.table.round_by_side(side, price, conservative)
+        })
+    }
+
+    /// Builds a take-profit ladder closing a position in steps.
+    ///
+    /// `szi` is the signed position size and `entry_px` its entry price. Each level is
+    /// a `(fraction, offset)` pair: close `fraction` of the position once the price
+    /// has moved `offset` (a fraction of the entry price) in its favour. Fractions must
+    /// add up to at most 1; when they add up to exactly 1, the last level also takes
+    /// the rounding remainder so the ladder closes the whole position.
+    ///
+    /// The orders are reduce-only take-profit triggers with limit prices rounded away
+    /// from the market. They are grouped as [`OrderGrouping::Na`]: `positionTpsl`
+    /// resizes each trigger to the whole position, which would defeat the ladder.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hypersdk::{dec, hypercore::PerpMarket};
+    /// # fn example(btc: &PerpMarket) -> anyhow::Result<()> {
+    /// // Long 1 BTC from 100k: close half at +1% and a quarter at +2%.
+    /// let ladder = btc.scale_out(
+    ///     dec!(1),
+    ///     dec!(100000),
+    ///     &[(dec!(0.5), dec!(0.01)), (dec!(0.25), dec!(0.02))],
+    /// )?;
+    /// assert_eq!(ladder.orders.len(), 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn scale_out(
+        &self,
+        szi: Decimal,
+        entry_px: Decimal,
+        levels: &[(Decimal, Decimal)],
+    ) -> anyhow::Result<BatchOrder> {
+        anyhow::ensure!(!szi.is_zero(), "no {} position to scale out of", self.name);
+        anyhow::ensure!(
+            levels
+                .iter()
+                .all(|(fraction, offset)| *fraction > Decimal::ZERO && *offset > Decimal::ZERO),
+            "scale-out fractions and offsets must be positive"
+        );
+        let total: Decimal = levels.iter().map(|(fraction, _)| fraction).sum();
+        anyhow::ensure!(
+            total <= Decimal::ONE,
+            "scale-out fractions add up to {total}, more than the position"
+        );
+
+        let is_long = szi.is_sign_positive();
+        let side = if is_long { Side::Ask } else { Side::Bid };
+        let size = szi.abs();
+        let sz_decimals = self.sz_decimals.max(0) as u32;
+
+        let mut remaining = size;
+        let mut orders = Vec::with_capacity(levels.len());
+        for (i, (fraction, offset)) in levels.iter().enumerate() {
+            let sz = if total == Decimal::ONE && i + 1 == levels.len() {
+                remaining
+            } else {
+                (size * fraction).round_dp_with_strategy(sz_decimals, RoundingStrategy::ToZero)
+            };
+            anyhow::ensure!(
+                !sz.is_zero(),
+                "scale-out level #{i} rounds to a zero size on {}",
+                self.name
+            );
+            remaining -= sz;
+
+            let target = if is_long {
+                entry_px * (Decimal::ONE + offset)
+            } else {
+                entry_px * (Decimal::ONE - offset)
+            };
+            let px = self
+                .round_by_side(side, target, true)
+                .with_context(|| format!("invalid scale-out price {target} on {}", self.name))?;
+            orders.push(OrderRequest {
+                asset: self.index,
+                is_buy: !is_long,
+                limit_px: px,
+                sz,
+                reduce_only: true,
+                order_type: OrderTypePlacement::Trigger {
+                    is_market: false,
+                    trigger_px: px,
+                    tpsl: TpSl::Tp,
+                },
+                cloid: Default::default(),
+            });
+        }
+
+        Ok(BatchOrder {
+            orders,
+            grouping: OrderGrouping::Na,
+            builder: None,
+        })
     }
 }
 
@@ -983,6 +1347,21 @@ impl SpotMarket {
         format!("{}/{}", self.tokens[0].name, self.tokens[1].name)
     }
 
+    /// Returns the coin name used by info requests and WebSocket subscriptions.
+    ///
+    /// This is `"@{spot_index}"` for most pairs, and the pair name for a few
+    /// legacy markets such as `"PURR/USDC"`.
+    #[must_use]
+    pub fn coin(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the spot index (the asset index without the 10_000 offset).
+    #[must_use]
+    pub fn spot_index(&self) -> usize {
+        self.index - 10_000
+    }
+
     /// Returns the base token (first token in the pair).
     #[must_use]
     pub fn base(&self) -> &SpotToken {
@@ -1004,15 +1383,15 @@ impl SpotMarket {
     /// Returns the tick size for a given price in this market.
     ///
     /// See [`PriceTick::tick_for`] for details on the calculation.
-    pub fn tick_for(&self, price: Decimal) -> Option<Decimal> {
-        self.table.tick_for(price)
+    pub fn tick_for<T: Numeric>(&self, price: T) -> Option<T> {
+        numeric::map(price, |price| self.table.tick_for(price))
     }
 
     /// Rounds a price to the nearest valid tick for this market.
     ///
     /// Uses midpoint-toward-zero rounding strategy (round half down).
     ///
-    /// Returns `None` if the price is invalid.
+    /// Returns `None` if the price is invalid. Accepts any [`Numeric`] backend.
     ///
     /// # Example
     ///
@@ -1022,8 +1401,8 @@ impl SpotMarket {
     /// // Example: round_price() rounds to nearest valid tick
     /// // let rounded = market.round_price(price);
     /// ```
-    pub fn round_price(&self, price: Decimal) -> Option<Decimal> {
-        self.table.round(price)
+    pub fn round_price<T: Numeric>(&self, price: T) -> Option<T> {
+        numeric::map(price, |price| self.table.round(price))
     }
 
     /// Rounds a price based on order side and trading strategy.
@@ -1068,8 +1447,10 @@ impl SpotMarket {
     ///
     /// - [`PriceTick::round_by_side`]: Detailed explanation of rounding logic
     /// - [`round_price`](Self::round_price): Neutral rounding
-    pub fn round_by_side(&self, side: Side, price: Decimal, conservative: bool) -> Option<Decimal> {
-        self.table.round_by_side(side, price, conservative)
+    pub fn round_by_side<T: Numeric>(&self, side: Side, price: T, conservative: bool) -> Option<T> {
+        numeric::map(price, |price| {
+            self.table.round_by_side(side, price, conservative)
+        })
     }
 }
 
@@ -1488,9 +1869,11 @@ pub async fn spot_tokens(
     client: reqwest::Client,
 ) -> anyhow::Result<Vec<SpotToken>> {
     let data = raw_spot_markets(core_url, client).await?;
+    Ok(spot_tokens_from(&data))
+}
 
-    let spot_tokens: Vec<_> = data.tokens.iter().cloned().map(SpotToken::from).collect();
-    Ok(spot_tokens)
+fn spot_tokens_from(data: &SpotTokens) -> Vec<SpotToken> {
+    data.tokens.iter().cloned().map(SpotToken::from).collect()
 }
 
 /// Fetches all available spot trading markets from HyperCore.
@@ -1518,9 +1901,13 @@ pub async fn spot_markets(
     client: reqwest::Client,
 ) -> anyhow::Result<Vec<SpotMarket>> {
     let data = raw_spot_markets(core_url, client).await?;
+    spot_markets_from(data)
+}
+
+fn spot_markets_from(data: SpotTokens) -> anyhow::Result<Vec<SpotMarket>> {
     let mut markets = Vec::with_capacity(data.universe.len());
 
-    let spot_tokens: Vec<_> = data.tokens.iter().cloned().map(SpotToken::from).collect();
+    let spot_tokens = spot_tokens_from(&data);
 
     for item in data.universe {
         // Match by the token's `index` field, NOT its position in the `tokens`
@@ -1548,6 +1935,63 @@ pub async fn spot_markets(
     Ok(markets)
 }
 
+/// Returns the spot coin name (e.g. `"@107"`) for a `base`/`quote` token pair.
+///
+/// Token names are matched case-insensitively. The returned name is what info
+/// requests and WebSocket subscriptions expect as `coin`.
+///
+/// # Example
+///
+/// ```no_run
+/// use hypersdk::hypercore;
+///
+/// # async fn example() -> anyhow::Result<()> {
+/// let markets = hypercore::mainnet().spot().await?;
+/// let coin = hypercore::spot_coin_for_pair(&markets, "HYPE", "USDC");
+/// assert_eq!(coin.as_deref(), Some("@107"));
+/// # Ok(())
+/// # }
+/// ```
+#[must_use]
+pub fn spot_coin_for_pair(markets: &[SpotMarket], base: &str, quote: &str) -> Option<String> {
+    markets
+        .iter()
+        .find(|market| {
+            market.base().name.eq_ignore_ascii_case(base)
+                && market.quote().name.eq_ignore_ascii_case(quote)
+        })
+        .map(|market| market.coin().to_owned())
+}
+
+/// Returns the `"BASE/QUOTE"` pair for a spot coin name such as `"@107"` or `"PURR/USDC"`.
+///
+/// # Example
+///
+/// ```no_run
+/// use hypersdk::hypercore;
+///
+/// # async fn example() -> anyhow::Result<()> {
+/// let markets = hypercore::mainnet().spot().await?;
+/// let pair = hypercore::pair_for_spot_coin(&markets, "@107");
+/// assert_eq!(pair.as_deref(), Some("HYPE/USDC"));
+/// # Ok(())
+/// # }
+/// ```
+#[must_use]
+pub fn pair_for_spot_coin(markets: &[SpotMarket], coin: &str) -> Option<String> {
+    let spot_index = coin
+        .strip_prefix('@')
+        .and_then(|index| index.parse::<usize>().ok());
+
+    markets
+        .iter()
+        .find(|market| match spot_index {
+            Some(index) => market.spot_index() == index,
+            None => market.name == coin,
+        })
+        .map(SpotMarket::symbol)
+}
+
 /// Fetches all available perpetual futures DEXes from HyperCore.
 ///
 /// Returns a list of all DEXes that offer perpetual futures trading.
@@ -1589,7 +2033,11 @@ pub async fn perp_dexes(
         .context("info")?;
 
     let dexes: Vec<Option<PerpDex>> = resp.json().await?;
-    let dex_list = dexes
+    Ok(perp_dexes_from(dexes))
+}
+
+fn perp_dexes_from(dexes: Vec<Option<PerpDex>>) -> Vec<Dex> {
+    dexes
         .into_iter()
         .enumerate()
         .filter_map(|(index, dex)| {
@@ -1599,9 +2047,7 @@ pub async fn perp_dexes(
                 deployer_fee_scale: dex.deployer_fee_scale,
             })
         })
-        .collect();
-
-    Ok(dex_list)
+        .collect()
 }
 
 /// Misspelled alias of [`perp_dexes`].
@@ -1643,6 +2089,14 @@ pub async fn perp_markets(
         .await
         .context("meta")?;
     let data: PerpTokens = resp.json().await?;
+    perp_markets_from(&spot, data, dex)
+}
+
+fn perp_markets_from(
+    spot: &SpotTokens,
+    data: PerpTokens,
+    dex: Option<Dex>,
+) -> anyhow::Result<Vec<PerpMarket>> {
     let collateral = spot
         .tokens
         .get(data.collateral_token)
@@ -1933,6 +2387,122 @@ mod tests {
     use super::*;
     use crate::hypercore;
 
+    fn spot_token(name: &str, index: u32) -> SpotToken {
+        SpotToken {
+            name: name.to_owned(),
+            index,
+            token_id: B128::ZERO,
+            evm_contract: None,
+            cross_chain_address: None,
+            sz_decimals: 2,
+            wei_decimals: 8,
+            evm_extra_decimals: 0,
+        }
+    }
+
+    #[test]
+    fn test_spot_coin_pair_conversion() {
+        let usdc = spot_token("USDC", 0);
+        let markets = vec![
+            SpotMarket {
+                name: "PURR/USDC".into(),
+                index: 10_000,
+                tokens: [spot_token("PURR", 1), usdc.clone()],
+                table: PriceTick::for_spot(0),
+            },
+            SpotMarket {
+                name: "@107".into(),
+                index: 10_107,
+                tokens: [spot_token("HYPE", 150), usdc],
+                table: PriceTick::for_spot(2),
+            },
+        ];
+
+        assert_eq!(
+            spot_coin_for_pair(&markets, "hype", "USDC").as_deref(),
+            Some("@107")
+        );
+        assert_eq!(
+            spot_coin_for_pair(&markets, "PURR", "USDC").as_deref(),
+            Some("PURR/USDC")
+        );
+        assert_eq!(spot_coin_for_pair(&markets, "BTC", "USDC"), None);
+
+        assert_eq!(
+            pair_for_spot_coin(&markets, "@107").as_deref(),
+            Some("HYPE/USDC")
+        );
+        assert_eq!(
+            pair_for_spot_coin(&markets, "@0").as_deref(),
+            Some("PURR/USDC")
+        );
+        assert_eq!(
+            pair_for_spot_coin(&markets, "PURR/USDC").as_deref(),
+            Some("PURR/USDC")
+        );
+        assert_eq!(pair_for_spot_coin(&markets, "@999"), None);
+    }
+
+    #[test]
+    fn test_perp_scale_out() {
+        let btc = PerpMarket {
+            name: "BTC".into(),
+            index: 0,
+            sz_decimals: 3,
+            collateral: spot_token("USDC", 0),
+            max_leverage: 40,
+            isolated_margin: false,
+            margin_mode: None,
+            growth_mode: false,
+            aligned_quote_token: false,
+            table: PriceTick::for_perp(3),
+        };
+        let ladder = [
+            (Decimal::new(5, 1), Decimal::new(1, 2)),
+            (Decimal::new(25, 2), Decimal::new(2, 2)),
+            (Decimal::new(25, 2), Decimal::new(3, 2)),
+        ];
+
+        // Short 0.333 BTC: buys below the entry, last level closes the remainder.
+        let batch = btc
+            .scale_out(Decimal::new(-333, 3), Decimal::from(100_000), &ladder)
+            .unwrap();
+        let sizes: Vec<_> = batch.orders.iter().map(|order| order.sz).collect();
+        assert_eq!(
+            sizes,
+            [
+                Decimal::new(166, 3),
+                Decimal::new(83, 3),
+                Decimal::new(84, 3)
+            ]
+        );
+        let prices: Vec<_> = batch.orders.iter().map(|order| order.limit_px).collect();
+        assert_eq!(
+            prices,
+            [
+                Decimal::from(99_000),
+                Decimal::from(98_000),
+                Decimal::from(97_000)
+            ]
+        );
+        assert!(
+            batch
+                .orders
+                .iter()
+                .all(|order| order.is_buy && order.reduce_only)
+        );
+
+        assert!(
+            btc.scale_out(
+                Decimal::ONE,
+                Decimal::from(100_000),
+                &[(Decimal::TWO, Decimal::ONE)]
+            )
+            .is_err()
+        );
+        assert!(btc.scale_out(Decimal::ZERO, Decimal::ONE, &ladder).is_err());
+    }
+
     #[tokio::test]
     async fn test_spot_markets() {
         let client = reqwest::Client::new();
@@ -2154,6 +2724,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_nonce_handler_clock_skew() {
+        let handler = NonceHandler::default();
+        handler.set_clock_skew(chrono::TimeDelta::minutes(10));
+        assert_eq!(handler.clock_skew(), chrono::TimeDelta::minutes(10));
+
+        // The next nonce jumps to the server's clock.
+        let nonce = handler.next();
+        let local = Utc::now().timestamp_millis() as u64;
+        assert!(nonce > local + 9 * 60 * 1000);
+    }
+
     #[tokio::test]
     async fn test_http_outcome_meta_mainnet() {
         let client = hypercore::mainnet();
@@ -0,0 +1,202 @@
+//! Multi-sig proposals exchanged between signers.
+//!
+//! The lead signer of a multi-sig action builds a [`Proposal`]: the inner action, the
+//! nonce, and the chain it will be submitted to. Every authorized signer signs the same
+//! proposal, and the lead submits it once enough signatures are collected.
+//!
+//! Proposals and signatures can be encoded as compact base64 blobs, to be exchanged over
+//! any channel (chat, email, QR codes) when signers can't reach each other directly.
+//!
+//! # Example
+//!
+//! ```
+//! use hypersdk::hypercore::{Chain, PrivateKeySigner, api::Action, multisig::{self, Proposal}};
+//!
+//! # fn example() -> anyhow::Result<()> {
+//! let lead = PrivateKeySigner::random();
+//! let cosigner = PrivateKeySigner::random();
+//! let multi_sig_user = "0x0000000000000000000000000000000000000001".parse()?;
+//!
+//! let proposal = Proposal::new(Chain::Testnet, multi_sig_user, lead.address(), Action::Noop, 1);
+//! let blob = proposal.encode()?;
+//!
+//! // On the co-signer's machine:
+//! let received = Proposal::decode(&blob)?;
+//! let signature = received.sign_sync(&cosigner)?;
+//! let reply = multisig::encode_signature(&signature);
+//!
+//! // Back on the lead's machine:
+//! let signature = multisig::decode_signature(&reply)?;
+//! assert_eq!(proposal.recover(&signature)?, cosigner.address());
+//! # Ok(())
+//! # }
+//! ```
+
+use alloy::{
+    primitives::{Address, B256, U256},
+    signers::{Signer, SignerSync},
+};
+use anyhow::Context;
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    Chain, Signature,
+    api::{Action, MultiSigAction, MultiSigPayload},
+};
+
+/// A multi-sig action waiting for signatures.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Proposal {
+    /// Chain the action will be submitted to
+    pub chain: Chain,
+    /// Nonce shared by all signatures
+    pub nonce: u64,
+    /// The multi-sig user, lead signer, and inner action
+    pub payload: MultiSigPayload,
+}
+
+impl Proposal {
+    /// Creates a proposal for `action` on behalf of `multi_sig_user`, submitted by `lead`.
+    #[must_use]
+    pub fn new(
+        chain: Chain,
+        multi_sig_user: Address,
+        lead: Address,
+        action: Action,
+        nonce: u64,
+    ) -> Self {
+        Self {
+            chain,
+            nonce,
+            payload: MultiSigPayload {
+                multi_sig_user: multi_sig_user.to_string().to_lowercase(),
+                outer_signer: lead.to_string().to_lowercase(),
+                action: Box::new(action),
+            },
+        }
+    }
+
+    /// The multi-sig account the action is for.
+    pub fn multi_sig_user(&self) -> anyhow::Result<Address> {
+        Ok(self.payload.multi_sig_user.parse()?)
+    }
+
+    /// The signer that submits the action.
+    pub fn lead(&self) -> anyhow::Result<Address> {
+        Ok(self.payload.outer_signer.parse()?)
+    }
+
+    /// The hash every signer signs.
+    pub fn hash(&self) -> anyhow::Result<B256> {
+        self.payload.prehash(self.nonce, self.chain)
+    }
+
+    /// Signs the proposal.
+    pub fn sign_sync<S: SignerSync>(&self, signer: &S) -> anyhow::Result<Signature> {
+        self.payload.sign_sync(signer, self.nonce, self.chain)
+    }
+
+    /// Signs the proposal.
+    pub async fn sign<S: Signer + Send + Sync>(&self, signer: &S) -> anyhow::Result<Signature> {
+        self.payload.sign(signer, self.nonce, self.chain).await
+    }
+
+    /// Recovers the address that produced `signature`.
+    pub fn recover(&self, signature: &Signature) -> anyhow::Result<Address> {
+        self.payload.recover(signature, self.nonce, self.chain)
+    }
+
+    /// Wraps the proposal and its signatures into the action the lead submits.
+    #[must_use]
+    pub fn into_action(self, signatures: Vec<Signature>) -> MultiSigAction {
+        MultiSigAction {
+            signature_chain_id: self.chain.arbitrum_id().to_owned(),
+            signatures,
+            payload: self.payload,
+        }
+    }
+
+    /// Encodes the proposal as URL-safe base64 of its MessagePack form.
+    pub fn encode(&self) -> anyhow::Result<String> {
+        Ok(URL_SAFE_NO_PAD.encode(rmp_serde::to_vec_named(self)?))
+    }
+
+    /// Decodes a proposal produced by [`encode`](Self::encode).
+    pub fn decode(blob: &str) -> anyhow::Result<Self> {
+        let bytes = URL_SAFE_NO_PAD
+            .decode(blob.trim())
+            .context("proposal is not valid base64")?;
+        rmp_serde::from_slice(&bytes).context("malformed proposal")
+    }
+}
+
+/// Encodes a signature as URL-safe base64 of its 65 bytes (`r`, `s`, `v`).
+#[must_use]
+pub fn encode_signature(signature: &Signature) -> String {
+    let mut bytes = [0u8; 65];
+    bytes[..32].copy_from_slice(&signature.r.to_be_bytes::<32>());
+    bytes[32..64].copy_from_slice(&signature.s.to_be_bytes::<32>());
+    bytes[64] = signature.v as u8;
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// Decodes a signature produced by [`encode_signature`].
+///
+/// Hex signatures (`0x…`) are accepted as well.
+pub fn decode_signature(blob: &str) -> anyhow::Result<Signature> {
+    let blob = blob.trim();
+    if blob.starts_with("0x") {
+        return blob.parse();
+    }
+    let bytes = URL_SAFE_NO_PAD
+        .decode(blob)
+        .context("signature is not valid base64")?;
+    anyhow::ensure!(
+        bytes.len() == 65,
+        "invalid signature length: expected 65 bytes, got {}",
+        bytes.len()
+    );
+    Ok(Signature {
+        r: U256::from_be_slice(&bytes[..32]),
+        s: U256::from_be_slice(&bytes[32..64]),
+        v: bytes[64].into(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::signers::local::PrivateKeySigner;
+
+    use super::*;
+
+    #[test]
+    fn test_proposal_roundtrip() {
+        let lead = PrivateKeySigner::random();
+        let cosigner = PrivateKeySigner::random();
+        let proposal = Proposal::new(
+            Chain::Testnet,
+            Address::repeat_byte(1),
+            lead.address(),
+            Action::Noop,
+            1_700_000_000_000,
+        );
+
+        let decoded = Proposal::decode(&proposal.encode().unwrap()).unwrap();
+        assert_eq!(decoded.chain, Chain::Testnet);
+        assert_eq!(decoded.nonce, proposal.nonce);
+        assert_eq!(decoded.multi_sig_user().unwrap(), Address::repeat_byte(1));
+        assert_eq!(decoded.lead().unwrap(), lead.address());
+        assert_eq!(decoded.hash().unwrap(), proposal.hash().unwrap());
+
+        let signature = decoded.sign_sync(&cosigner).unwrap();
+        let blob = encode_signature(&signature);
+        assert_eq!(blob.len(), 87);
+        let signature = decode_signature(&blob).unwrap();
+        assert_eq!(proposal.recover(&signature).unwrap(), cosigner.address());
+
+        let hex = decode_signature(&signature.to_string()).unwrap();
+        assert_eq!(hex.to_string(), signature.to_string());
+    }
+}
@@ -0,0 +1,131 @@
+//! Scheduled oracle price pushes for HIP-3 perp DEXes.
+//!
+//! A HIP-3 deployer is responsible for keeping its DEX's oracle and mark prices fresh.
+//! [`OraclePusher`] fetches prices from a caller-supplied source on a fixed interval
+//! and submits them as `perpDeploy` / `setOracle` actions.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use std::{collections::BTreeMap, time::Duration};
+//!
+//! use hypersdk::{
+//!     dec,
+//!     hypercore::{self, PrivateKeySigner, oracle::{OraclePrices, OraclePusher}},
+//! };
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let signer: PrivateKeySigner = "your_key".parse()?;
+//! let pusher = OraclePusher::new(hypercore::testnet(), signer, "xyz")
+//!     .interval(Duration::from_secs(3));
+//!
+//! pusher
+//!     .run(|| async {
+//!         // Fetch from your own feeds here.
+//!         let oracle = BTreeMap::from([("xyz:BTC".to_string(), dec!(100000))]);
+//!         Ok(OraclePrices::new(oracle))
+//!     })
+//!     .await;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{collections::BTreeMap, future::Future, time::Duration};
+
+use alloy::signers::SignerSync;
+use rust_decimal::Decimal;
+use tokio::time::MissedTickBehavior;
+
+use super::{HttpClient, NonceHandler};
+
+/// Default interval between pushes.
+pub const DEFAULT_PUSH_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Prices for one `setOracle` update, keyed by coin (e.g. `"xyz:BTC"`).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OraclePrices {
+    /// Oracle prices, used for funding and margining
+    pub oracle: BTreeMap<String, Decimal>,
+    /// Mark price sources, combined by the exchange with its own mark
+    pub mark: Vec<BTreeMap<String, Decimal>>,
+    /// Prices of external perps referenced by the DEX
+    pub external: BTreeMap<String, Decimal>,
+}
+
+impl OraclePrices {
+    /// Oracle prices, also used as the single mark price source.
+    #[must_use]
+    pub fn new(oracle: BTreeMap<String, Decimal>) -> Self {
+        Self {
+            mark: vec![oracle.clone()],
+            oracle,
+            external: BTreeMap::new(),
+        }
+    }
+}
+
+/// Pushes oracle prices for a HIP-3 DEX on a fixed interval.
+pub struct OraclePusher<S> {
+    client: HttpClient,
+    signer: S,
+    dex: String,
+    interval: Duration,
+    nonces: NonceHandler,
+}
+
+impl<S> OraclePusher<S>
+where
+    S: SignerSync + Send + Sync,
+{
+    /// Creates a pusher for `dex`, signing with the DEX's deployer or oracle updater.
+    pub fn new(client: HttpClient, signer: S, dex: impl Into<String>) -> Self {
+        Self {
+            client,
+            signer,
+            dex: dex.into(),
+            interval: DEFAULT_PUSH_INTERVAL,
+            nonces: NonceHandler::default(),
+        }
+    }
+
+    /// Sets the interval between pushes.
+    #[must_use]
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Pushes `prices` once.
+    pub async fn push(&self, prices: &OraclePrices) -> anyhow::Result<()> {
+        self.client
+            .set_oracle(&self.signer, &self.dex, prices, self.nonces.next())
+            .await
+    }
+
+    /// Fetches prices from `source` and pushes them every interval, forever.
+    ///
+    /// Failures to fetch or push are logged and retried on the next tick, so a flaky
+    /// feed doesn't stop the DEX's updates. Empty updates are skipped.
+    pub async fn run<F, Fut>(&self, mut source: F)
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = anyhow::Result<OraclePrices>>,
+    {
+        let mut ticker = tokio::time::interval(self.interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            let prices = match source().await {
+                Ok(prices) if prices.oracle.is_empty() => continue,
+                Ok(prices) => prices,
+                Err(err) => {
+                    log::warn!("{}: oracle source failed: {err:?}", self.dex);
+                    continue;
+                }
+            };
+            if let Err(err) = self.push(&prices).await {
+                log::warn!("{}: setOracle failed: {err:?}", self.dex);
+            }
+        }
+    }
+}
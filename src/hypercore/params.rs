@@ -0,0 +1,468 @@
+//! Hot-reloadable strategy parameters.
+//!
+//! A [`ParamStore`] holds a strategy's tunable parameters (spreads, sizes, limits)
+//! and replaces them while the strategy runs. Updates come from a watched JSON file
+//! (or TOML with the `toml` feature) through [`ParamStore::watch`], or from any
+//! other transport, such as a local control socket, through
+//! [`ParamStore::update`] and [`ParamStore::patch`]. Every update is validated with
+//! [`Params::validate`] before it's applied, and each change, applied or rejected by
+//! validation, is recorded in the audit trail.
+//!
+//! The strategy keeps a [`ParamReader`] and picks up new parameters in its hooks.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use hypersdk::hypercore::{
+//!     params::{ParamReader, ParamStore, Params},
+//!     strategy::{Context, Strategy},
+//!     types::*,
+//! };
+//! use rust_decimal::Decimal;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Clone, Serialize, Deserialize)]
+//! struct GridParams {
+//!     spacing: Decimal,
+//!     size: Decimal,
+//! }
+//!
+//! impl Params for GridParams {
+//!     fn validate(&self) -> anyhow::Result<()> {
+//!         anyhow::ensure!(self.spacing > Decimal::ZERO, "spacing must be positive");
+//!         Ok(())
+//!     }
+//! }
+//!
+//! struct Grid {
+//!     params: ParamReader<GridParams>,
+//!     current: GridParams,
+//! }
+//!
+//! impl Strategy for Grid {
+//!     fn subscriptions(&self) -> Vec<Subscription> {
+//!         vec![]
+//!     }
+//!
+//!     fn on_timer(&mut self, _ctx: &mut Context) -> anyhow::Result<()> {
+//!         if let Some(params) = self.params.changed() {
+//!             self.current = params;
+//!         }
+//!         Ok(())
+//!     }
+//! }
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let store = ParamStore::<GridParams>::load("grid.json")?.audit_log("grid-audit.jsonl");
+//! tokio::spawn(store.clone().watch("grid.json", Duration::from_secs(1)));
+//!
+//! let grid = Grid {
+//!     params: store.reader(),
+//!     current: store.get(),
+//! };
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, MutexGuard},
+    time::{Duration, SystemTime},
+};
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::Value;
+
+/// Parameters that can be held by a [`ParamStore`].
+pub trait Params: Clone + Serialize + DeserializeOwned + Send + 'static {
+    /// Checks that the parameters are usable.
+    ///
+    /// Updates failing validation are rejected and the previous parameters stay in
+    /// place.
+    fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// A top-level field changed by an update.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldChange {
+    /// Field name
+    pub field: String,
+    /// Value before the update, `null` if the field was absent
+    pub from: Value,
+    /// Value after the update, `null` if the field was removed
+    pub to: Value,
+}
+
+/// An entry of the audit trail.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParamChange {
+    /// Time of the update in milliseconds
+    pub time: u64,
+    /// Where the update came from, e.g. the watched file's path
+    pub source: String,
+    /// Version after the update; unchanged if it was rejected
+    pub version: u64,
+    /// Fields changed by the update
+    pub changes: Vec<FieldChange>,
+    /// Why the update was rejected, if it was
+    pub error: Option<String>,
+}
+
+impl ParamChange {
+    /// Returns whether the update was applied.
+    #[must_use]
+    pub fn is_applied(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+#[derive(Debug)]
+struct Inner<P> {
+    params: P,
+    version: u64,
+    audit: Vec<ParamChange>,
+    audit_log: Option<PathBuf>,
+}
+
+/// Shared, validated strategy parameters.
+///
+/// Clones share the same parameters.
+#[derive(Debug, Clone)]
+pub struct ParamStore<P> {
+    inner: Arc<Mutex<Inner<P>>>,
+}
+
+impl<P: Params> ParamStore<P> {
+    /// Creates a store holding `params`.
+    pub fn new(params: P) -> Result<Self> {
+        params.validate()?;
+        Ok(Self {
+            inner: Arc::new(Mutex::new(Inner {
+                params,
+                version: 0,
+                audit: vec![],
+                audit_log: None,
+            })),
+        })
+    }
+
+    /// Creates a store holding the parameters in the file at `path`.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        Self::new(read(path.as_ref())?)
+    }
+
+    /// Appends every audit entry to the file at `path`, one JSON object per line.
+    #[must_use]
+    pub fn audit_log(self, path: impl Into<PathBuf>) -> Self {
+        self.lock().audit_log = Some(path.into());
+        self
+    }
+
+    /// Returns the current parameters.
+    #[must_use]
+    pub fn get(&self) -> P {
+        self.lock().params.clone()
+    }
+
+    /// Returns the version of the current parameters.
+    ///
+    /// The version starts at 0 and increments with every applied update.
+    #[must_use]
+    pub fn version(&self) -> u64 {
+        self.lock().version
+    }
+
+    /// Returns the audit trail, oldest first.
+    #[must_use]
+    pub fn audit(&self) -> Vec<ParamChange> {
+        self.lock().audit.clone()
+    }
+
+    /// Returns a reader that reports updates applied from now on.
+    #[must_use]
+    pub fn reader(&self) -> ParamReader<P> {
+        ParamReader {
+            seen: self.version(),
+            store: self.clone(),
+        }
+    }
+
+    /// Replaces the parameters, returning the new version.
+    ///
+    /// An update that changes nothing is accepted without a new version or an audit
+    /// entry.
+    pub fn update(&self, params: P, source: &str) -> Result<u64> {
+        let mut inner = self.lock();
+        let changes = diff(&to_value(&inner.params), &to_value(&params));
+        if changes.is_empty() {
+            return Ok(inner.version);
+        }
+        let error = params.validate().err();
+        if error.is_none() {
+            inner.params = params;
+            inner.version += 1;
+        }
+        let entry = ParamChange {
+            time: Utc::now().timestamp_millis() as u64,
+            source: source.to_owned(),
+            version: inner.version,
+            changes,
+            error: error.as_ref().map(|err| format!("{err:#}")),
+        };
+        inner.record(entry);
+        match error {
+            Some(err) => Err(err.context(format!("rejected parameters from {source}"))),
+            None => Ok(inner.version),
+        }
+    }
+
+    /// Merges `patch` into the current parameters, returning the new version.
+    ///
+    /// The patch is a JSON merge patch (RFC 7386): fields it sets replace the
+    /// current ones and `null` fields are removed. It suits partial updates sent
+    /// over a control channel, e.g. `{"spacing": "2.5"}`.
+    pub fn patch(&self, patch: &Value, source: &str) -> Result<u64> {
+        let mut value = to_value(&self.get());
+        merge(&mut value, patch);
+        let params = serde_json::from_value(value)
+            .with_context(|| format!("invalid parameters from {source}"))?;
+        self.update(params, source)
+    }
+
+    /// Reloads the parameters from the file at `path`, returning the new version.
+    pub fn reload(&self, path: impl AsRef<Path>) -> Result<u64> {
+        let path = path.as_ref();
+        self.update(read(path)?, &path.display().to_string())
+    }
+
+    /// Reloads the file at `path` whenever its modification time changes.
+    ///
+    /// Checks the file every `interval` and runs until dropped; spawn it next to the
+    /// strategy. Files that fail to parse or validate are logged and leave the
+    /// current parameters in place.
+    pub async fn watch(self, path: impl Into<PathBuf>, interval: Duration) {
+        let path = path.into();
+        let mut modified = mtime(&path);
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            let current = mtime(&path);
+            if current == modified {
+                continue;
+            }
+            modified = current;
+            match self.reload(&path) {
+                Ok(version) => log::info!("{}: parameters at version {version}", path.display()),
+                Err(err) => log::warn!("{}: {err:#}", path.display()),
+            }
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Inner<P>> {
+        self.inner.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl<P> Inner<P> {
+    fn record(&mut self, entry: ParamChange) {
+        if let Some(path) = &self.audit_log {
+            let written = fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .and_then(|mut file| {
+                    let line = serde_json::to_string(&entry)?;
+                    writeln!(file, "{line}")
+                });
+            if let Err(err) = written {
+                log::warn!("unable to write audit log {}: {err}", path.display());
+            }
+        }
+        self.audit.push(entry);
+    }
+}
+
+/// Picks up the updates of a [`ParamStore`].
+#[derive(Debug, Clone)]
+pub struct ParamReader<P> {
+    store: ParamStore<P>,
+    seen: u64,
+}
+
+impl<P: Params> ParamReader<P> {
+    /// Returns the current parameters if they changed since the last call.
+    pub fn changed(&mut self) -> Option<P> {
+        let inner = self.store.lock();
+        if inner.version == self.seen {
+            return None;
+        }
+        self.seen = inner.version;
+        Some(inner.params.clone())
+    }
+
+    /// Returns the current parameters.
+    #[must_use]
+    pub fn get(&self) -> P {
+        self.store.get()
+    }
+}
+
+/// Reads parameters from a JSON file, or a TOML file with the `toml` feature.
+fn read<P: Params>(path: &Path) -> Result<P> {
+    let text =
+        fs::read_to_string(path).with_context(|| format!("unable to read {}", path.display()))?;
+    let is_toml = path.extension().is_some_and(|ext| ext == "toml");
+    let params = if is_toml {
+        parse_toml(&text)
+    } else {
+        serde_json::from_str(&text).map_err(Into::into)
+    };
+    params.with_context(|| format!("invalid parameters in {}", path.display()))
+}
+
+#[cfg(feature = "toml")]
+fn parse_toml<P: Params>(text: &str) -> Result<P> {
+    Ok(toml::from_str(text)?)
+}
+
+#[cfg(not(feature = "toml"))]
+fn parse_toml<P: Params>(_text: &str) -> Result<P> {
+    anyhow::bail!("TOML parameters require the `toml` feature")
+}
+
+fn mtime(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+fn to_value<P: Serialize>(params: &P) -> Value {
+    serde_json::to_value(params).unwrap_or_default()
+}
+
+/// Lists the top-level fields that differ between `from` and `to`.
+///
+/// Parameters that don't serialize to an object are compared as a whole, as the
+/// field `""`.
+fn diff(from: &Value, to: &Value) -> Vec<FieldChange> {
+    let (Value::Object(from), Value::Object(to)) = (from, to) else {
+        if from == to {
+            return vec![];
+        }
+        return vec![FieldChange {
+            field: String::new(),
+            from: from.clone(),
+            to: to.clone(),
+        }];
+    };
+    let mut fields: Vec<&String> = from.keys().chain(to.keys()).collect();
+    fields.sort();
+    fields.dedup();
+    fields
+        .into_iter()
+        .filter_map(|field| {
+            let before = from.get(field).cloned().unwrap_or_default();
+            let after = to.get(field).cloned().unwrap_or_default();
+            (before != after).then(|| FieldChange {
+                field: field.clone(),
+                from: before,
+                to: after,
+            })
+        })
+        .collect()
+}
+
+/// Applies a JSON merge patch.
+fn merge(target: &mut Value, patch: &Value) {
+    let Value::Object(patch) = patch else {
+        *target = patch.clone();
+        return;
+    };
+    if !target.is_object() {
+        *target = Value::Object(Default::default());
+    }
+    let target = target.as_object_mut().expect("target is an object");
+    for (key, value) in patch {
+        if value.is_null() {
+            target.remove(key);
+        } else {
+            merge(target.entry(key.clone()).or_insert(Value::Null), value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::{Decimal, dec};
+
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct GridParams {
+        spacing: Decimal,
+        levels: u32,
+    }
+
+    impl Params for GridParams {
+        fn validate(&self) -> Result<()> {
+            anyhow::ensure!(self.spacing > Decimal::ZERO, "spacing must be positive");
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_param_store() {
+        let store = ParamStore::new(GridParams {
+            spacing: dec!(1),
+            levels: 5,
+        })
+        .unwrap();
+        let mut reader = store.reader();
+        assert_eq!(reader.changed(), None);
+
+        let version = store
+            .patch(&serde_json::json!({"spacing": "2.5"}), "socket")
+            .unwrap();
+        assert_eq!(version, 1);
+        assert_eq!(
+            reader.changed(),
+            Some(GridParams {
+                spacing: dec!(2.5),
+                levels: 5,
+            })
+        );
+        assert_eq!(reader.changed(), None);
+
+        assert!(
+            store
+                .patch(&serde_json::json!({"spacing": "-1"}), "socket")
+                .is_err()
+        );
+        assert_eq!(store.get().spacing, dec!(2.5));
+        assert_eq!(reader.changed(), None);
+
+        // Unchanged parameters leave no trace.
+        store.update(store.get(), "socket").unwrap();
+
+        let audit = store.audit();
+        assert_eq!(audit.len(), 2);
+        assert!(audit[0].is_applied());
+        assert_eq!(
+            audit[0].changes,
+            [FieldChange {
+                field: "spacing".into(),
+                from: "1".into(),
+                to: "2.5".into(),
+            }]
+        );
+        assert!(!audit[1].is_applied());
+        assert_eq!(audit[1].version, 1);
+    }
+}
@@ -0,0 +1,331 @@
+//! WebSocket subscriptions sharded across connections.
+//!
+//! Hyperliquid caps the number of subscriptions a WebSocket connection may hold. A
+//! [`ConnectionPool`] spreads subscriptions over as many [`Connection`]s as needed,
+//! opening a new one when the others are full, and merges their events into a
+//! single stream. When a connection drops, its subscriptions move to the connected
+//! ones (or to a new connection) instead of waiting for it to reconnect.
+//!
+//! Connection status events ([`Event::Connected`], [`Event::Disconnected`], ...)
+//! are passed through from each connection.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use futures::StreamExt;
+//! use hypersdk::hypercore::{self, pool::ConnectionPool, types::*, ws::Event};
+//!
+//! # async fn example(coins: Vec<String>) {
+//! let mut pool = ConnectionPool::new(hypercore::mainnet_websocket_url());
+//! for coin in coins {
+//!     pool.subscribe(Subscription::Trades { coin: coin.clone() });
+//!     pool.subscribe(Subscription::Bbo { coin });
+//! }
+//! println!("{} connections", pool.connections());
+//!
+//! while let Some(event) = pool.next().await {
+//!     if let Event::Message(Incoming::Trades(trades)) = event {
+//!         println!("{} trades", trades.len());
+//!     }
+//! }
+//! # }
+//! ```
+
+use std::{
+    collections::{HashMap, HashSet},
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+
+use futures::{Stream, StreamExt, stream::SelectAll};
+use url::Url;
+
+use super::{
+    types::Subscription,
+    ws::{Connection, ConnectionHandle, ConnectionStats, ConnectionStream, Event},
+};
+
+/// Default number of subscriptions per connection.
+pub const DEFAULT_MAX_PER_CONNECTION: usize = 1000;
+
+/// One connection of the pool.
+struct Shard {
+    handle: ConnectionHandle,
+    load: usize,
+    connected: bool,
+}
+
+/// Events of one connection, tagged with its index.
+struct ShardStream {
+    index: usize,
+    stream: ConnectionStream,
+}
+
+impl Stream for ShardStream {
+    type Item = (usize, Event);
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let index = this.index;
+        this.stream
+            .poll_next_unpin(cx)
+            .map(|event| event.map(|event| (index, event)))
+    }
+}
+
+/// Pool of WebSocket connections sharing a set of subscriptions.
+///
+/// Implements `futures::Stream`, yielding the [`Event`]s of every connection.
+/// Connections are opened on demand and live as long as the pool.
+pub struct ConnectionPool {
+    url: Url,
+    max_per_connection: usize,
+    shards: Vec<Shard>,
+    streams: SelectAll<ShardStream>,
+    assignments: HashMap<Subscription, usize>,
+    /// Task that polled the pool while it had no connection.
+    waker: Option<Waker>,
+}
+
+impl ConnectionPool {
+    /// Creates an empty pool of connections to `url`.
+    pub fn new(url: Url) -> Self {
+        Self {
+            url,
+            max_per_connection: DEFAULT_MAX_PER_CONNECTION,
+            shards: Vec::new(),
+            streams: SelectAll::new(),
+            assignments: HashMap::new(),
+            waker: None,
+        }
+    }
+
+    /// Sets the number of subscriptions per connection.
+    ///
+    /// Defaults to [`DEFAULT_MAX_PER_CONNECTION`].
+    #[must_use]
+    pub fn max_per_connection(mut self, max: usize) -> Self {
+        self.max_per_connection = max.max(1);
+        self
+    }
+
+    /// Subscribes on the least loaded connection with room, opening a new one if
+    /// all are full.
+    ///
+    /// Does nothing if the pool is already subscribed.
+    pub fn subscribe(&mut self, subscription: Subscription) {
+        if self.assignments.contains_key(&subscription) {
+            return;
+        }
+        let index = self.target(None);
+        self.assign(subscription, index);
+    }
+
+    /// Unsubscribes from the connection holding `subscription`.
+    pub fn unsubscribe(&mut self, subscription: Subscription) {
+        if let Some(index) = self.assignments.remove(&subscription) {
+            let shard = &mut self.shards[index];
+            shard.load -= 1;
+            shard.handle.unsubscribe(subscription);
+        }
+    }
+
+    /// Returns the subscriptions of the pool.
+    #[must_use]
+    pub fn subscriptions(&self) -> HashSet<Subscription> {
+        self.assignments.keys().cloned().collect()
+    }
+
+    /// Returns the number of open connections.
+    #[must_use]
+    pub fn connections(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// Returns the health of each connection; see [`Connection::stats`].
+    #[must_use]
+    pub fn stats(&self) -> Vec<ConnectionStats> {
+        self.shards
+            .iter()
+            .map(|shard| shard.handle.stats())
+            .collect()
+    }
+
+    /// Returns the shard a subscription should go to, other than `exclude`,
+    /// opening a connection if none has room.
+    ///
+    /// Connected shards are preferred, then the least loaded.
+    fn target(&mut self, exclude: Option<usize>) -> usize {
+        let best = self
+            .shards
+            .iter()
+            .enumerate()
+            .filter(|(index, shard)| {
+                Some(*index) != exclude && shard.load < self.max_per_connection
+            })
+            .min_by_key(|(_, shard)| (!shard.connected, shard.load))
+            .map(|(index, _)| index);
+        match best {
+            Some(index) => index,
+            None => self.open(),
+        }
+    }
+
+    fn assign(&mut self, subscription: Subscription, index: usize) {
+        let shard = &mut self.shards[index];
+        shard.load += 1;
+        shard.handle.subscribe(subscription.clone());
+        self.assignments.insert(subscription, index);
+    }
+
+    fn open(&mut self) -> usize {
+        let index = self.shards.len();
+        let (handle, stream) = Connection::new(self.url.clone()).split();
+        self.shards.push(Shard {
+            handle,
+            load: 0,
+            connected: false,
+        });
+        self.streams.push(ShardStream { index, stream });
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+        index
+    }
+
+    /// Moves the subscriptions of the disconnected shard `index` to connected ones.
+    ///
+    /// Left in place if no other shard is connected: they are restored when the
+    /// connection comes back.
+    fn rebalance(&mut self, index: usize) {
+        let others_connected = self
+            .shards
+            .iter()
+            .enumerate()
+            .any(|(other, shard)| other != index && shard.connected);
+        if !others_connected {
+            return;
+        }
+
+        let moved: Vec<Subscription> = self
+            .assignments
+            .iter()
+            .filter(|(_, shard)| **shard == index)
+            .map(|(sub, _)| sub.clone())
+            .collect();
+        if !moved.is_empty() {
+            log::debug!(
+                "Moving {} subscriptions off connection {index}",
+                moved.len()
+            );
+        }
+        for sub in moved {
+            let shard = &mut self.shards[index];
+            shard.load -= 1;
+            shard.handle.unsubscribe(sub.clone());
+            let target = self.target(Some(index));
+            self.assign(sub, target);
+        }
+    }
+}
+
+impl Stream for ConnectionPool {
+    type Item = Event;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        // An empty pool has nothing to yield yet, but may get connections later.
+        if this.shards.is_empty() {
+            this.waker = Some(cx.waker().clone());
+            return Poll::Pending;
+        }
+        match this.streams.poll_next_unpin(cx) {
+            Poll::Ready(Some((index, event))) => {
+                match event {
+                    Event::Connected => this.shards[index].connected = true,
+                    Event::Disconnected => {
+                        this.shards[index].connected = false;
+                        this.rebalance(index);
+                    }
+                    _ => {}
+                }
+                Poll::Ready(Some(event))
+            }
+            Poll::Ready(None) => Poll::Ready(None),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_pool_sharding() {
+        // Nothing listens there; only the bookkeeping is exercised.
+        let mut pool =
+            ConnectionPool::new("ws://127.0.0.1:9".parse().unwrap()).max_per_connection(2);
+        let trades = |coin: &str| Subscription::Trades { coin: coin.into() };
+        for coin in ["BTC", "ETH", "SOL", "HYPE", "BTC"] {
+            pool.subscribe(trades(coin));
+        }
+        assert_eq!(pool.connections(), 2);
+        assert_eq!(pool.subscriptions().len(), 4);
+        let loads = |pool: &ConnectionPool| {
+            pool.shards
+                .iter()
+                .map(|shard| shard.load)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(loads(&pool), [2, 2]);
+
+        pool.unsubscribe(trades("ETH"));
+        pool.subscribe(trades("DOGE"));
+        assert_eq!(pool.connections(), 2);
+        pool.subscribe(trades("ARB"));
+        assert_eq!(loads(&pool), [2, 2, 1]);
+
+        // Shard 0 drops: its subscriptions move to the connected shards with room.
+        pool.shards[2].connected = true;
+        pool.rebalance(0);
+        assert_eq!(loads(&pool), [0, 2, 2, 1]);
+        assert_eq!(pool.subscriptions().len(), 5);
+
+        // Nothing else connected: subscriptions stay put.
+        pool.shards[2].connected = false;
+        pool.rebalance(1);
+        assert_eq!(loads(&pool), [0, 2, 2, 1]);
+    }
+
+    #[tokio::test]
+    async fn test_empty_pool_wakes_on_subscribe() {
+        use std::{
+            sync::{
+                Arc,
+                atomic::{AtomicBool, Ordering},
+            },
+            task::Wake,
+        };
+
+        struct Flag(AtomicBool);
+
+        impl Wake for Flag {
+            fn wake(self: Arc<Self>) {
+                self.0.store(true, Ordering::SeqCst);
+            }
+        }
+
+        let flag = Arc::new(Flag(AtomicBool::new(false)));
+        let waker = Waker::from(flag.clone());
+        let mut cx = Context::from_waker(&waker);
+
+        let mut pool = ConnectionPool::new("ws://127.0.0.1:9".parse().unwrap());
+        assert!(pool.poll_next_unpin(&mut cx).is_pending());
+        assert!(!flag.0.load(Ordering::SeqCst));
+
+        pool.subscribe(Subscription::Trades { coin: "BTC".into() });
+        assert!(flag.0.load(Ordering::SeqCst));
+        assert!(pool.waker.is_none());
+    }
+}
@@ -0,0 +1,286 @@
+//! Recording and replaying WebSocket messages.
+//!
+//! A [`Recorder`] writes every message of a feed to disk as JSON lines, one
+//! [`Tick`] per line: the local receive time in milliseconds and the message in its
+//! wire shape (`{"channel": ..., "data": ...}`). A [`Replay`] reads a recording back
+//! as the same [`Incoming`] types, either as ticks for a
+//! [`Backtest`](super::backtest::Backtest) or as an [`Event`] stream paced at the
+//! original speed, faster, or as fast as it can be read. A session recorded once
+//! then drives strategy backtests and regression tests deterministically.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use futures::StreamExt;
+//! use hypersdk::hypercore::{self, record::{Recorder, Replay}, types::*, ws::Event};
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! // Record an hour of BTC trades.
+//! let ws = hypercore::mainnet_ws();
+//! ws.subscribe(Subscription::Trades { coin: "BTC".into() });
+//! let mut recording = Recorder::create("btc-trades.jsonl")?.tap(ws);
+//! let hour = tokio::time::sleep(std::time::Duration::from_secs(3600));
+//! tokio::pin!(hour);
+//! loop {
+//!     tokio::select! {
+//!         _ = &mut hour => break,
+//!         Some(_) = recording.next() => {}
+//!     }
+//! }
+//! recording.into_recorder().flush()?;
+//!
+//! // Play it back ten times faster.
+//! let mut replay = Box::pin(Replay::open("btc-trades.jsonl")?.speed(10.0).into_stream());
+//! while let Some(event) = replay.next().await {
+//!     if let Event::Message(Incoming::Trades(trades)) = event {
+//!         println!("{} trades", trades.len());
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, BufWriter, Write},
+    path::Path,
+    pin::Pin,
+    task::{Context, Poll, ready},
+    time::Duration,
+};
+
+use anyhow::{Context as _, Result};
+use chrono::Utc;
+use futures::{Stream, StreamExt};
+use serde::Serialize;
+
+use super::{backtest::Tick, types::Incoming, ws::Event};
+
+/// Writes messages as JSON lines of [`Tick`]s.
+///
+/// Buffered writers are flushed when dropped; call [`flush`](Self::flush) to see
+/// write errors.
+pub struct Recorder<W: Write> {
+    writer: W,
+}
+
+/// Borrowed [`Tick`], to record without cloning the message.
+#[derive(Serialize)]
+struct TickRef<'a> {
+    time: u64,
+    msg: &'a Incoming,
+}
+
+impl Recorder<BufWriter<File>> {
+    /// Creates a recording at `path`, replacing any existing file.
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Self::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl<W: Write> Recorder<W> {
+    /// Records to `writer`.
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    /// Records `msg`, received now.
+    pub fn record(&mut self, msg: &Incoming) -> io::Result<()> {
+        self.record_at(Utc::now().timestamp_millis() as u64, msg)
+    }
+
+    /// Records `msg`, received at `time` in milliseconds.
+    pub fn record_at(&mut self, time: u64, msg: &Incoming) -> io::Result<()> {
+        serde_json::to_writer(&mut self.writer, &TickRef { time, msg })?;
+        self.writer.write_all(b"\n")
+    }
+
+    /// Flushes buffered messages to the writer.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Wraps `stream` so that every [`Event::Message`] it yields is recorded.
+    pub fn tap<S>(self, stream: S) -> Recording<S, W> {
+        Recording {
+            inner: stream,
+            recorder: self,
+        }
+    }
+
+    /// Returns the writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// Event stream recording its messages, created by [`Recorder::tap`].
+///
+/// Yields the events of the wrapped stream unchanged. A message that can't be
+/// written is logged and still yielded.
+pub struct Recording<S, W: Write> {
+    inner: S,
+    recorder: Recorder<W>,
+}
+
+impl<S, W: Write> Recording<S, W> {
+    /// Returns the recorder, ending the recording.
+    pub fn into_recorder(self) -> Recorder<W> {
+        self.recorder
+    }
+}
+
+impl<S, W> Stream for Recording<S, W>
+where
+    S: Stream<Item = Event> + Unpin,
+    W: Write + Unpin,
+{
+    type Item = Event;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let event = ready!(this.inner.poll_next_unpin(cx));
+        if let Some(Event::Message(msg)) = &event {
+            if let Err(err) = this.recorder.record(msg) {
+                log::warn!("unable to record message: {err}");
+            }
+        }
+        Poll::Ready(event)
+    }
+}
+
+/// Reads a recording back.
+///
+/// Replays run as fast as the recording can be read unless a [`speed`](Self::speed)
+/// is set.
+pub struct Replay<R> {
+    reader: R,
+    speed: Option<f64>,
+}
+
+impl Replay<BufReader<File>> {
+    /// Opens the recording at `path`.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Self::new(BufReader::new(File::open(path)?)))
+    }
+}
+
+impl<R: BufRead> Replay<R> {
+    /// Replays the recording read from `reader`.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            speed: None,
+        }
+    }
+
+    /// Paces [`into_stream`](Self::into_stream) at `speed` times the recorded rate:
+    /// `1.0` for the original timing, `10.0` for ten times faster.
+    #[must_use]
+    pub fn speed(mut self, speed: f64) -> Self {
+        self.speed = Some(speed).filter(|speed| *speed > 0.0);
+        self
+    }
+
+    /// Returns the recorded ticks in order, e.g. for a
+    /// [`Backtest`](super::backtest::Backtest), without pacing.
+    ///
+    /// Blank lines are skipped; a line that isn't a tick is an error naming its line
+    /// number.
+    pub fn ticks(self) -> Ticks<R> {
+        Ticks {
+            lines: self.reader.lines(),
+            line: 0,
+        }
+    }
+
+    /// Returns the recorded messages as [`Event::Message`]s, paced by
+    /// [`speed`](Self::speed) from the first one.
+    ///
+    /// Unreadable lines are logged and skipped.
+    pub fn into_stream(self) -> impl Stream<Item = Event> {
+        let speed = self.speed;
+        let state = (self.ticks(), None::<(u64, tokio::time::Instant)>);
+        futures::stream::unfold(state, move |(mut ticks, mut start)| async move {
+            loop {
+                let tick = match ticks.next()? {
+                    Ok(tick) => tick,
+                    Err(err) => {
+                        log::warn!("skipping recorded message: {err:#}");
+                        continue;
+                    }
+                };
+                if let Some(speed) = speed {
+                    let (first, at) =
+                        *start.get_or_insert((tick.time, tokio::time::Instant::now()));
+                    let offset = Duration::from_millis(tick.time.saturating_sub(first));
+                    tokio::time::sleep_until(at + offset.div_f64(speed)).await;
+                }
+                return Some((Event::Message(tick.msg), (ticks, start)));
+            }
+        })
+    }
+}
+
+/// Iterator over the ticks of a recording, created by [`Replay::ticks`].
+pub struct Ticks<R> {
+    lines: io::Lines<R>,
+    line: usize,
+}
+
+impl<R: BufRead> Iterator for Ticks<R> {
+    type Item = Result<Tick>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let line = self.lines.next()?;
+            self.line += 1;
+            let line = match line {
+                Ok(line) => line,
+                Err(err) => return Some(Err(err.into())),
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            let tick = serde_json::from_str(&line)
+                .with_context(|| format!("line {} of the recording", self.line));
+            return Some(tick);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_and_replay() {
+        let mids: Incoming =
+            serde_json::from_str(r#"{"channel":"allMids","data":{"mids":{"BTC":"97000.5"}}}"#)
+                .unwrap();
+        let fast: Incoming = serde_json::from_str(
+            r#"{"channel":"fastAssetCtxs","data":"q1ZyCnFWsqpWyk0syg6oULJSsjQ3NTDQM1Wq1VFyDfFAkTI2MzXQMwJLVVRWWfmFuTiiyBuamOoZKdXWAgA="}"#,
+        )
+        .unwrap();
+
+        let mut recorder = Recorder::new(Vec::new());
+        recorder.record_at(1_000, &mids).unwrap();
+        recorder.record_at(1_250, &fast).unwrap();
+        let mut recording = recorder.into_inner();
+        recording.extend_from_slice(b"\nnot a tick\n");
+
+        let mut ticks = Replay::new(recording.as_slice()).ticks();
+        let tick = ticks.next().unwrap().unwrap();
+        assert_eq!(tick.time, 1_000);
+        assert!(matches!(
+            tick.msg,
+            Incoming::AllMids { mids, .. } if mids["BTC"].to_string() == "97000.5"
+        ));
+        let tick = ticks.next().unwrap().unwrap();
+        assert_eq!(tick.time, 1_250);
+        assert!(matches!(tick.msg, Incoming::FastAssetCtxs(ctxs) if ctxs.len() == 3));
+        let err = ticks.next().unwrap().unwrap_err();
+        assert_eq!(err.to_string(), "line 4 of the recording");
+        assert!(ticks.next().is_none());
+    }
+}
@@ -0,0 +1,343 @@
+//! Per-purpose signer selection.
+//!
+//! Production setups rarely sign everything with one key: an API agent places orders, a
+//! separate (often hardware or KMS-backed) key moves funds, and yet another signs as a
+//! multi-sig member. A [`SignerRegistry`] maps each [`Purpose`] to a signer and an
+//! optional [`Policy`], picks the right signer for every action, and refuses actions the
+//! signer isn't allowed to sign.
+//!
+//! Any [`Signer`] backend can be registered: private keys, decrypted keystores, Ledger
+//! and Trezor devices, or remote signers such as AWS/GCP KMS.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use hypersdk::hypercore::{
+//!     self, PrivateKeySigner,
+//!     registry::{Policy, Purpose, SignerRegistry},
+//! };
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let agent: PrivateKeySigner = "0x...".parse()?;
+//! let treasury: PrivateKeySigner = "0x...".parse()?;
+//!
+//! let registry = SignerRegistry::new()
+//!     .with(Purpose::Trading, agent)
+//!     // The treasury key may only withdraw to Arbitrum, never send on Core.
+//!     .with_policy(Purpose::Withdrawals, treasury, Policy::only(["withdraw3"]));
+//!
+//! let client = hypercore::mainnet();
+//! # let action = hypercore::api::Action::Noop;
+//! let nonce = chrono::Utc::now().timestamp_millis() as u64;
+//! client.send_with(&registry, action, nonce, None, None).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+
+use alloy::{primitives::Address, signers::Signer};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    Chain, Signature,
+    api::{Action, ActionRequest},
+    multisig::Proposal,
+};
+
+/// A boxed signer of any backend.
+pub type DynSigner = Box<dyn Signer + Send + Sync>;
+
+/// What a signer is used for.
+///
+/// Every [`Action`] belongs to exactly one purpose, see [`Purpose::of`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, derive_more::Display)]
+#[serde(rename_all = "camelCase")]
+pub enum Purpose {
+    /// Orders, cancels, leverage and margin. Usually an API agent.
+    #[display("trading")]
+    Trading,
+    /// Moving funds: sends, withdrawals, class, vault and staking transfers.
+    #[display("withdrawals")]
+    Withdrawals,
+    /// Account administration: agents, builder fees, abstraction, deployments.
+    #[display("account")]
+    Account,
+    /// Signing multi-sig actions, as the lead or as a member.
+    #[display("multisig")]
+    MultiSig,
+}
+
+impl Purpose {
+    /// Returns the purpose `action` belongs to.
+    pub fn of(action: &Action) -> Self {
+        match action {
+            Action::Order(_)
+            | Action::BatchModify(_)
+            | Action::Cancel(_)
+            | Action::CancelByCloid(_)
+            | Action::ScheduleCancel(_)
+            | Action::UpdateIsolatedMargin(_)
+            | Action::UpdateLeverage(_)
+            | Action::AgentSendAsset(_)
+            | Action::Noop
+            | Action::GossipPriorityBid(_)
+            | Action::TwapOrder { .. }
+            | Action::TwapCancel { .. }
+            | Action::ReserveRequestWeight { .. }
+            | Action::UserOutcome(_) => Self::Trading,
+            Action::UsdSend(_)
+            | Action::SendAsset(_)
+            | Action::SpotSend(_)
+            | Action::VaultTransfer(_)
+            | Action::Withdraw3(_)
+            | Action::UsdClassTransfer(_)
+            | Action::CDeposit(_)
+            | Action::CWithdraw(_)
+            | Action::TokenDelegate(_)
+            | Action::Hip3LiquidatorTransfer(_) => Self::Withdrawals,
+            Action::EvmUserModify { .. }
+            | Action::ApproveAgent(_)
+            | Action::ApproveBuilderFee(_)
+            | Action::ConvertToMultiSigUser(_)
+            | Action::AgentEnableDexAbstraction
+            | Action::AgentSetAbstraction { .. }
+            | Action::UserDexAbstraction(_)
+            | Action::UserSetAbstraction(_)
+            | Action::SpotDeploy(_)
+            | Action::PerpDeploy(_) => Self::Account,
+            Action::MultiSig(_) => Self::MultiSig,
+        }
+    }
+}
+
+/// Which actions a registered signer may sign.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Policy {
+    /// Every action of the signer's purpose.
+    #[default]
+    Any,
+    /// Only actions of the listed types, by their wire name (`"order"`, `"withdraw3"`).
+    ///
+    /// For multi-sig signers this applies to the inner action.
+    Only(Vec<String>),
+}
+
+impl Policy {
+    /// Allows only the listed action types.
+    pub fn only<I, T>(types: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        Self::Only(types.into_iter().map(Into::into).collect())
+    }
+
+    /// Whether `action` may be signed under this policy.
+    pub fn allows(&self, action: &Action) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Only(types) => action_type(action).is_some_and(|ty| types.contains(&ty)),
+        }
+    }
+}
+
+/// The registry refused to sign an action.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum PolicyError {
+    /// No signer is registered for the action's purpose.
+    #[error("no signer registered for {0} actions")]
+    NoSigner(Purpose),
+    /// The signer's policy doesn't allow the action.
+    #[error("{purpose} signer is not allowed to sign {action:?}")]
+    NotAllowed {
+        /// Purpose of the signer
+        purpose: Purpose,
+        /// Wire name of the refused action
+        action: String,
+    },
+}
+
+struct Entry {
+    signer: DynSigner,
+    policy: Policy,
+}
+
+/// Signers keyed by [`Purpose`], each with a [`Policy`].
+///
+/// Pass it to [`HttpClient::send_with`](crate::hypercore::HttpClient::send_with) to sign
+/// and submit any action with the right key.
+#[derive(Default)]
+pub struct SignerRegistry {
+    entries: HashMap<Purpose, Entry>,
+}
+
+impl SignerRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `signer` for `purpose` with the default policy.
+    #[must_use]
+    pub fn with<S>(self, purpose: Purpose, signer: S) -> Self
+    where
+        S: Signer + Send + Sync + 'static,
+    {
+        self.with_policy(purpose, signer, Policy::Any)
+    }
+
+    /// Registers `signer` for `purpose` with `policy`.
+    #[must_use]
+    pub fn with_policy<S>(mut self, purpose: Purpose, signer: S, policy: Policy) -> Self
+    where
+        S: Signer + Send + Sync + 'static,
+    {
+        self.insert(purpose, Box::new(signer), policy);
+        self
+    }
+
+    /// Registers `signer` for `purpose`, replacing and returning the previous signer.
+    pub fn insert(
+        &mut self,
+        purpose: Purpose,
+        signer: DynSigner,
+        policy: Policy,
+    ) -> Option<DynSigner> {
+        self.entries
+            .insert(purpose, Entry { signer, policy })
+            .map(|entry| entry.signer)
+    }
+
+    /// Removes the signer registered for `purpose`.
+    pub fn remove(&mut self, purpose: Purpose) -> Option<DynSigner> {
+        self.entries.remove(&purpose).map(|entry| entry.signer)
+    }
+
+    /// Returns the signer registered for `purpose`.
+    pub fn signer(&self, purpose: Purpose) -> Option<&DynSigner> {
+        self.entries.get(&purpose).map(|entry| &entry.signer)
+    }
+
+    /// Returns the policy of the signer registered for `purpose`.
+    pub fn policy(&self, purpose: Purpose) -> Option<&Policy> {
+        self.entries.get(&purpose).map(|entry| &entry.policy)
+    }
+
+    /// Returns the address of the signer registered for `purpose`.
+    pub fn address(&self, purpose: Purpose) -> Option<Address> {
+        self.signer(purpose).map(|signer| signer.address())
+    }
+
+    /// Returns the signer that may sign `action`.
+    pub fn select(&self, action: &Action) -> Result<&DynSigner, PolicyError> {
+        self.select_for(Purpose::of(action), action)
+    }
+
+    /// Signs `action` with the signer of its purpose.
+    pub async fn sign(
+        &self,
+        action: Action,
+        nonce: u64,
+        maybe_vault_address: Option<Address>,
+        maybe_expires_after: Option<DateTime<Utc>>,
+        chain: Chain,
+    ) -> anyhow::Result<ActionRequest> {
+        let signer = self.select(&action)?;
+        action
+            .sign(
+                signer,
+                nonce,
+                maybe_vault_address,
+                maybe_expires_after,
+                chain,
+            )
+            .await
+    }
+
+    /// Signs a multi-sig proposal with the [`Purpose::MultiSig`] signer.
+    ///
+    /// The policy is checked against the proposal's inner action.
+    pub async fn sign_proposal(&self, proposal: &Proposal) -> anyhow::Result<Signature> {
+        let signer = self.select_for(Purpose::MultiSig, &proposal.payload.action)?;
+        proposal.sign(signer).await
+    }
+
+    fn select_for(&self, purpose: Purpose, action: &Action) -> Result<&DynSigner, PolicyError> {
+        let entry = self
+            .entries
+            .get(&purpose)
+            .ok_or(PolicyError::NoSigner(purpose))?;
+        if !entry.policy.allows(action) {
+            return Err(PolicyError::NotAllowed {
+                purpose,
+                action: action_type(action).unwrap_or_default(),
+            });
+        }
+        Ok(&entry.signer)
+    }
+}
+
+impl std::fmt::Debug for SignerRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut map = f.debug_map();
+        for (purpose, entry) in &self.entries {
+            map.entry(purpose, &(entry.signer.address(), &entry.policy));
+        }
+        map.finish()
+    }
+}
+
+/// Returns the wire name of `action` (its `type` field).
+fn action_type(action: &Action) -> Option<String> {
+    let value = serde_json::to_value(action).ok()?;
+    value.get("type")?.as_str().map(str::to_owned)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hypercore::{PrivateKeySigner, ScheduleCancel};
+
+    #[tokio::test]
+    async fn test_registry_policy() {
+        let agent = PrivateKeySigner::random();
+        let member = PrivateKeySigner::random();
+        let agent_address = agent.address();
+        let registry = SignerRegistry::new()
+            .with_policy(Purpose::Trading, agent, Policy::only(["scheduleCancel"]))
+            .with_policy(Purpose::MultiSig, member, Policy::only(["noop"]));
+
+        let cancel = Action::ScheduleCancel(ScheduleCancel { time: None });
+        assert_eq!(Purpose::of(&cancel), Purpose::Trading);
+        assert_eq!(registry.select(&cancel).unwrap().address(), agent_address);
+
+        assert_eq!(
+            registry.select(&Action::Noop).err().unwrap(),
+            PolicyError::NotAllowed {
+                purpose: Purpose::Trading,
+                action: "noop".into(),
+            }
+        );
+        assert!(matches!(
+            registry.select(&Action::EvmUserModify {
+                using_big_blocks: true
+            }),
+            Err(PolicyError::NoSigner(Purpose::Account))
+        ));
+
+        let proposal = Proposal::new(
+            Chain::Testnet,
+            Address::repeat_byte(1),
+            Address::repeat_byte(2),
+            Action::Noop,
+            1,
+        );
+        let signature = registry.sign_proposal(&proposal).await.unwrap();
+        assert_eq!(
+            proposal.recover(&signature).unwrap(),
+            registry.address(Purpose::MultiSig).unwrap()
+        );
+    }
+}
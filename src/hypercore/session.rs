@@ -0,0 +1,173 @@
+//! Short-lived agent keys for a trading session.
+//!
+//! A bot that keeps its agent key for months turns every leak into a long-lived
+//! compromise. A [`SessionKey`] instead generates a fresh agent key when the session
+//! starts, approves it with an expiry (`valid_until`), signs all trading in the session
+//! with it, and revokes it on shutdown. If the process dies before revoking, the
+//! exchange expires the agent on its own.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use hypersdk::hypercore::{self, NonceHandler, PrivateKeySigner, session::SessionKey};
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let client = hypercore::mainnet();
+//! let owner: PrivateKeySigner = "0x...".parse()?;
+//! let nonces = NonceHandler::default();
+//!
+//! let session = SessionKey::approve(
+//!     &client,
+//!     &owner,
+//!     "bot-session",
+//!     Duration::from_secs(8 * 60 * 60),
+//!     nonces.next(),
+//! )
+//! .await?;
+//!
+//! // Trade with `session.signer()` instead of the owner key...
+//!
+//! session.revoke(&client, &owner, nonces.next()).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::time::Duration;
+
+use alloy::{primitives::Address, signers::Signer};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+
+use super::{HttpClient, PrivateKeySigner};
+
+/// Longest validity the exchange accepts for an agent.
+pub const MAX_VALIDITY: Duration = Duration::from_secs(180 * 24 * 60 * 60);
+
+/// An ephemeral agent key approved for a bounded time.
+///
+/// Dropping a session doesn't revoke it, since revoking is a signed request; call
+/// [`revoke`](Self::revoke) on shutdown and rely on the expiry otherwise.
+#[derive(Debug, Clone)]
+pub struct SessionKey {
+    signer: PrivateKeySigner,
+    owner: Address,
+    name: String,
+    valid_until: DateTime<Utc>,
+}
+
+impl SessionKey {
+    /// Generates an agent key and approves it on behalf of `owner` until `validity`
+    /// from now.
+    ///
+    /// `name` is the agent slot the key occupies. Approving under the name of an
+    /// existing agent replaces it, so a crashed session is superseded by the next one.
+    pub async fn approve<S: Signer + Send + Sync>(
+        client: &HttpClient,
+        owner: &S,
+        name: impl Into<String>,
+        validity: Duration,
+        nonce: u64,
+    ) -> Result<Self> {
+        anyhow::ensure!(
+            !validity.is_zero() && validity <= MAX_VALIDITY,
+            "session validity must be between 0 and {} days",
+            MAX_VALIDITY.as_secs() / 86_400
+        );
+        let name = name.into();
+        anyhow::ensure!(!name.is_empty(), "session keys need a named agent slot");
+
+        let signer = PrivateKeySigner::random();
+        let valid_until = Utc::now() + chrono::TimeDelta::from_std(validity)?;
+        client
+            .approve_agent(
+                owner,
+                signer.address(),
+                agent_name(&name, valid_until),
+                nonce,
+            )
+            .await?;
+
+        Ok(Self {
+            signer,
+            owner: owner.address(),
+            name,
+            valid_until,
+        })
+    }
+
+    /// The agent key to sign trading actions with.
+    pub fn signer(&self) -> &PrivateKeySigner {
+        &self.signer
+    }
+
+    /// Address of the agent.
+    pub fn address(&self) -> Address {
+        self.signer.address()
+    }
+
+    /// Account the agent trades for.
+    pub fn owner(&self) -> Address {
+        self.owner
+    }
+
+    /// Agent slot the key occupies.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// When the exchange stops accepting the agent's signatures.
+    pub fn valid_until(&self) -> DateTime<Utc> {
+        self.valid_until
+    }
+
+    /// Whether the agent has expired.
+    pub fn is_expired(&self) -> bool {
+        Utc::now() >= self.valid_until
+    }
+
+    /// Time left before the agent expires.
+    pub fn remaining(&self) -> Duration {
+        (self.valid_until - Utc::now()).to_std().unwrap_or_default()
+    }
+
+    /// Revokes the agent by approving the zero address in its slot.
+    ///
+    /// `owner` must be the account that approved the session.
+    pub async fn revoke<S: Signer + Send + Sync>(
+        self,
+        client: &HttpClient,
+        owner: &S,
+        nonce: u64,
+    ) -> Result<()> {
+        anyhow::ensure!(
+            owner.address() == self.owner,
+            "session was approved by {}, not {}",
+            self.owner,
+            owner.address()
+        );
+        client
+            .approve_agent(owner, Address::ZERO, self.name, nonce)
+            .await
+    }
+}
+
+/// Agent name carrying the expiry, as the exchange expects it.
+fn agent_name(name: &str, valid_until: DateTime<Utc>) -> String {
+    format!("{name} valid_until {}", valid_until.timestamp_millis())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_agent_name() {
+        let valid_until = DateTime::from_timestamp_millis(1_700_000_000_000).unwrap();
+        assert_eq!(
+            agent_name("bot", valid_until),
+            "bot valid_until 1700000000000"
+        );
+    }
+}
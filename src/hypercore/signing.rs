@@ -8,7 +8,7 @@
 //! `Action` using `Into`.
 
 use alloy::{
-    dyn_abi::TypedData,
+    dyn_abi::{Eip712Domain, TypedData},
     primitives::{Address, B256},
     signers::{Signer, SignerSync},
 };
@@ -17,12 +17,114 @@ use chrono::{DateTime, Utc};
 use serde::Serialize;
 
 use crate::hypercore::{
-    Chain,
+    Chain, ChainMismatch,
     api::{Action, MultiSigAction, MultiSigPayload},
     types::{CORE_MAINNET_EIP712_DOMAIN, Signature, solidity},
     utils::{get_typed_data, rmp_hash},
 };
 
+/// Chain-dependent values used when signing actions.
+///
+/// Every value is derived from a [`Chain`], so user-signed actions can be built without
+/// passing raw `signatureChainId` constants around:
+///
+/// - [`signature_chain_id`](Self::signature_chain_id): `signatureChainId` of user-signed actions
+/// - [`hyperliquid_chain`](Self::hyperliquid_chain): `hyperliquidChain` of user-signed actions
+/// - [`domain`](Self::domain): EIP-712 domain of user-signed actions
+/// - [`agent_source`](Self::agent_source): `source` of the Agent wrapper for L1 actions
+///
+/// [`Action::sign`] and [`Action::sign_sync`] call [`validate`](Self::validate) before
+/// signing, so an action built for the wrong chain fails with a [`ChainMismatch`]
+/// instead of being rejected by the exchange.
+///
+/// # Example
+///
+/// ```rust
+/// use hypersdk::hypercore::{Chain, SigningContext};
+///
+/// let ctx = SigningContext::from(Chain::Mainnet);
+/// assert_eq!(ctx.signature_chain_id(), "0xa4b1");
+/// assert_eq!(ctx.hyperliquid_chain(), "Mainnet");
+/// assert_eq!(ctx.agent_source(), "a");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SigningContext {
+    chain: Chain,
+}
+
+impl From<Chain> for SigningContext {
+    fn from(chain: Chain) -> Self {
+        Self { chain }
+    }
+}
+
+impl SigningContext {
+    /// Returns the chain this context signs for.
+    pub const fn chain(&self) -> Chain {
+        self.chain
+    }
+
+    /// Returns the `signatureChainId` for user-signed actions.
+    pub fn signature_chain_id(&self) -> &'static str {
+        self.chain.arbitrum_id()
+    }
+
+    /// Returns the `hyperliquidChain` for user-signed actions.
+    pub fn hyperliquid_chain(&self) -> &'static str {
+        self.chain.hyperliquid_chain()
+    }
+
+    /// Returns the EIP-712 domain for user-signed actions.
+    pub fn domain(&self) -> Eip712Domain {
+        self.chain.domain()
+    }
+
+    /// Returns the Agent `source` for L1 actions: `"a"` on mainnet, `"b"` otherwise.
+    pub fn agent_source(&self) -> &'static str {
+        if self.chain.is_mainnet() { "a" } else { "b" }
+    }
+
+    /// Checks that a user-signed action's chain fields match this context.
+    ///
+    /// L1 actions carry no chain fields and always pass. Multisig actions are checked
+    /// along with their inner action.
+    pub fn validate(&self, action: &Action) -> Result<(), ChainMismatch> {
+        if let Action::MultiSig(inner) = action {
+            self.validate(&inner.payload.action)?;
+        }
+        let Some((signature_chain_id, hyperliquid_chain)) = action.chain_fields() else {
+            return Ok(());
+        };
+
+        if !signature_chain_id.eq_ignore_ascii_case(self.signature_chain_id()) {
+            return Err(self.mismatch(
+                "signatureChainId",
+                self.signature_chain_id(),
+                signature_chain_id,
+            ));
+        }
+        if let Some(chain) = hyperliquid_chain {
+            if chain.hyperliquid_chain() != self.hyperliquid_chain() {
+                return Err(self.mismatch(
+                    "hyperliquidChain",
+                    self.hyperliquid_chain(),
+                    chain.hyperliquid_chain(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn mismatch(&self, field: &'static str, expected: &str, found: &str) -> ChainMismatch {
+        ChainMismatch {
+            chain: self.chain,
+            field,
+            expected: expected.to_owned(),
+            found: found.to_owned(),
+        }
+    }
+}
+
 /// Computes the EIP-712 signing hash for an Agent struct with the given connection ID.
 ///
 /// This is used for RMP-based actions where the signature is over an Agent wrapper
@@ -31,7 +133,7 @@ use crate::hypercore::{
 pub fn agent_signing_hash(chain: Chain, connection_id: B256) -> B256 {
     use alloy::sol_types::SolStruct;
     let agent = solidity::Agent {
-        source: if chain.is_mainnet() { "a" } else { "b" }.to_string(),
+        source: chain.signing_context().agent_source().to_owned(),
         connectionId: connection_id,
     };
     agent.eip712_signing_hash(&CORE_MAINNET_EIP712_DOMAIN)
@@ -47,7 +149,7 @@ pub async fn sign_l1_action<S: Signer + Send + Sync>(
     let sig = signer
         .sign_typed_data(
             &solidity::Agent {
-                source: if chain.is_mainnet() { "a" } else { "b" }.to_string(),
+                source: chain.signing_context().agent_source().to_owned(),
                 connectionId: connection_id,
             },
             &CORE_MAINNET_EIP712_DOMAIN,
@@ -79,7 +181,7 @@ pub fn multisig_lead_msg_sync<S: SignerSync>(
     }
 
     let envelope = Envelope {
-        hyperliquid_chain: chain.to_string(),
+        hyperliquid_chain: chain.hyperliquid_chain().to_owned(),
         multi_sig_action_hash: multsig_hash.to_string(),
         nonce,
     };
@@ -119,7 +221,7 @@ pub async fn multisig_lead_msg<S: Signer + Send + Sync>(
     }
 
     let envelope = Envelope {
-        hyperliquid_chain: chain.to_string(),
+        hyperliquid_chain: chain.hyperliquid_chain().to_owned(),
         multi_sig_action_hash: multsig_hash.to_string(),
         nonce,
     };
@@ -222,7 +324,7 @@ mod tests {
     use alloy::signers::local::PrivateKeySigner;
 
     use super::*;
-    use crate::hypercore::{ARBITRUM_MAINNET_CHAIN_ID, types};
+    use crate::hypercore::{ARBITRUM_MAINNET_CHAIN_ID, ARBITRUM_TESTNET_CHAIN_ID, types};
 
     fn get_signer() -> PrivateKeySigner {
         let priv_key = "e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e";
@@ -361,4 +463,42 @@ mod tests {
             .unwrap();
         assert_eq!(recovered, expected_address);
     }
+
+    #[test]
+    fn test_signing_context_rejects_chain_mismatch() {
+        let signer = get_signer();
+        let usd_send = |signature_chain_id: &str, hyperliquid_chain| {
+            Action::UsdSend(types::api::UsdSendAction {
+                signature_chain_id: signature_chain_id.to_owned(),
+                hyperliquid_chain,
+                destination: Address::ZERO,
+                amount: rust_decimal::Decimal::ONE,
+                time: 1690393044548,
+            })
+        };
+
+        let ctx = Chain::Mainnet.signing_context();
+        assert!(ctx.validate(&usd_send("0xA4B1", Chain::Mainnet)).is_ok());
+        assert!(ctx.validate(&Action::Noop).is_ok());
+
+        let err = ctx
+            .validate(&usd_send(ARBITRUM_TESTNET_CHAIN_ID, Chain::Mainnet))
+            .unwrap_err();
+        assert_eq!(err.field, "signatureChainId");
+        assert_eq!(err.expected, ARBITRUM_MAINNET_CHAIN_ID);
+
+        let err = usd_send(ARBITRUM_MAINNET_CHAIN_ID, Chain::Testnet)
+            .sign_sync(&signer, 1, None, None, Chain::Mainnet)
+            .unwrap_err();
+        let err = err.downcast::<ChainMismatch>().unwrap();
+        assert_eq!(err.field, "hyperliquidChain");
+        assert_eq!(err.found, "Testnet");
+
+        // Local signs as testnet.
+        let ctx = Chain::Local.signing_context();
+        assert!(
+            ctx.validate(&usd_send(ARBITRUM_TESTNET_CHAIN_ID, Chain::Testnet))
+                .is_ok()
+        );
+    }
 }
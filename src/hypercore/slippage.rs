@@ -0,0 +1,232 @@
+//! Slippage models for market orders.
+//!
+//! A market order on Hyperliquid is an IOC limit order whose price is the worst
+//! acceptable fill. How far from the market to put that price depends on the
+//! strategy: a liquidation-avoidance close wants certainty, a rebalancer wants to cap
+//! its cost. A [`SlippageModel`] turns the current book into that price, so
+//! [`HttpClient::market_order`](crate::hypercore::HttpClient::market_order) and
+//! [`HttpClient::market_close`](crate::hypercore::HttpClient::market_close) can be
+//! reused with different aggressiveness.
+//!
+//! Three models are provided:
+//! - [`FixedBps`]: a fixed distance from the mid
+//! - [`DepthBased`]: the level the order would sweep to in the book, plus a buffer
+//! - [`VolatilityScaled`]: a distance growing with recent volatility
+//!
+//! # Example
+//!
+//! ```no_run
+//! use hypersdk::{
+//!     dec,
+//!     hypercore::{self, HttpClient, slippage::DepthBased},
+//! };
+//!
+//! // Price market orders at the level they sweep to, plus 10 bps.
+//! let client = HttpClient::builder(hypercore::Chain::Mainnet)
+//!     .slippage(DepthBased::new(dec!(10)))
+//!     .build();
+//! ```
+
+use rust_decimal::{Decimal, MathematicalOps, dec};
+
+use super::types::{Candle, L2Book};
+
+/// Basis points in one.
+const BPS: Decimal = dec!(10000);
+
+/// What a [`SlippageModel`] prices an order from.
+#[derive(Debug, Clone, Copy)]
+pub struct SlippageContext<'a> {
+    /// `true` for a buy
+    pub is_buy: bool,
+    /// Order size in base units
+    pub size: Decimal,
+    /// Current book of the market
+    pub book: &'a L2Book,
+}
+
+impl SlippageContext<'_> {
+    /// Returns `reference` moved `bps` basis points against the order.
+    #[must_use]
+    pub fn worse_by(&self, reference: Decimal, bps: Decimal) -> Decimal {
+        let offset = reference * bps / BPS;
+        if self.is_buy {
+            reference + offset
+        } else {
+            reference - offset
+        }
+    }
+}
+
+/// Prices the worst acceptable fill of a market order.
+pub trait SlippageModel: std::fmt::Debug + Send + Sync {
+    /// Returns the limit price for the order, or `None` if the book can't price it
+    /// (e.g. it is empty on one side).
+    ///
+    /// The price doesn't need to be on a valid tick; callers round it.
+    fn limit_px(&self, ctx: &SlippageContext<'_>) -> Option<Decimal>;
+}
+
+/// A fixed distance from the mid, in basis points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedBps(pub Decimal);
+
+impl FixedBps {
+    /// 5% from the mid, as in the official Python SDK.
+    pub const DEFAULT: Self = Self(dec!(500));
+}
+
+impl Default for FixedBps {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl SlippageModel for FixedBps {
+    fn limit_px(&self, ctx: &SlippageContext<'_>) -> Option<Decimal> {
+        Some(ctx.worse_by(ctx.book.mid()?, self.0))
+    }
+}
+
+/// The price of the deepest level the order would fill against, plus a buffer.
+///
+/// Returns no price when the visible book can't fill the whole order, so an order
+/// larger than the market can take is refused rather than priced blindly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DepthBased {
+    /// Distance past the sweep price, in basis points
+    pub buffer_bps: Decimal,
+}
+
+impl DepthBased {
+    /// Creates a model adding `buffer_bps` past the sweep price.
+    #[must_use]
+    pub fn new(buffer_bps: Decimal) -> Self {
+        Self { buffer_bps }
+    }
+}
+
+impl SlippageModel for DepthBased {
+    fn limit_px(&self, ctx: &SlippageContext<'_>) -> Option<Decimal> {
+        let levels = if ctx.is_buy {
+            ctx.book.asks()
+        } else {
+            ctx.book.bids()
+        };
+        let mut filled = Decimal::ZERO;
+        let sweep = levels.iter().find(|level| {
+            filled += level.sz;
+            filled >= ctx.size
+        })?;
+        Some(ctx.worse_by(sweep.px, self.buffer_bps))
+    }
+}
+
+/// A distance from the mid of `base_bps` plus `multiplier` standard deviations of
+/// recent returns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VolatilityScaled {
+    /// Distance at zero volatility, in basis points
+    pub base_bps: Decimal,
+    /// Standard deviations of returns to add
+    pub multiplier: Decimal,
+    /// Standard deviation of returns per period, as a fraction
+    pub volatility: Decimal,
+}
+
+impl VolatilityScaled {
+    /// Creates a model with the volatility of close-to-close returns in `candles`.
+    ///
+    /// Use candles of an interval close to the expected time to fill; with fewer than
+    /// two candles the volatility is zero.
+    #[must_use]
+    pub fn from_candles(base_bps: Decimal, multiplier: Decimal, candles: &[Candle]) -> Self {
+        Self {
+            base_bps,
+            multiplier,
+            volatility: volatility(candles),
+        }
+    }
+
+    /// Returns the distance from the mid, in basis points.
+    #[must_use]
+    pub fn bps(&self) -> Decimal {
+        self.base_bps + self.multiplier * self.volatility * BPS
+    }
+}
+
+impl SlippageModel for VolatilityScaled {
+    fn limit_px(&self, ctx: &SlippageContext<'_>) -> Option<Decimal> {
+        Some(ctx.worse_by(ctx.book.mid()?, self.bps()))
+    }
+}
+
+/// Standard deviation of close-to-close returns.
+fn volatility(candles: &[Candle]) -> Decimal {
+    let returns: Vec<Decimal> = candles
+        .windows(2)
+        .filter(|pair| !pair[0].close.is_zero())
+        .map(|pair| (pair[1].close - pair[0].close) / pair[0].close)
+        .collect();
+    if returns.len() < 2 {
+        return Decimal::ZERO;
+    }
+    let n = Decimal::from(returns.len());
+    let mean = returns.iter().sum::<Decimal>() / n;
+    let variance = returns
+        .iter()
+        .map(|r| (r - mean) * (r - mean))
+        .sum::<Decimal>()
+        / (n - Decimal::ONE);
+    variance.sqrt().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hypercore::types::BookLevel;
+
+    fn book() -> L2Book {
+        let level = |px, sz| BookLevel { px, sz, n: 1 };
+        L2Book {
+            coin: "BTC".into(),
+            time: 0,
+            snapshot: true,
+            levels: [
+                vec![level(dec!(99), dec!(1)), level(dec!(98), dec!(2))],
+                vec![level(dec!(101), dec!(1)), level(dec!(102), dec!(2))],
+            ],
+        }
+    }
+
+    #[test]
+    fn test_slippage_models() {
+        let book = book();
+        let buy = |size| SlippageContext {
+            is_buy: true,
+            size,
+            book: &book,
+        };
+        let sell = SlippageContext {
+            is_buy: false,
+            ..buy(dec!(1))
+        };
+
+        assert_eq!(FixedBps(dec!(100)).limit_px(&buy(dec!(1))), Some(dec!(101)));
+        assert_eq!(FixedBps(dec!(100)).limit_px(&sell), Some(dec!(99)));
+
+        let depth = DepthBased::new(dec!(0));
+        assert_eq!(depth.limit_px(&buy(dec!(0.5))), Some(dec!(101)));
+        assert_eq!(depth.limit_px(&buy(dec!(2))), Some(dec!(102)));
+        assert_eq!(depth.limit_px(&buy(dec!(4))), None);
+        assert_eq!(depth.limit_px(&sell), Some(dec!(99)));
+
+        let calm = VolatilityScaled {
+            base_bps: dec!(10),
+            multiplier: dec!(2),
+            volatility: dec!(0.01),
+        };
+        assert_eq!(calm.bps(), dec!(210));
+        assert_eq!(calm.limit_px(&buy(dec!(1))), Some(dec!(102.1)));
+    }
+}
@@ -0,0 +1,479 @@
+//! Strategy runtime.
+//!
+//! Every trading bot needs the same plumbing: subscribe to market data and the
+//! account's fills, keep positions up to date, turn decisions into signed orders, and
+//! shut down cleanly. A [`Runtime`] does that plumbing and calls a [`Strategy`] at
+//! each step of its life, so a bot is only its decision logic.
+//!
+//! Hooks are synchronous and queue their orders on the [`Context`]; the runtime
+//! sends them once the hook returns. A hook returning an error stops the runtime, as
+//! does the [`KillSwitch`], after which [`Strategy::on_stop`] runs and the account's
+//! open orders are cancelled.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use hypersdk::hypercore::{
+//!     self, PrivateKeySigner,
+//!     strategy::{Context, Runtime, Strategy},
+//!     types::*,
+//! };
+//!
+//! /// Logs the BTC book and trades nothing.
+//! struct Watcher;
+//!
+//! impl Strategy for Watcher {
+//!     fn subscriptions(&self) -> Vec<Subscription> {
+//!         vec![Subscription::L2Book { coin: "BTC".into() }]
+//!     }
+//!
+//!     fn on_book(&mut self, _ctx: &mut Context, book: &L2Book) -> anyhow::Result<()> {
+//!         println!("{} mid {:?}", book.coin, book.mid());
+//!         Ok(())
+//!     }
+//! }
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let signer: PrivateKeySigner = "your_key".parse()?;
+//! let runtime = Runtime::new(hypercore::mainnet(), signer);
+//!
+//! let kill = runtime.kill_switch();
+//! tokio::spawn(async move {
+//!     let _ = tokio::signal::ctrl_c().await;
+//!     kill.trigger();
+//! });
+//!
+//! runtime.run(&mut Watcher).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{collections::HashMap, time::Duration};
+
+use alloy::{
+    primitives::Address,
+    signers::{Signer, SignerSync},
+};
+use anyhow::Result;
+use chrono::Utc;
+use futures::StreamExt;
+use rust_decimal::Decimal;
+use tokio_util::sync::CancellationToken;
+
+use super::{
+    HttpClient, NonceHandler, WebSocket,
+    types::{
+        AssetContext, BatchCancel, BatchOrder, Cancel, Fill, Incoming, L2Book, Side, Subscription,
+        Trade,
+    },
+    ws::{Event, FillDedup},
+};
+
+/// Default interval between [`Strategy::on_timer`] calls.
+pub const DEFAULT_TIMER: Duration = Duration::from_secs(1);
+
+/// Trading logic driven by a [`Runtime`].
+///
+/// Every hook has a default that does nothing, so a strategy implements only the
+/// ones it needs.
+pub trait Strategy: Send {
+    /// Market data feeds the strategy needs.
+    ///
+    /// The runtime subscribes to the account's fills on its own.
+    fn subscriptions(&self) -> Vec<Subscription>;
+
+    /// Called once, after positions are loaded and before any market data.
+    fn on_start(&mut self, ctx: &mut Context) -> Result<()> {
+        let _ = ctx;
+        Ok(())
+    }
+
+    /// Called on every order book update.
+    fn on_book(&mut self, ctx: &mut Context, book: &L2Book) -> Result<()> {
+        let _ = (ctx, book);
+        Ok(())
+    }
+
+    /// Called on every public trade.
+    fn on_trade(&mut self, ctx: &mut Context, trade: &Trade) -> Result<()> {
+        let _ = (ctx, trade);
+        Ok(())
+    }
+
+    /// Called on every perp asset context update from
+    /// [`Subscription::ActiveAssetCtx`]: mark and oracle price, funding and open
+    /// interest.
+    fn on_asset_ctx(&mut self, ctx: &mut Context, coin: &str, asset: &AssetContext) -> Result<()> {
+        let _ = (ctx, coin, asset);
+        Ok(())
+    }
+
+    /// Called on every fill of the account, after its position is updated.
+    ///
+    /// Fills of other accounts the strategy subscribed to go to
+    /// [`on_message`](Self::on_message).
+    fn on_fill(&mut self, ctx: &mut Context, fill: &Fill) -> Result<()> {
+        let _ = (ctx, fill);
+        Ok(())
+    }
+
+    /// Called on every other message of the subscribed feeds.
+    fn on_message(&mut self, ctx: &mut Context, msg: &Incoming) -> Result<()> {
+        let _ = (ctx, msg);
+        Ok(())
+    }
+
+    /// Called at the runtime's timer interval.
+    fn on_timer(&mut self, ctx: &mut Context) -> Result<()> {
+        let _ = ctx;
+        Ok(())
+    }
+
+    /// Called once when the runtime stops, before open orders are cancelled.
+    fn on_stop(&mut self, ctx: &mut Context) -> Result<()> {
+        let _ = ctx;
+        Ok(())
+    }
+}
+
+/// A request queued by a strategy hook.
+#[derive(Debug, Clone)]
+pub(super) enum Command {
+    Place(BatchOrder),
+    Cancel(BatchCancel),
+}
+
+/// What a hook sees of the runtime, and where it queues orders.
+#[derive(Debug, Default)]
+pub struct Context {
+    pub(super) user: Address,
+    pub(super) now: u64,
+    positions: HashMap<String, Decimal>,
+    pub(super) commands: Vec<Command>,
+    pub(super) stopping: bool,
+}
+
+impl Context {
+    /// Returns the account the strategy trades for.
+    #[must_use]
+    pub fn user(&self) -> Address {
+        self.user
+    }
+
+    /// Returns the time the hook was called, in milliseconds.
+    #[must_use]
+    pub fn now(&self) -> u64 {
+        self.now
+    }
+
+    /// Returns the signed position on `coin`, zero when flat.
+    #[must_use]
+    pub fn position(&self, coin: &str) -> Decimal {
+        self.positions.get(coin).copied().unwrap_or_default()
+    }
+
+    /// Returns the non-zero positions.
+    pub fn positions(&self) -> impl Iterator<Item = (&str, Decimal)> {
+        self.positions
+            .iter()
+            .filter(|(_, szi)| !szi.is_zero())
+            .map(|(coin, szi)| (coin.as_str(), *szi))
+    }
+
+    /// Queues orders to place once the hook returns.
+    pub fn place(&mut self, batch: BatchOrder) {
+        self.commands.push(Command::Place(batch));
+    }
+
+    /// Queues cancels to send once the hook returns.
+    pub fn cancel(&mut self, batch: BatchCancel) {
+        self.commands.push(Command::Cancel(batch));
+    }
+
+    /// Stops the runtime once the hook returns.
+    pub fn stop(&mut self) {
+        self.stopping = true;
+    }
+
+    pub(super) fn apply_fill(&mut self, fill: &Fill) {
+        let delta = match fill.side {
+            Side::Bid => fill.sz,
+            Side::Ask => -fill.sz,
+        };
+        *self.positions.entry(fill.coin.clone()).or_default() += delta;
+    }
+}
+
+/// Stops a running [`Runtime`] from another task.
+#[derive(Debug, Clone, Default)]
+pub struct KillSwitch(CancellationToken);
+
+impl KillSwitch {
+    /// Stops the runtime: no new hooks run except [`Strategy::on_stop`], and the
+    /// account's open orders are cancelled.
+    pub fn trigger(&self) {
+        self.0.cancel();
+    }
+
+    /// Returns whether the switch was triggered.
+    #[must_use]
+    pub fn is_triggered(&self) -> bool {
+        self.0.is_cancelled()
+    }
+}
+
+/// Runs a [`Strategy`] against live feeds and the exchange.
+pub struct Runtime<S> {
+    client: HttpClient,
+    ws: WebSocket,
+    signer: S,
+    nonces: NonceHandler,
+    vault_address: Option<Address>,
+    timer: Duration,
+    kill: KillSwitch,
+}
+
+impl<S: Signer + SignerSync + Send + Sync> Runtime<S> {
+    /// Creates a runtime trading with `signer` through `client`.
+    ///
+    /// The feeds use [`HttpClient::websocket`].
+    pub fn new(client: HttpClient, signer: S) -> Self {
+        Self {
+            ws: client.websocket(),
+            client,
+            signer,
+            nonces: NonceHandler::default(),
+            vault_address: None,
+            timer: DEFAULT_TIMER,
+            kill: KillSwitch::default(),
+        }
+    }
+
+    /// Trades for a vault or subaccount instead of the signer's account.
+    #[must_use]
+    pub fn vault_address(mut self, vault_address: Address) -> Self {
+        self.vault_address = Some(vault_address);
+        self
+    }
+
+    /// Sets the interval between [`Strategy::on_timer`] calls.
+    #[must_use]
+    pub fn timer(mut self, timer: Duration) -> Self {
+        self.timer = timer;
+        self
+    }
+
+    /// Returns a handle stopping the runtime.
+    #[must_use]
+    pub fn kill_switch(&self) -> KillSwitch {
+        self.kill.clone()
+    }
+
+    /// Runs `strategy` until it stops, fails, or the kill switch is triggered.
+    ///
+    /// Returns the error of the hook that failed, if any. Orders that fail to place
+    /// are logged and don't stop the runtime.
+    pub async fn run(mut self, strategy: &mut impl Strategy) -> Result<()> {
+        let user = self.vault_address.unwrap_or_else(|| self.signer.address());
+        let state = self.client.clearinghouse_state(user, None).await?;
+        let mut ctx = Context {
+            user,
+            now: now_ms(),
+            positions: state
+                .asset_positions
+                .into_iter()
+                .map(|asset| (asset.position.coin, asset.position.szi))
+                .collect(),
+            ..Context::default()
+        };
+
+        for subscription in strategy.subscriptions() {
+            self.ws.subscribe(subscription);
+        }
+        self.ws.subscribe(Subscription::UserFills { user });
+
+        let result = self.event_loop(strategy, &mut ctx).await;
+
+        ctx.now = now_ms();
+        let stopped = strategy.on_stop(&mut ctx);
+        self.flush(&mut ctx).await;
+        self.cancel_all(user).await;
+        result.and(stopped)
+    }
+
+    async fn event_loop(&mut self, strategy: &mut impl Strategy, ctx: &mut Context) -> Result<()> {
+        ctx.now = now_ms();
+        strategy.on_start(ctx)?;
+        self.flush(ctx).await;
+
+        let mut timer = tokio::time::interval(self.timer);
+        let mut dedup = FillDedup::default();
+        let mut synced = false;
+        while !ctx.stopping {
+            let event = tokio::select! {
+                _ = self.kill.0.cancelled() => break,
+                _ = timer.tick() => None,
+                event = self.ws.next() => match event {
+                    Some(event) => Some(event),
+                    None => break,
+                },
+            };
+            ctx.now = now_ms();
+            match event {
+                None => strategy.on_timer(ctx)?,
+                Some(Event::Message(msg)) => match &msg {
+                    Incoming::L2Book(book) => strategy.on_book(ctx, book)?,
+                    Incoming::Trades(trades) => {
+                        for trade in trades {
+                            strategy.on_trade(ctx, trade)?;
+                        }
+                    }
+                    Incoming::ActiveAssetCtx { coin, ctx: asset } => {
+                        strategy.on_asset_ctx(ctx, coin, asset)?;
+                    }
+                    Incoming::UserFills {
+                        is_snapshot,
+                        user,
+                        fills,
+                    } if *user == ctx.user => {
+                        // The first snapshot predates the loaded positions; later ones
+                        // replay fills after a reconnect, mostly already seen live.
+                        let replayed = *is_snapshot && !synced;
+                        synced = true;
+                        for fill in fills {
+                            if dedup.insert(fill) && !replayed {
+                                ctx.apply_fill(fill);
+                                strategy.on_fill(ctx, fill)?;
+                            }
+                        }
+                    }
+                    _ => strategy.on_message(ctx, &msg)?,
+                },
+                Some(_) => continue,
+            }
+            self.flush(ctx).await;
+        }
+        Ok(())
+    }
+
+    /// Sends the commands queued by the last hook.
+    async fn flush(&mut self, ctx: &mut Context) {
+        for command in std::mem::take(&mut ctx.commands) {
+            let nonce = self.nonces.next();
+            let result = match command {
+                Command::Place(batch) => self
+                    .client
+                    .place(&self.signer, batch, nonce, self.vault_address, None)
+                    .await
+                    .map(drop)
+                    .map_err(anyhow::Error::from),
+                Command::Cancel(batch) => self
+                    .client
+                    .cancel(&self.signer, batch, nonce, self.vault_address, None)
+                    .await
+                    .map(drop)
+                    .map_err(anyhow::Error::from),
+            };
+            if let Err(err) = result {
+                log::warn!("strategy order failed: {err:?}");
+            }
+        }
+    }
+
+    /// Cancels every open order of the account.
+    async fn cancel_all(&self, user: Address) {
+        let fetched = futures::try_join!(
+            self.client.open_orders(user, None),
+            self.client.perps(),
+            self.client.spot()
+        );
+        let (orders, perps, spots) = match fetched {
+            Ok(fetched) => fetched,
+            Err(err) => {
+                log::warn!("failed to list open orders on stop: {err:?}");
+                return;
+            }
+        };
+        let indices: HashMap<&str, usize> = perps
+            .iter()
+            .map(|market| (market.name.as_str(), market.index))
+            .chain(
+                spots
+                    .iter()
+                    .map(|market| (market.name.as_str(), market.index)),
+            )
+            .collect();
+
+        let cancels: Vec<_> = orders
+            .iter()
+            .filter_map(|order| {
+                let asset = indices.get(order.coin.as_str());
+                if asset.is_none() {
+                    log::warn!("unknown asset {} on stop", order.coin);
+                }
+                Some(Cancel {
+                    asset: *asset?,
+                    oid: order.oid,
+                })
+            })
+            .collect();
+        if cancels.is_empty() {
+            return;
+        }
+        let nonce = self.nonces.next();
+        if let Err(err) = self
+            .client
+            .cancel(
+                &self.signer,
+                BatchCancel { cancels },
+                nonce,
+                self.vault_address,
+                None,
+            )
+            .await
+        {
+            log::warn!("failed to cancel open orders on stop: {err:?}");
+        }
+    }
+}
+
+fn now_ms() -> u64 {
+    Utc::now().timestamp_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_context_positions() {
+        let fill = |side, sz: i64| -> Fill {
+            serde_json::from_value(serde_json::json!({
+                "coin": "BTC",
+                "px": "100",
+                "sz": sz.to_string(),
+                "side": side,
+                "time": 0,
+                "startPosition": "0",
+                "dir": "Open Long",
+                "closedPnl": "0",
+                "hash": "0x0",
+                "oid": 1,
+                "crossed": true,
+                "fee": "0",
+                "tid": 1,
+                "feeToken": "USDC",
+            }))
+            .unwrap()
+        };
+
+        let mut ctx = Context::default();
+        ctx.apply_fill(&fill("B", 3));
+        ctx.apply_fill(&fill("A", 1));
+        assert_eq!(ctx.position("BTC"), Decimal::TWO);
+        assert_eq!(ctx.position("ETH"), Decimal::ZERO);
+
+        ctx.apply_fill(&fill("A", 2));
+        assert_eq!(ctx.positions().count(), 0);
+    }
+}
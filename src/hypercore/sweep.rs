@@ -0,0 +1,396 @@
+//! Parameter sweeps and walk-forward analysis over backtests.
+//!
+//! A [`Sweep`] runs the same [`Backtest`] once per set of strategy parameters,
+//! spreading the runs over threads, and summarizes each run with [`Metrics`]. Since
+//! every run is deterministic, a sweep gives the same [`SweepReport`] however many
+//! threads it uses.
+//!
+//! [`Sweep::walk_forward`] guards against overfitting: it picks the best parameters
+//! on a training window, scores them on the window that follows, and slides forward
+//! through the data.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use hypersdk::hypercore::{
+//!     backtest::{Backtest, BacktestConfig, Tick},
+//!     strategy::Strategy,
+//!     sweep::Sweep,
+//! };
+//! use rust_decimal::{Decimal, dec};
+//!
+//! # fn example<S: Strategy>(
+//! #     ticks: Vec<Tick>,
+//! #     grid: impl Fn(Decimal, u32) -> S + Sync,
+//! # ) -> anyhow::Result<()> {
+//! let backtest = Backtest::new(BacktestConfig::default()).asset(0, "BTC");
+//! let params = [dec!(5), dec!(10), dec!(20)]
+//!     .into_iter()
+//!     .flat_map(|spacing| [3, 5].map(|levels| (spacing, levels)));
+//!
+//! let report = Sweep::new(backtest, params)
+//!     .run(&ticks, |(spacing, levels)| grid(*spacing, *levels))?;
+//! report.write_csv("sweep.csv")?;
+//! if let Some(best) = report.best_by(|metrics| metrics.pnl) {
+//!     println!("best {:?}: {:?}", best.params, best.metrics);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    fmt::Write as _,
+    num::NonZeroUsize,
+    path::Path,
+    sync::{
+        Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+
+use anyhow::{Context, Result};
+use rust_decimal::{Decimal, MathematicalOps};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    backtest::{Backtest, BacktestReport, RunManifest, Tick},
+    strategy::Strategy,
+};
+
+/// Summary of a backtest run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Metrics {
+    /// Final equity: realized and unrealized PnL, net of fees
+    pub pnl: Decimal,
+    /// Mean over standard deviation of the equity changes between samples, not
+    /// annualized; `None` with fewer than 3 samples or a flat curve
+    pub sharpe: Option<Decimal>,
+    /// Largest fall of equity from a previous peak
+    pub max_drawdown: Decimal,
+    /// Share of submitted orders that filled at least partially; `None` without orders
+    pub fill_ratio: Option<Decimal>,
+    /// Number of fills
+    pub fills: usize,
+    /// Number of orders submitted
+    pub orders: u64,
+}
+
+impl Metrics {
+    /// Computes the metrics of `report`.
+    #[must_use]
+    pub fn new(report: &BacktestReport) -> Self {
+        let changes: Vec<Decimal> = report
+            .equity
+            .windows(2)
+            .map(|pair| pair[1].1 - pair[0].1)
+            .collect();
+
+        let mut peak = Decimal::ZERO;
+        let mut max_drawdown = Decimal::ZERO;
+        for (_, equity) in &report.equity {
+            peak = peak.max(*equity);
+            max_drawdown = max_drawdown.max(peak - equity);
+        }
+
+        let mut filled: Vec<u64> = report.fills.iter().map(|fill| fill.oid).collect();
+        filled.sort_unstable();
+        filled.dedup();
+        let fill_ratio =
+            (report.orders > 0).then(|| Decimal::from(filled.len()) / Decimal::from(report.orders));
+
+        Self {
+            pnl: report.pnl(),
+            sharpe: sharpe(&changes),
+            max_drawdown,
+            fill_ratio,
+            fills: report.fills.len(),
+            orders: report.orders,
+        }
+    }
+}
+
+fn sharpe(changes: &[Decimal]) -> Option<Decimal> {
+    if changes.len() < 2 {
+        return None;
+    }
+    let n = Decimal::from(changes.len());
+    let mean = changes.iter().sum::<Decimal>() / n;
+    let variance = changes
+        .iter()
+        .map(|change| (change - mean) * (change - mean))
+        .sum::<Decimal>()
+        / (n - Decimal::ONE);
+    let deviation = variance.sqrt()?;
+    (!deviation.is_zero()).then(|| mean / deviation)
+}
+
+/// One run of a sweep.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SweepRun<P> {
+    /// Strategy parameters of the run
+    pub params: P,
+    /// Summary of the run
+    pub metrics: Metrics,
+    /// What the run was made of
+    pub manifest: RunManifest,
+}
+
+/// Runs of a sweep, in the order of the parameter grid.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SweepReport<P> {
+    /// One run per set of parameters
+    pub runs: Vec<SweepRun<P>>,
+}
+
+impl<P: Serialize> SweepReport<P> {
+    /// Returns the run with the highest `key`, the first one on ties.
+    pub fn best_by(&self, key: impl Fn(&Metrics) -> Decimal) -> Option<&SweepRun<P>> {
+        self.runs.iter().rev().max_by_key(|run| key(&run.metrics))
+    }
+
+    /// Formats the runs as CSV, one row per run with its parameters as JSON.
+    #[must_use]
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "run,params,pnl,sharpe,max_drawdown,fill_ratio,fills,orders,config_hash\n",
+        );
+        let optional = |value: Option<Decimal>| value.map(|v| v.to_string()).unwrap_or_default();
+        for (i, run) in self.runs.iter().enumerate() {
+            let params = serde_json::to_string(&run.params).unwrap_or_default();
+            let _ = writeln!(
+                csv,
+                "{i},\"{}\",{},{},{},{},{},{},{}",
+                params.replace('"', "\"\""),
+                run.metrics.pnl,
+                optional(run.metrics.sharpe),
+                run.metrics.max_drawdown,
+                optional(run.metrics.fill_ratio),
+                run.metrics.fills,
+                run.metrics.orders,
+                run.manifest.config_hash,
+            );
+        }
+        csv
+    }
+
+    /// Writes [`to_csv`](Self::to_csv) to the file at `path`.
+    pub fn write_csv(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        std::fs::write(path, self.to_csv())
+            .with_context(|| format!("unable to write {}", path.display()))
+    }
+}
+
+/// One step of a walk-forward analysis.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalkForwardStep<P> {
+    /// Training window `[start, end)`, in milliseconds
+    pub train: (u64, u64),
+    /// Test window `[start, end)`, in milliseconds
+    pub test: (u64, u64),
+    /// Parameters that scored best on the training window
+    pub params: P,
+    /// Metrics of those parameters on the training window
+    pub in_sample: Metrics,
+    /// Metrics of those parameters on the test window
+    pub out_of_sample: Metrics,
+}
+
+/// Runs a backtest over a grid of strategy parameters.
+#[derive(Debug, Clone)]
+pub struct Sweep<P> {
+    backtest: Backtest,
+    grid: Vec<P>,
+    threads: usize,
+}
+
+impl<P: Clone + Serialize + Send + Sync> Sweep<P> {
+    /// Creates a sweep running `backtest` once per set of parameters in `grid`.
+    ///
+    /// Each set of parameters is part of its run's config hash.
+    #[must_use]
+    pub fn new(backtest: Backtest, grid: impl IntoIterator<Item = P>) -> Self {
+        Self {
+            backtest,
+            grid: grid.into_iter().collect(),
+            threads: std::thread::available_parallelism().map_or(1, NonZeroUsize::get),
+        }
+    }
+
+    /// Sets the number of threads running backtests, the number of CPUs by default.
+    #[must_use]
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Runs the strategy built by `build` for every set of parameters over `ticks`.
+    ///
+    /// Fails with the error of the first failing run in grid order.
+    pub fn run<S, F>(&self, ticks: &[Tick], build: F) -> Result<SweepReport<P>>
+    where
+        S: Strategy,
+        F: Fn(&P) -> S + Sync,
+    {
+        let next = AtomicUsize::new(0);
+        let results = Mutex::new((0..self.grid.len()).map(|_| None).collect::<Vec<_>>());
+        std::thread::scope(|scope| {
+            for _ in 0..self.threads.min(self.grid.len()) {
+                scope.spawn(|| {
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(params) = self.grid.get(i) else {
+                            break;
+                        };
+                        let result = self.run_one(params, ticks, &build);
+                        results.lock().unwrap_or_else(|err| err.into_inner())[i] = Some(result);
+                    }
+                });
+            }
+        });
+
+        let runs = results
+            .into_inner()
+            .unwrap_or_else(|err| err.into_inner())
+            .into_iter()
+            .enumerate()
+            .map(|(i, result)| {
+                result
+                    .expect("every run completes")
+                    .with_context(|| format!("sweep run {i}"))
+            })
+            .collect::<Result<_>>()?;
+        Ok(SweepReport { runs })
+    }
+
+    /// Walks forward through `ticks`: sweeps a `train_ms` window, scores the
+    /// parameters with the highest `key` on the `test_ms` window that follows, then
+    /// slides both windows by `test_ms`.
+    pub fn walk_forward<S, F>(
+        &self,
+        ticks: &[Tick],
+        train_ms: u64,
+        test_ms: u64,
+        key: impl Fn(&Metrics) -> Decimal,
+        build: F,
+    ) -> Result<Vec<WalkForwardStep<P>>>
+    where
+        S: Strategy,
+        F: Fn(&P) -> S + Sync,
+    {
+        anyhow::ensure!(test_ms > 0, "walk-forward test window must not be empty");
+        let (Some(first), Some(last)) = (ticks.first(), ticks.last()) else {
+            return Ok(vec![]);
+        };
+        let window = |start: u64, end: u64| {
+            let from = ticks.partition_point(|tick| tick.time < start);
+            let to = ticks.partition_point(|tick| tick.time < end);
+            &ticks[from..to]
+        };
+
+        let mut steps = vec![];
+        let mut start = first.time;
+        while start + train_ms + test_ms <= last.time + 1 {
+            let train = (start, start + train_ms);
+            let test = (train.1, train.1 + test_ms);
+            let report = self.run(window(train.0, train.1), &build)?;
+            let Some(best) = report.best_by(&key) else {
+                break;
+            };
+            let out_of_sample = self.run_one(&best.params, window(test.0, test.1), &build)?;
+            steps.push(WalkForwardStep {
+                train,
+                test,
+                params: best.params.clone(),
+                in_sample: best.metrics.clone(),
+                out_of_sample: out_of_sample.metrics,
+            });
+            start += test_ms;
+        }
+        Ok(steps)
+    }
+
+    fn run_one<S, F>(&self, params: &P, ticks: &[Tick], build: &F) -> Result<SweepRun<P>>
+    where
+        S: Strategy,
+        F: Fn(&P) -> S,
+    {
+        let backtest = self.backtest.clone().strategy_config(params);
+        let report = backtest.run(&mut build(params), ticks.iter().cloned())?;
+        Ok(SweepRun {
+            params: params.clone(),
+            metrics: Metrics::new(&report),
+            manifest: report.manifest,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::dec;
+
+    use super::*;
+    use crate::hypercore::backtest::BacktestConfig;
+
+    #[test]
+    fn test_metrics() {
+        let report = BacktestReport {
+            manifest: RunManifest {
+                start: 0,
+                end: 3,
+                ticks: 4,
+                seed: 0,
+                config_hash: Default::default(),
+            },
+            orders: 4,
+            fills: vec![],
+            realized_pnl: Decimal::ZERO,
+            fees: Decimal::ZERO,
+            positions: Default::default(),
+            equity: vec![(0, dec!(0)), (1, dec!(3)), (2, dec!(1)), (3, dec!(4))],
+        };
+        let metrics = Metrics::new(&report);
+        assert_eq!(metrics.pnl, dec!(4));
+        assert_eq!(metrics.max_drawdown, dec!(2));
+        assert_eq!(metrics.fill_ratio, Some(Decimal::ZERO));
+        // Changes 3, -2, 3: mean 4/3 over a deviation of sqrt(25/3).
+        let sharpe = metrics.sharpe.unwrap();
+        assert!((sharpe - dec!(0.4619)).abs() < dec!(0.0001));
+
+        let sweep = SweepReport {
+            runs: [dec!(1), dec!(4), dec!(4)]
+                .into_iter()
+                .enumerate()
+                .map(|(params, pnl)| SweepRun {
+                    params,
+                    metrics: Metrics {
+                        pnl,
+                        ..metrics.clone()
+                    },
+                    manifest: report.manifest.clone(),
+                })
+                .collect(),
+        };
+        assert_eq!(sweep.best_by(|metrics| metrics.pnl).unwrap().params, 1);
+        assert_eq!(sweep.to_csv().lines().count(), 4);
+
+        let backtest = Backtest::new(BacktestConfig::default());
+        let runs = Sweep::new(backtest, [1, 2, 3])
+            .threads(2)
+            .run(&[], |_| Idle)
+            .unwrap();
+        assert_eq!(runs.runs.len(), 3);
+        assert_ne!(
+            runs.runs[0].manifest.config_hash,
+            runs.runs[1].manifest.config_hash
+        );
+    }
+
+    struct Idle;
+
+    impl Strategy for Idle {
+        fn subscriptions(&self) -> Vec<crate::hypercore::types::Subscription> {
+            vec![]
+        }
+    }
+}
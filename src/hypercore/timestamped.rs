@@ -0,0 +1,160 @@
+//! Snapshots tagged with the time they were taken.
+//!
+//! A book or a margin snapshot is only useful while it is recent, and every
+//! consumer ends up carrying its own notion of "when did this arrive". A
+//! [`Timestamped`] wraps a snapshot with the server time it describes and the
+//! local time it was received, both in milliseconds, so freshness checks read the
+//! same everywhere. The `*_timestamped` methods of
+//! [`HttpClient`](super::HttpClient) return their snapshots wrapped.
+//!
+//! The server time comes from the payload when it has one (`l2Book`,
+//! `clearinghouseState`) and otherwise from the response's `Date` header, which
+//! has one-second resolution.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use hypersdk::hypercore;
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let client = hypercore::mainnet();
+//! let book = client.l2_book_timestamped("BTC".into(), None, None).await?;
+//!
+//! // Refuse to quote off a book more than 2 seconds old.
+//! let book = book.fresh(Duration::from_secs(2))?;
+//! println!("{} bid levels", book.bids().len());
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{ops::Deref, time::Duration};
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+/// A snapshot older than the caller accepts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("snapshot is {age:?} old, more than the allowed {max_age:?}")]
+pub struct Stale {
+    /// Age of the snapshot
+    pub age: Duration,
+    /// Largest age accepted
+    pub max_age: Duration,
+}
+
+/// A value with the server time it describes and the local time it was received.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Timestamped<T> {
+    /// The snapshot
+    pub value: T,
+    /// Server time of the snapshot in milliseconds
+    pub server_time: u64,
+    /// Local time the snapshot was received in milliseconds
+    pub received_at: u64,
+}
+
+impl<T> Timestamped<T> {
+    /// Wraps `value`, received now, describing the server state at `server_time`.
+    pub fn received(value: T, server_time: u64) -> Self {
+        Self {
+            value,
+            server_time,
+            received_at: now_ms(),
+        }
+    }
+
+    /// Returns the time elapsed since the server time of the snapshot.
+    ///
+    /// Measured against the local clock, so it includes any clock skew with the
+    /// server; see [`since_received`](Self::since_received) for a skew-free bound.
+    #[must_use]
+    pub fn age(&self) -> Duration {
+        self.age_at(now_ms())
+    }
+
+    /// Returns the time elapsed since the snapshot was received.
+    #[must_use]
+    pub fn since_received(&self) -> Duration {
+        Duration::from_millis(now_ms().saturating_sub(self.received_at))
+    }
+
+    /// Returns the snapshot if its [`age`](Self::age) is at most `max_age`.
+    pub fn fresh(&self, max_age: Duration) -> Result<&T, Stale> {
+        self.fresh_at(now_ms(), max_age)
+    }
+
+    /// Applies `f` to the value, keeping the timestamps.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Timestamped<U> {
+        Timestamped {
+            value: f(self.value),
+            server_time: self.server_time,
+            received_at: self.received_at,
+        }
+    }
+
+    /// Returns the value, dropping the timestamps.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    fn age_at(&self, now: u64) -> Duration {
+        Duration::from_millis(now.saturating_sub(self.server_time))
+    }
+
+    fn fresh_at(&self, now: u64, max_age: Duration) -> Result<&T, Stale> {
+        let age = self.age_at(now);
+        if age > max_age {
+            return Err(Stale { age, max_age });
+        }
+        Ok(&self.value)
+    }
+}
+
+impl<T> Deref for Timestamped<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+fn now_ms() -> u64 {
+    Utc::now().timestamp_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timestamped() {
+        let snapshot = Timestamped {
+            value: vec![1, 2],
+            server_time: 10_000,
+            received_at: 10_050,
+        };
+        assert_eq!(snapshot.len(), 2);
+        assert_eq!(snapshot.age_at(11_000), Duration::from_secs(1));
+        assert_eq!(snapshot.age_at(9_000), Duration::ZERO);
+        assert_eq!(
+            snapshot.fresh_at(11_000, Duration::from_secs(1)),
+            Ok(&vec![1, 2])
+        );
+        assert_eq!(
+            snapshot.fresh_at(12_000, Duration::from_secs(1)),
+            Err(Stale {
+                age: Duration::from_secs(2),
+                max_age: Duration::from_secs(1)
+            })
+        );
+
+        let json = serde_json::to_value(snapshot.clone().map(|value| value.len())).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "value": 2, "serverTime": 10_000, "receivedAt": 10_050 })
+        );
+    }
+}
@@ -0,0 +1,250 @@
+//! Client-side trailing stops.
+//!
+//! Hyperliquid has no native trailing stop. A [`TrailingStop`] follows the best bid
+//! (for a long) or best ask (for a short) from the BBO feed, keeps the most
+//! favourable price seen as its watermark, and triggers once the price retraces
+//! from it by the configured distance. Closing the position is then a reduce-only
+//! market order, e.g. [`HttpClient::market_close`](crate::hypercore::HttpClient::market_close).
+//!
+//! The stop serializes to JSON, so its watermark survives a restart: save it after
+//! every update and load it on startup instead of starting over from the current
+//! price.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use futures::StreamExt;
+//! use hypersdk::{
+//!     dec,
+//!     hypercore::{
+//!         self, NonceHandler, PrivateKeySigner,
+//!         trailing::{TrailDistance, TrailingStop},
+//!         types::*,
+//!         ws::Event,
+//!     },
+//! };
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let client = hypercore::mainnet();
+//! let signer: PrivateKeySigner = "your_key".parse()?;
+//! let nonces = NonceHandler::default();
+//! let perps = client.perps().await?;
+//! let eth = perps.iter().find(|m| m.name == "ETH").expect("ETH");
+//!
+//! let path = std::path::Path::new("eth-trailing-stop.json");
+//! let mut stop = match TrailingStop::load(path) {
+//!     Ok(stop) => stop,
+//!     Err(_) => TrailingStop::new("ETH", true, TrailDistance::Percent(dec!(0.02))),
+//! };
+//!
+//! let mut ws = hypercore::mainnet_ws();
+//! ws.subscribe(Subscription::Bbo { coin: "ETH".into() });
+//! while let Some(event) = ws.next().await {
+//!     let Event::Message(Incoming::Bbo(bbo)) = event else { continue };
+//!     let triggered = stop.observe(&bbo);
+//!     stop.save(path)?;
+//!     if triggered.is_some() {
+//!         client
+//!             .market_close(&signer, eth, None, nonces.next(), None, None, None)
+//!             .await?;
+//!         break;
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::path::Path;
+
+use anyhow::Result;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use super::types::Bbo;
+
+/// How far the price may retrace from the watermark before the stop triggers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TrailDistance {
+    /// A fixed price distance
+    Absolute(Decimal),
+    /// A fraction of the watermark (`0.02` for 2%)
+    Percent(Decimal),
+}
+
+impl TrailDistance {
+    fn at(&self, watermark: Decimal) -> Decimal {
+        match self {
+            Self::Absolute(distance) => *distance,
+            Self::Percent(fraction) => watermark * fraction,
+        }
+    }
+}
+
+/// A stop trailing the market by a fixed distance.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrailingStop {
+    coin: String,
+    is_long: bool,
+    distance: TrailDistance,
+    #[serde(default)]
+    activation_px: Option<Decimal>,
+    #[serde(default)]
+    watermark: Option<Decimal>,
+    #[serde(default)]
+    triggered_at: Option<Decimal>,
+}
+
+impl TrailingStop {
+    /// Creates a stop protecting a long (`is_long`) or short position on `coin`.
+    #[must_use]
+    pub fn new(coin: impl Into<String>, is_long: bool, distance: TrailDistance) -> Self {
+        Self {
+            coin: coin.into(),
+            is_long,
+            distance,
+            activation_px: None,
+            watermark: None,
+            triggered_at: None,
+        }
+    }
+
+    /// Only starts trailing once the price reaches `px`, e.g. to lock in profit
+    /// past a target rather than from entry.
+    #[must_use]
+    pub fn activation_px(mut self, px: Decimal) -> Self {
+        self.activation_px = Some(px);
+        self
+    }
+
+    /// Returns the coin the stop follows.
+    #[must_use]
+    pub fn coin(&self) -> &str {
+        &self.coin
+    }
+
+    /// Returns the most favourable price seen since the stop was activated.
+    #[must_use]
+    pub fn watermark(&self) -> Option<Decimal> {
+        self.watermark
+    }
+
+    /// Returns the price at which the stop triggers.
+    #[must_use]
+    pub fn stop_px(&self) -> Option<Decimal> {
+        let watermark = self.watermark?;
+        let distance = self.distance.at(watermark);
+        Some(if self.is_long {
+            watermark - distance
+        } else {
+            watermark + distance
+        })
+    }
+
+    /// Returns the price that triggered the stop, once it has.
+    #[must_use]
+    pub fn triggered_at(&self) -> Option<Decimal> {
+        self.triggered_at
+    }
+
+    /// Updates the stop from a BBO message, returning the price that triggered it.
+    ///
+    /// Longs follow the best bid and shorts the best ask, the prices a closing order
+    /// would hit. Messages for other coins are ignored. Once triggered, the stop
+    /// stays triggered and doesn't fire again.
+    pub fn observe(&mut self, bbo: &Bbo) -> Option<Decimal> {
+        if bbo.coin != self.coin {
+            return None;
+        }
+        let level = if self.is_long { bbo.bid() } else { bbo.ask() };
+        self.update(level?.px)
+    }
+
+    /// Updates the stop with a price, returning it if it triggered the stop.
+    pub fn update(&mut self, px: Decimal) -> Option<Decimal> {
+        if self.triggered_at.is_some() {
+            return None;
+        }
+        let is_long = self.is_long;
+        let better = |a: Decimal, b: Decimal| if is_long { a > b } else { a < b };
+
+        if self.watermark.is_none() {
+            if self
+                .activation_px
+                .is_some_and(|activation| better(activation, px))
+            {
+                return None;
+            }
+            self.watermark = Some(px);
+            return None;
+        }
+
+        if self
+            .watermark
+            .is_some_and(|watermark| better(px, watermark))
+        {
+            self.watermark = Some(px);
+        }
+        let stop_px = self.stop_px()?;
+        let hit = if is_long {
+            px <= stop_px
+        } else {
+            px >= stop_px
+        };
+        if hit {
+            self.triggered_at = Some(px);
+        }
+        self.triggered_at
+    }
+
+    /// Reads a stop saved with [`save`](Self::save).
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let bytes = std::fs::read(path)?;
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+
+    /// Writes the stop to `path` as JSON.
+    ///
+    /// The file is replaced atomically, so a crash mid-write leaves the previous
+    /// state in place.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, serde_json::to_vec_pretty(self)?)?;
+        std::fs::rename(&tmp, path)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::dec;
+
+    use super::*;
+
+    #[test]
+    fn test_trailing_stop() {
+        let mut long = TrailingStop::new("ETH", true, TrailDistance::Absolute(dec!(10)));
+        assert_eq!(long.update(dec!(100)), None);
+        assert_eq!(long.update(dec!(120)), None);
+        assert_eq!(long.stop_px(), Some(dec!(110)));
+        assert_eq!(long.update(dec!(115)), None);
+        assert_eq!(long.watermark(), Some(dec!(120)));
+        assert_eq!(long.update(dec!(110)), Some(dec!(110)));
+        assert_eq!(long.update(dec!(90)), None);
+
+        let mut short = TrailingStop::new("ETH", false, TrailDistance::Percent(dec!(0.1)))
+            .activation_px(dec!(90));
+        assert_eq!(short.update(dec!(95)), None);
+        assert_eq!(short.watermark(), None);
+        assert_eq!(short.update(dec!(80)), None);
+        assert_eq!(short.stop_px(), Some(dec!(88)));
+
+        // The watermark survives a round trip.
+        let restored: TrailingStop =
+            serde_json::from_value(serde_json::to_value(&short).unwrap()).unwrap();
+        assert_eq!(restored, short);
+        assert_eq!(short.update(dec!(88)), Some(dec!(88)));
+    }
+}
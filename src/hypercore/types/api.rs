@@ -4,13 +4,15 @@
 //! used for interacting with the Hyperliquid exchange API. These types handle
 //! signing, serialization, and API communication.
 
+use std::collections::BTreeMap;
+
 use alloy::{
     dyn_abi::TypedData,
     primitives::{Address, B256},
     signers::{Signer, SignerSync, k256::ecdsa::RecoveryId},
 };
 use chrono::{DateTime, Utc};
-use rust_decimal::Decimal;
+use rust_decimal::{Decimal, prelude::ToPrimitive};
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 
@@ -27,7 +29,7 @@ use crate::hypercore::{
 /// Request for an action.
 ///
 /// Contains the action, a nonce, signature, optional vault address, and optional expiry.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ActionRequest {
     /// Action.
@@ -142,20 +144,14 @@ pub enum Action {
     /// Transfer between spot and perp balances.
     #[from(skip)]
     UsdClassTransfer(UsdClassTransferAction),
-    /// Stake native token (HYPE).
+    /// Transfer HYPE from the spot balance to the staking balance.
     #[from(skip)]
     #[serde(rename = "cDeposit")]
-    CDeposit {
-        /// Amount in wei (native token).
-        wei: u64,
-    },
-    /// Unstake native token (HYPE). 7-day queue.
+    CDeposit(CDepositAction),
+    /// Transfer HYPE from the staking balance to the spot balance. 7-day queue.
     #[from(skip)]
     #[serde(rename = "cWithdraw")]
-    CWithdraw {
-        /// Amount in wei (native token).
-        wei: u64,
-    },
+    CWithdraw(CWithdrawAction),
     /// Delegate or undelegate staked tokens to a validator.
     #[from(skip)]
     TokenDelegate(TokenDelegateAction),
@@ -172,6 +168,12 @@ pub enum Action {
     /// HIP-4 outcome token split/merge/negate.
     #[from(skip)]
     UserOutcome(UserOutcomeAction),
+    /// HIP-1 / HIP-2 spot token deployment step.
+    #[from(skip)]
+    SpotDeploy(SpotDeployAction),
+    /// HIP-3 perp DEX deployment or maintenance step.
+    #[from(skip)]
+    PerpDeploy(PerpDeployAction),
 }
 
 impl Action {
@@ -222,6 +224,75 @@ impl Action {
     }
 }
 
+impl Action {
+    /// Returns the chain a user-signed action was built for.
+    ///
+    /// This is the action's `hyperliquidChain`, or for multisig wrappers the chain of the
+    /// inner action, falling back to the one implied by `signatureChainId`. Returns `None`
+    /// for L1 actions, which don't carry chain fields.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use hypersdk::hypercore::{Chain, api::Action, types::UsdSend};
+    /// use rust_decimal::dec;
+    ///
+    /// let send = UsdSend {
+    ///     destination: "0x0D1d9635D0640821d15e323ac8AdADfA9c111414".parse().unwrap(),
+    ///     amount: dec!(1),
+    ///     time: 1690393044548,
+    /// };
+    /// let action = Action::UsdSend(send.into_action(Chain::Testnet));
+    /// assert_eq!(action.chain(), Some(Chain::Testnet));
+    /// assert_eq!(Action::Noop.chain(), None);
+    /// ```
+    pub fn chain(&self) -> Option<Chain> {
+        if let Action::MultiSig(inner) = self {
+            if let Some(chain) = inner.payload.action.chain() {
+                return Some(chain);
+            }
+        }
+        let (signature_chain_id, hyperliquid_chain) = self.chain_fields()?;
+        hyperliquid_chain.or_else(|| Chain::from_signature_chain_id(signature_chain_id))
+    }
+
+    /// Returns `(signatureChainId, hyperliquidChain)` of a user-signed action.
+    pub(crate) fn chain_fields(&self) -> Option<(&str, Option<Chain>)> {
+        let fields = match self {
+            Action::UsdSend(inner) => (&inner.signature_chain_id, Some(inner.hyperliquid_chain)),
+            Action::SpotSend(inner) => (&inner.signature_chain_id, Some(inner.hyperliquid_chain)),
+            Action::SendAsset(inner) => (&inner.signature_chain_id, Some(inner.hyperliquid_chain)),
+            Action::ApproveAgent(inner) => {
+                (&inner.signature_chain_id, Some(inner.hyperliquid_chain))
+            }
+            Action::ApproveBuilderFee(inner) => {
+                (&inner.signature_chain_id, Some(inner.hyperliquid_chain))
+            }
+            Action::ConvertToMultiSigUser(inner) => {
+                (&inner.signature_chain_id, Some(inner.hyperliquid_chain))
+            }
+            Action::UserDexAbstraction(inner) => {
+                (&inner.signature_chain_id, Some(inner.hyperliquid_chain))
+            }
+            Action::UserSetAbstraction(inner) => {
+                (&inner.signature_chain_id, Some(inner.hyperliquid_chain))
+            }
+            Action::Withdraw3(inner) => (&inner.signature_chain_id, Some(inner.hyperliquid_chain)),
+            Action::UsdClassTransfer(inner) => {
+                (&inner.signature_chain_id, Some(inner.hyperliquid_chain))
+            }
+            Action::CDeposit(inner) => (&inner.signature_chain_id, Some(inner.hyperliquid_chain)),
+            Action::CWithdraw(inner) => (&inner.signature_chain_id, Some(inner.hyperliquid_chain)),
+            Action::TokenDelegate(inner) => {
+                (&inner.signature_chain_id, Some(inner.hyperliquid_chain))
+            }
+            Action::MultiSig(inner) => (&inner.signature_chain_id, None),
+            _ => return None,
+        };
+        Some((fields.0.as_str(), fields.1))
+    }
+}
+
 /// API response wrapper.
 ///
 /// The `Ok` variant contains a successful response, while `Err` holds an error message.
@@ -261,6 +332,9 @@ impl Action {
     ///
     /// Computes the prehash using the action's signing method (RMP+Agent for orders/cancels,
     /// EIP-712 for transfers), then signs it with the provided signer.
+    ///
+    /// Fails with a [`ChainMismatch`](crate::hypercore::ChainMismatch) if a user-signed
+    /// action's `signatureChainId` or `hyperliquidChain` doesn't match `chain`.
     pub fn sign_sync<S: SignerSync>(
         self,
         signer: &S,
@@ -269,6 +343,7 @@ impl Action {
         maybe_expires_after: Option<DateTime<Utc>>,
         chain: Chain,
     ) -> anyhow::Result<ActionRequest> {
+        chain.signing_context().validate(&self)?;
         let expires_after = maybe_expires_after.map(|after| after.timestamp_millis() as u64);
 
         // Sign based on action type
@@ -290,14 +365,14 @@ impl Action {
             | Action::AgentSetAbstraction { .. }
             | Action::TwapOrder { .. }
             | Action::TwapCancel { .. }
-            | Action::CDeposit { .. }
-            | Action::CWithdraw { .. }
             | Action::ReserveRequestWeight { .. }
             | Action::Hip3LiquidatorTransfer(_)
-            | Action::UserOutcome(_) => {
+            | Action::UserOutcome(_)
+            | Action::SpotDeploy(_)
+            | Action::PerpDeploy(_) => {
                 let connection_id = self.hash(nonce, maybe_vault_address, expires_after)?;
                 let agent = solidity::Agent {
-                    source: if chain.is_mainnet() { "a" } else { "b" }.to_string(),
+                    source: chain.signing_context().agent_source().to_owned(),
                     connectionId: connection_id,
                 };
                 signer.sign_typed_data_sync(&agent, &CORE_MAINNET_EIP712_DOMAIN)?
@@ -350,6 +425,14 @@ impl Action {
                 let typed_data = get_typed_data::<solidity::TokenDelegate>(&inner, chain, None);
                 signer.sign_dynamic_typed_data_sync(&typed_data)?
             }
+            Action::CDeposit(inner) => {
+                let typed_data = get_typed_data::<solidity::CDeposit>(&inner, chain, None);
+                signer.sign_dynamic_typed_data_sync(&typed_data)?
+            }
+            Action::CWithdraw(inner) => {
+                let typed_data = get_typed_data::<solidity::CWithdraw>(&inner, chain, None);
+                signer.sign_dynamic_typed_data_sync(&typed_data)?
+            }
             // MultiSig - wrap in envelope
             Action::MultiSig(inner) => {
                 let multsig_hash =
@@ -364,7 +447,7 @@ impl Action {
                 }
 
                 let envelope = Envelope {
-                    hyperliquid_chain: chain.to_string(),
+                    hyperliquid_chain: chain.hyperliquid_chain().to_owned(),
                     multi_sig_action_hash: multsig_hash.to_string(),
                     nonce,
                 };
@@ -390,6 +473,9 @@ impl Action {
     ///
     /// Computes the prehash using the action's signing method (RMP+Agent for orders/cancels,
     /// EIP-712 for transfers), then signs it with the provided signer.
+    ///
+    /// Fails with a [`ChainMismatch`](crate::hypercore::ChainMismatch) if a user-signed
+    /// action's `signatureChainId` or `hyperliquidChain` doesn't match `chain`.
     pub async fn sign<S: Signer + Send + Sync>(
         self,
         signer: &S,
@@ -398,6 +484,7 @@ impl Action {
         maybe_expires_after: Option<DateTime<Utc>>,
         chain: Chain,
     ) -> anyhow::Result<ActionRequest> {
+        chain.signing_context().validate(&self)?;
         let expires_after = maybe_expires_after.map(|after| after.timestamp_millis() as u64);
 
         // Sign based on action type
@@ -419,14 +506,14 @@ impl Action {
             | Action::AgentSetAbstraction { .. }
             | Action::TwapOrder { .. }
             | Action::TwapCancel { .. }
-            | Action::CDeposit { .. }
-            | Action::CWithdraw { .. }
             | Action::ReserveRequestWeight { .. }
             | Action::Hip3LiquidatorTransfer(_)
-            | Action::UserOutcome(_) => {
+            | Action::UserOutcome(_)
+            | Action::SpotDeploy(_)
+            | Action::PerpDeploy(_) => {
                 let connection_id = self.hash(nonce, maybe_vault_address, expires_after)?;
                 let agent = solidity::Agent {
-                    source: if chain.is_mainnet() { "a" } else { "b" }.to_string(),
+                    source: chain.signing_context().agent_source().to_owned(),
                     connectionId: connection_id,
                 };
                 signer
@@ -481,6 +568,14 @@ impl Action {
                 let typed_data = get_typed_data::<solidity::TokenDelegate>(&inner, chain, None);
                 signer.sign_dynamic_typed_data(&typed_data).await?
             }
+            Action::CDeposit(inner) => {
+                let typed_data = get_typed_data::<solidity::CDeposit>(&inner, chain, None);
+                signer.sign_dynamic_typed_data(&typed_data).await?
+            }
+            Action::CWithdraw(inner) => {
+                let typed_data = get_typed_data::<solidity::CWithdraw>(&inner, chain, None);
+                signer.sign_dynamic_typed_data(&typed_data).await?
+            }
             Action::MultiSig(inner) => {
                 let multsig_hash =
                     utils::rmp_hash(&inner, nonce, maybe_vault_address, expires_after)?;
@@ -494,7 +589,7 @@ impl Action {
                 }
 
                 let envelope = Envelope {
-                    hyperliquid_chain: chain.to_string(),
+                    hyperliquid_chain: chain.hyperliquid_chain().to_owned(),
                     multi_sig_action_hash: multsig_hash.to_string(),
                     nonce,
                 };
@@ -527,6 +622,7 @@ impl Action {
         maybe_expires_after: Option<DateTime<Utc>>,
         chain: Chain,
     ) -> anyhow::Result<B256> {
+        chain.signing_context().validate(self)?;
         match self {
             // RMP-based actions - hash and wrap in Agent struct
             Action::Order(_)
@@ -545,11 +641,11 @@ impl Action {
             | Action::AgentSetAbstraction { .. }
             | Action::TwapOrder { .. }
             | Action::TwapCancel { .. }
-            | Action::CDeposit { .. }
-            | Action::CWithdraw { .. }
             | Action::ReserveRequestWeight { .. }
             | Action::Hip3LiquidatorTransfer(_)
-            | Action::UserOutcome(_) => {
+            | Action::UserOutcome(_)
+            | Action::SpotDeploy(_)
+            | Action::PerpDeploy(_) => {
                 let expires_after =
                     maybe_expires_after.map(|after| after.timestamp_millis() as u64);
                 let connection_id = self
@@ -608,6 +704,14 @@ impl Action {
                 let typed_data = get_typed_data::<solidity::TokenDelegate>(&inner, chain, None);
                 Ok(typed_data.eip712_signing_hash()?)
             }
+            Action::CDeposit(inner) => {
+                let typed_data = get_typed_data::<solidity::CDeposit>(&inner, chain, None);
+                Ok(typed_data.eip712_signing_hash()?)
+            }
+            Action::CWithdraw(inner) => {
+                let typed_data = get_typed_data::<solidity::CWithdraw>(&inner, chain, None);
+                Ok(typed_data.eip712_signing_hash()?)
+            }
             Action::MultiSig(inner) => {
                 let expires_after =
                     maybe_expires_after.map(|after| after.timestamp_millis() as u64);
@@ -623,7 +727,7 @@ impl Action {
                 }
 
                 let envelope = Envelope {
-                    hyperliquid_chain: chain.to_string(),
+                    hyperliquid_chain: chain.hyperliquid_chain().to_owned(),
                     multi_sig_action_hash: multsig_hash.to_string(),
                     nonce,
                 };
@@ -909,6 +1013,8 @@ pub struct ConvertToMultiSigUser {
 /// Request to update isolated margin for a position.
 ///
 /// Allows adding or removing margin from an isolated-margin position.
+///
+/// <https://hyperliquid.gitbook.io/hyperliquid-docs/for-developers/api/exchange-endpoint#update-isolated-margin>
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateIsolatedMargin {
@@ -916,8 +1022,65 @@ pub struct UpdateIsolatedMargin {
     pub asset: usize,
     /// `true` for a long position, `false` for a short position.
     pub is_buy: bool,
-    /// Margin delta in USD (scaled integer representation).
-    pub ntli: u64,
+    /// Margin delta in micro-USD (`1_000_000` = $1). Negative values remove margin.
+    pub ntli: i64,
+}
+
+impl UpdateIsolatedMargin {
+    /// Scale of [`ntli`](Self::ntli): USD amounts carry 6 decimals.
+    pub const NTLI_DECIMALS: u32 = 6;
+
+    /// Adds `usd` of margin to the position.
+    ///
+    /// Returns `None` if `usd` is negative, has more than 6 decimals, or overflows.
+    #[must_use]
+    pub fn add(asset: usize, is_buy: bool, usd: Decimal) -> Option<Self> {
+        if usd.is_sign_negative() {
+            return None;
+        }
+        Self::with_delta(asset, is_buy, usd)
+    }
+
+    /// Removes `usd` of margin from the position.
+    ///
+    /// Returns `None` if `usd` is negative, has more than 6 decimals, or overflows.
+    #[must_use]
+    pub fn remove(asset: usize, is_buy: bool, usd: Decimal) -> Option<Self> {
+        if usd.is_sign_negative() {
+            return None;
+        }
+        Self::with_delta(asset, is_buy, -usd)
+    }
+
+    fn with_delta(asset: usize, is_buy: bool, usd: Decimal) -> Option<Self> {
+        let scaled = usd.checked_mul(Decimal::from(10i64.pow(Self::NTLI_DECIMALS)))?;
+        if !scaled.fract().is_zero() {
+            return None;
+        }
+        Some(Self {
+            asset,
+            is_buy,
+            ntli: scaled.to_i64()?,
+        })
+    }
+
+    /// Returns the margin delta in USD (negative when removing margin).
+    #[must_use]
+    pub fn usd(&self) -> Decimal {
+        Decimal::new(self.ntli, Self::NTLI_DECIMALS)
+    }
+}
+
+/// Margin change accepted by the exchange, returned by
+/// [`HttpClient::update_isolated_margin`](crate::hypercore::HttpClient::update_isolated_margin).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IsolatedMarginUpdate {
+    /// Asset index of the position.
+    pub asset: usize,
+    /// `true` for a long position, `false` for a short position.
+    pub is_buy: bool,
+    /// Margin delta in USD, negative when margin was removed.
+    pub usd: Decimal,
 }
 
 /// Request to update leverage for a perpetual asset.
@@ -936,6 +1099,28 @@ pub struct UpdateLeverage {
     pub leverage: u32,
 }
 
+impl UpdateLeverage {
+    /// Sets cross-margin leverage for `asset`.
+    #[must_use]
+    pub fn cross(asset: usize, leverage: u32) -> Self {
+        Self {
+            asset,
+            is_cross: true,
+            leverage,
+        }
+    }
+
+    /// Sets isolated-margin leverage for `asset`.
+    #[must_use]
+    pub fn isolated(asset: usize, leverage: u32) -> Self {
+        Self {
+            asset,
+            is_cross: false,
+            leverage,
+        }
+    }
+}
+
 /// Deposit or withdraw USDC from a vault.
 ///
 /// <https://hyperliquid.gitbook.io/hyperliquid-docs/for-developers/api/exchange-endpoint#vault-transfer>
@@ -1197,7 +1382,7 @@ impl MultiSigPayload {
                 None,
             )?;
             let agent = solidity::Agent {
-                source: if chain.is_mainnet() { "a" } else { "b" }.to_string(),
+                source: chain.signing_context().agent_source().to_owned(),
                 connectionId: connection_id,
             };
             Ok(signer
@@ -1231,7 +1416,7 @@ impl MultiSigPayload {
                 None,
             )?;
             let agent = solidity::Agent {
-                source: if chain.is_mainnet() { "a" } else { "b" }.to_string(),
+                source: chain.signing_context().agent_source().to_owned(),
                 connectionId: connection_id,
             };
             Ok(signer
@@ -1341,20 +1526,78 @@ pub struct UsdClassTransferAction {
     pub nonce: u64,
 }
 
-/// Delegate or undelegate staked tokens to a validator.
+/// Transfer HYPE from the spot balance to the staking balance.
+///
+/// Signed with EIP-712 (`HyperliquidTransaction:CDeposit`).
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CDepositAction {
+    pub signature_chain_id: String,
+    pub hyperliquid_chain: Chain,
+    /// Amount in wei of HYPE (see [`HYPE_WEI_DECIMALS`]).
+    pub wei: u64,
+    pub nonce: u64,
+}
+
+/// Transfer HYPE from the staking balance back to the spot balance.
+///
+/// Withdrawals go through a 7-day unstaking queue. Signed with EIP-712
+/// (`HyperliquidTransaction:CWithdraw`).
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CWithdrawAction {
+    pub signature_chain_id: String,
+    pub hyperliquid_chain: Chain,
+    /// Amount in wei of HYPE (see [`HYPE_WEI_DECIMALS`]).
+    pub wei: u64,
+    pub nonce: u64,
+}
+
+/// Delegate or undelegate staked HYPE to a validator.
+///
+/// Signed with EIP-712 (`HyperliquidTransaction:TokenDelegate`).
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct TokenDelegateAction {
+    pub signature_chain_id: String,
+    pub hyperliquid_chain: Chain,
     /// Validator address.
     #[serde(
         serialize_with = "crate::hypercore::utils::serialize_address_as_hex",
         deserialize_with = "crate::hypercore::utils::deserialize_address_from_hex"
     )]
     pub validator: Address,
+    /// Amount in wei of HYPE (see [`HYPE_WEI_DECIMALS`]).
+    pub wei: u64,
     /// `true` to undelegate, `false` to delegate.
     pub is_undelegate: bool,
-    /// Amount in wei of native token.
-    pub wei: u64,
+    pub nonce: u64,
+}
+
+/// Decimals of the HYPE `wei` amounts used by staking actions.
+pub const HYPE_WEI_DECIMALS: u32 = 8;
+
+/// Converts a HYPE amount into staking `wei`.
+///
+/// Returns `None` if the amount is negative, has more than [`HYPE_WEI_DECIMALS`]
+/// decimals, or overflows `u64`.
+///
+/// # Example
+///
+/// ```
+/// use hypersdk::hypercore::types::hype_to_wei;
+/// use rust_decimal::dec;
+///
+/// assert_eq!(hype_to_wei(dec!(1.5)), Some(150_000_000));
+/// assert_eq!(hype_to_wei(dec!(0.000000001)), None);
+/// ```
+#[must_use]
+pub fn hype_to_wei(amount: Decimal) -> Option<u64> {
+    let wei = amount.checked_mul(Decimal::from(10u64.pow(HYPE_WEI_DECIMALS)))?;
+    if wei.fract() != Decimal::ZERO {
+        return None;
+    }
+    wei.to_u64()
 }
 
 /// HIP-3 backstop liquidator transfer.
@@ -1501,6 +1744,422 @@ pub struct NegateOutcome {
     pub amount: Decimal,
 }
 
+/// HIP-1 / HIP-2 spot token deployment (`spotDeploy`).
+///
+/// Deploying a token is a sequence of `spotDeploy` actions, each carrying exactly one step:
+///
+/// 1. [`register_token`](Self::register_token): reserve the token name and decimals, paying the
+///    deploy auction gas (see [`spot_pair_deploy_auction_status`](crate::hypercore::HttpClient::spot_pair_deploy_auction_status)).
+/// 2. [`user_genesis`](Self::user_genesis): allocate balances to users and to holders of existing
+///    tokens. Can be sent several times.
+/// 3. [`genesis`](Self::genesis): fix the max supply and finalize balances.
+/// 4. [`register_spot`](Self::register_spot): create the spot pair against a quote token.
+/// 5. [`register_hyperliquidity`](Self::register_hyperliquidity): seed the HIP-2 Hyperliquidity
+///    strategy on the new pair.
+///
+/// Like [`UserOutcomeAction`], this is an L1 action signed via the msgpack + `Agent` wrapper.
+///
+/// <https://hyperliquid.gitbook.io/hyperliquid-docs/hyperliquid-improvement-proposals-hips/hip-1-native-token-standard>
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SpotDeployAction {
+    /// Register a new token.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub register_token2: Option<RegisterToken>,
+    /// Allocate genesis balances.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_genesis: Option<UserGenesis>,
+    /// Finalize genesis.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub genesis: Option<Genesis>,
+    /// Register the spot pair.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub register_spot: Option<RegisterSpot>,
+    /// Seed Hyperliquidity on the pair.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub register_hyperliquidity: Option<RegisterHyperliquidity>,
+}
+
+impl SpotDeployAction {
+    /// Build a [`Self`] that registers a token.
+    ///
+    /// `max_gas` caps the deploy auction price, in HYPE wei (8 decimals).
+    #[must_use]
+    pub fn register_token(
+        name: impl Into<String>,
+        sz_decimals: u8,
+        wei_decimals: u8,
+        max_gas: u64,
+        full_name: Option<String>,
+    ) -> Self {
+        Self {
+            register_token2: Some(RegisterToken {
+                spec: TokenSpec {
+                    name: name.into(),
+                    sz_decimals,
+                    wei_decimals,
+                },
+                max_gas,
+                full_name,
+            }),
+            ..Default::default()
+        }
+    }
+
+    /// Build a [`Self`] that allocates genesis balances of `token`.
+    ///
+    /// `users` are `(address, wei)` allocations. `existing_tokens` are `(token, wei)` pairs
+    /// distributed pro rata to holders of an existing token.
+    #[must_use]
+    pub fn user_genesis(
+        token: u32,
+        users: impl IntoIterator<Item = (Address, u128)>,
+        existing_tokens: impl IntoIterator<Item = (u32, u128)>,
+    ) -> Self {
+        Self {
+            user_genesis: Some(UserGenesis {
+                token,
+                user_and_wei: users
+                    .into_iter()
+                    .map(|(user, wei)| UserWei(user, wei))
+                    .collect(),
+                existing_token_and_wei: existing_tokens
+                    .into_iter()
+                    .map(|(token, wei)| TokenWei(token, wei))
+                    .collect(),
+            }),
+            ..Default::default()
+        }
+    }
+
+    /// Build a [`Self`] that finalizes genesis of `token` with `max_supply` wei.
+    ///
+    /// `no_hyperliquidity` skips reserving supply for Hyperliquidity.
+    #[must_use]
+    pub fn genesis(token: u32, max_supply: u128, no_hyperliquidity: bool) -> Self {
+        Self {
+            genesis: Some(Genesis {
+                token,
+                max_supply,
+                no_hyperliquidity: no_hyperliquidity.then_some(true),
+            }),
+            ..Default::default()
+        }
+    }
+
+    /// Build a [`Self`] that registers the `base`/`quote` spot pair.
+    #[must_use]
+    pub fn register_spot(base: u32, quote: u32) -> Self {
+        Self {
+            register_spot: Some(RegisterSpot {
+                tokens: [base, quote],
+            }),
+            ..Default::default()
+        }
+    }
+
+    /// Build a [`Self`] that seeds Hyperliquidity on `spot`.
+    ///
+    /// Places `n_orders` asks of `order_sz` starting at `start_px`, each 0.3% above the
+    /// previous. `n_seeded_levels` bids are funded from the deployer's quote balance.
+    #[must_use]
+    pub fn register_hyperliquidity(
+        spot: u32,
+        start_px: Decimal,
+        order_sz: Decimal,
+        n_orders: u32,
+        n_seeded_levels: Option<u32>,
+    ) -> Self {
+        Self {
+            register_hyperliquidity: Some(RegisterHyperliquidity {
+                spot,
+                start_px: start_px.normalize(),
+                order_sz: order_sz.normalize(),
+                n_orders,
+                n_seeded_levels,
+            }),
+            ..Default::default()
+        }
+    }
+}
+
+/// Token registration (`registerToken2`).
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RegisterToken {
+    /// Token name and decimals.
+    pub spec: TokenSpec,
+    /// Maximum deploy gas, in HYPE wei.
+    pub max_gas: u64,
+    /// Optional long name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub full_name: Option<String>,
+}
+
+/// Token name and decimals.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenSpec {
+    /// Ticker.
+    pub name: String,
+    /// Decimals of order sizes.
+    pub sz_decimals: u8,
+    /// Decimals of balances.
+    pub wei_decimals: u8,
+}
+
+/// Genesis balances for users and holders of existing tokens.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UserGenesis {
+    /// Token index.
+    pub token: u32,
+    /// Direct allocations.
+    pub user_and_wei: Vec<UserWei>,
+    /// Pro rata allocations to holders of existing tokens.
+    pub existing_token_and_wei: Vec<TokenWei>,
+}
+
+/// `[user, wei]` genesis allocation.
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UserWei(
+    #[serde(
+        serialize_with = "crate::hypercore::utils::serialize_address_as_hex",
+        deserialize_with = "crate::hypercore::utils::deserialize_address_from_hex"
+    )]
+    pub Address,
+    #[serde_as(as = "serde_with::DisplayFromStr")] pub u128,
+);
+
+/// `[token, wei]` genesis allocation to holders of `token`.
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TokenWei(
+    pub u32,
+    #[serde_as(as = "serde_with::DisplayFromStr")] pub u128,
+);
+
+/// Genesis finalization.
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Genesis {
+    /// Token index.
+    pub token: u32,
+    /// Max supply in wei.
+    #[serde_as(as = "serde_with::DisplayFromStr")]
+    pub max_supply: u128,
+    /// Set to skip reserving supply for Hyperliquidity.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_hyperliquidity: Option<bool>,
+}
+
+/// Spot pair registration.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RegisterSpot {
+    /// `[base, quote]` token indices.
+    pub tokens: [u32; 2],
+}
+
+/// HIP-2 Hyperliquidity registration.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RegisterHyperliquidity {
+    /// Spot pair index.
+    pub spot: u32,
+    /// Price of the first ask.
+    #[serde(with = "rust_decimal::serde::str")]
+    pub start_px: Decimal,
+    /// Size of each order.
+    #[serde(with = "rust_decimal::serde::str")]
+    pub order_sz: Decimal,
+    /// Number of asks.
+    pub n_orders: u32,
+    /// Number of bid levels seeded with the deployer's quote balance.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub n_seeded_levels: Option<u32>,
+}
+
+/// HIP-3 perp DEX deployment and maintenance (`perpDeploy`).
+///
+/// Each action carries exactly one operation:
+///
+/// - [`register_asset`](Self::register_asset): list a new perp, creating the DEX if `schema`
+///   is given
+/// - [`set_oracle`](Self::set_oracle): push oracle and mark prices (the deployer's recurring job,
+///   see [`OraclePusher`](crate::hypercore::oracle::OraclePusher))
+/// - [`set_funding_multipliers`](Self::set_funding_multipliers): scale funding per asset
+/// - [`halt_trading`](Self::halt_trading): halt or resume an asset
+///
+/// This is an L1 action signed via the msgpack + `Agent` wrapper.
+///
+/// <https://hyperliquid.gitbook.io/hyperliquid-docs/hyperliquid-improvement-proposals-hips/hip-3-builder-deployed-perpetuals>
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PerpDeployAction {
+    /// List a new asset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub register_asset: Option<RegisterAsset>,
+    /// Update oracle and mark prices.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub set_oracle: Option<SetOracle>,
+    /// `[coin, multiplier]` pairs, sorted by coin.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub set_funding_multipliers: Option<Vec<(String, Decimal)>>,
+    /// Halt or resume trading.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub halt_trading: Option<HaltTrading>,
+}
+
+impl PerpDeployAction {
+    /// Build a [`Self`] that lists `asset` on `dex`.
+    ///
+    /// Pass `schema` only for the first asset, to create the DEX. `max_gas` caps the deploy
+    /// auction price in HYPE wei (8 decimals); `None` accepts the current price.
+    #[must_use]
+    pub fn register_asset(
+        dex: impl Into<String>,
+        asset: AssetRequest,
+        schema: Option<PerpDexSchema>,
+        max_gas: Option<u64>,
+    ) -> Self {
+        Self {
+            register_asset: Some(RegisterAsset {
+                max_gas,
+                asset_request: AssetRequest {
+                    oracle_px: asset.oracle_px.normalize(),
+                    ..asset
+                },
+                dex: dex.into(),
+                schema,
+            }),
+            ..Default::default()
+        }
+    }
+
+    /// Build a [`Self`] that sets prices on `dex`.
+    ///
+    /// `mark_pxs` may hold several sources; the exchange takes the median with the
+    /// local mark price. Maps keep the coins sorted, as the exchange requires.
+    #[must_use]
+    pub fn set_oracle(
+        dex: impl Into<String>,
+        oracle_pxs: &BTreeMap<String, Decimal>,
+        mark_pxs: &[BTreeMap<String, Decimal>],
+        external_perp_pxs: &BTreeMap<String, Decimal>,
+    ) -> Self {
+        Self {
+            set_oracle: Some(SetOracle {
+                dex: dex.into(),
+                oracle_pxs: sorted_pxs(oracle_pxs),
+                mark_pxs: mark_pxs.iter().map(sorted_pxs).collect(),
+                external_perp_pxs: sorted_pxs(external_perp_pxs),
+            }),
+            ..Default::default()
+        }
+    }
+
+    /// Build a [`Self`] that sets funding multipliers.
+    #[must_use]
+    pub fn set_funding_multipliers(multipliers: &BTreeMap<String, Decimal>) -> Self {
+        Self {
+            set_funding_multipliers: Some(sorted_pxs(multipliers)),
+            ..Default::default()
+        }
+    }
+
+    /// Build a [`Self`] that halts (`true`) or resumes (`false`) trading of `coin`.
+    #[must_use]
+    pub fn halt_trading(coin: impl Into<String>, is_halted: bool) -> Self {
+        Self {
+            halt_trading: Some(HaltTrading {
+                coin: coin.into(),
+                is_halted,
+            }),
+            ..Default::default()
+        }
+    }
+}
+
+fn sorted_pxs(pxs: &BTreeMap<String, Decimal>) -> Vec<(String, Decimal)> {
+    pxs.iter()
+        .map(|(coin, px)| (coin.clone(), px.normalize()))
+        .collect()
+}
+
+/// Asset listing (`registerAsset`).
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RegisterAsset {
+    /// Maximum deploy gas, in HYPE wei.
+    pub max_gas: Option<u64>,
+    /// Asset parameters.
+    pub asset_request: AssetRequest,
+    /// DEX name.
+    pub dex: String,
+    /// DEX parameters, only when creating the DEX.
+    pub schema: Option<PerpDexSchema>,
+}
+
+/// Parameters of a new perp.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetRequest {
+    /// Coin name, prefixed with the DEX (e.g. `"xyz:BTC"`).
+    pub coin: String,
+    /// Decimals of order sizes.
+    pub sz_decimals: u32,
+    /// Initial oracle price.
+    #[serde(with = "rust_decimal::serde::str")]
+    pub oracle_px: Decimal,
+    /// Margin table, from `meta`.
+    pub margin_table_id: u32,
+    /// Whether only isolated margin is allowed.
+    pub only_isolated: bool,
+}
+
+/// DEX parameters set at creation.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PerpDexSchema {
+    /// Display name.
+    pub full_name: String,
+    /// Collateral token index.
+    pub collateral_token: u32,
+    /// Address allowed to push oracle prices, if not the deployer.
+    #[serde(
+        serialize_with = "crate::hypercore::utils::serialize_option_address_as_hex",
+        deserialize_with = "crate::hypercore::utils::deserialize_option_address_from_hex"
+    )]
+    pub oracle_updater: Option<Address>,
+}
+
+/// Oracle and mark price update (`setOracle`).
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SetOracle {
+    /// DEX name.
+    pub dex: String,
+    /// `[coin, px]` oracle prices, sorted by coin.
+    pub oracle_pxs: Vec<(String, Decimal)>,
+    /// One `[coin, px]` list per mark price source.
+    pub mark_pxs: Vec<Vec<(String, Decimal)>>,
+    /// `[coin, px]` prices for external perps, sorted by coin.
+    pub external_perp_pxs: Vec<(String, Decimal)>,
+}
+
+/// Trading halt toggle.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HaltTrading {
+    /// Coin name.
+    pub coin: String,
+    /// `true` to halt, `false` to resume.
+    pub is_halted: bool,
+}
+
 #[cfg(test)]
 mod tests {
     use alloy::primitives::address;
@@ -1525,6 +2184,238 @@ mod tests {
         );
     }
 
+    #[test]
+    fn staking_actions_are_user_signed() {
+        use alloy::signers::local::PrivateKeySigner;
+
+        let signer = PrivateKeySigner::random();
+        let chain = Chain::Testnet;
+        let nonce = 1_700_000_000_000;
+        let actions = [
+            Action::CDeposit(CDepositAction {
+                signature_chain_id: chain.arbitrum_id().to_string(),
+                hyperliquid_chain: chain,
+                wei: 150_000_000,
+                nonce,
+            }),
+            Action::CWithdraw(CWithdrawAction {
+                signature_chain_id: chain.arbitrum_id().to_string(),
+                hyperliquid_chain: chain,
+                wei: 150_000_000,
+                nonce,
+            }),
+            Action::TokenDelegate(TokenDelegateAction {
+                signature_chain_id: chain.arbitrum_id().to_string(),
+                hyperliquid_chain: chain,
+                validator: address!("0x5aC99df645F3414876C816Caa18b2d234024b487"),
+                wei: 150_000_000,
+                is_undelegate: false,
+                nonce,
+            }),
+        ];
+
+        for action in actions {
+            let json = serde_json::to_value(&action).unwrap();
+            assert_eq!(json["hyperliquidChain"], "Testnet");
+            assert_eq!(json["nonce"], nonce);
+
+            let req = action.sign_sync(&signer, nonce, None, None, chain).unwrap();
+            assert_eq!(req.recover(chain).unwrap(), signer.address());
+        }
+    }
+
+    #[test]
+    fn local_chain_signs_as_testnet() {
+        let deposit = |chain: Chain| {
+            Action::CDeposit(CDepositAction {
+                signature_chain_id: chain.arbitrum_id().to_string(),
+                hyperliquid_chain: chain,
+                wei: 1,
+                nonce: 1,
+            })
+        };
+
+        for action in [Action::Noop, deposit(Chain::Testnet)] {
+            assert_eq!(
+                action.prehash(1, None, None, Chain::Local).unwrap(),
+                action.prehash(1, None, None, Chain::Testnet).unwrap(),
+            );
+        }
+        assert_eq!(
+            deposit(Chain::Local)
+                .prehash(1, None, None, Chain::Local)
+                .unwrap(),
+            deposit(Chain::Testnet)
+                .prehash(1, None, None, Chain::Testnet)
+                .unwrap(),
+        );
+        assert_eq!(
+            serde_json::to_string(&Chain::Local).unwrap(),
+            r#""Testnet""#
+        );
+        assert_eq!("local".parse::<Chain>().unwrap(), Chain::Local);
+    }
+
+    #[test]
+    fn action_chain_from_fields() {
+        let deposit = Action::CDeposit(CDepositAction {
+            signature_chain_id: Chain::Mainnet.arbitrum_id().to_string(),
+            hyperliquid_chain: Chain::Mainnet,
+            wei: 1,
+            nonce: 1,
+        });
+        assert_eq!(deposit.chain(), Some(Chain::Mainnet));
+        assert_eq!(Action::Noop.chain(), None);
+
+        let multisig = |action: Action| {
+            Action::MultiSig(MultiSigAction {
+                signature_chain_id: "0x66EEE".to_string(),
+                signatures: vec![],
+                payload: MultiSigPayload {
+                    multi_sig_user: String::new(),
+                    outer_signer: String::new(),
+                    action: Box::new(action),
+                },
+            })
+        };
+        // The inner action's chain wins, then the signature chain ID.
+        assert_eq!(multisig(deposit).chain(), Some(Chain::Mainnet));
+        assert_eq!(multisig(Action::Noop).chain(), Some(Chain::Testnet));
+
+        assert_eq!(
+            alloy::dyn_abi::Eip712Domain::from(Chain::Local),
+            Chain::Testnet.domain()
+        );
+    }
+
+    #[test]
+    fn hype_wei_conversion() {
+        use rust_decimal::dec;
+
+        assert_eq!(hype_to_wei(dec!(10)), Some(1_000_000_000));
+        assert_eq!(hype_to_wei(dec!(0.00000001)), Some(1));
+        assert_eq!(hype_to_wei(dec!(0.000000015)), None);
+        assert_eq!(hype_to_wei(dec!(-1)), None);
+    }
+
+    #[test]
+    fn spot_deploy_serialization() {
+        use rust_decimal::dec;
+
+        let action = Action::SpotDeploy(SpotDeployAction::register_token(
+            "TEST",
+            2,
+            8,
+            1_000_000_000_000,
+            None,
+        ));
+        assert_eq!(
+            serde_json::to_string(&action).unwrap(),
+            r#"{"type":"spotDeploy","registerToken2":{"spec":{"name":"TEST","szDecimals":2,"weiDecimals":8},"maxGas":1000000000000}}"#
+        );
+
+        let user = address!("0xABCDEF0000000000000000000000000000000001");
+        let action = Action::SpotDeploy(SpotDeployAction::user_genesis(
+            7,
+            [(user, 100_000_000)],
+            [(0, 5)],
+        ));
+        assert_eq!(
+            serde_json::to_string(&action).unwrap(),
+            r#"{"type":"spotDeploy","userGenesis":{"token":7,"userAndWei":[["0xabcdef0000000000000000000000000000000001","100000000"]],"existingTokenAndWei":[[0,"5"]]}}"#
+        );
+
+        let action = Action::SpotDeploy(SpotDeployAction::genesis(7, 1_000_000_000, true));
+        assert_eq!(
+            serde_json::to_string(&action).unwrap(),
+            r#"{"type":"spotDeploy","genesis":{"token":7,"maxSupply":"1000000000","noHyperliquidity":true}}"#
+        );
+
+        let action = Action::SpotDeploy(SpotDeployAction::register_spot(7, 0));
+        assert_eq!(
+            serde_json::to_string(&action).unwrap(),
+            r#"{"type":"spotDeploy","registerSpot":{"tokens":[7,0]}}"#
+        );
+
+        let action = Action::SpotDeploy(SpotDeployAction::register_hyperliquidity(
+            12,
+            dec!(1.50),
+            dec!(100),
+            10,
+            None,
+        ));
+        let json = serde_json::to_string(&action).unwrap();
+        assert_eq!(
+            json,
+            r#"{"type":"spotDeploy","registerHyperliquidity":{"spot":12,"startPx":"1.5","orderSz":"100","nOrders":10}}"#
+        );
+
+        // Round-trip, then sign and recover.
+        let action: Action = serde_json::from_str(&json).unwrap();
+        let signer = alloy::signers::local::PrivateKeySigner::random();
+        let req = action
+            .sign_sync(&signer, 1, None, None, Chain::Testnet)
+            .unwrap();
+        assert_eq!(req.recover(Chain::Testnet).unwrap(), signer.address());
+    }
+
+    #[test]
+    fn perp_deploy_serialization() {
+        use std::collections::BTreeMap;
+
+        use rust_decimal::dec;
+
+        let action = Action::PerpDeploy(PerpDeployAction::register_asset(
+            "xyz",
+            AssetRequest {
+                coin: "xyz:BTC".into(),
+                sz_decimals: 4,
+                oracle_px: dec!(100000.0),
+                margin_table_id: 1,
+                only_isolated: false,
+            },
+            Some(PerpDexSchema {
+                full_name: "XYZ".into(),
+                collateral_token: 0,
+                oracle_updater: Some(address!("0xABCDEF0000000000000000000000000000000001")),
+            }),
+            None,
+        ));
+        assert_eq!(
+            serde_json::to_string(&action).unwrap(),
+            r#"{"type":"perpDeploy","registerAsset":{"maxGas":null,"assetRequest":{"coin":"xyz:BTC","szDecimals":4,"oraclePx":"100000","marginTableId":1,"onlyIsolated":false},"dex":"xyz","schema":{"fullName":"XYZ","collateralToken":0,"oracleUpdater":"0xabcdef0000000000000000000000000000000001"}}}"#
+        );
+
+        let oracle = BTreeMap::from([
+            ("xyz:ETH".to_string(), dec!(3000.50)),
+            ("xyz:BTC".to_string(), dec!(100000)),
+        ]);
+        let action = Action::PerpDeploy(PerpDeployAction::set_oracle(
+            "xyz",
+            &oracle,
+            &[oracle.clone()],
+            &BTreeMap::new(),
+        ));
+        let json = serde_json::to_string(&action).unwrap();
+        assert_eq!(
+            json,
+            r#"{"type":"perpDeploy","setOracle":{"dex":"xyz","oraclePxs":[["xyz:BTC","100000"],["xyz:ETH","3000.5"]],"markPxs":[[["xyz:BTC","100000"],["xyz:ETH","3000.5"]]],"externalPerpPxs":[]}}"#
+        );
+
+        let action = Action::PerpDeploy(PerpDeployAction::halt_trading("xyz:BTC", true));
+        assert_eq!(
+            serde_json::to_string(&action).unwrap(),
+            r#"{"type":"perpDeploy","haltTrading":{"coin":"xyz:BTC","isHalted":true}}"#
+        );
+
+        let action: Action = serde_json::from_str(&json).unwrap();
+        let signer = alloy::signers::local::PrivateKeySigner::random();
+        let req = action
+            .sign_sync(&signer, 1, None, None, Chain::Testnet)
+            .unwrap();
+        assert_eq!(req.recover(Chain::Testnet).unwrap(), signer.address());
+    }
+
     #[test]
     fn user_outcome_serialization() {
         use rust_decimal::dec;
@@ -1667,6 +2558,36 @@ mod tests {
         }
     }
 
+    #[test]
+    fn update_isolated_margin_usd() {
+        use rust_decimal::dec;
+
+        let add = UpdateIsolatedMargin::add(173, true, dec!(2)).unwrap();
+        assert_eq!(add.ntli, 2_000_000);
+        assert_eq!(add.usd(), dec!(2));
+
+        let remove = UpdateIsolatedMargin::remove(0, false, dec!(12.5)).unwrap();
+        assert_eq!(remove.ntli, -12_500_000);
+        assert_eq!(remove.usd(), dec!(-12.5));
+        assert_eq!(
+            serde_json::to_string(&Action::UpdateIsolatedMargin(remove)).unwrap(),
+            r#"{"type":"updateIsolatedMargin","asset":0,"isBuy":false,"ntli":-12500000}"#
+        );
+
+        assert!(UpdateIsolatedMargin::add(0, true, dec!(0.0000001)).is_none());
+        assert!(UpdateIsolatedMargin::remove(0, true, dec!(-1)).is_none());
+    }
+
+    #[test]
+    fn update_leverage_isolated() {
+        let action = Action::UpdateLeverage(UpdateLeverage::isolated(173, 3));
+        let json = serde_json::to_string(&action).unwrap();
+        assert_eq!(
+            json,
+            r#"{"type":"updateLeverage","asset":173,"isCross":false,"leverage":3}"#
+        );
+    }
+
     #[test]
     fn approve_builder_fee_serialization() {
         let action = Action::ApproveBuilderFee(ApproveBuilderFee {
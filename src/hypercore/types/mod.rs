@@ -68,7 +68,7 @@
 //! ```
 
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     fmt,
     hash::{Hash, Hasher},
     io::Read,
@@ -82,22 +82,27 @@ use alloy::{
     sol_types::eip712_domain,
 };
 use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64_STANDARD};
+use chrono::{DateTime, Utc};
 use flate2::read::DeflateDecoder;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error, ser::SerializeMap};
 use serde_with::{DisplayFromStr, serde_as};
 
-use crate::hypercore::{Chain, Cloid, OidOrCloid, SpotToken};
+use crate::hypercore::{
+    Chain, Cloid, DuplicateCloid, OidOrCloid, SpotToken,
+    explorer::{BlockSummary, ExplorerTx},
+};
 
 pub mod api;
 pub(super) mod solidity;
 
 // Re-export important raw types for convenience
 pub use api::{
-    AbstractionMode, Action, ActionRequest, ApproveBuilderFee, GossipPriorityBid,
-    Hip3LiquidatorTransferAction, MultiSigAction, MultiSigPayload, OkResponse, Response,
-    TokenDelegateAction, TwapOrderParams, UsdClassTransferAction, UserDexAbstractionAction,
-    UserSetAbstractionAction, Withdraw3Action,
+    AbstractionMode, Action, ActionRequest, ApproveBuilderFee, CDepositAction, CWithdrawAction,
+    GossipPriorityBid, HYPE_WEI_DECIMALS, Hip3LiquidatorTransferAction, MultiSigAction,
+    MultiSigPayload, OkResponse, Response, TokenDelegateAction, TwapOrderParams,
+    UsdClassTransferAction, UserDexAbstractionAction, UserSetAbstractionAction, Withdraw3Action,
+    hype_to_wei,
 };
 use api::{AgentSendAssetAction, SendAssetAction, SpotSendAction, UsdSendAction};
 
@@ -156,13 +161,25 @@ where
     }
 }
 
+/// `fastAssetCtxs` payload: compressed on the wire, decoded once re-serialized (e.g.
+/// in a [recording](crate::hypercore::record)).
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum FastAssetCtxsPayload {
+    Encoded(String),
+    Decoded(HashMap<String, FastAssetCtx>),
+}
+
 fn deserialize_fast_asset_ctxs<'de, D>(
     deserializer: D,
 ) -> Result<HashMap<String, FastAssetCtx>, D::Error>
 where
     D: Deserializer<'de>,
 {
-    let encoded = String::deserialize(deserializer)?;
+    let encoded = match FastAssetCtxsPayload::deserialize(deserializer)? {
+        FastAssetCtxsPayload::Encoded(encoded) => encoded,
+        FastAssetCtxsPayload::Decoded(ctxs) => return Ok(ctxs),
+    };
     let compressed = BASE64_STANDARD
         .decode(encoded)
         .map_err(serde::de::Error::custom)?;
@@ -246,6 +263,39 @@ impl Dex {
     pub fn deployer_fee_scale(&self) -> Option<Decimal> {
         self.deployer_fee_scale
     }
+
+    /// Returns the DEX-scoped coin name for `symbol`, e.g. `"xyz:BTC"`.
+    ///
+    /// See [`dex_coin`].
+    #[must_use]
+    pub fn coin(&self, symbol: &str) -> String {
+        dex_coin(Some(&self.name), symbol)
+    }
+}
+
+/// Returns the coin name for `symbol` scoped to a HIP-3 DEX.
+///
+/// HIP-3 markets are addressed as `"{dex}:{symbol}"` in info requests and in
+/// coin-keyed subscriptions ([`Subscription::L2Book`], [`Subscription::Trades`],
+/// [`Subscription::Bbo`], [`Subscription::Candle`], [`Subscription::ActiveAssetCtx`]).
+/// The default perp DEX (`None` or `""`) uses the bare symbol, and symbols that are
+/// already scoped are returned unchanged.
+///
+/// # Example
+///
+/// ```rust
+/// use hypersdk::hypercore::types::dex_coin;
+///
+/// assert_eq!(dex_coin(Some("xyz"), "BTC"), "xyz:BTC");
+/// assert_eq!(dex_coin(Some("xyz"), "xyz:BTC"), "xyz:BTC");
+/// assert_eq!(dex_coin(None, "BTC"), "BTC");
+/// ```
+#[must_use]
+pub fn dex_coin(dex: Option<&str>, symbol: &str) -> String {
+    match dex {
+        Some(dex) if !dex.is_empty() && !symbol.contains(':') => format!("{dex}:{symbol}"),
+        _ => symbol.to_owned(),
+    }
 }
 
 impl PartialEq for Dex {
@@ -282,12 +332,64 @@ pub enum Side {
 #[serde(tag = "method")]
 #[serde(rename_all = "camelCase")]
 pub enum Outgoing {
-    Subscribe { subscription: Subscription },
-    Unsubscribe { subscription: Subscription },
+    Subscribe {
+        subscription: Subscription,
+    },
+    Unsubscribe {
+        subscription: Subscription,
+    },
+    /// Request sent through the `post` channel, answered by an [`Incoming::Post`]
+    /// with the same `id`
+    Post {
+        id: u64,
+        request: Box<PostRequest>,
+    },
     Ping,
     Pong,
 }
 
+/// Request carried by [`Outgoing::Post`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "payload")]
+#[serde(rename_all = "camelCase")]
+pub enum PostRequest {
+    /// Signed exchange action, as sent to the `/exchange` endpoint
+    Action(ActionRequest),
+}
+
+/// Response to a WebSocket post request.
+///
+/// `response` holds `{"type": "action" | "info" | "error", "payload": ...}`; use
+/// [`into_action`](Self::into_action) to decode the answer to an action.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PostResponse {
+    /// Id of the request this answers
+    pub id: u64,
+    /// Raw response
+    pub response: serde_json::Value,
+}
+
+impl PostResponse {
+    /// Decodes the answer to a [`PostRequest::Action`].
+    ///
+    /// A request the server refused before reaching the exchange (malformed,
+    /// rate limited) becomes an [`ApiError`](crate::hypercore::ApiError).
+    pub fn into_action(self) -> anyhow::Result<Response> {
+        #[derive(Deserialize)]
+        #[serde(tag = "type", content = "payload")]
+        #[serde(rename_all = "camelCase")]
+        enum Payload {
+            Action(Response),
+            Error(String),
+        }
+
+        match serde_json::from_value(self.response)? {
+            Payload::Action(response) => Ok(response),
+            Payload::Error(err) => Err(crate::hypercore::ApiError(err).into()),
+        }
+    }
+}
+
 /// WebSocket subscription request.
 ///
 /// Each variant corresponds to a subscription type that can be requested from the WebSocket API.
@@ -316,6 +418,16 @@ pub enum Outgoing {
 /// | [`ActiveAssetData`](Self::ActiveAssetData) | [`Incoming::ActiveAssetData`] | User leverage and trading limits for a perp asset |
 /// | [`WebData2`](Self::WebData2) | [`Incoming::WebData2`] | Frontend-style aggregate account snapshot |
 ///
+/// # Explorer Subscriptions
+///
+/// Served by the explorer WebSocket, opened with
+/// [`ExplorerClient::websocket`](crate::hypercore::explorer::ExplorerClient::websocket).
+///
+/// | Subscription | Incoming Message | Description |
+/// |--------------|------------------|-------------|
+/// | [`ExplorerBlock`](Self::ExplorerBlock) | [`Incoming::ExplorerBlock`] | New blocks |
+/// | [`ExplorerTxs`](Self::ExplorerTxs) | [`Incoming::ExplorerTxs`] | New transactions |
+///
 /// # Related Types
 ///
 /// - [`Incoming`]: Messages received from WebSocket subscriptions
@@ -336,7 +448,7 @@ pub enum Outgoing {
 /// ws.subscribe(Subscription::Trades { coin: "ETH".into() });
 /// ws.subscribe(Subscription::Candle {
 ///     coin: "BTC".into(),
-///     interval: "15m".into()
+///     interval: CandleInterval::FifteenMinutes,
 /// });
 ///
 /// // Subscribe to user events
@@ -372,7 +484,10 @@ pub enum Subscription {
     },
     /// Real-time candlestick updates
     #[display("candle({coin}@{interval})")]
-    Candle { coin: String, interval: String },
+    Candle {
+        coin: String,
+        interval: CandleInterval,
+    },
     /// Mid prices for all markets
     #[display("allMids({dex:?})")]
     AllMids {
@@ -459,6 +574,124 @@ pub enum Subscription {
     /// Outcome market metadata updates
     #[display("outcomeMetaUpdates")]
     OutcomeMetaUpdates,
+    /// New blocks, on the explorer WebSocket
+    #[display("explorerBlock")]
+    ExplorerBlock,
+    /// New transactions, on the explorer WebSocket
+    #[display("explorerTxs")]
+    ExplorerTxs,
+}
+
+impl Subscription {
+    /// Returns the HIP-3 DEX this subscription is scoped to, if any.
+    ///
+    /// User and aggregate feeds carry the DEX in their `dex` field, while coin-keyed
+    /// market data feeds carry it as the coin prefix (`"xyz:BTC"`).
+    #[must_use]
+    pub fn dex(&self) -> Option<&str> {
+        match self {
+            Self::Bbo { coin }
+            | Self::Trades { coin }
+            | Self::L2Book { coin, .. }
+            | Self::Candle { coin, .. }
+            | Self::ActiveAssetCtx { coin }
+            | Self::ActiveAssetData { coin, .. } => coin.split_once(':').map(|(dex, _)| dex),
+            Self::AllMids { dex }
+            | Self::WebData2 { dex, .. }
+            | Self::ClearinghouseState { dex, .. }
+            | Self::OpenOrders { dex, .. }
+            | Self::TwapStates { dex, .. } => dex.as_deref().filter(|dex| !dex.is_empty()),
+            _ => None,
+        }
+    }
+
+    /// Returns true if `msg` was sent for this subscription.
+    ///
+    /// Messages are matched by channel and by the coin, user, interval or DEX they
+    /// carry. Channels whose messages don't name their user (`orderUpdates`,
+    /// `userEvents`, `notification`, `webData3`) match any message of the channel.
+    /// Aggregation settings of `l2Book` aren't echoed back and aren't compared.
+    #[must_use]
+    pub fn matches(&self, msg: &Incoming) -> bool {
+        let same_dex = |a: &Option<String>, b: &Option<String>| {
+            a.as_deref().unwrap_or_default() == b.as_deref().unwrap_or_default()
+        };
+        match (self, msg) {
+            (Self::Bbo { coin }, Incoming::Bbo(bbo)) => bbo.coin == *coin,
+            (Self::Trades { coin }, Incoming::Trades(trades)) => {
+                trades.first().is_some_and(|trade| trade.coin == *coin)
+            }
+            (Self::L2Book { coin, .. }, Incoming::L2Book(book)) => book.coin == *coin,
+            (Self::Candle { coin, interval }, Incoming::Candle(candle)) => {
+                candle.coin == *coin && candle.interval == interval.to_string()
+            }
+            (Self::AllMids { dex }, Incoming::AllMids { dex: msg_dex, .. }) => {
+                same_dex(dex, msg_dex)
+            }
+            (Self::ActiveAssetCtx { coin }, Incoming::ActiveAssetCtx { coin: msg_coin, .. })
+            | (
+                Self::ActiveAssetCtx { coin },
+                Incoming::ActiveSpotAssetCtx { coin: msg_coin, .. },
+            ) => msg_coin == coin,
+            (Self::ActiveAssetData { user, coin }, Incoming::ActiveAssetData(data)) => {
+                data.user == *user && data.coin == *coin
+            }
+            (Self::UserFills { user }, Incoming::UserFills { user: msg_user, .. })
+            | (
+                Self::AllDexsClearinghouseState { user },
+                Incoming::AllDexsClearinghouseState { user: msg_user, .. },
+            )
+            | (Self::SpotState { user, .. }, Incoming::SpotState { user: msg_user, .. })
+            | (Self::UserFundings { user }, Incoming::UserFundings { user: msg_user, .. })
+            | (
+                Self::UserNonFundingLedgerUpdates { user },
+                Incoming::UserNonFundingLedgerUpdates { user: msg_user, .. },
+            ) => msg_user == user,
+            (Self::UserTwapSliceFills { user }, Incoming::UserTwapSliceFills(fills)) => {
+                fills.user == *user
+            }
+            (Self::UserTwapHistory { user }, Incoming::UserTwapHistory(history)) => {
+                history.user == *user
+            }
+            (
+                Self::ClearinghouseState { user, dex },
+                Incoming::ClearinghouseState {
+                    user: msg_user,
+                    dex: msg_dex,
+                    ..
+                },
+            )
+            | (
+                Self::OpenOrders { user, dex },
+                Incoming::OpenOrders {
+                    user: msg_user,
+                    dex: msg_dex,
+                    ..
+                },
+            )
+            | (
+                Self::TwapStates { user, dex },
+                Incoming::TwapStates {
+                    user: msg_user,
+                    dex: msg_dex,
+                    ..
+                },
+            ) => msg_user == user && same_dex(dex, msg_dex),
+            (Self::WebData2 { dex, .. }, Incoming::WebData2 { dex: msg_dex, .. }) => {
+                same_dex(dex, msg_dex)
+            }
+            (Self::OrderUpdates { .. }, Incoming::OrderUpdates(_))
+            | (Self::UserEvents { .. }, Incoming::UserEvents(_))
+            | (Self::Notification { .. }, Incoming::Notification { .. })
+            | (Self::WebData3 { .. }, Incoming::WebData3 { .. })
+            | (Self::AllDexsAssetCtxs, Incoming::AllDexsAssetCtxs { .. })
+            | (Self::FastAssetCtxs, Incoming::FastAssetCtxs(_))
+            | (Self::OutcomeMetaUpdates, Incoming::OutcomeMetaUpdates(_))
+            | (Self::ExplorerBlock, Incoming::ExplorerBlock(_))
+            | (Self::ExplorerTxs, Incoming::ExplorerTxs(_)) => true,
+            _ => false,
+        }
+    }
 }
 
 /// Hyperliquid websocket message.
@@ -482,6 +715,7 @@ pub enum Subscription {
 /// - **ActiveAssetData**: User leverage and limits for a specific perp asset
 /// - **FastAssetCtxs**: Low-latency mark/mid price updates for all assets
 /// - **WebData2**: Frontend-style aggregate user snapshot
+/// - **ExplorerBlock** / **ExplorerTxs**: New blocks and transactions from the explorer
 /// - **Ping/Pong**: Heartbeat messages
 ///
 /// # Example
@@ -630,6 +864,12 @@ pub enum Incoming {
     ),
     /// Outcome market metadata updates
     OutcomeMetaUpdates(serde_json::Value),
+    /// New blocks from the explorer WebSocket
+    ExplorerBlock(Vec<BlockSummary>),
+    /// New transactions from the explorer WebSocket
+    ExplorerTxs(Vec<ExplorerTx>),
+    /// Response to a post request
+    Post(PostResponse),
     /// Server heartbeat ping
     Ping,
     /// Server heartbeat pong
@@ -1592,6 +1832,9 @@ pub struct TwapHistory {
     pub state: TwapState,
     pub status: TwapHistoryStatus,
     pub time: u64,
+    /// TWAP id, reported by the `twapHistory` info endpoint
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub twap_id: Option<u64>,
 }
 
 /// `userTwapHistory` feed payload.
@@ -1676,19 +1919,44 @@ pub struct WsBasicOrder {
     pub cloid: Option<B128>,
 }
 
+/// Open order as shown by the Hyperliquid frontend.
+///
+/// Returned by [`HttpClient::frontend_open_orders`](crate::hypercore::HttpClient::frontend_open_orders)
+/// and the `openOrders` WebSocket channel. Adds trigger details and the TP/SL orders
+/// attached to a parent order to the [`BasicOrder`] fields.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde_as]
 #[serde(rename_all = "camelCase")]
 pub struct OpenOrder {
+    /// Common order fields
     #[serde(flatten)]
     pub basic_order: BasicOrder,
+    /// Trigger condition, e.g. "Price above 10.0", or "N/A" for non-trigger orders
     pub trigger_condition: String,
+    /// Whether this is a trigger (stop/take-profit) order
     pub is_trigger: bool,
+    /// Trigger price, zero for non-trigger orders
     pub trigger_px: Decimal,
+    /// TP/SL orders that activate when this order fills
+    #[serde(default)]
     pub children: Vec<OpenOrder>,
+    /// Whether the order is part of a position-level TP/SL bracket
     pub is_position_tpsl: bool,
 }
 
+impl OpenOrder {
+    /// Returns the triggered order type (e.g. "Stop Market"), or `None` for plain orders.
+    #[must_use]
+    pub fn trigger_type(&self) -> Option<&OrderType> {
+        self.is_trigger.then_some(&self.basic_order.order_type)
+    }
+
+    /// Iterates over this order and all of its children, depth first.
+    pub fn iter_tree(&self) -> Box<dyn Iterator<Item = &OpenOrder> + '_> {
+        Box::new(std::iter::once(self).chain(self.children.iter().flat_map(OpenOrder::iter_tree)))
+    }
+}
+
 /// Liquidation details.
 ///
 /// Information about a liquidation event associated with a trade or fill.
@@ -2012,8 +2280,8 @@ impl UsdSend {
     ///
     /// # Parameters
     ///
-    /// - `signature_chain_id`: The chain ID for signature verification (e.g., [`super::ARBITRUM_MAINNET_CHAIN_ID`])
-    /// - `chain`: Whether this is mainnet or testnet
+    /// - `chain`: Chain to sign for; `signatureChainId` and `hyperliquidChain` are derived
+    ///   from it (see [`SigningContext`](super::SigningContext))
     ///
     /// # Example
     ///
@@ -2024,7 +2292,7 @@ impl UsdSend {
     ///     time: chrono::Utc::now().timestamp_millis() as u64,
     /// };
     ///
-    /// let action = send.into_action(ARBITRUM_MAINNET_CHAIN_ID, Chain::Mainnet);
+    /// let action = send.into_action(Chain::Mainnet);
     /// ```
     #[must_use]
     pub fn into_action(self, chain: Chain) -> UsdSendAction {
@@ -2061,8 +2329,8 @@ impl SpotSend {
     ///
     /// # Parameters
     ///
-    /// - `signature_chain_id`: The chain ID for signature verification (e.g., [`super::ARBITRUM_MAINNET_CHAIN_ID`])
-    /// - `chain`: Whether this is mainnet or testnet
+    /// - `chain`: Chain to sign for; `signatureChainId` and `hyperliquidChain` are derived
+    ///   from it (see [`SigningContext`](super::SigningContext))
     ///
     /// # Example
     ///
@@ -2074,7 +2342,7 @@ impl SpotSend {
     ///     time: chrono::Utc::now().timestamp_millis() as u64,
     /// };
     ///
-    /// let action = send.into_action(ARBITRUM_MAINNET_CHAIN_ID, Chain::Mainnet);
+    /// let action = send.into_action(Chain::Mainnet);
     /// ```
     #[must_use]
     pub fn into_action(self, chain: Chain) -> SpotSendAction {
@@ -2148,8 +2416,8 @@ impl SendAsset {
     ///
     /// # Parameters
     ///
-    /// - `signature_chain_id`: The chain ID for signature verification (e.g., [`super::ARBITRUM_MAINNET_CHAIN_ID`])
-    /// - `chain`: Whether this is mainnet or testnet
+    /// - `chain`: Chain to sign for; `signatureChainId` and `hyperliquidChain` are derived
+    ///   from it (see [`SigningContext`](super::SigningContext))
     ///
     /// # Example
     ///
@@ -2164,7 +2432,7 @@ impl SendAsset {
     ///     nonce: 12345,
     /// };
     ///
-    /// let action = send.into_action(ARBITRUM_MAINNET_CHAIN_ID, Chain::Mainnet);
+    /// let action = send.into_action(Chain::Mainnet);
     /// ```
     #[must_use]
     pub fn into_action(self, chain: Chain) -> SendAssetAction {
@@ -2333,6 +2601,117 @@ impl OrderResponseStatus {
     }
 }
 
+/// Exchange action result annotated with request metadata.
+///
+/// The exchange does not echo a server timestamp on `/exchange` responses, so the
+/// client records when the request left and when the response was parsed. This is
+/// enough for round-trip latency measurement and for correlating a response with
+/// the nonce that produced it.
+#[derive(Debug)]
+pub struct ActionResponse<T> {
+    /// Response payload.
+    pub data: T,
+    /// Nonce of the signed request.
+    pub nonce: u64,
+    /// Time the request was handed to the HTTP client.
+    pub sent_at: DateTime<Utc>,
+    /// Time the response body was received and parsed.
+    pub received_at: DateTime<Utc>,
+    /// Round-trip time, measured on a monotonic clock.
+    pub elapsed: Duration,
+}
+
+impl<T> ActionResponse<T> {
+    /// Returns the round-trip latency of the request.
+    ///
+    /// Unlike `received_at - sent_at`, this isn't skewed by wall clock adjustments.
+    #[must_use]
+    pub fn latency(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Maps the payload, keeping the request metadata.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> ActionResponse<U> {
+        ActionResponse {
+            data: f(self.data),
+            nonce: self.nonce,
+            sent_at: self.sent_at,
+            received_at: self.received_at,
+            elapsed: self.elapsed,
+        }
+    }
+
+    /// Discards the metadata and returns the payload.
+    pub fn into_inner(self) -> T {
+        self.data
+    }
+}
+
+/// Status of a single order within a batch, tied back to the request that produced it.
+///
+/// The exchange returns one status per submitted order, in submission order.
+#[derive(Debug)]
+pub struct IndexedOrderStatus {
+    /// Position of the order in [`BatchOrder::orders`].
+    pub index: usize,
+    /// Client order ID submitted with the order (`Cloid::ZERO` if none was set).
+    pub cloid: Cloid,
+    /// Exchange status for the order.
+    pub status: OrderResponseStatus,
+}
+
+/// Batch order statuses keyed by the submitted client order ID.
+///
+/// Built from the positional status list returned by the exchange. If the exchange
+/// returns fewer statuses than submitted orders, the unanswered cloids are listed in
+/// [`missing`](Self::missing) instead of being silently dropped or mis-associated.
+#[derive(Debug, Default)]
+pub struct CloidStatuses {
+    /// Status for every order the exchange answered.
+    pub statuses: HashMap<Cloid, OrderResponseStatus>,
+    /// Cloids of submitted orders for which no status was returned.
+    pub missing: Vec<Cloid>,
+}
+
+impl CloidStatuses {
+    /// Associates positional statuses with the cloids of the submitted orders.
+    ///
+    /// `cloids` must be in submission order. Extra statuses (more than cloids) are ignored.
+    #[must_use]
+    pub fn new(cloids: Vec<Cloid>, statuses: Vec<OrderResponseStatus>) -> Self {
+        let mut statuses = statuses.into_iter();
+        let mut out = Self::default();
+        for cloid in cloids {
+            match statuses.next() {
+                Some(status) => {
+                    out.statuses.insert(cloid, status);
+                }
+                None => out.missing.push(cloid),
+            }
+        }
+        out
+    }
+
+    /// Returns the status for a submitted cloid, if one was returned.
+    #[must_use]
+    pub fn get(&self, cloid: &Cloid) -> Option<&OrderResponseStatus> {
+        self.statuses.get(cloid)
+    }
+
+    /// Returns true if every submitted order received a status.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty()
+    }
+
+    /// Iterates over the cloids whose orders were rejected, with the error message.
+    pub fn errors(&self) -> impl Iterator<Item = (&Cloid, &str)> {
+        self.statuses
+            .iter()
+            .filter_map(|(cloid, status)| status.error().map(|err| (cloid, err)))
+    }
+}
+
 /// Batch order submission.
 ///
 /// A collection of orders sent together in a single transaction, optionally grouped
@@ -2411,6 +2790,35 @@ pub struct BatchOrder {
     pub builder: Option<Builder>,
 }
 
+impl BatchOrder {
+    /// Checks that no two orders of the batch share a cloid.
+    ///
+    /// Orders without a cloid (`Cloid::ZERO`) are ignored.
+    pub fn check_cloids(&self) -> Result<(), DuplicateCloid> {
+        self.check_cloids_against(&HashSet::new())
+    }
+
+    /// Checks that no two orders of the batch share a cloid, and that none uses a
+    /// cloid in `live`, the cloids of the account's open orders.
+    ///
+    /// Orders without a cloid (`Cloid::ZERO`) are ignored.
+    pub fn check_cloids_against(&self, live: &HashSet<Cloid>) -> Result<(), DuplicateCloid> {
+        let mut seen = HashSet::with_capacity(self.orders.len());
+        for cloid in self.orders.iter().map(|req| req.cloid) {
+            if cloid.is_zero() {
+                continue;
+            }
+            if live.contains(&cloid) {
+                return Err(DuplicateCloid { cloid, live: true });
+            }
+            if !seen.insert(cloid) {
+                return Err(DuplicateCloid { cloid, live: false });
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Builder fee metadata attached to an order action.
 ///
 /// Serialized under the `builder` key as `{ "b": <address>, "f": <tenths_of_bps> }`.
@@ -2572,6 +2980,40 @@ pub struct Modify {
     pub order: OrderRequest,
 }
 
+impl Modify {
+    /// Modifies the order with the exchange-assigned `oid`.
+    #[must_use]
+    pub fn by_oid(oid: u64, order: OrderRequest) -> Self {
+        Self {
+            oid: OidOrCloid::Left(oid),
+            order,
+        }
+    }
+
+    /// Modifies the order with the client-assigned `cloid`.
+    #[must_use]
+    pub fn by_cloid(cloid: Cloid, order: OrderRequest) -> Self {
+        Self {
+            oid: OidOrCloid::Right(cloid),
+            order,
+        }
+    }
+}
+
+impl From<Vec<Modify>> for BatchModify {
+    fn from(modifies: Vec<Modify>) -> Self {
+        Self { modifies }
+    }
+}
+
+impl From<Modify> for BatchModify {
+    fn from(modify: Modify) -> Self {
+        Self {
+            modifies: vec![modify],
+        }
+    }
+}
+
 /// Batch cancel request.
 ///
 /// Contains a list of order IDs to cancel.
@@ -2617,13 +3059,32 @@ pub struct CancelByCloid {
 
 /// Schedule cancellation of all orders.
 ///
-/// The optional `time` field can be used to delay the cancellation.
+/// `time` is the Unix timestamp (ms) at which all open orders are canceled; it must be
+/// at least 5 seconds in the future. Sending `None` clears a previously armed timer,
+/// in which case the field is omitted from the action entirely.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ScheduleCancel {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub time: Option<u64>,
 }
 
+impl ScheduleCancel {
+    /// Arms the dead man's switch to cancel all orders at `time`.
+    #[must_use]
+    pub fn at(time: DateTime<Utc>) -> Self {
+        Self {
+            time: Some(time.timestamp_millis() as u64),
+        }
+    }
+
+    /// Clears a previously scheduled cancellation.
+    #[must_use]
+    pub fn clear() -> Self {
+        Self { time: None }
+    }
+}
+
 /// Clearinghouse state for a user's perpetual positions.
 ///
 /// # Example
@@ -3081,10 +3542,10 @@ pub struct UserBalance {
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UserFees {
-    /// Daily user volume breakdown by date.
-    pub daily_user_vlm: serde_json::Value,
-    /// Fee schedule details.
-    pub fee_schedule: serde_json::Value,
+    /// Daily volume, oldest first, covering the window fee tiers are computed on.
+    pub daily_user_vlm: Vec<DailyUserVolume>,
+    /// Base rates and tiers of the exchange.
+    pub fee_schedule: FeeSchedule,
     /// Effective perpetual maker fee rate.
     #[serde(rename = "userAddRate")]
     pub maker_rate: Decimal,
@@ -3102,12 +3563,15 @@ pub struct UserFees {
     /// Whether the user is in a fee trial period.
     #[serde(default)]
     pub trial: Option<serde_json::Value>,
+    /// Reward accrued during the fee trial.
+    #[serde(default)]
+    pub fee_trial_reward: Option<Decimal>,
     /// Link to staking discount.
     #[serde(default)]
     pub staking_link: Option<serde_json::Value>,
     /// Active staking discount.
     #[serde(default)]
-    pub active_staking_discount: Option<serde_json::Value>,
+    pub active_staking_discount: Option<StakingDiscountTier>,
     /// Fee trial escrow.
     #[serde(default)]
     pub fee_trial_escrow: Option<String>,
@@ -3116,6 +3580,144 @@ pub struct UserFees {
     pub next_trial_available_timestamp: Option<u64>,
 }
 
+impl UserFees {
+    /// Returns the user's volume over the fee window (taker plus maker).
+    ///
+    /// Fee tiers are computed on 14-day volume; the API returns the days of the
+    /// current window.
+    #[must_use]
+    pub fn volume_14d(&self) -> Decimal {
+        self.daily_user_vlm.iter().map(DailyUserVolume::total).sum()
+    }
+
+    /// Returns the share of the user's volume that was maker volume.
+    #[must_use]
+    pub fn maker_volume_share(&self) -> Option<Decimal> {
+        let total = self.volume_14d();
+        if total.is_zero() {
+            return None;
+        }
+        let maker: Decimal = self.daily_user_vlm.iter().map(|day| day.user_add).sum();
+        Some(maker / total)
+    }
+
+    /// Returns the active staking discount, as a fraction of the fee.
+    #[must_use]
+    pub fn staking_discount(&self) -> Decimal {
+        self.active_staking_discount
+            .as_ref()
+            .map_or(Decimal::ZERO, |tier| tier.discount)
+    }
+
+    /// Returns the fee rate that applies to an order.
+    #[must_use]
+    pub fn rate(&self, is_maker: bool, is_spot: bool) -> Decimal {
+        match (is_maker, is_spot) {
+            (true, false) => self.maker_rate,
+            (false, false) => self.taker_rate,
+            (true, true) => self.spot_maker_rate,
+            (false, true) => self.spot_taker_rate,
+        }
+    }
+
+    /// Returns the fee paid (or, when negative, the rebate received) for `notional`.
+    #[must_use]
+    pub fn expected_fee(&self, notional: Decimal, is_maker: bool, is_spot: bool) -> Decimal {
+        notional * self.rate(is_maker, is_spot)
+    }
+}
+
+/// A user's volume on one day, from [`UserFees::daily_user_vlm`].
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyUserVolume {
+    /// Day, as `YYYY-MM-DD`
+    pub date: String,
+    /// Taker volume
+    pub user_cross: Decimal,
+    /// Maker volume
+    pub user_add: Decimal,
+    /// Volume of the whole exchange
+    pub exchange: Decimal,
+}
+
+impl DailyUserVolume {
+    /// Returns the user's taker and maker volume.
+    #[must_use]
+    pub fn total(&self) -> Decimal {
+        self.user_cross + self.user_add
+    }
+}
+
+/// Exchange fee schedule, from [`UserFees::fee_schedule`].
+///
+/// Rates are fractions of notional: `0.00045` is 4.5 bps.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeSchedule {
+    /// Base perpetual taker rate
+    pub cross: Decimal,
+    /// Base perpetual maker rate
+    pub add: Decimal,
+    /// Base spot taker rate
+    pub spot_cross: Decimal,
+    /// Base spot maker rate
+    pub spot_add: Decimal,
+    /// Volume and maker rebate tiers
+    pub tiers: FeeTiers,
+    /// Discount for referred users
+    pub referral_discount: Decimal,
+    /// Discounts by HYPE staked
+    #[serde(default)]
+    pub staking_discount_tiers: Vec<StakingDiscountTier>,
+}
+
+/// Fee tiers of a [`FeeSchedule`].
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeTiers {
+    /// Tiers by 14-day volume, lowest first
+    pub vip: Vec<VipFeeTier>,
+    /// Maker rebate tiers by share of exchange maker volume, lowest first
+    pub mm: Vec<MakerRebateTier>,
+}
+
+/// Rates for users above a 14-day volume.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VipFeeTier {
+    /// Minimum 14-day volume
+    pub ntl_cutoff: Decimal,
+    /// Perpetual taker rate
+    pub cross: Decimal,
+    /// Perpetual maker rate
+    pub add: Decimal,
+    /// Spot taker rate
+    pub spot_cross: Decimal,
+    /// Spot maker rate
+    pub spot_add: Decimal,
+}
+
+/// Maker rate for users above a share of exchange maker volume.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MakerRebateTier {
+    /// Minimum share of 14-day exchange maker volume
+    pub maker_fraction_cutoff: Decimal,
+    /// Maker rate, negative for a rebate
+    pub add: Decimal,
+}
+
+/// Fee discount for staking a share of the HYPE supply.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StakingDiscountTier {
+    /// Minimum stake, in basis points of the max supply
+    pub bps_of_max_supply: Decimal,
+    /// Discount, as a fraction of the fee
+    pub discount: Decimal,
+}
+
 /// User rate limit information.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -3131,20 +3733,55 @@ pub struct UserRateLimit {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PerpAssetCtx {
+    /// 24h notional volume
     pub day_ntl_vlm: Decimal,
+    /// Current hourly funding rate
     pub funding: Decimal,
+    /// Impact prices [bid, ask] for funding calculation
     #[serde(default)]
     pub impact_pxs: Option<Vec<String>>,
+    /// Mark price (used for liquidations)
     pub mark_px: Decimal,
+    /// Mid price between best bid/ask, absent when the book is one-sided
     pub mid_px: Option<Decimal>,
+    /// Total open interest, in contracts
     pub open_interest: Decimal,
+    /// Oracle price from external feed
     pub oracle_px: Decimal,
+    /// Premium component of funding
     pub premium: Option<Decimal>,
+    /// Previous day closing price
     pub prev_day_px: Decimal,
+    /// 24h base volume
     #[serde(default)]
     pub day_base_vlm: Option<Decimal>,
 }
 
+impl PerpAssetCtx {
+    /// Returns the annualized funding rate.
+    #[must_use]
+    pub fn annualized_rate(&self) -> Decimal {
+        self.funding * Decimal::from(24 * 365)
+    }
+
+    /// Returns the open interest in USD at the mark price.
+    #[must_use]
+    pub fn open_interest_usd(&self) -> Decimal {
+        self.open_interest * self.mark_px
+    }
+}
+
+/// A perpetual's market context, as returned by
+/// [`HttpClient::perp_contexts`](crate::hypercore::HttpClient::perp_contexts).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PerpContext {
+    /// Perpetual name (e.g. `"BTC"`, or `"xyz:TSLA"` on HIP-3 DEXs)
+    pub coin: String,
+    /// Live market context
+    #[serde(flatten)]
+    pub ctx: PerpAssetCtx,
+}
+
 /// Spot asset context.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -3155,59 +3792,336 @@ pub struct SpotAssetCtx {
     pub prev_day_px: Decimal,
 }
 
-/// User funding delta.
+/// A single funding payment.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UserFundingDelta {
+    /// Always `"funding"`
     #[serde(rename = "type")]
     pub delta_type: String,
+    /// Perpetual the payment applies to
     pub coin: String,
+    /// USDC received (positive) or paid (negative)
     pub usdc: Decimal,
+    /// Signed position size at the time of payment
     pub szi: Decimal,
+    /// Hourly funding rate applied
     pub funding_rate: Decimal,
+    /// Number of premium samples the rate was computed from
     #[serde(default)]
     pub n_samples: Option<u64>,
 }
 
-/// User funding entry.
+/// Funding payment returned by [`HttpClient::user_funding`](crate::hypercore::HttpClient::user_funding).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UserFundingEntry {
     pub delta: UserFundingDelta,
     pub hash: String,
+    /// Payment timestamp in milliseconds
     pub time: u64,
 }
 
+/// Venue of a predicted funding rate.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, derive_more::Display)]
+pub enum FundingVenue {
+    /// Hyperliquid perpetuals
+    #[serde(rename = "HlPerp")]
+    #[display("HlPerp")]
+    Hyperliquid,
+    /// Binance perpetuals
+    #[serde(rename = "BinPerp")]
+    #[display("BinPerp")]
+    Binance,
+    /// Bybit perpetuals
+    #[serde(rename = "BybitPerp")]
+    #[display("BybitPerp")]
+    Bybit,
+    /// A venue this version doesn't know about
+    #[serde(untagged)]
+    #[display("{_0}")]
+    Other(String),
+}
+
 /// Predicted funding for a venue.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PredictedFundingVenue {
+    /// Predicted rate for the venue's next funding interval
     pub funding_rate: Decimal,
+    /// Next funding timestamp in milliseconds
     pub next_funding_time: u64,
+    /// Length of the venue's funding interval (1 on Hyperliquid, usually 8 elsewhere)
+    #[serde(default)]
+    pub funding_interval_hours: Option<u32>,
 }
 
-/// Staking delegation entry.
-#[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct Delegation {
-    pub validator: Address,
-    pub amount: Decimal,
-    pub locked_until_timestamp: Option<u64>,
+impl PredictedFundingVenue {
+    /// Returns the predicted rate per hour, so venues with different intervals compare.
+    ///
+    /// Assumes an hourly interval when the venue doesn't report one.
+    #[must_use]
+    pub fn hourly_rate(&self) -> Decimal {
+        self.funding_rate / Decimal::from(self.funding_interval_hours.unwrap_or(1).max(1))
+    }
 }
 
-/// Delegation summary.
+/// Predicted funding rates of a coin across venues, as returned by
+/// [`HttpClient::predicted_fundings`](crate::hypercore::HttpClient::predicted_fundings).
+///
+/// ## Response shape
+///
+/// The raw JSON is a `[coin, [[venue, funding | null], ...]]` pair. Venues that don't
+/// list the coin are `null` and left out of [`venues`](Self::venues).
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct DelegatorSummary {
-    pub delegated: Decimal,
-    pub undelegated: Decimal,
-    pub total_pending_withdrawal: Decimal,
-    pub n_pending_withdrawals: u64,
+#[serde(from = "RawPredictedFunding")]
+pub struct PredictedFunding {
+    /// Coin name
+    pub coin: String,
+    /// Predicted funding on each venue listing the coin
+    pub venues: Vec<(FundingVenue, PredictedFundingVenue)>,
 }
 
-/// Perp deploy auction status.
-#[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "camelCase")]
+impl PredictedFunding {
+    /// Returns the predicted funding on `venue`.
+    #[must_use]
+    pub fn venue(&self, venue: &FundingVenue) -> Option<&PredictedFundingVenue> {
+        self.venues
+            .iter()
+            .find(|(v, _)| v == venue)
+            .map(|(_, funding)| funding)
+    }
+
+    /// Returns the hourly rate on Hyperliquid minus the hourly rate on `venue`.
+    ///
+    /// Positive when Hyperliquid longs pay more than longs on `venue`, i.e. shorting
+    /// on Hyperliquid and going long on `venue` collects the difference.
+    #[must_use]
+    pub fn hourly_spread(&self, venue: &FundingVenue) -> Option<Decimal> {
+        let hl = self.venue(&FundingVenue::Hyperliquid)?;
+        let other = self.venue(venue)?;
+        Some(hl.hourly_rate() - other.hourly_rate())
+    }
+}
+
+// Deserializes [coin, [[venue, funding | null]]] → PredictedFunding.
+#[derive(Deserialize)]
+struct RawPredictedFunding(String, Vec<(FundingVenue, Option<PredictedFundingVenue>)>);
+
+impl From<RawPredictedFunding> for PredictedFunding {
+    fn from(raw: RawPredictedFunding) -> Self {
+        Self {
+            coin: raw.0,
+            venues: raw
+                .1
+                .into_iter()
+                .filter_map(|(venue, funding)| Some((venue, funding?)))
+                .collect(),
+        }
+    }
+}
+
+/// Staking delegation entry.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Delegation {
+    pub validator: Address,
+    pub amount: Decimal,
+    pub locked_until_timestamp: Option<u64>,
+}
+
+/// Delegation summary.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DelegatorSummary {
+    pub delegated: Decimal,
+    pub undelegated: Decimal,
+    pub total_pending_withdrawal: Decimal,
+    pub n_pending_withdrawals: u64,
+}
+
+/// Delegation history entry from `delegatorHistory`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DelegatorEvent {
+    pub time: u64,
+    pub hash: String,
+    pub delta: DelegatorDelta,
+}
+
+/// Change to a user's staking balances.
+#[derive(Debug, Clone, Serialize, Deserialize, derive_more::IsVariant)]
+#[serde(rename_all = "camelCase")]
+pub enum DelegatorDelta {
+    /// Stake delegated to or undelegated from a validator.
+    #[serde(rename_all = "camelCase")]
+    Delegate {
+        validator: Address,
+        amount: Decimal,
+        is_undelegate: bool,
+    },
+    /// HYPE moved from spot to the staking balance.
+    CDeposit { amount: Decimal },
+    /// HYPE moved from the staking balance back to spot.
+    Withdrawal {
+        amount: Decimal,
+        phase: WithdrawalPhase,
+    },
+}
+
+/// Progress of a staking withdrawal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WithdrawalPhase {
+    /// Queued, waiting out the unstaking period
+    Initiated,
+    /// Credited to the spot balance
+    Finalized,
+    #[serde(other)]
+    Unknown,
+}
+
+/// Staking reward entry from `delegatorRewards`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DelegatorReward {
+    pub time: u64,
+    pub source: RewardSource,
+    pub total_amount: Decimal,
+}
+
+/// Origin of a staking reward.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RewardSource {
+    /// Rewards on the user's own delegations
+    Delegation,
+    /// Validator commission
+    Commission,
+    #[serde(other)]
+    Unknown,
+}
+
+/// Validator entry from `validatorSummaries`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorSummary {
+    /// Validator address, used when delegating
+    pub validator: Address,
+    /// Address signing blocks
+    pub signer: Address,
+    pub name: String,
+    pub description: String,
+    /// Blocks produced recently
+    pub n_recent_blocks: u64,
+    /// Total stake in HYPE wei (8 decimals)
+    pub stake: u64,
+    pub is_jailed: bool,
+    #[serde(default)]
+    pub unjailable_after: Option<u64>,
+    pub is_active: bool,
+    /// Commission rate (e.g. `0.04` for 4%)
+    pub commission: Decimal,
+    /// Uptime and APR per window
+    pub stats: Vec<(ValidatorStatsWindow, ValidatorStats)>,
+}
+
+impl ValidatorSummary {
+    /// Total stake in HYPE.
+    #[must_use]
+    pub fn stake_hype(&self) -> Decimal {
+        Decimal::from(self.stake) / Decimal::from(10u64.pow(api::HYPE_WEI_DECIMALS))
+    }
+
+    /// Stats over `window`, if reported.
+    #[must_use]
+    pub fn stats(&self, window: ValidatorStatsWindow) -> Option<&ValidatorStats> {
+        self.stats
+            .iter()
+            .find_map(|(w, stats)| (*w == window).then_some(stats))
+    }
+
+    /// Uptime fraction over the last day.
+    #[must_use]
+    pub fn uptime(&self) -> Option<Decimal> {
+        self.stats(ValidatorStatsWindow::Day)
+            .map(|stats| stats.uptime_fraction)
+    }
+}
+
+/// Window of a validator's stats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ValidatorStatsWindow {
+    Day,
+    Week,
+    Month,
+    #[serde(other)]
+    Unknown,
+}
+
+/// Validator performance over a window.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorStats {
+    /// Fraction of the window the validator was up
+    pub uptime_fraction: Decimal,
+    /// Predicted staking APR
+    pub predicted_apr: Decimal,
+    pub n_samples: u64,
+}
+
+/// Exchange status, returned by the `exchangeStatus` info endpoint.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExchangeStatus {
+    /// Server time in milliseconds
+    pub time: u64,
+    /// Special statuses such as maintenance, if any
+    #[serde(default)]
+    pub special_statuses: Option<serde_json::Value>,
+}
+
+/// Offset of the server clock from the local clock, measured by
+/// [`HttpClient::measure_clock_skew`](crate::hypercore::HttpClient::measure_clock_skew).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClockSkew {
+    /// Server time minus local time; positive when the local clock is behind
+    pub offset: chrono::TimeDelta,
+    /// Round trip of the measuring request, which bounds the error of `offset` to half
+    /// of it
+    pub round_trip: Duration,
+}
+
+impl ClockSkew {
+    /// Estimates the skew from a server timestamp taken between the local times
+    /// `sent` and `received`, all in milliseconds.
+    ///
+    /// The server is assumed to have stamped the response halfway through the round
+    /// trip.
+    #[must_use]
+    pub fn new(sent: u64, server: u64, received: u64) -> Self {
+        let round_trip = received.saturating_sub(sent);
+        let midpoint = sent + round_trip / 2;
+        Self {
+            offset: chrono::TimeDelta::milliseconds(server as i64 - midpoint as i64),
+            round_trip: Duration::from_millis(round_trip),
+        }
+    }
+
+    /// Returns true if the offset exceeds `tolerance` in either direction.
+    #[must_use]
+    pub fn exceeds(&self, tolerance: Duration) -> bool {
+        self.offset
+            .abs()
+            .to_std()
+            .is_ok_and(|offset| offset > tolerance)
+    }
+}
+
+/// Perp deploy auction status.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct DeployAuctionStatus {
     pub start_time_seconds: u64,
     pub duration_seconds: u64,
@@ -3220,11 +4134,77 @@ pub struct DeployAuctionStatus {
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PerpDexLimits {
+    /// Open interest cap of the whole DEX, in USD notional
     pub total_oi_cap: Option<Decimal>,
+    /// Open interest cap of each perp, in contracts
     pub oi_sz_cap_per_perp: Option<Decimal>,
+    /// Largest notional transfer in or out of the DEX
     pub max_transfer_ntl: Option<Decimal>,
+    /// Open interest caps of individual perps, in USD notional
     #[serde(default)]
-    pub coin_to_oi_cap: Option<serde_json::Value>,
+    pub coin_to_oi_cap: Vec<(String, Decimal)>,
+}
+
+impl PerpDexLimits {
+    /// Returns the notional open interest cap of `coin`, if it has its own.
+    #[must_use]
+    pub fn oi_cap(&self, coin: &str) -> Option<Decimal> {
+        self.coin_to_oi_cap
+            .iter()
+            .find(|(name, _)| name == coin)
+            .map(|(_, cap)| *cap)
+    }
+
+    /// Returns how much of its caps each perp of the DEX uses.
+    ///
+    /// `contexts` are the DEX's market contexts, from
+    /// [`HttpClient::perp_contexts_from`](crate::hypercore::HttpClient::perp_contexts_from).
+    #[must_use]
+    pub fn utilization(&self, contexts: &[PerpContext]) -> Vec<OiUtilization> {
+        contexts
+            .iter()
+            .map(|perp| {
+                let ratio = |used: Decimal, cap: Option<Decimal>| {
+                    cap.filter(|cap| !cap.is_zero()).map(|cap| used / cap)
+                };
+                let open_interest = perp.ctx.open_interest;
+                let open_interest_ntl = open_interest * perp.ctx.mark_px;
+                OiUtilization {
+                    coin: perp.coin.clone(),
+                    open_interest,
+                    open_interest_ntl,
+                    ntl_utilization: ratio(open_interest_ntl, self.oi_cap(&perp.coin)),
+                    sz_utilization: ratio(open_interest, self.oi_sz_cap_per_perp),
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the share of [`total_oi_cap`](Self::total_oi_cap) used by the DEX.
+    #[must_use]
+    pub fn total_utilization(&self, contexts: &[PerpContext]) -> Option<Decimal> {
+        let cap = self.total_oi_cap.filter(|cap| !cap.is_zero())?;
+        let used: Decimal = contexts
+            .iter()
+            .map(|perp| perp.ctx.open_interest * perp.ctx.mark_px)
+            .sum();
+        Some(used / cap)
+    }
+}
+
+/// Open interest of a HIP-3 perp against the limits of its DEX.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OiUtilization {
+    /// Perp name
+    pub coin: String,
+    /// Open interest in contracts
+    pub open_interest: Decimal,
+    /// Open interest in USD notional, at the mark price
+    pub open_interest_ntl: Decimal,
+    /// Share of the perp's notional cap used, if it has one
+    pub ntl_utilization: Option<Decimal>,
+    /// Share of the DEX's per-perp size cap used, if it has one
+    pub sz_utilization: Option<Decimal>,
 }
 
 /// HIP-3 DEX status.
@@ -3251,20 +4231,79 @@ pub struct TokenDetails {
     pub mark_px: Option<Decimal>,
     #[serde(default)]
     pub prev_day_px: Option<Decimal>,
+    /// Balances the token was deployed with
     #[serde(default)]
-    pub genesis: Option<serde_json::Value>,
+    pub genesis: Option<TokenGenesis>,
     #[serde(default)]
     pub deployer: Option<Address>,
+    /// Gas paid in the deploy auction, in HYPE
     #[serde(default)]
-    pub deploy_gas: Option<u64>,
+    pub deploy_gas: Option<Decimal>,
+    /// Deployment time, as an ISO 8601 timestamp without time zone (UTC)
     #[serde(default)]
-    pub deploy_time: Option<u64>,
+    pub deploy_time: Option<String>,
     #[serde(default)]
     pub seeded_usdc: Option<Decimal>,
     #[serde(default)]
-    pub future_emissions: Option<serde_json::Value>,
+    pub future_emissions: Option<Decimal>,
+    /// Balances excluded from the circulating supply, such as team and treasury
+    /// wallets
+    #[serde(default)]
+    pub non_circulating_user_balances: Vec<(Address, Decimal)>,
+}
+
+impl TokenDetails {
+    /// Returns the supply held by non-circulating wallets.
+    #[must_use]
+    pub fn non_circulating_supply(&self) -> Decimal {
+        self.non_circulating_user_balances
+            .iter()
+            .map(|(_, balance)| balance)
+            .sum()
+    }
+}
+
+/// Initial distribution of a spot token, from [`TokenDetails::genesis`].
+///
+/// Holders of existing tokens can be included in a genesis by token index rather than
+/// address; their share is listed in
+/// [`existing_token_balances`](Self::existing_token_balances).
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenGenesis {
+    /// Balances assigned to addresses
+    #[serde(default)]
+    pub user_balances: Vec<(Address, Decimal)>,
+    /// Balances distributed pro rata to the holders of existing tokens, by token index
     #[serde(default)]
-    pub non_circulating_user_balances: Option<serde_json::Value>,
+    pub existing_token_balances: Vec<(u32, Decimal)>,
+    /// Addresses excluded from the distributions to existing token holders
+    #[serde(default)]
+    pub blacklist_users: Vec<Address>,
+}
+
+impl TokenGenesis {
+    /// Returns the total amount assigned at genesis, to addresses and to existing
+    /// token holders.
+    #[must_use]
+    pub fn total(&self) -> Decimal {
+        let users: Decimal = self.user_balances.iter().map(|(_, balance)| balance).sum();
+        let existing: Decimal = self
+            .existing_token_balances
+            .iter()
+            .map(|(_, balance)| balance)
+            .sum();
+        users + existing
+    }
+
+    /// Returns the `n` largest genesis balances, largest first.
+    #[must_use]
+    pub fn top_holders(&self, n: usize) -> Vec<(Address, Decimal)> {
+        let mut holders = self.user_balances.clone();
+        holders.sort_by_key(|h| std::cmp::Reverse(h.1));
+        holders.truncate(n);
+        holders
+    }
 }
 
 impl UserBalance {
@@ -3481,6 +4520,23 @@ pub struct VaultDetails {
     pub always_close_on_withdraw: bool,
 }
 
+impl VaultDetails {
+    /// Returns the parent and child links of the vault.
+    #[must_use]
+    pub fn relationships(&self) -> VaultRelationships {
+        let (parent, children) = match &self.relationship {
+            Some(VaultRelationship::Parent { child_addresses }) => (None, child_addresses.clone()),
+            Some(VaultRelationship::Child { parent_address }) => (Some(*parent_address), vec![]),
+            Some(VaultRelationship::Normal) | None => (None, vec![]),
+        };
+        VaultRelationships {
+            vault: self.vault_address,
+            parent,
+            children,
+        }
+    }
+}
+
 /// Raw gossip priority auction slot data returned by the Hyperliquid API.
 ///
 /// Each element of the outer `slots` array corresponds to one Dutch auction slot
@@ -3562,13 +4618,39 @@ impl From<RawGossipPriorityAuctionStatus> for GossipPriorityAuctionStatus {
     }
 }
 
-/// Vault relationship type.
-#[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct VaultRelationship {
-    /// Type of relationship
-    #[serde(rename = "type")]
-    pub relationship_type: VaultRelationshipType,
+/// Vault relationship.
+///
+/// Protocol vaults such as HLP are split into a parent vault that users deposit into
+/// and child vaults that trade; each side links to the other.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "camelCase")]
+pub enum VaultRelationship {
+    /// Standalone vault
+    Normal,
+    /// Parent vault of the given child vaults
+    Parent {
+        /// Child vault addresses
+        #[serde(rename = "childAddresses")]
+        child_addresses: Vec<Address>,
+    },
+    /// Child vault of the given parent vault
+    Child {
+        /// Parent vault address
+        #[serde(rename = "parentAddress")]
+        parent_address: Address,
+    },
+}
+
+impl VaultRelationship {
+    /// Returns the type of relationship.
+    #[must_use]
+    pub fn relationship_type(&self) -> VaultRelationshipType {
+        match self {
+            Self::Normal => VaultRelationshipType::Normal,
+            Self::Parent { .. } => VaultRelationshipType::Parent,
+            Self::Child { .. } => VaultRelationshipType::Child,
+        }
+    }
 }
 
 /// Type of vault relationship.
@@ -3576,23 +4658,334 @@ pub struct VaultRelationship {
 #[serde(rename_all = "lowercase")]
 pub enum VaultRelationshipType {
     /// Normal vault relationship
+    #[display("normal")]
     Normal,
+    /// Parent of child vaults
+    #[display("parent")]
+    Parent,
+    /// Child of a parent vault
+    #[display("child")]
+    Child,
 }
 
-/// Vault portfolio data for a specific time period.
+/// Parent and child links of a vault.
 ///
-/// Contains historical account value and PnL data.
+/// Returned by [`HttpClient::vault_relationships`](crate::hypercore::HttpClient::vault_relationships).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VaultRelationships {
+    /// Vault address
+    pub vault: Address,
+    /// Parent vault, if this is a child vault
+    pub parent: Option<Address>,
+    /// Child vaults, if this is a parent vault
+    pub children: Vec<Address>,
+}
+
+impl VaultRelationships {
+    /// Returns true if the vault is neither a parent nor a child.
+    #[must_use]
+    pub fn is_standalone(&self) -> bool {
+        self.parent.is_none() && self.children.is_empty()
+    }
+}
+
+/// A vault in the vault list, with its recent returns.
+///
+/// Returned by [`HttpClient::vault_summaries`](crate::hypercore::HttpClient::vault_summaries).
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct VaultPortfolio {
+pub struct VaultSummary {
+    /// Annual percentage return
+    pub apr: Decimal,
+    /// Sampled PnL over each period, oldest first
+    #[serde(default)]
+    pub pnls: Vec<(PortfolioPeriod, Vec<Decimal>)>,
+    /// Vault details
+    pub summary: VaultOverview,
+}
+
+/// Details of a [`VaultSummary`].
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultOverview {
+    /// Name of the vault
+    pub name: String,
+    /// Address of the vault
+    pub vault_address: Address,
+    /// Leader (manager) of the vault
+    pub leader: Address,
+    /// Total value locked
+    pub tvl: Decimal,
+    /// Whether the vault is closed
+    #[serde(default)]
+    pub is_closed: bool,
+    /// Relationship type
+    #[serde(default)]
+    pub relationship: Option<VaultRelationship>,
+    /// Creation time in milliseconds
+    #[serde(default)]
+    pub create_time_millis: u64,
+}
+
+/// Filter and page of vaults to return from
+/// [`HttpClient::vault_summaries`](crate::hypercore::HttpClient::vault_summaries).
+///
+/// `offset` and `limit` count vaults that pass the filter, in the order the server
+/// lists them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VaultFilter {
+    /// Skip closed vaults
+    pub open_only: bool,
+    /// Minimum total value locked
+    pub min_tvl: Option<Decimal>,
+    /// Only vaults led by this address
+    pub leader: Option<Address>,
+    /// Number of matching vaults to skip
+    pub offset: usize,
+    /// Maximum number of vaults to return
+    pub limit: Option<usize>,
+}
+
+impl VaultFilter {
+    /// Skips closed vaults.
+    #[must_use]
+    pub fn open_only(mut self) -> Self {
+        self.open_only = true;
+        self
+    }
+
+    /// Skips vaults with less than `min_tvl` locked.
+    #[must_use]
+    pub fn min_tvl(mut self, min_tvl: Decimal) -> Self {
+        self.min_tvl = Some(min_tvl);
+        self
+    }
+
+    /// Only returns vaults led by `leader`.
+    #[must_use]
+    pub fn leader(mut self, leader: Address) -> Self {
+        self.leader = Some(leader);
+        self
+    }
+
+    /// Skips the first `offset` matching vaults.
+    #[must_use]
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Returns at most `limit` vaults.
+    #[must_use]
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Returns true if `vault` passes the filter, ignoring `offset` and `limit`.
+    #[must_use]
+    pub fn matches(&self, vault: &VaultSummary) -> bool {
+        let summary = &vault.summary;
+        !(self.open_only && summary.is_closed)
+            && self.min_tvl.is_none_or(|min| summary.tvl >= min)
+            && self.leader.is_none_or(|leader| summary.leader == leader)
+    }
+}
+
+/// Vault portfolio data for a specific time period.
+pub type VaultPortfolio = PortfolioHistory;
+
+/// Account value and PnL curves of an account or vault over one period.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PortfolioHistory {
     /// Historical account values as (timestamp_ms, value) pairs
     pub account_value_history: Vec<(u64, Decimal)>,
-    /// Historical PnL values as (timestamp_ms, value) pairs
+    /// Historical PnL values as (timestamp_ms, value) pairs, cumulative since the
+    /// start of the period
     pub pnl_history: Vec<(u64, Decimal)>,
     /// Volume for the period
     pub vlm: Decimal,
 }
 
+impl PortfolioHistory {
+    /// Returns the latest account value.
+    #[must_use]
+    pub fn account_value(&self) -> Option<Decimal> {
+        self.account_value_history.last().map(|(_, value)| *value)
+    }
+
+    /// Returns the PnL over the period.
+    #[must_use]
+    pub fn pnl(&self) -> Option<Decimal> {
+        self.pnl_history.last().map(|(_, pnl)| *pnl)
+    }
+
+    /// Returns the largest peak-to-trough fall of the PnL curve, in USD.
+    ///
+    /// Uses PnL rather than account value so deposits and withdrawals don't count as
+    /// gains or drawdowns.
+    #[must_use]
+    pub fn max_drawdown(&self) -> Decimal {
+        let mut peak = Decimal::ZERO;
+        let mut drawdown = Decimal::ZERO;
+        for (_, pnl) in &self.pnl_history {
+            peak = peak.max(*pnl);
+            drawdown = drawdown.max(peak - pnl);
+        }
+        drawdown
+    }
+}
+
+/// Period of a [`PortfolioHistory`], or window of a [`LeaderboardEntry`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, derive_more::Display)]
+#[serde(rename_all = "camelCase")]
+pub enum PortfolioPeriod {
+    /// Last 24 hours
+    #[display("day")]
+    Day,
+    /// Last 7 days
+    #[display("week")]
+    Week,
+    /// Last 30 days
+    #[display("month")]
+    Month,
+    /// Since the account was created
+    #[display("allTime")]
+    AllTime,
+    /// Last 24 hours, perps only
+    #[display("perpDay")]
+    PerpDay,
+    /// Last 7 days, perps only
+    #[display("perpWeek")]
+    PerpWeek,
+    /// Last 30 days, perps only
+    #[display("perpMonth")]
+    PerpMonth,
+    /// Since the account was created, perps only
+    #[display("perpAllTime")]
+    PerpAllTime,
+    /// A period this version doesn't know about
+    #[serde(untagged)]
+    #[display("{_0}")]
+    Other(String),
+}
+
+/// Account value and PnL histories of a user, as returned by
+/// [`HttpClient::portfolio`](crate::hypercore::HttpClient::portfolio).
+///
+/// ## Response shape
+///
+/// The raw JSON is a list of `[period, history]` pairs.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(transparent)]
+pub struct Portfolio {
+    /// History of each period
+    pub periods: Vec<(PortfolioPeriod, PortfolioHistory)>,
+}
+
+impl Portfolio {
+    /// Returns the history over `period`.
+    #[must_use]
+    pub fn period(&self, period: &PortfolioPeriod) -> Option<&PortfolioHistory> {
+        self.periods
+            .iter()
+            .find(|(p, _)| p == period)
+            .map(|(_, history)| history)
+    }
+
+    /// Returns the history since the account was created.
+    #[must_use]
+    pub fn all_time(&self) -> Option<&PortfolioHistory> {
+        self.period(&PortfolioPeriod::AllTime)
+    }
+}
+
+/// Performance of a trader over one window of the leaderboard.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WindowPerformance {
+    /// PnL over the window
+    pub pnl: Decimal,
+    /// Return on investment over the window, as a fraction
+    pub roi: Decimal,
+    /// Traded volume over the window
+    pub vlm: Decimal,
+}
+
+/// A trader on the leaderboard.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LeaderboardEntry {
+    /// Trader's account
+    pub eth_address: Address,
+    /// Current account value
+    pub account_value: Decimal,
+    /// Performance of each window, as `[window, performance]` pairs
+    pub window_performances: Vec<(PortfolioPeriod, WindowPerformance)>,
+    /// Prize won in competitions
+    #[serde(default)]
+    pub prize: Decimal,
+    /// Display name, if the trader set one
+    #[serde(default)]
+    pub display_name: Option<String>,
+}
+
+impl LeaderboardEntry {
+    /// Returns the performance over `window`.
+    #[must_use]
+    pub fn window(&self, window: &PortfolioPeriod) -> Option<&WindowPerformance> {
+        self.window_performances
+            .iter()
+            .find(|(w, _)| w == window)
+            .map(|(_, performance)| performance)
+    }
+}
+
+/// The trader leaderboard, as returned by
+/// [`HttpClient::leaderboard`](crate::hypercore::HttpClient::leaderboard).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Leaderboard {
+    /// Every ranked trader
+    pub leaderboard_rows: Vec<LeaderboardEntry>,
+}
+
+impl Leaderboard {
+    /// Returns the entry of `user`, if ranked.
+    #[must_use]
+    pub fn entry(&self, user: Address) -> Option<&LeaderboardEntry> {
+        self.leaderboard_rows
+            .iter()
+            .find(|entry| entry.eth_address == user)
+    }
+
+    /// Returns the top `n` traders over `window`, ranked by `key` in descending order.
+    ///
+    /// Traders without a performance over `window` are left out.
+    ///
+    /// ```
+    /// # use hypersdk::hypercore::types::{Leaderboard, PortfolioPeriod};
+    /// # fn example(leaderboard: &Leaderboard) {
+    /// let top_roi = leaderboard.top(&PortfolioPeriod::Month, 10, |perf| perf.roi);
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn top(
+        &self,
+        window: &PortfolioPeriod,
+        n: usize,
+        key: impl Fn(&WindowPerformance) -> Decimal,
+    ) -> Vec<&LeaderboardEntry> {
+        let mut ranked: Vec<(Decimal, &LeaderboardEntry)> = self
+            .leaderboard_rows
+            .iter()
+            .filter_map(|entry| Some((key(entry.window(window)?), entry)))
+            .collect();
+        ranked.sort_by_key(|r| std::cmp::Reverse(r.0));
+        ranked.into_iter().take(n).map(|(_, entry)| entry).collect()
+    }
+}
+
 /// State of a user as a vault follower.
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -3980,6 +5373,8 @@ pub(super) enum InfoRequest {
     },
     /// Perp deploy auction status.
     PerpDeployAuctionStatus,
+    /// Server time and special exchange statuses.
+    ExchangeStatus,
     /// User leverage and trade-size limits for a specific asset (info endpoint).
     ActiveAssetData {
         user: Address,
@@ -4046,6 +5441,8 @@ pub(super) enum InfoRequest {
     DelegatorRewards {
         user: Address,
     },
+    /// Validator set with stake, commission, and uptime.
+    ValidatorSummaries,
     /// Borrow/lend user state.
     BorrowLendUserState {
         user: Address,
@@ -4064,6 +5461,10 @@ pub(super) enum InfoRequest {
     UserTwapSliceFills {
         user: Address,
     },
+    /// TWAP lifecycle history via info endpoint.
+    TwapHistory {
+        user: Address,
+    },
     /// L2 order book snapshot.
     L2Book {
         coin: String,
@@ -4123,6 +5524,28 @@ mod tests {
         assert!(res.is_ok());
     }
 
+    #[test]
+    fn test_cloid_statuses_missing() {
+        let a = Cloid::with_last_byte(1);
+        let b = Cloid::with_last_byte(2);
+        let c = Cloid::with_last_byte(3);
+
+        let statuses = vec![
+            OrderResponseStatus::Resting {
+                oid: 7,
+                cloid: None,
+            },
+            OrderResponseStatus::Error("Order must have minimum value of $10.".into()),
+        ];
+        let res = CloidStatuses::new(vec![a, b, c], statuses);
+
+        assert!(!res.is_complete());
+        assert_eq!(res.missing, vec![c]);
+        assert_eq!(res.get(&a).and_then(|s| s.oid()), Some(7));
+        assert!(res.get(&b).is_some_and(|s| s.is_err()));
+        assert_eq!(res.errors().count(), 1);
+    }
+
     #[test]
     fn test_signature_from_str_with_0x_prefix() {
         let hex_sig = "0x1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef1b";
@@ -4251,7 +5674,7 @@ mod tests {
     fn test_candle_subscription() {
         let sub = Subscription::Candle {
             coin: "BTC".to_string(),
-            interval: "1m".to_string(),
+            interval: CandleInterval::OneMinute,
         };
 
         let json = serde_json::to_string(&sub).unwrap();
@@ -4259,6 +5682,88 @@ mod tests {
         assert_eq!(sub, deserialized);
     }
 
+    #[test]
+    fn test_schedule_cancel_wire_format() {
+        let time = DateTime::from_timestamp_millis(1_700_000_000_000).unwrap();
+        let json = serde_json::to_value(Action::from(ScheduleCancel::at(time))).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "type": "scheduleCancel", "time": 1_700_000_000_000u64 })
+        );
+
+        // Clearing omits `time` instead of sending `null`.
+        let json = serde_json::to_value(Action::from(ScheduleCancel::clear())).unwrap();
+        assert_eq!(json, serde_json::json!({ "type": "scheduleCancel" }));
+    }
+
+    #[test]
+    fn test_batch_modify_wire_format() {
+        let order = OrderRequest {
+            asset: 0,
+            is_buy: true,
+            limit_px: Decimal::new(50_500, 0),
+            sz: Decimal::new(1, 1),
+            reduce_only: false,
+            order_type: OrderTypePlacement::Limit {
+                tif: TimeInForce::Gtc,
+            },
+            cloid: Cloid::ZERO,
+        };
+        let cloid = Cloid::with_last_byte(1);
+        let batch = BatchModify::from(vec![
+            Modify::by_oid(42, order.clone()),
+            Modify::by_cloid(cloid, order),
+        ]);
+
+        let json = serde_json::to_value(Action::from(batch)).unwrap();
+        assert_eq!(json["type"], "batchModify");
+        assert_eq!(json["modifies"][0]["oid"], 42);
+        assert_eq!(json["modifies"][1]["oid"], format!("{cloid:#x}"));
+        assert_eq!(json["modifies"][0]["order"]["p"], "50500");
+        assert_eq!(json["modifies"][0]["order"]["s"], "0.1");
+    }
+
+    #[test]
+    fn test_hip3_subscription_dex() {
+        let sub = Subscription::L2Book {
+            coin: dex_coin(Some("xyz"), "BTC"),
+            n_sig_figs: None,
+            mantissa: None,
+            fast: false,
+        };
+        assert_eq!(
+            serde_json::to_value(&sub).unwrap(),
+            serde_json::json!({ "type": "l2Book", "coin": "xyz:BTC" })
+        );
+        assert_eq!(sub.dex(), Some("xyz"));
+
+        let sub = Subscription::AllMids {
+            dex: Some("xyz".into()),
+        };
+        assert_eq!(
+            serde_json::to_value(&sub).unwrap(),
+            serde_json::json!({ "type": "allMids", "dex": "xyz" })
+        );
+        assert_eq!(sub.dex(), Some("xyz"));
+
+        assert_eq!(Subscription::Trades { coin: "BTC".into() }.dex(), None);
+    }
+
+    #[test]
+    fn test_candle_subscription_interval_wire_format() {
+        let sub = Subscription::Candle {
+            coin: "BTC".to_string(),
+            interval: CandleInterval::OneMonth,
+        };
+        assert_eq!(
+            serde_json::to_value(&sub).unwrap(),
+            serde_json::json!({ "type": "candle", "coin": "BTC", "interval": "1M" })
+        );
+
+        let bad = serde_json::json!({ "type": "candle", "coin": "BTC", "interval": "1min" });
+        assert!(serde_json::from_value::<Subscription>(bad).is_err());
+    }
+
     #[test]
     fn test_fast_asset_ctxs_subscription() {
         let sub = Subscription::FastAssetCtxs;
@@ -4297,6 +5802,439 @@ mod tests {
         assert_eq!(fast, deserialized);
     }
 
+    #[test]
+    fn test_user_fees_response() {
+        let fees: UserFees = serde_json::from_value(serde_json::json!({
+            "dailyUserVlm": [
+                {"date": "2025-05-23", "userCross": "1000.0", "userAdd": "3000.0", "exchange": "2852367.07"},
+                {"date": "2025-05-24", "userCross": "0.0", "userAdd": "4000.0", "exchange": "2952367.07"},
+            ],
+            "feeSchedule": {
+                "cross": "0.00045",
+                "add": "0.00015",
+                "spotCross": "0.0007",
+                "spotAdd": "0.0004",
+                "tiers": {
+                    "vip": [{
+                        "ntlCutoff": "5000000.0",
+                        "cross": "0.0004",
+                        "add": "0.00012",
+                        "spotCross": "0.0006",
+                        "spotAdd": "0.0003",
+                    }],
+                    "mm": [{"makerFractionCutoff": "0.005", "add": "-0.00001"}],
+                },
+                "referralDiscount": "0.04",
+                "stakingDiscountTiers": [
+                    {"bpsOfMaxSupply": "0.0", "discount": "0.0"},
+                    {"bpsOfMaxSupply": "0.0001", "discount": "0.05"},
+                ],
+            },
+            "userCrossRate": "0.000315",
+            "userAddRate": "0.000105",
+            "userSpotCrossRate": "0.00049",
+            "userSpotAddRate": "0.00028",
+            "activeReferralDiscount": "0.0",
+            "trial": null,
+            "feeTrialReward": "0.0",
+            "nextTrialAvailableTimestamp": null,
+            "stakingLink": null,
+            "activeStakingDiscount": {"bpsOfMaxSupply": "4.7577998927", "discount": "0.3"},
+        }))
+        .unwrap();
+
+        assert_eq!(fees.volume_14d(), Decimal::from(8000));
+        assert_eq!(fees.maker_volume_share(), Some(Decimal::new(875, 3)));
+        assert_eq!(fees.staking_discount(), Decimal::new(3, 1));
+        assert_eq!(fees.fee_schedule.tiers.mm[0].add, Decimal::new(-1, 5));
+        assert_eq!(
+            fees.expected_fee(Decimal::from(10_000), false, false),
+            Decimal::new(315, 2)
+        );
+    }
+
+    #[test]
+    fn test_token_details_genesis() {
+        let details: TokenDetails = serde_json::from_value(serde_json::json!({
+            "name": "TEST",
+            "maxSupply": "1852229076.12716007",
+            "totalSupply": "851681534.05516005",
+            "circulatingSupply": "851681534.05516005",
+            "szDecimals": 0,
+            "weiDecimals": 5,
+            "midPx": "3.2049",
+            "markPx": "3.2025",
+            "prevDayPx": "3.2025",
+            "genesis": {
+                "userBalances": [
+                    ["0x0000000000000000000000000000000000000001", "1000.0"],
+                    ["0xffffffffffffffffffffffffffffffffffffffff", "3000.0"],
+                ],
+                "existingTokenBalances": [[1, "500.0"]],
+            },
+            "deployer": "0x0000000000000000000000000000000000000001",
+            "deployGas": "100.0",
+            "deployTime": "2024-06-05T10:50:16.722",
+            "seededUsdc": "0.0",
+            "nonCirculatingUserBalances": [
+                ["0x0000000000000000000000000000000000000001", "250.0"],
+            ],
+            "futureEmissions": "0.0",
+        }))
+        .unwrap();
+
+        let genesis = details.genesis.as_ref().unwrap();
+        assert_eq!(genesis.total(), Decimal::from(4500));
+        assert_eq!(
+            genesis.top_holders(1),
+            [(Address::repeat_byte(0xff), Decimal::from(3000))]
+        );
+        assert!(genesis.blacklist_users.is_empty());
+        assert_eq!(details.deploy_gas, Some(Decimal::from(100)));
+        assert_eq!(details.non_circulating_supply(), Decimal::from(250));
+    }
+
+    #[test]
+    fn test_predicted_fundings_response() {
+        let fundings: Vec<PredictedFunding> = serde_json::from_value(serde_json::json!([
+            ["BTC", [
+                ["BinPerp", {
+                    "fundingRate": "0.0001",
+                    "nextFundingTime": 1733961600000u64,
+                    "fundingIntervalHours": 8
+                }],
+                ["HlPerp", {
+                    "fundingRate": "0.0000125",
+                    "nextFundingTime": 1733958000000u64,
+                    "fundingIntervalHours": 1
+                }],
+                ["BybitPerp", null],
+                ["OkxPerp", {
+                    "fundingRate": "0.00008",
+                    "nextFundingTime": 1733961600000u64,
+                    "fundingIntervalHours": 8
+                }]
+            ]]
+        ]))
+        .unwrap();
+
+        let btc = &fundings[0];
+        assert_eq!(btc.coin, "BTC");
+        assert_eq!(btc.venues.len(), 3);
+        assert!(btc.venue(&FundingVenue::Bybit).is_none());
+        assert_eq!(btc.venues[2].0, FundingVenue::Other("OkxPerp".into()));
+        // 0.01% per 8h on Binance is 0.00125% per hour, same as Hyperliquid.
+        assert_eq!(
+            btc.hourly_spread(&FundingVenue::Binance),
+            Some(Decimal::ZERO)
+        );
+    }
+
+    #[test]
+    fn test_perp_dex_limits_response() {
+        use rust_decimal::dec;
+
+        let limits: PerpDexLimits = serde_json::from_str(
+            r#"{
+                "totalOiCap": "1000000.0",
+                "oiSzCapPerPerp": "1000.0",
+                "maxTransferNtl": "100000.0",
+                "coinToOiCap": [["xyz:XYZ100", "500000.0"]]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(limits.oi_cap("xyz:XYZ100"), Some(dec!(500000)));
+        assert_eq!(limits.oi_cap("xyz:TSLA"), None);
+
+        let ctx = |coin: &str, open_interest: &str, mark_px: &str| -> PerpContext {
+            serde_json::from_value(serde_json::json!({
+                "coin": coin,
+                "dayNtlVlm": "0",
+                "funding": "0",
+                "markPx": mark_px,
+                "openInterest": open_interest,
+                "oraclePx": mark_px,
+                "prevDayPx": mark_px,
+            }))
+            .unwrap()
+        };
+        let contexts = [
+            ctx("xyz:XYZ100", "100", "2500"),
+            ctx("xyz:TSLA", "500", "400"),
+        ];
+        let utilization = limits.utilization(&contexts);
+        assert_eq!(utilization[0].open_interest_ntl, dec!(250000));
+        assert_eq!(utilization[0].ntl_utilization, Some(dec!(0.5)));
+        assert_eq!(utilization[1].ntl_utilization, None);
+        assert_eq!(utilization[1].sz_utilization, Some(dec!(0.5)));
+        assert_eq!(limits.total_utilization(&contexts), Some(dec!(0.45)));
+    }
+
+    #[test]
+    fn test_leaderboard_response() {
+        let leaderboard: Leaderboard = serde_json::from_str(
+            r#"{
+                "leaderboardRows": [
+                    {
+                        "ethAddress": "0x0000000000000000000000000000000000001234",
+                        "accountValue": "1500000.5",
+                        "windowPerformances": [
+                            ["day", {"pnl": "1200.0", "roi": "0.0008", "vlm": "250000.0"}],
+                            ["month", {"pnl": "90000.0", "roi": "0.064", "vlm": "8000000.0"}]
+                        ],
+                        "prize": 0,
+                        "displayName": "whale"
+                    },
+                    {
+                        "ethAddress": "0x0000000000000000000000000000000000005678",
+                        "accountValue": "2000.0",
+                        "windowPerformances": [
+                            ["month", {"pnl": "500.0", "roi": "0.33", "vlm": "40000.0"}]
+                        ],
+                        "prize": 0,
+                        "displayName": null
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let whale = leaderboard
+            .entry(
+                "0x0000000000000000000000000000000000001234"
+                    .parse()
+                    .unwrap(),
+            )
+            .unwrap();
+        assert_eq!(whale.display_name.as_deref(), Some("whale"));
+        assert_eq!(
+            whale.window(&PortfolioPeriod::Day).unwrap().pnl,
+            Decimal::from(1200)
+        );
+        assert!(whale.window(&PortfolioPeriod::Week).is_none());
+
+        let top = leaderboard.top(&PortfolioPeriod::Month, 1, |perf| perf.roi);
+        assert_eq!(top.len(), 1);
+        assert!(top[0].display_name.is_none());
+        assert_eq!(
+            leaderboard
+                .top(&PortfolioPeriod::Day, 10, |perf| perf.pnl)
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_vault_relationship_response() {
+        let parent: VaultRelationship = serde_json::from_str(
+            r#"{"type":"parent","data":{"childAddresses":["0x010461c14e146ac35fe42271bdc1134ee31c703a","0x31ca8395cf837de08b24da3f660e77761dfb974b"]}}"#,
+        )
+        .unwrap();
+        assert_eq!(parent.relationship_type(), VaultRelationshipType::Parent);
+
+        let child: VaultRelationship = serde_json::from_str(
+            r#"{"type":"child","data":{"parentAddress":"0xdfc24b077bc1425ad1dea75bcb6f8158e10df303"}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            child,
+            VaultRelationship::Child {
+                parent_address: "0xdfc24b077bc1425ad1dea75bcb6f8158e10df303"
+                    .parse()
+                    .unwrap(),
+            }
+        );
+
+        let normal: VaultRelationship = serde_json::from_str(r#"{"type":"normal"}"#).unwrap();
+        assert_eq!(normal, VaultRelationship::Normal);
+        assert_eq!(normal.relationship_type().to_string(), "normal");
+
+        let json = r#"{
+            "name": "Hyperliquidity Provider (HLP)",
+            "vaultAddress": "0xdfc24b077bc1425ad1dea75bcb6f8158e10df303",
+            "leader": "0x677d831aef5328190852e24f13c46cac05f984e7",
+            "description": "",
+            "portfolio": [],
+            "apr": "0.05",
+            "followerState": null,
+            "leaderFraction": "0.0001",
+            "leaderCommission": "0",
+            "followers": [],
+            "maxDistributable": "100",
+            "maxWithdrawable": "100",
+            "isClosed": false,
+            "relationship": {"type":"parent","data":{"childAddresses":["0x010461c14e146ac35fe42271bdc1134ee31c703a"]}},
+            "allowDeposits": true,
+            "alwaysCloseOnWithdraw": false
+        }"#;
+        let details: VaultDetails = serde_json::from_str(json).unwrap();
+        let relationships = details.relationships();
+        assert_eq!(relationships.parent, None);
+        assert_eq!(relationships.children.len(), 1);
+        assert!(!relationships.is_standalone());
+    }
+
+    #[test]
+    fn test_vault_summary_filter() {
+        use rust_decimal::dec;
+
+        let vaults: Vec<VaultSummary> = serde_json::from_str(
+            r#"[
+                {"apr":0.12,"pnls":[["day",["0.0","1.5"]]],"summary":{"name":"A","vaultAddress":"0x0000000000000000000000000000000000000001","leader":"0x00000000000000000000000000000000000000aa","tvl":"5000.0","isClosed":false,"relationship":{"type":"normal"},"createTimeMillis":1700000000000}},
+                {"apr":0.0,"pnls":[],"summary":{"name":"B","vaultAddress":"0x0000000000000000000000000000000000000002","leader":"0x00000000000000000000000000000000000000bb","tvl":"90.0","isClosed":true,"relationship":{"type":"normal"},"createTimeMillis":1700000000000}}
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(vaults[0].apr, dec!(0.12));
+        assert_eq!(vaults[0].pnls[0].0, PortfolioPeriod::Day);
+
+        let open = VaultFilter::default().open_only();
+        assert!(open.matches(&vaults[0]));
+        assert!(!open.matches(&vaults[1]));
+        assert!(
+            !VaultFilter::default()
+                .min_tvl(dec!(100))
+                .matches(&vaults[1])
+        );
+        let leader = "0x00000000000000000000000000000000000000bb"
+            .parse()
+            .unwrap();
+        assert!(VaultFilter::default().leader(leader).matches(&vaults[1]));
+        assert!(!VaultFilter::default().leader(leader).matches(&vaults[0]));
+    }
+
+    #[test]
+    fn test_exchange_status_clock_skew() {
+        let status: ExchangeStatus =
+            serde_json::from_str(r#"{"time":1700000005000,"specialStatuses":null}"#).unwrap();
+        assert_eq!(status.time, 1_700_000_005_000);
+
+        let skew = ClockSkew::new(1_700_000_000_000, status.time, 1_700_000_000_200);
+        assert_eq!(skew.offset, chrono::TimeDelta::milliseconds(4_900));
+        assert_eq!(skew.round_trip, Duration::from_millis(200));
+        assert!(skew.exceeds(Duration::from_secs(1)));
+        assert!(!skew.exceeds(Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn test_batch_order_duplicate_cloids() {
+        let order = |cloid| OrderRequest {
+            asset: 0,
+            is_buy: true,
+            limit_px: Decimal::new(50_500, 0),
+            sz: Decimal::new(1, 1),
+            reduce_only: false,
+            order_type: OrderTypePlacement::Limit {
+                tif: TimeInForce::Gtc,
+            },
+            cloid,
+        };
+        let first = Cloid::with_last_byte(1);
+        let second = Cloid::with_last_byte(2);
+        let mut batch = BatchOrder {
+            orders: vec![order(first), order(Cloid::ZERO), order(Cloid::ZERO)],
+            grouping: OrderGrouping::Na,
+            builder: None,
+        };
+        // Orders without a cloid never clash.
+        assert_eq!(batch.check_cloids(), Ok(()));
+
+        let live = HashSet::from([second]);
+        assert_eq!(batch.check_cloids_against(&live), Ok(()));
+        batch.orders.push(order(second));
+        assert_eq!(
+            batch.check_cloids_against(&live),
+            Err(DuplicateCloid {
+                cloid: second,
+                live: true
+            })
+        );
+
+        batch.orders.push(order(first));
+        assert_eq!(
+            batch.check_cloids(),
+            Err(DuplicateCloid {
+                cloid: first,
+                live: false
+            })
+        );
+    }
+
+    #[test]
+    fn test_subscription_matches() {
+        let candle: Incoming = serde_json::from_str(
+            r#"{"channel":"candle","data":{"t":1700000000000,"T":1700000059999,"s":"BTC","i":"1m","o":"1","h":"1","l":"1","c":"1","v":"1","n":1}}"#,
+        )
+        .unwrap();
+        let sub = |coin: &str, interval| Subscription::Candle {
+            coin: coin.into(),
+            interval,
+        };
+        assert!(sub("BTC", CandleInterval::OneMinute).matches(&candle));
+        assert!(!sub("BTC", CandleInterval::FiveMinutes).matches(&candle));
+        assert!(!sub("ETH", CandleInterval::OneMinute).matches(&candle));
+        assert!(!Subscription::Trades { coin: "BTC".into() }.matches(&candle));
+
+        let mids: Incoming =
+            serde_json::from_str(r#"{"channel":"allMids","data":{"mids":{"BTC":"1"}}}"#).unwrap();
+        assert!(Subscription::AllMids { dex: None }.matches(&mids));
+        assert!(
+            !Subscription::AllMids {
+                dex: Some("xyz".into())
+            }
+            .matches(&mids)
+        );
+    }
+
+    #[test]
+    fn test_portfolio_response() {
+        let portfolio: Portfolio = serde_json::from_value(serde_json::json!([
+            ["day", {
+                "accountValueHistory": [[1733900000000u64, "1000.0"], [1733950000000u64, "1040.0"]],
+                "pnlHistory": [
+                    [1733900000000u64, "0.0"],
+                    [1733910000000u64, "50.0"],
+                    [1733920000000u64, "-20.0"],
+                    [1733950000000u64, "40.0"]
+                ],
+                "vlm": "12000.5"
+            }],
+            ["perpDay", {"accountValueHistory": [], "pnlHistory": [], "vlm": "0.0"}],
+            ["perpQuarter", {"accountValueHistory": [], "pnlHistory": [], "vlm": "0.0"}]
+        ]))
+        .unwrap();
+
+        let day = portfolio.period(&PortfolioPeriod::Day).unwrap();
+        assert_eq!(day.account_value(), Some(Decimal::from(1040)));
+        assert_eq!(day.pnl(), Some(Decimal::from(40)));
+        assert_eq!(day.max_drawdown(), Decimal::from(70));
+        assert!(portfolio.all_time().is_none());
+        assert_eq!(
+            portfolio.periods[2].0,
+            PortfolioPeriod::Other("perpQuarter".into())
+        );
+    }
+
+    #[test]
+    fn test_l2_book_rest_response() {
+        // The info endpoint returns the same shape as the feed, without `snapshot`.
+        let book: Option<L2Book> = serde_json::from_value(serde_json::json!({
+            "coin": "BTC",
+            "time": 1754450974231u64,
+            "levels": [
+                [{"px": "113377.0", "sz": "7.6699", "n": 17}],
+                [{"px": "113378.0", "sz": "0.1", "n": 1}],
+            ],
+        }))
+        .unwrap();
+        let book = book.unwrap();
+        assert_eq!(book.best_bid().unwrap().n, 17);
+        assert_eq!(book.spread(), Some(Decimal::ONE));
+
+        let unknown: Option<L2Book> = serde_json::from_value(serde_json::Value::Null).unwrap();
+        assert!(unknown.is_none());
+    }
+
     #[test]
     fn test_user_stream_subscription_roundtrip() {
         let user: Address = "0x1234567890abcdef1234567890abcdef12345678"
@@ -4370,6 +6308,41 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_incoming_active_asset_ctx() {
+        let json = r#"{
+            "channel":"activeAssetCtx",
+            "data":{
+                "coin":"BTC",
+                "ctx":{
+                    "funding":"0.0000125",
+                    "openInterest":"28450.12",
+                    "prevDayPx":"96500.0",
+                    "dayNtlVlm":"1850000000.5",
+                    "premium":"0.0002",
+                    "oraclePx":"97480.0",
+                    "markPx":"97500.5",
+                    "midPx":"97501.0",
+                    "impactPxs":["97500.0","97502.0"],
+                    "dayBaseVlm":"19000.1"
+                }
+            }
+        }"#;
+
+        let incoming: Incoming = serde_json::from_str(json).unwrap();
+        match incoming {
+            Incoming::ActiveAssetCtx { coin, ctx } => {
+                assert_eq!(coin, "BTC");
+                assert_eq!(ctx.mark_px.unwrap().to_string(), "97500.5");
+                assert_eq!(ctx.oracle_px.unwrap().to_string(), "97480.0");
+                assert_eq!(ctx.open_interest.to_string(), "28450.12");
+                assert_eq!(ctx.annualized_rate().to_string(), "0.1095000");
+                assert!(ctx.is_positive());
+            }
+            _ => assert!(false, "Expected Incoming::ActiveAssetCtx"),
+        }
+    }
+
     #[test]
     fn test_fast_asset_ctx_accepts_numbers_and_nulls() {
         let ctx: FastAssetCtx = serde_json::from_value(serde_json::json!({
@@ -4626,6 +6599,36 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_twap_history_response() {
+        let json = r#"[
+            {
+                "time":1710001800,
+                "state":{
+                    "coin":"ETH",
+                    "user":"0x1234567890abcdef1234567890abcdef12345678",
+                    "side":"A",
+                    "sz":"10.0",
+                    "executedSz":"10.0",
+                    "executedNtl":"35120.5",
+                    "minutes":10,
+                    "reduceOnly":true,
+                    "randomize":false,
+                    "timestamp":1710001200333
+                },
+                "status":{"status":"terminated"},
+                "twapId":42
+            }
+        ]"#;
+
+        let history: Vec<TwapHistory> = serde_json::from_str(json).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].twap_id, Some(42));
+        assert_eq!(history[0].state.executed_ntl.to_string(), "35120.5");
+        assert!(matches!(history[0].status.status, TwapStatus::Terminated));
+        assert!(history[0].status.description.is_none());
+    }
+
     #[test]
     fn test_incoming_user_twap_history_without_description() {
         let json = r#"{
@@ -5002,6 +7005,148 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_delegator_history_deserialization() {
+        let json = r#"[
+            {
+                "time": 1735380381000,
+                "hash": "0x55492465cb523f90815a041a226ba90147008d4b221a24ae8dc35a0dbede4ea4",
+                "delta": {
+                    "delegate": {
+                        "validator": "0x5ac99df645f3414876c816caa18b2d234024b487",
+                        "amount": "10000.0",
+                        "isUndelegate": false
+                    }
+                }
+            },
+            {"time": 1735380380000, "hash": "0x00", "delta": {"cDeposit": {"amount": "10000.0"}}},
+            {
+                "time": 1735380390000,
+                "hash": "0x01",
+                "delta": {"withdrawal": {"amount": "5.5", "phase": "initiated"}}
+            }
+        ]"#;
+
+        let events: Vec<DelegatorEvent> = serde_json::from_str(json).unwrap();
+        assert_eq!(events.len(), 3);
+        match &events[0].delta {
+            DelegatorDelta::Delegate {
+                amount,
+                is_undelegate,
+                ..
+            } => {
+                assert_eq!(amount.to_string(), "10000.0");
+                assert!(!is_undelegate);
+            }
+            other => panic!("expected delegate, got {other:?}"),
+        }
+        assert!(events[1].delta.is_c_deposit());
+        assert!(matches!(
+            events[2].delta,
+            DelegatorDelta::Withdrawal {
+                phase: WithdrawalPhase::Initiated,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_delegator_rewards_deserialization() {
+        let json = r#"[
+            {"time": 1736726400073, "source": "delegation", "totalAmount": "0.73117184"},
+            {"time": 1736726400073, "source": "commission", "totalAmount": "130.76445876"}
+        ]"#;
+
+        let rewards: Vec<DelegatorReward> = serde_json::from_str(json).unwrap();
+        assert_eq!(rewards[0].source, RewardSource::Delegation);
+        assert_eq!(rewards[1].source, RewardSource::Commission);
+        assert_eq!(rewards[1].total_amount.to_string(), "130.76445876");
+    }
+
+    #[test]
+    fn test_validator_summaries_deserialization() {
+        let json = r#"[{
+            "validator": "0x5ac99df645f3414876c816caa18b2d234024b487",
+            "signer": "0x6f3c8d6b3b4b9b1a2e2d5b1c1f0a6b5e4d3c2b1a",
+            "name": "ValiDAO",
+            "description": "",
+            "nRecentBlocks": 12,
+            "stake": 123456789000000,
+            "isJailed": false,
+            "unjailableAfter": null,
+            "isActive": true,
+            "commission": "0.04",
+            "stats": [
+                ["day", {"uptimeFraction": "0.999", "predictedApr": "0.0213", "nSamples": 1440}],
+                ["week", {"uptimeFraction": "0.998", "predictedApr": "0.0215", "nSamples": 10080}]
+            ]
+        }]"#;
+
+        let validators: Vec<ValidatorSummary> = serde_json::from_str(json).unwrap();
+        let validator = &validators[0];
+        assert_eq!(validator.name, "ValiDAO");
+        assert_eq!(validator.stake_hype(), rust_decimal::dec!(1234567.89));
+        assert_eq!(validator.uptime(), Some(rust_decimal::dec!(0.999)));
+        assert_eq!(
+            validator
+                .stats(ValidatorStatsWindow::Week)
+                .map(|stats| stats.n_samples),
+            Some(10080)
+        );
+        assert!(validator.stats(ValidatorStatsWindow::Month).is_none());
+    }
+
+    #[test]
+    fn test_frontend_open_orders_deserialization() {
+        let json = r#"[{
+            "coin": "BTC",
+            "isPositionTpsl": false,
+            "isTrigger": false,
+            "limitPx": "29792.0",
+            "oid": 91490942,
+            "orderType": "Limit",
+            "origSz": "5.0",
+            "reduceOnly": false,
+            "side": "B",
+            "sz": "5.0",
+            "tif": "Gtc",
+            "timestamp": 1681247412573,
+            "triggerCondition": "N/A",
+            "triggerPx": "0.0",
+            "cloid": null,
+            "children": [{
+                "coin": "BTC",
+                "isPositionTpsl": false,
+                "isTrigger": true,
+                "limitPx": "28000.0",
+                "oid": 91490943,
+                "orderType": "Stop Market",
+                "origSz": "5.0",
+                "reduceOnly": true,
+                "side": "A",
+                "sz": "5.0",
+                "tif": null,
+                "timestamp": 1681247412573,
+                "triggerCondition": "Price below 28000",
+                "triggerPx": "28000.0",
+                "cloid": null,
+                "children": []
+            }]
+        }]"#;
+
+        let orders: Vec<OpenOrder> = serde_json::from_str(json).unwrap();
+        assert_eq!(orders.len(), 1);
+        let parent = &orders[0];
+        assert!(parent.trigger_type().is_none());
+        assert_eq!(parent.iter_tree().count(), 2);
+
+        let child = &parent.children[0];
+        assert!(matches!(child.trigger_type(), Some(OrderType::StopMarket)));
+        assert_eq!(child.trigger_px, rust_decimal::dec!(28000));
+        assert_eq!(child.trigger_condition, "Price below 28000");
+        assert!(child.basic_order.reduce_only);
+    }
+
     #[test]
     fn test_incoming_user_channel_fills() {
         // Hyperliquid sends fill notifications on channel "user" (not "userEvents").
@@ -5431,6 +7576,14 @@ mod tests {
             );
         }
 
+        #[test]
+        fn exchange_status() {
+            assert_json(
+                InfoRequest::ExchangeStatus,
+                serde_json::json!({"type": "exchangeStatus"}),
+            );
+        }
+
         #[test]
         fn perp_deploy_auction_status() {
             assert_json(
@@ -5592,6 +7745,14 @@ mod tests {
             );
         }
 
+        #[test]
+        fn validator_summaries() {
+            assert_json(
+                InfoRequest::ValidatorSummaries,
+                serde_json::json!({"type": "validatorSummaries"}),
+            );
+        }
+
         #[test]
         fn borrow_lend_user_state() {
             assert_json(
@@ -5632,6 +7793,14 @@ mod tests {
             );
         }
 
+        #[test]
+        fn twap_history() {
+            assert_json(
+                InfoRequest::TwapHistory { user: USER },
+                serde_json::json!({"type": "twapHistory", "user": "0x0000000000000000000000000000000000001234"}),
+            );
+        }
+
         #[test]
         fn l2_book() {
             assert_json(
@@ -102,8 +102,21 @@ sol! {
     struct TokenDelegate {
         string hyperliquidChain;
         address validator;
+        uint64 wei;
         bool isUndelegate;
+        uint64 nonce;
+    }
+
+    struct CDeposit {
+        string hyperliquidChain;
         uint64 wei;
+        uint64 nonce;
+    }
+
+    struct CWithdraw {
+        string hyperliquidChain;
+        uint64 wei;
+        uint64 nonce;
     }
 }
 
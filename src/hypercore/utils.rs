@@ -163,6 +163,32 @@ where
     s.parse::<Address>().map_err(serde::de::Error::custom)
 }
 
+/// Serializes an optional address as a hex string, or `null`.
+pub(super) fn serialize_option_address_as_hex<S>(
+    value: &Option<Address>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match value {
+        Some(address) => serialize_address_as_hex(address, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// Deserializes an optional address from a hex string or `null`.
+pub(super) fn deserialize_option_address_from_hex<'de, D>(
+    deserializer: D,
+) -> Result<Option<Address>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<String>::deserialize(deserializer)?
+        .map(|s| s.parse::<Address>().map_err(serde::de::Error::custom))
+        .transpose()
+}
+
 /// Serializes a U256 value as a hex string.
 pub(super) fn serialize_as_hex<S>(value: &U256, serializer: S) -> Result<S::Ok, S::Error>
 where
@@ -0,0 +1,393 @@
+//! Webhook emitter for fills, order updates and alerts.
+//!
+//! Teams usually want trading activity in the same place as the rest of their
+//! alerts. A [`WebhookEmitter`] POSTs each [`WebhookEvent`] to every configured
+//! [`Webhook`]. Failed deliveries are retried with exponential backoff.
+//!
+//! [`WebhookFormat::Json`] hooks receive the event as a JSON envelope. When a
+//! secret is set, the envelope is signed: the `X-Hypersdk-Timestamp` header holds
+//! the send time in milliseconds, and `X-Hypersdk-Signature` holds
+//! `sha256=<hex>`, an HMAC-SHA256 of `"{timestamp}.{body}"`. Receivers recompute it
+//! with [`signature`]. Slack and Discord hooks receive a one-line summary in the
+//! message format of their incoming webhooks.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use futures::StreamExt;
+//! use hypersdk::hypercore::{
+//!     self,
+//!     types::*,
+//!     webhooks::{self, Webhook, WebhookEmitter, WebhookEvent},
+//!     ws::Event,
+//! };
+//! use hypersdk::Address;
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let user: Address = "0x...".parse()?;
+//! let emitter = WebhookEmitter::new()
+//!     .hook(Webhook::json("https://example.com/hooks/fills".parse()?, "secret"))
+//!     .hook(Webhook::slack("https://hooks.slack.com/services/...".parse()?));
+//!
+//! emitter.emit(&WebhookEvent::alert("bot started")).await?;
+//!
+//! let mut ws = hypercore::mainnet_ws();
+//! ws.subscribe(Subscription::UserFills { user });
+//! ws.subscribe(Subscription::OrderUpdates { user });
+//! while let Some(event) = ws.next().await {
+//!     let Event::Message(msg) = event else { continue };
+//!     for event in webhooks::events(&msg) {
+//!         if let Err(err) = emitter.emit(&event).await {
+//!             eprintln!("webhook delivery failed: {err}");
+//!         }
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::time::Duration;
+
+use anyhow::Result;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use url::Url;
+
+use super::types::{Fill, Incoming, OrderUpdate, WsBasicOrder};
+
+/// Header holding the time the payload was sent, in milliseconds.
+pub const TIMESTAMP_HEADER: &str = "X-Hypersdk-Timestamp";
+
+/// Header holding the HMAC-SHA256 signature of the payload.
+pub const SIGNATURE_HEADER: &str = "X-Hypersdk-Signature";
+
+/// Default number of retries of a failed delivery.
+pub const DEFAULT_RETRIES: u32 = 3;
+
+/// Default timeout of a single delivery attempt.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Base delay between retries, doubled after each attempt.
+const RETRY_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Upper bound on the delay between retries.
+const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(10);
+
+/// An event delivered to webhooks.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "camelCase")]
+pub enum WebhookEvent {
+    /// A fill of one of the user's orders
+    Fill(Fill),
+    /// A status change of one of the user's orders
+    OrderUpdate(OrderUpdate<WsBasicOrder>),
+    /// A free-form alert raised by the application
+    Alert {
+        /// Alert text
+        message: String,
+    },
+}
+
+impl WebhookEvent {
+    /// Creates an alert event.
+    pub fn alert(message: impl Into<String>) -> Self {
+        Self::Alert {
+            message: message.into(),
+        }
+    }
+
+    /// Returns a one-line, human-readable summary of the event.
+    #[must_use]
+    pub fn summary(&self) -> String {
+        match self {
+            Self::Fill(fill) => format!(
+                "{} {} {} @ {} (pnl {}, fee {} {})",
+                fill.coin, fill.dir, fill.sz, fill.px, fill.closed_pnl, fill.fee, fill.fee_token
+            ),
+            Self::OrderUpdate(update) => format!(
+                "{} order {} {}: {} {} @ {}",
+                update.order.coin,
+                update.order.oid,
+                update.status,
+                update.order.side,
+                update.order.sz,
+                update.order.limit_px
+            ),
+            Self::Alert { message } => message.clone(),
+        }
+    }
+}
+
+/// Body of a [`WebhookFormat::Json`] delivery.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookPayload {
+    /// Time the event was emitted, in milliseconds
+    pub time: u64,
+    /// The event
+    #[serde(flatten)]
+    pub event: WebhookEvent,
+}
+
+/// Body format of a [`Webhook`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WebhookFormat {
+    /// The [`WebhookPayload`] as JSON, signed if the hook has a secret
+    #[default]
+    Json,
+    /// A Slack incoming-webhook message with the event summary
+    Slack,
+    /// A Discord webhook message with the event summary
+    Discord,
+}
+
+/// A webhook endpoint.
+#[derive(Debug, Clone)]
+pub struct Webhook {
+    url: Url,
+    format: WebhookFormat,
+    secret: Option<String>,
+}
+
+impl Webhook {
+    /// Creates a hook receiving signed [`WebhookPayload`]s.
+    pub fn json(url: Url, secret: impl Into<String>) -> Self {
+        Self {
+            url,
+            format: WebhookFormat::Json,
+            secret: Some(secret.into()),
+        }
+    }
+
+    /// Creates a Slack incoming webhook.
+    pub fn slack(url: Url) -> Self {
+        Self {
+            url,
+            format: WebhookFormat::Slack,
+            secret: None,
+        }
+    }
+
+    /// Creates a Discord webhook.
+    pub fn discord(url: Url) -> Self {
+        Self {
+            url,
+            format: WebhookFormat::Discord,
+            secret: None,
+        }
+    }
+
+    /// Returns the endpoint URL.
+    #[must_use]
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// Returns the body format.
+    #[must_use]
+    pub fn format(&self) -> WebhookFormat {
+        self.format
+    }
+
+    /// Returns the body posted for `event` emitted at `time`.
+    fn body(&self, event: &WebhookEvent, time: u64) -> Result<String> {
+        let body = match self.format {
+            WebhookFormat::Json => serde_json::to_string(&WebhookPayload {
+                time,
+                event: event.clone(),
+            })?,
+            WebhookFormat::Slack => serde_json::json!({ "text": event.summary() }).to_string(),
+            WebhookFormat::Discord => serde_json::json!({ "content": event.summary() }).to_string(),
+        };
+        Ok(body)
+    }
+}
+
+/// Returns the signature sent in [`SIGNATURE_HEADER`] for `body` sent at
+/// `timestamp`, without the `sha256=` prefix.
+#[must_use]
+pub fn signature(secret: &str, timestamp: u64, body: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body.as_bytes());
+    const_hex::encode(mac.finalize().into_bytes())
+}
+
+/// Returns the webhook events carried by a WebSocket message.
+///
+/// Fills come from `userFills` and status changes from `orderUpdates`. The
+/// snapshot of past fills sent on subscription is skipped. Fills also sent on
+/// `userEvents` are not mapped, so subscribing to both doesn't deliver them twice.
+#[must_use]
+pub fn events(msg: &Incoming) -> Vec<WebhookEvent> {
+    match msg {
+        Incoming::UserFills {
+            is_snapshot: false,
+            fills,
+            ..
+        } => fills.iter().cloned().map(WebhookEvent::Fill).collect(),
+        Incoming::OrderUpdates(updates) => updates
+            .iter()
+            .cloned()
+            .map(WebhookEvent::OrderUpdate)
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// Posts [`WebhookEvent`]s to a set of webhooks.
+#[derive(Debug, Clone)]
+pub struct WebhookEmitter {
+    http_client: reqwest::Client,
+    hooks: Vec<Webhook>,
+    retries: u32,
+    timeout: Duration,
+}
+
+impl Default for WebhookEmitter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WebhookEmitter {
+    /// Creates an emitter without hooks.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            http_client: reqwest::Client::new(),
+            hooks: vec![],
+            retries: DEFAULT_RETRIES,
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// Adds a hook.
+    #[must_use]
+    pub fn hook(mut self, hook: Webhook) -> Self {
+        self.hooks.push(hook);
+        self
+    }
+
+    /// Sets how many times a failed delivery is retried.
+    #[must_use]
+    pub fn retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Sets the timeout of a single delivery attempt.
+    #[must_use]
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Returns the configured hooks.
+    #[must_use]
+    pub fn hooks(&self) -> &[Webhook] {
+        &self.hooks
+    }
+
+    /// Delivers `event` to every hook concurrently.
+    ///
+    /// Server errors, rate limits, timeouts and connection failures are retried.
+    /// Every hook is attempted even if another fails; the error lists the hooks
+    /// that never accepted the event.
+    pub async fn emit(&self, event: &WebhookEvent) -> Result<()> {
+        let time = chrono::Utc::now().timestamp_millis() as u64;
+        let results = futures::future::join_all(
+            self.hooks
+                .iter()
+                .map(|hook| self.deliver(hook, event, time)),
+        )
+        .await;
+        let failures: Vec<String> = self
+            .hooks
+            .iter()
+            .zip(results)
+            .filter_map(|(hook, res)| res.err().map(|err| format!("{}: {err:#}", hook.url)))
+            .collect();
+        if !failures.is_empty() {
+            anyhow::bail!("webhook delivery failed: {}", failures.join("; "));
+        }
+        Ok(())
+    }
+
+    async fn deliver(&self, hook: &Webhook, event: &WebhookEvent, time: u64) -> Result<()> {
+        let body = hook.body(event, time)?;
+        let mut attempt = 0;
+        loop {
+            let mut req = self
+                .http_client
+                .post(hook.url.clone())
+                .timeout(self.timeout)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(body.clone());
+            if let Some(secret) = &hook.secret {
+                req = req.header(TIMESTAMP_HEADER, time.to_string()).header(
+                    SIGNATURE_HEADER,
+                    format!("sha256={}", signature(secret, time, &body)),
+                );
+            }
+            let res = req.send().await;
+            let retryable = match &res {
+                Ok(res) if res.status().is_success() => return Ok(()),
+                Ok(res) => {
+                    res.status().is_server_error()
+                        || res.status() == reqwest::StatusCode::TOO_MANY_REQUESTS
+                }
+                Err(err) => err.is_connect() || err.is_timeout(),
+            };
+            if !retryable || attempt >= self.retries {
+                let res = res?;
+                anyhow::bail!("HTTP {}", res.status());
+            }
+            log::debug!(
+                "[webhook] retrying {} after attempt {}",
+                hook.url,
+                attempt + 1
+            );
+            let backoff = RETRY_BACKOFF * 2u32.saturating_pow(attempt);
+            tokio::time::sleep(backoff.min(MAX_RETRY_BACKOFF)).await;
+            attempt += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_webhook_payloads() {
+        // printf '1700000000000.{"a":1}' | openssl dgst -sha256 -hmac secret
+        assert_eq!(
+            signature("secret", 1700000000000, r#"{"a":1}"#),
+            "4ef2732b0d632a6897af3a6d02a6de287f60d6c5f15dabdb0ceb045a34e3c5a7"
+        );
+
+        let msg: Incoming = serde_json::from_str(
+            r#"{"channel":"orderUpdates","data":[{"status":"filled","statusTimestamp":1700000000000,"order":{"timestamp":1699999999000,"coin":"BTC","side":"B","limitPx":"50000","sz":"0","oid":42,"origSz":"0.1"}}]}"#,
+        )
+        .unwrap();
+        let events = events(&msg);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].summary(), "BTC order 42 Filled: Bid 0 @ 50000");
+
+        let hook = Webhook::json("https://example.com".parse().unwrap(), "secret");
+        let body: serde_json::Value =
+            serde_json::from_str(&hook.body(&events[0], 1700000000001).unwrap()).unwrap();
+        assert_eq!(body["time"], 1700000000001u64);
+        assert_eq!(body["type"], "orderUpdate");
+        assert_eq!(body["data"]["order"]["oid"], 42);
+
+        let hook = Webhook::slack("https://example.com".parse().unwrap());
+        assert_eq!(
+            hook.body(&WebhookEvent::alert("halted"), 0).unwrap(),
+            r#"{"text":"halted"}"#
+        );
+    }
+}
@@ -10,6 +10,12 @@
 //! - [`Event::Connected`] — Connection established (including after reconnection)
 //! - [`Event::Disconnected`] — Connection lost (will auto-reconnect)
 //! - [`Event::Message`] — Contains an [`Incoming`] data message
+//! - [`Event::Lagging`] / [`Event::Gap`] — A subscription went quiet or missed
+//!   updates during a reconnect
+//!
+//! [`Connection::stats`] reports reconnects, the ping round trip and per
+//! subscription message rates for monitoring. [`Connection::next_stamped`] returns
+//! each event with its local receive time and per subscription sequence number.
 //!
 //! # Examples
 //!
@@ -31,6 +37,12 @@
 //!         Event::Disconnected => {
 //!             println!("Disconnected");
 //!         }
+//!         Event::Lagging { subscription, age } => {
+//!             println!("No {subscription} update for {age:?}");
+//!         }
+//!         Event::Gap { subscription, missed } => {
+//!             println!("Missed {missed:?} of {subscription}");
+//!         }
 //!         Event::Message(msg) => match msg {
 //!             Incoming::Trades(trades) => {
 //!                 for trade in trades {
@@ -117,23 +129,40 @@
 //! ```
 
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet, VecDeque},
     pin::Pin,
+    sync::{Arc, Mutex, MutexGuard},
     task::{Context, Poll, ready},
-    time::Duration,
+    time::{Duration, Instant},
 };
 
-use anyhow::Result;
+use alloy::primitives::Address;
+use anyhow::{Context as _, Result};
 use futures::{SinkExt, StreamExt};
+use rust_decimal::Decimal;
 use tokio::{
-    sync::mpsc::{UnboundedReceiver, UnboundedSender, unbounded_channel},
+    sync::{
+        mpsc::{UnboundedReceiver, UnboundedSender, unbounded_channel},
+        oneshot,
+    },
     time::{interval, sleep, timeout},
 };
 use tokio_util::sync::CancellationToken;
 use url::Url;
 use yawc::{Frame, OpCode, Options, TcpWebSocket};
 
-use crate::hypercore::types::{Incoming, Outgoing, Subscription};
+use crate::hypercore::{
+    ReadOnly,
+    explorer::{BlockSummary, ExplorerTx},
+    types::{
+        ActionRequest, AssetContext, Bbo, Candle, CandleInterval, Fill, Incoming, L2Book,
+        OrderUpdate, Outgoing, PostRequest, PostResponse, Response, Subscription, Trade,
+        WsBasicOrder,
+    },
+};
+
+/// How long [`Connection::post`] waits for its response.
+pub const POST_TIMEOUT: Duration = Duration::from_secs(10);
 
 struct Stream {
     stream: TcpWebSocket,
@@ -168,6 +197,16 @@ impl Stream {
         Ok(())
     }
 
+    /// Sends a post request.
+    async fn post(&mut self, id: u64, request: PostRequest) -> anyhow::Result<()> {
+        let text = serde_json::to_string(&Outgoing::Post {
+            id,
+            request: Box::new(request),
+        })?;
+        self.stream.send(Frame::text(text)).await?;
+        Ok(())
+    }
+
     /// Send a ping
     async fn ping(&mut self) -> anyhow::Result<()> {
         let text = serde_json::to_string(&Outgoing::Ping)?;
@@ -210,7 +249,102 @@ impl futures::Stream for Stream {
     }
 }
 
-type SubChannelData = (bool, Subscription);
+/// Request from a connection handle to the background task.
+enum Command {
+    Subscribe(Subscription),
+    Unsubscribe(Subscription),
+    Post(PostRequest, oneshot::Sender<PostResponse>),
+    /// Subscribes and sends the subscription's messages to the sender instead of
+    /// the event stream.
+    Route(Subscription, UnboundedSender<Incoming>),
+}
+
+/// Sends `request` through the background task and waits for its response.
+async fn post(
+    tx: &UnboundedSender<Command>,
+    state: &Mutex<Tracker>,
+    request: ActionRequest,
+) -> Result<Response> {
+    if lock(state).read_only {
+        return Err(ReadOnly.into());
+    }
+    let (resp_tx, resp_rx) = oneshot::channel();
+    tx.send(Command::Post(PostRequest::Action(request), resp_tx))
+        .map_err(|_| anyhow::anyhow!("connection closed"))?;
+    let resp = timeout(POST_TIMEOUT, resp_rx)
+        .await
+        .context("post request timed out")?
+        .context("connection lost before the post response")?;
+    resp.into_action()
+}
+
+/// Locks the shared state, which stays usable if a holder panicked.
+fn lock(tracker: &Mutex<Tracker>) -> MutexGuard<'_, Tracker> {
+    tracker.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+/// Adds `subscription` to the shared set and asks the background task to subscribe.
+fn subscribe(tx: &UnboundedSender<Command>, tracker: &Mutex<Tracker>, subscription: Subscription) {
+    lock(tracker).subscribe(&subscription);
+    let _ = tx.send(Command::Subscribe(subscription));
+}
+
+/// Removes `subscription` from the shared set and asks the background task to
+/// unsubscribe.
+fn unsubscribe(
+    tx: &UnboundedSender<Command>,
+    tracker: &Mutex<Tracker>,
+    subscription: Subscription,
+) {
+    lock(tracker).unsubscribe(&subscription);
+    let _ = tx.send(Command::Unsubscribe(subscription));
+}
+
+/// Unsubscribes from everything in the shared set.
+fn unsubscribe_all(tx: &UnboundedSender<Command>, tracker: &Mutex<Tracker>) {
+    let subs: Vec<_> = lock(tracker).subs.drain().map(|(sub, _)| sub).collect();
+    for sub in subs {
+        let _ = tx.send(Command::Unsubscribe(sub));
+    }
+}
+
+/// Subscribes to `subscription` and returns a feed of its messages, mapped by
+/// `extract`.
+fn feed<T>(
+    tx: &UnboundedSender<Command>,
+    tracker: &Mutex<Tracker>,
+    guard: &ConnectionGuard,
+    subscription: Subscription,
+    extract: fn(Incoming) -> Vec<T>,
+) -> Feed<T> {
+    let (route_tx, rx) = unbounded_channel();
+    lock(tracker).subscribe(&subscription);
+    let _ = tx.send(Command::Route(subscription, route_tx));
+    Feed {
+        rx,
+        items: VecDeque::new(),
+        extract,
+        guard: guard.clone(),
+    }
+}
+
+/// Drops the routes of dropped feeds and returns the subscriptions that no feed,
+/// nor a direct [`Connection::subscribe`], still wants.
+fn prune_routes(
+    routes: &mut Vec<(Subscription, UnboundedSender<Incoming>)>,
+    direct: &HashSet<Subscription>,
+) -> HashSet<Subscription> {
+    let mut closed = HashSet::new();
+    routes.retain(|(sub, route_tx)| {
+        if route_tx.is_closed() {
+            closed.insert(sub.clone());
+            return false;
+        }
+        true
+    });
+    closed.retain(|sub| !direct.contains(sub) && !routes.iter().any(|(route, _)| route == sub));
+    closed
+}
 
 /// Shared handle that keeps the WebSocket background task alive.
 ///
@@ -246,6 +380,7 @@ struct ConnectionGuard {
 ///         Event::Message(msg) => {
 ///             // Handle data messages
 ///         }
+///         Event::Lagging { .. } | Event::Gap { .. } => println!("Feed unhealthy"),
 ///     }
 /// }
 /// # }
@@ -264,6 +399,97 @@ pub enum Event {
     Disconnected,
     /// A data message received from the WebSocket.
     Message(Incoming),
+    /// A subscription has been silent for much longer than usual.
+    ///
+    /// Sent once per silence, when a subscription has received nothing for ten
+    /// times its average interval between messages and at least [`MIN_LAG`].
+    /// Event-driven channels such as `orderUpdates` are legitimately quiet, so
+    /// this mostly matters for market data.
+    Lagging {
+        /// The silent subscription
+        subscription: Box<Subscription>,
+        /// Time since its last message
+        age: Duration,
+    },
+    /// A subscription's first message after a reconnect, usually the snapshot sent
+    /// on re-subscribing.
+    ///
+    /// Updates published while the connection was down were missed; `missed` is the
+    /// time between the last message before the disconnect and this one. Sent
+    /// before the message itself.
+    Gap {
+        /// The subscription that missed updates
+        subscription: Box<Subscription>,
+        /// Time between the last message before the disconnect and the first after
+        missed: Duration,
+    },
+}
+
+/// Shortest silence reported as [`Event::Lagging`].
+pub const MIN_LAG: Duration = Duration::from_secs(5);
+
+/// Window over which [`SubscriptionStats::messages_per_sec`] is measured.
+const RATE_WINDOW: Duration = Duration::from_secs(10);
+
+/// Health of a [`Connection`], returned by [`Connection::stats`].
+#[derive(Debug, Clone, Default)]
+pub struct ConnectionStats {
+    /// Number of times the connection was re-established
+    pub reconnects: u32,
+    /// Round trip of the last answered ping
+    pub ping_rtt: Option<Duration>,
+    /// Statistics of every active subscription
+    pub subscriptions: HashMap<Subscription, SubscriptionStats>,
+}
+
+/// Message statistics of one subscription.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SubscriptionStats {
+    /// Messages received since subscribing
+    pub messages: u64,
+    /// Messages per second over the last 10 seconds
+    pub messages_per_sec: f64,
+    /// Time since the last message, `None` before the first
+    pub last_message_age: Option<Duration>,
+}
+
+/// An [`Event`] stamped by the background task when it was received, returned by
+/// [`Connection::next_stamped`].
+///
+/// The receive time is taken from the monotonic clock as the message comes off the
+/// socket, so `received_at.elapsed()` is the time the event spent queued and in
+/// your own processing. Each subscription numbers its messages from 1; a jump in a
+/// subscription's `seq` means a message was lost downstream of the connection.
+///
+/// A message can match several subscriptions, such as two [`Subscription::L2Book`]
+/// with different significant figures. Every one of them counts it, and `seq`
+/// reports the one that was subscribed first.
+#[derive(Clone, Debug)]
+pub struct Stamped {
+    /// The event
+    pub event: Event,
+    /// When the event was received or, for status events, emitted
+    pub received_at: Instant,
+    /// Subscription of an [`Event::Message`] (the earliest subscribed one it matches)
+    /// and the message's position in it, counted since subscribing; `None` for other
+    /// events
+    pub seq: Option<(Subscription, u64)>,
+}
+
+impl Stamped {
+    fn now(event: Event) -> Self {
+        Self {
+            event,
+            received_at: Instant::now(),
+            seq: None,
+        }
+    }
+
+    /// Returns the time since the event was received.
+    #[must_use]
+    pub fn age(&self) -> Duration {
+        self.received_at.elapsed()
+    }
 }
 
 /// Persistent WebSocket connection with automatic reconnection.
@@ -283,6 +509,7 @@ pub enum Event {
 /// - [`Event::Connected`] - Connection established (including after reconnection)
 /// - [`Event::Disconnected`] - Connection lost
 /// - [`Event::Message`] - Contains an [`Incoming`] data message
+/// - [`Event::Lagging`] / [`Event::Gap`] - A subscription went quiet or missed updates
 ///
 /// # Graceful Shutdown
 ///
@@ -318,8 +545,9 @@ pub enum Event {
 /// # }
 /// ```
 pub struct Connection {
-    rx: UnboundedReceiver<Event>,
-    tx: UnboundedSender<SubChannelData>,
+    rx: UnboundedReceiver<Stamped>,
+    tx: UnboundedSender<Command>,
+    state: Arc<Mutex<Tracker>>,
     guard: ConnectionGuard,
 }
 
@@ -379,7 +607,8 @@ pub struct Connection {
 #[allow(dead_code)]
 #[derive(Clone)]
 pub struct ConnectionHandle {
-    tx: UnboundedSender<SubChannelData>,
+    tx: UnboundedSender<Command>,
+    state: Arc<Mutex<Tracker>>,
     /// Keeps the CancellationToken alive; dropping this handle may trigger
     /// graceful shutdown of the background task if it was the last reference.
     #[allow(dead_code)]
@@ -424,7 +653,7 @@ pub struct ConnectionHandle {
 /// ```
 #[allow(dead_code)]
 pub struct ConnectionStream {
-    rx: UnboundedReceiver<Event>,
+    rx: UnboundedReceiver<Stamped>,
     /// Keeps the CancellationToken alive; dropping this stream may trigger
     /// graceful shutdown of the background task if it was the last reference.
     #[allow(dead_code)]
@@ -450,14 +679,27 @@ impl Connection {
         let (tx, rx) = unbounded_channel();
         let (stx, srx) = unbounded_channel();
         let token = CancellationToken::new();
-        tokio::spawn(connection(url, tx, srx, token.clone()));
+        let state = Arc::new(Mutex::new(Tracker::default()));
+        tokio::spawn(connection(url, tx, srx, state.clone(), token.clone()));
         Self {
             rx,
             tx: stx,
+            state,
             guard: ConnectionGuard { token },
         }
     }
 
+    /// Disables [`post`](Self::post), which then fails with [`ReadOnly`].
+    ///
+    /// Subscriptions are unaffected. Set by
+    /// [`HttpClient::websocket`](crate::hypercore::HttpClient::websocket) on a read-only
+    /// client.
+    #[must_use]
+    pub fn read_only(self) -> Self {
+        lock(&self.state).read_only = true;
+        self
+    }
+
     /// Subscribes to a WebSocket channel.
     ///
     /// The subscription will persist across reconnections. If you're already
@@ -469,7 +711,7 @@ impl Connection {
     /// - `ws.subscribe(Subscription::Trades { coin: "BTC".into() })`
     /// - `ws.subscribe(Subscription::L2Book { coin: "ETH".into(), n_sig_figs: None, mantissa: None, fast: false })`
     pub fn subscribe(&self, subscription: Subscription) {
-        let _ = self.tx.send((true, subscription));
+        subscribe(&self.tx, &self.state, subscription);
     }
 
     /// Unsubscribes from a WebSocket channel.
@@ -482,7 +724,133 @@ impl Connection {
     /// Unsubscribe from a channel:
     /// `ws.unsubscribe(Subscription::Trades { coin: "BTC".into() })`
     pub fn unsubscribe(&self, subscription: Subscription) {
-        let _ = self.tx.send((false, subscription));
+        unsubscribe(&self.tx, &self.state, subscription);
+    }
+
+    /// Returns the active subscriptions, including those of typed feeds.
+    ///
+    /// Reflects [`subscribe`](Self::subscribe) and [`unsubscribe`](Self::unsubscribe)
+    /// calls immediately, before the server has acknowledged them.
+    #[must_use]
+    pub fn subscriptions(&self) -> HashSet<Subscription> {
+        lock(&self.state).subs.keys().cloned().collect()
+    }
+
+    /// Unsubscribes from every channel, ending all typed feeds.
+    ///
+    /// Useful to rotate a set of subscriptions, e.g. when the list of traded
+    /// symbols changes.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hypersdk::hypercore::{self, types::*};
+    ///
+    /// # fn example(coins: Vec<String>) {
+    /// let ws = hypercore::mainnet_ws();
+    /// ws.unsubscribe_all();
+    /// for coin in coins {
+    ///     ws.subscribe(Subscription::Trades { coin });
+    /// }
+    /// println!("subscribed to {} channels", ws.subscriptions().len());
+    /// # }
+    /// ```
+    pub fn unsubscribe_all(&self) {
+        unsubscribe_all(&self.tx, &self.state);
+    }
+
+    /// Sends a signed action through the `post` channel and waits for the exchange's
+    /// response.
+    ///
+    /// Skips the HTTP round trip of [`HttpClient::send`](crate::hypercore::HttpClient::send)
+    /// on an already open socket. Requests are matched to responses by id, so several
+    /// can be in flight at once. A request made while disconnected is sent after the
+    /// reconnect; one whose response doesn't arrive within [`POST_TIMEOUT`] fails, and
+    /// one not yet sent by then is dropped.
+    ///
+    /// Fails with [`ReadOnly`] on a [`read_only`](Self::read_only) connection.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hypersdk::hypercore::{self, Chain, PrivateKeySigner, types::*};
+    ///
+    /// # async fn example(batch: BatchOrder) -> anyhow::Result<()> {
+    /// let signer: PrivateKeySigner = "your_key".parse()?;
+    /// let ws = hypercore::mainnet_ws();
+    ///
+    /// let nonce = chrono::Utc::now().timestamp_millis() as u64;
+    /// let req = Action::from(batch).sign_sync(&signer, nonce, None, None, Chain::Mainnet)?;
+    /// match ws.post(req).await? {
+    ///     Response::Ok(OkResponse::Order { statuses }) => println!("{statuses:?}"),
+    ///     other => eprintln!("{other:?}"),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn post(&self, request: ActionRequest) -> Result<Response> {
+        post(&self.tx, &self.state, request).await
+    }
+
+    /// Returns the connection's health: reconnects, ping round trip and per
+    /// subscription message rates.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hypersdk::hypercore;
+    ///
+    /// # fn example(ws: &hypercore::WebSocket) {
+    /// let stats = ws.stats();
+    /// println!("{} reconnects, ping {:?}", stats.reconnects, stats.ping_rtt);
+    /// for (sub, sub_stats) in &stats.subscriptions {
+    ///     println!(
+    ///         "{sub}: {:.1} msg/s, last {:?} ago",
+    ///         sub_stats.messages_per_sec, sub_stats.last_message_age
+    ///     );
+    /// }
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn stats(&self) -> ConnectionStats {
+        lock(&self.state).snapshot(Instant::now())
+    }
+
+    /// Returns the next event with its receive time and sequence number, for
+    /// measuring processing lag and detecting dropped messages.
+    ///
+    /// Takes events from the same queue as the `Stream` implementation; see
+    /// [`Stamped`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::collections::HashMap;
+    ///
+    /// use hypersdk::hypercore::{self, types::*};
+    ///
+    /// # async fn example() {
+    /// let mut ws = hypercore::mainnet_ws();
+    /// ws.subscribe(Subscription::Trades { coin: "BTC".into() });
+    /// ws.subscribe(Subscription::Bbo { coin: "BTC".into() });
+    ///
+    /// // Sequence numbers are per subscription.
+    /// let mut last_seq = HashMap::new();
+    /// while let Some(stamped) = ws.next_stamped().await {
+    ///     if let Some((sub, seq)) = stamped.seq {
+    ///         if let Some(last) = last_seq.insert(sub.clone(), seq) {
+    ///             if seq != last + 1 {
+    ///                 println!("{sub}: lost {} messages", seq - last - 1);
+    ///             }
+    ///         }
+    ///     }
+    ///     // Handle stamped.event...
+    ///     println!("processed after {:?}", stamped.age());
+    /// }
+    /// # }
+    /// ```
+    pub async fn next_stamped(&mut self) -> Option<Stamped> {
+        self.rx.recv().await
     }
 
     /// Closes the WebSocket connection and shuts down the background task.
@@ -497,6 +865,119 @@ impl Connection {
         drop(self);
     }
 
+    /// Subscribes to `userFills` for `user` and returns a typed fill stream.
+    ///
+    /// The returned stream deduplicates fills by default; see [`UserFillStream`].
+    pub fn user_fills(self, user: Address) -> UserFillStream<Self> {
+        self.subscribe(Subscription::UserFills { user });
+        UserFillStream::new(self, user)
+    }
+
+    /// Subscribes to `subscription` and returns a stream of its messages.
+    ///
+    /// Messages are routed by [`Subscription::matches`]: those of a routed
+    /// subscription go to its feeds only, and no longer to this stream.
+    /// [`unsubscribe`](Self::unsubscribe) ends the subscription's feeds.
+    pub fn route(&self, subscription: Subscription) -> Feed<Incoming> {
+        feed(&self.tx, &self.state, &self.guard, subscription, |msg| {
+            vec![msg]
+        })
+    }
+
+    /// Returns a stream of the trades of `coin`.
+    pub fn trades(&self, coin: impl Into<String>) -> Feed<Trade> {
+        let sub = Subscription::Trades { coin: coin.into() };
+        feed(&self.tx, &self.state, &self.guard, sub, extract_trades)
+    }
+
+    /// Returns a stream of the order book of `coin`, at full precision.
+    pub fn l2_book(&self, coin: impl Into<String>) -> Feed<L2Book> {
+        let sub = Subscription::L2Book {
+            coin: coin.into(),
+            n_sig_figs: None,
+            mantissa: None,
+            fast: false,
+        };
+        feed(&self.tx, &self.state, &self.guard, sub, extract_l2_book)
+    }
+
+    /// Returns a stream of the best bid and offer of `coin`.
+    pub fn bbo(&self, coin: impl Into<String>) -> Feed<Bbo> {
+        let sub = Subscription::Bbo { coin: coin.into() };
+        feed(&self.tx, &self.state, &self.guard, sub, extract_bbo)
+    }
+
+    /// Returns a stream of the `interval` candles of `coin`.
+    pub fn candles(&self, coin: impl Into<String>, interval: CandleInterval) -> Feed<Candle> {
+        let sub = Subscription::Candle {
+            coin: coin.into(),
+            interval,
+        };
+        feed(&self.tx, &self.state, &self.guard, sub, extract_candle)
+    }
+
+    /// Returns a stream of the mid prices of every market of `dex`, the main DEX
+    /// if `None`.
+    pub fn all_mids(&self, dex: Option<String>) -> Feed<HashMap<String, Decimal>> {
+        feed(
+            &self.tx,
+            &self.state,
+            &self.guard,
+            Subscription::AllMids { dex },
+            extract_all_mids,
+        )
+    }
+
+    /// Returns a stream of the context (mark price, funding, open interest) of the
+    /// perpetual `coin`.
+    pub fn asset_ctx(&self, coin: impl Into<String>) -> Feed<AssetContext> {
+        let sub = Subscription::ActiveAssetCtx { coin: coin.into() };
+        feed(&self.tx, &self.state, &self.guard, sub, extract_asset_ctx)
+    }
+
+    /// Returns a stream of the order status changes of `user`.
+    ///
+    /// `orderUpdates` messages don't name their user, so a connection should track
+    /// the orders of one user only.
+    pub fn order_updates(&self, user: Address) -> Feed<OrderUpdate<WsBasicOrder>> {
+        let sub = Subscription::OrderUpdates { user };
+        feed(
+            &self.tx,
+            &self.state,
+            &self.guard,
+            sub,
+            extract_order_updates,
+        )
+    }
+
+    /// Returns a stream of the new blocks of the chain.
+    ///
+    /// Only served by the explorer WebSocket; see
+    /// [`ExplorerClient::websocket`](crate::hypercore::explorer::ExplorerClient::websocket).
+    pub fn explorer_blocks(&self) -> Feed<BlockSummary> {
+        feed(
+            &self.tx,
+            &self.state,
+            &self.guard,
+            Subscription::ExplorerBlock,
+            extract_explorer_blocks,
+        )
+    }
+
+    /// Returns a stream of the new transactions of the chain.
+    ///
+    /// Only served by the explorer WebSocket. The channel carries every user's
+    /// transactions; filter on [`ExplorerTx::user`] to follow one account.
+    pub fn explorer_txs(&self) -> Feed<ExplorerTx> {
+        feed(
+            &self.tx,
+            &self.state,
+            &self.guard,
+            Subscription::ExplorerTxs,
+            extract_explorer_txs,
+        )
+    }
+
     /// Splits the connection into a subscription handle and an event stream.
     ///
     /// This is useful when you want to drive the stream in one task and
@@ -507,6 +988,7 @@ impl Connection {
         (
             ConnectionHandle {
                 tx: self.tx,
+                state: self.state,
                 guard: self.guard.clone(),
             },
             ConnectionStream {
@@ -522,7 +1004,9 @@ impl futures::Stream for Connection {
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let this = self.get_mut();
-        this.rx.poll_recv(cx)
+        this.rx
+            .poll_recv(cx)
+            .map(|stamped| stamped.map(|stamped| stamped.event))
     }
 }
 
@@ -538,7 +1022,7 @@ impl ConnectionHandle {
     /// - `ws.subscribe(Subscription::Trades { coin: "BTC".into() })`
     /// - `ws.subscribe(Subscription::L2Book { coin: "ETH".into(), n_sig_figs: None, mantissa: None, fast: false })`
     pub fn subscribe(&self, subscription: Subscription) {
-        let _ = self.tx.send((true, subscription));
+        subscribe(&self.tx, &self.state, subscription);
     }
 
     /// Unsubscribes from a WebSocket channel.
@@ -551,7 +1035,149 @@ impl ConnectionHandle {
     /// Unsubscribe from a channel:
     /// `ws.unsubscribe(Subscription::Trades { coin: "BTC".into() })`
     pub fn unsubscribe(&self, subscription: Subscription) {
-        let _ = self.tx.send((false, subscription));
+        unsubscribe(&self.tx, &self.state, subscription);
+    }
+
+    /// Returns the active subscriptions.
+    ///
+    /// See [`Connection::subscriptions`].
+    #[must_use]
+    pub fn subscriptions(&self) -> HashSet<Subscription> {
+        lock(&self.state).subs.keys().cloned().collect()
+    }
+
+    /// Unsubscribes from every channel.
+    ///
+    /// See [`Connection::unsubscribe_all`].
+    pub fn unsubscribe_all(&self) {
+        unsubscribe_all(&self.tx, &self.state);
+    }
+
+    /// Sends a signed action through the `post` channel and waits for the exchange's
+    /// response.
+    ///
+    /// See [`Connection::post`].
+    pub async fn post(&self, request: ActionRequest) -> Result<Response> {
+        post(&self.tx, &self.state, request).await
+    }
+
+    /// Subscribes to `subscription` and returns a stream of its messages.
+    ///
+    /// See [`Connection::route`].
+    pub fn route(&self, subscription: Subscription) -> Feed<Incoming> {
+        feed(&self.tx, &self.state, &self.guard, subscription, |msg| {
+            vec![msg]
+        })
+    }
+
+    /// Returns a stream of the trades of `coin`.
+    pub fn trades(&self, coin: impl Into<String>) -> Feed<Trade> {
+        let sub = Subscription::Trades { coin: coin.into() };
+        feed(&self.tx, &self.state, &self.guard, sub, extract_trades)
+    }
+
+    /// Returns a stream of the order book of `coin`, at full precision.
+    pub fn l2_book(&self, coin: impl Into<String>) -> Feed<L2Book> {
+        let sub = Subscription::L2Book {
+            coin: coin.into(),
+            n_sig_figs: None,
+            mantissa: None,
+            fast: false,
+        };
+        feed(&self.tx, &self.state, &self.guard, sub, extract_l2_book)
+    }
+
+    /// Returns a stream of the best bid and offer of `coin`.
+    pub fn bbo(&self, coin: impl Into<String>) -> Feed<Bbo> {
+        let sub = Subscription::Bbo { coin: coin.into() };
+        feed(&self.tx, &self.state, &self.guard, sub, extract_bbo)
+    }
+
+    /// Returns a stream of the `interval` candles of `coin`.
+    pub fn candles(&self, coin: impl Into<String>, interval: CandleInterval) -> Feed<Candle> {
+        let sub = Subscription::Candle {
+            coin: coin.into(),
+            interval,
+        };
+        feed(&self.tx, &self.state, &self.guard, sub, extract_candle)
+    }
+
+    /// Returns a stream of the mid prices of every market of `dex`.
+    ///
+    /// See [`Connection::all_mids`].
+    pub fn all_mids(&self, dex: Option<String>) -> Feed<HashMap<String, Decimal>> {
+        feed(
+            &self.tx,
+            &self.state,
+            &self.guard,
+            Subscription::AllMids { dex },
+            extract_all_mids,
+        )
+    }
+
+    /// Returns a stream of the context of the perpetual `coin`.
+    pub fn asset_ctx(&self, coin: impl Into<String>) -> Feed<AssetContext> {
+        let sub = Subscription::ActiveAssetCtx { coin: coin.into() };
+        feed(&self.tx, &self.state, &self.guard, sub, extract_asset_ctx)
+    }
+
+    /// Returns a stream of the order status changes of `user`.
+    ///
+    /// See [`Connection::order_updates`].
+    pub fn order_updates(&self, user: Address) -> Feed<OrderUpdate<WsBasicOrder>> {
+        let sub = Subscription::OrderUpdates { user };
+        feed(
+            &self.tx,
+            &self.state,
+            &self.guard,
+            sub,
+            extract_order_updates,
+        )
+    }
+
+    /// Returns a stream of the new blocks of the chain.
+    ///
+    /// See [`Connection::explorer_blocks`].
+    pub fn explorer_blocks(&self) -> Feed<BlockSummary> {
+        feed(
+            &self.tx,
+            &self.state,
+            &self.guard,
+            Subscription::ExplorerBlock,
+            extract_explorer_blocks,
+        )
+    }
+
+    /// Returns a stream of the new transactions of the chain.
+    ///
+    /// See [`Connection::explorer_txs`].
+    pub fn explorer_txs(&self) -> Feed<ExplorerTx> {
+        feed(
+            &self.tx,
+            &self.state,
+            &self.guard,
+            Subscription::ExplorerTxs,
+            extract_explorer_txs,
+        )
+    }
+
+    /// Returns a deduplicated stream of the fills of `user`.
+    ///
+    /// Unlike [`Connection::user_fills`], this doesn't take over the event stream.
+    pub fn user_fills(&self, user: Address) -> UserFillStream<Feed<Event>> {
+        let sub = Subscription::UserFills { user };
+        let events = feed(&self.tx, &self.state, &self.guard, sub, |msg| {
+            vec![Event::Message(msg)]
+        });
+        UserFillStream::new(events, user)
+    }
+
+    /// Returns the connection's health.
+    ///
+    /// See [`Connection::stats`].
+    #[must_use]
+    pub fn stats(&self) -> ConnectionStats {
+        lock(&self.state).snapshot(Instant::now())
     }
 
     /// Drops this handle, releasing its reference to the shared connection.
@@ -568,19 +1194,465 @@ impl ConnectionHandle {
     }
 }
 
+impl ConnectionStream {
+    /// Returns the next event with its receive time and sequence number.
+    ///
+    /// See [`Connection::next_stamped`].
+    pub async fn next_stamped(&mut self) -> Option<Stamped> {
+        self.rx.recv().await
+    }
+}
+
 impl futures::Stream for ConnectionStream {
     type Item = Event;
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let this = self.get_mut();
-        this.rx.poll_recv(cx)
+        this.rx
+            .poll_recv(cx)
+            .map(|stamped| stamped.map(|stamped| stamped.event))
+    }
+}
+
+/// Typed stream of the messages of one subscription.
+///
+/// Created by the typed methods of [`Connection`] and [`ConnectionHandle`], such
+/// as [`trades`](Connection::trades) or [`l2_book`](Connection::l2_book). Each
+/// feed receives its subscription's messages from the background task, so many
+/// feeds can share one connection without matching on [`Incoming`]. The stream
+/// keeps the connection alive and ends when the subscription is unsubscribed.
+/// Dropping the last feed of a subscription unsubscribes from it, unless it was
+/// also subscribed with [`Connection::subscribe`].
+///
+/// # Example
+///
+/// ```no_run
+/// use futures::StreamExt;
+/// use hypersdk::hypercore;
+///
+/// # async fn example() -> anyhow::Result<()> {
+/// let ws = hypercore::mainnet_ws();
+/// let mut trades = ws.trades("BTC");
+/// let mut book = ws.l2_book("ETH");
+///
+/// loop {
+///     tokio::select! {
+///         Some(trade) = trades.next() => println!("BTC {} {} @ {}", trade.side, trade.sz, trade.px),
+///         Some(book) = book.next() => println!("ETH book at {}", book.time),
+///         else => break,
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct Feed<T> {
+    rx: UnboundedReceiver<Incoming>,
+    items: VecDeque<T>,
+    extract: fn(Incoming) -> Vec<T>,
+    /// Keeps the CancellationToken alive while the feed is used.
+    #[allow(dead_code)]
+    guard: ConnectionGuard,
+}
+
+// The buffered items are never pinned.
+impl<T> Unpin for Feed<T> {}
+
+impl<T> futures::Stream for Feed<T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            if let Some(item) = this.items.pop_front() {
+                return Poll::Ready(Some(item));
+            }
+            match ready!(this.rx.poll_recv(cx)) {
+                Some(msg) => this.items.extend((this.extract)(msg)),
+                None => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+fn extract_trades(msg: Incoming) -> Vec<Trade> {
+    match msg {
+        Incoming::Trades(trades) => trades,
+        _ => vec![],
+    }
+}
+
+fn extract_l2_book(msg: Incoming) -> Vec<L2Book> {
+    match msg {
+        Incoming::L2Book(book) => vec![book],
+        _ => vec![],
+    }
+}
+
+fn extract_bbo(msg: Incoming) -> Vec<Bbo> {
+    match msg {
+        Incoming::Bbo(bbo) => vec![bbo],
+        _ => vec![],
+    }
+}
+
+fn extract_candle(msg: Incoming) -> Vec<Candle> {
+    match msg {
+        Incoming::Candle(candle) => vec![candle],
+        _ => vec![],
+    }
+}
+
+fn extract_all_mids(msg: Incoming) -> Vec<HashMap<String, Decimal>> {
+    match msg {
+        Incoming::AllMids { mids, .. } => vec![mids],
+        _ => vec![],
+    }
+}
+
+fn extract_asset_ctx(msg: Incoming) -> Vec<AssetContext> {
+    match msg {
+        Incoming::ActiveAssetCtx { ctx, .. } => vec![ctx],
+        _ => vec![],
+    }
+}
+
+fn extract_order_updates(msg: Incoming) -> Vec<OrderUpdate<WsBasicOrder>> {
+    match msg {
+        Incoming::OrderUpdates(updates) => updates,
+        _ => vec![],
+    }
+}
+
+fn extract_explorer_blocks(msg: Incoming) -> Vec<BlockSummary> {
+    match msg {
+        Incoming::ExplorerBlock(blocks) => blocks,
+        _ => vec![],
+    }
+}
+
+fn extract_explorer_txs(msg: Incoming) -> Vec<ExplorerTx> {
+    match msg {
+        Incoming::ExplorerTxs(txs) => txs,
+        _ => vec![],
+    }
+}
+
+/// Deduplicates fills by `(oid, tid, time)`.
+///
+/// After a reconnection the `userFills` channel replays recent fills as a snapshot
+/// (`is_snapshot: true`), most of which were already delivered before the disconnect.
+/// Feeding every batch through a `FillDedup` drops fills that were already seen so
+/// downstream PnL accounting does not double-count them.
+///
+/// Only the most recent `capacity` keys are remembered.
+#[derive(Debug, Clone)]
+pub struct FillDedup {
+    seen: HashSet<(u64, u64, u64)>,
+    order: VecDeque<(u64, u64, u64)>,
+    capacity: usize,
+}
+
+impl Default for FillDedup {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_CAPACITY)
+    }
+}
+
+impl FillDedup {
+    /// Default number of fill keys remembered.
+    pub const DEFAULT_CAPACITY: usize = 10_000;
+
+    /// Creates a deduplicator remembering up to `capacity` fills.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            seen: HashSet::with_capacity(capacity),
+            order: VecDeque::with_capacity(capacity),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// Returns `true` if the fill has not been seen before, and records it.
+    pub fn insert(&mut self, fill: &Fill) -> bool {
+        let key = (fill.oid, fill.tid, fill.time);
+        if !self.seen.insert(key) {
+            return false;
+        }
+
+        self.order.push_back(key);
+        if self.order.len() > self.capacity {
+            if let Some(old) = self.order.pop_front() {
+                self.seen.remove(&old);
+            }
+        }
+
+        true
+    }
+
+    /// Removes already-seen fills from `fills`, recording the new ones.
+    pub fn retain_new(&mut self, fills: &mut Vec<Fill>) {
+        fills.retain(|fill| self.insert(fill));
+    }
+}
+
+/// A batch of fills received on the `userFills` channel.
+#[derive(Debug, Clone)]
+pub struct UserFillBatch {
+    /// Whether this batch is the snapshot sent on (re)subscription.
+    pub is_snapshot: bool,
+    /// Fills in this batch.
+    pub fills: Vec<Fill>,
+}
+
+/// Typed stream of fills for a single user.
+///
+/// Created with [`Connection::user_fills`]. Filters the underlying event stream down
+/// to `userFills` messages for `user`. Deduplication via [`FillDedup`] is enabled by
+/// default, so snapshots replayed after a reconnect only yield fills that were not
+/// delivered before; disable it with [`dedup(false)`](Self::dedup).
+///
+/// # Example
+///
+/// ```no_run
+/// use hypersdk::hypercore;
+/// use hypersdk::Address;
+/// use futures::StreamExt;
+///
+/// # async fn example() -> anyhow::Result<()> {
+/// let user: Address = "0x...".parse()?;
+/// let mut fills = hypercore::mainnet_ws().user_fills(user);
+///
+/// while let Some(batch) = fills.next().await {
+///     for fill in batch.fills {
+///         println!("{} {} @ {}", fill.coin, fill.sz, fill.px);
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct UserFillStream<S = Connection> {
+    inner: S,
+    user: Address,
+    dedup: Option<FillDedup>,
+}
+
+impl<S> UserFillStream<S> {
+    /// Wraps an event stream that is already subscribed to `userFills` for `user`.
+    pub fn new(inner: S, user: Address) -> Self {
+        Self {
+            inner,
+            user,
+            dedup: Some(FillDedup::default()),
+        }
+    }
+
+    /// Enables or disables fill deduplication.
+    #[must_use]
+    pub fn dedup(mut self, enabled: bool) -> Self {
+        self.dedup = enabled.then(FillDedup::default);
+        self
+    }
+
+    /// Enables deduplication with a custom deduplicator.
+    #[must_use]
+    pub fn with_dedup(mut self, dedup: FillDedup) -> Self {
+        self.dedup = Some(dedup);
+        self
+    }
+
+    /// Returns the underlying event stream.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S> futures::Stream for UserFillStream<S>
+where
+    S: futures::Stream<Item = Event> + Unpin,
+{
+    type Item = UserFillBatch;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        while let Some(event) = ready!(this.inner.poll_next_unpin(cx)) {
+            let Event::Message(Incoming::UserFills {
+                is_snapshot,
+                user,
+                mut fills,
+            }) = event
+            else {
+                continue;
+            };
+
+            if user != this.user {
+                continue;
+            }
+
+            if let Some(dedup) = this.dedup.as_mut() {
+                dedup.retain_new(&mut fills);
+                if fills.is_empty() {
+                    continue;
+                }
+            }
+
+            return Poll::Ready(Some(UserFillBatch { is_snapshot, fills }));
+        }
+
+        Poll::Ready(None)
+    }
+}
+
+/// State shared between the background task and the connection handles: the
+/// subscription set, kept by the handles, its statistics and whether posting is
+/// disabled.
+#[derive(Default)]
+struct Tracker {
+    read_only: bool,
+    reconnects: u32,
+    ping_sent: Option<Instant>,
+    ping_rtt: Option<Duration>,
+    subs: HashMap<Subscription, SubTracker>,
+    /// Order assigned to the next new subscription.
+    next_order: u64,
+}
+
+#[derive(Default)]
+struct SubTracker {
+    /// When the subscription was made relative to the others.
+    order: u64,
+    messages: u64,
+    recent: VecDeque<Instant>,
+    last: Option<Instant>,
+    /// Moving average of the time between messages.
+    interval: Option<Duration>,
+    /// Last message before a disconnect, until the first message after it.
+    gap_from: Option<Instant>,
+    lagging: bool,
+}
+
+impl Tracker {
+    fn subscribe(&mut self, sub: &Subscription) {
+        let order = &mut self.next_order;
+        self.subs.entry(sub.clone()).or_insert_with(|| {
+            *order += 1;
+            SubTracker {
+                order: *order,
+                ..SubTracker::default()
+            }
+        });
+    }
+
+    fn unsubscribe(&mut self, sub: &Subscription) {
+        self.subs.remove(sub);
+    }
+
+    fn disconnected(&mut self) {
+        self.ping_sent = None;
+        for sub in self.subs.values_mut() {
+            sub.gap_from = sub.gap_from.or(sub.last);
+        }
+    }
+
+    fn pong(&mut self, now: Instant) {
+        if let Some(sent) = self.ping_sent.take() {
+            self.ping_rtt = Some(now - sent);
+        }
+    }
+
+    /// Counts `msg` against the subscriptions it belongs to, returning a
+    /// [`Event::Gap`] for those receiving their first message after a reconnect,
+    /// and the sequence number of the earliest subscribed one.
+    fn record(
+        &mut self,
+        msg: &Incoming,
+        now: Instant,
+    ) -> (Vec<Event>, Option<(Subscription, u64)>) {
+        let mut events = Vec::new();
+        let mut seq: Option<(u64, Subscription, u64)> = None;
+        for (subscription, sub) in &mut self.subs {
+            if !subscription.matches(msg) {
+                continue;
+            }
+            sub.messages += 1;
+            if seq.as_ref().is_none_or(|(order, ..)| sub.order < *order) {
+                seq = Some((sub.order, subscription.clone(), sub.messages));
+            }
+            sub.recent.push_back(now);
+            while sub.recent.front().is_some_and(|&t| now - t > RATE_WINDOW) {
+                sub.recent.pop_front();
+            }
+            match (sub.gap_from.take(), sub.last) {
+                (Some(from), _) => events.push(Event::Gap {
+                    subscription: Box::new(subscription.clone()),
+                    missed: now - from,
+                }),
+                (None, Some(last)) => {
+                    let elapsed = now - last;
+                    sub.interval = Some(match sub.interval {
+                        Some(avg) => avg.mul_f64(0.9) + elapsed.mul_f64(0.1),
+                        None => elapsed,
+                    });
+                }
+                (None, None) => {}
+            }
+            sub.last = Some(now);
+            sub.lagging = false;
+        }
+        (
+            events,
+            seq.map(|(_, subscription, seq)| (subscription, seq)),
+        )
+    }
+
+    /// Returns a [`Event::Lagging`] for each subscription that just went silent.
+    fn lagging(&mut self, now: Instant) -> Vec<Event> {
+        let mut events = Vec::new();
+        for (subscription, sub) in &mut self.subs {
+            let (Some(last), Some(interval)) = (sub.last, sub.interval) else {
+                continue;
+            };
+            let age = now - last;
+            if !sub.lagging && age > (interval * 10).max(MIN_LAG) {
+                sub.lagging = true;
+                events.push(Event::Lagging {
+                    subscription: Box::new(subscription.clone()),
+                    age,
+                });
+            }
+        }
+        events
+    }
+
+    fn snapshot(&self, now: Instant) -> ConnectionStats {
+        let subscriptions = self
+            .subs
+            .iter()
+            .map(|(subscription, sub)| {
+                let recent = sub
+                    .recent
+                    .iter()
+                    .filter(|&&t| now - t <= RATE_WINDOW)
+                    .count();
+                let stats = SubscriptionStats {
+                    messages: sub.messages,
+                    messages_per_sec: recent as f64 / RATE_WINDOW.as_secs_f64(),
+                    last_message_age: sub.last.map(|last| now - last),
+                };
+                (subscription.clone(), stats)
+            })
+            .collect();
+        ConnectionStats {
+            reconnects: self.reconnects,
+            ping_rtt: self.ping_rtt,
+            subscriptions,
+        }
     }
 }
 
 async fn connection(
     url: Url,
-    tx: UnboundedSender<Event>,
-    mut srx: UnboundedReceiver<SubChannelData>,
+    tx: UnboundedSender<Stamped>,
+    mut srx: UnboundedReceiver<Command>,
+    state: Arc<Mutex<Tracker>>,
     shutdown: CancellationToken,
 ) {
     const MAX_MISSED_PONGS: u8 = 2;
@@ -588,7 +1660,14 @@ async fn connection(
     const INITIAL_RECONNECT_DELAY_MS: u64 = 500;
 
     let mut subs: HashSet<Subscription> = HashSet::new();
+    // Subscriptions asked for with `subscribe`, whose messages go to the event stream.
+    let mut direct: HashSet<Subscription> = HashSet::new();
+    let mut pending: HashMap<u64, oneshot::Sender<PostResponse>> = HashMap::new();
+    let mut routes: Vec<(Subscription, UnboundedSender<Incoming>)> = Vec::new();
+    let mut next_post_id = 0u64;
     let mut reconnect_attempts = 0u32;
+    let mut connected_before = false;
+    let track = || lock(&state);
 
     loop {
         // Race the connect attempt (with timeout) against the shutdown signal.
@@ -638,7 +1717,11 @@ async fn connection(
 
         log::debug!("Connected to {url}");
         reconnect_attempts = 0; // Reset on successful connection
-        let _ = tx.send(Event::Connected);
+        if connected_before {
+            track().reconnects += 1;
+        }
+        connected_before = true;
+        let _ = tx.send(Stamped::now(Event::Connected));
 
         // Re-subscribe to all active subscriptions after reconnection
         if !subs.is_empty() {
@@ -662,7 +1745,13 @@ async fn connection(
                         break;
                     }
 
+                    let lagging = track().lagging(Instant::now());
+                    for event in lagging {
+                        let _ = tx.send(Stamped::now(event));
+                    }
+
                     if stream.ping().await.is_ok() {
+                        track().ping_sent = Some(Instant::now());
                         missed_pongs += 1;
                     }
                 }
@@ -671,31 +1760,105 @@ async fn connection(
                     match item {
                         Incoming::Pong => {
                             missed_pongs = 0;
+                            track().pong(Instant::now());
                         }
                         Incoming::Ping => {
                             let _ = stream.pong().await;
                         }
+                        Incoming::Post(resp) if pending.contains_key(&resp.id) => {
+                            if let Some(resp_tx) = pending.remove(&resp.id) {
+                                let _ = resp_tx.send(resp);
+                            }
+                        }
                         _ => {
-                            let _ = tx.send(Event::Message(item));
+                            let received_at = Instant::now();
+                            let (gaps, seq) = track().record(&item, received_at);
+                            for event in gaps {
+                                let _ = tx.send(Stamped {
+                                    event,
+                                    received_at,
+                                    seq: None,
+                                });
+                            }
+                            // Unsubscribe once the last feed of a subscription is dropped,
+                            // or its messages would pile up in the event stream.
+                            let mut failed = false;
+                            for sub in prune_routes(&mut routes, &direct) {
+                                subs.remove(&sub);
+                                track().unsubscribe(&sub);
+                                if let Err(err) = stream.unsubscribe(sub).await {
+                                    log::error!("Unsubscribing: {err:?}");
+                                    failed = true;
+                                    break;
+                                }
+                            }
+                            if failed {
+                                break;
+                            }
+                            let mut routed = false;
+                            for (sub, route_tx) in &routes {
+                                if sub.matches(&item) {
+                                    routed = true;
+                                    let _ = route_tx.send(item.clone());
+                                }
+                            }
+                            if !routed {
+                                let _ = tx.send(Stamped {
+                                    event: Event::Message(item),
+                                    received_at,
+                                    seq,
+                                });
+                            }
                         }
                     }
                 }
                 item = srx.recv() => {
-                    let Some((is_sub, sub)) = item else { return };
-                    if is_sub {
-                        if !subs.insert(sub.clone()) {
-                            log::debug!("Already subscribed to {sub:?}");
-                            continue;
-                        }
+                    let Some(command) = item else { return };
+                    match command {
+                        Command::Subscribe(sub) => {
+                            direct.insert(sub.clone());
+                            if !subs.insert(sub.clone()) {
+                                log::debug!("Already subscribed to {sub:?}");
+                                continue;
+                            }
 
-                        if let Err(err) = stream.subscribe(sub).await {
-                            log::error!("Subscribing: {err:?}");
-                            break;
+                            if let Err(err) = stream.subscribe(sub).await {
+                                log::error!("Subscribing: {err:?}");
+                                break;
+                            }
                         }
-                    } else if subs.remove(&sub) {
-                        if let Err(err) = stream.unsubscribe(sub).await {
-                            log::error!("Unsubscribing: {err:?}");
-                            break;
+                        Command::Unsubscribe(sub) => {
+                            direct.remove(&sub);
+                            routes.retain(|(route, _)| *route != sub);
+                            if subs.remove(&sub) {
+                                if let Err(err) = stream.unsubscribe(sub).await {
+                                    log::error!("Unsubscribing: {err:?}");
+                                    break;
+                                }
+                            }
+                        }
+                        Command::Post(request, resp_tx) => {
+                            // The caller gave up while the socket was down.
+                            if resp_tx.is_closed() {
+                                continue;
+                            }
+                            let id = next_post_id;
+                            next_post_id += 1;
+                            if let Err(err) = stream.post(id, request).await {
+                                log::error!("Posting: {err:?}");
+                                break;
+                            }
+                            pending.insert(id, resp_tx);
+                        }
+                        Command::Route(sub, route_tx) => {
+                            routes.push((sub.clone(), route_tx));
+                            if !subs.insert(sub.clone()) {
+                                continue;
+                            }
+                            if let Err(err) = stream.subscribe(sub).await {
+                                log::error!("Subscribing: {err:?}");
+                                break;
+                            }
                         }
                     }
                 }
@@ -707,9 +1870,230 @@ async fn connection(
             }
         }
 
+        // Responses to in-flight posts are lost with the socket.
+        pending.clear();
+        track().disconnected();
         log::info!("Disconnected from {url}, attempting to reconnect...");
-        let _ = tx.send(Event::Disconnected);
+        let _ = tx.send(Stamped::now(Event::Disconnected));
     }
 
     log::debug!("WebSocket background task shutting down");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fill(oid: u64, tid: u64, time: u64) -> Fill {
+        serde_json::from_value(serde_json::json!({
+            "coin": "BTC",
+            "px": "95000.0",
+            "sz": "0.01",
+            "side": "B",
+            "time": time,
+            "startPosition": "0.0",
+            "dir": "Open Long",
+            "closedPnl": "0.0",
+            "hash": "0xabc",
+            "oid": oid,
+            "crossed": true,
+            "fee": "0.01",
+            "tid": tid,
+            "cloid": null,
+            "feeToken": "USDC"
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn fill_dedup_drops_replayed_snapshot() {
+        let mut dedup = FillDedup::default();
+
+        let mut live = vec![fill(1, 10, 100), fill(2, 11, 101)];
+        dedup.retain_new(&mut live);
+        assert_eq!(live.len(), 2);
+
+        // Snapshot after reconnect replays the old fills plus a new one.
+        let mut snapshot = vec![fill(1, 10, 100), fill(2, 11, 101), fill(3, 12, 102)];
+        dedup.retain_new(&mut snapshot);
+        assert_eq!(snapshot.len(), 1);
+        assert_eq!(snapshot[0].oid, 3);
+    }
+
+    #[test]
+    fn post_request_wire_format() {
+        use alloy::signers::local::PrivateKeySigner;
+
+        use crate::hypercore::{
+            Chain,
+            types::{Action, OkResponse},
+        };
+
+        let signer = PrivateKeySigner::random();
+        let request = Action::Noop
+            .sign_sync(&signer, 1, None, None, Chain::Mainnet)
+            .unwrap();
+        let json = serde_json::to_value(Outgoing::Post {
+            id: 7,
+            request: Box::new(PostRequest::Action(request)),
+        })
+        .unwrap();
+        assert_eq!(json["method"], "post");
+        assert_eq!(json["id"], 7);
+        assert_eq!(json["request"]["type"], "action");
+        assert_eq!(json["request"]["payload"]["action"]["type"], "noop");
+        assert_eq!(json["request"]["payload"]["nonce"], 1);
+
+        let incoming: Incoming = serde_json::from_str(
+            r#"{"channel":"post","data":{"id":7,"response":{"type":"action","payload":{"status":"ok","response":{"type":"default"}}}}}"#,
+        )
+        .unwrap();
+        let Incoming::Post(resp) = incoming else {
+            panic!("expected a post response");
+        };
+        assert_eq!(resp.id, 7);
+        assert!(matches!(
+            resp.into_action().unwrap(),
+            Response::Ok(OkResponse::Default)
+        ));
+
+        let rejected = PostResponse {
+            id: 8,
+            response: serde_json::json!({"type": "error", "payload": "rate limited"}),
+        };
+        assert_eq!(
+            rejected.into_action().unwrap_err().to_string(),
+            "rate limited"
+        );
+    }
+
+    #[test]
+    fn tracker_reports_rates_lag_and_gaps() {
+        let mids: Incoming =
+            serde_json::from_str(r#"{"channel":"allMids","data":{"mids":{"BTC":"1"}}}"#).unwrap();
+        let sub = Subscription::AllMids { dex: None };
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+
+        let mut tracker = Tracker::default();
+        tracker.subscribe(&sub);
+        for secs in 0..3 {
+            let (gaps, seq) = tracker.record(&mids, at(secs));
+            assert!(gaps.is_empty());
+            assert_eq!(seq, Some((sub.clone(), secs + 1)));
+        }
+        let stats = tracker.snapshot(at(4));
+        assert_eq!(
+            stats.subscriptions[&sub],
+            SubscriptionStats {
+                messages: 3,
+                messages_per_sec: 0.3,
+                last_message_age: Some(Duration::from_secs(2)),
+            }
+        );
+
+        // One message a second: quiet for 10s is lagging, reported once.
+        assert!(tracker.lagging(at(12)).is_empty());
+        let lagging = tracker.lagging(at(13));
+        assert!(matches!(
+            &lagging[..],
+            [Event::Lagging { age, .. }] if *age == Duration::from_secs(11)
+        ));
+        assert!(tracker.lagging(at(14)).is_empty());
+
+        tracker.disconnected();
+        let (gaps, _) = tracker.record(&mids, at(20));
+        assert!(matches!(
+            &gaps[..],
+            [Event::Gap { missed, .. }] if *missed == Duration::from_secs(18)
+        ));
+        assert!(tracker.record(&mids, at(21)).0.is_empty());
+
+        // Unrelated messages carry no sequence number.
+        let trades: Incoming = serde_json::from_str(r#"{"channel":"trades","data":[]}"#).unwrap();
+        let (gaps, seq) = tracker.record(&trades, at(22));
+        assert!(gaps.is_empty() && seq.is_none());
+        assert_eq!(tracker.snapshot(at(21)).subscriptions[&sub].messages, 5);
+    }
+
+    #[test]
+    fn tracker_reports_seq_of_earliest_subscription() {
+        let mids: Incoming =
+            serde_json::from_str(r#"{"channel":"allMids","data":{"mids":{"BTC":"1"}}}"#).unwrap();
+        // Both match the default DEX's mids.
+        let first = Subscription::AllMids {
+            dex: Some(String::new()),
+        };
+        let second = Subscription::AllMids { dex: None };
+        let now = Instant::now();
+
+        let mut tracker = Tracker::default();
+        tracker.subscribe(&first);
+        tracker.record(&mids, now);
+        tracker.subscribe(&second);
+        for seq in 2..5 {
+            assert_eq!(tracker.record(&mids, now).1, Some((first.clone(), seq)));
+        }
+        assert_eq!(tracker.snapshot(now).subscriptions[&second].messages, 3);
+
+        tracker.unsubscribe(&first);
+        assert_eq!(tracker.record(&mids, now).1, Some((second, 4)));
+    }
+
+    #[tokio::test]
+    async fn subscriptions_reflect_calls() {
+        // Nothing listens there; subscriptions are tracked while reconnecting.
+        let ws = Connection::new("ws://127.0.0.1:9".parse().unwrap());
+        let trades = Subscription::Trades { coin: "BTC".into() };
+        let bbo = Subscription::Bbo { coin: "ETH".into() };
+
+        ws.subscribe(trades.clone());
+        let _feed = ws.bbo("ETH");
+        assert_eq!(
+            ws.subscriptions(),
+            HashSet::from([trades.clone(), bbo.clone()])
+        );
+
+        ws.unsubscribe(trades);
+        assert_eq!(ws.subscriptions(), HashSet::from([bbo]));
+
+        ws.unsubscribe_all();
+        assert!(ws.subscriptions().is_empty());
+    }
+
+    #[test]
+    fn dropped_feeds_release_their_subscription() {
+        let trades = Subscription::Trades { coin: "BTC".into() };
+        let bbo = Subscription::Bbo { coin: "ETH".into() };
+        let mids = Subscription::AllMids { dex: None };
+        let (trades_tx, trades_rx) = unbounded_channel();
+        let (bbo_tx, bbo_rx) = unbounded_channel();
+        let (bbo_tx2, _bbo_rx2) = unbounded_channel();
+        let (mids_tx, mids_rx) = unbounded_channel();
+        let mut routes = vec![
+            (trades.clone(), trades_tx),
+            (bbo.clone(), bbo_tx),
+            (bbo.clone(), bbo_tx2),
+            (mids.clone(), mids_tx),
+        ];
+        let direct = HashSet::from([mids.clone()]);
+        assert!(prune_routes(&mut routes, &direct).is_empty());
+
+        drop((trades_rx, bbo_rx, mids_rx));
+        // BBO still has a feed, and mids are also wanted by the event stream.
+        assert_eq!(prune_routes(&mut routes, &direct), HashSet::from([trades]));
+        assert_eq!(routes.len(), 1);
+        assert_eq!(routes[0].0, bbo);
+    }
+
+    #[test]
+    fn fill_dedup_evicts_oldest() {
+        let mut dedup = FillDedup::new(2);
+        assert!(dedup.insert(&fill(1, 10, 100)));
+        assert!(dedup.insert(&fill(2, 11, 101)));
+        assert!(dedup.insert(&fill(3, 12, 102)));
+        // The first key was evicted and is accepted again.
+        assert!(dedup.insert(&fill(1, 10, 100)));
+        assert!(!dedup.insert(&fill(3, 12, 102)));
+    }
+}
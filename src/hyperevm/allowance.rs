@@ -0,0 +1,255 @@
+//! ERC-20 allowance management.
+//!
+//! Every write path that lets a contract pull the caller's tokens starts by making
+//! sure the allowance is in place. [`AllowanceManager`] wraps that pattern, and backs
+//! [`morpho::Client::liquidate`](crate::hyperevm::morpho::Client::liquidate) and
+//! [`Disperser`](crate::hyperevm::disperse::Disperser):
+//!
+//! - checks the current allowance and skips the approval when it already covers the amount
+//! - approves exactly the amount needed, or `U256::MAX` under [`ApprovalPolicy::Infinite`]
+//! - waits for the approval to be mined before returning
+//! - revokes allowances that are no longer needed
+//!
+//! # Example
+//!
+//! ```no_run
+//! use hypersdk::hyperevm::{self, U256, allowance::{AllowanceManager, ApprovalPolicy}};
+//! use alloy::signers::local::PrivateKeySigner;
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let signer: PrivateKeySigner = "your_key".parse()?;
+//! let owner = signer.address();
+//! let provider = hyperevm::mainnet_with_signer(signer).await?;
+//!
+//! let token = "0x...".parse()?;
+//! let spender = "0x...".parse()?;
+//!
+//! let manager = AllowanceManager::new(provider, owner).policy(ApprovalPolicy::Exact);
+//! manager.ensure(token, spender, U256::from(1_000_000)).await?;
+//!
+//! // Later, once the position is closed
+//! manager.revoke(token, spender).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use alloy::{network::ReceiptResponse, primitives::TxHash};
+use anyhow::{Result, anyhow};
+
+use crate::hyperevm::{Address, ERC20, Provider, U256};
+
+/// How much to approve when the current allowance is insufficient.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ApprovalPolicy {
+    /// Approve exactly the amount required by the operation.
+    #[default]
+    Exact,
+    /// Approve `U256::MAX` so later operations skip the approval transaction.
+    Infinite,
+}
+
+impl ApprovalPolicy {
+    /// Returns the amount to approve for an operation that needs `required`.
+    #[must_use]
+    pub fn amount(self, required: U256) -> U256 {
+        match self {
+            Self::Exact => required,
+            Self::Infinite => U256::MAX,
+        }
+    }
+}
+
+/// Result of [`AllowanceManager::ensure`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AllowanceOutcome {
+    /// The existing allowance already covered the amount; nothing was sent.
+    Sufficient {
+        /// Current allowance
+        allowance: U256,
+    },
+    /// An approval transaction was mined.
+    Approved {
+        /// Newly approved allowance
+        allowance: U256,
+        /// Approval transaction hash
+        tx_hash: TxHash,
+    },
+}
+
+impl AllowanceOutcome {
+    /// Returns the allowance in place after the call.
+    #[must_use]
+    pub fn allowance(&self) -> U256 {
+        match self {
+            Self::Sufficient { allowance } | Self::Approved { allowance, .. } => *allowance,
+        }
+    }
+
+    /// Returns the approval transaction hash, if one was sent.
+    #[must_use]
+    pub fn tx_hash(&self) -> Option<TxHash> {
+        match self {
+            Self::Sufficient { .. } => None,
+            Self::Approved { tx_hash, .. } => Some(*tx_hash),
+        }
+    }
+}
+
+/// A revoked `(token, spender)` allowance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Revoked {
+    /// ERC-20 token address
+    pub token: Address,
+    /// Spender whose allowance was reset to zero
+    pub spender: Address,
+    /// Allowance before revoking
+    pub previous: U256,
+    /// Revoke transaction hash
+    pub tx_hash: TxHash,
+}
+
+/// Checks, grants, and revokes ERC-20 allowances for a single owner.
+///
+/// The provider must be able to sign for `owner` (see
+/// [`mainnet_with_signer`](crate::hyperevm::mainnet_with_signer)).
+pub struct AllowanceManager<P>
+where
+    P: Provider,
+{
+    provider: P,
+    owner: Address,
+    policy: ApprovalPolicy,
+    confirmations: u64,
+}
+
+impl<P> AllowanceManager<P>
+where
+    P: Provider,
+{
+    /// Creates a manager for `owner` with [`ApprovalPolicy::Exact`] and one confirmation.
+    pub fn new(provider: P, owner: Address) -> Self {
+        Self {
+            provider,
+            owner,
+            policy: ApprovalPolicy::default(),
+            confirmations: 1,
+        }
+    }
+
+    /// Sets the approval policy.
+    #[must_use]
+    pub fn policy(mut self, policy: ApprovalPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Sets how many confirmations to wait for after each approval.
+    #[must_use]
+    pub fn confirmations(mut self, confirmations: u64) -> Self {
+        self.confirmations = confirmations;
+        self
+    }
+
+    /// Returns the owner whose allowances are managed.
+    pub fn owner(&self) -> Address {
+        self.owner
+    }
+
+    /// Returns the current allowance of `spender` over the owner's `token`.
+    pub async fn allowance(&self, token: Address, spender: Address) -> Result<U256> {
+        let erc20 = ERC20::new(token, self.provider.clone());
+        Ok(erc20.allowance(self.owner, spender).call().await?)
+    }
+
+    /// Makes sure `spender` can pull at least `amount` of `token`.
+    ///
+    /// Sends an approval (sized by the [`ApprovalPolicy`]) only when the current
+    /// allowance is lower than `amount`, and waits for it to be mined.
+    pub async fn ensure(
+        &self,
+        token: Address,
+        spender: Address,
+        amount: U256,
+    ) -> Result<AllowanceOutcome> {
+        let current = self.allowance(token, spender).await?;
+        if current >= amount {
+            return Ok(AllowanceOutcome::Sufficient { allowance: current });
+        }
+
+        let allowance = self.policy.amount(amount);
+        let tx_hash = self.approve(token, spender, allowance).await?;
+        Ok(AllowanceOutcome::Approved { allowance, tx_hash })
+    }
+
+    /// Sets the allowance of `spender` to exactly `amount`, regardless of the current value.
+    ///
+    /// Returns the hash of the mined approval transaction.
+    pub async fn approve(&self, token: Address, spender: Address, amount: U256) -> Result<TxHash> {
+        let erc20 = ERC20::new(token, self.provider.clone());
+        let receipt = erc20
+            .approve(spender, amount)
+            .from(self.owner)
+            .send()
+            .await?
+            .with_required_confirmations(self.confirmations)
+            .get_receipt()
+            .await?;
+
+        if !receipt.status() {
+            return Err(anyhow!(
+                "approve({spender}, {amount}) on {token} reverted in {}",
+                receipt.transaction_hash()
+            ));
+        }
+
+        Ok(receipt.transaction_hash())
+    }
+
+    /// Resets the allowance of `spender` to zero.
+    ///
+    /// Returns `None` without sending a transaction if the allowance is already zero.
+    pub async fn revoke(&self, token: Address, spender: Address) -> Result<Option<Revoked>> {
+        let previous = self.allowance(token, spender).await?;
+        if previous.is_zero() {
+            return Ok(None);
+        }
+
+        let tx_hash = self.approve(token, spender, U256::ZERO).await?;
+        Ok(Some(Revoked {
+            token,
+            spender,
+            previous,
+            tx_hash,
+        }))
+    }
+
+    /// Revokes every non-zero allowance in `pairs` of `(token, spender)`.
+    ///
+    /// Revocations are sent one after another so nonces stay ordered. Stops at the
+    /// first failure; allowances revoked before it stay revoked.
+    pub async fn revoke_all(
+        &self,
+        pairs: impl IntoIterator<Item = (Address, Address)>,
+    ) -> Result<Vec<Revoked>> {
+        let mut revoked = vec![];
+        for (token, spender) in pairs {
+            if let Some(r) = self.revoke(token, spender).await? {
+                revoked.push(r);
+            }
+        }
+        Ok(revoked)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_approval_policy_amount() {
+        let required = U256::from(1_500_000);
+        assert_eq!(ApprovalPolicy::Exact.amount(required), required);
+        assert_eq!(ApprovalPolicy::Infinite.amount(required), U256::MAX);
+        assert_eq!(ApprovalPolicy::default(), ApprovalPolicy::Exact);
+    }
+}
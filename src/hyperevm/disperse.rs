@@ -0,0 +1,218 @@
+//! Batch ERC-20 transfers.
+//!
+//! [`Disperser`] pays many recipients from one account, for airdrops and payouts:
+//!
+//! - with a [Disperse](https://disperse.app)-compatible contract configured, payouts are
+//!   sent in chunks through `disperseToken`, after approving the contract for the total
+//! - otherwise each payout is its own `transfer`, sent back to back with consecutive
+//!   nonces and confirmed at the end
+//!
+//! # Example
+//!
+//! ```no_run
+//! use hypersdk::hyperevm::{self, U256, disperse::Disperser};
+//! use alloy::signers::local::PrivateKeySigner;
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let signer: PrivateKeySigner = "your_key".parse()?;
+//! let sender = signer.address();
+//! let provider = hyperevm::mainnet_with_signer(signer).await?;
+//!
+//! let token = "0x...".parse()?;
+//! let recipients = vec!["0x...".parse()?, "0x...".parse()?];
+//!
+//! let disperser = Disperser::new(provider, sender).contract("0x...".parse()?);
+//! let batches = disperser
+//!     .send_same(token, &recipients, U256::from(1_000_000))
+//!     .await?;
+//! for batch in batches {
+//!     println!("{} payouts in {} (ok: {})", batch.payouts.len(), batch.tx_hash, batch.success);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use alloy::{network::ReceiptResponse, primitives::TxHash, sol};
+use anyhow::{Result, anyhow};
+
+use crate::hyperevm::{Address, ERC20, Provider, U256, allowance::AllowanceManager};
+
+sol! {
+    #[derive(Debug)]
+    #[sol(rpc)]
+    interface IDisperse {
+        function disperseToken(address token, address[] recipients, uint256[] values) external;
+    }
+}
+
+/// Default number of payouts per `disperseToken` call.
+pub const DEFAULT_CHUNK_SIZE: usize = 200;
+
+/// A group of payouts settled by one transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisperseBatch {
+    /// `(recipient, amount)` pairs paid by the transaction
+    pub payouts: Vec<(Address, U256)>,
+    /// Transaction hash
+    pub tx_hash: TxHash,
+    /// Whether the transaction succeeded
+    pub success: bool,
+}
+
+/// Sends ERC-20 payouts to many recipients.
+///
+/// The provider must be able to sign for `sender`.
+pub struct Disperser<P>
+where
+    P: Provider,
+{
+    provider: P,
+    sender: Address,
+    contract: Option<Address>,
+    chunk_size: usize,
+}
+
+impl<P> Disperser<P>
+where
+    P: Provider,
+{
+    /// Creates a disperser sending one transfer per payout.
+    pub fn new(provider: P, sender: Address) -> Self {
+        Self {
+            provider,
+            sender,
+            contract: None,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+        }
+    }
+
+    /// Routes payouts through a Disperse-compatible contract.
+    #[must_use]
+    pub fn contract(mut self, contract: Address) -> Self {
+        self.contract = Some(contract);
+        self
+    }
+
+    /// Sets the number of payouts per contract call. Ignored without a contract.
+    #[must_use]
+    pub fn chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    /// Sends `amount` of `token` to each of `recipients`.
+    pub async fn send_same(
+        &self,
+        token: Address,
+        recipients: &[Address],
+        amount: U256,
+    ) -> Result<Vec<DisperseBatch>> {
+        let payouts: Vec<_> = recipients.iter().map(|&to| (to, amount)).collect();
+        self.send(token, &payouts).await
+    }
+
+    /// Sends each `(recipient, amount)` payout of `token`.
+    ///
+    /// Fails before sending anything if the sender's balance doesn't cover the total.
+    /// A reverted transaction is reported with `success = false` rather than as an
+    /// error, so the caller can retry only the affected payouts.
+    pub async fn send(
+        &self,
+        token: Address,
+        payouts: &[(Address, U256)],
+    ) -> Result<Vec<DisperseBatch>> {
+        if payouts.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let total = payouts
+            .iter()
+            .try_fold(U256::ZERO, |acc, (_, amount)| acc.checked_add(*amount))
+            .ok_or_else(|| anyhow!("payout total overflows uint256"))?;
+        let balance = ERC20::new(token, self.provider.clone())
+            .balanceOf(self.sender)
+            .call()
+            .await?;
+        if balance < total {
+            return Err(anyhow!(
+                "insufficient balance of {token}: {balance} < {total}"
+            ));
+        }
+
+        match self.contract {
+            Some(contract) => {
+                self.send_with_contract(contract, token, payouts, total)
+                    .await
+            }
+            None => self.send_sequential(token, payouts).await,
+        }
+    }
+
+    async fn send_with_contract(
+        &self,
+        contract: Address,
+        token: Address,
+        payouts: &[(Address, U256)],
+        total: U256,
+    ) -> Result<Vec<DisperseBatch>> {
+        AllowanceManager::new(self.provider.clone(), self.sender)
+            .ensure(token, contract, total)
+            .await?;
+
+        let disperse = IDisperse::new(contract, self.provider.clone());
+        let mut batches = vec![];
+        for chunk in payouts.chunks(self.chunk_size) {
+            let (recipients, values): (Vec<_>, Vec<_>) = chunk.iter().copied().unzip();
+            let receipt = disperse
+                .disperseToken(token, recipients, values)
+                .from(self.sender)
+                .send()
+                .await?
+                .get_receipt()
+                .await?;
+            batches.push(DisperseBatch {
+                payouts: chunk.to_vec(),
+                tx_hash: receipt.transaction_hash(),
+                success: receipt.status(),
+            });
+        }
+        Ok(batches)
+    }
+
+    async fn send_sequential(
+        &self,
+        token: Address,
+        payouts: &[(Address, U256)],
+    ) -> Result<Vec<DisperseBatch>> {
+        let erc20 = ERC20::new(token, self.provider.clone());
+        let mut nonce = self
+            .provider
+            .get_transaction_count(self.sender)
+            .pending()
+            .await?;
+
+        // Broadcast everything first, then wait for confirmations.
+        let mut pending = Vec::with_capacity(payouts.len());
+        for &(to, amount) in payouts {
+            let tx = erc20
+                .transfer(to, amount)
+                .from(self.sender)
+                .nonce(nonce)
+                .send()
+                .await?;
+            pending.push(((to, amount), tx));
+            nonce += 1;
+        }
+
+        let mut batches = Vec::with_capacity(pending.len());
+        for (payout, tx) in pending {
+            let receipt = tx.get_receipt().await?;
+            batches.push(DisperseBatch {
+                payouts: vec![payout],
+                tx_hash: receipt.transaction_hash(),
+                success: receipt.status(),
+            });
+        }
+        Ok(batches)
+    }
+}
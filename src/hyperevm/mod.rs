@@ -14,7 +14,13 @@
 //!
 //! # Submodules
 //!
+//! - [`allowance`]: ERC-20 allowance checks, approvals, and revocations
+//! - [`disperse`]: Batch ERC-20 payouts to many recipients
 //! - [`morpho`]: Morpho Blue lending protocol integration
+//! - [`multicall`]: Batched reads of arbitrary contracts in one round trip
+//! - [`revert`]: Decoding revert data into typed protocol errors
+//! - [`staking`]: HYPE liquid staking tokens (kHYPE, stHYPE)
+//! - [`tx`]: Transaction simulation (with state overrides) before broadcasting
 //! - [`uniswap`]: Uniswap V3 DEX integration
 //!
 //! # Examples
@@ -46,13 +52,25 @@
 //! ## Wei Conversions
 //!
 //! Convert between decimal amounts and wei using `to_wei(amount, decimals)` and `from_wei(wei, decimals)`.
+//!
+//! `Decimal` only holds a 96-bit mantissa, so large balances (or tokens with many decimals)
+//! may not fit. Use [`try_from_wei`] / [`try_to_wei`] to detect that, or [`from_wei_lossy`]
+//! to drop the low-order digits. For 18-decimal math that stays in `U256`, see [`Wad`].
 
+pub mod allowance;
+pub mod disperse;
 pub mod morpho;
+pub mod multicall;
+pub mod revert;
+pub mod staking;
+pub mod tx;
 pub mod uniswap;
+pub mod wad;
 
 // reimport
 pub use alloy::providers::ProviderBuilder;
 use alloy::{
+    eips::BlockNumberOrTag,
     network::{Ethereum, IntoWallet},
     transports::TransportError,
 };
@@ -62,7 +80,9 @@ pub use alloy::{
     providers::Provider as ProviderTrait,
     sol,
 };
+pub use multicall::multicall;
 use rust_decimal::Decimal;
+pub use wad::Wad;
 
 /// Default HyperEVM RPC URL.
 ///
@@ -86,6 +106,7 @@ pub type DynProvider = alloy::providers::DynProvider<Ethereum>;
 impl<T> Provider for T where T: alloy::providers::Provider<Ethereum> + Send + Clone + 'static {}
 
 sol!(
+    #[derive(Debug)]
     #[sol(rpc)]
     ERC20,
     "abi/ERC20.json"
@@ -189,6 +210,71 @@ where
     Ok(provider)
 }
 
+/// Returns the number of the last block produced at or before `timestamp` (unix seconds).
+///
+/// Binary-searches block headers, so it costs about `log2(latest block)` RPC calls.
+/// Pass the result to the `*_at_block` queries (e.g.
+/// [`morpho::Client::apy_at_block`]) to read state as of a past date.
+///
+/// Fails if `timestamp` is before the genesis block.
+///
+/// # Example
+///
+/// ```no_run
+/// use hypersdk::hyperevm;
+///
+/// # async fn example() -> anyhow::Result<()> {
+/// let provider = hyperevm::mainnet().await?;
+/// // 2025-01-01T00:00:00Z
+/// let block = hyperevm::block_by_timestamp(&provider, 1_735_689_600).await?;
+/// println!("first block of 2025: {}", block + 1);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn block_by_timestamp<P: Provider>(provider: &P, timestamp: u64) -> anyhow::Result<u64> {
+    let latest = provider.get_block_number().await?;
+    bisect_block(latest, timestamp, |number| async move {
+        let block = provider
+            .get_block_by_number(BlockNumberOrTag::Number(number))
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("block {number} not found"))?;
+        Ok(block.header.timestamp)
+    })
+    .await
+}
+
+/// Finds the last block in `0..=latest` whose timestamp is `<= timestamp`.
+async fn bisect_block<F, Fut>(
+    latest: u64,
+    timestamp: u64,
+    block_timestamp: F,
+) -> anyhow::Result<u64>
+where
+    F: Fn(u64) -> Fut,
+    Fut: Future<Output = anyhow::Result<u64>>,
+{
+    if block_timestamp(latest).await? <= timestamp {
+        return Ok(latest);
+    }
+    anyhow::ensure!(
+        block_timestamp(0).await? <= timestamp,
+        "timestamp {timestamp} is before the genesis block"
+    );
+
+    // Invariant: ts(lo) <= timestamp < ts(hi)
+    let (mut lo, mut hi) = (0, latest);
+    while hi - lo > 1 {
+        let mid = lo + (hi - lo) / 2;
+        if block_timestamp(mid).await? <= timestamp {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+
+    Ok(lo)
+}
+
 /// Converts a decimal amount to wei representation.
 ///
 /// Wei is the smallest unit of Ethereum tokens (like satoshis for Bitcoin).
@@ -201,6 +287,9 @@ where
 /// # Example
 ///
 /// Convert 1.5 ETH to wei (18 decimals): `to_wei(dec!(1.5), 18)`
+///
+/// Negative amounts and digits beyond `decimals` are not rejected; use [`try_to_wei`]
+/// when the input isn't trusted.
 #[must_use]
 #[inline]
 pub fn to_wei(mut size: Decimal, decimals: u32) -> U256 {
@@ -218,15 +307,119 @@ pub fn to_wei(mut size: Decimal, decimals: u32) -> U256 {
 /// # Example
 ///
 /// Convert wei back to decimal: `from_wei(wei, 18)`
+///
+/// # Panics
+///
+/// Panics if `wei` does not fit in a [`Decimal`] mantissa (96 bits) or `decimals > 28`.
+/// Use [`try_from_wei`] or [`from_wei_lossy`] for amounts that may be that large.
 #[must_use]
 #[inline]
 pub fn from_wei(wei: U256, decimals: u32) -> Decimal {
     Decimal::from_i128_with_scale(wei.to::<i128>(), decimals)
 }
 
+/// Converts wei to a decimal amount, returning `None` if it can't be represented exactly.
+///
+/// Values with more than 28 decimals are accepted as long as the extra digits are zero.
+///
+/// # Example
+///
+/// ```rust
+/// use hypersdk::hyperevm::{U256, try_from_wei};
+/// use rust_decimal::dec;
+///
+/// assert_eq!(try_from_wei(U256::from(1_500_000), 6), Some(dec!(1.5)));
+/// assert_eq!(try_from_wei(U256::MAX, 18), None);
+/// ```
+#[must_use]
+pub fn try_from_wei(mut wei: U256, decimals: u32) -> Option<Decimal> {
+    let ten = U256::from(10);
+    let mut scale = decimals;
+    while scale > Decimal::MAX_SCALE {
+        if !(wei % ten).is_zero() {
+            return None;
+        }
+        wei /= ten;
+        scale -= 1;
+    }
+
+    let mantissa = i128::try_from(u128::try_from(wei).ok()?).ok()?;
+    Decimal::try_from_i128_with_scale(mantissa, scale).ok()
+}
+
+/// Converts wei to a decimal amount, truncating digits that don't fit.
+///
+/// Low-order decimals are dropped first; values too large for a [`Decimal`] saturate
+/// at [`Decimal::MAX`]. Never panics.
+///
+/// # Example
+///
+/// ```rust
+/// use hypersdk::hyperevm::{U256, from_wei_lossy};
+/// use rust_decimal::{Decimal, dec};
+///
+/// assert_eq!(
+///     from_wei_lossy(U256::MAX, 77),
+///     dec!(1.1579208923731619542357098500)
+/// );
+/// assert_eq!(from_wei_lossy(U256::MAX, 0), Decimal::MAX);
+/// ```
+#[must_use]
+pub fn from_wei_lossy(mut wei: U256, decimals: u32) -> Decimal {
+    let ten = U256::from(10);
+    let max = U256::from(Decimal::MAX.mantissa());
+    let mut scale = decimals;
+    while scale > Decimal::MAX_SCALE || wei > max {
+        if scale == 0 {
+            return Decimal::MAX;
+        }
+        wei /= ten;
+        scale -= 1;
+    }
+
+    Decimal::from_i128_with_scale(wei.to::<i128>(), scale)
+}
+
+/// Converts a decimal amount to wei, returning `None` if it can't be represented exactly.
+///
+/// Fails for negative amounts, amounts with more than `decimals` fractional digits,
+/// and results that overflow `U256`.
+///
+/// # Example
+///
+/// ```rust
+/// use hypersdk::hyperevm::{U256, try_to_wei};
+/// use rust_decimal::dec;
+///
+/// assert_eq!(try_to_wei(dec!(1.5), 6), Some(U256::from(1_500_000)));
+/// assert_eq!(try_to_wei(dec!(0.0000001), 6), None);
+/// assert_eq!(try_to_wei(dec!(-1), 6), None);
+/// ```
+#[must_use]
+pub fn try_to_wei(size: Decimal, decimals: u32) -> Option<U256> {
+    if size.is_sign_negative() && !size.is_zero() {
+        return None;
+    }
+
+    let mut mantissa = size.mantissa().unsigned_abs();
+    let scale = size.scale();
+    if scale > decimals {
+        let div = 10u128.pow(scale - decimals);
+        if mantissa % div != 0 {
+            return None;
+        }
+        mantissa /= div;
+        return Some(U256::from(mantissa));
+    }
+
+    let mul = U256::from(10).checked_pow(U256::from(decimals - scale))?;
+    U256::from(mantissa).checked_mul(mul)
+}
+
 #[cfg(test)]
 mod tests {
     use alloy::{primitives::U256, providers::ProviderBuilder};
+    use proptest::prelude::*;
     use rust_decimal::dec;
 
     use super::*;
@@ -273,4 +466,155 @@ mod tests {
             assert_eq!(to_wei(got, decimals), expect, "failed at {index}");
         }
     }
+
+    #[tokio::test]
+    async fn test_bisect_block() {
+        // Block n is produced at 1000 + 2n, with a stall between blocks 40 and 41.
+        let ts = |n: u64| async move {
+            Ok::<_, anyhow::Error>(1000 + 2 * n + if n > 40 { 60 } else { 0 })
+        };
+
+        assert_eq!(bisect_block(100, 1000, ts).await.unwrap(), 0);
+        assert_eq!(bisect_block(100, 1001, ts).await.unwrap(), 0);
+        assert_eq!(bisect_block(100, 1002, ts).await.unwrap(), 1);
+        assert_eq!(bisect_block(100, 1100, ts).await.unwrap(), 40);
+        assert_eq!(bisect_block(100, 1141, ts).await.unwrap(), 40);
+        assert_eq!(bisect_block(100, 1142, ts).await.unwrap(), 41);
+        assert_eq!(bisect_block(100, 99_999, ts).await.unwrap(), 100);
+        assert!(bisect_block(100, 999, ts).await.is_err());
+    }
+
+    /// Magnitudes around the Decimal/u128/U256 boundaries.
+    fn extreme_values() -> Vec<U256> {
+        let mut values = vec![
+            U256::ZERO,
+            U256::from(1),
+            U256::MAX,
+            U256::MAX - U256::from(1),
+        ];
+        for bits in [63usize, 64, 95, 96, 127, 128, 192, 255] {
+            let pow = U256::from(1) << bits;
+            values.extend([pow - U256::from(1), pow, pow + U256::from(1)]);
+        }
+        for exp in [18, 28, 29, 38, 39, 76, 77] {
+            values.push(U256::from(10).pow(U256::from(exp)));
+        }
+        values
+    }
+
+    #[test]
+    fn test_try_from_wei_extremes() {
+        let max_mantissa = U256::from(Decimal::MAX.mantissa());
+        for wei in extreme_values() {
+            for decimals in [0, 6, 18, 28, 36, 77] {
+                match try_from_wei(wei, decimals) {
+                    // Whatever converts must round-trip exactly.
+                    Some(value) => {
+                        assert_eq!(try_to_wei(value, decimals), Some(wei), "{wei} @ {decimals}")
+                    }
+                    None => assert!(
+                        wei > max_mantissa || decimals > Decimal::MAX_SCALE,
+                        "{wei} @ {decimals}"
+                    ),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_wei_lossy_extremes() {
+        for wei in extreme_values() {
+            for decimals in [0, 6, 18, 28, 36, 77] {
+                let lossy = from_wei_lossy(wei, decimals);
+                assert!(lossy >= Decimal::ZERO, "{wei} @ {decimals}");
+
+                // Exact whenever the checked variant succeeds.
+                if let Some(exact) = try_from_wei(wei, decimals) {
+                    assert_eq!(lossy, exact, "{wei} @ {decimals}");
+                }
+
+                // Truncation only ever rounds down.
+                if lossy != Decimal::MAX {
+                    let back = try_to_wei(lossy, decimals).unwrap();
+                    assert!(back <= wei, "{wei} @ {decimals}");
+                }
+            }
+        }
+
+        assert_eq!(from_wei_lossy(U256::MAX, 0), Decimal::MAX);
+        assert_eq!(
+            from_wei_lossy(U256::MAX, 77),
+            dec!(1.1579208923731619542357098500)
+        );
+        assert_eq!(
+            from_wei_lossy(U256::from(1) << 128, 18),
+            dec!(340282366920938463463.37460743)
+        );
+    }
+
+    #[test]
+    fn test_try_to_wei_extremes() {
+        assert_eq!(
+            try_to_wei(Decimal::MAX, 0),
+            Some(U256::from(Decimal::MAX.mantissa()))
+        );
+        assert_eq!(
+            try_to_wei(Decimal::MAX, 77),
+            None,
+            "overflows U256 after scaling"
+        );
+        assert_eq!(
+            try_to_wei(dec!(1), 77),
+            Some(U256::from(10).pow(U256::from(77)))
+        );
+        assert_eq!(try_to_wei(dec!(0.1), 0), None);
+
+        // Agrees with `to_wei` wherever both are defined.
+        for size in [
+            dec!(0),
+            dec!(1),
+            dec!(72.305406316320073386),
+            dec!(79228162514.264337593543950335),
+        ] {
+            assert_eq!(try_to_wei(size, 18), Some(to_wei(size, 18)), "{size}");
+        }
+    }
+
+    /// Values of every bit length, from 0 up to full 256-bit words.
+    fn any_u256() -> impl Strategy<Value = U256> {
+        (any::<[u64; 4]>(), 0usize..256).prop_map(|(limbs, shift)| U256::from_limbs(limbs) >> shift)
+    }
+
+    proptest! {
+        #[test]
+        fn prop_try_from_wei_roundtrips(wei in any_u256(), decimals in 0u32..=77) {
+            match try_from_wei(wei, decimals) {
+                Some(value) => prop_assert_eq!(try_to_wei(value, decimals), Some(wei)),
+                None => prop_assert!(
+                    wei > U256::from(Decimal::MAX.mantissa()) || decimals > Decimal::MAX_SCALE
+                ),
+            }
+        }
+
+        #[test]
+        fn prop_from_wei_lossy_rounds_down(wei in any_u256(), decimals in 0u32..=77) {
+            let lossy = from_wei_lossy(wei, decimals);
+            prop_assert!(lossy >= Decimal::ZERO);
+            if let Some(exact) = try_from_wei(wei, decimals) {
+                prop_assert_eq!(lossy, exact);
+            }
+            if lossy != Decimal::MAX {
+                prop_assert!(try_to_wei(lossy, decimals).unwrap() <= wei);
+            }
+        }
+
+        #[test]
+        fn prop_try_to_wei_roundtrips(mantissa in 0..10_000_000_000i64, scale in 0u32..=18) {
+            // At most 28 digits once scaled to 18 decimals, so the way back is exact.
+            let size = Decimal::new(mantissa, scale);
+            let wei = try_to_wei(size, 18).unwrap();
+            prop_assert_eq!(try_from_wei(wei, 18), Some(size));
+            prop_assert_eq!(from_wei_lossy(wei, 18), size);
+        }
+    }
 }
@@ -0,0 +1,226 @@
+//! MetaMorpho allocation breakdown.
+//!
+//! [`MetaClient::allocation`] reports where a vault's deposits sit: the assets
+//! supplied to each market of its withdraw queue, the market's cap and utilization,
+//! and the idle balance that earns nothing.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use hypersdk::hyperevm::morpho::MetaClient;
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let client = MetaClient::mainnet().await?;
+//! let allocation = client.allocation("0x...".parse()?).await?;
+//!
+//! println!("total: {} (idle {})", allocation.total_assets, allocation.idle);
+//! for market in &allocation.markets {
+//!     println!(
+//!         "{}: {} / cap {} ({:.2}% utilized)",
+//!         market.market_id,
+//!         market.supplied,
+//!         market.cap,
+//!         market.utilization * rust_decimal::Decimal::ONE_HUNDRED
+//!     );
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use alloy::{
+    primitives::{Address, U256},
+    sol_types::SolCall,
+};
+use rust_decimal::Decimal;
+
+use super::{
+    MarketId, MetaClient,
+    contracts::{IMetaMorpho, IMorpho, Market, MarketParams},
+    liquidation::to_assets_down,
+};
+use crate::hyperevm::{Provider, from_wei_lossy, multicall};
+
+/// A vault's position in one market.
+#[derive(Debug, Clone, Copy)]
+pub struct MarketAllocation {
+    /// Market identifier
+    pub market_id: MarketId,
+    /// Market parameters
+    pub params: MarketParams,
+    /// Assets supplied by the vault
+    pub supplied: Decimal,
+    /// Vault supply cap for the market
+    pub cap: Decimal,
+    /// Borrowed / supplied assets of the whole market
+    pub utilization: Decimal,
+    /// Whether the market is enabled in the vault
+    pub enabled: bool,
+}
+
+impl MarketAllocation {
+    /// Returns true for an idle market (no collateral, nothing can be borrowed).
+    #[must_use]
+    pub fn is_idle(&self) -> bool {
+        self.params.collateralToken.is_zero()
+    }
+
+    /// Supply room left under the cap.
+    #[must_use]
+    pub fn remaining_cap(&self) -> Decimal {
+        (self.cap - self.supplied).max(Decimal::ZERO)
+    }
+}
+
+/// Breakdown of a vault's assets across its markets.
+#[derive(Debug, Clone)]
+pub struct VaultAllocation {
+    /// Underlying asset
+    pub asset: Address,
+    /// Decimals of the underlying asset
+    pub decimals: u8,
+    /// Total assets reported by the vault
+    pub total_assets: Decimal,
+    /// Unallocated assets: the vault's own token balance plus supply in idle markets
+    pub idle: Decimal,
+    /// Markets in withdraw queue order
+    pub markets: Vec<MarketAllocation>,
+}
+
+impl VaultAllocation {
+    /// Assets supplied to markets that can be borrowed from.
+    #[must_use]
+    pub fn allocated(&self) -> Decimal {
+        self.markets
+            .iter()
+            .filter(|m| !m.is_idle())
+            .map(|m| m.supplied)
+            .sum()
+    }
+
+    /// Share of total assets sitting idle, zero for an empty vault.
+    #[must_use]
+    pub fn idle_ratio(&self) -> Decimal {
+        self.idle
+            .checked_div(self.total_assets)
+            .unwrap_or(Decimal::ZERO)
+    }
+}
+
+impl<P> MetaClient<P>
+where
+    P: Provider,
+{
+    /// Returns the vault's per-market allocation and idle balance.
+    ///
+    /// All reads are batched with Multicall3 in three round trips.
+    pub async fn allocation(&self, vault: Address) -> anyhow::Result<VaultAllocation> {
+        let meta_morpho = IMetaMorpho::new(vault, self.provider.clone());
+        let (asset, total_assets, queue_len, morpho) = self
+            .provider
+            .multicall()
+            .add(meta_morpho.asset())
+            .add(meta_morpho.totalAssets())
+            .add(meta_morpho.withdrawQueueLength())
+            .add(meta_morpho.MORPHO())
+            .aggregate()
+            .await?;
+
+        let mut batch = multicall(self.provider.clone());
+        let token = batch.token_balance(asset, vault);
+        let queue: Vec<_> = (0..queue_len.to::<usize>())
+            .map(|i| {
+                batch.add(
+                    vault,
+                    <IMetaMorpho::withdrawQueueCall as SolCall>::new((U256::from(i),)),
+                )
+            })
+            .collect();
+        let results = batch.call().await?;
+        let decimals = results.get(&token.decimals)?;
+        let balance = results.get(&token.balance)?;
+        let market_ids = queue
+            .iter()
+            .map(|slot| results.get(slot))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let mut batch = multicall(self.provider.clone());
+        let slots: Vec<_> = market_ids
+            .iter()
+            .map(|&id| {
+                (
+                    batch.add(vault, <IMetaMorpho::configCall as SolCall>::new((id,))),
+                    batch.add(morpho, IMorpho::idToMarketParamsCall { id }),
+                    batch.add(morpho, IMorpho::marketCall { id }),
+                    batch.add(morpho, IMorpho::positionCall { id, user: vault }),
+                )
+            })
+            .collect();
+        let results = batch.call().await?;
+
+        let to_decimal = |raw: U256| from_wei_lossy(raw, u32::from(decimals));
+        let mut idle = to_decimal(balance);
+        let mut markets = Vec::with_capacity(market_ids.len());
+        for (&market_id, (config, params, market, position)) in market_ids.iter().zip(&slots) {
+            let config = results.get(config)?;
+            let params: MarketParams = results.get(params)?.into();
+            let market: Market = results.get(market)?.into();
+            let position = results.get(position)?;
+
+            let supplied = to_decimal(to_assets_down(
+                position.supplyShares,
+                U256::from(market.totalSupplyAssets),
+                U256::from(market.totalSupplyShares),
+            ));
+            let allocation = MarketAllocation {
+                market_id,
+                params,
+                supplied,
+                cap: to_decimal(U256::from(config.cap)),
+                utilization: utilization(&market),
+                enabled: config.enabled,
+            };
+            if allocation.is_idle() {
+                idle += supplied;
+            }
+            markets.push(allocation);
+        }
+
+        Ok(VaultAllocation {
+            asset,
+            decimals,
+            total_assets: to_decimal(total_assets),
+            idle,
+            markets,
+        })
+    }
+}
+
+/// Borrowed / supplied assets of a market, zero without supply.
+fn utilization(market: &Market) -> Decimal {
+    let supplied = from_wei_lossy(U256::from(market.totalSupplyAssets), 0);
+    let borrowed = from_wei_lossy(U256::from(market.totalBorrowAssets), 0);
+    borrowed.checked_div(supplied).unwrap_or(Decimal::ZERO)
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::dec;
+
+    use super::*;
+
+    #[test]
+    fn test_utilization() {
+        let mut market = Market {
+            totalSupplyAssets: 1_000_000,
+            totalSupplyShares: 1_000_000_000_000,
+            totalBorrowAssets: 920_000,
+            totalBorrowShares: 920_000_000_000,
+            lastUpdate: 0,
+            fee: 0,
+        };
+        assert_eq!(utilization(&market), dec!(0.92));
+
+        market.totalSupplyAssets = 0;
+        assert_eq!(utilization(&market), Decimal::ZERO);
+    }
+}
@@ -7,7 +7,11 @@
 
 #![allow(clippy::too_many_arguments)]
 
-use alloy::sol;
+use alloy::{
+    primitives::{FixedBytes, keccak256},
+    sol,
+    sol_types::SolValue,
+};
 
 macro_rules! transmute_this {
     ($from:ty,$into:ty) => {
@@ -48,6 +52,13 @@ sol! {
     }
 }
 
+impl MarketParams {
+    /// Returns the market id (`keccak256(abi.encode(marketParams))`).
+    pub fn id(&self) -> FixedBytes<32> {
+        keccak256(self.abi_encode())
+    }
+}
+
 sol!(
     #[derive(Debug)]
     #[sol(rpc)]
@@ -0,0 +1,266 @@
+//! MetaMorpho vault curation.
+//!
+//! Write access to the curator, allocator, and owner roles of a MetaMorpho vault:
+//!
+//! - supply caps: [`submit_cap`](MetaClient::submit_cap) / [`accept_cap`](MetaClient::accept_cap)
+//! - queues: [`set_supply_queue`](MetaClient::set_supply_queue) /
+//!   [`update_withdraw_queue`](MetaClient::update_withdraw_queue)
+//! - performance fee: [`set_fee`](MetaClient::set_fee)
+//!
+//! Raising a cap is timelocked: `submitCap` only records a [`Pending`] value, which can
+//! be accepted once the vault's timelock has elapsed. Lowering a cap applies immediately.
+//! Every write is simulated first (see [`TxManager`]), so a call missing its role or
+//! hitting a timelock fails with the decoded MetaMorpho error instead of a mined revert.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use hypersdk::hyperevm::{
+//!     self, U256,
+//!     morpho::{self, MetaClient, contracts::MarketParams},
+//! };
+//! use alloy::signers::local::PrivateKeySigner;
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let signer: PrivateKeySigner = "your_key".parse()?;
+//! let curator = signer.address();
+//! let client = MetaClient::new(hyperevm::mainnet_with_signer(signer).await?);
+//!
+//! let vault = "0x...".parse()?;
+//! let morpho_addr = "0x...".parse()?;
+//! let market_id = [0u8; 32].into();
+//! let params: MarketParams = morpho::Client::new(client.provider().clone())
+//!     .instance(morpho_addr)
+//!     .idToMarketParams(market_id)
+//!     .call()
+//!     .await?
+//!     .into();
+//!
+//! client.submit_cap(vault, params, U256::from(1_000_000_000_000u64), curator).await?;
+//!
+//! // Later, once the timelock has elapsed
+//! if let Some(pending) = client.pending_cap(vault, market_id).await? {
+//!     println!("cap {} acceptable at {}", pending.value, pending.valid_at);
+//!     client.accept_cap(vault, params, curator).await?;
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use alloy::{
+    eips::BlockNumberOrTag,
+    primitives::{Address, U256},
+    rpc::types::TransactionReceipt,
+};
+use anyhow::anyhow;
+
+use super::{MarketId, MetaClient, contracts::MarketParams};
+use crate::hyperevm::{Provider, Wad, tx::TxManager};
+
+/// A timelocked value waiting to be accepted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pending<T> {
+    /// Submitted value
+    pub value: T,
+    /// Unix timestamp (seconds) from which the value can be accepted
+    pub valid_at: u64,
+}
+
+impl<T> Pending<T> {
+    /// Returns true if the value can be accepted at `timestamp`.
+    #[must_use]
+    pub fn is_ready(&self, timestamp: u64) -> bool {
+        timestamp >= self.valid_at
+    }
+}
+
+/// Configuration of a market in a vault.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MarketConfig {
+    /// Supply cap in raw asset units
+    pub cap: U256,
+    /// Whether the market is in the withdraw queue
+    pub enabled: bool,
+    /// Unix timestamp from which the market can be removed, 0 if no removal is pending
+    pub removable_at: u64,
+}
+
+impl<P> MetaClient<P>
+where
+    P: Provider,
+{
+    /// Returns the vault's timelock in seconds.
+    pub async fn timelock(&self, vault: Address) -> anyhow::Result<u64> {
+        let timelock = self.instance(vault).timelock().call().await?;
+        Ok(timelock.to())
+    }
+
+    /// Returns the vault's configuration for a market.
+    pub async fn market_config(
+        &self,
+        vault: Address,
+        market_id: MarketId,
+    ) -> anyhow::Result<MarketConfig> {
+        let config = self.instance(vault).config(market_id).call().await?;
+        Ok(MarketConfig {
+            cap: U256::from(config.cap),
+            enabled: config.enabled,
+            removable_at: config.removableAt,
+        })
+    }
+
+    /// Returns the cap waiting to be accepted for a market, if any.
+    pub async fn pending_cap(
+        &self,
+        vault: Address,
+        market_id: MarketId,
+    ) -> anyhow::Result<Option<Pending<U256>>> {
+        let pending = self.instance(vault).pendingCap(market_id).call().await?;
+        Ok((pending.validAt != 0).then(|| Pending {
+            value: U256::from(pending.value),
+            valid_at: pending.validAt,
+        }))
+    }
+
+    /// Returns the timelock waiting to be accepted, if any.
+    pub async fn pending_timelock(&self, vault: Address) -> anyhow::Result<Option<Pending<u64>>> {
+        let pending = self.instance(vault).pendingTimelock().call().await?;
+        Ok((pending.validAt != 0).then(|| Pending {
+            value: pending.value.to(),
+            valid_at: pending.validAt,
+        }))
+    }
+
+    /// Submits a new supply cap for a market. Requires the curator role.
+    ///
+    /// Lowering the cap applies immediately; raising it creates a pending cap to be
+    /// accepted with [`accept_cap`](Self::accept_cap) after the timelock.
+    pub async fn submit_cap(
+        &self,
+        vault: Address,
+        params: impl Into<MarketParams>,
+        new_cap: U256,
+        sender: Address,
+    ) -> anyhow::Result<TransactionReceipt> {
+        let params: MarketParams = params.into();
+        let tx = self
+            .instance(vault)
+            .submitCap(params.into(), new_cap)
+            .from(sender)
+            .into_transaction_request();
+        TxManager::new(self.provider.clone()).send(tx).await
+    }
+
+    /// Accepts the pending cap of a market. Callable by anyone once the timelock elapsed.
+    ///
+    /// Fails without sending a transaction if there is no pending cap or its timelock
+    /// hasn't elapsed at the latest block.
+    pub async fn accept_cap(
+        &self,
+        vault: Address,
+        params: impl Into<MarketParams>,
+        sender: Address,
+    ) -> anyhow::Result<TransactionReceipt> {
+        let params: MarketParams = params.into();
+        let market_id = params.id();
+        let pending = self
+            .pending_cap(vault, market_id)
+            .await?
+            .ok_or_else(|| anyhow!("no pending cap for market {market_id}"))?;
+
+        let now = self.latest_timestamp().await?;
+        if !pending.is_ready(now) {
+            return Err(anyhow!(
+                "cap {} for market {market_id} is timelocked for another {}s",
+                pending.value,
+                pending.valid_at - now
+            ));
+        }
+
+        let tx = self
+            .instance(vault)
+            .acceptCap(params.into())
+            .from(sender)
+            .into_transaction_request();
+        TxManager::new(self.provider.clone()).send(tx).await
+    }
+
+    /// Replaces the supply queue. Requires the allocator role.
+    ///
+    /// Every market must have a non-zero cap.
+    pub async fn set_supply_queue(
+        &self,
+        vault: Address,
+        queue: Vec<MarketId>,
+        sender: Address,
+    ) -> anyhow::Result<TransactionReceipt> {
+        let tx = self
+            .instance(vault)
+            .setSupplyQueue(queue)
+            .from(sender)
+            .into_transaction_request();
+        TxManager::new(self.provider.clone()).send(tx).await
+    }
+
+    /// Reorders the withdraw queue. Requires the allocator role.
+    ///
+    /// `indexes` lists the current queue positions in their new order; markets left
+    /// out are removed, which requires a zero cap and either no supply or an elapsed
+    /// removal timelock.
+    pub async fn update_withdraw_queue(
+        &self,
+        vault: Address,
+        indexes: Vec<usize>,
+        sender: Address,
+    ) -> anyhow::Result<TransactionReceipt> {
+        let indexes = indexes.into_iter().map(U256::from).collect();
+        let tx = self
+            .instance(vault)
+            .updateWithdrawQueue(indexes)
+            .from(sender)
+            .into_transaction_request();
+        TxManager::new(self.provider.clone()).send(tx).await
+    }
+
+    /// Sets the performance fee (e.g. `0.1` for 10%). Requires the owner role.
+    ///
+    /// MetaMorpho caps the fee at 50% and requires a fee recipient to be set.
+    pub async fn set_fee(
+        &self,
+        vault: Address,
+        fee: Wad,
+        sender: Address,
+    ) -> anyhow::Result<TransactionReceipt> {
+        let tx = self
+            .instance(vault)
+            .setFee(fee.raw())
+            .from(sender)
+            .into_transaction_request();
+        TxManager::new(self.provider.clone()).send(tx).await
+    }
+
+    async fn latest_timestamp(&self) -> anyhow::Result<u64> {
+        let block = self
+            .provider
+            .get_block_by_number(BlockNumberOrTag::Latest)
+            .await?
+            .ok_or_else(|| anyhow!("latest block not found"))?;
+        Ok(block.header.timestamp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pending_is_ready() {
+        let pending = Pending {
+            value: U256::from(1_000),
+            valid_at: 1_700_000_000,
+        };
+        assert!(!pending.is_ready(1_699_999_999));
+        assert!(pending.is_ready(1_700_000_000));
+        assert!(pending.is_ready(1_700_000_001));
+    }
+}
@@ -0,0 +1,407 @@
+//! Morpho Blue liquidation primitives.
+//!
+//! Mirrors the on-chain math of `Morpho.sol` so liquidators can screen borrowers and
+//! size liquidations off-chain:
+//!
+//! - [`BorrowerHealth`]: debt vs. borrowing capacity of a position at the oracle price
+//! - [`liquidation_incentive_factor`]: the LIF granted for a market's LLTV
+//! - [`LiquidationQuote`]: collateral seized, debt repaid, and expected profit
+//!
+//! Market totals are read as stored, without accruing pending interest, so debts are
+//! slightly understated between interactions with the market.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use hypersdk::hyperevm::morpho;
+//! use hypersdk::Address;
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let client = morpho::Client::mainnet().await?;
+//! let morpho_addr: Address = "0x...".parse()?;
+//! let market_id = [0u8; 32].into();
+//! let borrowers: Vec<Address> = vec!["0x...".parse()?];
+//!
+//! let scan = client
+//!     .liquidatable_positions(morpho_addr, market_id, &borrowers)
+//!     .await?;
+//! for health in &scan.positions {
+//!     let quote = scan.quote(health);
+//!     println!(
+//!         "{}: seize {} collateral for {} loan, profit {}",
+//!         health.borrower, quote.seized_assets, quote.repaid_assets, quote.profit
+//!     );
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use alloy::{
+    primitives::{Address, Bytes, U256},
+    rpc::types::TransactionReceipt,
+};
+
+use super::{
+    Client, MarketId, OraclePrice,
+    contracts::{IMorpho, Market, MarketParams},
+};
+use crate::hyperevm::{
+    Provider, Wad,
+    allowance::AllowanceManager,
+    multicall,
+    tx::TxManager,
+    wad::{WAD, mul_div},
+};
+
+/// Virtual shares added to share/asset conversions (`SharesMathLib.VIRTUAL_SHARES`).
+pub const VIRTUAL_SHARES: U256 = U256::from_limbs([1_000_000, 0, 0, 0]);
+
+/// Virtual assets added to share/asset conversions (`SharesMathLib.VIRTUAL_ASSETS`).
+pub const VIRTUAL_ASSETS: U256 = U256::from_limbs([1, 0, 0, 0]);
+
+/// Liquidation cursor (0.3, WAD-scaled).
+pub const LIQUIDATION_CURSOR: U256 = U256::from_limbs([300_000_000_000_000_000, 0, 0, 0]);
+
+/// Maximum liquidation incentive factor (1.15, WAD-scaled).
+pub const MAX_LIQUIDATION_INCENTIVE_FACTOR: U256 =
+    U256::from_limbs([1_150_000_000_000_000_000, 0, 0, 0]);
+
+/// Converts supply shares to assets, rounding down like `SharesMathLib.toAssetsDown`.
+#[must_use]
+pub fn to_assets_down(shares: U256, total_assets: U256, total_shares: U256) -> U256 {
+    mul_div(
+        shares,
+        total_assets + VIRTUAL_ASSETS,
+        total_shares + VIRTUAL_SHARES,
+    )
+    .unwrap_or(U256::MAX)
+}
+
+/// Converts borrow shares to assets, rounding up like `SharesMathLib.toAssetsUp`.
+#[must_use]
+pub fn to_assets_up(shares: U256, total_assets: U256, total_shares: U256) -> U256 {
+    let num = total_assets + VIRTUAL_ASSETS;
+    let den = total_shares + VIRTUAL_SHARES;
+    // shares * num / den, rounded up. `den` is never zero thanks to virtual shares.
+    let down = mul_div(shares, num, den).unwrap_or(U256::MAX);
+    let exact = down
+        .checked_mul(den)
+        .is_some_and(|v| Some(v) == shares.checked_mul(num));
+    if exact {
+        down
+    } else {
+        down.saturating_add(U256::from(1))
+    }
+}
+
+/// Liquidation incentive factor for a market with the given LLTV.
+///
+/// `min(MAX_LIF, 1 / (1 - cursor * (1 - lltv)))`
+#[must_use]
+pub fn liquidation_incentive_factor(lltv: U256) -> Wad {
+    let one_minus_lltv = WAD.saturating_sub(lltv);
+    let discount = mul_div(LIQUIDATION_CURSOR, one_minus_lltv, WAD).unwrap_or(U256::ZERO);
+    let lif = mul_div(WAD, WAD, WAD - discount).unwrap_or(MAX_LIQUIDATION_INCENTIVE_FACTOR);
+    Wad::from_raw(lif.min(MAX_LIQUIDATION_INCENTIVE_FACTOR))
+}
+
+/// Health of a single borrower position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BorrowerHealth {
+    /// Borrower address
+    pub borrower: Address,
+    /// Borrow shares held
+    pub borrow_shares: U256,
+    /// Debt in raw loan token units (rounded up)
+    pub borrowed: U256,
+    /// Collateral in raw collateral token units
+    pub collateral: U256,
+    /// Maximum debt allowed by the collateral at the oracle price and LLTV
+    pub max_borrow: U256,
+}
+
+impl BorrowerHealth {
+    /// Computes position health from raw position data.
+    #[must_use]
+    pub fn new(
+        borrower: Address,
+        borrow_shares: U256,
+        collateral: U256,
+        market: &Market,
+        lltv: U256,
+        price: &OraclePrice,
+    ) -> Self {
+        let borrowed = to_assets_up(
+            borrow_shares,
+            U256::from(market.totalBorrowAssets),
+            U256::from(market.totalBorrowShares),
+        );
+        let max_borrow = price
+            .collateral_value(collateral)
+            .and_then(|value| mul_div(value, lltv, WAD))
+            .unwrap_or(U256::MAX);
+
+        Self {
+            borrower,
+            borrow_shares,
+            borrowed,
+            collateral,
+            max_borrow,
+        }
+    }
+
+    /// Returns true if the position can be liquidated (`borrowed > max_borrow`).
+    #[must_use]
+    pub fn is_liquidatable(&self) -> bool {
+        self.borrowed > self.max_borrow
+    }
+
+    /// Health factor (`max_borrow / borrowed`); below 1 means liquidatable.
+    ///
+    /// `None` for positions without debt.
+    #[must_use]
+    pub fn health_factor(&self) -> Option<Wad> {
+        Wad::from_raw(self.max_borrow).checked_div(Wad::from_raw(self.borrowed))
+    }
+}
+
+/// Expected outcome of liquidating a position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LiquidationQuote {
+    /// Collateral to seize, in raw collateral token units
+    pub seized_assets: U256,
+    /// Debt repaid in exchange, in raw loan token units
+    pub repaid_assets: U256,
+    /// Value of the seized collateral in raw loan token units
+    pub seized_value: U256,
+    /// `seized_value - repaid_assets`, before gas and swap costs
+    pub profit: U256,
+    /// Liquidation incentive factor applied
+    pub incentive: Wad,
+}
+
+impl LiquidationQuote {
+    /// Quotes the largest liquidation of `health`: repay all debt, or seize all
+    /// collateral if it isn't enough to cover the debt plus incentive.
+    #[must_use]
+    pub fn max(health: &BorrowerHealth, price: &OraclePrice, incentive: Wad) -> Self {
+        let full_repay_value = mul_div(health.borrowed, incentive.raw(), WAD).unwrap_or(U256::MAX);
+        let full_repay_seize = if price.raw.is_zero() {
+            U256::MAX
+        } else {
+            mul_div(full_repay_value, super::oracle_price_scale(), price.raw).unwrap_or(U256::MAX)
+        };
+        Self::for_seized(health.collateral.min(full_repay_seize), price, incentive)
+    }
+
+    /// Quotes seizing `seized_assets` of collateral.
+    #[must_use]
+    pub fn for_seized(seized_assets: U256, price: &OraclePrice, incentive: Wad) -> Self {
+        let seized_value = price.collateral_value(seized_assets).unwrap_or(U256::MAX);
+        let repaid_assets = mul_div(seized_value, WAD, incentive.raw()).unwrap_or(U256::ZERO);
+
+        Self {
+            seized_assets,
+            repaid_assets,
+            seized_value,
+            profit: seized_value.saturating_sub(repaid_assets),
+            incentive,
+        }
+    }
+}
+
+/// Result of [`Client::liquidatable_positions`].
+#[derive(Debug, Clone)]
+pub struct LiquidationScan {
+    /// Market parameters
+    pub params: MarketParams,
+    /// Market state used for the checks
+    pub market: Market,
+    /// Oracle price used for the checks
+    pub price: OraclePrice,
+    /// Liquidation incentive factor of the market
+    pub incentive: Wad,
+    /// Liquidatable positions
+    pub positions: Vec<BorrowerHealth>,
+}
+
+impl LiquidationScan {
+    /// Quotes the largest liquidation of `health` in this market.
+    #[must_use]
+    pub fn quote(&self, health: &BorrowerHealth) -> LiquidationQuote {
+        LiquidationQuote::max(health, &self.price, self.incentive)
+    }
+}
+
+impl<P> Client<P>
+where
+    P: Provider,
+{
+    /// Checks the health of `users` in a market, batching all reads into one multicall.
+    ///
+    /// Returns every position with debt, healthy or not.
+    pub async fn positions_health(
+        &self,
+        address: Address,
+        market_id: MarketId,
+        users: &[Address],
+    ) -> anyhow::Result<LiquidationScan> {
+        let morpho = IMorpho::new(address, self.provider.clone());
+        let (params, market) = self
+            .provider
+            .multicall()
+            .add(morpho.idToMarketParams(market_id))
+            .add(morpho.market(market_id))
+            .aggregate()
+            .await?;
+        let params: MarketParams = params.into();
+        let market: Market = market.into();
+        let price = self.oracle_price_with(params).await?;
+
+        let mut batch = multicall(self.provider.clone());
+        let slots: Vec<_> = users
+            .iter()
+            .map(|&user| {
+                batch.add(
+                    address,
+                    IMorpho::positionCall {
+                        id: market_id,
+                        user,
+                    },
+                )
+            })
+            .collect();
+        let results = batch.call().await?;
+
+        let mut positions = Vec::with_capacity(users.len());
+        for (&user, slot) in users.iter().zip(&slots) {
+            let position = results.get(slot)?;
+            if position.borrowShares == 0 {
+                continue;
+            }
+            positions.push(BorrowerHealth::new(
+                user,
+                U256::from(position.borrowShares),
+                U256::from(position.collateral),
+                &market,
+                params.lltv,
+                &price,
+            ));
+        }
+
+        Ok(LiquidationScan {
+            incentive: liquidation_incentive_factor(params.lltv),
+            params,
+            market,
+            price,
+            positions,
+        })
+    }
+
+    /// Returns the positions among `users` that can be liquidated right now.
+    pub async fn liquidatable_positions(
+        &self,
+        address: Address,
+        market_id: MarketId,
+        users: &[Address],
+    ) -> anyhow::Result<LiquidationScan> {
+        let mut scan = self.positions_health(address, market_id, users).await?;
+        scan.positions.retain(BorrowerHealth::is_liquidatable);
+        Ok(scan)
+    }
+
+    /// Liquidates `borrower` by seizing `seized_assets` of collateral.
+    ///
+    /// `liquidator` must be the provider's signing account. Morpho is first allowed to
+    /// pull up to `max_repaid` loan tokens through an [`AllowanceManager`]; size it from
+    /// [`LiquidationQuote::repaid_assets`] with a few units of headroom, since Morpho
+    /// rounds the repaid amount up. The liquidation is then simulated and only sent if
+    /// it would succeed.
+    pub async fn liquidate(
+        &self,
+        address: Address,
+        params: impl Into<MarketParams>,
+        borrower: Address,
+        seized_assets: U256,
+        max_repaid: U256,
+        liquidator: Address,
+    ) -> anyhow::Result<TransactionReceipt> {
+        let params: MarketParams = params.into();
+        AllowanceManager::new(self.provider.clone(), liquidator)
+            .ensure(params.loanToken, address, max_repaid)
+            .await?;
+
+        let morpho = IMorpho::new(address, self.provider.clone());
+        let tx = morpho
+            .liquidate(
+                params.into(),
+                borrower,
+                seized_assets,
+                U256::ZERO,
+                Bytes::new(),
+            )
+            .from(liquidator)
+            .into_transaction_request();
+
+        TxManager::new(self.provider.clone()).send(tx).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wad(value: u64, decimals: u32) -> U256 {
+        U256::from(value) * U256::from(10).pow(U256::from(18 - decimals))
+    }
+
+    #[test]
+    fn test_liquidation_incentive_factor() {
+        // LLTV 86%: 1 / (1 - 0.3 * 0.14) = 1.04384...
+        let lif = liquidation_incentive_factor(wad(86, 2));
+        assert_eq!(
+            lif.raw() / U256::from(10).pow(U256::from(13)),
+            U256::from(104384)
+        );
+
+        // Low LLTVs are capped at 1.15.
+        let lif = liquidation_incentive_factor(wad(38, 2));
+        assert_eq!(lif.raw(), MAX_LIQUIDATION_INCENTIVE_FACTOR);
+    }
+
+    #[test]
+    fn test_borrower_health_and_quote() {
+        // 1 BTC (8 decimals) at 100,000 USDC (6 decimals), LLTV 86%.
+        let raw_price = U256::from(100_000u64) * U256::from(10).pow(U256::from(34));
+        let price = OraclePrice::from_raw(Address::ZERO, raw_price, 6, 8);
+        let market = Market {
+            totalSupplyAssets: 0,
+            totalSupplyShares: 0,
+            totalBorrowAssets: 90_000_000_000,
+            totalBorrowShares: 90_000_000_000_000_000,
+            lastUpdate: 0,
+            fee: 0,
+        };
+        let lltv = wad(86, 2);
+
+        // Borrowed 90,000 USDC against 86,000 capacity.
+        let health = BorrowerHealth::new(
+            Address::ZERO,
+            U256::from(90_000_000_000_000_000u128),
+            U256::from(100_000_000u64),
+            &market,
+            lltv,
+            &price,
+        );
+        assert_eq!(health.max_borrow, U256::from(86_000_000_000u64));
+        assert!(health.borrowed >= U256::from(90_000_000_000u64));
+        assert!(health.is_liquidatable());
+        assert!(health.health_factor().unwrap() < Wad::ONE);
+
+        // Repaying 90,000 at LIF ~1.0438 needs ~93,946 of collateral value: all of it fits.
+        let quote = LiquidationQuote::max(&health, &price, liquidation_incentive_factor(lltv));
+        assert!(quote.seized_assets < health.collateral);
+        assert!(quote.repaid_assets <= health.borrowed);
+        assert!(quote.profit > U256::from(3_900_000_000u64));
+    }
+}
@@ -17,6 +17,11 @@
 //! - [`Client`]: For interacting with individual Morpho Blue markets
 //! - [`MetaClient`]: For interacting with MetaMorpho vaults
 //!
+//! See also:
+//! - [`liquidation`]: liquidation health checks and quotes
+//! - [`curator`]: MetaMorpho vault curation
+//! - [`allocation`]: per-market breakdown of a vault's assets
+//!
 //! # Examples
 //!
 //! ## Query Market APY
@@ -59,23 +64,29 @@
 use std::ops::{Add, Div, Mul, Sub};
 
 use alloy::{
+    eips::BlockId,
     primitives::{Address, FixedBytes, U256},
     providers::Provider,
     transports::TransportError,
 };
 use num_traits::{FromPrimitive, One, ToPrimitive};
+use rust_decimal::Decimal;
 
 use crate::hyperevm::{
-    DynProvider,
+    DynProvider, ERC20, Wad, from_wei_lossy,
     morpho::contracts::{
         IIrm,
         IMetaMorpho::{self, IMetaMorphoInstance},
         IMorpho::{self, IMorphoInstance},
-        Market, MarketParams,
+        Market, MarketParams, MorphoIOracle,
     },
+    wad::mul_div,
 };
 
+pub mod allocation;
 pub mod contracts;
+pub mod curator;
+pub mod liquidation;
 
 /// Morpho market identifier.
 ///
@@ -140,14 +151,16 @@ where
     /// the vault's management fee. Components whose `supply_apy` does not fit in
     /// `u128` are skipped instead of aborting the process.
     ///
-    /// The return value is scaled to 18 decimals. Which means, if the
-    /// APY of a vault is 4.20% the string representation of your decimal
-    /// implementation should be close to `0.042`.
+    /// The APY is computed with [`apy_wad`](Self::apy_wad) and only the final
+    /// 1e18-scaled result is converted, then divided by 1e18 in your type. Which
+    /// means, if the APY of a vault is 4.20% the string representation of your
+    /// decimal implementation should be close to `0.042`. A result too large for
+    /// 256 bits saturates at `U256::MAX`.
     ///
     /// # Type Parameters
     ///
     /// - `T`: The numeric type to use for calculations (e.g., f64, Decimal, etc.)
-    ///   Must support arithmetic operations and conversion from U256.
+    ///   Must support division and conversion from U256.
     /// - `F`: Conversion function from U256 to T
     ///
     /// # Arguments
@@ -176,49 +189,59 @@ where
     #[must_use]
     pub fn apy<T256, F>(&self, convert: F) -> T256
     where
-        T256: Add<T256, Output = T256>
-            + Sub<T256, Output = T256>
-            + Mul<T256, Output = T256>
-            + Div<T256, Output = T256>
-            + One
-            + Copy,
+        T256: Div<T256, Output = T256>,
         F: Fn(U256) -> T256,
     {
-        let zero = convert(U256::ZERO);
-        let wad = convert(U256::from(1_000_000_000_000_000_000u128));
+        let apy = self.apy_wad().unwrap_or(Wad::from_raw(U256::MAX));
+        convert(apy.raw()) / convert(Wad::ONE.raw())
+    }
 
+    /// Calculates the effective vault APY after fees using [`Wad`] integer math.
+    ///
+    /// Every intermediate product stays in `U256` (with 512-bit `mul_div`), so large
+    /// share counts can't overflow or lose precision. [`apy`](Self::apy) converts this
+    /// result into your numeric type. Returns `None` if a result doesn't fit in 256 bits.
+    ///
+    /// ```no_run
+    /// use hypersdk::hyperevm::morpho;
+    /// use hypersdk::Address;
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let client = morpho::MetaClient::mainnet().await?;
+    /// let vault_addr: Address = "0x...".parse()?;
+    /// let vault_apy = client.apy::<f64, _>(vault_addr, |value| value.exp()).await?;
+    ///
+    /// let apy = vault_apy.apy_wad().unwrap();
+    /// println!("Vault APY: {}", apy.to_decimal_lossy());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn apy_wad(&self) -> Option<Wad> {
         if self.total_deposits.is_zero() {
-            return zero;
+            return Some(Wad::ZERO);
         }
 
-        let total_deposits = convert(self.total_deposits);
-        let fee = convert(self.fee);
-
-        let fee_multiplier = (wad - fee) / wad;
-
-        let gross_apy = self
-            .components
-            .iter()
-            .filter_map(|component| {
-                if component.pool.market.totalSupplyShares == 0 {
-                    return None;
-                }
-
-                let supplied_shares = convert(component.supplied_shares);
-                let total_supply_assets =
-                    convert(U256::from(component.pool.market.totalSupplyAssets));
-                let total_supply_shares =
-                    convert(U256::from(component.pool.market.totalSupplyShares));
-
-                // Convert shares to assets: shares * total_assets / total_shares = assets
-                let supplied_assets = supplied_shares * total_supply_assets / total_supply_shares;
-                let supply_apy_raw = component.supply_apy.to_u128()?;
-                let supply_apy = convert(U256::from(supply_apy_raw));
-                Some(supplied_assets * supply_apy / total_deposits)
-            })
-            .fold(zero, |acc, x| acc + x);
+        let mut gross_apy = U256::ZERO;
+        for component in &self.components {
+            if component.pool.market.totalSupplyShares == 0 {
+                continue;
+            }
+            let Some(supply_apy) = component.supply_apy.to_u128() else {
+                continue;
+            };
+
+            let supplied_assets = mul_div(
+                component.supplied_shares,
+                U256::from(component.pool.market.totalSupplyAssets),
+                U256::from(component.pool.market.totalSupplyShares),
+            )?;
+            let weighted = mul_div(supplied_assets, U256::from(supply_apy), self.total_deposits)?;
+            gross_apy = gross_apy.checked_add(weighted)?;
+        }
 
-        gross_apy * fee_multiplier / wad
+        let fee_multiplier = Wad::ONE.saturating_sub(Wad::from_raw(self.fee));
+        Wad::from_raw(gross_apy).checked_mul(fee_multiplier)
     }
 
     /// Returns the number of markets in the vault.
@@ -228,6 +251,73 @@ where
     }
 }
 
+/// Number of decimals of a raw Morpho oracle price, before token decimal adjustments.
+pub const ORACLE_PRICE_SCALE_DECIMALS: u32 = 36;
+
+/// Price reported by a Morpho market oracle.
+///
+/// Morpho oracles quote 1 unit of collateral token in loan token, scaled by
+/// `1e36 * 10^loan_decimals / 10^collateral_decimals`. [`price`](Self::price) undoes
+/// that scaling into a human-readable price (e.g. `95000.12` USDC per BTC).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OraclePrice {
+    /// Oracle contract address
+    pub oracle: Address,
+    /// Raw `price()` value
+    pub raw: U256,
+    /// Decimals of the loan token
+    pub loan_decimals: u8,
+    /// Decimals of the collateral token
+    pub collateral_decimals: u8,
+    /// Collateral price in loan token units
+    pub price: Decimal,
+}
+
+impl OraclePrice {
+    /// Builds an [`OraclePrice`] from a raw oracle value and the market's token decimals.
+    ///
+    /// Digits beyond `Decimal`'s precision are truncated.
+    #[must_use]
+    pub fn from_raw(
+        oracle: Address,
+        raw: U256,
+        loan_decimals: u8,
+        collateral_decimals: u8,
+    ) -> Self {
+        let scale = ORACLE_PRICE_SCALE_DECIMALS + u32::from(loan_decimals);
+        let price = match scale.checked_sub(u32::from(collateral_decimals)) {
+            Some(scale) => from_wei_lossy(raw, scale),
+            None => {
+                let shift = u32::from(collateral_decimals) - scale;
+                let factor = U256::from(10).checked_pow(U256::from(shift));
+                factor
+                    .and_then(|f| raw.checked_mul(f))
+                    .map_or(Decimal::MAX, |v| from_wei_lossy(v, 0))
+            }
+        };
+
+        Self {
+            oracle,
+            raw,
+            loan_decimals,
+            collateral_decimals,
+            price,
+        }
+    }
+
+    /// Values `collateral` (raw collateral token units) in raw loan token units.
+    ///
+    /// Matches Morpho's `collateral * price / ORACLE_PRICE_SCALE`, rounding down.
+    #[must_use]
+    pub fn collateral_value(&self, collateral: U256) -> Option<U256> {
+        mul_div(collateral, self.raw, oracle_price_scale())
+    }
+}
+
+fn oracle_price_scale() -> U256 {
+    U256::from(10).pow(U256::from(ORACLE_PRICE_SCALE_DECIMALS))
+}
+
 /// Client for Morpho Blue lending markets.
 ///
 /// Provides methods for querying market information and calculating APYs.
@@ -360,6 +450,52 @@ where
         market_id: MarketId,
         exp: F,
     ) -> anyhow::Result<PoolApy<T128>>
+    where
+        T128: FromPrimitive
+            + Add<T128, Output = T128>
+            + Sub<T128, Output = T128>
+            + Mul<T128, Output = T128>
+            + Div<T128, Output = T128>
+            + One
+            + Copy,
+        F: FnOnce(T128) -> T128,
+    {
+        self.apy_at_block(address, market_id, BlockId::latest(), exp)
+            .await
+    }
+
+    /// Calculates the APY for a specific Morpho market as of a past block.
+    ///
+    /// Same as [`apy`](Self::apy), but every read is pinned to `block`. Use
+    /// [`block_by_timestamp`](crate::hyperevm::block_by_timestamp) to find the block
+    /// for a date. The RPC node must serve historical state for that block.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hypersdk::hyperevm::{self, morpho};
+    /// use hypersdk::Address;
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let client = morpho::Client::mainnet().await?;
+    /// let morpho_addr: Address = "0x...".parse()?;
+    /// let market_id = [0u8; 32].into();
+    ///
+    /// let block = hyperevm::block_by_timestamp(client.provider(), 1_735_689_600).await?;
+    /// let apy = client
+    ///     .apy_at_block::<f64, _>(morpho_addr, market_id, block.into(), |e| e.exp())
+    ///     .await?;
+    /// println!("Supply APY on Jan 1st: {:.2}%", apy.supply * 100.0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn apy_at_block<T128, F>(
+        &self,
+        address: Address,
+        market_id: MarketId,
+        block: BlockId,
+        exp: F,
+    ) -> anyhow::Result<PoolApy<T128>>
     where
         T128: FromPrimitive
             + Add<T128, Output = T128>
@@ -376,9 +512,10 @@ where
             .multicall()
             .add(morpho.idToMarketParams(market_id))
             .add(morpho.market(market_id))
+            .block(block)
             .aggregate()
             .await?;
-        self.apy_with(params, market, exp).await
+        self.apy_with_at_block(params, market, block, exp).await
     }
 
     /// Returns the APY of the market.
@@ -388,6 +525,29 @@ where
         market: impl Into<Market>,
         exp: F,
     ) -> anyhow::Result<PoolApy<T128>>
+    where
+        T128: FromPrimitive
+            + Sub<T128, Output = T128>
+            + Mul<T128, Output = T128>
+            + Div<T128, Output = T128>
+            + One
+            + Copy,
+        F: FnOnce(T128) -> T128,
+    {
+        self.apy_with_at_block(params, market, BlockId::latest(), exp)
+            .await
+    }
+
+    /// Returns the APY of the market, reading the borrow rate at `block`.
+    ///
+    /// `market` should be the market state at the same block.
+    pub async fn apy_with_at_block<T128, F>(
+        &self,
+        params: impl Into<MarketParams>,
+        market: impl Into<Market>,
+        block: BlockId,
+        exp: F,
+    ) -> anyhow::Result<PoolApy<T128>>
     where
         T128: FromPrimitive
             + Sub<T128, Output = T128>
@@ -407,6 +567,7 @@ where
         let irm = IIrm::new(params.irm, self.provider.clone());
         let rate = irm
             .borrowRateView(params.into(), market.into())
+            .block(block)
             .call()
             .await?;
 
@@ -430,6 +591,62 @@ where
             supply: supply_apy,
         })
     }
+
+    /// Reads the oracle price of a market.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hypersdk::hyperevm::morpho;
+    /// use hypersdk::Address;
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let client = morpho::Client::mainnet().await?;
+    /// let morpho_addr: Address = "0x...".parse()?;
+    /// let market_id = [0u8; 32].into();
+    ///
+    /// let price = client.oracle_price(morpho_addr, market_id).await?;
+    /// println!("collateral price: {} (raw {})", price.price, price.raw);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn oracle_price(
+        &self,
+        address: Address,
+        market_id: MarketId,
+    ) -> anyhow::Result<OraclePrice> {
+        let morpho = IMorpho::new(address, self.provider.clone());
+        let params = morpho.idToMarketParams(market_id).call().await?;
+        self.oracle_price_with(params).await
+    }
+
+    /// Reads the oracle price for already fetched market parameters.
+    pub async fn oracle_price_with(
+        &self,
+        params: impl Into<MarketParams>,
+    ) -> anyhow::Result<OraclePrice> {
+        let params = params.into();
+        anyhow::ensure!(!params.oracle.is_zero(), "market has no oracle");
+
+        let oracle = MorphoIOracle::new(params.oracle, self.provider.clone());
+        let loan = ERC20::new(params.loanToken, self.provider.clone());
+        let collateral = ERC20::new(params.collateralToken, self.provider.clone());
+        let (raw, loan_decimals, collateral_decimals) = self
+            .provider
+            .multicall()
+            .add(oracle.price())
+            .add(loan.decimals())
+            .add(collateral.decimals())
+            .aggregate()
+            .await?;
+
+        Ok(OraclePrice::from_raw(
+            params.oracle,
+            raw,
+            loan_decimals,
+            collateral_decimals,
+        ))
+    }
 }
 
 /// MetaMorpho client
@@ -477,6 +694,28 @@ where
     ///
     /// <https://github.com/morpho-org/metamorpho-v1.1/blob/main/src/MetaMorphoV1_1.sol#L796>
     pub async fn apy<T128, F>(&self, address: Address, exp: F) -> anyhow::Result<VaultApy<T128>>
+    where
+        T128: FromPrimitive
+            + Add<T128, Output = T128>
+            + Sub<T128, Output = T128>
+            + Mul<T128, Output = T128>
+            + Div<T128, Output = T128>
+            + One
+            + Copy,
+        F: FnOnce(T128) -> T128 + Copy,
+    {
+        self.apy_at_block(address, BlockId::latest(), exp).await
+    }
+
+    /// Returns the vault's APY as of a past block.
+    ///
+    /// Same as [`apy`](Self::apy), but every read is pinned to `block`.
+    pub async fn apy_at_block<T128, F>(
+        &self,
+        address: Address,
+        block: BlockId,
+        exp: F,
+    ) -> anyhow::Result<VaultApy<T128>>
     where
         T128: FromPrimitive
             + Add<T128, Output = T128>
@@ -498,6 +737,7 @@ where
             .add(meta_morpho.supplyQueueLength())
             .add(meta_morpho.totalAssets())
             .add(meta_morpho.MORPHO())
+            .block(block)
             .aggregate()
             .await?;
         let supply_queue_len = supply_queue_len.to::<usize>();
@@ -511,7 +751,11 @@ where
         };
         for i in 0..supply_queue_len {
             // TODO: is there a way to aggregate this?
-            let market_id = meta_morpho.supplyQueue(U256::from(i)).call().await?;
+            let market_id = meta_morpho
+                .supplyQueue(U256::from(i))
+                .block(block)
+                .call()
+                .await?;
 
             let (config, params, market) = self
                 .provider
@@ -519,6 +763,7 @@ where
                 .add(meta_morpho.config(market_id))
                 .add(morpho.idToMarketParams(market_id))
                 .add(morpho.market(market_id))
+                .block(block)
                 .aggregate()
                 .await?;
 
@@ -533,11 +778,12 @@ where
 
             let position = morpho
                 .position(market_id, *meta_morpho.address())
+                .block(block)
                 .call()
                 .await?;
 
             let pool = Client::new(self.provider.clone())
-                .apy_with::<T128, F>(params, market, exp)
+                .apy_with_at_block::<T128, F>(params, market, block, exp)
                 .await?;
 
             let supply_apy = pool.supply * wad;
@@ -552,3 +798,148 @@ where
         Ok(apy)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use rust_decimal::dec;
+
+    use super::*;
+
+    /// A vault fully allocated to markets quoting one share per asset.
+    fn vault(components: &[(u128, u128)], fee: u128) -> VaultApy<u128> {
+        let components = components
+            .iter()
+            .map(|&(supplied, supply_apy)| {
+                market_supply(U256::from(supplied), supplied, supplied, supply_apy)
+            })
+            .collect::<Vec<_>>();
+        let total_deposits = components
+            .iter()
+            .fold(U256::ZERO, |acc, c| acc + c.supplied_shares);
+        VaultApy {
+            components,
+            fee: U256::from(fee),
+            total_deposits,
+        }
+    }
+
+    fn market_supply(
+        supplied_shares: U256,
+        total_assets: u128,
+        total_shares: u128,
+        supply_apy: u128,
+    ) -> VaultSupply<u128> {
+        let params = MarketParams {
+            loanToken: Address::ZERO,
+            collateralToken: Address::ZERO,
+            oracle: Address::ZERO,
+            irm: Address::ZERO,
+            lltv: U256::ZERO,
+        };
+        let market = Market {
+            totalSupplyAssets: total_assets,
+            totalSupplyShares: total_shares,
+            totalBorrowAssets: 0,
+            totalBorrowShares: 0,
+            lastUpdate: 0,
+            fee: 0,
+        };
+        VaultSupply {
+            supplied_shares,
+            pool: PoolApy {
+                params,
+                market,
+                borrow: 0,
+                supply: supply_apy,
+            },
+            supply_apy,
+        }
+    }
+
+    fn to_f64(value: U256) -> f64 {
+        value.to_string().parse().unwrap()
+    }
+
+    const MAX_APY: u128 = 10 * 1_000_000_000_000_000_000;
+
+    proptest! {
+        #[test]
+        fn prop_vault_apy_is_a_weighted_average(
+            components in prop::collection::vec((1..=u128::MAX, 0..=MAX_APY), 1..8),
+        ) {
+            let vault = vault(&components, 0);
+            let apy = vault.apy_wad().unwrap().raw();
+            let max = components.iter().map(|c| c.1).max().unwrap();
+            let min = components.iter().map(|c| c.1).min().unwrap();
+            // Each weighted component rounds down by less than one unit.
+            let slack = components.len() as u128;
+            prop_assert!(apy <= U256::from(max));
+            prop_assert!(apy >= U256::from(min.saturating_sub(slack)));
+        }
+
+        #[test]
+        fn prop_vault_apy_falls_with_fee(
+            components in prop::collection::vec((1..=u128::MAX, 0..=MAX_APY), 1..8),
+            fee_a in 0..=1_000_000_000_000_000_000u128,
+            fee_b in 0..=1_000_000_000_000_000_000u128,
+        ) {
+            let (low, high) = (fee_a.min(fee_b), fee_a.max(fee_b));
+            let apy_low = vault(&components, low).apy_wad().unwrap();
+            let apy_high = vault(&components, high).apy_wad().unwrap();
+            prop_assert!(apy_high <= apy_low);
+        }
+
+        #[test]
+        fn prop_vault_apy_converts_the_wad_result(
+            components in prop::collection::vec((1..=u128::MAX, 0..=MAX_APY), 1..8),
+            fee in 0..=1_000_000_000_000_000_000u128,
+        ) {
+            let vault = vault(&components, fee);
+            let wad = vault.apy_wad().unwrap();
+            prop_assert_eq!(vault.apy(to_f64), to_f64(wad.raw()) / 1e18);
+        }
+
+        #[test]
+        fn prop_vault_apy_never_panics(
+            components in prop::collection::vec(
+                (any::<[u64; 4]>(), any::<u128>(), any::<u128>(), any::<u128>()),
+                0..8,
+            ),
+            total_deposits in any::<[u64; 4]>(),
+            fee in any::<[u64; 4]>(),
+        ) {
+            let vault = VaultApy {
+                components: components
+                    .into_iter()
+                    .map(|(shares, assets, total_shares, apy)| {
+                        market_supply(U256::from_limbs(shares), assets, total_shares, apy)
+                    })
+                    .collect(),
+                fee: U256::from_limbs(fee),
+                total_deposits: U256::from_limbs(total_deposits),
+            };
+            let _ = vault.apy_wad();
+            let _ = vault.apy(to_f64);
+        }
+    }
+
+    #[test]
+    fn test_oracle_price_scaling() {
+        // 95,000 USDC (6 decimals) per BTC (8 decimals): 95000 * 1e36 * 1e6 / 1e8
+        let raw = U256::from(95_000u64) * U256::from(10).pow(U256::from(34));
+        let price = OraclePrice::from_raw(Address::ZERO, raw, 6, 8);
+        assert_eq!(price.price, dec!(95000));
+
+        // 1 BTC (1e8 units) is worth 95,000 USDC (95_000e6 units).
+        assert_eq!(
+            price.collateral_value(U256::from(100_000_000u64)),
+            Some(U256::from(95_000_000_000u64))
+        );
+
+        // 18-decimal pair at a fractional price keeps its precision.
+        let raw = U256::from(1_234_567u64) * U256::from(10).pow(U256::from(30));
+        let price = OraclePrice::from_raw(Address::ZERO, raw, 18, 18);
+        assert_eq!(price.price, dec!(1.234567));
+    }
+}
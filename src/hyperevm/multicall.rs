@@ -0,0 +1,306 @@
+//! Batched contract reads.
+//!
+//! [`multicall()`] batches arbitrary view calls into a single `eth_call` to the
+//! [Multicall3](https://www.multicall3.com) contract. Each call added returns a typed
+//! [`Slot`] used to decode its result, so reads of different contracts and return
+//! types can be mixed freely:
+//!
+//! ```no_run
+//! use hypersdk::hyperevm::{self, ERC20};
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let provider = hyperevm::mainnet().await?;
+//! let user = "0x...".parse()?;
+//! let usdc = "0x...".parse()?;
+//! let vault = "0x...".parse()?;
+//!
+//! let mut batch = hyperevm::multicall(provider);
+//! let whype = batch.token_balance(hyperevm::WHYPE_ADDRESS, user);
+//! let usdc = batch.token_balance(usdc, user);
+//! let shares = batch.add(vault, ERC20::balanceOfCall { account: user });
+//!
+//! let results = batch.call().await?;
+//! println!("WHYPE: {}", results.token_amount(&whype)?);
+//! println!("USDC: {}", results.token_amount(&usdc)?);
+//! println!("vault shares (raw): {}", results.get(&shares)?);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Calls are added with `allowFailure = true`: a reverting call only fails its own
+//! [`MulticallResults::get`], not the whole batch.
+
+use std::marker::PhantomData;
+
+use alloy::{
+    eips::BlockId,
+    primitives::{Bytes, address},
+    sol,
+    sol_types::SolCall,
+};
+use anyhow::{Result, anyhow};
+use rust_decimal::Decimal;
+
+use crate::hyperevm::{Address, ERC20, Provider, U256, try_from_wei};
+
+/// Canonical Multicall3 deployment address (same on every EVM chain, including HyperEVM).
+pub const MULTICALL3_ADDRESS: Address = address!("0xcA11bde05977b3631167028862bE2a173976CA11");
+
+sol! {
+    #[derive(Debug)]
+    #[sol(rpc)]
+    interface IMulticall3 {
+        struct Call3 {
+            address target;
+            bool allowFailure;
+            bytes callData;
+        }
+
+        struct Result {
+            bool success;
+            bytes returnData;
+        }
+
+        function aggregate3(Call3[] calldata calls) external payable returns (Result[] memory returnData);
+    }
+}
+
+/// Creates an empty [`Multicall`] batch over `provider`.
+pub fn multicall<P: Provider>(provider: P) -> Multicall<P> {
+    Multicall::new(provider)
+}
+
+/// Handle to a call added to a [`Multicall`], used to decode its result.
+pub struct Slot<C> {
+    index: usize,
+    _call: PhantomData<fn() -> C>,
+}
+
+impl<C> Slot<C> {
+    /// Position of the call in the batch.
+    #[must_use]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+impl<C> Clone for Slot<C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C> Copy for Slot<C> {}
+
+impl<C> std::fmt::Debug for Slot<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Slot").field(&self.index).finish()
+    }
+}
+
+/// Handles for an ERC-20 balance and the token's decimals, added by
+/// [`Multicall::token_balance`].
+#[derive(Debug, Clone, Copy)]
+pub struct TokenBalanceSlot {
+    /// Raw `balanceOf` result
+    pub balance: Slot<ERC20::balanceOfCall>,
+    /// `decimals` result
+    pub decimals: Slot<ERC20::decimalsCall>,
+}
+
+/// A batch of view calls executed in one round trip.
+pub struct Multicall<P> {
+    provider: P,
+    calls: Vec<IMulticall3::Call3>,
+    block: Option<BlockId>,
+    address: Address,
+}
+
+impl<P: Provider> Multicall<P> {
+    /// Creates an empty batch using the canonical Multicall3 deployment.
+    pub fn new(provider: P) -> Self {
+        Self {
+            provider,
+            calls: vec![],
+            block: None,
+            address: MULTICALL3_ADDRESS,
+        }
+    }
+
+    /// Uses a Multicall3 deployment at a non-canonical address.
+    #[must_use]
+    pub fn with_address(mut self, address: Address) -> Self {
+        self.address = address;
+        self
+    }
+
+    /// Executes the batch against `block` instead of the latest block.
+    #[must_use]
+    pub fn block(mut self, block: BlockId) -> Self {
+        self.block = Some(block);
+        self
+    }
+
+    /// Number of calls in the batch.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.calls.len()
+    }
+
+    /// Returns true if no calls were added.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.calls.is_empty()
+    }
+
+    /// Adds `call` on `target` and returns the slot for its result.
+    pub fn add<C: SolCall>(&mut self, target: Address, call: C) -> Slot<C> {
+        self.add_raw(target, call.abi_encode().into());
+        Slot {
+            index: self.calls.len() - 1,
+            _call: PhantomData,
+        }
+    }
+
+    /// Adds pre-encoded calldata on `target` and returns its index.
+    ///
+    /// Decode the result with [`MulticallResults::raw`].
+    pub fn add_raw(&mut self, target: Address, call_data: Bytes) -> usize {
+        self.calls.push(IMulticall3::Call3 {
+            target,
+            allowFailure: true,
+            callData: call_data,
+        });
+        self.calls.len() - 1
+    }
+
+    /// Adds `token.balanceOf(owner)`.
+    pub fn balance_of(&mut self, token: Address, owner: Address) -> Slot<ERC20::balanceOfCall> {
+        self.add(token, ERC20::balanceOfCall { account: owner })
+    }
+
+    /// Adds `token.decimals()`.
+    pub fn decimals(&mut self, token: Address) -> Slot<ERC20::decimalsCall> {
+        self.add(token, ERC20::decimalsCall {})
+    }
+
+    /// Adds `token.balanceOf(owner)` and `token.decimals()`, decoded together by
+    /// [`MulticallResults::token_amount`].
+    pub fn token_balance(&mut self, token: Address, owner: Address) -> TokenBalanceSlot {
+        TokenBalanceSlot {
+            balance: self.balance_of(token, owner),
+            decimals: self.decimals(token),
+        }
+    }
+
+    /// Executes the batch.
+    pub async fn call(&self) -> Result<MulticallResults> {
+        let multicall = IMulticall3::new(self.address, self.provider.clone());
+        let results = multicall
+            .aggregate3(self.calls.clone())
+            .block(self.block.unwrap_or_else(BlockId::latest))
+            .call()
+            .await?;
+        Ok(MulticallResults { results })
+    }
+}
+
+/// Results of an executed [`Multicall`].
+#[derive(Debug, Clone)]
+pub struct MulticallResults {
+    results: Vec<IMulticall3::Result>,
+}
+
+impl MulticallResults {
+    /// Decodes the result of a typed call.
+    ///
+    /// Fails if that call reverted or returned data that doesn't match its ABI.
+    pub fn get<C: SolCall>(&self, slot: &Slot<C>) -> Result<C::Return> {
+        let data = self.raw(slot.index)?;
+        Ok(C::abi_decode_returns(data)?)
+    }
+
+    /// Returns the raw return data of the call at `index`, or an error if it reverted.
+    pub fn raw(&self, index: usize) -> Result<&Bytes> {
+        let result = self
+            .results
+            .get(index)
+            .ok_or_else(|| anyhow!("no multicall result at index {index}"))?;
+        if !result.success {
+            return Err(anyhow!(
+                "multicall call {index} reverted: {}",
+                crate::hyperevm::revert::decode_revert(&result.returnData)
+            ));
+        }
+        Ok(&result.returnData)
+    }
+
+    /// Returns true if the call at `index` succeeded.
+    #[must_use]
+    pub fn succeeded(&self, index: usize) -> bool {
+        self.results.get(index).is_some_and(|r| r.success)
+    }
+
+    /// Decodes a `uint256` result as a [`Decimal`] with `decimals` decimal places.
+    ///
+    /// Fails if the value doesn't fit in a `Decimal`.
+    pub fn decimal<C>(&self, slot: &Slot<C>, decimals: u32) -> Result<Decimal>
+    where
+        C: SolCall<Return = U256>,
+    {
+        let raw = self.get(slot)?;
+        try_from_wei(raw, decimals)
+            .ok_or_else(|| anyhow!("{raw} with {decimals} decimals does not fit in a Decimal"))
+    }
+
+    /// Decodes a token balance scaled by the token's own decimals.
+    pub fn token_amount(&self, slot: &TokenBalanceSlot) -> Result<Decimal> {
+        let decimals = self.get(&slot.decimals)?;
+        self.decimal(&slot.balance, u32::from(decimals))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ok(data: impl Into<Bytes>) -> IMulticall3::Result {
+        IMulticall3::Result {
+            success: true,
+            returnData: data.into(),
+        }
+    }
+
+    #[test]
+    fn test_decode_results() {
+        let results = MulticallResults {
+            results: vec![
+                ok(U256::from(1_500_000).to_be_bytes_vec()),
+                ok(U256::from(6).to_be_bytes_vec()),
+                IMulticall3::Result {
+                    success: false,
+                    returnData: Bytes::new(),
+                },
+            ],
+        };
+
+        let slot = TokenBalanceSlot {
+            balance: Slot {
+                index: 0,
+                _call: PhantomData,
+            },
+            decimals: Slot {
+                index: 1,
+                _call: PhantomData,
+            },
+        };
+        assert_eq!(
+            results.token_amount(&slot).unwrap(),
+            rust_decimal::dec!(1.5)
+        );
+        assert!(results.succeeded(0));
+        assert!(!results.succeeded(2));
+        assert!(results.raw(2).is_err());
+        assert!(results.raw(3).is_err());
+    }
+}
@@ -0,0 +1,363 @@
+//! Revert data decoding.
+//!
+//! Contract calls that revert surface as an RPC error carrying raw revert bytes.
+//! [`decode_revert`] maps those bytes against the protocols this crate binds and
+//! returns a typed [`RevertError`]:
+//!
+//! - ERC-20 custom errors (`ERC20InsufficientBalance`, ...)
+//! - MetaMorpho custom errors (`SupplyCapExceeded`, `NotAllocatorRole`, ...)
+//! - Morpho Blue string reasons (`"insufficient collateral"`, `"healthy position"`, ...)
+//! - Uniswap V3 short codes (`"STF"`, `"Too little received"`, ...)
+//! - Solidity `Panic(uint256)`
+//!
+//! # Example
+//!
+//! ```no_run
+//! use hypersdk::hyperevm::{self, ERC20, U256, revert::RevertError};
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let provider = hyperevm::mainnet().await?;
+//! let token = ERC20::new("0x...".parse()?, provider);
+//!
+//! match token.transfer("0x...".parse()?, U256::MAX).call().await {
+//!     Ok(_) => println!("would succeed"),
+//!     Err(err) => match RevertError::from_contract_error(&err) {
+//!         Some(revert) => println!("would revert: {revert}"),
+//!         None => return Err(err.into()),
+//!     },
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use alloy::{
+    contract,
+    primitives::Bytes,
+    sol,
+    sol_types::{Panic, Revert, SolError, SolInterface},
+    transports::TransportError,
+};
+
+use crate::hyperevm::ERC20;
+
+sol! {
+    /// Custom errors raised by MetaMorpho vaults (`ErrorsLib`).
+    #[derive(Debug, PartialEq, Eq)]
+    interface MetaMorphoErrors {
+        error AboveMaxTimelock();
+        error AllCapsReached();
+        error AlreadyPending();
+        error AlreadySet();
+        error BelowMinTimelock();
+        error DuplicateMarket(bytes32 id);
+        error InconsistentAsset(bytes32 id);
+        error InconsistentReallocation();
+        error InvalidMarketRemovalNonZeroCap(bytes32 id);
+        error InvalidMarketRemovalNonZeroSupply(bytes32 id);
+        error InvalidMarketRemovalTimelockNotElapsed(bytes32 id);
+        error MarketNotCreated();
+        error MarketNotEnabled(bytes32 id);
+        error MaxFeeExceeded();
+        error MaxQueueLengthExceeded();
+        error NoPendingValue();
+        error NonZeroCap();
+        error NotAllocatorRole();
+        error NotCuratorNorGuardianRole();
+        error NotCuratorRole();
+        error NotEnoughLiquidity();
+        error NotGuardianRole();
+        error PendingCap(bytes32 id);
+        error PendingRemoval();
+        error SupplyCapExceeded(bytes32 id);
+        error TimelockNotElapsed();
+        error UnauthorizedMarket(bytes32 id);
+        error ZeroAddress();
+        error ZeroFeeRecipient();
+    }
+}
+
+pub use MetaMorphoErrors::MetaMorphoErrorsErrors as MetaMorphoError;
+
+/// Revert reasons raised by Morpho Blue (`ErrorsLib`), which reverts with strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, derive_more::Display)]
+pub enum MorphoError {
+    #[display("not owner")]
+    NotOwner,
+    #[display("max LLTV exceeded")]
+    MaxLltvExceeded,
+    #[display("max fee exceeded")]
+    MaxFeeExceeded,
+    #[display("already set")]
+    AlreadySet,
+    #[display("IRM not enabled")]
+    IrmNotEnabled,
+    #[display("LLTV not enabled")]
+    LltvNotEnabled,
+    #[display("market already created")]
+    MarketAlreadyCreated,
+    #[display("no code")]
+    NoCode,
+    #[display("market not created")]
+    MarketNotCreated,
+    #[display("inconsistent input")]
+    InconsistentInput,
+    #[display("zero assets")]
+    ZeroAssets,
+    #[display("zero address")]
+    ZeroAddress,
+    #[display("unauthorized")]
+    Unauthorized,
+    #[display("insufficient collateral")]
+    InsufficientCollateral,
+    #[display("insufficient liquidity")]
+    InsufficientLiquidity,
+    #[display("healthy position")]
+    HealthyPosition,
+    #[display("invalid signature")]
+    InvalidSignature,
+    #[display("signature expired")]
+    SignatureExpired,
+    #[display("invalid nonce")]
+    InvalidNonce,
+    #[display("transfer reverted")]
+    TransferReverted,
+    #[display("transfer returned false")]
+    TransferReturnedFalse,
+    #[display("transferFrom reverted")]
+    TransferFromReverted,
+    #[display("transferFrom returned false")]
+    TransferFromReturnedFalse,
+    #[display("max uint128 exceeded")]
+    MaxUint128Exceeded,
+}
+
+impl MorphoError {
+    const ALL: [Self; 24] = [
+        Self::NotOwner,
+        Self::MaxLltvExceeded,
+        Self::MaxFeeExceeded,
+        Self::AlreadySet,
+        Self::IrmNotEnabled,
+        Self::LltvNotEnabled,
+        Self::MarketAlreadyCreated,
+        Self::NoCode,
+        Self::MarketNotCreated,
+        Self::InconsistentInput,
+        Self::ZeroAssets,
+        Self::ZeroAddress,
+        Self::Unauthorized,
+        Self::InsufficientCollateral,
+        Self::InsufficientLiquidity,
+        Self::HealthyPosition,
+        Self::InvalidSignature,
+        Self::SignatureExpired,
+        Self::InvalidNonce,
+        Self::TransferReverted,
+        Self::TransferReturnedFalse,
+        Self::TransferFromReverted,
+        Self::TransferFromReturnedFalse,
+        Self::MaxUint128Exceeded,
+    ];
+
+    /// Matches a Morpho Blue revert string.
+    #[must_use]
+    pub fn from_reason(reason: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|e| e.to_string() == reason)
+    }
+}
+
+/// Revert codes raised by Uniswap V3 core and periphery contracts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UniswapError {
+    /// `LOK`: pool is locked (reentrancy)
+    Locked,
+    /// `TLU`: lower tick must be below upper tick
+    TickLowerNotBelowUpper,
+    /// `TLM`: lower tick below minimum
+    TickLowerTooLow,
+    /// `TUM`: upper tick above maximum
+    TickUpperTooHigh,
+    /// `AS`: amount specified is zero
+    ZeroAmountSpecified,
+    /// `SPL`: square root price limit out of range
+    PriceLimitOutOfRange,
+    /// `IIA`: insufficient input amount
+    InsufficientInputAmount,
+    /// `L`: pool has no liquidity
+    NoLiquidity,
+    /// `STF`: `safeTransferFrom` failed (usually balance or allowance)
+    SafeTransferFromFailed,
+    /// `ST`: `safeTransfer` failed
+    SafeTransferFailed,
+    /// `SA`: `safeApprove` failed
+    SafeApproveFailed,
+    /// `STE`: `safeTransferETH` failed
+    SafeTransferEthFailed,
+    /// `Too little received`: output below `amountOutMinimum`
+    TooLittleReceived,
+    /// `Too much requested`: input above `amountInMaximum`
+    TooMuchRequested,
+    /// `Transaction too old`: deadline has passed
+    TransactionTooOld,
+    /// `Price slippage check`: liquidity change moved amounts below minimums
+    PriceSlippageCheck,
+    /// `Not approved`: caller may not manage the position NFT
+    NotApproved,
+    /// `Not cleared`: position must be emptied before burning
+    NotCleared,
+}
+
+impl UniswapError {
+    /// Matches a Uniswap V3 revert string.
+    #[must_use]
+    pub fn from_reason(reason: &str) -> Option<Self> {
+        Some(match reason {
+            "LOK" => Self::Locked,
+            "TLU" => Self::TickLowerNotBelowUpper,
+            "TLM" => Self::TickLowerTooLow,
+            "TUM" => Self::TickUpperTooHigh,
+            "AS" => Self::ZeroAmountSpecified,
+            "SPL" => Self::PriceLimitOutOfRange,
+            "IIA" => Self::InsufficientInputAmount,
+            "L" => Self::NoLiquidity,
+            "STF" => Self::SafeTransferFromFailed,
+            "ST" => Self::SafeTransferFailed,
+            "SA" => Self::SafeApproveFailed,
+            "STE" => Self::SafeTransferEthFailed,
+            "Too little received" => Self::TooLittleReceived,
+            "Too much requested" => Self::TooMuchRequested,
+            "Transaction too old" => Self::TransactionTooOld,
+            "Price slippage check" => Self::PriceSlippageCheck,
+            "Not approved" => Self::NotApproved,
+            "Not cleared" => Self::NotCleared,
+            _ => return None,
+        })
+    }
+}
+
+/// A decoded contract revert.
+#[derive(Debug, thiserror::Error)]
+pub enum RevertError {
+    /// ERC-20 custom error (OpenZeppelin `IERC20Errors`).
+    #[error("ERC20: {0:?}")]
+    Erc20(ERC20::ERC20Errors),
+    /// MetaMorpho custom error.
+    #[error("MetaMorpho: {0:?}")]
+    MetaMorpho(MetaMorphoError),
+    /// Morpho Blue revert string.
+    #[error("Morpho: {0}")]
+    Morpho(MorphoError),
+    /// Uniswap V3 revert code.
+    #[error("Uniswap: {0:?}")]
+    Uniswap(UniswapError),
+    /// `Error(string)` with a message not recognized above.
+    #[error("revert: {0}")]
+    Message(String),
+    /// Solidity `Panic(uint256)` (overflow, division by zero, ...).
+    #[error("{0}")]
+    Panic(Panic),
+    /// Revert data that matched nothing known, possibly empty.
+    #[error("reverted with {0}")]
+    Unknown(Bytes),
+}
+
+impl RevertError {
+    /// Extracts and decodes revert data from an RPC error.
+    ///
+    /// Returns `None` if the error is not a revert (network failure, bad request, ...).
+    #[must_use]
+    pub fn from_transport_error(err: &TransportError) -> Option<Self> {
+        let payload = err.as_error_resp()?;
+        match payload.as_revert_data() {
+            Some(data) => Some(decode_revert(&data)),
+            None if payload.message.contains("revert") => Some(Self::Unknown(Bytes::new())),
+            None => None,
+        }
+    }
+
+    /// Extracts and decodes revert data from a `sol!` contract call error.
+    #[must_use]
+    pub fn from_contract_error(err: &contract::Error) -> Option<Self> {
+        match err {
+            contract::Error::TransportError(err) => Self::from_transport_error(err),
+            _ => None,
+        }
+    }
+}
+
+/// Decodes raw revert data into a [`RevertError`].
+///
+/// Never fails: unrecognized data is returned as [`RevertError::Unknown`].
+#[must_use]
+pub fn decode_revert(data: &[u8]) -> RevertError {
+    if let Ok(err) = ERC20::ERC20Errors::abi_decode(data) {
+        return RevertError::Erc20(err);
+    }
+    if let Ok(err) = MetaMorphoError::abi_decode(data) {
+        return RevertError::MetaMorpho(err);
+    }
+    if let Ok(panic) = Panic::abi_decode(data) {
+        return RevertError::Panic(panic);
+    }
+    if let Ok(revert) = Revert::abi_decode(data) {
+        let reason = revert.reason;
+        if let Some(err) = MorphoError::from_reason(&reason) {
+            return RevertError::Morpho(err);
+        }
+        if let Some(err) = UniswapError::from_reason(&reason) {
+            return RevertError::Uniswap(err);
+        }
+        return RevertError::Message(reason);
+    }
+
+    RevertError::Unknown(Bytes::copy_from_slice(data))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::{Address, B256, U256};
+
+    use super::*;
+
+    #[test]
+    fn test_decode_custom_errors() {
+        let data = ERC20::ERC20InsufficientBalance {
+            sender: Address::ZERO,
+            balance: U256::from(1),
+            needed: U256::from(2),
+        }
+        .abi_encode();
+        assert!(matches!(
+            decode_revert(&data),
+            RevertError::Erc20(ERC20::ERC20Errors::ERC20InsufficientBalance(_))
+        ));
+
+        let data = MetaMorphoErrors::SupplyCapExceeded { id: B256::ZERO }.abi_encode();
+        assert!(matches!(
+            decode_revert(&data),
+            RevertError::MetaMorpho(MetaMorphoError::SupplyCapExceeded(_))
+        ));
+    }
+
+    #[test]
+    fn test_decode_string_reasons() {
+        let revert = |reason: &str| Revert::from(reason).abi_encode();
+
+        assert!(matches!(
+            decode_revert(&revert("insufficient collateral")),
+            RevertError::Morpho(MorphoError::InsufficientCollateral)
+        ));
+        assert!(matches!(
+            decode_revert(&revert("STF")),
+            RevertError::Uniswap(UniswapError::SafeTransferFromFailed)
+        ));
+        assert!(matches!(
+            decode_revert(&revert("something else")),
+            RevertError::Message(msg) if msg == "something else"
+        ));
+        assert!(matches!(
+            decode_revert(&[0xde, 0xad]),
+            RevertError::Unknown(_)
+        ));
+    }
+}
@@ -0,0 +1,203 @@
+//! Kinetiq kHYPE.
+//!
+//! HYPE is staked through the `StakingManager`, which mints kHYPE at the rate reported
+//! by the `StakingAccountant`. Withdrawals are queued and can be confirmed once the
+//! manager's withdrawal delay has passed.
+
+use alloy::{
+    primitives::{Address, U256},
+    providers::DynProvider,
+    rpc::types::TransactionReceipt,
+    sol,
+    transports::TransportError,
+};
+use rust_decimal::Decimal;
+
+use super::{WithdrawalRequest, rate_from_wei};
+use crate::hyperevm::{ERC20, Provider, from_wei_lossy, tx::TxManager, wad::WAD};
+
+sol! {
+    #[derive(Debug)]
+    #[sol(rpc)]
+    interface IStakingManager {
+        function stake() external payable;
+        function queueWithdrawal(uint256 amount) external;
+        function confirmWithdrawal(uint256 withdrawalId) external;
+        function nextWithdrawalId(address user) external view returns (uint256);
+        function withdrawalRequests(address user, uint256 id) external view returns (uint256 hypeAmount, uint256 kHYPEAmount, uint256 timestamp);
+        function withdrawalDelay() external view returns (uint256);
+    }
+
+    #[derive(Debug)]
+    #[sol(rpc)]
+    interface IStakingAccountant {
+        function kHYPEToHYPE(uint256 kHYPEAmount) external view returns (uint256);
+        function HYPEToKHYPE(uint256 HYPEAmount) external view returns (uint256);
+    }
+}
+
+/// Kinetiq contract addresses.
+#[derive(Debug, Clone, Copy)]
+pub struct Contracts {
+    /// StakingManager (stake, withdrawal queue)
+    pub staking_manager: Address,
+    /// StakingAccountant (exchange rate)
+    pub staking_accountant: Address,
+    /// kHYPE token
+    pub khype: Address,
+}
+
+/// Client for Kinetiq kHYPE.
+pub struct Client<P>
+where
+    P: Provider,
+{
+    provider: P,
+    contracts: Contracts,
+}
+
+impl Client<DynProvider> {
+    /// Creates a client for mainnet.
+    pub async fn mainnet(contracts: Contracts) -> Result<Self, TransportError> {
+        let provider = DynProvider::new(crate::hyperevm::mainnet().await?);
+        Ok(Self::new(provider, contracts))
+    }
+
+    /// Creates a client for mainnet.
+    pub async fn mainnet_with_url(url: &str, contracts: Contracts) -> Result<Self, TransportError> {
+        let provider = DynProvider::new(crate::hyperevm::mainnet_with_url(url).await?);
+        Ok(Self::new(provider, contracts))
+    }
+}
+
+impl<P> Client<P>
+where
+    P: Provider,
+{
+    /// Creates a Kinetiq client.
+    pub fn new(provider: P, contracts: Contracts) -> Self {
+        Self {
+            provider,
+            contracts,
+        }
+    }
+
+    /// Returns the root provider.
+    pub fn provider(&self) -> &P {
+        &self.provider
+    }
+
+    /// Returns the staking manager.
+    pub fn staking_manager(&self) -> IStakingManager::IStakingManagerInstance<P> {
+        IStakingManager::new(self.contracts.staking_manager, self.provider.clone())
+    }
+
+    /// Returns the staking accountant.
+    pub fn staking_accountant(&self) -> IStakingAccountant::IStakingAccountantInstance<P> {
+        IStakingAccountant::new(self.contracts.staking_accountant, self.provider.clone())
+    }
+
+    /// HYPE value of 1 kHYPE.
+    pub async fn exchange_rate(&self) -> anyhow::Result<Decimal> {
+        let hype = self.staking_accountant().kHYPEToHYPE(WAD).call().await?;
+        Ok(rate_from_wei(hype))
+    }
+
+    /// kHYPE minted for `hype` wei of HYPE.
+    pub async fn preview_deposit(&self, hype: U256) -> anyhow::Result<U256> {
+        Ok(self.staking_accountant().HYPEToKHYPE(hype).call().await?)
+    }
+
+    /// kHYPE balance of `user`.
+    pub async fn balance(&self, user: Address) -> anyhow::Result<Decimal> {
+        let balance = ERC20::new(self.contracts.khype, self.provider.clone())
+            .balanceOf(user)
+            .call()
+            .await?;
+        Ok(from_wei_lossy(balance, super::HYPE_DECIMALS))
+    }
+
+    /// Stakes `hype` wei of HYPE for kHYPE.
+    pub async fn deposit(&self, hype: U256, sender: Address) -> anyhow::Result<TransactionReceipt> {
+        let tx = self
+            .staking_manager()
+            .stake()
+            .value(hype)
+            .from(sender)
+            .into_transaction_request();
+        TxManager::new(self.provider.clone()).send(tx).await
+    }
+
+    /// Queues a withdrawal of `khype` wei of kHYPE.
+    pub async fn request_withdrawal(
+        &self,
+        khype: U256,
+        sender: Address,
+    ) -> anyhow::Result<TransactionReceipt> {
+        let tx = self
+            .staking_manager()
+            .queueWithdrawal(khype)
+            .from(sender)
+            .into_transaction_request();
+        TxManager::new(self.provider.clone()).send(tx).await
+    }
+
+    /// Confirms a queued withdrawal, sending its HYPE to `sender`.
+    pub async fn claim(&self, id: U256, sender: Address) -> anyhow::Result<TransactionReceipt> {
+        let tx = self
+            .staking_manager()
+            .confirmWithdrawal(id)
+            .from(sender)
+            .into_transaction_request();
+        TxManager::new(self.provider.clone()).send(tx).await
+    }
+
+    /// Withdrawals of `user` that haven't been confirmed yet.
+    ///
+    /// `now` is the current unix timestamp (seconds), used to flag claimable requests.
+    pub async fn withdrawal_requests(
+        &self,
+        user: Address,
+        now: u64,
+    ) -> anyhow::Result<Vec<WithdrawalRequest>> {
+        let manager = self.staking_manager();
+        let (next_id, delay) = self
+            .provider
+            .multicall()
+            .add(manager.nextWithdrawalId(user))
+            .add(manager.withdrawalDelay())
+            .aggregate()
+            .await?;
+
+        let mut batch = crate::hyperevm::multicall(self.provider.clone());
+        let slots: Vec<_> = (0..next_id.to::<u64>())
+            .map(|id| {
+                let id = U256::from(id);
+                (
+                    id,
+                    batch.add(
+                        self.contracts.staking_manager,
+                        IStakingManager::withdrawalRequestsCall { user, id },
+                    ),
+                )
+            })
+            .collect();
+        let results = batch.call().await?;
+
+        let mut requests = vec![];
+        for (id, slot) in slots {
+            let request = results.get(&slot)?;
+            // Confirmed requests are deleted.
+            if request.hypeAmount.is_zero() {
+                continue;
+            }
+            let ready_at = request.timestamp.saturating_add(delay);
+            requests.push(WithdrawalRequest {
+                id,
+                hype_amount: request.hypeAmount,
+                claimable: U256::from(now) >= ready_at,
+            });
+        }
+        Ok(requests)
+    }
+}
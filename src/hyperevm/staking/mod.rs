@@ -0,0 +1,85 @@
+//! HYPE liquid staking tokens (LSTs).
+//!
+//! Typed clients for the main HYPE liquid-staking protocols on HyperEVM:
+//!
+//! - [`kinetiq`]: Kinetiq kHYPE (value-accruing, withdrawals go through a queue)
+//! - [`sthype`]: Thunderhead stHYPE (share-based, burns redeemed once liquidity is available)
+//!
+//! Both clients expose the same operations: the exchange rate between the LST and HYPE,
+//! depositing HYPE, requesting a withdrawal, and claiming it once it is ready. Writes
+//! are simulated before being sent (see [`TxManager`](crate::hyperevm::tx::TxManager)).
+//!
+//! Only the functions needed for these operations are bound. Contract addresses are
+//! passed in through each protocol's `Contracts`; take them from the protocol's docs.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use hypersdk::hyperevm::staking::kinetiq::{self, Contracts};
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let contracts = Contracts {
+//!     staking_manager: "0x...".parse()?,
+//!     staking_accountant: "0x...".parse()?,
+//!     khype: "0x...".parse()?,
+//! };
+//! let client = kinetiq::Client::mainnet(contracts).await?;
+//! println!("1 kHYPE = {} HYPE", client.exchange_rate().await?);
+//! # Ok(())
+//! # }
+//! ```
+
+use alloy::primitives::U256;
+use rust_decimal::Decimal;
+
+use crate::hyperevm::from_wei_lossy;
+
+pub mod kinetiq;
+pub mod sthype;
+
+/// Decimals of HYPE and of the LSTs.
+pub const HYPE_DECIMALS: u32 = 18;
+
+/// A withdrawal waiting to be claimed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WithdrawalRequest {
+    /// Protocol-specific request id, passed back to `claim`
+    pub id: U256,
+    /// HYPE to receive, in wei
+    pub hype_amount: U256,
+    /// Whether the request can be claimed now
+    pub claimable: bool,
+}
+
+impl WithdrawalRequest {
+    /// HYPE to receive.
+    #[must_use]
+    pub fn hype(&self) -> Decimal {
+        from_wei_lossy(self.hype_amount, HYPE_DECIMALS)
+    }
+}
+
+/// Converts the HYPE value of 1 LST (in wei) into an exchange rate.
+fn rate_from_wei(hype_per_lst: U256) -> Decimal {
+    from_wei_lossy(hype_per_lst, HYPE_DECIMALS)
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::dec;
+
+    use super::*;
+
+    #[test]
+    fn test_rate_from_wei() {
+        let rate = rate_from_wei(U256::from(1_012_500_000_000_000_000u64));
+        assert_eq!(rate, dec!(1.0125));
+
+        let request = WithdrawalRequest {
+            id: U256::ZERO,
+            hype_amount: U256::from(2_500_000_000_000_000_000u64),
+            claimable: false,
+        };
+        assert_eq!(request.hype(), dec!(2.5));
+    }
+}
@@ -0,0 +1,187 @@
+//! Thunderhead stHYPE.
+//!
+//! HYPE is deposited through the `Overseer`, which mints stHYPE. stHYPE balances are
+//! backed by shares whose value grows with staking rewards. Withdrawals burn stHYPE
+//! and are redeemed immediately when the Overseer has enough liquid HYPE, or later
+//! through [`claim`](Client::claim).
+
+use alloy::{
+    primitives::{Address, U256},
+    providers::DynProvider,
+    rpc::types::TransactionReceipt,
+    sol,
+    transports::TransportError,
+};
+use rust_decimal::Decimal;
+
+use super::{WithdrawalRequest, rate_from_wei};
+use crate::hyperevm::{ERC20, Provider, from_wei_lossy, tx::TxManager, wad::WAD};
+
+sol! {
+    #[derive(Debug)]
+    #[sol(rpc)]
+    interface IOverseer {
+        function mint(address to) external payable returns (uint256);
+        function burnAndRedeemIfPossible(address to, uint256 amount, string communityCode) external returns (bool, uint256);
+        function redeem(uint256 burnId) external;
+        function redeemable(uint256 burnId) external view returns (bool);
+        function getBurnIds(address account) external view returns (uint256[]);
+        function burns(uint256 burnId) external view returns (uint88 amount, address user, bool completed, uint256 sum);
+    }
+
+    #[derive(Debug)]
+    #[sol(rpc)]
+    interface IStHype {
+        function sharesToBalance(uint256 shares) external view returns (uint256);
+        function balanceToShares(uint256 balance) external view returns (uint256);
+    }
+}
+
+/// Thunderhead contract addresses.
+#[derive(Debug, Clone, Copy)]
+pub struct Contracts {
+    /// Overseer (mint, burn, redeem)
+    pub overseer: Address,
+    /// stHYPE token
+    pub sthype: Address,
+}
+
+/// Client for Thunderhead stHYPE.
+pub struct Client<P>
+where
+    P: Provider,
+{
+    provider: P,
+    contracts: Contracts,
+}
+
+impl Client<DynProvider> {
+    /// Creates a client for mainnet.
+    pub async fn mainnet(contracts: Contracts) -> Result<Self, TransportError> {
+        let provider = DynProvider::new(crate::hyperevm::mainnet().await?);
+        Ok(Self::new(provider, contracts))
+    }
+
+    /// Creates a client for mainnet.
+    pub async fn mainnet_with_url(url: &str, contracts: Contracts) -> Result<Self, TransportError> {
+        let provider = DynProvider::new(crate::hyperevm::mainnet_with_url(url).await?);
+        Ok(Self::new(provider, contracts))
+    }
+}
+
+impl<P> Client<P>
+where
+    P: Provider,
+{
+    /// Creates a Thunderhead client.
+    pub fn new(provider: P, contracts: Contracts) -> Self {
+        Self {
+            provider,
+            contracts,
+        }
+    }
+
+    /// Returns the root provider.
+    pub fn provider(&self) -> &P {
+        &self.provider
+    }
+
+    /// Returns the overseer.
+    pub fn overseer(&self) -> IOverseer::IOverseerInstance<P> {
+        IOverseer::new(self.contracts.overseer, self.provider.clone())
+    }
+
+    /// Returns the stHYPE token.
+    pub fn sthype(&self) -> IStHype::IStHypeInstance<P> {
+        IStHype::new(self.contracts.sthype, self.provider.clone())
+    }
+
+    /// HYPE value of 1 stHYPE share.
+    pub async fn exchange_rate(&self) -> anyhow::Result<Decimal> {
+        let hype = self.sthype().sharesToBalance(WAD).call().await?;
+        Ok(rate_from_wei(hype))
+    }
+
+    /// stHYPE balance of `user`.
+    pub async fn balance(&self, user: Address) -> anyhow::Result<Decimal> {
+        let balance = ERC20::new(self.contracts.sthype, self.provider.clone())
+            .balanceOf(user)
+            .call()
+            .await?;
+        Ok(from_wei_lossy(balance, super::HYPE_DECIMALS))
+    }
+
+    /// Deposits `hype` wei of HYPE, minting stHYPE to `sender`.
+    pub async fn deposit(&self, hype: U256, sender: Address) -> anyhow::Result<TransactionReceipt> {
+        let tx = self
+            .overseer()
+            .mint(sender)
+            .value(hype)
+            .from(sender)
+            .into_transaction_request();
+        TxManager::new(self.provider.clone()).send(tx).await
+    }
+
+    /// Burns `sthype` wei of stHYPE, redeeming it right away if the Overseer has the
+    /// liquidity. Otherwise the burn shows up in
+    /// [`withdrawal_requests`](Self::withdrawal_requests).
+    pub async fn request_withdrawal(
+        &self,
+        sthype: U256,
+        sender: Address,
+    ) -> anyhow::Result<TransactionReceipt> {
+        let tx = self
+            .overseer()
+            .burnAndRedeemIfPossible(sender, sthype, String::new())
+            .from(sender)
+            .into_transaction_request();
+        TxManager::new(self.provider.clone()).send(tx).await
+    }
+
+    /// Redeems a pending burn.
+    pub async fn claim(&self, id: U256, sender: Address) -> anyhow::Result<TransactionReceipt> {
+        let tx = self
+            .overseer()
+            .redeem(id)
+            .from(sender)
+            .into_transaction_request();
+        TxManager::new(self.provider.clone()).send(tx).await
+    }
+
+    /// Burns of `user` that haven't been redeemed yet.
+    pub async fn withdrawal_requests(
+        &self,
+        user: Address,
+    ) -> anyhow::Result<Vec<WithdrawalRequest>> {
+        let ids = self.overseer().getBurnIds(user).call().await?;
+
+        let mut batch = crate::hyperevm::multicall(self.provider.clone());
+        let slots: Vec<_> = ids
+            .iter()
+            .map(|&id| {
+                (
+                    batch.add(self.contracts.overseer, IOverseer::burnsCall { burnId: id }),
+                    batch.add(
+                        self.contracts.overseer,
+                        IOverseer::redeemableCall { burnId: id },
+                    ),
+                )
+            })
+            .collect();
+        let results = batch.call().await?;
+
+        let mut requests = vec![];
+        for (&id, (burn, redeemable)) in ids.iter().zip(slots) {
+            let burn = results.get(&burn)?;
+            if burn.completed {
+                continue;
+            }
+            requests.push(WithdrawalRequest {
+                id,
+                hype_amount: U256::from(burn.amount),
+                claimable: results.get(&redeemable)?,
+            });
+        }
+        Ok(requests)
+    }
+}
@@ -0,0 +1,355 @@
+//! Transaction simulation and submission.
+//!
+//! [`TxManager`] dry-runs transactions with `eth_call` before broadcasting them, so a
+//! Morpho or Uniswap call that would revert is caught without paying gas. Simulations
+//! can apply state overrides (e.g. fund an account or fake an allowance) and report
+//! ERC-20 balance deltas.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use hypersdk::hyperevm::{self, ERC20, tx::TxManager};
+//! use alloy::signers::local::PrivateKeySigner;
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let signer: PrivateKeySigner = "your_key".parse()?;
+//! let from = signer.address();
+//! let provider = hyperevm::mainnet_with_signer(signer).await?;
+//! let manager = TxManager::new(provider.clone());
+//!
+//! let token = ERC20::new("0x...".parse()?, provider);
+//! let tx = token
+//!     .transfer("0x...".parse()?, hyperevm::U256::from(1_000_000))
+//!     .from(from)
+//!     .into_transaction_request();
+//!
+//! let sim = manager.simulate(tx.clone()).await?;
+//! if let Some(reason) = sim.reason() {
+//!     println!("would revert: {reason}");
+//! } else {
+//!     let receipt = manager.send(tx).await?;
+//!     println!("mined in {:?}", receipt.block_number);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use alloy::{
+    network::ReceiptResponse,
+    primitives::{Bytes, I256},
+    rpc::types::{
+        TransactionReceipt, TransactionRequest,
+        simulate::{SimBlock, SimulatePayload},
+        state::StateOverride,
+    },
+    sol_types::{SolCall, SolInterface, decode_revert_reason},
+    transports::TransportError,
+};
+use anyhow::{Result, anyhow};
+
+use crate::hyperevm::{
+    Address, ERC20, Provider, U256,
+    revert::{RevertError, decode_revert},
+};
+
+/// Outcome of a simulated transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Simulation {
+    /// The call succeeded.
+    Success {
+        /// ABI-encoded return data
+        return_data: Bytes,
+    },
+    /// The call reverted.
+    Reverted {
+        /// Raw revert data (selector + ABI-encoded arguments), possibly empty
+        data: Bytes,
+    },
+}
+
+impl Simulation {
+    /// Returns true if the call succeeded.
+    #[must_use]
+    pub fn is_success(&self) -> bool {
+        matches!(self, Self::Success { .. })
+    }
+
+    /// Returns the raw revert data, if the call reverted.
+    #[must_use]
+    pub fn revert_data(&self) -> Option<&Bytes> {
+        match self {
+            Self::Success { .. } => None,
+            Self::Reverted { data } => Some(data),
+        }
+    }
+
+    /// Returns a human-readable revert reason for `Error(string)`, `Panic(uint256)`,
+    /// or undecodable revert data. `None` if the call succeeded.
+    #[must_use]
+    pub fn reason(&self) -> Option<String> {
+        let data = self.revert_data()?;
+        Some(decode_revert_reason(data).unwrap_or_else(|| format!("reverted with {data}")))
+    }
+
+    /// Decodes the revert data against the protocols bound by this crate.
+    ///
+    /// See [`decode_revert`] for what is recognized. `None` if the call succeeded.
+    #[must_use]
+    pub fn decode(&self) -> Option<RevertError> {
+        self.revert_data().map(|data| decode_revert(data))
+    }
+
+    /// Decodes the revert data as one of the custom errors of a `sol!` interface.
+    ///
+    /// ```rust,ignore
+    /// use hypersdk::hyperevm::morpho::contracts::IMorpho;
+    ///
+    /// if let Some(err) = sim.decode_error::<IMorpho::IMorphoErrors>() {
+    ///     println!("{err:?}");
+    /// }
+    /// ```
+    #[must_use]
+    pub fn decode_error<E: SolInterface>(&self) -> Option<E> {
+        E::abi_decode(self.revert_data()?).ok()
+    }
+}
+
+/// Change in an ERC-20 balance predicted by [`TxManager::balance_deltas`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BalanceDelta {
+    /// ERC-20 token address
+    pub token: Address,
+    /// Balance before the transaction
+    pub before: U256,
+    /// Balance after the transaction
+    pub after: U256,
+}
+
+impl BalanceDelta {
+    /// Signed balance change (`after - before`).
+    #[must_use]
+    pub fn delta(&self) -> I256 {
+        I256::from_raw(self.after).wrapping_sub(I256::from_raw(self.before))
+    }
+}
+
+/// Simulates transactions before sending them.
+pub struct TxManager<P>
+where
+    P: Provider,
+{
+    provider: P,
+}
+
+impl<P> TxManager<P>
+where
+    P: Provider,
+{
+    /// Creates a manager over `provider`.
+    ///
+    /// The provider needs a wallet for [`send`](Self::send); simulations don't.
+    pub fn new(provider: P) -> Self {
+        Self { provider }
+    }
+
+    /// Returns the underlying provider.
+    pub fn provider(&self) -> &P {
+        &self.provider
+    }
+
+    /// Simulates `tx` against the latest block with `eth_call`.
+    ///
+    /// A revert is reported as [`Simulation::Reverted`]; transport failures are errors.
+    pub async fn simulate(&self, tx: TransactionRequest) -> Result<Simulation> {
+        self.simulate_with(tx, StateOverride::default()).await
+    }
+
+    /// Simulates `tx` with `eth_call`, applying `overrides` to the state first.
+    pub async fn simulate_with(
+        &self,
+        tx: TransactionRequest,
+        overrides: StateOverride,
+    ) -> Result<Simulation> {
+        let res = self.provider.call(tx).overrides(overrides).await;
+        into_simulation(res)
+    }
+
+    /// Predicts how `tx` changes `owner`'s balance of each token in `tokens`.
+    ///
+    /// Uses `eth_simulateV1` to read `balanceOf` before and after `tx` within a single
+    /// simulated block, with `overrides` applied to the state first (e.g. to fund
+    /// `owner` or fake an allowance). Fails if `tx` reverts or the node doesn't support
+    /// `eth_simulateV1`.
+    pub async fn balance_deltas(
+        &self,
+        tx: TransactionRequest,
+        owner: Address,
+        tokens: &[Address],
+        overrides: Option<StateOverride>,
+    ) -> Result<Vec<BalanceDelta>> {
+        let balances: Vec<TransactionRequest> = tokens
+            .iter()
+            .map(|&token| {
+                ERC20::new(token, self.provider.clone())
+                    .balanceOf(owner)
+                    .into_transaction_request()
+            })
+            .collect();
+
+        let payload = deltas_payload(tx, balances, overrides);
+        let blocks = self.provider.simulate(&payload).await?;
+        let results = blocks
+            .into_iter()
+            .next()
+            .map(|block| block.calls)
+            .ok_or_else(|| anyhow!("eth_simulateV1 returned no blocks"))?;
+        if results.len() != 2 * tokens.len() + 1 {
+            return Err(anyhow!(
+                "eth_simulateV1 returned {} results for {} calls",
+                results.len(),
+                2 * tokens.len() + 1
+            ));
+        }
+
+        let tx_result = &results[tokens.len()];
+        if !tx_result.status {
+            let sim = Simulation::Reverted {
+                data: tx_result.return_data.clone(),
+            };
+            return Err(anyhow!(
+                "transaction reverted: {}",
+                sim.reason().unwrap_or_default()
+            ));
+        }
+
+        let decode = |data: &Bytes| ERC20::balanceOfCall::abi_decode_returns(data);
+        tokens
+            .iter()
+            .enumerate()
+            .map(|(i, &token)| {
+                let before = decode(&results[i].return_data)?;
+                let after = decode(&results[tokens.len() + 1 + i].return_data)?;
+                Ok(BalanceDelta {
+                    token,
+                    before,
+                    after,
+                })
+            })
+            .collect()
+    }
+
+    /// Simulates `tx` and only broadcasts it if the simulation succeeds.
+    ///
+    /// Waits for the receipt and fails if the mined transaction reverted anyway.
+    pub async fn send(&self, tx: TransactionRequest) -> Result<TransactionReceipt> {
+        let sim = self.simulate(tx.clone()).await?;
+        if let Some(reason) = sim.reason() {
+            return Err(anyhow!("simulation reverted: {reason}"));
+        }
+
+        let receipt = self
+            .provider
+            .send_transaction(tx)
+            .await?
+            .get_receipt()
+            .await?;
+        if !receipt.status() {
+            return Err(anyhow!(
+                "transaction {} reverted",
+                receipt.transaction_hash()
+            ));
+        }
+
+        Ok(receipt)
+    }
+}
+
+/// Builds the single-block `eth_simulateV1` payload of [`TxManager::balance_deltas`]:
+/// the balance reads, `tx`, then the balance reads again.
+fn deltas_payload(
+    tx: TransactionRequest,
+    balances: Vec<TransactionRequest>,
+    overrides: Option<StateOverride>,
+) -> SimulatePayload {
+    let mut calls = balances.clone();
+    calls.push(tx);
+    calls.extend(balances);
+
+    let block = SimBlock {
+        state_overrides: overrides,
+        ..SimBlock::default()
+    };
+    SimulatePayload::default().extend(block.extend_calls(calls))
+}
+
+/// Maps an `eth_call` result to a [`Simulation`], treating revert errors as data.
+fn into_simulation(res: Result<Bytes, TransportError>) -> Result<Simulation> {
+    match res {
+        Ok(return_data) => Ok(Simulation::Success { return_data }),
+        Err(err) => {
+            let revert = err.as_error_resp().and_then(|payload| {
+                payload
+                    .as_revert_data()
+                    .or_else(|| payload.message.contains("revert").then(Bytes::new))
+            });
+            match revert {
+                Some(data) => Ok(Simulation::Reverted { data }),
+                None => Err(err.into()),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::sol_types::{Revert, SolError};
+
+    use super::*;
+
+    #[test]
+    fn test_simulation_revert_reason() {
+        let data = Revert::from("insufficient balance").abi_encode();
+        let sim = Simulation::Reverted { data: data.into() };
+        assert!(!sim.is_success());
+        assert_eq!(
+            sim.reason().as_deref(),
+            Some("revert: insufficient balance")
+        );
+
+        let ok = Simulation::Success {
+            return_data: Bytes::new(),
+        };
+        assert_eq!(ok.reason(), None);
+    }
+
+    #[test]
+    fn test_balance_delta_sign() {
+        let delta = BalanceDelta {
+            token: Address::ZERO,
+            before: U256::from(100),
+            after: U256::from(40),
+        };
+        assert_eq!(delta.delta(), I256::try_from(-60i64).unwrap());
+    }
+
+    #[test]
+    fn test_deltas_payload_overrides() {
+        use alloy::rpc::types::state::AccountOverride;
+
+        let token = Address::repeat_byte(1);
+        let read = TransactionRequest::default().to(token);
+        let tx = TransactionRequest::default().to(Address::repeat_byte(2));
+        let overrides = StateOverride::from_iter([(
+            Address::repeat_byte(3),
+            AccountOverride::default().with_balance(U256::from(10)),
+        )]);
+
+        let payload = deltas_payload(tx.clone(), vec![read.clone()], Some(overrides.clone()));
+        let block = &payload.block_state_calls[0];
+        assert_eq!(block.calls, [read.clone(), tx.clone(), read.clone()]);
+        assert_eq!(block.state_overrides, Some(overrides));
+
+        let payload = deltas_payload(tx, vec![read], None);
+        assert_eq!(payload.block_state_calls[0].state_overrides, None);
+    }
+}
@@ -0,0 +1,270 @@
+//! 18-decimal fixed-point arithmetic on [`U256`].
+//!
+//! EVM protocols (Morpho, ERC-4626 vaults, most ERC-20s) express rates and amounts
+//! as integers scaled by 1e18 ("WAD"). [`Wad`] keeps those values in `U256` so the
+//! math never leaves integer space, and only converts to [`Decimal`] at the edges.
+//!
+//! Multiplication and division go through a 512-bit intermediate, so `a * b / c`
+//! does not overflow as long as the final result fits in 256 bits.
+//!
+//! # Example
+//!
+//! ```rust
+//! use hypersdk::hyperevm::Wad;
+//! use rust_decimal::dec;
+//!
+//! let rate = Wad::from_decimal(dec!(0.05)).unwrap();
+//! let amount = Wad::from_decimal(dec!(1000)).unwrap();
+//!
+//! let interest = amount.checked_mul(rate).unwrap();
+//! assert_eq!(interest.to_decimal_lossy(), dec!(50));
+//! ```
+
+use std::fmt;
+
+use alloy::primitives::{U256, U512};
+use rust_decimal::Decimal;
+
+/// 1e18 as a `U256`.
+pub const WAD: U256 = U256::from_limbs([1_000_000_000_000_000_000, 0, 0, 0]);
+
+/// Number of decimals represented by a [`Wad`].
+pub const WAD_DECIMALS: u32 = 18;
+
+/// Computes `a * b / denominator` with a 512-bit intermediate, rounding down.
+///
+/// Returns `None` if `denominator` is zero or the result does not fit in 256 bits.
+#[must_use]
+pub fn mul_div(a: U256, b: U256, denominator: U256) -> Option<U256> {
+    if denominator.is_zero() {
+        return None;
+    }
+
+    let res = U512::from(a) * U512::from(b) / U512::from(denominator);
+    if res.bit_len() > 256 {
+        return None;
+    }
+
+    Some(U256::from_limbs_slice(&res.as_limbs()[..4]))
+}
+
+/// Unsigned 18-decimal fixed-point number backed by a [`U256`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Wad(U256);
+
+impl Wad {
+    /// Zero.
+    pub const ZERO: Self = Self(U256::ZERO);
+    /// One (raw value 1e18).
+    pub const ONE: Self = Self(WAD);
+
+    /// Wraps a raw 1e18-scaled value, e.g. a Morpho rate or fee.
+    #[must_use]
+    pub const fn from_raw(raw: U256) -> Self {
+        Self(raw)
+    }
+
+    /// Returns the raw 1e18-scaled value.
+    #[must_use]
+    pub const fn raw(self) -> U256 {
+        self.0
+    }
+
+    /// Converts an integer amount into a `Wad` (`value * 1e18`).
+    ///
+    /// Returns `None` on overflow.
+    #[must_use]
+    pub fn from_integer(value: U256) -> Option<Self> {
+        value.checked_mul(WAD).map(Self)
+    }
+
+    /// Converts a non-negative [`Decimal`] into a `Wad`.
+    ///
+    /// Digits beyond 18 decimal places are truncated. Returns `None` for negative values.
+    #[must_use]
+    pub fn from_decimal(value: Decimal) -> Option<Self> {
+        super::try_to_wei(value.trunc_with_scale(WAD_DECIMALS), WAD_DECIMALS).map(Self)
+    }
+
+    /// Converts into a [`Decimal`], returning `None` if the value does not fit exactly.
+    #[must_use]
+    pub fn try_to_decimal(self) -> Option<Decimal> {
+        super::try_from_wei(self.0, WAD_DECIMALS)
+    }
+
+    /// Converts into a [`Decimal`], dropping low-order digits that don't fit.
+    ///
+    /// Saturates at [`Decimal::MAX`].
+    #[must_use]
+    pub fn to_decimal_lossy(self) -> Decimal {
+        super::from_wei_lossy(self.0, WAD_DECIMALS)
+    }
+
+    /// Checked addition.
+    #[must_use]
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        self.0.checked_add(rhs.0).map(Self)
+    }
+
+    /// Checked subtraction. Returns `None` if `rhs > self`.
+    #[must_use]
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.0.checked_sub(rhs.0).map(Self)
+    }
+
+    /// Saturating subtraction.
+    #[must_use]
+    pub fn saturating_sub(self, rhs: Self) -> Self {
+        Self(self.0.saturating_sub(rhs.0))
+    }
+
+    /// Fixed-point multiplication (`self * rhs / 1e18`), rounding down.
+    #[must_use]
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        mul_div(self.0, rhs.0, WAD).map(Self)
+    }
+
+    /// Fixed-point division (`self * 1e18 / rhs`), rounding down.
+    ///
+    /// Returns `None` if `rhs` is zero or on overflow.
+    #[must_use]
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        mul_div(self.0, WAD, rhs.0).map(Self)
+    }
+
+    /// Returns true if the value is zero.
+    #[must_use]
+    pub fn is_zero(self) -> bool {
+        self.0.is_zero()
+    }
+}
+
+impl From<U256> for Wad {
+    fn from(raw: U256) -> Self {
+        Self::from_raw(raw)
+    }
+}
+
+impl From<Wad> for U256 {
+    fn from(wad: Wad) -> Self {
+        wad.0
+    }
+}
+
+impl fmt::Display for Wad {
+    /// Formats the exact decimal value, without trailing zeros.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let int = self.0 / WAD;
+        let frac = self.0 % WAD;
+        if frac.is_zero() {
+            return write!(f, "{int}");
+        }
+
+        let frac = format!("{frac:0>18}");
+        write!(f, "{int}.{}", frac.trim_end_matches('0'))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use rust_decimal::dec;
+
+    use super::*;
+
+    #[test]
+    fn mul_div_uses_wide_intermediate() {
+        // (2^255 * 4) / 8 overflows in 256 bits but the result fits.
+        let a = U256::from(1) << 255usize;
+        assert_eq!(mul_div(a, U256::from(4), U256::from(8)), Some(a >> 1usize));
+        assert_eq!(mul_div(U256::MAX, U256::from(2), U256::from(1)), None);
+        assert_eq!(mul_div(U256::MAX, U256::MAX, U256::MAX), Some(U256::MAX));
+        assert_eq!(mul_div(U256::from(1), U256::from(1), U256::ZERO), None);
+    }
+
+    #[test]
+    fn wad_arithmetic() {
+        let half = Wad::from_decimal(dec!(0.5)).unwrap();
+        let three = Wad::from_integer(U256::from(3)).unwrap();
+
+        assert_eq!(three.checked_mul(half).unwrap().to_string(), "1.5");
+        assert_eq!(three.checked_div(half).unwrap().to_string(), "6");
+        assert_eq!(half.checked_sub(three), None);
+        assert_eq!(Wad::ONE.checked_div(Wad::ZERO), None);
+        assert_eq!(
+            Wad::from_raw(U256::from(1)).to_string(),
+            "0.000000000000000001"
+        );
+    }
+
+    #[test]
+    fn wad_decimal_roundtrip_across_magnitudes() {
+        // Powers of ten from 1e-18 to 1e10 fit an 18-decimal Decimal mantissa exactly.
+        let mut value = dec!(0.000000000000000001);
+        for _ in 0..=28 {
+            let wad = Wad::from_decimal(value).unwrap();
+            assert_eq!(wad.try_to_decimal(), Some(value));
+            value *= dec!(10);
+        }
+
+        // Values far beyond Decimal's range still convert lossily without panicking.
+        let huge = Wad::from_raw(U256::MAX);
+        assert_eq!(huge.try_to_decimal(), None);
+        assert_eq!(huge.to_decimal_lossy(), Decimal::MAX);
+    }
+
+    /// Values of every bit length, from 0 up to full 256-bit words.
+    fn any_u256() -> impl Strategy<Value = U256> {
+        (any::<[u64; 4]>(), 0usize..256).prop_map(|(limbs, shift)| U256::from_limbs(limbs) >> shift)
+    }
+
+    proptest! {
+        #[test]
+        fn prop_mul_div_matches_exact_product(
+            a in any::<u128>(),
+            b in any::<u128>(),
+            d in 1..=u128::MAX,
+        ) {
+            // The product of two u128s always fits in 256 bits.
+            let (a, b, d) = (U256::from(a), U256::from(b), U256::from(d));
+            prop_assert_eq!(mul_div(a, b, d), Some(a * b / d));
+        }
+
+        #[test]
+        fn prop_mul_div_cancels(a in any_u256(), b in any_u256()) {
+            prop_assume!(!b.is_zero());
+            prop_assert_eq!(mul_div(a, b, b), Some(a));
+        }
+
+        #[test]
+        fn prop_wad_one_is_identity(raw in any_u256()) {
+            let value = Wad::from_raw(raw);
+            prop_assert_eq!(value.checked_mul(Wad::ONE), Some(value));
+            prop_assert_eq!(value.checked_div(Wad::ONE), Some(value));
+        }
+
+        #[test]
+        fn prop_wad_decimal_roundtrip(
+            mantissa in 0..10_000_000_000i64,
+            scale in 0..=WAD_DECIMALS,
+        ) {
+            // Any scale up to 18 then needs at most 28 digits, which a Decimal holds.
+            let value = Decimal::new(mantissa, scale);
+            let wad = Wad::from_decimal(value).unwrap();
+            prop_assert_eq!(wad.try_to_decimal(), Some(value));
+            prop_assert_eq!(wad.to_decimal_lossy(), value);
+        }
+
+        #[test]
+        fn prop_wad_to_decimal_lossy_rounds_down(raw in any_u256()) {
+            let value = Wad::from_raw(raw);
+            let lossy = value.to_decimal_lossy();
+            if let Some(exact) = value.try_to_decimal() {
+                prop_assert_eq!(lossy, exact);
+            }
+            if lossy != Decimal::MAX {
+                prop_assert!(Wad::from_decimal(lossy).unwrap() <= value);
+            }
+        }
+    }
+}
@@ -17,6 +17,9 @@
 //! | [`hypercore::signing`] | Signature utilities | Sign actions, recover addresses |
 //! | [`hyperevm::morpho`] | Morpho lending | Query APY, lending positions |
 //! | [`hyperevm::uniswap`] | Uniswap V3 | Pool prices, liquidity positions |
+//! | [`arb`] | Cross-layer arbitrage | Core spot vs HyperEVM DEX divergence |
+//! | [`balance_sheet`] | Cross-layer balances | Portfolio value across Core and EVM |
+//! | [`bridge::arbitrum`] | Onboarding | Deposit USDC from Arbitrum |
 //!
 //! ## Features
 //!
@@ -112,7 +115,7 @@
 //! });
 //! ws.subscribe(Subscription::Candle {
 //!     coin: "BTC".into(),
-//!     interval: "15m".into()
+//!     interval: CandleInterval::FifteenMinutes,
 //! });
 //!
 //! // Optional: user streams
@@ -193,6 +196,8 @@
 //!
 //! All prices and quantities use [`rust_decimal::Decimal`] for precise financial calculations.
 //! This avoids floating-point rounding errors that are critical in trading applications.
+//! The [`numeric`] module lets market price rounding work on `f64` or
+//! `bigdecimal::BigDecimal` when the corresponding cargo feature is enabled.
 //!
 //! ### Zero-Copy WebSocket
 //!
@@ -229,17 +234,33 @@
 //!   - [`hypercore::http`]: HTTP API client for queries and trading
 //!   - [`hypercore::ws`]: WebSocket client for real-time data
 //!   - [`hypercore::types`]: Core type definitions (orders, trades, market data)
+//!   - [`hypercore::decode`]: Strict or lenient handling of unknown response fields
+//!     (`strict` cargo feature)
 //! - [`hyperevm`]: HyperEVM contract interactions
 //!   - [`hyperevm::morpho`]: Morpho lending protocol integration
 //!   - [`hyperevm::uniswap`]: Uniswap V3 DEX integration
+//! - [`arb`]: HyperCore spot vs HyperEVM DEX price divergence monitor
+//! - [`balance_sheet`]: Consolidated Core and EVM balances valued in USD ([`Hyperliquid::balance_sheet`])
+//! - [`bridge`]: USDC deposits from Arbitrum, waiting for the credit on HyperCore
+//! - [`numeric`]: Conversions between [`Decimal`] and optional numeric backends
+//!   (`f64`, `bigdecimal` cargo features)
+//! - [`prelude`]: Commonly used types (`use hypersdk::prelude::*`)
 
+pub mod arb;
+pub mod balance_sheet;
+pub mod bridge;
+mod client;
 pub mod hypercore;
 pub mod hyperevm;
+pub mod numeric;
+pub mod prelude;
 
 /// Re-exported Ethereum address type from Alloy.
 ///
 /// Used throughout the SDK for representing Ethereum-compatible addresses.
 pub use alloy::primitives::{Address, U160, U256, address};
+/// Combined HyperCore and HyperEVM client.
+pub use client::Hyperliquid;
 /// Re-exported decimal type from rust_decimal.
 ///
 /// Used for precise numerical operations, especially for prices and quantities.
@@ -0,0 +1,174 @@
+//! Numeric backends for prices and sizes.
+//!
+//! Prices, sizes, and amounts are carried as [`Decimal`] on the wire and in every
+//! SDK type. Applications that do their own math in a different representation
+//! implement or use a [`Numeric`] backend: market price rounding
+//! ([`PerpMarket::round_price`](crate::hypercore::PerpMarket::round_price),
+//! [`round_by_side`](crate::hypercore::SpotMarket::round_by_side), ...) accepts and
+//! returns any backend, and [`convert`] moves values between them.
+//!
+//! Conversions into [`Decimal`] fail with a [`NumericError`] when the value doesn't
+//! fit its range or 28 decimal places. The `f64` backend is the exception: it
+//! carries a binary approximation, so converting to `f64` rounds to the nearest
+//! double and converting back yields that double's value rather than the
+//! original decimal.
+//!
+//! # Backends
+//!
+//! | Type | Cargo feature | Notes |
+//! |------|---------------|-------|
+//! | [`Decimal`] | always enabled | Default; 96-bit mantissa, up to 28 decimal places |
+//! | `f64` | `f64` | Fast, lossy; NaN, infinite and out-of-range values fail to convert |
+//! | `bigdecimal::BigDecimal` | `bigdecimal` | Arbitrary precision; useful for 18-decimal EVM math |
+//!
+//! # Example
+//!
+//! ```rust
+//! use hypersdk::{dec, numeric::Numeric};
+//!
+//! let px = dec!(95000.5);
+//! let same = rust_decimal::Decimal::from_decimal(px).unwrap();
+//! assert_eq!(same.to_decimal(), Ok(px));
+//! ```
+
+use rust_decimal::Decimal;
+
+/// A value that doesn't fit the target numeric type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum NumericError {
+    /// NaN or infinite
+    #[error("value is not finite")]
+    NotFinite,
+    /// Too large for the target type
+    #[error("value is out of range")]
+    OutOfRange,
+    /// More decimal places than the target type holds
+    #[error("value has more precision than the target type holds")]
+    Inexact,
+}
+
+/// A numeric type that can be converted to and from [`Decimal`].
+pub trait Numeric: Sized {
+    /// Converts a [`Decimal`] into this type.
+    fn from_decimal(value: Decimal) -> Result<Self, NumericError>;
+
+    /// Converts this value into a [`Decimal`].
+    fn to_decimal(&self) -> Result<Decimal, NumericError>;
+}
+
+impl Numeric for Decimal {
+    fn from_decimal(value: Decimal) -> Result<Self, NumericError> {
+        Ok(value)
+    }
+
+    fn to_decimal(&self) -> Result<Decimal, NumericError> {
+        Ok(*self)
+    }
+}
+
+#[cfg(feature = "f64")]
+impl Numeric for f64 {
+    fn from_decimal(value: Decimal) -> Result<Self, NumericError> {
+        use rust_decimal::prelude::ToPrimitive;
+        value.to_f64().ok_or(NumericError::OutOfRange)
+    }
+
+    fn to_decimal(&self) -> Result<Decimal, NumericError> {
+        if !self.is_finite() {
+            return Err(NumericError::NotFinite);
+        }
+        Decimal::from_f64_retain(*self).ok_or(NumericError::OutOfRange)
+    }
+}
+
+#[cfg(feature = "bigdecimal")]
+impl Numeric for bigdecimal::BigDecimal {
+    fn from_decimal(value: Decimal) -> Result<Self, NumericError> {
+        let mantissa = bigdecimal::num_bigint::BigInt::from(value.mantissa());
+        Ok(Self::new(mantissa, i64::from(value.scale())))
+    }
+
+    fn to_decimal(&self) -> Result<Decimal, NumericError> {
+        let (mantissa, scale) = self.normalized().into_bigint_and_exponent();
+        // A negative scale is a multiple of a power of ten; make it an integer.
+        let (mantissa, scale) = if scale < 0 {
+            let exp = u32::try_from(-scale).map_err(|_| NumericError::OutOfRange)?;
+            (
+                mantissa * bigdecimal::num_bigint::BigInt::from(10).pow(exp),
+                0,
+            )
+        } else {
+            (mantissa, scale)
+        };
+        if scale > i64::from(Decimal::MAX_SCALE) {
+            return Err(NumericError::Inexact);
+        }
+        let mantissa = i128::try_from(&mantissa).map_err(|_| NumericError::OutOfRange)?;
+        Decimal::try_from_i128_with_scale(mantissa, scale as u32)
+            .map_err(|_| NumericError::OutOfRange)
+    }
+}
+
+/// Converts between two numeric backends through [`Decimal`].
+pub fn convert<A: Numeric, B: Numeric>(value: &A) -> Result<B, NumericError> {
+    value.to_decimal().and_then(B::from_decimal)
+}
+
+/// Runs a [`Decimal`] computation on a value of any backend, `None` if the value
+/// or the result doesn't convert.
+pub(crate) fn map<T: Numeric>(value: T, f: impl FnOnce(Decimal) -> Option<Decimal>) -> Option<T> {
+    let value = f(value.to_decimal().ok()?)?;
+    T::from_decimal(value).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::dec;
+
+    use super::*;
+
+    #[test]
+    fn decimal_roundtrip() {
+        let value = dec!(0.000123456789);
+        let out: Decimal = convert(&value).unwrap();
+        assert_eq!(out, value);
+        assert_eq!(
+            map(value, |v| Some(v * dec!(2))),
+            Some(dec!(0.000246913578))
+        );
+    }
+
+    #[cfg(feature = "f64")]
+    #[test]
+    fn f64_backend() {
+        assert_eq!(f64::from_decimal(dec!(1.5)), Ok(1.5));
+        assert_eq!(2.25f64.to_decimal(), Ok(dec!(2.25)));
+        assert_eq!(f64::NAN.to_decimal(), Err(NumericError::NotFinite));
+        assert_eq!(f64::MAX.to_decimal(), Err(NumericError::OutOfRange));
+    }
+
+    #[cfg(feature = "bigdecimal")]
+    #[test]
+    fn bigdecimal_backend() {
+        use bigdecimal::BigDecimal;
+
+        let big = BigDecimal::from_decimal(dec!(123.456)).unwrap();
+        assert_eq!(big.to_decimal(), Ok(dec!(123.456)));
+
+        // Exponent notation converts exactly.
+        let exp: BigDecimal = "1.5e3".parse().unwrap();
+        assert_eq!(exp.to_decimal(), Ok(dec!(1500)));
+        let small: BigDecimal = "25e-4".parse().unwrap();
+        assert_eq!(small.to_decimal(), Ok(dec!(0.0025)));
+
+        // 18-decimal EVM amounts beyond Decimal's 28-digit mantissa don't fit.
+        let huge: BigDecimal = "1000000000000000000000000000000.000000000000000001"
+            .parse()
+            .unwrap();
+        assert_eq!(huge.to_decimal(), Err(NumericError::OutOfRange));
+
+        // Precision is never dropped silently.
+        let precise: BigDecimal = "0.00000000000000000000000000001".parse().unwrap();
+        assert_eq!(precise.to_decimal(), Err(NumericError::Inexact));
+    }
+}
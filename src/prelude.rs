@@ -0,0 +1,38 @@
+//! Commonly used types, re-exported in one place.
+//!
+//! ```no_run
+//! use hypersdk::prelude::*;
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let client = HttpClient::new(Chain::Mainnet);
+//! let signer: PrivateKeySigner = "your_key".parse()?;
+//! let nonce = NonceHandler::default();
+//!
+//! let order = BatchOrder {
+//!     orders: vec![OrderRequest {
+//!         asset: 0,
+//!         is_buy: true,
+//!         limit_px: dec!(87_000),
+//!         sz: dec!(0.01),
+//!         reduce_only: false,
+//!         order_type: OrderTypePlacement::Limit {
+//!             tif: TimeInForce::Alo,
+//!         },
+//!         cloid: Cloid::random(),
+//!     }],
+//!     grouping: OrderGrouping::Na,
+//!     builder: None,
+//! };
+//! client.place(&signer, order, nonce.next(), None, None).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+pub use crate::{
+    Address, Decimal, U256, dec,
+    hypercore::{
+        BatchCancel, BatchModify, BatchOrder, Cancel, Chain, Cloid, HttpClient, Incoming, Modify,
+        NonceHandler, OidOrCloid, OrderGrouping, OrderRequest, OrderResponseStatus,
+        OrderTypePlacement, PrivateKeySigner, Subscription, TimeInForce, WebSocket, ws::Event,
+    },
+};
@@ -0,0 +1,306 @@
+//! Schema checks against the live testnet API.
+//!
+//! Hyperliquid adds and renames response fields without notice. The typed
+//! responses tolerate unknown fields by default, so drift only shows up as debug
+//! logs or, for removed fields, as parse errors in users' code. These tests decode
+//! every typed info endpoint and WebSocket channel in [`DecodeMode::Strict`] and
+//! report every endpoint that fails, so drift is caught here first.
+//!
+//! They hit the network and are gated behind the `live-tests` feature:
+//!
+//! ```bash
+//! cargo test --features live-tests --test live
+//! ```
+//!
+//! User endpoints query `HYPERSDK_LIVE_USER` when set, and otherwise the largest
+//! open testnet vault, which has positions, fills and funding to decode. Metadata
+//! (`meta`, `spotMeta`, `perpDexs`) is always decoded leniently by the client, so
+//! only missing fields are caught for those.
+
+use std::{collections::BTreeSet, time::Duration};
+
+use alloy::primitives::Address;
+use chrono::Utc;
+use futures::{SinkExt, StreamExt};
+use hypersdk::hypercore::{
+    self, CandleInterval, HttpClient, Incoming, Outgoing, Subscription, VaultFilter,
+    decode::{DecodeMode, decode},
+};
+use yawc::{Frame, OpCode, Options};
+
+/// How long to listen to the WebSocket for.
+const LISTEN: Duration = Duration::from_secs(20);
+
+/// Collects endpoint failures and panics with all of them at the end.
+#[derive(Default)]
+struct Report {
+    checked: usize,
+    failures: Vec<String>,
+}
+
+impl Report {
+    fn check<T>(&mut self, label: &str, res: anyhow::Result<T>) -> Option<T> {
+        self.checked += 1;
+        match res {
+            Ok(value) => Some(value),
+            Err(err) => {
+                self.failures.push(format!("{label}: {err:#}"));
+                None
+            }
+        }
+    }
+
+    fn finish(self) {
+        assert!(
+            self.failures.is_empty(),
+            "{} of {} checks failed:\n{}",
+            self.failures.len(),
+            self.checked,
+            self.failures.join("\n")
+        );
+    }
+}
+
+fn client() -> HttpClient {
+    hypercore::testnet().with_decode_mode(DecodeMode::Strict)
+}
+
+async fn live_user(client: &HttpClient) -> Address {
+    if let Ok(user) = std::env::var("HYPERSDK_LIVE_USER") {
+        return user.parse().expect("HYPERSDK_LIVE_USER is not an address");
+    }
+    let vaults = client
+        .vault_summaries(&VaultFilter::default().open_only())
+        .await
+        .expect("vault summaries");
+    vaults
+        .iter()
+        .max_by_key(|vault| vault.summary.tvl)
+        .map(|vault| vault.summary.vault_address)
+        .expect("no open testnet vault")
+}
+
+#[tokio::test]
+async fn test_live_market_endpoints() {
+    let client = client();
+    let mut report = Report::default();
+    let now = Utc::now().timestamp_millis() as u64;
+    let day_ago = now - 24 * 60 * 60 * 1000;
+
+    let perps = report
+        .check("perps", client.perps().await)
+        .unwrap_or_default();
+    let spot_tokens = report
+        .check("spot_tokens", client.spot_tokens().await)
+        .unwrap_or_default();
+    let dexes = report
+        .check("perp_dexes", client.perp_dexes().await)
+        .unwrap_or_default();
+    report.check("spot", client.spot().await);
+    report.check("outcome_meta", client.outcome_meta().await);
+    report.check("all_mids", client.all_mids(None).await);
+    report.check("perp_contexts", client.perp_contexts().await);
+    report.check("predicted_fundings", client.predicted_fundings().await);
+    report.check(
+        "perps_at_open_interest_cap",
+        client.perps_at_open_interest_cap(None).await,
+    );
+    report.check("exchange_status", client.exchange_status().await);
+    report.check(
+        "perp_deploy_auction_status",
+        client.perp_deploy_auction_status().await,
+    );
+    report.check(
+        "spot_pair_deploy_auction_status",
+        client.spot_pair_deploy_auction_status().await,
+    );
+    report.check("perp_categories", client.perp_categories().await);
+    report.check("validator_summaries", client.validator_summaries().await);
+    report.check(
+        "gossip_priority_auction_status",
+        client.gossip_priority_auction_status().await,
+    );
+    report.check("leaderboard", client.leaderboard().await);
+
+    if let Some(perp) = perps.first() {
+        let coin = perp.name.clone();
+        report.check("l2_book", client.l2_book(coin.clone(), None, None).await);
+        report.check(
+            "candle_snapshot",
+            client
+                .candle_snapshot(coin.clone(), CandleInterval::OneHour, day_ago, now)
+                .await,
+        );
+        report.check(
+            "funding_history",
+            client.funding_history(coin, day_ago, None).await,
+        );
+    }
+    if let Some(token) = spot_tokens.first() {
+        report.check(
+            "token_details",
+            client.token_details(token.token_id.to_string()).await,
+        );
+    }
+    if let Some(dex) = dexes.first() {
+        let name = dex.name().to_owned();
+        report.check(
+            "perp_dex_limits",
+            client.perp_dex_limits(name.clone()).await,
+        );
+        report.check("perp_dex_status", client.perp_dex_status(name).await);
+    }
+
+    report.finish();
+}
+
+#[tokio::test]
+async fn test_live_user_endpoints() {
+    let client = client();
+    let user = live_user(&client).await;
+    let mut report = Report::default();
+    let month_ago = (Utc::now().timestamp_millis() as u64) - 30 * 24 * 60 * 60 * 1000;
+
+    report.check("open_orders", client.open_orders(user, None).await);
+    report.check(
+        "frontend_open_orders",
+        client.frontend_open_orders(user, None).await,
+    );
+    report.check("historical_orders", client.historical_orders(user).await);
+    report.check("user_fills", client.user_fills(user).await);
+    report.check(
+        "user_fills_by_time",
+        client
+            .user_fills_by_time(user, month_ago, None, false)
+            .await,
+    );
+    report.check("user_balances", client.user_balances(user).await);
+    report.check("user_fees", client.user_fees(user).await);
+    report.check(
+        "clearinghouse_state",
+        client.clearinghouse_state(user, None).await,
+    );
+    report.check("vault_details", client.vault_details(user, None).await);
+    report.check("api_agents", client.api_agents(user).await);
+    report.check(
+        "user_vault_equities",
+        client.user_vault_equities(user).await,
+    );
+    report.check("user_role", client.user_role(user).await);
+    report.check("subaccounts", client.subaccounts(user).await);
+    report.check("user_rate_limit", client.user_rate_limit(user).await);
+    report.check(
+        "user_funding",
+        client.user_funding(user, month_ago, None).await,
+    );
+    report.check("portfolio", client.portfolio(user).await);
+    report.check("approved_builders", client.approved_builders(user).await);
+    report.check("delegations", client.delegations(user).await);
+    report.check("delegator_summary", client.delegator_summary(user).await);
+    report.check("delegator_history", client.delegator_history(user).await);
+    report.check("delegator_rewards", client.delegator_rewards(user).await);
+    report.check(
+        "user_twap_slice_fills",
+        client.user_twap_slice_fills(user).await,
+    );
+    report.check("user_twap_history", client.user_twap_history(user).await);
+    report.check("abstraction_mode", client.abstraction_mode(user).await);
+
+    report.finish();
+}
+
+#[tokio::test]
+async fn test_live_ws_channels() {
+    let client = client();
+    let user = live_user(&client).await;
+    let coin = client.perps().await.unwrap()[0].name.clone();
+
+    let subscriptions = [
+        Subscription::Bbo { coin: coin.clone() },
+        Subscription::Trades { coin: coin.clone() },
+        Subscription::L2Book {
+            coin: coin.clone(),
+            n_sig_figs: None,
+            mantissa: None,
+            fast: false,
+        },
+        Subscription::Candle {
+            coin: coin.clone(),
+            interval: CandleInterval::OneMinute,
+        },
+        Subscription::AllMids { dex: None },
+        Subscription::ActiveAssetCtx { coin: coin.clone() },
+        Subscription::ActiveAssetData { user, coin },
+        Subscription::OrderUpdates { user },
+        Subscription::UserFills { user },
+        Subscription::UserEvents { user },
+        Subscription::UserTwapSliceFills { user },
+        Subscription::UserTwapHistory { user },
+        Subscription::ClearinghouseState { user, dex: None },
+        Subscription::OpenOrders { user, dex: None },
+        Subscription::SpotState {
+            user,
+            is_portfolio_margin: None,
+        },
+        Subscription::TwapStates { user, dex: None },
+        Subscription::UserFundings { user },
+        Subscription::UserNonFundingLedgerUpdates { user },
+        Subscription::AllDexsAssetCtxs,
+        Subscription::FastAssetCtxs,
+    ];
+
+    let mut ws = yawc::WebSocket::connect(hypercore::testnet_websocket_url())
+        .with_options(Options::default().with_utf8())
+        .await
+        .expect("connect");
+    for subscription in subscriptions {
+        let text = serde_json::to_string(&Outgoing::Subscribe { subscription }).unwrap();
+        ws.send(Frame::text(text)).await.unwrap();
+    }
+
+    // Market channels stream constantly; user channels send a snapshot at most.
+    let expected = [
+        "bbo",
+        "l2Book",
+        "candle",
+        "allMids",
+        "activeAssetCtx",
+        "allDexsAssetCtxs",
+    ];
+    let mut seen = BTreeSet::new();
+    let mut report = Report::default();
+    let listen = tokio::time::sleep(LISTEN);
+    tokio::pin!(listen);
+    loop {
+        let frame = tokio::select! {
+            _ = &mut listen => break,
+            frame = ws.next() => match frame {
+                Some(frame) => frame,
+                None => break,
+            },
+        };
+        if frame.opcode() != OpCode::Text {
+            continue;
+        }
+        let text = frame.as_str();
+        let channel = serde_json::from_str::<serde_json::Value>(text)
+            .ok()
+            .and_then(|value| value["channel"].as_str().map(str::to_owned))
+            .unwrap_or_default();
+        // Report each channel once, with the first message that failed.
+        if seen.contains(&channel) {
+            continue;
+        }
+        report.check(&channel, decode::<Incoming>(text, DecodeMode::Strict));
+        seen.insert(channel);
+    }
+
+    for channel in expected {
+        if !seen.contains(channel) {
+            report
+                .failures
+                .push(format!("{channel}: no message within {LISTEN:?}"));
+        }
+    }
+    report.finish();
+}